@@ -48,6 +48,11 @@ object PrettyPrinter {
     case NamedKey(name, Some(key)) => s"NamedKey($name, ${buildString(key)})"
   }
 
+  def buildString(v: ContractPackage.Version): String = v match {
+    case ContractPackage.Version(version, None)      => s"Version($version, KeyEmpty)"
+    case ContractPackage.Version(version, Some(key)) => s"Version($version, ${buildString(key)})"
+  }
+
   def buildString(v: Value): String = v.value match {
     case Value.Value.Empty => "ValueEmpty"
     case Value.Value.Account(
@@ -67,6 +72,8 @@ object PrettyPrinter {
     case Value.Value.BytesValue(bytes) => s"ByteArray(${buildString(bytes)})"
     case Value.Value.Contract(Contract(body, urefs, protocolVersion)) =>
       s"Contract(${buildString(body)}, {${urefs.map(buildString).mkString(",")}}, ${buildString(protocolVersion)})"
+    case Value.Value.ContractPackage(ContractPackage(versions)) =>
+      s"ContractPackage(${versions.map(buildString).mkString(",")})"
     case Value.Value.IntList(IntList(list))       => s"List(${list.mkString(",")})"
     case Value.Value.IntValue(i)                  => s"Int32($i)"
     case Value.Value.NamedKey(nk)                 => buildString(nk)
@@ -76,6 +83,8 @@ object PrettyPrinter {
     case Value.Value.Key(key)                     => buildString(key)
     case Value.Value.LongValue(l)                 => s"Long($l)"
     case Value.Value.Unit(_)                      => "Unit"
+    case Value.Value.ClValue(CLValue(_, serializedValue)) =>
+      s"CLValue(${buildString(serializedValue)})"
   }
 
   def buildString(b: BlockMessage): String =
@@ -42,17 +42,18 @@ object ExecEngineUtil {
       protocolVersion: state.ProtocolVersion
   ): F[DeploysCheckpoint] =
     for {
-      preStateHash <- computePrestate[F](merged)
-      deployStream = DeployBuffer[F].getByHashes(hashes)
+      preStateHash               <- computePrestate[F](merged)
+      (stepEffects, steppedHash) <- step[F](preStateHash, blocktime, protocolVersion)
+      deployStream               = DeployBuffer[F].getByHashes(hashes)
       pdr <- DeploySelection[F].select(
-              (preStateHash, blocktime, protocolVersion, deployStream)
+              (steppedHash, blocktime, protocolVersion, deployStream)
             )
       (invalidDeploys, deployEffects) = ProcessedDeployResult.split(pdr)
       _                               <- handleInvalidDeploys[F](invalidDeploys)
       (deploysForBlock, transforms)   = ExecEngineUtil.unzipEffectsAndDeploys(deployEffects).unzip
-      commitResult                    <- ExecutionEngineService[F].commit(preStateHash, transforms.flatten).rethrow
+      commitResult                    <- ExecutionEngineService[F].commit(steppedHash, transforms.flatten).rethrow
       //TODO: Remove this logging at some point
-      msgBody = transforms.flatten
+      msgBody = (stepEffects ++ transforms.flatten)
         .map(t => {
           val k    = PrettyPrinter.buildString(t.key.get)
           val tStr = PrettyPrinter.buildString(t.transform.get)
@@ -96,6 +97,28 @@ object ExecEngineUtil {
             .markAsDiscarded(invalidDeploys.preconditionFailures.map(_.deploy)) whenA invalidDeploys.preconditionFailures.nonEmpty
     } yield ()
 
+  /** Runs the PoS step of a block on its pre-state and commits its effects, which apply the due
+    * bonding and unbonding requests and enter the era the block time falls in.
+    *
+    * @return The effects of the step, and the state the deploys of the block execute on.
+    */
+  def step[F[_]: MonadThrowable: ExecutionEngineService](
+      prestate: StateHash,
+      blocktime: Long,
+      protocolVersion: state.ProtocolVersion
+  ): F[(TransformMap, StateHash)] =
+    for {
+      stepEffects <- ExecutionEngineService[F]
+                      .step(prestate, blocktime, protocolVersion)
+                      .rethrow
+      steppedHash <- if (stepEffects.isEmpty) prestate.pure[F]
+                    else
+                      ExecutionEngineService[F]
+                        .commit(prestate, stepEffects)
+                        .rethrow
+                        .map(_.postStateHash)
+    } yield (stepEffects, steppedHash)
+
   def processDeploys[F[_]: MonadThrowable: ExecutionEngineService](
       prestate: StateHash,
       blocktime: Long,
@@ -178,11 +201,11 @@ object ExecEngineUtil {
     block.getHeader.parentHashes.isEmpty &&
       block.getHeader.getState.preStateHash == ExecutionEngineService[F].emptyStateHash
 
-  /** Runs deploys from the block and returns the effects they make.
+  /** Runs the PoS step and the deploys from the block and returns the effects they make.
     *
     * @param block Block to run.
-    * @param prestate prestate hash of the GlobalState on top of which to run deploys.
-    * @return Effects of running deploys from the block
+    * @param prestate prestate hash of the GlobalState on top of which to run the step.
+    * @return Effects of the step followed by the effects of running deploys from the block
     */
   def effectsForBlock[F[_]: MonadThrowable: BlockStorage: ExecutionEngineService](
       block: Block,
@@ -199,8 +222,9 @@ object ExecEngineUtil {
       } yield transformMap
     } else {
       for {
+        (stepEffects, steppedHash) <- step[F](prestate, blocktime, protocolVersion)
         processedDeploys <- processDeploys[F](
-                             prestate,
+                             steppedHash,
                              blocktime,
                              deploys,
                              protocolVersion
@@ -209,7 +233,7 @@ object ExecEngineUtil {
         effectfulDeploys = ProcessedDeployResult.split(deployEffects.toList)._2
         transformMap = unzipEffectsAndDeploys(findCommutingEffects(effectfulDeploys))
           .flatMap(_._2)
-      } yield transformMap
+      } yield stepEffects ++ transformMap
     }
   }
 
@@ -314,6 +314,7 @@ object DeploySelectionTest {
   ): ExecutionEngineService[F] = ExecutionEngineServiceStub.mock(
     (_, _) => raiseNotImplemented[F, Either[Throwable, GenesisResult]],
     execFunc,
+    (_, _, _) => raiseNotImplemented[F, Either[Throwable, Seq[TransformEntry]]],
     (_, _) => raiseNotImplemented[F, Either[Throwable, CommitResult]],
     (_, _, _) => raiseNotImplemented[F, Either[Throwable, Value]],
     _ => raiseNotImplemented[F, Either[String, Unit]]
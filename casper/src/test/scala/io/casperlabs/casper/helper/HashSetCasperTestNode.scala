@@ -330,6 +330,13 @@ object HashSetCasperTestNode {
           .asRight[Throwable]
           .pure[F]
 
+      override def step(
+          prestate: ByteString,
+          blocktime: Long,
+          protocolVersion: ProtocolVersion
+      ): F[Either[Throwable, Seq[TransformEntry]]] =
+        Seq.empty[TransformEntry].asRight[Throwable].pure[F]
+
       override def runGenesis(
           deploys: Seq[ipc.DeployItem],
           protocolVersion: ProtocolVersion
@@ -166,6 +166,7 @@ class ExecEngineUtilTest extends FlatSpec with Matchers with BlockGenerator with
         mock[Task](
           (_, _) => new Throwable("failed when run genesis").asLeft.pure[Task],
           (_, _, _, _) => new Throwable("failed when exec deploys").asLeft.pure[Task],
+          (_, _, _) => Seq.empty[TransformEntry].asRight[Throwable].pure[Task],
           (_, _) => new Throwable("failed when commit transform").asLeft.pure[Task],
           (_, _, _) => SmartContractEngineError("unimplemented").asLeft.pure[Task],
           _ => ().asRight[String].pure[Task]
@@ -196,6 +197,7 @@ class ExecEngineUtilTest extends FlatSpec with Matchers with BlockGenerator with
                 )
                 .asRight[Throwable]
             },
+          (_, _, _) => Seq.empty[TransformEntry].asRight[Throwable].pure[Task],
           (_, _) => new Throwable("failed when commit transform").asLeft.pure[Task],
           (_, _, _) => SmartContractEngineError("unimplemented").asLeft.pure[Task],
           _ => ().asRight[String].pure[Task]
@@ -56,6 +56,11 @@ object ExecutionEngineServiceStub {
           Seq[DeployItem],
           ProtocolVersion
       ) => F[Either[Throwable, Seq[DeployResult]]],
+      stepFunc: (
+          ByteString,
+          Long,
+          ProtocolVersion
+      ) => F[Either[Throwable, Seq[TransformEntry]]],
       commitFunc: (
           ByteString,
           Seq[TransformEntry]
@@ -76,6 +81,12 @@ object ExecutionEngineServiceStub {
         protocolVersion: ProtocolVersion
     ): F[Either[Throwable, Seq[DeployResult]]] =
       execFunc(prestate, blocktime, deploys, protocolVersion)
+    override def step(
+        prestate: ByteString,
+        blocktime: Long,
+        protocolVersion: ProtocolVersion
+    ): F[Either[Throwable, Seq[TransformEntry]]] =
+      stepFunc(prestate, blocktime, protocolVersion)
     override def commit(
         prestate: ByteString,
         effects: Seq[TransformEntry]
@@ -94,6 +105,7 @@ object ExecutionEngineServiceStub {
     mock[F](
       (_, _) => GenesisResult().asRight[Throwable].pure[F],
       (_, _, _, _) => Seq.empty[DeployResult].asRight[Throwable].pure[F],
+      (_, _, _) => Seq.empty[TransformEntry].asRight[Throwable].pure[F],
       (_, _) =>
         ExecutionEngineService
           .CommitResult(ByteString.EMPTY, Seq.empty[Bond])
@@ -118,6 +118,7 @@ object DeployRuntime {
 
   def bond[F[_]: Sync: DeployService](
       amount: Long,
+      maybePurse: Option[String],
       deployConfig: DeployConfig,
       privateKeyFile: File
   ): F[Unit] =
@@ -128,7 +129,11 @@ object DeployRuntime {
             deployConfig.withSessionResource(BONDING_WASM_FILE),
             maybeEitherPublicKey = None,
             maybeEitherPrivateKey = rawPrivateKey.asLeft[PrivateKey].some,
-            sessionArgs = List(longArg("amount", amount))
+            sessionArgs = List(
+              longArg("amount", amount),
+              // No source purse means bonding from the account's main purse.
+              optionalArg("purse", maybePurse)(Deploy.Arg.Value.Value.StringValue(_))
+            )
           )
     } yield ()
 
@@ -62,11 +62,13 @@ object Main {
         )
       case Bond(
           amount,
+          purse,
           contracts,
           privateKey
           ) =>
         DeployRuntime.bond(
           amount,
+          purse,
           contracts,
           privateKey
         )
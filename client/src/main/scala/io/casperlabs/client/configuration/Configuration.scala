@@ -157,6 +157,7 @@ final case class ShowDeploy(deployHash: String) extends Configuration
 final case class ShowBlocks(depth: Int)         extends Configuration
 final case class Bond(
     amount: Long,
+    purse: Option[String],
     deployConfig: DeployConfig,
     privateKey: File
 ) extends Configuration
@@ -247,6 +248,7 @@ object Configuration {
       case options.bond =>
         Bond(
           options.bond.amount(),
+          options.bond.purse.toOption,
           DeployConfig(options.bond),
           options.bond.privateKey()
         )
@@ -405,6 +405,12 @@ final case class Options(arguments: Seq[String]) extends ScallopConf(arguments)
       required = true
     )
 
+    val purse = opt[String](
+      name = "purse",
+      descr =
+        "Name of the purse in the account's named keys to bond from. If not provided then the account's main purse is used."
+    )
+
     val privateKey =
       opt[File](
         descr = "Path to the file with account private key (Ed25519)",
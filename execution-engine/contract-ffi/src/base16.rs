@@ -3,6 +3,11 @@ use alloc::vec::Vec;
 use core::num::ParseIntError;
 use core::str;
 
+use blake2::digest::{Input, VariableOutput};
+use blake2::VarBlake2b;
+
+const CHECKSUM_HASH_SIZE: usize = 32;
+
 /// Encodes a slice of bytes in base16 form in lower case
 pub fn encode_lower(input: &[u8]) -> String {
     input.iter().map(|b| format!("{:02x}", b)).collect()
@@ -16,6 +21,8 @@ pub enum Error {
     DecodeError(str::Utf8Error),
     #[fail(display = "{}", _0)]
     ParseError(ParseIntError),
+    #[fail(display = "Checksum mismatch in the case of the input's letters")]
+    ChecksumError,
 }
 
 /// Decodes a slice of bytes in base16 form
@@ -37,6 +44,41 @@ pub fn decode_lower(input: &str) -> Result<Vec<u8>, Error> {
     }
 }
 
+/// Encodes a slice of bytes in base16 form, with each letter upper-cased if the bit of the input's
+/// Blake2b hash at the letter's position is set.  This allows [`decode_checksummed`] to detect
+/// most typos without changing the length of the encoding.
+pub fn encode_checksummed(input: &[u8]) -> String {
+    let mut hash = [0u8; CHECKSUM_HASH_SIZE];
+    // Safe to unwrap here because our digest length is constant and valid
+    let mut hasher = VarBlake2b::new(CHECKSUM_HASH_SIZE).unwrap();
+    hasher.input(input);
+    hasher.variable_result(|result| hash.clone_from_slice(result));
+
+    encode_lower(input)
+        .chars()
+        .enumerate()
+        .map(|(index, ch)| {
+            let bit_index = index % (CHECKSUM_HASH_SIZE * 8);
+            let bit = (hash[bit_index / 8] >> (7 - bit_index % 8)) & 1;
+            if bit == 1 {
+                ch.to_ascii_uppercase()
+            } else {
+                ch
+            }
+        })
+        .collect()
+}
+
+/// Decodes a slice of bytes in the base16 form produced by [`encode_checksummed`], returning
+/// [`Error::ChecksumError`] if the case of any letter doesn't match.
+pub fn decode_checksummed(input: &str) -> Result<Vec<u8>, Error> {
+    let bytes = decode_lower(input)?;
+    if encode_checksummed(&bytes) != input {
+        return Err(Error::ChecksumError);
+    }
+    Ok(bytes)
+}
+
 #[test]
 fn test_encode_lower() {
     assert_eq!(encode_lower(&[1, 2, 3, 254, 255]), "010203feff");
@@ -56,3 +98,29 @@ fn test_decode_lower() {
     // invalid characters
     assert!(decode_lower("\u{012345}deadbeef").is_err());
 }
+
+#[test]
+fn test_checksummed_roundtrip() {
+    let input = [0xab; 32];
+    let encoded = encode_checksummed(&input);
+    assert_eq!(encoded.to_ascii_lowercase(), encode_lower(&input));
+    assert_ne!(encoded, encode_lower(&input));
+    assert_eq!(
+        decode_checksummed(&encoded).expect("should decode"),
+        input.to_vec()
+    );
+}
+
+#[test]
+fn test_decode_checksummed_rejects_wrong_case() {
+    let input = [0xab; 32];
+    let lower = encode_lower(&input);
+    let upper = lower.to_ascii_uppercase();
+    assert_eq!(decode_checksummed(&lower), Err(Error::ChecksumError));
+    assert_eq!(decode_checksummed(&upper), Err(Error::ChecksumError));
+    // Digits have no case, so an input without letters is always valid
+    assert_eq!(
+        decode_checksummed(&encode_checksummed(&[0x12, 0x34])).expect("should decode"),
+        vec![0x12, 0x34]
+    );
+}
@@ -79,6 +79,22 @@ pub fn safe_split_at(bytes: &[u8], n: usize) -> Result<(&[u8], &[u8]), Error> {
     }
 }
 
+impl ToBytes for bool {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        u8::from(*self).to_bytes()
+    }
+}
+
+impl FromBytes for bool {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        match FromBytes::from_bytes(bytes)? {
+            (0u8, rem) => Ok((false, rem)),
+            (1u8, rem) => Ok((true, rem)),
+            _ => Err(Error::FormattingError),
+        }
+    }
+}
+
 impl ToBytes for u8 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         let mut result = Vec::with_capacity(1);
@@ -490,16 +506,40 @@ impl<T: FromBytes, E: FromBytes> FromBytes for Result<T, E> {
     }
 }
 
+impl<T1: ToBytes, T2: ToBytes> ToBytes for (T1, T2) {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = self.0.to_bytes()?;
+        result.append(&mut self.1.to_bytes()?);
+        Ok(result)
+    }
+}
+
+impl<T1: FromBytes, T2: FromBytes> FromBytes for (T1, T2) {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (t1, rem1): (T1, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (t2, rem2): (T2, &[u8]) = FromBytes::from_bytes(rem1)?;
+        Ok(((t1, t2), rem2))
+    }
+}
+
 #[cfg(test)]
 mod proptests {
     // Bring the macros and other important things into scope.
+    use crate::bytesrepr::{ToBytes, U32_SIZE};
     use crate::gens::*;
-    use crate::test_utils::test_serialization_roundtrip;
+    use crate::test_utils::{
+        length_prefix, test_serialization_consumes_own_bytes, test_serialization_roundtrip,
+    };
     use proptest::collection::vec;
     use proptest::prelude::*;
 
     proptest! {
 
+        #[test]
+        fn test_bool(b in any::<bool>()) {
+            assert!(test_serialization_roundtrip(&b));
+        }
+
         #[test]
         fn test_u8(u in any::<u8>()) {
             assert!(test_serialization_roundtrip(&u));
@@ -590,6 +630,21 @@ mod proptests {
             assert!(test_serialization_roundtrip(&key));
         }
 
+        #[test]
+        fn test_tuple(t in (any::<u32>(), "\\PC*")) {
+            assert!(test_serialization_roundtrip(&t));
+        }
+
+        #[test]
+        fn test_cl_type_serialization(cl_type in cl_type_arb()) {
+            assert!(test_serialization_roundtrip(&cl_type));
+        }
+
+        #[test]
+        fn test_cl_value_serialization(cl_value in cl_value_arb()) {
+            assert!(test_serialization_roundtrip(&cl_value));
+        }
+
         #[test]
         fn test_value_serialization(v in value_arb()) {
             assert!(test_serialization_roundtrip(&v));
@@ -614,5 +669,74 @@ mod proptests {
         fn test_phase_serialization(phase in phase_arb()) {
             assert!(test_serialization_roundtrip(&phase));
         }
+
+        #[test]
+        fn test_contract_serialization(contract in contract_arb()) {
+            assert!(test_serialization_roundtrip(&contract));
+        }
+
+        #[test]
+        fn test_key_consumes_own_bytes(key in key_arb(), trailing in vec(any::<u8>(), 0..100)) {
+            assert!(test_serialization_consumes_own_bytes(&key, &trailing));
+        }
+
+        #[test]
+        fn test_value_consumes_own_bytes(v in value_arb(), trailing in vec(any::<u8>(), 0..100)) {
+            assert!(test_serialization_consumes_own_bytes(&v, &trailing));
+        }
+
+        #[test]
+        fn test_account_consumes_own_bytes(
+            acct in account_arb(),
+            trailing in vec(any::<u8>(), 0..100),
+        ) {
+            assert!(test_serialization_consumes_own_bytes(&acct, &trailing));
+        }
+
+        #[test]
+        fn test_contract_consumes_own_bytes(
+            contract in contract_arb(),
+            trailing in vec(any::<u8>(), 0..100),
+        ) {
+            assert!(test_serialization_consumes_own_bytes(&contract, &trailing));
+        }
+
+        #[test]
+        fn test_u512_consumes_own_bytes(u in u512_arb(), trailing in vec(any::<u8>(), 0..100)) {
+            assert!(test_serialization_consumes_own_bytes(&u, &trailing));
+        }
+
+        #[test]
+        fn test_u512_length_prefix(u in u512_arb()) {
+            let bytes = u.to_bytes().unwrap();
+            prop_assert_eq!(bytes[0] as usize, bytes.len() - 1);
+            prop_assert_ne!(bytes.last(), Some(&0));
+        }
+
+        #[test]
+        fn test_vec_u8_length_prefix(u in vec(any::<u8>(), 0..100)) {
+            let bytes = u.to_bytes().unwrap();
+            prop_assert_eq!(length_prefix(&bytes) as usize, u.len());
+            prop_assert_eq!(bytes.len(), U32_SIZE + u.len());
+        }
+
+        #[test]
+        fn test_string_length_prefix(s in "\\PC*") {
+            let bytes = s.to_bytes().unwrap();
+            prop_assert_eq!(length_prefix(&bytes) as usize, s.len());
+            prop_assert_eq!(bytes.len(), U32_SIZE + s.len());
+        }
+
+        #[test]
+        fn test_vec_string_length_prefix(v in vec("\\PC*", 0..20)) {
+            let bytes = v.to_bytes().unwrap();
+            prop_assert_eq!(length_prefix(&bytes) as usize, v.len());
+        }
+
+        #[test]
+        fn test_uref_map_length_prefix(m in uref_map_arb(20)) {
+            let bytes = m.to_bytes().unwrap();
+            prop_assert_eq!(length_prefix(&bytes) as usize, m.len());
+        }
     }
 }
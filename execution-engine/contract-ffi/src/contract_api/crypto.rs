@@ -0,0 +1,70 @@
+//! Cryptographic primitives computed by the host.
+//!
+//! Each call is charged a fixed amount of gas given by the chainspec's cost table, which is far
+//! cheaper than running the same computation in wasm.
+
+use crate::ext_ffi;
+use crate::value::account::{PublicKey, ED25519_PUBLIC_KEY_LENGTH, SECP256K1_PUBLIC_KEY_LENGTH};
+
+/// Length in bytes of the digests returned by [`blake2b256`] and [`sha256`].
+pub const DIGEST_LENGTH: usize = 32;
+
+/// Length in bytes of an Ed25519 signature.
+pub const ED25519_SIGNATURE_LENGTH: usize = 64;
+
+/// Length in bytes of a compact Secp256k1 signature, without its recovery id.
+pub const SECP256K1_SIGNATURE_LENGTH: usize = 64;
+
+/// Returns the 32 byte Blake2b digest of `data`.
+pub fn blake2b256(data: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut digest = [0u8; DIGEST_LENGTH];
+    unsafe { ext_ffi::blake2b256(data.as_ptr(), data.len(), digest.as_mut_ptr()) };
+    digest
+}
+
+/// Returns the SHA-256 digest of `data`.
+pub fn sha256(data: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut digest = [0u8; DIGEST_LENGTH];
+    unsafe { ext_ffi::sha256(data.as_ptr(), data.len(), digest.as_mut_ptr()) };
+    digest
+}
+
+/// Checks that `signature` is a valid Ed25519 signature of `message` by `public_key`.
+pub fn ed25519_verify(
+    public_key: &[u8; ED25519_PUBLIC_KEY_LENGTH],
+    message: &[u8],
+    signature: &[u8; ED25519_SIGNATURE_LENGTH],
+) -> bool {
+    let result = unsafe {
+        ext_ffi::ed25519_verify(
+            public_key.as_ptr(),
+            message.as_ptr(),
+            message.len(),
+            signature.as_ptr(),
+        )
+    };
+    result == 1
+}
+
+/// Recovers the Secp256k1 public key which produced the compact `signature` of `message_hash`
+/// with the given `recovery_id`, or returns `None` if the signature or recovery id is invalid.
+pub fn secp256k1_recover(
+    message_hash: &[u8; DIGEST_LENGTH],
+    signature: &[u8; SECP256K1_SIGNATURE_LENGTH],
+    recovery_id: u8,
+) -> Option<PublicKey> {
+    let mut public_key = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+    let result = unsafe {
+        ext_ffi::secp256k1_recover(
+            message_hash.as_ptr(),
+            signature.as_ptr(),
+            i32::from(recovery_id),
+            public_key.as_mut_ptr(),
+        )
+    };
+    if result == 1 {
+        Some(PublicKey::secp256k1(public_key))
+    } else {
+        None
+    }
+}
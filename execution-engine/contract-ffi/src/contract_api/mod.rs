@@ -1,19 +1,25 @@
 mod alloc_util;
 pub mod argsparser;
+pub mod crypto;
 pub mod pointers;
+pub mod runtime_args;
+pub mod safe;
 
 use self::alloc_util::*;
 use self::pointers::*;
-use crate::bytesrepr::{deserialize, FromBytes, ToBytes};
+use self::runtime_args::RuntimeArgs;
+use crate::bytesrepr::{deserialize, FromBytes, ToBytes, U64_SIZE};
 use crate::execution::{Phase, PHASE_SIZE};
 use crate::ext_ffi;
 use crate::key::{Key, UREF_SIZE};
+use crate::system_contracts::mint;
 use crate::uref::{AccessRights, URef};
 use crate::value::account::{
     Account, ActionType, AddKeyFailure, BlockTime, PublicKey, PurseId, RemoveKeyFailure,
-    SetThresholdFailure, UpdateKeyFailure, Weight, BLOCKTIME_SER_SIZE, PURSE_ID_SIZE_SERIALIZED,
+    SetThresholdFailure, UpdateKeyFailure, Weight, BLOCKTIME_SER_SIZE,
+    PUBLIC_KEY_SERIALIZED_MAX_LENGTH, PURSE_ID_SIZE_SERIALIZED,
 };
-use crate::value::{Contract, Value, U512};
+use crate::value::{CLTyped, CLValue, Contract, ContractPackage, Value, U512};
 use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
@@ -37,17 +43,21 @@ where
 }
 
 fn read_untyped(key: &Key) -> Option<Value> {
+    deserialize(&read_untyped_bytes(key)).unwrap()
+}
+
+/// Reads the serialized `Option<Value>` under the key in the global state.
+fn read_untyped_bytes(key: &Key) -> Vec<u8> {
     // Note: _bytes is necessary to keep the Vec<u8> in scope. If _bytes is
     //      dropped then key_ptr becomes invalid.
 
     let (key_ptr, key_size, _bytes) = to_ptr(key);
     let value_size = unsafe { ext_ffi::read_value(key_ptr, key_size) };
     let value_ptr = alloc_bytes(value_size);
-    let value_bytes = unsafe {
+    unsafe {
         ext_ffi::get_read(value_ptr);
         Vec::from_raw_parts(value_ptr, value_size, value_size)
-    };
-    deserialize(&value_bytes).unwrap()
+    }
 }
 
 /// Reads the value at the given key in the context-local partition of global
@@ -66,15 +76,20 @@ where
 }
 
 fn read_untyped_local(key_bytes: &[u8]) -> Option<Value> {
+    deserialize(&read_untyped_local_bytes(key_bytes)).unwrap()
+}
+
+/// Reads the serialized `Option<Value>` under the key in the context-local
+/// partition of global state.
+fn read_untyped_local_bytes(key_bytes: &[u8]) -> Vec<u8> {
     let key_bytes_ptr = key_bytes.as_ptr();
     let key_bytes_size = key_bytes.len();
     let value_size = unsafe { ext_ffi::read_value_local(key_bytes_ptr, key_bytes_size) };
     let value_ptr = alloc_bytes(value_size);
-    let value_bytes = unsafe {
+    unsafe {
         ext_ffi::get_read(value_ptr);
         Vec::from_raw_parts(value_ptr, value_size, value_size)
-    };
-    deserialize(&value_bytes).unwrap()
+    }
 }
 
 /// Write the value under the key in the global state
@@ -115,6 +130,61 @@ fn write_untyped_local(key_bytes: &[u8], value: &Value) {
     }
 }
 
+/// Creates a new dictionary, stored under `name` in the current context, and
+/// returns its seed.
+///
+/// Items are addressed by the seed and their key, so a dictionary can be
+/// shared with other contracts by passing them its seed, and its items can be
+/// queried with the path `[name, item_key]` from the current context.
+pub fn new_dictionary(name: &str) -> URef {
+    let seed: URef = new_uref(()).into();
+    add_uref(name, &Key::URef(seed));
+    seed
+}
+
+/// Reads the item stored under `item_key` in the dictionary with the given
+/// seed. Requires read access to the seed.
+pub fn dictionary_get<V: TryFrom<Value>>(seed: URef, item_key: &str) -> Option<V> {
+    let value: Option<Value> = deserialize(&dictionary_get_bytes(seed, item_key)).unwrap();
+    value.map(|v| {
+        v.try_into()
+            .map_err(|_| "T could not be derived from Value")
+            .unwrap()
+    })
+}
+
+/// Reads the serialized `Option<Value>` under `item_key` in the dictionary.
+fn dictionary_get_bytes(seed: URef, item_key: &str) -> Vec<u8> {
+    let (seed_ptr, seed_size, _bytes) = to_ptr(&seed);
+    let (item_key_ptr, item_key_size, _bytes2) = str_ref_to_ptr(item_key);
+    let value_size =
+        unsafe { ext_ffi::dictionary_get(seed_ptr, seed_size, item_key_ptr, item_key_size) };
+    let value_ptr = alloc_bytes(value_size);
+    unsafe {
+        ext_ffi::get_read(value_ptr);
+        Vec::from_raw_parts(value_ptr, value_size, value_size)
+    }
+}
+
+/// Writes `value` under `item_key` in the dictionary with the given seed.
+/// Requires write access to the seed.
+pub fn dictionary_put<V: Into<Value>>(seed: URef, item_key: &str, value: V) {
+    let (seed_ptr, seed_size, _bytes) = to_ptr(&seed);
+    let (item_key_ptr, item_key_size, _bytes2) = str_ref_to_ptr(item_key);
+    let value: Value = value.into();
+    let (value_ptr, value_size, _bytes3) = to_ptr(&value);
+    unsafe {
+        ext_ffi::dictionary_put(
+            seed_ptr,
+            seed_size,
+            item_key_ptr,
+            item_key_size,
+            value_ptr,
+            value_size,
+        );
+    }
+}
+
 /// Add the given value to the one currently under the key in the global state
 pub fn add<T>(u_ptr: UPointer<T>, t: T)
 where
@@ -166,13 +236,22 @@ fn fn_bytes_by_name(name: &str) -> Vec<u8> {
 }
 
 pub fn list_known_urefs() -> BTreeMap<String, Key> {
+    list_named_keys()
+}
+
+/// Returns all the keys known by the current context, along with the names
+/// they are known under.
+pub fn list_named_keys() -> BTreeMap<String, Key> {
+    deserialize(&list_named_keys_bytes()).unwrap()
+}
+
+fn list_named_keys_bytes() -> Vec<u8> {
     let bytes_size = unsafe { ext_ffi::serialize_known_urefs() };
     let dest_ptr = alloc_bytes(bytes_size);
-    let bytes = unsafe {
+    unsafe {
         ext_ffi::list_known_urefs(dest_ptr);
         Vec::from_raw_parts(dest_ptr, bytes_size, bytes_size)
-    };
-    deserialize(&bytes).unwrap()
+    }
 }
 
 // TODO: fn_by_name, fn_bytes_by_name and ext_ffi::serialize_function should be
@@ -210,35 +289,133 @@ pub fn store_function_at(name: &str, known_urefs: BTreeMap<String, Key>, uref: U
     write(uref, contract);
 }
 
+/// Creates a new contract package, without any versions, under a new unforgable
+/// reference.
+pub fn create_contract_package() -> UPointer<ContractPackage> {
+    new_uref(ContractPackage::new())
+}
+
+/// Finds function by the name and stores it as the next version of the given
+/// contract package. The new version number is returned. Only the holders of a
+/// writeable reference to the package can add versions to it.
+pub fn add_contract_version(
+    package: UPointer<ContractPackage>,
+    name: &str,
+    known_urefs: BTreeMap<String, Key>,
+) -> u32 {
+    let package_key: Key = package.into();
+    let (package_ptr, package_size, _bytes1) = to_ptr(&package_key);
+    let (fn_ptr, fn_size, _bytes2) = str_ref_to_ptr(name);
+    let (urefs_ptr, urefs_size, _bytes3) = to_ptr(&known_urefs);
+    unsafe {
+        ext_ffi::add_contract_version(
+            package_ptr,
+            package_size,
+            fn_ptr,
+            fn_size,
+            urefs_ptr,
+            urefs_size,
+        )
+    }
+}
+
+/// Disables the given version of a contract package, so that calls to it
+/// through the package fail. Only the holders of a writeable reference to the
+/// package can disable its versions.
+pub fn disable_contract_version(package: UPointer<ContractPackage>, version: u32) {
+    let mut contract_package = read(package.clone());
+    assert!(
+        contract_package.disable_version(version),
+        "contract version should exist"
+    );
+    write(package, contract_package);
+}
+
+/// Enables a version of a contract package disabled by
+/// `disable_contract_version`.
+pub fn enable_contract_version(package: UPointer<ContractPackage>, version: u32) {
+    let mut contract_package = read(package.clone());
+    assert!(
+        contract_package.enable_version(version),
+        "contract version should exist"
+    );
+    write(package, contract_package);
+}
+
 /// Return the i-th argument passed to the host for the current module
 /// invocation. Note that this is only relevant to contracts stored on-chain
 /// since a contract deployed directly is not invoked with any arguments.
 pub fn get_arg<T: FromBytes>(i: u32) -> T {
+    // TODO: better error handling (i.e. pass the `Result` on)
+    deserialize(&get_arg_bytes(i)).unwrap()
+}
+
+fn get_arg_bytes(i: u32) -> Vec<u8> {
     let arg_size = unsafe { ext_ffi::load_arg(i) };
     let dest_ptr = alloc_bytes(arg_size);
-    let arg_bytes = unsafe {
+    unsafe {
         ext_ffi::get_arg(dest_ptr);
         Vec::from_raw_parts(dest_ptr, arg_size, arg_size)
-    };
+    }
+}
+
+/// Return the i-th argument passed to the current module invocation, which must
+/// have been passed as a serialized `CLValue` holding a `T`.
+pub fn get_typed_arg<T: CLTyped + FromBytes>(i: u32) -> T {
     // TODO: better error handling (i.e. pass the `Result` on)
-    deserialize(&arg_bytes).unwrap()
+    let cl_value: CLValue = deserialize(&get_arg_bytes(i)).unwrap();
+    cl_value
+        .into_t()
+        .map_err(|_| "T could not be derived from CLValue")
+        .unwrap()
+}
+
+/// Return the argument passed to the current module invocation under the given
+/// name by `call_contract_with_args`.
+pub fn get_named_arg<T: TryFrom<Value>>(name: &str) -> T {
+    // TODO: better error handling (i.e. pass the `Result` on)
+    let value: Value = deserialize(&get_named_arg_bytes(name).unwrap()).unwrap();
+    value
+        .try_into()
+        .map_err(|_| "T could not be derived from Value")
+        .unwrap()
+}
+
+/// Returns the serialized `Value` passed under the given name, or `None` if no
+/// argument was passed under it.
+fn get_named_arg_bytes(name: &str) -> Option<Vec<u8>> {
+    let (name_ptr, name_size, _bytes) = str_ref_to_ptr(name);
+    let arg_size = unsafe { ext_ffi::load_named_arg(name_ptr, name_size) };
+    if arg_size < 0 {
+        return None;
+    }
+    let arg_size = arg_size as usize;
+    let dest_ptr = alloc_bytes(arg_size);
+    unsafe {
+        ext_ffi::get_arg(dest_ptr);
+        Some(Vec::from_raw_parts(dest_ptr, arg_size, arg_size))
+    }
 }
 
 /// Return the unforgable reference known by the current module under the given
 /// name. This either comes from the known_urefs of the account or contract,
 /// depending on whether the current module is a sub-call or not.
 pub fn get_uref(name: &str) -> Option<Key> {
+    // TODO: better error handling (i.e. pass the `Result` on)
+    deserialize(&get_uref_bytes(name)).unwrap()
+}
+
+/// Returns the serialized `Option<Key>` known under the given name.
+fn get_uref_bytes(name: &str) -> Vec<u8> {
     let (name_ptr, name_size, _bytes) = str_ref_to_ptr(name);
     let key_size = unsafe { ext_ffi::get_uref(name_ptr, name_size) };
     let dest_ptr = alloc_bytes(key_size);
-    let key_bytes = unsafe {
+    unsafe {
         // TODO: unify FFIs that just copy from the host buffer
         // https://casperlabs.atlassian.net/browse/EE-426
         ext_ffi::get_arg(dest_ptr);
         Vec::from_raw_parts(dest_ptr, key_size, key_size)
-    };
-    // TODO: better error handling (i.e. pass the `Result` on)
-    deserialize(&key_bytes).unwrap()
+    }
 }
 
 /// Check if the given name corresponds to a known unforgable reference
@@ -266,11 +443,18 @@ pub fn remove_uref(name: &str) {
 /// When in the sub call - returns public key of the account that made the
 /// deploy.
 pub fn get_caller() -> PublicKey {
-    //  TODO: Once `PUBLIC_KEY_SIZE` is fixed, replace 36 with it.
-    let dest_ptr = alloc_bytes(36);
+    let dest_ptr = alloc_bytes(PUBLIC_KEY_SERIALIZED_MAX_LENGTH);
     unsafe { ext_ffi::get_caller(dest_ptr) };
-    let bytes = unsafe { Vec::from_raw_parts(dest_ptr, 36, 36) };
-    deserialize(&bytes).unwrap()
+    let bytes = unsafe {
+        Vec::from_raw_parts(
+            dest_ptr,
+            PUBLIC_KEY_SERIALIZED_MAX_LENGTH,
+            PUBLIC_KEY_SERIALIZED_MAX_LENGTH,
+        )
+    };
+    // Keys shorter than the maximum leave unused bytes at the end of the buffer.
+    let (public_key, _rem) = PublicKey::from_bytes(&bytes).unwrap();
+    public_key
 }
 
 pub fn get_blocktime() -> BlockTime {
@@ -282,6 +466,31 @@ pub fn get_blocktime() -> BlockTime {
     deserialize(&bytes).unwrap()
 }
 
+/// Returns the height of the block the current deploy is executed in.
+pub fn get_block_height() -> u64 {
+    let dest_ptr = alloc_bytes(U64_SIZE);
+    let bytes = unsafe {
+        ext_ffi::get_block_height(dest_ptr);
+        Vec::from_raw_parts(dest_ptr, U64_SIZE, U64_SIZE)
+    };
+    deserialize(&bytes).unwrap()
+}
+
+/// Length in bytes of the seeds returned by [`get_entropy`].
+pub const ENTROPY_LENGTH: usize = 32;
+
+/// Returns a deterministic 32-byte seed, which is different on each call.
+///
+/// The seeds are derived from the deploy hash, the block time and the number of
+/// seeds returned before within the deploy, so every node executing the deploy
+/// gets the same ones.  They suit lotteries and sampling, but are predictable
+/// by anyone who knows the deploy and its block time.
+pub fn get_entropy() -> [u8; ENTROPY_LENGTH] {
+    let mut entropy = [0u8; ENTROPY_LENGTH];
+    unsafe { ext_ffi::get_entropy(entropy.as_mut_ptr()) };
+    entropy
+}
+
 /// Return `t` to the host, terminating the currently running module.
 /// Note this function is only relevant to contracts stored on chain which
 /// return a value to their caller. The return value of a directly deployed
@@ -306,22 +515,88 @@ pub fn call_contract<A: ArgsParser, T: FromBytes>(
     extra_urefs: &Vec<Key>,
 ) -> T {
     let contract_key: Key = c_ptr.into();
-    let (key_ptr, key_size, _bytes1) = to_ptr(&contract_key);
-    let (args_ptr, args_size, _bytes2) = ArgsParser::parse(args).map(|args| to_ptr(&args)).unwrap();
+    let args = ArgsParser::parse(args).unwrap();
+    let res_bytes = call_contract_bytes(&contract_key, &args, extra_urefs);
+    deserialize(&res_bytes).unwrap()
+}
+
+/// Calls the given contract with arguments passed by name, which the callee reads
+/// with `get_named_arg`. See `call_contract`.
+#[allow(clippy::ptr_arg)]
+pub fn call_contract_with_args<T: FromBytes>(
+    c_ptr: ContractPointer,
+    args: &RuntimeArgs,
+    extra_urefs: &Vec<Key>,
+) -> T {
+    let contract_key: Key = c_ptr.into();
+    let res_bytes = call_contract_with_args_bytes(&contract_key, args, extra_urefs);
+    deserialize(&res_bytes).unwrap()
+}
+
+/// Calls the contract under the given key with arguments passed by name,
+/// returning the serialized result of the call.
+#[allow(clippy::ptr_arg)]
+fn call_contract_with_args_bytes(
+    contract_key: &Key,
+    args: &RuntimeArgs,
+    extra_urefs: &Vec<Key>,
+) -> Vec<u8> {
+    let (key_ptr, key_size, _bytes1) = to_ptr(contract_key);
+    let (args_ptr, args_size, _bytes2) = to_ptr(args);
     let (urefs_ptr, urefs_size, _bytes3) = to_ptr(extra_urefs);
     let res_size = unsafe {
-        ext_ffi::call_contract(
+        ext_ffi::call_contract_with_named_args(
             key_ptr, key_size, args_ptr, args_size, urefs_ptr, urefs_size,
         )
     };
     let res_ptr = alloc_bytes(res_size);
-    let res_bytes = unsafe {
+    unsafe {
         ext_ffi::get_call_result(res_ptr);
         Vec::from_raw_parts(res_ptr, res_size, res_size)
-    };
+    }
+}
+
+/// Calls the given version of a contract package with the given arguments. See
+/// `call_contract`.
+#[allow(clippy::ptr_arg)]
+pub fn call_versioned_contract<A: ArgsParser, T: FromBytes>(
+    package: UPointer<ContractPackage>,
+    version: u32,
+    args: &A,
+    extra_urefs: &Vec<Key>,
+) -> T {
+    let package = read(package);
+    assert!(
+        !package.is_version_disabled(version),
+        "contract version should be enabled"
+    );
+    let contract_key = *package
+        .get_version(version)
+        .expect("contract version should exist");
+    let args = ArgsParser::parse(args).unwrap();
+    let res_bytes = call_contract_bytes(&contract_key, &args, extra_urefs);
     deserialize(&res_bytes).unwrap()
 }
 
+/// Calls the contract under the given key with already serialized arguments,
+/// returning the serialized result of the call.
+#[allow(clippy::ptr_arg)]
+fn call_contract_bytes(contract_key: &Key, args: &Vec<Vec<u8>>, extra_urefs: &Vec<Key>) -> Vec<u8> {
+    let (key_ptr, key_size, _bytes1) = to_ptr(contract_key);
+    let (args_ptr, args_size, _bytes2) = to_ptr(args);
+    let (urefs_ptr, urefs_size, _bytes3) = to_ptr(extra_urefs);
+    let res_size = unsafe {
+        ext_ffi::call_contract(
+            key_ptr, key_size, args_ptr, args_size, urefs_ptr, urefs_size,
+        )
+    };
+    let res_ptr = alloc_bytes(res_size);
+    unsafe {
+        ext_ffi::get_call_result(res_ptr);
+        Vec::from_raw_parts(res_ptr, res_size, res_size)
+    }
+}
+
 /// Stops execution of a contract and reverts execution effects
 /// with a given reason.
 pub fn revert(status: u32) -> ! {
@@ -392,6 +667,13 @@ pub fn set_action_threshold(
     }
 }
 
+/// Returns the threshold which has to be met by the deploy's authorization
+/// keys to perform an action of the given type on the caller's account.
+pub fn get_action_threshold(action_type: ActionType) -> Weight {
+    let account = caller_account();
+    *account.action_thresholds().get_threshold(action_type)
+}
+
 pub fn create_purse() -> PurseId {
     let purse_id_ptr = alloc_bytes(PURSE_ID_SIZE_SERIALIZED);
     unsafe {
@@ -409,23 +691,32 @@ pub fn create_purse() -> PurseId {
     }
 }
 
-/// Gets the balance of a given purse
+/// Gets the balance of a given purse.
+///
+/// The balance is read by the host directly from the mint's storage, so unlike the mint's
+/// `balance` entry point this doesn't require calling into the mint contract.
 pub fn get_balance(purse_id: PurseId) -> Option<U512> {
+    let balance_bytes = get_balance_bytes(purse_id)?;
+
+    let balance: U512 = deserialize(&balance_bytes).unwrap_or_else(|_| revert(100));
+
+    Some(balance)
+}
+
+/// Returns the serialized balance of the given purse, or `None` if the purse
+/// doesn't exist.
+fn get_balance_bytes(purse_id: PurseId) -> Option<Vec<u8>> {
     let (purse_id_ptr, purse_id_size, _bytes) = to_ptr(&purse_id);
 
-    let balance_bytes: Vec<u8> = unsafe {
+    unsafe {
         let value_size = ext_ffi::get_balance(purse_id_ptr, purse_id_size) as usize;
         if value_size == 0 {
             return None;
         }
         let dest_ptr = alloc_bytes(value_size);
         ext_ffi::get_read(dest_ptr);
-        Vec::from_raw_parts(dest_ptr, value_size, value_size)
-    };
-
-    let balance: U512 = deserialize(&balance_bytes).unwrap_or_else(|_| revert(100));
-
-    Some(balance)
+        Some(Vec::from_raw_parts(dest_ptr, value_size, value_size))
+    }
 }
 
 pub fn main_purse() -> PurseId {
@@ -435,17 +726,89 @@ pub fn main_purse() -> PurseId {
     // API) would get around this problem. However, this solution
     // works for the time being.
     // https://casperlabs.atlassian.net/browse/EE-439
+    caller_account().purse_id()
+}
+
+/// Reads the account of the caller from the global state.
+fn caller_account() -> Account {
     let account_pk = get_caller();
-    let key = Key::Account(account_pk.value());
-    let account: Account = read_untyped(&key).unwrap().try_into().unwrap();
-    account.purse_id()
+    let key = Key::Account(account_pk.to_account_address());
+    read_untyped(&key).unwrap().try_into().unwrap()
+}
+
+/// The reason a transfer failed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TransferFailure {
+    /// The source purse doesn't hold enough motes.
+    InsufficientFunds,
+    /// The source purse is not known to the mint.
+    SourceNotFound,
+    /// The target purse is not known to the mint.
+    DestNotFound,
+    /// A purse URef is invalid or lacks the access rights required by the
+    /// transfer.
+    InvalidPurse,
+    /// A purse URef is not known to the calling context, i.e. it was forged.
+    ForgedReference,
+    /// The transfer failed for any other reason.
+    Other,
+}
+
+impl TransferFailure {
+    // Codes of all the failures other than `Other`, which is encoded
+    // differently by `TransferResult` and `PurseTransferResult`.
+    fn from_code(code: i32) -> Option<Self> {
+        match code {
+            3 => Some(TransferFailure::InsufficientFunds),
+            4 => Some(TransferFailure::SourceNotFound),
+            5 => Some(TransferFailure::DestNotFound),
+            6 => Some(TransferFailure::InvalidPurse),
+            7 => Some(TransferFailure::ForgedReference),
+            _ => None,
+        }
+    }
+
+    fn code(self, other_code: i32) -> i32 {
+        match self {
+            TransferFailure::InsufficientFunds => 3,
+            TransferFailure::SourceNotFound => 4,
+            TransferFailure::DestNotFound => 5,
+            TransferFailure::InvalidPurse => 6,
+            TransferFailure::ForgedReference => 7,
+            TransferFailure::Other => other_code,
+        }
+    }
+}
+
+impl From<mint::error::Error> for TransferFailure {
+    fn from(error: mint::error::Error) -> Self {
+        match error {
+            mint::error::Error::InsufficientFunds => TransferFailure::InsufficientFunds,
+            mint::error::Error::SourceNotFound => TransferFailure::SourceNotFound,
+            mint::error::Error::DestNotFound => TransferFailure::DestNotFound,
+            mint::error::Error::InvalidURef | mint::error::Error::InvalidAccessRights => {
+                TransferFailure::InvalidPurse
+            }
+            mint::error::Error::InvalidNonEmptyPurseCreation => TransferFailure::Other,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum TransferResult {
     TransferredToExistingAccount,
     TransferredToNewAccount,
-    TransferError,
+    TransferError(TransferFailure),
+}
+
+impl TransferResult {
+    pub fn is_success(&self) -> bool {
+        match self {
+            TransferResult::TransferredToExistingAccount
+            | TransferResult::TransferredToNewAccount => true,
+            TransferResult::TransferError(_) => false,
+        }
+    }
 }
 
 impl TryFrom<i32> for TransferResult {
@@ -455,8 +818,10 @@ impl TryFrom<i32> for TransferResult {
         match value {
             0 => Ok(TransferResult::TransferredToExistingAccount),
             1 => Ok(TransferResult::TransferredToNewAccount),
-            2 => Ok(TransferResult::TransferError),
-            _ => Err(()),
+            2 => Ok(TransferResult::TransferError(TransferFailure::Other)),
+            code => TransferFailure::from_code(code)
+                .map(TransferResult::TransferError)
+                .ok_or(()),
         }
     }
 }
@@ -466,7 +831,7 @@ impl From<TransferResult> for i32 {
         match result {
             TransferResult::TransferredToExistingAccount => 0,
             TransferResult::TransferredToNewAccount => 1,
-            TransferResult::TransferError => 2,
+            TransferResult::TransferError(failure) => failure.code(2),
         }
     }
 }
@@ -505,11 +870,19 @@ pub fn transfer_from_purse_to_account(
     .expect("should parse result")
 }
 
-// TODO: Improve returned result type.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum PurseTransferResult {
     TransferSuccessful,
-    TransferError,
+    TransferError(TransferFailure),
+}
+
+impl PurseTransferResult {
+    pub fn is_success(&self) -> bool {
+        match self {
+            PurseTransferResult::TransferSuccessful => true,
+            PurseTransferResult::TransferError(_) => false,
+        }
+    }
 }
 
 impl TryFrom<i32> for PurseTransferResult {
@@ -518,8 +891,10 @@ impl TryFrom<i32> for PurseTransferResult {
     fn try_from(value: i32) -> Result<Self, Self::Error> {
         match value {
             0 => Ok(PurseTransferResult::TransferSuccessful),
-            1 => Ok(PurseTransferResult::TransferError),
-            _ => Err(()),
+            1 => Ok(PurseTransferResult::TransferError(TransferFailure::Other)),
+            code => TransferFailure::from_code(code)
+                .map(PurseTransferResult::TransferError)
+                .ok_or(()),
         }
     }
 }
@@ -528,12 +903,15 @@ impl From<PurseTransferResult> for i32 {
     fn from(result: PurseTransferResult) -> Self {
         match result {
             PurseTransferResult::TransferSuccessful => 0,
-            PurseTransferResult::TransferError => 1,
+            PurseTransferResult::TransferError(failure) => failure.code(1),
         }
     }
 }
 
 /// Transfers `amount` of motes from `source` purse to `target` purse.
+///
+/// The caller must hold a writeable reference to `source` and an addable reference to `target`;
+/// otherwise execution traps rather than returning a `TransferError`.
 pub fn transfer_from_purse_to_purse(
     source: PurseId,
     target: PurseId,
@@ -567,9 +945,98 @@ pub fn get_mint() -> Option<ContractPointer> {
     }
 }
 
+/// Destroys `amount` motes held in the given purse, reducing the total supply.
+/// The caller must have write access to the purse.
+pub fn burn(purse_id: PurseId, amount: U512) -> Result<(), mint::error::Error> {
+    let mint_contract = get_mint().expect("mint contract should exist");
+    let purse = purse_id.value();
+    call_contract(
+        mint_contract,
+        &(mint::BURN_METHOD, purse, amount),
+        &vec![Key::URef(purse)],
+    )
+}
+
+/// Returns the total supply of motes issued by the mint.
+pub fn read_total_supply() -> U512 {
+    let mint_contract = get_mint().expect("mint contract should exist");
+    call_contract(
+        mint_contract,
+        &(mint::READ_TOTAL_SUPPLY_METHOD,),
+        &Vec::new(),
+    )
+}
+
 pub fn get_phase() -> Phase {
     let dest_ptr = alloc_bytes(PHASE_SIZE);
     unsafe { ext_ffi::get_phase(dest_ptr) };
     let bytes = unsafe { Vec::from_raw_parts(dest_ptr, PHASE_SIZE, PHASE_SIZE) };
     deserialize(&bytes).unwrap()
 }
+
+/// Emits an event with the given topic and payload.  Events are not written to
+/// global state; they are reported in the effects of the deploy, so that
+/// off-chain indexers can observe them.  Events emitted by an execution which
+/// fails are discarded along with its other effects.
+pub fn emit_event(topic: &str, payload: &[u8]) {
+    let (topic_ptr, topic_size, _bytes) = str_ref_to_ptr(topic);
+    unsafe { ext_ffi::emit_event(topic_ptr, topic_size, payload.as_ptr(), payload.len()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use super::{PurseTransferResult, TransferFailure, TransferResult};
+
+    const FAILURES: [TransferFailure; 6] = [
+        TransferFailure::InsufficientFunds,
+        TransferFailure::SourceNotFound,
+        TransferFailure::DestNotFound,
+        TransferFailure::InvalidPurse,
+        TransferFailure::ForgedReference,
+        TransferFailure::Other,
+    ];
+
+    #[test]
+    fn transfer_result_should_round_trip_through_i32() {
+        let mut results = vec![
+            TransferResult::TransferredToExistingAccount,
+            TransferResult::TransferredToNewAccount,
+        ];
+        results.extend(FAILURES.iter().cloned().map(TransferResult::TransferError));
+        for result in results {
+            let code: i32 = result.into();
+            assert_eq!(TransferResult::try_from(code), Ok(result));
+        }
+    }
+
+    #[test]
+    fn purse_transfer_result_should_round_trip_through_i32() {
+        let mut results = vec![PurseTransferResult::TransferSuccessful];
+        results.extend(
+            FAILURES
+                .iter()
+                .cloned()
+                .map(PurseTransferResult::TransferError),
+        );
+        for result in results {
+            let code: i32 = result.into();
+            assert_eq!(PurseTransferResult::try_from(code), Ok(result));
+        }
+    }
+
+    #[test]
+    fn should_keep_codes_of_existing_results() {
+        assert_eq!(
+            i32::from(TransferResult::TransferError(TransferFailure::Other)),
+            2
+        );
+        assert_eq!(
+            i32::from(PurseTransferResult::TransferError(TransferFailure::Other)),
+            1
+        );
+        assert!(TransferResult::try_from(8).is_err());
+        assert!(PurseTransferResult::try_from(2).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::bytesrepr::{Error, FromBytes, ToBytes};
+use crate::value::Value;
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Arguments of a contract call, passed by name rather than by position.
+///
+/// Each argument is held as a [`Value`], so it carries its own type tag and a
+/// callee can tell a missing argument from one of the wrong type.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct RuntimeArgs(BTreeMap<String, Value>);
+
+impl RuntimeArgs {
+    pub fn new() -> Self {
+        RuntimeArgs(BTreeMap::new())
+    }
+
+    /// Adds an argument under `name`, replacing any argument already there.
+    pub fn insert<T: Into<Value>>(&mut self, name: &str, value: T) {
+        self.0.insert(name.to_string(), value.into());
+    }
+
+    /// Builder-style variant of [`RuntimeArgs::insert`].
+    pub fn with<T: Into<Value>>(mut self, name: &str, value: T) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.get(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<BTreeMap<String, Value>> for RuntimeArgs {
+    fn from(args: BTreeMap<String, Value>) -> Self {
+        RuntimeArgs(args)
+    }
+}
+
+impl ToBytes for RuntimeArgs {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.0.to_bytes()
+    }
+}
+
+impl FromBytes for RuntimeArgs {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (args, rem): (BTreeMap<String, Value>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        Ok((RuntimeArgs(args), rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RuntimeArgs;
+    use crate::test_utils::test_serialization_roundtrip;
+    use crate::value::{Value, U512};
+    use alloc::string::String;
+
+    #[test]
+    fn should_look_up_args_by_name() {
+        let args = RuntimeArgs::new()
+            .with("amount", U512::from(10))
+            .with("method", String::from("transfer"));
+
+        assert_eq!(args.get("amount"), Some(&Value::UInt512(U512::from(10))));
+        assert_eq!(args.get("target"), None);
+        assert!(test_serialization_roundtrip(&args));
+    }
+}
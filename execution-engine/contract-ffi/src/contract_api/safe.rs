@@ -0,0 +1,422 @@
+//! Variants of the `contract_api` functions which return a `Result` instead of
+//! trapping, reverting or returning an `Option`.
+//!
+//! Every error is an [`ApiError`], which maps to a stable revert code, so a
+//! contract can propagate errors with `?` and hand them to [`revert`] (or use
+//! [`UnwrapOrRevert`]) without defining its own codes.
+
+use core::convert::{TryFrom, TryInto};
+
+use super::pointers::{ContractPointer, UPointer};
+use super::runtime_args::RuntimeArgs;
+use super::{PurseTransferResult, TransferResult, MINT_NAME};
+use crate::bytesrepr::{deserialize, FromBytes, ToBytes};
+use crate::contract_api::argsparser::ArgsParser;
+use crate::key::Key;
+use crate::system_contracts::mint;
+use crate::uref::{AccessRights, URef};
+use crate::value::account::{
+    Account, ActionType, AddKeyFailure, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure,
+    UpdateKeyFailure, Weight,
+};
+use crate::value::{CLTyped, CLValue, ContractPackage, Value, U512};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub use crate::unwrap_or_revert::UnwrapOrRevert;
+
+/// Revert codes of all the [`ApiError`]s other than [`ApiError::User`] start at
+/// this value, which leaves the lower codes to errors defined by contracts.
+pub const API_ERROR_BASE: u32 = 0x1_0000;
+
+// Offsets from `API_ERROR_BASE` of the codes which wrap the key management
+// failures. The failure's own code is added to the offset.
+const ADD_KEY_FAILURE_OFFSET: u32 = 0x100;
+const REMOVE_KEY_FAILURE_OFFSET: u32 = 0x200;
+const UPDATE_KEY_FAILURE_OFFSET: u32 = 0x300;
+const SET_THRESHOLD_FAILURE_OFFSET: u32 = 0x400;
+
+/// An error returned by the functions of this module.
+///
+/// Each variant is converted to a revert code by `From<ApiError> for u32`.
+/// The codes are part of the contracts' interface, so the code of an
+/// existing variant should never change; new variants should take unused
+/// codes.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ApiError {
+    /// Optional data was unexpectedly `None`.
+    None,
+    /// Failed to serialize a value.
+    Serialize,
+    /// Failed to deserialize a value.
+    Deserialize,
+    /// No value was found under the given key.
+    ValueNotFound,
+    /// The value found under the given key is not of the requested type.
+    UnexpectedValueType,
+    /// No key is known under the given name.
+    URefNotFound,
+    /// The key is not of the expected variant.
+    UnexpectedKeyVariant,
+    /// The argument could not be deserialized to the requested type.
+    InvalidArgument,
+    /// The purse does not exist.
+    InvalidPurse,
+    /// The mint contract is not known to the current context.
+    MintNotFound,
+    /// The transfer failed.
+    Transfer,
+    /// The contract package has no contract under the given version.
+    ContractVersionNotFound,
+    /// The given version of the contract package is disabled.
+    ContractVersionDisabled,
+    /// No argument was passed under the given name.
+    MissingArgument,
+    AddKey(AddKeyFailure),
+    RemoveKey(RemoveKeyFailure),
+    UpdateKey(UpdateKeyFailure),
+    SetThreshold(SetThresholdFailure),
+    /// An error defined by the contract, reverted with the given code.
+    User(u16),
+}
+
+impl From<ApiError> for u32 {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::None => API_ERROR_BASE + 1,
+            ApiError::Serialize => API_ERROR_BASE + 2,
+            ApiError::Deserialize => API_ERROR_BASE + 3,
+            ApiError::ValueNotFound => API_ERROR_BASE + 4,
+            ApiError::UnexpectedValueType => API_ERROR_BASE + 5,
+            ApiError::URefNotFound => API_ERROR_BASE + 6,
+            ApiError::UnexpectedKeyVariant => API_ERROR_BASE + 7,
+            ApiError::InvalidArgument => API_ERROR_BASE + 8,
+            ApiError::InvalidPurse => API_ERROR_BASE + 9,
+            ApiError::MintNotFound => API_ERROR_BASE + 10,
+            ApiError::Transfer => API_ERROR_BASE + 11,
+            ApiError::ContractVersionNotFound => API_ERROR_BASE + 12,
+            ApiError::ContractVersionDisabled => API_ERROR_BASE + 13,
+            ApiError::MissingArgument => API_ERROR_BASE + 14,
+            ApiError::AddKey(failure) => API_ERROR_BASE + ADD_KEY_FAILURE_OFFSET + failure as u32,
+            ApiError::RemoveKey(failure) => {
+                API_ERROR_BASE + REMOVE_KEY_FAILURE_OFFSET + failure as u32
+            }
+            ApiError::UpdateKey(failure) => {
+                API_ERROR_BASE + UPDATE_KEY_FAILURE_OFFSET + failure as u32
+            }
+            ApiError::SetThreshold(failure) => {
+                API_ERROR_BASE + SET_THRESHOLD_FAILURE_OFFSET + failure as u32
+            }
+            ApiError::User(code) => u32::from(code),
+        }
+    }
+}
+
+impl From<AddKeyFailure> for ApiError {
+    fn from(failure: AddKeyFailure) -> Self {
+        ApiError::AddKey(failure)
+    }
+}
+
+impl From<RemoveKeyFailure> for ApiError {
+    fn from(failure: RemoveKeyFailure) -> Self {
+        ApiError::RemoveKey(failure)
+    }
+}
+
+impl From<UpdateKeyFailure> for ApiError {
+    fn from(failure: UpdateKeyFailure) -> Self {
+        ApiError::UpdateKey(failure)
+    }
+}
+
+impl From<SetThresholdFailure> for ApiError {
+    fn from(failure: SetThresholdFailure) -> Self {
+        ApiError::SetThreshold(failure)
+    }
+}
+
+/// Stops execution of a contract and reverts execution effects with the
+/// revert code of the given error.
+pub fn revert<E: Into<ApiError>>(error: E) -> ! {
+    let error: ApiError = error.into();
+    super::revert(error.into())
+}
+
+/// Reads the value under the key in the global state.
+pub fn read<T>(u_ptr: UPointer<T>) -> Result<T, ApiError>
+where
+    T: TryFrom<Value>,
+{
+    let key: Key = u_ptr.into();
+    read_untyped(&key)?
+        .try_into()
+        .map_err(|_| ApiError::UnexpectedValueType)
+}
+
+fn read_untyped(key: &Key) -> Result<Value, ApiError> {
+    let value: Option<Value> =
+        deserialize(&super::read_untyped_bytes(key)).map_err(|_| ApiError::Deserialize)?;
+    value.ok_or(ApiError::ValueNotFound)
+}
+
+/// Reads the value at the given key in the context-local partition of global
+/// state. Returns `Ok(None)` if there is no value under the key.
+pub fn read_local<K, V>(key: K) -> Result<Option<V>, ApiError>
+where
+    K: ToBytes,
+    V: TryFrom<Value>,
+{
+    let key_bytes = key.to_bytes().map_err(|_| ApiError::Serialize)?;
+    let value: Option<Value> = deserialize(&super::read_untyped_local_bytes(&key_bytes))
+        .map_err(|_| ApiError::Deserialize)?;
+    value
+        .map(|value| value.try_into().map_err(|_| ApiError::UnexpectedValueType))
+        .transpose()
+}
+
+/// Reads the item stored under `item_key` in the dictionary with the given
+/// seed.
+pub fn dictionary_get<V: TryFrom<Value>>(
+    seed: URef,
+    item_key: &str,
+) -> Result<Option<V>, ApiError> {
+    let value: Option<Value> = deserialize(&super::dictionary_get_bytes(seed, item_key))
+        .map_err(|_| ApiError::Deserialize)?;
+    value
+        .map(|value| value.try_into().map_err(|_| ApiError::UnexpectedValueType))
+        .transpose()
+}
+
+/// Returns the i-th argument passed to the current module invocation.
+///
+/// Requesting an argument which was not passed still traps, as the host
+/// doesn't report it as an error.
+pub fn get_arg<T: FromBytes>(i: u32) -> Result<T, ApiError> {
+    deserialize(&super::get_arg_bytes(i)).map_err(|_| ApiError::InvalidArgument)
+}
+
+/// Returns the i-th argument passed to the current module invocation, which
+/// must have been passed as a serialized [`CLValue`] holding a `T`.
+pub fn get_typed_arg<T: CLTyped + FromBytes>(i: u32) -> Result<T, ApiError> {
+    let cl_value: CLValue =
+        deserialize(&super::get_arg_bytes(i)).map_err(|_| ApiError::Deserialize)?;
+    cl_value.into_t().map_err(|_| ApiError::InvalidArgument)
+}
+
+/// Returns the argument passed to the current module invocation under the
+/// given name by [`call_contract_with_args`].
+pub fn get_named_arg<T: TryFrom<Value>>(name: &str) -> Result<T, ApiError> {
+    let bytes = super::get_named_arg_bytes(name).ok_or(ApiError::MissingArgument)?;
+    let value: Value = deserialize(&bytes).map_err(|_| ApiError::Deserialize)?;
+    value.try_into().map_err(|_| ApiError::InvalidArgument)
+}
+
+/// Returns the key known by the current module under the given name.
+pub fn get_uref(name: &str) -> Result<Key, ApiError> {
+    let key: Option<Key> =
+        deserialize(&super::get_uref_bytes(name)).map_err(|_| ApiError::Deserialize)?;
+    key.ok_or(ApiError::URefNotFound)
+}
+
+/// Returns all the keys known by the current context, along with the names
+/// they are known under.
+pub fn list_named_keys() -> Result<BTreeMap<String, Key>, ApiError> {
+    deserialize(&super::list_named_keys_bytes()).map_err(|_| ApiError::Deserialize)
+}
+
+/// Calls the given contract with the given arguments and returns the value it
+/// returned.
+#[allow(clippy::ptr_arg)]
+pub fn call_contract<A: ArgsParser, T: FromBytes>(
+    c_ptr: ContractPointer,
+    args: &A,
+    extra_urefs: &Vec<Key>,
+) -> Result<T, ApiError> {
+    let contract_key: Key = c_ptr.into();
+    let args = ArgsParser::parse(args).map_err(|_| ApiError::Serialize)?;
+    let res_bytes = super::call_contract_bytes(&contract_key, &args, extra_urefs);
+    deserialize(&res_bytes).map_err(|_| ApiError::Deserialize)
+}
+
+/// Calls the given contract with arguments passed by name and returns the value
+/// it returned.
+#[allow(clippy::ptr_arg)]
+pub fn call_contract_with_args<T: FromBytes>(
+    c_ptr: ContractPointer,
+    args: &RuntimeArgs,
+    extra_urefs: &Vec<Key>,
+) -> Result<T, ApiError> {
+    let contract_key: Key = c_ptr.into();
+    let res_bytes = super::call_contract_with_args_bytes(&contract_key, args, extra_urefs);
+    deserialize(&res_bytes).map_err(|_| ApiError::Deserialize)
+}
+
+/// Calls the given version of a contract package with the given arguments and
+/// returns the value it returned.
+#[allow(clippy::ptr_arg)]
+pub fn call_versioned_contract<A: ArgsParser, T: FromBytes>(
+    package: UPointer<ContractPackage>,
+    version: u32,
+    args: &A,
+    extra_urefs: &Vec<Key>,
+) -> Result<T, ApiError> {
+    let package = read(package)?;
+    if package.is_version_disabled(version) {
+        return Err(ApiError::ContractVersionDisabled);
+    }
+    let contract_key = *package
+        .get_version(version)
+        .ok_or(ApiError::ContractVersionNotFound)?;
+    let args = ArgsParser::parse(args).map_err(|_| ApiError::Serialize)?;
+    let res_bytes = super::call_contract_bytes(&contract_key, &args, extra_urefs);
+    deserialize(&res_bytes).map_err(|_| ApiError::Deserialize)
+}
+
+/// Disables the given version of a contract package, so that calls to it
+/// through the package fail with [`ApiError::ContractVersionDisabled`].
+pub fn disable_contract_version(
+    package: UPointer<ContractPackage>,
+    version: u32,
+) -> Result<(), ApiError> {
+    let mut contract_package = read(package.clone())?;
+    if !contract_package.disable_version(version) {
+        return Err(ApiError::ContractVersionNotFound);
+    }
+    super::write(package, contract_package);
+    Ok(())
+}
+
+/// Enables a version of a contract package disabled by
+/// [`disable_contract_version`].
+pub fn enable_contract_version(
+    package: UPointer<ContractPackage>,
+    version: u32,
+) -> Result<(), ApiError> {
+    let mut contract_package = read(package.clone())?;
+    if !contract_package.enable_version(version) {
+        return Err(ApiError::ContractVersionNotFound);
+    }
+    super::write(package, contract_package);
+    Ok(())
+}
+
+/// Returns the balance of the given purse.
+pub fn get_balance(purse_id: PurseId) -> Result<U512, ApiError> {
+    let balance_bytes = super::get_balance_bytes(purse_id).ok_or(ApiError::InvalidPurse)?;
+    deserialize(&balance_bytes).map_err(|_| ApiError::Deserialize)
+}
+
+/// Returns the main purse of the account of the caller.
+pub fn main_purse() -> Result<PurseId, ApiError> {
+    let key = Key::Account(super::get_caller().to_account_address());
+    let account: Account = read_untyped(&key)?
+        .try_into()
+        .map_err(|_| ApiError::UnexpectedValueType)?;
+    Ok(account.purse_id())
+}
+
+/// Returns a pointer to the mint contract.
+pub fn get_mint() -> Result<ContractPointer, ApiError> {
+    let mint_public_uref = get_uref(MINT_NAME).map_err(|_| ApiError::MintNotFound)?;
+    match read_untyped(&mint_public_uref)? {
+        Value::Key(Key::URef(mint_private_uref)) => {
+            let pointer = UPointer::new(mint_private_uref.addr(), AccessRights::READ);
+            Ok(ContractPointer::URef(pointer))
+        }
+        _ => Err(ApiError::UnexpectedKeyVariant),
+    }
+}
+
+/// Returns the total supply of motes issued by the mint.
+pub fn read_total_supply() -> Result<U512, ApiError> {
+    let mint_contract = get_mint()?;
+    call_contract(
+        mint_contract,
+        &(mint::READ_TOTAL_SUPPLY_METHOD,),
+        &Vec::new(),
+    )
+}
+
+/// Transfers `amount` of motes from the main purse of the account to the
+/// `target` account, creating the account if it doesn't exist.
+pub fn transfer_to_account(target: PublicKey, amount: U512) -> Result<(), ApiError> {
+    match super::transfer_to_account(target, amount) {
+        TransferResult::TransferError(_) => Err(ApiError::Transfer),
+        _ => Ok(()),
+    }
+}
+
+/// Transfers `amount` of motes from the `source` purse to the `target`
+/// account, creating the account if it doesn't exist.
+pub fn transfer_from_purse_to_account(
+    source: PurseId,
+    target: PublicKey,
+    amount: U512,
+) -> Result<(), ApiError> {
+    match super::transfer_from_purse_to_account(source, target, amount) {
+        TransferResult::TransferError(_) => Err(ApiError::Transfer),
+        _ => Ok(()),
+    }
+}
+
+/// Transfers `amount` of motes from the `source` purse to the `target` purse.
+///
+/// Requires [`AccessRights::WRITE`](crate::uref::AccessRights::WRITE) on `source` and
+/// [`AccessRights::ADD`](crate::uref::AccessRights::ADD) on `target`; execution traps otherwise.
+pub fn transfer_from_purse_to_purse(
+    source: PurseId,
+    target: PurseId,
+    amount: U512,
+) -> Result<(), ApiError> {
+    match super::transfer_from_purse_to_purse(source, target, amount) {
+        PurseTransferResult::TransferSuccessful => Ok(()),
+        PurseTransferResult::TransferError(_) => Err(ApiError::Transfer),
+    }
+}
+
+pub fn add_associated_key(public_key: PublicKey, weight: Weight) -> Result<(), ApiError> {
+    super::add_associated_key(public_key, weight).map_err(ApiError::from)
+}
+
+pub fn remove_associated_key(public_key: PublicKey) -> Result<(), ApiError> {
+    super::remove_associated_key(public_key).map_err(ApiError::from)
+}
+
+pub fn update_associated_key(public_key: PublicKey, weight: Weight) -> Result<(), ApiError> {
+    super::update_associated_key(public_key, weight).map_err(ApiError::from)
+}
+
+pub fn set_action_threshold(action_type: ActionType, threshold: Weight) -> Result<(), ApiError> {
+    super::set_action_threshold(action_type, threshold).map_err(ApiError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApiError, API_ERROR_BASE};
+    use crate::value::account::{AddKeyFailure, SetThresholdFailure};
+
+    #[test]
+    fn revert_codes_should_be_stable() {
+        assert_eq!(u32::from(ApiError::None), 65_537);
+        assert_eq!(u32::from(ApiError::Transfer), 65_547);
+        assert_eq!(
+            u32::from(ApiError::AddKey(AddKeyFailure::DuplicateKey)),
+            API_ERROR_BASE + 0x100 + AddKeyFailure::DuplicateKey as u32
+        );
+        assert_eq!(
+            u32::from(ApiError::SetThreshold(
+                SetThresholdFailure::InsufficientTotalWeight
+            )),
+            API_ERROR_BASE + 0x400 + 4
+        );
+    }
+
+    #[test]
+    fn user_errors_should_revert_with_their_own_code() {
+        assert_eq!(u32::from(ApiError::User(0)), 0);
+        assert_eq!(u32::from(ApiError::User(u16::max_value())), 65_535);
+        assert!(u32::from(ApiError::User(u16::max_value())) < API_ERROR_BASE);
+    }
+}
@@ -3,12 +3,13 @@ use crate::key::*;
 use crate::uref::{AccessRights, URef};
 use crate::value::account::{
     AccountActivity, ActionThresholds, AssociatedKeys, BlockTime, PublicKey, PurseId, Weight,
-    MAX_KEYS,
+    MAX_KEYS, SECP256K1_PUBLIC_KEY_LENGTH,
 };
 use crate::value::*;
+use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
 use alloc::string::String;
-use proptest::collection::{btree_map, vec};
+use proptest::collection::{btree_map, btree_set, vec};
 use proptest::prelude::*;
 use proptest::{array, bits, option, result};
 
@@ -62,7 +63,15 @@ pub fn key_arb() -> impl Strategy<Value = Key> {
 }
 
 pub fn public_key_arb() -> impl Strategy<Value = PublicKey> {
-    u8_slice_32().prop_map(Into::into)
+    prop_oneof![
+        u8_slice_32().prop_map(PublicKey::new),
+        (any::<u8>(), u8_slice_32()).prop_map(|(prefix, x)| {
+            let mut key = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+            key[0] = prefix;
+            key[1..].copy_from_slice(&x);
+            PublicKey::secp256k1(key)
+        })
+    ]
 }
 
 pub fn weight_arb() -> impl Strategy<Value = Weight> {
@@ -118,6 +127,16 @@ pub fn contract_arb() -> impl Strategy<Value = Contract> {
     })
 }
 
+pub fn contract_package_arb() -> impl Strategy<Value = ContractPackage> {
+    (
+        btree_map(any::<u32>(), key_arb(), 0..20),
+        btree_set(any::<u32>(), 0..20),
+    )
+        .prop_map(|(versions, disabled_versions)| {
+            ContractPackage::from_parts(versions, disabled_versions)
+        })
+}
+
 pub fn u128_arb() -> impl Strategy<Value = U128> {
     vec(any::<u8>(), 0..16).prop_map(|b| U128::from_little_endian(b.as_slice()))
 }
@@ -130,6 +149,50 @@ pub fn u512_arb() -> impl Strategy<Value = U512> {
     vec(any::<u8>(), 0..64).prop_map(|b| U512::from_little_endian(b.as_slice()))
 }
 
+pub fn cl_type_arb() -> impl Strategy<Value = CLType> {
+    let leaf = prop_oneof![
+        Just(CLType::Bool),
+        Just(CLType::I32),
+        Just(CLType::U8),
+        Just(CLType::U32),
+        Just(CLType::U64),
+        Just(CLType::U128),
+        Just(CLType::U256),
+        Just(CLType::U512),
+        Just(CLType::Unit),
+        Just(CLType::String),
+        Just(CLType::Key),
+        Just(CLType::URef),
+    ];
+    leaf.prop_recursive(4, 16, 2, |inner| {
+        prop_oneof![
+            inner.clone().prop_map(|t| CLType::Option(Box::new(t))),
+            inner.clone().prop_map(|t| CLType::List(Box::new(t))),
+            (inner.clone(), inner.clone()).prop_map(|(ok, err)| CLType::Result {
+                ok: Box::new(ok),
+                err: Box::new(err),
+            }),
+            (inner.clone(), inner.clone()).prop_map(|(key, value)| CLType::Map {
+                key: Box::new(key),
+                value: Box::new(value),
+            }),
+            (inner.clone(), inner).prop_map(|(t1, t2)| CLType::Tuple2(Box::new(t1), Box::new(t2))),
+        ]
+    })
+}
+
+pub fn cl_value_arb() -> impl Strategy<Value = CLValue> {
+    // Serializing any of these can't fail
+    prop_oneof![
+        any::<bool>().prop_map(|b| CLValue::from_t(&b).unwrap()),
+        any::<u32>().prop_map(|u| CLValue::from_t(&u).unwrap()),
+        u512_arb().prop_map(|u| CLValue::from_t(&u).unwrap()),
+        option::of(key_arb()).prop_map(|o| CLValue::from_t(&o).unwrap()),
+        btree_map("\\PC*", uref_arb(), 0..20).prop_map(|m| CLValue::from_t(&m).unwrap()),
+        ("\\PC*", result_arb()).prop_map(|t| CLValue::from_t(&t).unwrap()),
+    ]
+}
+
 pub fn value_arb() -> impl Strategy<Value = Value> {
     // If compiler brings you here it most probably means you've added a variant to
     // `Value` enum but forgot to add generator for it.
@@ -149,7 +212,9 @@ pub fn value_arb() -> impl Strategy<Value = Value> {
             | Value::Contract(_)
             | Value::Key(_)
             | Value::NamedKey(_, _)
-            | Value::Unit => (),
+            | Value::Unit
+            | Value::ContractPackage(_)
+            | Value::CLValue(_) => (),
         }
     };
     prop_oneof![
@@ -167,6 +232,8 @@ pub fn value_arb() -> impl Strategy<Value = Value> {
         u512_arb().prop_map(Value::UInt512),
         Just(Value::Unit),
         (any::<u64>().prop_map(Value::UInt64)),
+        contract_package_arb().prop_map(Value::ContractPackage),
+        cl_value_arb().prop_map(Value::CLValue),
     ]
 }
 
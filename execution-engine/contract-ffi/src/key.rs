@@ -18,6 +18,11 @@ pub const HASH_SIZE: usize = 32;
 pub const LOCAL_KEY_SIZE: usize = 32;
 pub const LOCAL_SEED_SIZE: usize = 32;
 
+const ACCOUNT_PREFIX: &str = "account-";
+const HASH_PREFIX: &str = "hash-";
+const UREF_PREFIX: &str = "uref-";
+const LOCAL_PREFIX: &str = "local-";
+
 const KEY_ID_SIZE: usize = 1; // u8 used to determine the ID
 const ACCOUNT_KEY_SIZE: usize = KEY_ID_SIZE + U32_SIZE + N32;
 const HASH_KEY_SIZE: usize = KEY_ID_SIZE + U32_SIZE + N32;
@@ -34,6 +39,38 @@ fn hash(bytes: &[u8]) -> [u8; LOCAL_KEY_SIZE] {
     ret
 }
 
+/// Errors returned when parsing a [`Key`] from its formatted string.
+#[derive(Debug, Fail, PartialEq)]
+pub enum FromStrError {
+    #[fail(display = "Unknown key prefix")]
+    InvalidPrefix,
+    #[fail(display = "Invalid address: {}", _0)]
+    InvalidAddress(base16::Error),
+    #[fail(display = "Invalid address length: expected {}, actual {}", _0, _1)]
+    InvalidAddressLength { expected: usize, actual: usize },
+    #[fail(display = "Invalid access rights")]
+    InvalidAccessRights,
+}
+
+impl From<base16::Error> for FromStrError {
+    fn from(error: base16::Error) -> Self {
+        FromStrError::InvalidAddress(error)
+    }
+}
+
+fn parse_addr(input: &str) -> Result<[u8; 32], FromStrError> {
+    let bytes = base16::decode_checksummed(input)?;
+    if bytes.len() != 32 {
+        return Err(FromStrError::InvalidAddressLength {
+            expected: 32,
+            actual: bytes.len(),
+        });
+    }
+    let mut addr = [0u8; 32];
+    addr.copy_from_slice(&bytes);
+    Ok(addr)
+}
+
 #[repr(C)]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum Key {
@@ -50,6 +87,12 @@ impl Key {
         Key::Local(hash)
     }
 
+    /// Returns the key of the item stored under `item_key` in the dictionary
+    /// with the given seed.
+    pub fn dictionary(seed: URef, item_key: &str) -> Self {
+        Key::local(seed.addr(), item_key.as_bytes())
+    }
+
     pub fn type_string(&self) -> String {
         match self {
             Key::Account(_) => String::from("Key::Account"),
@@ -181,6 +224,92 @@ impl Key {
             _ => None,
         }
     }
+
+    /// Formats the key as its variant's prefix followed by its checksummed base16 address, e.g.
+    /// "hash-…" or, for a [`Key::URef`], "uref-…-007" where the suffix holds the access rights
+    /// in octal.
+    pub fn to_formatted_string(&self) -> String {
+        match self {
+            Key::Account(addr) => format!("{}{}", ACCOUNT_PREFIX, base16::encode_checksummed(addr)),
+            Key::Hash(addr) => format!("{}{}", HASH_PREFIX, base16::encode_checksummed(addr)),
+            Key::URef(uref) => format!(
+                "{}{}-{:03o}",
+                UREF_PREFIX,
+                base16::encode_checksummed(&uref.addr()),
+                uref.access_rights()
+                    .map(|access_rights| access_rights.bits())
+                    .unwrap_or_default()
+            ),
+            Key::Local(hash) => format!("{}{}", LOCAL_PREFIX, base16::encode_checksummed(hash)),
+        }
+    }
+
+    /// Parses a key from the form produced by [`Key::to_formatted_string`], verifying the
+    /// checksum of its address.
+    pub fn from_formatted_str(input: &str) -> Result<Key, FromStrError> {
+        if input.starts_with(ACCOUNT_PREFIX) {
+            parse_addr(&input[ACCOUNT_PREFIX.len()..]).map(Key::Account)
+        } else if input.starts_with(HASH_PREFIX) {
+            parse_addr(&input[HASH_PREFIX.len()..]).map(Key::Hash)
+        } else if input.starts_with(LOCAL_PREFIX) {
+            parse_addr(&input[LOCAL_PREFIX.len()..]).map(Key::Local)
+        } else if input.starts_with(UREF_PREFIX) {
+            let mut parts = input[UREF_PREFIX.len()..].rsplitn(2, '-');
+            let access_rights = parts.next().ok_or(FromStrError::InvalidAccessRights)?;
+            let addr = parse_addr(parts.next().ok_or(FromStrError::InvalidAccessRights)?)?;
+            if access_rights.len() != 3 {
+                return Err(FromStrError::InvalidAccessRights);
+            }
+            let bits = u8::from_str_radix(access_rights, 8)
+                .map_err(|_| FromStrError::InvalidAccessRights)?;
+            let uref = match AccessRights::from_bits(bits) {
+                Some(access_rights) if access_rights.is_empty() => {
+                    URef::new(addr, access_rights).remove_access_rights()
+                }
+                Some(access_rights) => URef::new(addr, access_rights),
+                None => return Err(FromStrError::InvalidAccessRights),
+            };
+            Ok(Key::URef(uref))
+        } else {
+            Err(FromStrError::InvalidPrefix)
+        }
+    }
+}
+
+impl core::str::FromStr for Key {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Key::from_formatted_str(s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Key {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_formatted_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Key {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FormattedKeyVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for FormattedKeyVisitor {
+            type Value = Key;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("a formatted key such as \"hash-…\"")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Key, E> {
+                Key::from_formatted_str(value).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(FormattedKeyVisitor)
+    }
 }
 
 impl From<URef> for Key {
@@ -279,8 +408,9 @@ impl ToBytes for Vec<Key> {
 #[allow(clippy::unnecessary_operation)]
 #[cfg(test)]
 mod tests {
+    use crate::base16;
     use crate::bytesrepr::{Error, FromBytes};
-    use crate::key::Key;
+    use crate::key::{FromStrError, Key};
     use crate::uref::{AccessRights, URef};
     use alloc::string::String;
     use alloc::vec::Vec;
@@ -379,6 +509,83 @@ mod tests {
         assert_ne!(local1, local2);
     }
 
+    #[test]
+    fn should_format_and_parse_keys() {
+        let addr = [42u8; 32];
+        let keys = [
+            Key::Account(addr),
+            Key::Hash(addr),
+            Key::URef(URef::new(addr, AccessRights::READ_ADD_WRITE)),
+            Key::URef(URef::new(addr, AccessRights::READ).remove_access_rights()),
+            Key::Local(addr),
+        ];
+        for key in keys.iter() {
+            let formatted = key.to_formatted_string();
+            assert_eq!(
+                Key::from_formatted_str(&formatted),
+                Ok(*key),
+                "{}",
+                formatted
+            );
+            assert_eq!(formatted.parse::<Key>(), Ok(*key));
+        }
+
+        let uref = Key::URef(URef::new(addr, AccessRights::READ_ADD_WRITE));
+        assert!(uref.to_formatted_string().starts_with("uref-"));
+        assert!(uref.to_formatted_string().ends_with("-007"));
+        assert_eq!(
+            Key::Hash(addr).to_formatted_string().to_ascii_lowercase(),
+            Key::Hash(addr).as_string()
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_formatted_keys() {
+        let addr = [42u8; 32];
+        let formatted = Key::Hash(addr).to_formatted_string();
+
+        assert_eq!(
+            Key::from_formatted_str(&formatted.replacen("hash-", "contract-", 1)),
+            Err(FromStrError::InvalidPrefix)
+        );
+        assert_eq!(
+            Key::from_formatted_str(&formatted.to_ascii_uppercase().replacen("HASH-", "hash-", 1)),
+            Err(FromStrError::InvalidAddress(base16::Error::ChecksumError))
+        );
+        assert_eq!(
+            Key::from_formatted_str(&format!("hash-{}", base16::encode_checksummed(&[1, 2]))),
+            Err(FromStrError::InvalidAddressLength {
+                expected: 32,
+                actual: 2
+            })
+        );
+
+        let uref = Key::URef(URef::new(addr, AccessRights::READ)).to_formatted_string();
+        let without_rights = &uref[..uref.len() - 4];
+        assert_eq!(
+            Key::from_formatted_str(&format!("{}-010", without_rights)),
+            Err(FromStrError::InvalidAccessRights)
+        );
+        assert_eq!(
+            Key::from_formatted_str(&format!("{}-7", without_rights)),
+            Err(FromStrError::InvalidAccessRights)
+        );
+        assert_eq!(
+            Key::from_formatted_str(without_rights),
+            Err(FromStrError::InvalidAccessRights)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_serialize_key_as_formatted_string() {
+        let key = Key::Hash([42u8; 32]);
+        let json = serde_json::to_string(&key).expect("should serialize");
+        assert_eq!(json, format!("\"{}\"", key.to_formatted_string()));
+        let parsed: Key = serde_json::from_str(&json).expect("should deserialize");
+        assert_eq!(parsed, key);
+    }
+
     use proptest::prelude::*;
     use proptest::string::{string_regex, RegexGeneratorStrategy};
 
@@ -432,4 +639,16 @@ mod tests {
         let res: Result<(Vec<Key>, &[u8]), _> = FromBytes::from_bytes(&bytes);
         assert_eq!(res.expect_err("should fail"), Error::OutOfMemoryError);
     }
+
+    #[test]
+    fn should_address_dictionary_items_by_seed_and_key() {
+        let seed = URef::new([1; 32], AccessRights::READ_ADD_WRITE);
+        let other_seed = URef::new([2; 32], AccessRights::READ_ADD_WRITE);
+
+        let key = Key::dictionary(seed, "item");
+        let read_only_seed = URef::new([1; 32], AccessRights::READ);
+        assert_eq!(key, Key::dictionary(read_only_seed, "item"));
+        assert_ne!(key, Key::dictionary(seed, "other_item"));
+        assert_ne!(key, Key::dictionary(other_seed, "item"));
+    }
 }
@@ -24,6 +24,8 @@ extern crate num_derive;
 
 #[cfg(any(test, feature = "gens"))]
 extern crate proptest;
+#[cfg(feature = "serde")]
+extern crate serde;
 
 #[cfg(not(feature = "std"))]
 #[global_allocator]
@@ -39,6 +41,7 @@ pub mod key;
 pub mod system_contracts;
 #[cfg(any(test, feature = "gens"))]
 pub mod test_utils;
+pub mod unwrap_or_revert;
 pub mod uref;
 pub mod value;
 
@@ -55,6 +58,22 @@ mod ext_ffi {
             value_size: usize,
         );
         pub fn add(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
+        // Returns the size of the serialized `Option<Value>`, which can then be read with
+        // `get_read`.
+        pub fn dictionary_get(
+            seed_ptr: *const u8,
+            seed_size: usize,
+            item_key_ptr: *const u8,
+            item_key_size: usize,
+        ) -> usize;
+        pub fn dictionary_put(
+            seed_ptr: *const u8,
+            seed_size: usize,
+            item_key_ptr: *const u8,
+            item_key_size: usize,
+            value_ptr: *const u8,
+            value_size: usize,
+        );
         pub fn new_uref(key_ptr: *mut u8, value_ptr: *const u8, value_size: usize);
         pub fn serialize_function(name_ptr: *const u8, name_size: usize) -> usize;
         pub fn get_function(dest_ptr: *mut u8); //can only be called after `serialize_function`
@@ -69,6 +88,8 @@ mod ext_ffi {
         // Can only be called after `serialize_known_urefs`.
         pub fn list_known_urefs(dest_ptr: *mut u8);
         pub fn load_arg(i: u32) -> usize;
+        // Returns -1 if there is no argument under the given name.
+        pub fn load_named_arg(name_ptr: *const u8, name_size: usize) -> i32;
         pub fn get_arg(dest: *mut u8); //can only be called after `load_arg`
         pub fn ret(
             value_ptr: *const u8,
@@ -86,6 +107,15 @@ mod ext_ffi {
             extra_urefs_ptr: *const u8,
             extra_urefs_size: usize,
         ) -> usize;
+        pub fn call_contract_with_named_args(
+            key_ptr: *const u8,
+            key_size: usize,
+            args_ptr: *const u8,
+            args_size: usize,
+            // extra urefs known by the caller to make available to the callee
+            extra_urefs_ptr: *const u8,
+            extra_urefs_size: usize,
+        ) -> usize;
         pub fn get_call_result(res_ptr: *mut u8); //can only be called after `call_contract`
         pub fn get_uref(name_ptr: *const u8, name_size: usize) -> usize;
         pub fn has_uref_name(name_ptr: *const u8, name_size: usize) -> i32;
@@ -107,6 +137,9 @@ mod ext_ffi {
             amount_size: usize,
         ) -> i32;
         pub fn get_blocktime(dest_ptr: *const u8);
+        pub fn get_block_height(dest_ptr: *const u8);
+        // Writes the next 32 byte seed of the deploy to `dest_ptr`.
+        pub fn get_entropy(dest_ptr: *mut u8);
         pub fn transfer_from_purse_to_account(
             source_ptr: *const u8,
             source_size: usize,
@@ -125,6 +158,39 @@ mod ext_ffi {
         ) -> i32;
         pub fn get_balance(purse_id_ptr: *const u8, purse_id_size: usize) -> i32;
         pub fn get_phase(dest_ptr: *mut u8);
+        pub fn emit_event(
+            topic_ptr: *const u8,
+            topic_size: usize,
+            payload_ptr: *const u8,
+            payload_size: usize,
+        );
+        pub fn add_contract_version(
+            package_ptr: *const u8,
+            package_size: usize,
+            name_ptr: *const u8,
+            name_size: usize,
+            extra_urefs_ptr: *const u8,
+            extra_urefs_size: usize,
+        ) -> u32;
+        // Writes the 32 byte digest to `dest_ptr`.
+        pub fn blake2b256(data_ptr: *const u8, data_size: usize, dest_ptr: *mut u8);
+        // Writes the 32 byte digest to `dest_ptr`.
+        pub fn sha256(data_ptr: *const u8, data_size: usize, dest_ptr: *mut u8);
+        // Returns 1 if the signature is valid and 0 otherwise.
+        pub fn ed25519_verify(
+            public_key_ptr: *const u8,
+            message_ptr: *const u8,
+            message_size: usize,
+            signature_ptr: *const u8,
+        ) -> i32;
+        // Returns 1 and writes the 33 byte compressed public key to `dest_ptr` if a key could be
+        // recovered, and returns 0 otherwise.
+        pub fn secp256k1_recover(
+            message_hash_ptr: *const u8,
+            signature_ptr: *const u8,
+            recovery_id: i32,
+            dest_ptr: *mut u8,
+        ) -> i32;
     }
 }
 
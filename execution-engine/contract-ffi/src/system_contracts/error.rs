@@ -1,9 +1,14 @@
-use crate::system_contracts::mint;
+use crate::system_contracts::{mint, pos};
+
+pub use crate::system_contracts::mint::error::Error as MintError;
+pub use crate::system_contracts::pos::error::Error as PosError;
 
 /// An aggregate enum error with variants for each system contract's error.
 #[derive(Debug)]
 pub enum Error {
     MintError(mint::error::Error),
+    /// The Proof of Stake contract reverted with the given code.
+    ProofOfStakeError(u32),
 }
 
 impl From<mint::error::Error> for Error {
@@ -11,3 +16,9 @@ impl From<mint::error::Error> for Error {
         Error::MintError(error)
     }
 }
+
+impl From<pos::error::Error> for Error {
+    fn from(error: pos::error::Error) -> Error {
+        Error::ProofOfStakeError(error.into())
+    }
+}
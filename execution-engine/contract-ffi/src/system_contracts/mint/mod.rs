@@ -1,2 +1,11 @@
 pub mod error;
 pub mod purse_id;
+
+/// Name under which the mint stores the uref holding the total supply of motes.
+pub const TOTAL_SUPPLY_KEY: &str = "total_supply";
+
+/// Name of the mint method destroying motes held in a purse.
+pub const BURN_METHOD: &str = "burn";
+
+/// Name of the mint method returning the total supply of motes.
+pub const READ_TOTAL_SUPPLY_METHOD: &str = "read_total_supply";
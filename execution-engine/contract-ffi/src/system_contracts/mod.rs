@@ -7,3 +7,4 @@
 //! the supporting code i.e. mint.
 pub mod error;
 pub mod mint;
+pub mod pos;
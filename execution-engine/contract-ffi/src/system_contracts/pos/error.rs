@@ -0,0 +1,245 @@
+//! Errors of the Proof of Stake contract, shared between the contract, the
+//! host-side implementation and the engine.
+use core::convert::TryFrom;
+
+/// An error of the Proof of Stake contract, which reverts with its `u32` code.
+///
+/// The codes are part of the contract's interface, so the code of an existing
+/// variant should never change.
+#[derive(Fail, Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+// TODO: Split this up into user errors vs. system errors.
+pub enum Error {
+    #[fail(display = "Not bonded")]
+    NotBonded = 0,
+    #[fail(display = "Too many events in queue")]
+    TooManyEventsInQueue = 1,
+    #[fail(display = "Cannot unbond last validator")]
+    CannotUnbondLastValidator = 2,
+    #[fail(display = "Spread is too high")]
+    SpreadTooHigh = 3,
+    /// Returned when there is another QueueEntry in a Queue, for validator
+    /// making a request.
+    #[fail(display = "Multiple requests")]
+    MultipleRequests = 4,
+    #[fail(display = "Bond is too large")]
+    BondTooLarge = 5,
+    #[fail(display = "Unbond is too large")]
+    UnbondTooLarge = 6,
+    #[fail(display = "Bond transfer failed")]
+    BondTransferFailed = 7,
+    #[fail(display = "Unbond transfer failed")]
+    UnbondTransferFailed = 8,
+    #[fail(display = "Bond is too small")]
+    BondTooSmall = 9,
+    #[fail(display = "Delegation rate is too high")]
+    DelegationRateTooHigh = 10,
+    #[fail(display = "Delegation rate increase is too large")]
+    DelegationRateIncreaseTooLarge = 11,
+    #[fail(display = "Delegation rate changed too often")]
+    DelegationRateChangedTooOften = 12,
+    #[fail(display = "Bond is below the minimum bond amount")]
+    BondBelowMinimum = 13,
+    #[fail(display = "Bond is above the maximum bond amount")]
+    BondAboveMaximum = 14,
+    #[fail(display = "Too many validators")]
+    TooManyValidators = 15,
+    // System errors
+    #[fail(display = "Time went backwards")]
+    TimeWentBackwards = 0x100,
+    #[fail(display = "Stakes not found")]
+    StakesNotFound = 0x101,
+    #[fail(display = "Payment purse not found")]
+    PaymentPurseNotFound = 0x102,
+    #[fail(display = "Payment purse key has unexpected type")]
+    PaymentPurseKeyUnexpectedType = 0x103,
+    #[fail(display = "Payment purse balance not found")]
+    PaymentPurseBalanceNotFound = 0x104,
+    #[fail(display = "Bonding purse not found")]
+    BondingPurseNotFound = 0x105,
+    #[fail(display = "Bonding purse key has unexpected type")]
+    BondingPurseKeyUnexpectedType = 0x106,
+    #[fail(display = "Refund purse key has unexpected type")]
+    RefundPurseKeyUnexpectedType = 0x107,
+    #[fail(display = "Rewards purse not found")]
+    RewardsPurseNotFound = 0x108,
+    #[fail(display = "Rewards purse key has unexpected type")]
+    RewardsPurseKeyUnexpectedType = 0x109,
+    // TODO: Put these in their own enum, and wrap them separately in `BondingError` and
+    // `UnbondingError`.
+    #[fail(display = "Queue is not stored as a byte array")]
+    QueueNotStoredAsByteArray = 0x200,
+    #[fail(display = "Queue deserialization failed")]
+    QueueDeserializationFailed = 0x201,
+    #[fail(display = "Queue deserialization left extra bytes")]
+    QueueDeserializationExtraBytes = 0x202,
+    #[fail(display = "Stakes key deserialization failed")]
+    StakesKeyDeserializationFailed = 0x300,
+    #[fail(display = "Stakes deserialization failed")]
+    StakesDeserializationFailed = 0x301,
+    #[fail(display = "Era snapshot is not stored as a byte array")]
+    EraSnapshotNotStoredAsByteArray = 0x302,
+    #[fail(display = "Era snapshot deserialization failed")]
+    EraSnapshotDeserializationFailed = 0x303,
+    #[fail(display = "Delegation rate deserialization failed")]
+    DelegationRateDeserializationFailed = 0x304,
+    #[fail(display = "Delegation rate limits deserialization failed")]
+    DelegationRateLimitsDeserializationFailed = 0x305,
+    #[fail(display = "Delegation rate changes are not stored as a byte array")]
+    DelegationRateChangesNotStoredAsByteArray = 0x306,
+    #[fail(display = "Delegation rate changes deserialization failed")]
+    DelegationRateChangesDeserializationFailed = 0x307,
+    #[fail(display = "Bond limits deserialization failed")]
+    BondLimitsDeserializationFailed = 0x308,
+    #[fail(display = "System function called by user account")]
+    SystemFunctionCalledByUserAccount = 0x400,
+    #[fail(display = "Insufficient payment for amount spent")]
+    InsufficientPaymentForAmountSpent = 0x401,
+    #[fail(display = "Failed transfer to rewards purse")]
+    FailedTransferToRewardsPurse = 0x402,
+    #[fail(display = "Failed transfer to account purse")]
+    FailedTransferToAccountPurse = 0x403,
+    #[fail(display = "Set refund purse called outside payment")]
+    SetRefundPurseCalledOutsidePayment = 0x404,
+    #[fail(display = "Failed to burn fees")]
+    FailedToBurnFees = 0x405,
+    #[fail(display = "Failed transfer to proposer")]
+    FailedTransferToProposer = 0x406,
+}
+
+impl From<Error> for u32 {
+    fn from(error: Error) -> Self {
+        error as u32
+    }
+}
+
+/// The error type returned when a `u32` is not the code of any [`Error`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct TryFromU32Error(());
+
+impl TryFrom<u32> for Error {
+    type Error = TryFromU32Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == Error::NotBonded as u32 => Ok(Error::NotBonded),
+            d if d == Error::TooManyEventsInQueue as u32 => Ok(Error::TooManyEventsInQueue),
+            d if d == Error::CannotUnbondLastValidator as u32 => {
+                Ok(Error::CannotUnbondLastValidator)
+            }
+            d if d == Error::SpreadTooHigh as u32 => Ok(Error::SpreadTooHigh),
+            d if d == Error::MultipleRequests as u32 => Ok(Error::MultipleRequests),
+            d if d == Error::BondTooLarge as u32 => Ok(Error::BondTooLarge),
+            d if d == Error::UnbondTooLarge as u32 => Ok(Error::UnbondTooLarge),
+            d if d == Error::BondTransferFailed as u32 => Ok(Error::BondTransferFailed),
+            d if d == Error::UnbondTransferFailed as u32 => Ok(Error::UnbondTransferFailed),
+            d if d == Error::BondTooSmall as u32 => Ok(Error::BondTooSmall),
+            d if d == Error::DelegationRateTooHigh as u32 => Ok(Error::DelegationRateTooHigh),
+            d if d == Error::DelegationRateIncreaseTooLarge as u32 => {
+                Ok(Error::DelegationRateIncreaseTooLarge)
+            }
+            d if d == Error::DelegationRateChangedTooOften as u32 => {
+                Ok(Error::DelegationRateChangedTooOften)
+            }
+            d if d == Error::BondBelowMinimum as u32 => Ok(Error::BondBelowMinimum),
+            d if d == Error::BondAboveMaximum as u32 => Ok(Error::BondAboveMaximum),
+            d if d == Error::TooManyValidators as u32 => Ok(Error::TooManyValidators),
+            d if d == Error::TimeWentBackwards as u32 => Ok(Error::TimeWentBackwards),
+            d if d == Error::StakesNotFound as u32 => Ok(Error::StakesNotFound),
+            d if d == Error::PaymentPurseNotFound as u32 => Ok(Error::PaymentPurseNotFound),
+            d if d == Error::PaymentPurseKeyUnexpectedType as u32 => {
+                Ok(Error::PaymentPurseKeyUnexpectedType)
+            }
+            d if d == Error::PaymentPurseBalanceNotFound as u32 => {
+                Ok(Error::PaymentPurseBalanceNotFound)
+            }
+            d if d == Error::BondingPurseNotFound as u32 => Ok(Error::BondingPurseNotFound),
+            d if d == Error::BondingPurseKeyUnexpectedType as u32 => {
+                Ok(Error::BondingPurseKeyUnexpectedType)
+            }
+            d if d == Error::RefundPurseKeyUnexpectedType as u32 => {
+                Ok(Error::RefundPurseKeyUnexpectedType)
+            }
+            d if d == Error::RewardsPurseNotFound as u32 => Ok(Error::RewardsPurseNotFound),
+            d if d == Error::RewardsPurseKeyUnexpectedType as u32 => {
+                Ok(Error::RewardsPurseKeyUnexpectedType)
+            }
+            d if d == Error::QueueNotStoredAsByteArray as u32 => {
+                Ok(Error::QueueNotStoredAsByteArray)
+            }
+            d if d == Error::QueueDeserializationFailed as u32 => {
+                Ok(Error::QueueDeserializationFailed)
+            }
+            d if d == Error::QueueDeserializationExtraBytes as u32 => {
+                Ok(Error::QueueDeserializationExtraBytes)
+            }
+            d if d == Error::StakesKeyDeserializationFailed as u32 => {
+                Ok(Error::StakesKeyDeserializationFailed)
+            }
+            d if d == Error::StakesDeserializationFailed as u32 => {
+                Ok(Error::StakesDeserializationFailed)
+            }
+            d if d == Error::EraSnapshotNotStoredAsByteArray as u32 => {
+                Ok(Error::EraSnapshotNotStoredAsByteArray)
+            }
+            d if d == Error::EraSnapshotDeserializationFailed as u32 => {
+                Ok(Error::EraSnapshotDeserializationFailed)
+            }
+            d if d == Error::DelegationRateDeserializationFailed as u32 => {
+                Ok(Error::DelegationRateDeserializationFailed)
+            }
+            d if d == Error::DelegationRateLimitsDeserializationFailed as u32 => {
+                Ok(Error::DelegationRateLimitsDeserializationFailed)
+            }
+            d if d == Error::DelegationRateChangesNotStoredAsByteArray as u32 => {
+                Ok(Error::DelegationRateChangesNotStoredAsByteArray)
+            }
+            d if d == Error::DelegationRateChangesDeserializationFailed as u32 => {
+                Ok(Error::DelegationRateChangesDeserializationFailed)
+            }
+            d if d == Error::BondLimitsDeserializationFailed as u32 => {
+                Ok(Error::BondLimitsDeserializationFailed)
+            }
+            d if d == Error::SystemFunctionCalledByUserAccount as u32 => {
+                Ok(Error::SystemFunctionCalledByUserAccount)
+            }
+            d if d == Error::InsufficientPaymentForAmountSpent as u32 => {
+                Ok(Error::InsufficientPaymentForAmountSpent)
+            }
+            d if d == Error::FailedTransferToRewardsPurse as u32 => {
+                Ok(Error::FailedTransferToRewardsPurse)
+            }
+            d if d == Error::FailedTransferToAccountPurse as u32 => {
+                Ok(Error::FailedTransferToAccountPurse)
+            }
+            d if d == Error::SetRefundPurseCalledOutsidePayment as u32 => {
+                Ok(Error::SetRefundPurseCalledOutsidePayment)
+            }
+            d if d == Error::FailedToBurnFees as u32 => Ok(Error::FailedToBurnFees),
+            d if d == Error::FailedTransferToProposer as u32 => Ok(Error::FailedTransferToProposer),
+            _ => Err(TryFromU32Error(())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use super::Error;
+
+    #[test]
+    fn should_round_trip_codes() {
+        for code in 0..0x500 {
+            if let Ok(error) = Error::try_from(code) {
+                assert_eq!(u32::from(error), code);
+            }
+        }
+        assert_eq!(u32::from(Error::BondTransferFailed), 7);
+        assert_eq!(
+            Error::try_from(0x400),
+            Ok(Error::SystemFunctionCalledByUserAccount)
+        );
+        assert!(Error::try_from(16).is_err());
+    }
+}
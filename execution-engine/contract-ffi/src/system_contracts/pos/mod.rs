@@ -0,0 +1,389 @@
+pub mod error;
+
+use alloc::vec::Vec;
+
+use num_traits::{FromPrimitive, ToPrimitive};
+
+use crate::bytesrepr::{Error, FromBytes, ToBytes, U32_SIZE, U64_SIZE};
+use crate::value::U512;
+
+/// Selects where the Proof of Stake contract sends the fees paid for a deploy
+/// when finalizing its payment.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, FromPrimitive, ToPrimitive)]
+#[repr(u8)]
+pub enum FeeHandling {
+    /// Fees accumulate in the rewards purse, to be distributed to validators.
+    Accumulate = 0,
+    /// Fees are burned, reducing the total supply.
+    Burn = 1,
+    /// Fees are transferred to the main purse of the block's proposer.
+    PayToProposer = 2,
+}
+
+impl Default for FeeHandling {
+    fn default() -> Self {
+        FeeHandling::Accumulate
+    }
+}
+
+impl ToBytes for FeeHandling {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let id = self.to_u8().expect("FeeHandling is represented as a u8");
+
+        Ok(vec![id])
+    }
+}
+
+impl FromBytes for FeeHandling {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (id, rest): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let fee_handling = FromPrimitive::from_u8(id).ok_or(Error::FormattingError)?;
+        Ok((fee_handling, rest))
+    }
+}
+
+/// Identifies an era of the Proof of Stake contract.  The bonded stakes are snapshotted when an
+/// era starts, and a later era always has a higher ID.
+pub type EraId = u64;
+
+/// The time in milliseconds an era of the Proof of Stake contract lasts, unless the chainspec
+/// sets another duration: a week.
+pub const DEFAULT_ERA_DURATION: u64 = 7 * 24 * 60 * 60 * 1000;
+
+/// The time in milliseconds from an unbonding request until the Proof of Stake contract pays out
+/// the unbonded stake, unless the chainspec sets another delay.
+pub const DEFAULT_UNBOND_DELAY: u64 = 0;
+
+/// The percentage of the rewards earned through delegated stake which a validator keeps as
+/// commission.
+pub type DelegationRate = u8;
+
+/// The highest valid [`DelegationRate`], at which a validator keeps all rewards.
+pub const MAX_DELEGATION_RATE: DelegationRate = 100;
+
+/// The prefix of the uref name under which the Proof of Stake contract stores its
+/// [`DelegationRateLimits`], followed by `{max_increase_per_era}_{max_changes_per_era}`.
+pub const DELEGATION_RATE_LIMITS_PREFIX: &str = "pos_delegation_rate_limits_";
+
+/// Caps on how a validator may change its [`DelegationRate`] within an era, so its delegators
+/// aren't caught out by a sudden rise of the commission.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DelegationRateLimits {
+    max_increase_per_era: DelegationRate,
+    max_changes_per_era: u32,
+}
+
+impl DelegationRateLimits {
+    /// Creates new [`DelegationRateLimits`], returning `None` if `max_increase_per_era` is greater
+    /// than [`MAX_DELEGATION_RATE`].
+    pub fn new(max_increase_per_era: DelegationRate, max_changes_per_era: u32) -> Option<Self> {
+        if max_increase_per_era > MAX_DELEGATION_RATE {
+            return None;
+        }
+        Some(DelegationRateLimits {
+            max_increase_per_era,
+            max_changes_per_era,
+        })
+    }
+
+    /// Returns how many percentage points the rate may rise above the one an era started with.
+    pub fn max_increase_per_era(&self) -> DelegationRate {
+        self.max_increase_per_era
+    }
+
+    /// Returns how many times the rate may change within an era.
+    pub fn max_changes_per_era(&self) -> u32 {
+        self.max_changes_per_era
+    }
+}
+
+impl Default for DelegationRateLimits {
+    fn default() -> Self {
+        DelegationRateLimits {
+            max_increase_per_era: 5,
+            max_changes_per_era: 1,
+        }
+    }
+}
+
+impl ToBytes for DelegationRateLimits {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut ret = Vec::with_capacity(1 + U32_SIZE);
+        ret.append(&mut self.max_increase_per_era.to_bytes()?);
+        ret.append(&mut self.max_changes_per_era.to_bytes()?);
+        Ok(ret)
+    }
+}
+
+impl FromBytes for DelegationRateLimits {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (max_increase_per_era, rem): (DelegationRate, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (max_changes_per_era, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let limits = DelegationRateLimits::new(max_increase_per_era, max_changes_per_era)
+            .ok_or(Error::FormattingError)?;
+        Ok((limits, rem))
+    }
+}
+
+/// Limits on the stakes of the validators and on their number, against dust stakes spamming the
+/// validator set and single validators capturing it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BondLimits {
+    min_bond_amount: U512,
+    max_bond_multiplier: u64,
+    max_validator_count: u32,
+}
+
+impl BondLimits {
+    /// Creates new [`BondLimits`], returning `None` if `min_bond_amount` is zero.  A
+    /// `max_bond_multiplier` or `max_validator_count` of zero means there is no such limit.
+    pub fn new(
+        min_bond_amount: U512,
+        max_bond_multiplier: u64,
+        max_validator_count: u32,
+    ) -> Option<Self> {
+        if min_bond_amount.is_zero() {
+            return None;
+        }
+        Some(BondLimits {
+            min_bond_amount,
+            max_bond_multiplier,
+            max_validator_count,
+        })
+    }
+
+    /// Returns the smallest stake a validator may hold, in motes.
+    pub fn min_bond_amount(&self) -> U512 {
+        self.min_bond_amount
+    }
+
+    /// Returns the multiple of the minimum bond amount a validator may hold at most, or zero if
+    /// there is no maximum.
+    pub fn max_bond_multiplier(&self) -> u64 {
+        self.max_bond_multiplier
+    }
+
+    /// Returns the most validators which may be bonded at once, or zero if there is no maximum.
+    pub fn max_validator_count(&self) -> u32 {
+        self.max_validator_count
+    }
+
+    /// Returns the largest stake a validator may hold, in motes, or `None` if there is no maximum.
+    pub fn max_bond_amount(&self) -> Option<U512> {
+        if self.max_bond_multiplier == 0 {
+            return None;
+        }
+        let max_bond_amount = self
+            .min_bond_amount
+            .checked_mul(U512::from(self.max_bond_multiplier))
+            .unwrap_or(U512::MAX);
+        Some(max_bond_amount)
+    }
+
+    /// Checks that a validator may hold `stake`.
+    pub fn check_stake(&self, stake: U512) -> Result<(), error::Error> {
+        if stake < self.min_bond_amount {
+            return Err(error::Error::BondBelowMinimum);
+        }
+        match self.max_bond_amount() {
+            Some(max_bond_amount) if stake > max_bond_amount => Err(error::Error::BondAboveMaximum),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that `validator_count` validators may be bonded at once.
+    pub fn check_validator_count(&self, validator_count: usize) -> Result<(), error::Error> {
+        if self.max_validator_count != 0 && validator_count > self.max_validator_count as usize {
+            return Err(error::Error::TooManyValidators);
+        }
+        Ok(())
+    }
+}
+
+impl Default for BondLimits {
+    fn default() -> Self {
+        BondLimits {
+            min_bond_amount: U512::one(),
+            max_bond_multiplier: 0,
+            max_validator_count: 0,
+        }
+    }
+}
+
+impl ToBytes for BondLimits {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut ret = self.min_bond_amount.to_bytes()?;
+        ret.append(&mut self.max_bond_multiplier.to_bytes()?);
+        ret.append(&mut self.max_validator_count.to_bytes()?);
+        Ok(ret)
+    }
+}
+
+impl FromBytes for BondLimits {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (min_bond_amount, rem): (U512, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (max_bond_multiplier, rem): (u64, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_validator_count, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let bond_limits =
+            BondLimits::new(min_bond_amount, max_bond_multiplier, max_validator_count)
+                .ok_or(Error::FormattingError)?;
+        Ok((bond_limits, rem))
+    }
+}
+
+/// The prefix of the uref names under which the Proof of Stake contract stores the bonding purses
+/// of individual validators, followed by the validator's public key in hex.
+pub const VALIDATOR_BONDING_PURSE_PREFIX: &str = "pos_bonding_purse_";
+
+/// The share of a deploy's unspent payment amount which the Proof of Stake contract refunds to
+/// the deployer's purse when finalizing its payment, expressed as `numerator / denominator`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RefundRatio {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl RefundRatio {
+    /// Creates a new [`RefundRatio`], returning `None` if `denominator` is zero or the ratio
+    /// is greater than one.
+    pub fn new(numerator: u64, denominator: u64) -> Option<Self> {
+        if denominator == 0 || numerator > denominator {
+            return None;
+        }
+        Some(RefundRatio {
+            numerator,
+            denominator,
+        })
+    }
+
+    pub fn numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> u64 {
+        self.denominator
+    }
+
+    /// Returns the refunded share of `amount`, rounded down.
+    pub fn apply(&self, amount: U512) -> U512 {
+        amount * U512::from(self.numerator) / U512::from(self.denominator)
+    }
+}
+
+impl Default for RefundRatio {
+    fn default() -> Self {
+        RefundRatio {
+            numerator: 1,
+            denominator: 1,
+        }
+    }
+}
+
+impl ToBytes for RefundRatio {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut ret = Vec::with_capacity(2 * U64_SIZE);
+        ret.append(&mut self.numerator.to_bytes()?);
+        ret.append(&mut self.denominator.to_bytes()?);
+        Ok(ret)
+    }
+}
+
+impl FromBytes for RefundRatio {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (numerator, rem): (u64, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (denominator, rem): (u64, &[u8]) = FromBytes::from_bytes(rem)?;
+        let refund_ratio =
+            RefundRatio::new(numerator, denominator).ok_or(Error::FormattingError)?;
+        Ok((refund_ratio, rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::test_serialization_roundtrip;
+    use crate::value::U512;
+
+    use super::error::Error;
+    use super::{BondLimits, DelegationRateLimits, RefundRatio, MAX_DELEGATION_RATE};
+
+    #[test]
+    fn should_reject_invalid_refund_ratios() {
+        assert!(RefundRatio::new(1, 0).is_none());
+        assert!(RefundRatio::new(2, 1).is_none());
+        assert!(RefundRatio::new(0, 1).is_some());
+        assert!(RefundRatio::new(1, 1).is_some());
+    }
+
+    #[test]
+    fn should_round_refund_down() {
+        let one_third = RefundRatio::new(1, 3).unwrap();
+        assert_eq!(one_third.apply(U512::from(10)), U512::from(3));
+        assert_eq!(one_third.apply(U512::from(2)), U512::zero());
+
+        let two_thirds = RefundRatio::new(2, 3).unwrap();
+        assert_eq!(two_thirds.apply(U512::from(10)), U512::from(6));
+
+        let full = RefundRatio::default();
+        assert_eq!(full.apply(U512::from(10)), U512::from(10));
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_refund_ratio() {
+        assert!(test_serialization_roundtrip(&RefundRatio::default()));
+        assert!(test_serialization_roundtrip(
+            &RefundRatio::new(1, 3).unwrap()
+        ));
+    }
+
+    #[test]
+    fn should_reject_invalid_delegation_rate_limits() {
+        assert!(DelegationRateLimits::new(MAX_DELEGATION_RATE + 1, 1).is_none());
+        assert!(DelegationRateLimits::new(MAX_DELEGATION_RATE, 0).is_some());
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_delegation_rate_limits() {
+        assert!(test_serialization_roundtrip(
+            &DelegationRateLimits::default()
+        ));
+        assert!(test_serialization_roundtrip(
+            &DelegationRateLimits::new(0, 3).unwrap()
+        ));
+    }
+
+    #[test]
+    fn should_check_stakes_against_bond_limits() {
+        assert!(BondLimits::new(U512::zero(), 1, 1).is_none());
+
+        let limits = BondLimits::new(U512::from(10), 3, 2).unwrap();
+        assert_eq!(limits.max_bond_amount(), Some(U512::from(30)));
+        assert_eq!(
+            limits.check_stake(U512::from(9)),
+            Err(Error::BondBelowMinimum)
+        );
+        assert_eq!(limits.check_stake(U512::from(10)), Ok(()));
+        assert_eq!(limits.check_stake(U512::from(30)), Ok(()));
+        assert_eq!(
+            limits.check_stake(U512::from(31)),
+            Err(Error::BondAboveMaximum)
+        );
+        assert_eq!(limits.check_validator_count(2), Ok(()));
+        assert_eq!(
+            limits.check_validator_count(3),
+            Err(Error::TooManyValidators)
+        );
+
+        let unlimited = BondLimits::default();
+        assert_eq!(unlimited.max_bond_amount(), None);
+        assert_eq!(unlimited.check_stake(U512::MAX), Ok(()));
+        assert_eq!(unlimited.check_validator_count(usize::max_value()), Ok(()));
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_bond_limits() {
+        assert!(test_serialization_roundtrip(&BondLimits::default()));
+        assert!(test_serialization_roundtrip(
+            &BondLimits::new(U512::from(10), 3, 2).unwrap()
+        ));
+    }
+}
@@ -15,3 +15,24 @@ where
         None => false,
     }
 }
+
+/// Returns `true` if a value deserializes from its serialized form followed by `trailing`, leaving
+/// exactly `trailing` as the remainder.  This fails if any length prefix in the encoding is off.
+pub fn test_serialization_consumes_own_bytes<T>(t: &T, trailing: &[u8]) -> bool
+where
+    T: ToBytes + FromBytes + PartialEq,
+{
+    let mut bytes = ToBytes::to_bytes(t).expect("Unable to serialize data");
+    bytes.extend_from_slice(trailing);
+    match FromBytes::from_bytes(&bytes) {
+        Ok((deserialized, rem)) => deserialized == *t && rem == trailing,
+        Err(_) => false,
+    }
+}
+
+/// Returns the little-endian `u32` length prefix at the start of `bytes`.
+pub fn length_prefix(bytes: &[u8]) -> u32 {
+    let (prefix, _): (u32, &[u8]) =
+        FromBytes::from_bytes(bytes).expect("should have a length prefix");
+    prefix
+}
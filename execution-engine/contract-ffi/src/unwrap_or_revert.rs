@@ -0,0 +1,35 @@
+//! Home of [`UnwrapOrRevert`], a convenience trait for unwrapping values.
+
+use crate::contract_api::safe::{self, ApiError};
+
+/// A trait which provides syntactic sugar for unwrapping a type or reverting
+/// execution with the revert code of an [`ApiError`] if this is not possible.
+pub trait UnwrapOrRevert<T> {
+    /// Unwraps the value into its inner type or reverts execution with the
+    /// code of the contained error or, for an `Option`, of [`ApiError::None`].
+    fn unwrap_or_revert(self) -> T;
+
+    /// Unwraps the value into its inner type or reverts execution with the
+    /// code of the provided error.
+    fn unwrap_or_revert_with<E: Into<ApiError>>(self, error: E) -> T;
+}
+
+impl<T, E: Into<ApiError>> UnwrapOrRevert<T> for Result<T, E> {
+    fn unwrap_or_revert(self) -> T {
+        self.unwrap_or_else(|error| safe::revert(error))
+    }
+
+    fn unwrap_or_revert_with<F: Into<ApiError>>(self, error: F) -> T {
+        self.unwrap_or_else(|_| safe::revert(error))
+    }
+}
+
+impl<T> UnwrapOrRevert<T> for Option<T> {
+    fn unwrap_or_revert(self) -> T {
+        self.unwrap_or_else(|| safe::revert(ApiError::None))
+    }
+
+    fn unwrap_or_revert_with<E: Into<ApiError>>(self, error: E) -> T {
+        self.unwrap_or_else(|| safe::revert(error))
+    }
+}
@@ -1,11 +1,16 @@
+use crate::base16;
 use crate::bytesrepr::{Error, FromBytes, ToBytes, U32_SIZE, U64_SIZE, U8_SIZE};
-use crate::key::{addr_to_hex, Key, UREF_SIZE};
+use crate::key::{Key, UREF_SIZE};
 use crate::uref::{AccessRights, URef, UREF_SIZE_SERIALIZED};
 use alloc::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 use alloc::string::String;
 use alloc::vec::Vec;
+use blake2::digest::{Input, VariableOutput};
+use blake2::VarBlake2b;
+use core::cmp::Ordering;
 use core::convert::TryFrom;
 use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
 use failure::Fail;
 
 const DEFAULT_CURRENT_BLOCK_TIME: BlockTime = BlockTime(0);
@@ -51,6 +56,8 @@ pub enum ActionType {
     /// Required when adding/removing associated keys, changing threshold
     /// levels.
     KeyManagement = 1,
+    /// Required when transferring tokens out of the account.
+    Transfer = 2,
 }
 
 /// convert from u32 representation of `[ActionType]`
@@ -64,6 +71,7 @@ impl TryFrom<u32> for ActionType {
         match value {
             d if d == ActionType::Deployment as u32 => Ok(ActionType::Deployment),
             d if d == ActionType::KeyManagement as u32 => Ok(ActionType::KeyManagement),
+            d if d == ActionType::Transfer as u32 => Ok(ActionType::Transfer),
             _ => Err(TryFromIntError(())),
         }
     }
@@ -74,6 +82,7 @@ impl TryFrom<u32> for ActionType {
 pub struct ActionThresholds {
     deployment: Weight,
     key_management: Weight,
+    transfer: Weight,
 }
 
 /// Represents an error that occurs during the change of a thresholds on an
@@ -131,10 +140,12 @@ impl ActionThresholds {
     /// Creates new ActionThresholds object with provided weights
     ///
     /// Requires deployment threshold to be lower than or equal to
-    /// key management threshold.
+    /// key management threshold. The transfer threshold is not constrained
+    /// by the other two.
     pub fn new(
         deployment: Weight,
         key_management: Weight,
+        transfer: Weight,
     ) -> Result<ActionThresholds, SetThresholdFailure> {
         if deployment > key_management {
             return Err(SetThresholdFailure::DeploymentThresholdError);
@@ -142,6 +153,7 @@ impl ActionThresholds {
         Ok(ActionThresholds {
             deployment,
             key_management,
+            transfer,
         })
     }
     /// Sets new threshold for [ActionType::Deployment].
@@ -175,6 +187,11 @@ impl ActionThresholds {
         }
     }
 
+    /// Sets new threshold for [ActionType::Transfer].
+    pub fn set_transfer_threshold(&mut self, new_threshold: Weight) {
+        self.transfer = new_threshold;
+    }
+
     pub fn deployment(&self) -> &Weight {
         &self.deployment
     }
@@ -183,6 +200,19 @@ impl ActionThresholds {
         &self.key_management
     }
 
+    pub fn transfer(&self) -> &Weight {
+        &self.transfer
+    }
+
+    /// Returns the threshold defined for the given [ActionType].
+    pub fn get_threshold(&self, action_type: ActionType) -> &Weight {
+        match action_type {
+            ActionType::Deployment => self.deployment(),
+            ActionType::KeyManagement => self.key_management(),
+            ActionType::Transfer => self.transfer(),
+        }
+    }
+
     /// Unified function that takes an action type, and changes appropriate
     /// threshold defined by the [ActionType] variants.
     pub fn set_threshold(
@@ -193,6 +223,10 @@ impl ActionThresholds {
         match action_type {
             ActionType::Deployment => self.set_deployment_threshold(new_threshold),
             ActionType::KeyManagement => self.set_key_management_threshold(new_threshold),
+            ActionType::Transfer => {
+                self.set_transfer_threshold(new_threshold);
+                Ok(())
+            }
         }
     }
 }
@@ -202,6 +236,7 @@ impl Default for ActionThresholds {
         ActionThresholds {
             deployment: Weight::new(1),
             key_management: Weight::new(1),
+            transfer: Weight::new(1),
         }
     }
 }
@@ -262,9 +297,9 @@ impl AccountActivity {
 }
 
 pub const KEY_SIZE: usize = 32;
-/// Maximum number of associated keys.
+/// Default maximum number of associated keys.
 /// Value chosen arbitrary, shouldn't be too large to prevent bloating
-/// `associated_keys` table.
+/// `associated_keys` table. The engine may configure a different limit.
 pub const MAX_KEYS: usize = 10;
 
 #[derive(PartialOrd, Ord, PartialEq, Eq, Clone, Copy, Debug)]
@@ -282,12 +317,32 @@ impl Weight {
 
 pub const WEIGHT_SIZE: usize = U8_SIZE;
 
-#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy)]
-pub struct PublicKey([u8; KEY_SIZE]);
+/// Length in bytes of an Ed25519 public key.
+pub const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
+
+/// Length in bytes of a compressed Secp256k1 public key.
+pub const SECP256K1_PUBLIC_KEY_LENGTH: usize = 33;
+
+const ED25519_TAG: u8 = 0;
+const SECP256K1_TAG: u8 = 1;
+
+/// Length prefix marking the tagged (version 1) encoding of a [`PublicKey`].
+/// It can never be the length prefix of a legacy (version 0) encoding.
+pub const PUBLIC_KEY_TAGGED_ENCODING: u32 = u32::max_value();
+
+/// A public key, tagged with the signature algorithm it belongs to.
+#[derive(Clone, Copy)]
+pub enum PublicKey {
+    Ed25519([u8; ED25519_PUBLIC_KEY_LENGTH]),
+    Secp256k1([u8; SECP256K1_PUBLIC_KEY_LENGTH]),
+}
 
 impl Display for PublicKey {
     fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
-        write!(f, "PublicKey({})", addr_to_hex(&self.0))
+        match self {
+            PublicKey::Ed25519(_) => write!(f, "PublicKey::Ed25519({})", self.to_hex()),
+            PublicKey::Secp256k1(_) => write!(f, "PublicKey::Secp256k1({})", self.to_hex()),
+        }
     }
 }
 
@@ -297,52 +352,204 @@ impl Debug for PublicKey {
     }
 }
 
-// TODO: This needs to be updated, `PUBLIC_KEY_SIZE` is not 32 bytes as KEY_SIZE
-// * U8_SIZE. I am not changing that as I don't want to deal with ripple effect.
+// `[u8; 33]` doesn't implement the comparison traits, so they are implemented
+// over the algorithm tag and the key bytes instead of being derived.
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &PublicKey) -> bool {
+        self.tag() == other.tag() && self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for PublicKey {}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &PublicKey) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PublicKey {
+    fn cmp(&self, other: &PublicKey) -> Ordering {
+        (self.tag(), self.as_bytes()).cmp(&(other.tag(), other.as_bytes()))
+    }
+}
+
+impl Hash for PublicKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tag().hash(state);
+        self.as_bytes().hash(state);
+    }
+}
 
-// Public key is encoded as its underlying [u8; 32] array, which in turn
-// is serialized as u8 + [u8; 32], u8 represents the length and then 32 element
-// array.
-pub const PUBLIC_KEY_SIZE: usize = KEY_SIZE * U8_SIZE;
+/// Maximum length of a serialized [`PublicKey`]: the tagged encoding of the
+/// longest supported key.
+pub const PUBLIC_KEY_SERIALIZED_MAX_LENGTH: usize =
+    U32_SIZE + U8_SIZE + SECP256K1_PUBLIC_KEY_LENGTH;
 
 impl PublicKey {
-    pub fn new(key: [u8; KEY_SIZE]) -> PublicKey {
-        PublicKey(key)
+    /// Creates an Ed25519 public key.
+    pub fn new(key: [u8; ED25519_PUBLIC_KEY_LENGTH]) -> PublicKey {
+        PublicKey::Ed25519(key)
     }
 
-    pub fn value(self) -> [u8; KEY_SIZE] {
-        self.0
+    /// Creates a Secp256k1 public key from its compressed form.
+    pub fn secp256k1(key: [u8; SECP256K1_PUBLIC_KEY_LENGTH]) -> PublicKey {
+        PublicKey::Secp256k1(key)
+    }
+
+    /// Returns the tag identifying the signature algorithm of this key.
+    pub fn tag(&self) -> u8 {
+        match self {
+            PublicKey::Ed25519(_) => ED25519_TAG,
+            PublicKey::Secp256k1(_) => SECP256K1_TAG,
+        }
+    }
+
+    /// Returns the raw key bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            PublicKey::Ed25519(key) => key,
+            PublicKey::Secp256k1(key) => key,
+        }
+    }
+
+    /// Returns the key bytes as a lower case hex string.
+    pub fn to_hex(&self) -> String {
+        base16::encode_lower(self.as_bytes())
+    }
+
+    /// Returns the address of the account controlled by this key.
+    ///
+    /// Keys in the tagged (version 1) encoding are addressed by the Blake2b
+    /// hash of the algorithm tag followed by the key bytes.  Ed25519 keys use
+    /// the legacy (version 0) encoding, whose address is the key itself: the
+    /// node passes them as raw 32 byte account addresses, and the accounts
+    /// created under those addresses must remain reachable.
+    pub fn to_account_address(&self) -> [u8; KEY_SIZE] {
+        match self {
+            PublicKey::Ed25519(key) => *key,
+            PublicKey::Secp256k1(_) => {
+                let mut ret = [0u8; KEY_SIZE];
+                // Safe to unwrap here because our digest length is constant and valid
+                let mut hasher = VarBlake2b::new(KEY_SIZE).unwrap();
+                hasher.input(&[self.tag()]);
+                hasher.input(self.as_bytes());
+                hasher.variable_result(|hash| ret.clone_from_slice(hash));
+                ret
+            }
+        }
+    }
+
+    /// Returns the length of the key bytes of the algorithm with the given
+    /// tag, or `None` if the tag is unknown.
+    fn key_length_for_tag(tag: u8) -> Option<usize> {
+        match tag {
+            ED25519_TAG => Some(ED25519_PUBLIC_KEY_LENGTH),
+            SECP256K1_TAG => Some(SECP256K1_PUBLIC_KEY_LENGTH),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of a serialized key starting with `prefix`, or
+    /// `None` if the prefix starts no valid encoding.
+    ///
+    /// The tagged encoding needs its algorithm tag as well, so `tag` is asked
+    /// for it only when `prefix` is [`PUBLIC_KEY_TAGGED_ENCODING`].
+    pub fn serialized_length<F>(prefix: u32, tag: F) -> Option<usize>
+    where
+        F: FnOnce() -> Option<u8>,
+    {
+        match prefix {
+            PUBLIC_KEY_TAGGED_ENCODING => {
+                let key_length = tag().and_then(PublicKey::key_length_for_tag)?;
+                Some(U32_SIZE + U8_SIZE + key_length)
+            }
+            prefix if prefix as usize == ED25519_PUBLIC_KEY_LENGTH => {
+                Some(U32_SIZE + ED25519_PUBLIC_KEY_LENGTH)
+            }
+            _ => None,
+        }
     }
 }
 
-impl From<[u8; KEY_SIZE]> for PublicKey {
-    fn from(key: [u8; KEY_SIZE]) -> Self {
-        PublicKey(key)
+impl From<[u8; ED25519_PUBLIC_KEY_LENGTH]> for PublicKey {
+    fn from(key: [u8; ED25519_PUBLIC_KEY_LENGTH]) -> Self {
+        PublicKey::Ed25519(key)
     }
 }
 
+/// Creates a public key from raw key bytes, telling the algorithm apart by the
+/// length of the key.
 impl TryFrom<&[u8]> for PublicKey {
     type Error = TryFromSliceForPublicKeyError;
     fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
-        if bytes.len() != KEY_SIZE {
-            return Err(TryFromSliceForPublicKeyError(()));
+        match bytes.len() {
+            ED25519_PUBLIC_KEY_LENGTH => {
+                let mut public_key = [0u8; ED25519_PUBLIC_KEY_LENGTH];
+                public_key.copy_from_slice(bytes);
+                Ok(PublicKey::Ed25519(public_key))
+            }
+            SECP256K1_PUBLIC_KEY_LENGTH => {
+                let mut public_key = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+                public_key.copy_from_slice(bytes);
+                Ok(PublicKey::Secp256k1(public_key))
+            }
+            _ => Err(TryFromSliceForPublicKeyError(())),
         }
-        let mut public_key = [0u8; 32];
-        public_key.copy_from_slice(bytes);
-        Ok(PublicKey::new(public_key))
     }
 }
 
+// Public keys have two explicitly versioned encodings:
+//
+// * version 0, the legacy encoding, is the length-prefixed key bytes of an Ed25519 key, the same as
+//   the encoding of the raw 32 byte addresses which were used as keys before other algorithms were
+//   supported;
+// * version 1, the tagged encoding, is the `PUBLIC_KEY_TAGGED_ENCODING` marker followed by the
+//   algorithm tag and the key bytes.
+//
+// Ed25519 keys are serialized in version 0 and all other keys in version 1. Both versions are
+// accepted when deserializing.
 impl ToBytes for PublicKey {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        ToBytes::to_bytes(&self.0)
+        match self {
+            PublicKey::Ed25519(key) => key.to_bytes(),
+            PublicKey::Secp256k1(key) => {
+                let mut result = PUBLIC_KEY_TAGGED_ENCODING.to_bytes()?;
+                result.push(self.tag());
+                result.extend_from_slice(key);
+                Ok(result)
+            }
+        }
     }
 }
 
 impl FromBytes for PublicKey {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (key_bytes, rem): ([u8; KEY_SIZE], &[u8]) = FromBytes::from_bytes(bytes)?;
-        Ok((PublicKey::new(key_bytes), rem))
+        let (prefix, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        if prefix != PUBLIC_KEY_TAGGED_ENCODING {
+            let (key, rem): ([u8; ED25519_PUBLIC_KEY_LENGTH], &[u8]) =
+                FromBytes::from_bytes(bytes)?;
+            return Ok((PublicKey::Ed25519(key), rem));
+        }
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(rem)?;
+        let key_length = PublicKey::key_length_for_tag(tag).ok_or(Error::FormattingError)?;
+        if rem.len() < key_length {
+            return Err(Error::EarlyEndOfStream);
+        }
+        let (key_bytes, rem) = rem.split_at(key_length);
+        let public_key = match tag {
+            ED25519_TAG => {
+                let mut key = [0u8; ED25519_PUBLIC_KEY_LENGTH];
+                key.copy_from_slice(key_bytes);
+                PublicKey::Ed25519(key)
+            }
+            _ => {
+                let mut key = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+                key.copy_from_slice(key_bytes);
+                PublicKey::Secp256k1(key)
+            }
+        };
+        Ok((public_key, rem))
     }
 }
 
@@ -483,9 +690,19 @@ impl AssociatedKeys {
 
     /// Adds new AssociatedKey to the set.
     /// Returns true if added successfully, false otherwise.
-    #[allow(clippy::map_entry)]
     pub fn add_key(&mut self, key: PublicKey, weight: Weight) -> Result<(), AddKeyFailure> {
-        if self.0.len() == MAX_KEYS {
+        self.add_key_with_limit(key, weight, MAX_KEYS)
+    }
+
+    /// Adds new AssociatedKey to the set unless it already holds `max_keys` keys.
+    #[allow(clippy::map_entry)]
+    pub fn add_key_with_limit(
+        &mut self,
+        key: PublicKey,
+        weight: Weight,
+        max_keys: usize,
+    ) -> Result<(), AddKeyFailure> {
+        if self.0.len() >= max_keys {
             Err(AddKeyFailure::MaxKeysLimit)
         } else if self.0.contains_key(&key) {
             Err(AddKeyFailure::DuplicateKey)
@@ -589,12 +806,22 @@ impl Account {
         known_urefs: BTreeMap<String, Key>,
         purse_id: PurseId,
     ) -> Self {
-        let associated_keys = AssociatedKeys::new(PublicKey::new(account_addr), Weight::new(1));
+        Account::create_for_public_key(PublicKey::new(account_addr), known_urefs, purse_id)
+    }
+
+    /// Creates an account controlled by the given public key, stored under the
+    /// address derived from it.
+    pub fn create_for_public_key(
+        public_key: PublicKey,
+        known_urefs: BTreeMap<String, Key>,
+        purse_id: PurseId,
+    ) -> Self {
+        let associated_keys = AssociatedKeys::new(public_key, Weight::new(1));
         let action_thresholds: ActionThresholds = Default::default();
         let account_activity =
             AccountActivity::new(DEFAULT_CURRENT_BLOCK_TIME, DEFAULT_INACTIVITY_PERIOD_TIME);
         Account::new(
-            account_addr,
+            public_key.to_account_address(),
             known_urefs,
             purse_id,
             associated_keys,
@@ -650,6 +877,17 @@ impl Account {
         self.associated_keys.add_key(public_key, weight)
     }
 
+    /// Like [`Account::add_associated_key`], but with `max_keys` in place of [`MAX_KEYS`].
+    pub fn add_associated_key_with_limit(
+        &mut self,
+        public_key: PublicKey,
+        weight: Weight,
+        max_keys: usize,
+    ) -> Result<(), AddKeyFailure> {
+        self.associated_keys
+            .add_key_with_limit(public_key, weight, max_keys)
+    }
+
     /// Checks if subtracting passed weight from current total would make the
     /// new cumulative weight to fall below any of the thresholds on account.
     fn check_thresholds_for_weight_update(&self, weight: Weight) -> bool {
@@ -662,6 +900,7 @@ impl Account {
         // the thresholds.
         new_weight >= self.action_thresholds().deployment().value()
             && new_weight >= self.action_thresholds().key_management().value()
+            && new_weight >= self.action_thresholds().transfer().value()
     }
 
     pub fn remove_associated_key(&mut self, public_key: PublicKey) -> Result<(), RemoveKeyFailure> {
@@ -742,6 +981,16 @@ impl Account {
 
         total_weight >= *self.action_thresholds().key_management()
     }
+
+    /// Checks whether the sum of the weights of all authorization keys is
+    /// greater or equal to transfer threshold.
+    pub fn can_transfer_with(&self, authorization_keys: &BTreeSet<PublicKey>) -> bool {
+        let total_weight = self
+            .associated_keys
+            .calculate_keys_weight(authorization_keys);
+
+        total_weight >= *self.action_thresholds().transfer()
+    }
 }
 
 impl ToBytes for Weight {
@@ -794,43 +1043,56 @@ impl FromBytes for BlockTime {
 
 const DEPLOYMENT_THRESHOLD_ID: u8 = 0;
 const KEY_MANAGEMENT_THRESHOLD_ID: u8 = 1;
+const TRANSFER_THRESHOLD_ID: u8 = 2;
+
+/// Number of thresholds held by [`ActionThresholds`].
+const ACTION_THRESHOLDS_COUNT: usize = 3;
+
+/// Length of serialized [`ActionThresholds`]: each threshold is encoded as its
+/// id followed by its weight.
+pub const ACTION_THRESHOLDS_SERIALIZED_LENGTH: usize =
+    ACTION_THRESHOLDS_COUNT * (U8_SIZE + WEIGHT_SIZE);
 
 impl ToBytes for ActionThresholds {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let mut result = Vec::with_capacity(2 * (WEIGHT_SIZE + U8_SIZE));
+        let mut result = Vec::with_capacity(ACTION_THRESHOLDS_SERIALIZED_LENGTH);
         result.push(DEPLOYMENT_THRESHOLD_ID);
         result.extend(&self.deployment.to_bytes()?);
         result.push(KEY_MANAGEMENT_THRESHOLD_ID);
         result.extend(&self.key_management.to_bytes()?);
+        result.push(TRANSFER_THRESHOLD_ID);
+        result.extend(&self.transfer.to_bytes()?);
         Ok(result)
     }
 }
 
 impl FromBytes for ActionThresholds {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let mut action_thresholds: ActionThresholds = Default::default();
-        let (id_1, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
-        let (weight_1, rem2): (Weight, &[u8]) = FromBytes::from_bytes(&rem)?;
-        let (id_2, rem3): (u8, &[u8]) = FromBytes::from_bytes(&rem2)?;
-        let (weight_2, rem4): (Weight, &[u8]) = FromBytes::from_bytes(&rem3)?;
-        match (id_1, id_2) {
-            (DEPLOYMENT_THRESHOLD_ID, KEY_MANAGEMENT_THRESHOLD_ID) => {
-                action_thresholds
-                    .set_key_management_threshold(weight_2)
-                    .map_err(Error::custom)?;
-                action_thresholds
-                    .set_deployment_threshold(weight_1)
-                    .map_err(Error::custom)?;
-                Ok((action_thresholds, rem4))
+        let mut deployment: Option<Weight> = None;
+        let mut key_management: Option<Weight> = None;
+        let mut transfer: Option<Weight> = None;
+        let mut rem = bytes;
+        // Thresholds may come in any order, but each one has to be present
+        // exactly once.
+        for _ in 0..ACTION_THRESHOLDS_COUNT {
+            let (id, rem2): (u8, &[u8]) = FromBytes::from_bytes(rem)?;
+            let (weight, rem3): (Weight, &[u8]) = FromBytes::from_bytes(rem2)?;
+            let threshold = match id {
+                DEPLOYMENT_THRESHOLD_ID => &mut deployment,
+                KEY_MANAGEMENT_THRESHOLD_ID => &mut key_management,
+                TRANSFER_THRESHOLD_ID => &mut transfer,
+                _ => return Err(Error::FormattingError),
+            };
+            if threshold.replace(weight).is_some() {
+                return Err(Error::FormattingError);
             }
-            (KEY_MANAGEMENT_THRESHOLD_ID, DEPLOYMENT_THRESHOLD_ID) => {
-                action_thresholds
-                    .set_key_management_threshold(weight_1)
-                    .map_err(Error::custom)?;
-                action_thresholds
-                    .set_deployment_threshold(weight_2)
+            rem = rem3;
+        }
+        match (deployment, key_management, transfer) {
+            (Some(deployment), Some(key_management), Some(transfer)) => {
+                let action_thresholds = ActionThresholds::new(deployment, key_management, transfer)
                     .map_err(Error::custom)?;
-                Ok((action_thresholds, rem4))
+                Ok((action_thresholds, rem))
             }
             _ => Err(Error::FormattingError),
         }
@@ -881,10 +1143,11 @@ impl FromBytes for AccountActivity {
 
 impl ToBytes for Account {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let action_thresholds_size = 2 * (WEIGHT_SIZE + U8_SIZE);
+        let action_thresholds_size = ACTION_THRESHOLDS_SERIALIZED_LENGTH;
         let account_activity_size: usize = 3 * (BLOCKTIME_SER_SIZE + U8_SIZE);
-        let associated_keys_size =
-            self.associated_keys.0.len() * (PUBLIC_KEY_SIZE + WEIGHT_SIZE) + U32_SIZE;
+        let associated_keys_size = self.associated_keys.0.len()
+            * (PUBLIC_KEY_SERIALIZED_MAX_LENGTH + WEIGHT_SIZE)
+            + U32_SIZE;
         let known_urefs_size = UREF_SIZE * self.known_urefs.len() + U32_SIZE;
         let purse_id_size = UREF_SIZE;
         let serialized_account_size = KEY_SIZE // pub key
@@ -932,21 +1195,26 @@ impl FromBytes for Account {
 
 #[cfg(test)]
 mod tests {
+    use crate::bytesrepr::{deserialize, FromBytes, ToBytes, U32_SIZE};
     use crate::uref::{AccessRights, URef};
     use crate::value::account::{
         Account, AccountActivity, ActionThresholds, ActionType, AddKeyFailure, AssociatedKeys,
         BlockTime, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure,
-        Weight, KEY_SIZE, MAX_KEYS,
+        Weight, ACTION_THRESHOLDS_SERIALIZED_LENGTH, DEPLOYMENT_THRESHOLD_ID, ED25519_TAG,
+        KEY_MANAGEMENT_THRESHOLD_ID, KEY_SIZE, MAX_KEYS, PUBLIC_KEY_SERIALIZED_MAX_LENGTH,
+        PUBLIC_KEY_TAGGED_ENCODING, SECP256K1_TAG,
     };
     use alloc::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
     use alloc::vec::Vec;
+    use blake2::digest::{Input, VariableOutput};
+    use blake2::VarBlake2b;
     use core::convert::TryFrom;
     use core::iter::FromIterator;
 
     #[test]
     fn associated_keys_add() {
         let mut keys = AssociatedKeys::new([0u8; KEY_SIZE].into(), Weight::new(1));
-        let new_pk = PublicKey([1u8; KEY_SIZE]);
+        let new_pk = PublicKey::new([1u8; KEY_SIZE]);
         let new_pk_weight = Weight::new(2);
         assert!(keys.add_key(new_pk, new_pk_weight).is_ok());
         assert_eq!(keys.get(&new_pk), Some(&new_pk_weight))
@@ -954,7 +1222,8 @@ mod tests {
 
     #[test]
     fn associated_keys_add_full() {
-        let map = (0..MAX_KEYS).map(|k| (PublicKey([k as u8; KEY_SIZE]), Weight::new(k as u8)));
+        let map =
+            (0..MAX_KEYS).map(|k| (PublicKey::new([k as u8; KEY_SIZE]), Weight::new(k as u8)));
         assert_eq!(map.len(), 10);
         let mut keys = {
             let mut tmp = AssociatedKeys::empty();
@@ -962,14 +1231,37 @@ mod tests {
             tmp
         };
         assert_eq!(
-            keys.add_key(PublicKey([100u8; KEY_SIZE]), Weight::new(100)),
+            keys.add_key(PublicKey::new([100u8; KEY_SIZE]), Weight::new(100)),
             Err(AddKeyFailure::MaxKeysLimit)
         )
     }
 
+    #[test]
+    fn associated_keys_add_with_limit() {
+        let mut keys = AssociatedKeys::new([0u8; KEY_SIZE].into(), Weight::new(1));
+        assert_eq!(
+            keys.add_key_with_limit(PublicKey::new([1u8; KEY_SIZE]), Weight::new(1), 1),
+            Err(AddKeyFailure::MaxKeysLimit)
+        );
+        for k in 1..=MAX_KEYS {
+            let key = PublicKey::new([k as u8; KEY_SIZE]);
+            assert!(keys
+                .add_key_with_limit(key, Weight::new(1), MAX_KEYS + 1)
+                .is_ok());
+        }
+        assert_eq!(
+            keys.add_key_with_limit(
+                PublicKey::new([100u8; KEY_SIZE]),
+                Weight::new(1),
+                MAX_KEYS + 1
+            ),
+            Err(AddKeyFailure::MaxKeysLimit)
+        );
+    }
+
     #[test]
     fn associated_keys_add_duplicate() {
-        let pk = PublicKey([0u8; KEY_SIZE]);
+        let pk = PublicKey::new([0u8; KEY_SIZE]);
         let weight = Weight::new(1);
         let mut keys = AssociatedKeys::new(pk, weight);
         assert_eq!(
@@ -981,11 +1273,11 @@ mod tests {
 
     #[test]
     fn associated_keys_remove() {
-        let pk = PublicKey([0u8; KEY_SIZE]);
+        let pk = PublicKey::new([0u8; KEY_SIZE]);
         let weight = Weight::new(1);
         let mut keys = AssociatedKeys::new(pk, weight);
         assert!(keys.remove_key(&pk).is_ok());
-        assert!(keys.remove_key(&PublicKey([1u8; KEY_SIZE])).is_err());
+        assert!(keys.remove_key(&PublicKey::new([1u8; KEY_SIZE])).is_err());
     }
 
     #[test]
@@ -1008,7 +1300,7 @@ mod tests {
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             keys,
             // deploy: 33 (3*11)
-            ActionThresholds::new(Weight::new(33), Weight::new(48))
+            ActionThresholds::new(Weight::new(33), Weight::new(48), Weight::new(1))
                 .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
         );
@@ -1076,7 +1368,7 @@ mod tests {
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             associated_keys,
             // deploy: 33 (3*11)
-            ActionThresholds::new(Weight::new(33), Weight::new(48))
+            ActionThresholds::new(Weight::new(33), Weight::new(48), Weight::new(1))
                 .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
         );
@@ -1139,7 +1431,7 @@ mod tests {
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             associated_keys,
             // deploy: 33 (3*11)
-            ActionThresholds::new(Weight::new(11), Weight::new(33))
+            ActionThresholds::new(Weight::new(11), Weight::new(33), Weight::new(1))
                 .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
         );
@@ -1170,7 +1462,16 @@ mod tests {
     fn public_key_from_slice() {
         let bytes: Vec<u8> = (0..32).collect();
         let public_key = PublicKey::try_from(&bytes[..]).expect("should create public key");
-        assert_eq!(&bytes, &public_key.value());
+        assert_eq!(public_key.tag(), PublicKey::new([0; 32]).tag());
+        assert_eq!(&bytes[..], public_key.as_bytes());
+    }
+
+    #[test]
+    fn secp256k1_public_key_from_slice() {
+        let bytes: Vec<u8> = (0..33).collect();
+        let public_key = PublicKey::try_from(&bytes[..]).expect("should create public key");
+        assert_eq!(public_key.tag(), PublicKey::secp256k1([0; 33]).tag());
+        assert_eq!(&bytes[..], public_key.as_bytes());
     }
     #[test]
     fn public_key_from_slice_too_small() {
@@ -1181,22 +1482,179 @@ mod tests {
     #[test]
     fn public_key_from_slice_too_big() {
         let _public_key =
-            PublicKey::try_from(&[0u8; 33][..]).expect_err("should not create public key");
+            PublicKey::try_from(&[0u8; 34][..]).expect_err("should not create public key");
     }
 
     #[test]
+    fn public_keys_of_different_algorithms_should_differ() {
+        let mut secp256k1_bytes = [0u8; 33];
+        secp256k1_bytes[..32].copy_from_slice(&[1u8; 32]);
+        let ed25519_key = PublicKey::new([1u8; 32]);
+        let secp256k1_key = PublicKey::secp256k1(secp256k1_bytes);
+        assert_ne!(ed25519_key, secp256k1_key);
+        assert_ne!(
+            ed25519_key.to_account_address(),
+            secp256k1_key.to_account_address()
+        );
+    }
 
+    #[test]
+    fn ed25519_account_address_should_be_the_key() {
+        let public_key = PublicKey::new([42u8; 32]);
+        assert_eq!(public_key.to_account_address(), [42u8; 32]);
+    }
+
+    #[test]
+    fn secp256k1_account_address_should_hash_tag_and_key() {
+        let public_key = PublicKey::secp256k1([42u8; 33]);
+        let mut expected = [0u8; 32];
+        let mut hasher = VarBlake2b::new(32).unwrap();
+        hasher.input(&[SECP256K1_TAG]);
+        hasher.input(&[42u8; 33][..]);
+        hasher.variable_result(|hash| expected.clone_from_slice(hash));
+        assert_eq!(public_key.to_account_address(), expected);
+    }
+
+    #[test]
+    fn public_key_serialized_length_should_match_prefix() {
+        for public_key in &[PublicKey::new([1u8; 32]), PublicKey::secp256k1([2u8; 33])] {
+            let bytes = public_key.to_bytes().expect("should serialize");
+            let prefix: u32 = deserialize(&bytes[..U32_SIZE]).expect("should deserialize");
+            assert_eq!(
+                Some(bytes.len()),
+                PublicKey::serialized_length(prefix, || bytes.get(U32_SIZE).cloned())
+            );
+            assert!(bytes.len() <= PUBLIC_KEY_SERIALIZED_MAX_LENGTH);
+        }
+    }
+
+    #[test]
+    fn secp256k1_public_key_should_serialize_tagged() {
+        let bytes = PublicKey::secp256k1([2u8; 33])
+            .to_bytes()
+            .expect("should serialize");
+        assert_eq!(
+            &bytes[..U32_SIZE],
+            &PUBLIC_KEY_TAGGED_ENCODING.to_bytes().unwrap()[..]
+        );
+        assert_eq!(bytes[U32_SIZE], SECP256K1_TAG);
+    }
+
+    #[test]
+    fn tagged_ed25519_public_key_should_deserialize() {
+        let mut bytes = PUBLIC_KEY_TAGGED_ENCODING.to_bytes().unwrap();
+        bytes.push(ED25519_TAG);
+        bytes.extend_from_slice(&[7u8; 32]);
+        let public_key: PublicKey = deserialize(&bytes).expect("should deserialize");
+        assert_eq!(public_key, PublicKey::new([7u8; 32]));
+    }
+
+    #[test]
+    fn public_key_with_unknown_tag_should_not_deserialize() {
+        let mut bytes = PUBLIC_KEY_TAGGED_ENCODING.to_bytes().unwrap();
+        bytes.push(2);
+        bytes.extend_from_slice(&[7u8; 33]);
+        deserialize::<PublicKey>(&bytes).expect_err("should not deserialize");
+    }
+
+    #[test]
+    fn ed25519_public_key_should_serialize_like_address() {
+        let address = [7u8; 32];
+        assert_eq!(
+            PublicKey::new(address)
+                .to_bytes()
+                .expect("should serialize"),
+            address.to_bytes().expect("should serialize")
+        );
+    }
+
+    #[test]
     fn should_create_new_action_thresholds() {
-        let action_thresholds = ActionThresholds::new(Weight::new(1), Weight::new(42)).unwrap();
+        let action_thresholds =
+            ActionThresholds::new(Weight::new(1), Weight::new(42), Weight::new(7)).unwrap();
         assert_eq!(*action_thresholds.deployment(), Weight::new(1));
         assert_eq!(*action_thresholds.key_management(), Weight::new(42));
+        assert_eq!(*action_thresholds.transfer(), Weight::new(7));
+    }
+
+    #[test]
+    fn should_allow_transfer_threshold_independent_of_other_thresholds() {
+        let mut action_thresholds =
+            ActionThresholds::new(Weight::new(5), Weight::new(10), Weight::new(1)).unwrap();
+        action_thresholds
+            .set_threshold(ActionType::Transfer, Weight::new(20))
+            .expect("should set transfer threshold above key management threshold");
+        assert_eq!(
+            *action_thresholds.get_threshold(ActionType::Transfer),
+            Weight::new(20)
+        );
+        action_thresholds
+            .set_threshold(ActionType::Transfer, Weight::new(2))
+            .expect("should set transfer threshold below deployment threshold");
+        assert_eq!(
+            *action_thresholds.get_threshold(ActionType::Transfer),
+            Weight::new(2)
+        );
+    }
+
+    #[test]
+    fn action_thresholds_serialization_roundtrip() {
+        let action_thresholds =
+            ActionThresholds::new(Weight::new(3), Weight::new(42), Weight::new(7)).unwrap();
+        let bytes = action_thresholds.to_bytes().expect("should serialize");
+        assert_eq!(bytes.len(), ACTION_THRESHOLDS_SERIALIZED_LENGTH);
+        let (deserialized, rem): (ActionThresholds, &[u8]) =
+            FromBytes::from_bytes(&bytes).expect("should deserialize");
+        assert!(rem.is_empty());
+        assert_eq!(deserialized, action_thresholds);
+    }
+
+    #[test]
+    fn action_thresholds_with_duplicated_id_should_fail_to_deserialize() {
+        let bytes = vec![
+            DEPLOYMENT_THRESHOLD_ID,
+            1,
+            KEY_MANAGEMENT_THRESHOLD_ID,
+            1,
+            DEPLOYMENT_THRESHOLD_ID,
+            1,
+        ];
+        let result: Result<(ActionThresholds, &[u8]), _> = FromBytes::from_bytes(&bytes);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_check_transfer_threshold() {
+        let key_1 = PublicKey::new([1u8; 32]);
+        let key_2 = PublicKey::new([2u8; 32]);
+        let associated_keys = {
+            let mut res = AssociatedKeys::new(key_1, Weight::new(1));
+            res.add_key(key_2, Weight::new(1))
+                .expect("should add key 2");
+            res
+        };
+        let account = Account::new(
+            [0u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+            associated_keys,
+            ActionThresholds::new(Weight::new(1), Weight::new(1), Weight::new(2))
+                .expect("should create thresholds"),
+            AccountActivity::new(BlockTime(0), BlockTime(0)),
+        );
+
+        let one_key: BTreeSet<PublicKey> = vec![key_1].into_iter().collect();
+        let both_keys: BTreeSet<PublicKey> = vec![key_1, key_2].into_iter().collect();
+        assert!(account.can_deploy_with(&one_key));
+        assert!(!account.can_transfer_with(&one_key));
+        assert!(account.can_transfer_with(&both_keys));
     }
 
     #[test]
     #[should_panic]
     fn should_not_create_action_thresholds_with_invalid_deployment_threshold() {
         // deployment cant be greater than key management
-        ActionThresholds::new(Weight::new(5), Weight::new(1)).unwrap();
+        ActionThresholds::new(Weight::new(5), Weight::new(1), Weight::new(1)).unwrap();
     }
 
     #[test]
@@ -1221,7 +1679,7 @@ mod tests {
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             associated_keys,
             // deploy: 33 (3*11)
-            ActionThresholds::new(Weight::new(33), Weight::new(48))
+            ActionThresholds::new(Weight::new(33), Weight::new(48), Weight::new(1))
                 .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
         );
@@ -1262,8 +1720,12 @@ mod tests {
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             associated_keys,
             // deploy: 33 (3*11)
-            ActionThresholds::new(Weight::new(1 + 2 + 3 + 4), Weight::new(1 + 2 + 3 + 4 + 5))
-                .expect("should create thresholds"),
+            ActionThresholds::new(
+                Weight::new(1 + 2 + 3 + 4),
+                Weight::new(1 + 2 + 3 + 4 + 5),
+                Weight::new(1),
+            )
+            .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
         );
 
@@ -1296,8 +1758,12 @@ mod tests {
             PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
             associated_keys,
             // deploy: 33 (3*11)
-            ActionThresholds::new(Weight::new(1 + 2 + 3 + 4), Weight::new(1 + 2 + 3 + 4 + 1))
-                .expect("should create thresholds"),
+            ActionThresholds::new(
+                Weight::new(1 + 2 + 3 + 4),
+                Weight::new(1 + 2 + 3 + 4 + 1),
+                Weight::new(1),
+            )
+            .expect("should create thresholds"),
             AccountActivity::new(BlockTime(0), BlockTime(0)),
         );
 
@@ -0,0 +1,215 @@
+use crate::bytesrepr::{Error, FromBytes, ToBytes};
+use crate::key::Key;
+use crate::uref::URef;
+use crate::value::{U128, U256, U512};
+use alloc::boxed::Box;
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const BOOL_TAG: u8 = 0;
+const I32_TAG: u8 = 1;
+const U8_TAG: u8 = 2;
+const U32_TAG: u8 = 3;
+const U64_TAG: u8 = 4;
+const U128_TAG: u8 = 5;
+const U256_TAG: u8 = 6;
+const U512_TAG: u8 = 7;
+const UNIT_TAG: u8 = 8;
+const STRING_TAG: u8 = 9;
+const KEY_TAG: u8 = 10;
+const UREF_TAG: u8 = 11;
+const OPTION_TAG: u8 = 12;
+const LIST_TAG: u8 = 13;
+const RESULT_TAG: u8 = 14;
+const MAP_TAG: u8 = 15;
+const TUPLE2_TAG: u8 = 16;
+
+/// The type of a [`CLValue`](super::CLValue), serialized alongside its bytes so that the value can
+/// be checked and decoded without knowing its type up front.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum CLType {
+    Bool,
+    I32,
+    U8,
+    U32,
+    U64,
+    U128,
+    U256,
+    U512,
+    Unit,
+    String,
+    Key,
+    URef,
+    Option(Box<CLType>),
+    List(Box<CLType>),
+    Result {
+        ok: Box<CLType>,
+        err: Box<CLType>,
+    },
+    Map {
+        key: Box<CLType>,
+        value: Box<CLType>,
+    },
+    Tuple2(Box<CLType>, Box<CLType>),
+}
+
+impl ToBytes for CLType {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = Vec::new();
+        match self {
+            CLType::Bool => result.push(BOOL_TAG),
+            CLType::I32 => result.push(I32_TAG),
+            CLType::U8 => result.push(U8_TAG),
+            CLType::U32 => result.push(U32_TAG),
+            CLType::U64 => result.push(U64_TAG),
+            CLType::U128 => result.push(U128_TAG),
+            CLType::U256 => result.push(U256_TAG),
+            CLType::U512 => result.push(U512_TAG),
+            CLType::Unit => result.push(UNIT_TAG),
+            CLType::String => result.push(STRING_TAG),
+            CLType::Key => result.push(KEY_TAG),
+            CLType::URef => result.push(UREF_TAG),
+            CLType::Option(inner) => {
+                result.push(OPTION_TAG);
+                result.append(&mut inner.to_bytes()?);
+            }
+            CLType::List(inner) => {
+                result.push(LIST_TAG);
+                result.append(&mut inner.to_bytes()?);
+            }
+            CLType::Result { ok, err } => {
+                result.push(RESULT_TAG);
+                result.append(&mut ok.to_bytes()?);
+                result.append(&mut err.to_bytes()?);
+            }
+            CLType::Map { key, value } => {
+                result.push(MAP_TAG);
+                result.append(&mut key.to_bytes()?);
+                result.append(&mut value.to_bytes()?);
+            }
+            CLType::Tuple2(t1, t2) => {
+                result.push(TUPLE2_TAG);
+                result.append(&mut t1.to_bytes()?);
+                result.append(&mut t2.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl FromBytes for CLType {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            BOOL_TAG => Ok((CLType::Bool, rem)),
+            I32_TAG => Ok((CLType::I32, rem)),
+            U8_TAG => Ok((CLType::U8, rem)),
+            U32_TAG => Ok((CLType::U32, rem)),
+            U64_TAG => Ok((CLType::U64, rem)),
+            U128_TAG => Ok((CLType::U128, rem)),
+            U256_TAG => Ok((CLType::U256, rem)),
+            U512_TAG => Ok((CLType::U512, rem)),
+            UNIT_TAG => Ok((CLType::Unit, rem)),
+            STRING_TAG => Ok((CLType::String, rem)),
+            KEY_TAG => Ok((CLType::Key, rem)),
+            UREF_TAG => Ok((CLType::URef, rem)),
+            OPTION_TAG => {
+                let (inner, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((CLType::Option(Box::new(inner)), rem))
+            }
+            LIST_TAG => {
+                let (inner, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((CLType::List(Box::new(inner)), rem))
+            }
+            RESULT_TAG => {
+                let (ok, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+                let (err, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+                let cl_type = CLType::Result {
+                    ok: Box::new(ok),
+                    err: Box::new(err),
+                };
+                Ok((cl_type, rem))
+            }
+            MAP_TAG => {
+                let (key, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+                let (value, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+                let cl_type = CLType::Map {
+                    key: Box::new(key),
+                    value: Box::new(value),
+                };
+                Ok((cl_type, rem))
+            }
+            TUPLE2_TAG => {
+                let (t1, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+                let (t2, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((CLType::Tuple2(Box::new(t1), Box::new(t2)), rem))
+            }
+            _ => Err(Error::FormattingError),
+        }
+    }
+}
+
+/// A type which can be described as a [`CLType`].
+pub trait CLTyped {
+    fn cl_type() -> CLType;
+}
+
+macro_rules! cl_typed_impl {
+    ($type:ty, $cl_type:ident) => {
+        impl CLTyped for $type {
+            fn cl_type() -> CLType {
+                CLType::$cl_type
+            }
+        }
+    };
+}
+
+cl_typed_impl!(bool, Bool);
+cl_typed_impl!(i32, I32);
+cl_typed_impl!(u8, U8);
+cl_typed_impl!(u32, U32);
+cl_typed_impl!(u64, U64);
+cl_typed_impl!(U128, U128);
+cl_typed_impl!(U256, U256);
+cl_typed_impl!(U512, U512);
+cl_typed_impl!((), Unit);
+cl_typed_impl!(String, String);
+cl_typed_impl!(Key, Key);
+cl_typed_impl!(URef, URef);
+
+impl<T: CLTyped> CLTyped for Option<T> {
+    fn cl_type() -> CLType {
+        CLType::Option(Box::new(T::cl_type()))
+    }
+}
+
+impl<T: CLTyped> CLTyped for Vec<T> {
+    fn cl_type() -> CLType {
+        CLType::List(Box::new(T::cl_type()))
+    }
+}
+
+impl<T: CLTyped, E: CLTyped> CLTyped for Result<T, E> {
+    fn cl_type() -> CLType {
+        CLType::Result {
+            ok: Box::new(T::cl_type()),
+            err: Box::new(E::cl_type()),
+        }
+    }
+}
+
+impl<K: CLTyped, V: CLTyped> CLTyped for BTreeMap<K, V> {
+    fn cl_type() -> CLType {
+        CLType::Map {
+            key: Box::new(K::cl_type()),
+            value: Box::new(V::cl_type()),
+        }
+    }
+}
+
+impl<T1: CLTyped, T2: CLTyped> CLTyped for (T1, T2) {
+    fn cl_type() -> CLType {
+        CLType::Tuple2(Box::new(T1::cl_type()), Box::new(T2::cl_type()))
+    }
+}
@@ -0,0 +1,254 @@
+use crate::bytesrepr::{self, FromBytes, ToBytes};
+use crate::key::Key;
+use crate::uref::URef;
+use crate::value::cl_type::{CLType, CLTyped};
+use crate::value::{Value, U128, U256, U512};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CLValueError {
+    Serialization(bytesrepr::Error),
+    /// The value holds a different type than the one requested; carries the held type.
+    Type(CLType),
+    /// The `Value` variant has no `CLType` counterpart; carries its type string.
+    NotCLTyped(String),
+}
+
+impl From<bytesrepr::Error> for CLValueError {
+    fn from(error: bytesrepr::Error) -> Self {
+        CLValueError::Serialization(error)
+    }
+}
+
+/// A self-describing value: the serialized bytes of a value together with its [`CLType`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CLValue {
+    cl_type: CLType,
+    bytes: Vec<u8>,
+}
+
+impl CLValue {
+    pub fn from_t<T: CLTyped + ToBytes>(t: &T) -> Result<CLValue, CLValueError> {
+        Ok(CLValue {
+            cl_type: T::cl_type(),
+            bytes: t.to_bytes()?,
+        })
+    }
+
+    /// Creates a [`CLValue`] from already serialized bytes, which are not checked against
+    /// `cl_type`.
+    pub fn from_parts(cl_type: CLType, bytes: Vec<u8>) -> CLValue {
+        CLValue { cl_type, bytes }
+    }
+
+    /// Returns the held value if it is of type `T`.
+    pub fn into_t<T: CLTyped + FromBytes>(self) -> Result<T, CLValueError> {
+        if self.cl_type != T::cl_type() {
+            return Err(CLValueError::Type(self.cl_type));
+        }
+        Ok(bytesrepr::deserialize(&self.bytes)?)
+    }
+
+    pub fn cl_type(&self) -> &CLType {
+        &self.cl_type
+    }
+
+    pub fn inner_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns all the keys and unforgeable references held in the value, so that they can be
+    /// validated like those held in any other `Value`.
+    pub fn keys(&self) -> Result<Vec<Key>, bytesrepr::Error> {
+        let mut keys = Vec::new();
+        let rem = collect_keys(&self.cl_type, &self.bytes, &mut keys)?;
+        if !rem.is_empty() {
+            return Err(bytesrepr::Error::LeftOverBytes);
+        }
+        Ok(keys)
+    }
+}
+
+fn skip<T: FromBytes>(bytes: &[u8]) -> Result<&[u8], bytesrepr::Error> {
+    let (_, rem): (T, &[u8]) = FromBytes::from_bytes(bytes)?;
+    Ok(rem)
+}
+
+/// Walks `bytes` as a value of type `cl_type`, pushing every key it holds onto `keys`. Returns the
+/// remaining bytes.
+fn collect_keys<'a>(
+    cl_type: &CLType,
+    bytes: &'a [u8],
+    keys: &mut Vec<Key>,
+) -> Result<&'a [u8], bytesrepr::Error> {
+    match cl_type {
+        CLType::Bool => skip::<bool>(bytes),
+        CLType::I32 => skip::<i32>(bytes),
+        CLType::U8 => skip::<u8>(bytes),
+        CLType::U32 => skip::<u32>(bytes),
+        CLType::U64 => skip::<u64>(bytes),
+        CLType::U128 => skip::<U128>(bytes),
+        CLType::U256 => skip::<U256>(bytes),
+        CLType::U512 => skip::<U512>(bytes),
+        CLType::Unit => Ok(bytes),
+        CLType::String => skip::<String>(bytes),
+        CLType::Key => {
+            let (key, rem): (Key, &[u8]) = FromBytes::from_bytes(bytes)?;
+            keys.push(key);
+            Ok(rem)
+        }
+        CLType::URef => {
+            let (uref, rem): (URef, &[u8]) = FromBytes::from_bytes(bytes)?;
+            keys.push(Key::URef(uref));
+            Ok(rem)
+        }
+        CLType::Option(inner) => {
+            let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+            match tag {
+                0 => Ok(rem),
+                1 => collect_keys(inner, rem, keys),
+                _ => Err(bytesrepr::Error::FormattingError),
+            }
+        }
+        CLType::List(inner) => {
+            let (count, mut rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+            for _ in 0..count {
+                rem = collect_keys(inner, rem, keys)?;
+            }
+            Ok(rem)
+        }
+        CLType::Result { ok, err } => {
+            let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+            match tag {
+                0 => collect_keys(err, rem, keys),
+                1 => collect_keys(ok, rem, keys),
+                _ => Err(bytesrepr::Error::FormattingError),
+            }
+        }
+        CLType::Map { key, value } => {
+            let (count, mut rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+            for _ in 0..count {
+                rem = collect_keys(key, rem, keys)?;
+                rem = collect_keys(value, rem, keys)?;
+            }
+            Ok(rem)
+        }
+        CLType::Tuple2(t1, t2) => {
+            let rem = collect_keys(t1, bytes, keys)?;
+            collect_keys(t2, rem, keys)
+        }
+    }
+}
+
+impl ToBytes for CLValue {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = self.cl_type.to_bytes()?;
+        result.append(&mut self.bytes.to_bytes()?);
+        Ok(result)
+    }
+}
+
+impl FromBytes for CLValue {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (cl_type, rem1): (CLType, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (bytes, rem2): (Vec<u8>, &[u8]) = FromBytes::from_bytes(rem1)?;
+        Ok((CLValue { cl_type, bytes }, rem2))
+    }
+}
+
+/// Converts the `Value` variants which have a `CLType` counterpart, so that values stored before
+/// `CLValue` existed can be read as one.
+impl TryFrom<Value> for CLValue {
+    type Error = CLValueError;
+
+    fn try_from(value: Value) -> Result<CLValue, CLValueError> {
+        match value {
+            Value::Int32(i) => CLValue::from_t(&i),
+            Value::UInt64(u) => CLValue::from_t(&u),
+            Value::UInt128(u) => CLValue::from_t(&u),
+            Value::UInt256(u) => CLValue::from_t(&u),
+            Value::UInt512(u) => CLValue::from_t(&u),
+            Value::ByteArray(arr) => Ok(CLValue {
+                cl_type: Vec::<u8>::cl_type(),
+                bytes: arr.to_bytes()?,
+            }),
+            Value::ListInt32(arr) => Ok(CLValue {
+                cl_type: Vec::<i32>::cl_type(),
+                bytes: arr.to_bytes()?,
+            }),
+            Value::String(s) => CLValue::from_t(&s),
+            Value::ListString(arr) => Ok(CLValue {
+                cl_type: Vec::<String>::cl_type(),
+                bytes: arr.to_bytes()?,
+            }),
+            Value::NamedKey(name, key) => CLValue::from_t(&(name, key)),
+            Value::Key(key) => CLValue::from_t(&key),
+            Value::Unit => CLValue::from_t(&()),
+            Value::CLValue(cl_value) => Ok(cl_value),
+            Value::Account(_) | Value::Contract(_) | Value::ContractPackage(_) => {
+                Err(CLValueError::NotCLTyped(value.type_string()))
+            }
+        }
+    }
+}
+
+impl From<CLValue> for Value {
+    fn from(cl_value: CLValue) -> Self {
+        Value::CLValue(cl_value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CLValue, CLValueError};
+    use crate::key::Key;
+    use crate::test_utils::test_serialization_roundtrip;
+    use crate::uref::{AccessRights, URef};
+    use crate::value::cl_type::CLType;
+    use crate::value::{Value, U512};
+    use alloc::boxed::Box;
+    use alloc::collections::btree_map::BTreeMap;
+    use alloc::string::String;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn should_check_type_when_reading_value() {
+        let cl_value = CLValue::from_t(&Some(10u32)).expect("should create CLValue");
+        assert!(test_serialization_roundtrip(&cl_value));
+
+        assert_eq!(cl_value.clone().into_t(), Ok(Some(10u32)));
+        assert_eq!(
+            cl_value.into_t::<Option<u64>>(),
+            Err(CLValueError::Type(CLType::Option(Box::new(CLType::U32))))
+        );
+    }
+
+    #[test]
+    fn should_collect_nested_keys() {
+        let uref = URef::new([1; 32], AccessRights::READ);
+        let mut map = BTreeMap::new();
+        map.insert(String::from("hash"), Some(Key::Hash([2; 32])));
+        map.insert(String::from("none"), None);
+        let cl_value = CLValue::from_t(&(uref, map)).expect("should create CLValue");
+
+        assert_eq!(
+            cl_value.keys(),
+            Ok(vec![Key::URef(uref), Key::Hash([2; 32])])
+        );
+    }
+
+    #[test]
+    fn should_convert_legacy_values() {
+        let cl_value = CLValue::try_from(Value::UInt512(U512::from(7))).expect("should convert");
+        assert_eq!(cl_value.into_t(), Ok(U512::from(7)));
+
+        let cl_value = CLValue::try_from(Value::ByteArray(vec![1, 2, 3])).expect("should convert");
+        assert_eq!(cl_value.into_t(), Ok(vec![1u8, 2, 3]));
+
+        let named_key = (String::from("name"), Key::Hash([3; 32]));
+        let cl_value = CLValue::try_from(Value::from(named_key.clone())).expect("should convert");
+        assert_eq!(cl_value.into_t(), Ok(named_key));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::bytesrepr::{Error, FromBytes, ToBytes, U32_SIZE};
+use crate::key::Key;
+use alloc::collections::btree_map::BTreeMap;
+use alloc::collections::btree_set::BTreeSet;
+use alloc::vec::Vec;
+
+/// The first version number of a contract package.
+pub const FIRST_VERSION: u32 = 1;
+
+/// A package of versioned contracts, stored under an unforgeable reference.
+///
+/// Each version points to an immutable contract stored under its hash. New versions can only be
+/// added by the holders of a writeable reference to the package, so callers can pin a version
+/// while the package owner ships upgrades without migrating references.
+///
+/// The owner can also disable a version, e.g. after a bug is found in it, so that calls to it
+/// through the package fail until it is enabled again.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ContractPackage {
+    versions: BTreeMap<u32, Key>,
+    disabled_versions: BTreeSet<u32>,
+}
+
+impl ContractPackage {
+    pub fn new() -> Self {
+        ContractPackage {
+            versions: BTreeMap::new(),
+            disabled_versions: BTreeSet::new(),
+        }
+    }
+
+    pub fn from_parts(versions: BTreeMap<u32, Key>, disabled_versions: BTreeSet<u32>) -> Self {
+        ContractPackage {
+            versions,
+            disabled_versions,
+        }
+    }
+
+    pub fn versions(&self) -> &BTreeMap<u32, Key> {
+        &self.versions
+    }
+
+    pub fn disabled_versions(&self) -> &BTreeSet<u32> {
+        &self.disabled_versions
+    }
+
+    /// Returns the key of the contract stored under `version`, if any.
+    pub fn get_version(&self, version: u32) -> Option<&Key> {
+        self.versions.get(&version)
+    }
+
+    /// Returns the most recent version number, or `None` if the package has no versions yet.
+    pub fn latest_version(&self) -> Option<u32> {
+        self.versions.keys().next_back().cloned()
+    }
+
+    /// Adds `contract` under the version following the latest one and returns the new version
+    /// number.
+    pub fn add_version(&mut self, contract: Key) -> u32 {
+        let version = self
+            .latest_version()
+            .map_or(FIRST_VERSION, |latest| latest + 1);
+        self.versions.insert(version, contract);
+        version
+    }
+
+    pub fn is_version_disabled(&self, version: u32) -> bool {
+        self.disabled_versions.contains(&version)
+    }
+
+    /// Disables `version`. Returns `false` if the package has no such version.
+    pub fn disable_version(&mut self, version: u32) -> bool {
+        if !self.versions.contains_key(&version) {
+            return false;
+        }
+        self.disabled_versions.insert(version);
+        true
+    }
+
+    /// Enables `version` again. Returns `false` if the package has no such version.
+    pub fn enable_version(&mut self, version: u32) -> bool {
+        if !self.versions.contains_key(&version) {
+            return false;
+        }
+        self.disabled_versions.remove(&version);
+        true
+    }
+}
+
+impl ToBytes for ContractPackage {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = self.versions.to_bytes()?;
+        result.try_reserve_exact(U32_SIZE * (self.disabled_versions.len() + 1))?;
+        result.append(&mut (self.disabled_versions.len() as u32).to_bytes()?);
+        for version in &self.disabled_versions {
+            result.append(&mut version.to_bytes()?);
+        }
+        Ok(result)
+    }
+}
+
+impl FromBytes for ContractPackage {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (versions, rem1): (BTreeMap<u32, Key>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (disabled_count, mut rem): (u32, &[u8]) = FromBytes::from_bytes(rem1)?;
+        let mut disabled_versions = BTreeSet::new();
+        for _ in 0..disabled_count {
+            let (version, rem2): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+            disabled_versions.insert(version);
+            rem = rem2;
+        }
+        Ok((
+            ContractPackage {
+                versions,
+                disabled_versions,
+            },
+            rem,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContractPackage, FIRST_VERSION};
+    use crate::key::Key;
+
+    #[test]
+    fn should_add_versions_in_order() {
+        let mut package = ContractPackage::new();
+        assert_eq!(package.latest_version(), None);
+
+        assert_eq!(package.add_version(Key::Hash([1; 32])), FIRST_VERSION);
+        assert_eq!(package.add_version(Key::Hash([2; 32])), FIRST_VERSION + 1);
+
+        assert_eq!(package.latest_version(), Some(FIRST_VERSION + 1));
+        assert_eq!(
+            package.get_version(FIRST_VERSION),
+            Some(&Key::Hash([1; 32]))
+        );
+        assert_eq!(package.get_version(FIRST_VERSION + 2), None);
+    }
+
+    #[test]
+    fn should_disable_and_enable_existing_versions() {
+        let mut package = ContractPackage::new();
+        let version = package.add_version(Key::Hash([1; 32]));
+
+        assert!(package.disable_version(version));
+        assert!(package.is_version_disabled(version));
+        assert!(package.enable_version(version));
+        assert!(!package.is_version_disabled(version));
+
+        assert!(!package.disable_version(version + 1));
+        assert!(!package.is_version_disabled(version + 1));
+    }
+}
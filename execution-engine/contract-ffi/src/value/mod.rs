@@ -1,5 +1,8 @@
 pub mod account;
+pub mod cl_type;
+pub mod cl_value;
 pub mod contract;
+pub mod contract_package;
 pub mod uint;
 
 use crate::bytesrepr::{
@@ -14,7 +17,10 @@ use core::iter;
 use core::mem::size_of;
 
 pub use self::account::Account;
+pub use self::cl_type::{CLType, CLTyped};
+pub use self::cl_value::{CLValue, CLValueError};
 pub use self::contract::Contract;
+pub use self::contract_package::ContractPackage;
 pub use self::uint::{U128, U256, U512};
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -33,6 +39,8 @@ pub enum Value {
     Account(account::Account),
     Contract(contract::Contract),
     Unit,
+    ContractPackage(contract_package::ContractPackage),
+    CLValue(cl_value::CLValue),
 }
 
 const INT32_ID: u8 = 0;
@@ -49,6 +57,8 @@ const U512_ID: u8 = 10;
 const KEY_ID: u8 = 11;
 const UNIT_ID: u8 = 12;
 const U64_ID: u8 = 13;
+const CONTRACT_PACKAGE_ID: u8 = 14;
+const CL_VALUE_ID: u8 = 15;
 
 use self::Value::*;
 
@@ -157,6 +167,12 @@ impl ToBytes for Value {
                 result.append(&mut num.to_bytes()?);
                 Ok(result)
             }
+            ContractPackage(package) => Ok(iter::once(CONTRACT_PACKAGE_ID)
+                .chain(package.to_bytes()?)
+                .collect()),
+            CLValue(cl_value) => Ok(iter::once(CL_VALUE_ID)
+                .chain(cl_value.to_bytes()?)
+                .collect()),
         }
     }
 }
@@ -218,6 +234,15 @@ impl FromBytes for Value {
                 let (num, rem): (u64, &[u8]) = FromBytes::from_bytes(rest)?;
                 Ok((UInt64(num), rem))
             }
+            CONTRACT_PACKAGE_ID => {
+                let (package, rem): (contract_package::ContractPackage, &[u8]) =
+                    FromBytes::from_bytes(rest)?;
+                Ok((ContractPackage(package), rem))
+            }
+            CL_VALUE_ID => {
+                let (cl_value, rem): (cl_value::CLValue, &[u8]) = FromBytes::from_bytes(rest)?;
+                Ok((CLValue(cl_value), rem))
+            }
             _ => Err(Error::FormattingError),
         }
     }
@@ -240,11 +265,35 @@ impl Value {
             ListString(_) => String::from("Value::List[String]"),
             Unit => String::from("Value::Unit"),
             UInt64(_) => String::from("Value::UInt64"),
+            ContractPackage(_) => String::from("Value::ContractPackage"),
+            CLValue(_) => String::from("Value::CLValue"),
         }
     }
 }
 
 macro_rules! from_try_from_impl {
+    ($type:ty, $variant:ident, cl_typed) => {
+        impl From<$type> for Value {
+            fn from(x: $type) -> Self {
+                Value::$variant(x)
+            }
+        }
+
+        /// Also accepts a [`CLValue`](cl_value::CLValue) holding the same type.
+        impl TryFrom<Value> for $type {
+            type Error = String;
+
+            fn try_from(v: Value) -> Result<$type, String> {
+                match v {
+                    Value::$variant(x) => Ok(x),
+                    Value::CLValue(cl_value) => cl_value
+                        .into_t()
+                        .map_err(|_| String::from("Value::CLValue")),
+                    _ => Err(v.type_string()),
+                }
+            }
+        }
+    };
     ($type:ty, $variant:ident) => {
         impl From<$type> for Value {
             fn from(x: $type) -> Self {
@@ -266,18 +315,48 @@ macro_rules! from_try_from_impl {
     };
 }
 
-from_try_from_impl!(i32, Int32);
-from_try_from_impl!(u64, UInt64);
-from_try_from_impl!(U128, UInt128);
-from_try_from_impl!(U256, UInt256);
-from_try_from_impl!(U512, UInt512);
-from_try_from_impl!(Vec<u8>, ByteArray);
-from_try_from_impl!(Vec<i32>, ListInt32);
-from_try_from_impl!(Vec<String>, ListString);
-from_try_from_impl!(String, String);
-from_try_from_impl!(key::Key, Key);
+from_try_from_impl!(i32, Int32, cl_typed);
+from_try_from_impl!(u64, UInt64, cl_typed);
+from_try_from_impl!(U128, UInt128, cl_typed);
+from_try_from_impl!(U256, UInt256, cl_typed);
+from_try_from_impl!(U512, UInt512, cl_typed);
+from_try_from_impl!(Vec<u8>, ByteArray, cl_typed);
+from_try_from_impl!(Vec<i32>, ListInt32, cl_typed);
+from_try_from_impl!(Vec<String>, ListString, cl_typed);
+from_try_from_impl!(String, String, cl_typed);
+from_try_from_impl!(key::Key, Key, cl_typed);
 from_try_from_impl!(account::Account, Account);
 from_try_from_impl!(contract::Contract, Contract);
+from_try_from_impl!(contract_package::ContractPackage, ContractPackage);
+
+macro_rules! cl_value_from_try_from_impl {
+    ($type:ty) => {
+        impl From<$type> for Value {
+            fn from(x: $type) -> Self {
+                // Serializing a fixed-size primitive can't fail
+                Value::CLValue(cl_value::CLValue::from_t(&x).unwrap())
+            }
+        }
+
+        impl TryFrom<Value> for $type {
+            type Error = String;
+
+            fn try_from(v: Value) -> Result<$type, String> {
+                if let Value::CLValue(cl_value) = v {
+                    cl_value
+                        .into_t()
+                        .map_err(|_| String::from("Value::CLValue"))
+                } else {
+                    Err(v.type_string())
+                }
+            }
+        }
+    };
+}
+
+cl_value_from_try_from_impl!(bool);
+cl_value_from_try_from_impl!(u8);
+cl_value_from_try_from_impl!(u32);
 
 impl From<URef> for Value {
     fn from(uref: URef) -> Self {
@@ -295,10 +374,10 @@ impl TryFrom<Value> for (String, key::Key) {
     type Error = ();
 
     fn try_from(v: Value) -> Result<(String, key::Key), ()> {
-        if let Value::NamedKey(name, key) = v {
-            Ok((name, key))
-        } else {
-            Err(())
+        match v {
+            Value::NamedKey(name, key) => Ok((name, key)),
+            Value::CLValue(cl_value) => cl_value.into_t().map_err(|_| ()),
+            _ => Err(()),
         }
     }
 }
@@ -313,10 +392,10 @@ impl TryFrom<Value> for () {
     type Error = ();
 
     fn try_from(v: Value) -> Result<(), ()> {
-        if let Value::Unit = v {
-            Ok(())
-        } else {
-            Err(())
+        match v {
+            Value::Unit => Ok(()),
+            Value::CLValue(cl_value) => cl_value.into_t().map_err(|_| ()),
+            _ => Err(()),
         }
     }
 }
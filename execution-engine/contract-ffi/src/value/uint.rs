@@ -1,6 +1,9 @@
 use crate::bytesrepr::{self, Error, FromBytes, ToBytes};
 use alloc::vec::Vec;
-use num_traits::{Bounded, Num, One, Unsigned, WrappingAdd, WrappingSub, Zero};
+use num_traits::{
+    Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Num, One, Saturating, Unsigned,
+    WrappingAdd, WrappingSub, Zero,
+};
 
 // Clippy generates a ton of warnings/errors for the code the macro generates.
 // As of uint v0.8.1 and using nightly-2019-08-25, this also generates the
@@ -119,6 +122,42 @@ macro_rules! ser_and_num_impls {
                 self.overflowing_sub(*other).0
             }
         }
+
+        // The checked and saturating traits forward to the inherent methods generated by
+        // `construct_uint!`, so that code generic over numeric types can use them too.
+        impl CheckedAdd for $type {
+            fn checked_add(&self, other: &$type) -> Option<$type> {
+                $type::checked_add(*self, *other)
+            }
+        }
+
+        impl CheckedSub for $type {
+            fn checked_sub(&self, other: &$type) -> Option<$type> {
+                $type::checked_sub(*self, *other)
+            }
+        }
+
+        impl CheckedMul for $type {
+            fn checked_mul(&self, other: &$type) -> Option<$type> {
+                $type::checked_mul(*self, *other)
+            }
+        }
+
+        impl CheckedDiv for $type {
+            fn checked_div(&self, other: &$type) -> Option<$type> {
+                $type::checked_div(*self, *other)
+            }
+        }
+
+        impl Saturating for $type {
+            fn saturating_add(self, other: $type) -> $type {
+                $type::saturating_add(self, other)
+            }
+
+            fn saturating_sub(self, other: $type) -> $type {
+                $type::saturating_sub(self, other)
+            }
+        }
     };
 }
 
@@ -158,3 +197,35 @@ fn wrapping_test_u128() {
     let value = min.wrapping_sub(&1.into());
     assert_eq!(value, U128::max_value());
 }
+
+#[test]
+fn checked_and_saturating_test_u512() {
+    let max = U512::max_value();
+    assert_eq!(CheckedAdd::checked_add(&max, &1.into()), None);
+    assert_eq!(CheckedSub::checked_sub(&U512::zero(), &1.into()), None);
+    assert_eq!(
+        CheckedSub::checked_sub(&U512::from(3), &1.into()),
+        Some(2.into())
+    );
+    assert_eq!(CheckedMul::checked_mul(&max, &2.into()), None);
+    assert_eq!(CheckedDiv::checked_div(&U512::one(), &U512::zero()), None);
+    assert_eq!(Saturating::saturating_add(max, 1.into()), max);
+    assert_eq!(
+        Saturating::saturating_sub(U512::zero(), 1.into()),
+        U512::zero()
+    );
+}
+
+#[test]
+fn display_test_u512() {
+    assert_eq!(format!("{}", U512::from(1_234_567_890u64)), "1234567890");
+    assert_eq!(format!("{}", U512::zero()), "0");
+}
+
+#[test]
+fn serialization_is_length_prefixed_and_minimal() {
+    assert_eq!(U512::zero().to_bytes().unwrap(), vec![0]);
+    assert_eq!(U512::from(1).to_bytes().unwrap(), vec![1, 1]);
+    assert_eq!(U512::from(256).to_bytes().unwrap(), vec![2, 0, 1]);
+    assert_eq!(U512::max_value().to_bytes().unwrap().len(), 65);
+}
@@ -25,6 +25,6 @@ pub fn create_account(account_addr: &[u8; 64], initial_amount: u64) {
     match contract_api::transfer_to_account(public_key, amount) {
         TransferResult::TransferredToNewAccount => (),
         TransferResult::TransferredToExistingAccount => contract_api::revert(10),
-        TransferResult::TransferError => contract_api::revert(11),
+        TransferResult::TransferError(_) => contract_api::revert(11),
     }
 }
@@ -4,7 +4,7 @@ extern crate alloc;
 extern crate contract_ffi;
 
 use alloc::vec::Vec;
-use contract_ffi::contract_api::{self, TransferResult};
+use contract_ffi::contract_api;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
 use core::convert::TryFrom;
@@ -28,7 +28,7 @@ pub extern "C" fn call() {
     let seed_amount: U512 = contract_api::get_arg(1);
     for public_key in accounts {
         let result = contract_ffi::contract_api::transfer_to_account(public_key, seed_amount);
-        if result == TransferResult::TransferError {
+        if !result.is_success() {
             contract_api::revert(Error::SeedTransferFail as u32);
         }
     }
@@ -29,6 +29,6 @@ pub extern "C" fn call() {
         TransferResult::TransferredToNewAccount => {
             contract_api::revert(Error::TransferredToNewAccount as u32)
         }
-        TransferResult::TransferError => contract_api::revert(Error::TransferError as u32),
+        TransferResult::TransferError(_) => contract_api::revert(Error::TransferError as u32),
     }
 }
@@ -4,44 +4,60 @@
 extern crate alloc;
 extern crate contract_ffi;
 
+use alloc::string::String;
+
+use contract_ffi::contract_api;
 use contract_ffi::contract_api::pointers::UPointer;
-use contract_ffi::contract_api::{self, PurseTransferResult};
+use contract_ffi::contract_api::safe::{self, ApiError};
 use contract_ffi::key::Key;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::account::PurseId;
 use contract_ffi::value::uint::U512;
 
 const BOND_METHOD_NAME: &str = "bond";
 const POS_CONTRACT_NAME: &str = "pos";
 
+enum Arg {
+    Amount = 0,
+    SourcePurse = 1,
+}
+
 // Bonding contract.
 //
 // Accepts bonding amount (of type `u64`) as first argument.
+// Accepts an optional source purse name (of type `Option<String>`) as second argument, naming a
+// purse in the account's named keys.
+// If no source purse is given, the bond is paid from the account's main purse.
 // Issues bonding request to the PoS contract.
 #[no_mangle]
 pub extern "C" fn call() {
-    let pos_uref = unwrap_or_revert(contract_api::get_uref(POS_CONTRACT_NAME), 55);
-    let pos_public: UPointer<Key> = unwrap_or_revert(pos_uref.to_u_ptr(), 66);
-    let pos_contract: Key = contract_api::read(pos_public);
-    let pos_pointer = unwrap_or_revert(pos_contract.to_c_ptr(), 77);
-
-    let source_purse = contract_api::main_purse();
+    let pos_uref = safe::get_uref(POS_CONTRACT_NAME).unwrap_or_revert();
+    let pos_public: UPointer<Key> = pos_uref
+        .to_u_ptr()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    let pos_contract: Key = safe::read(pos_public).unwrap_or_revert();
+    let pos_pointer = pos_contract
+        .to_c_ptr()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+
+    let bond_amount: U512 = U512::from(safe::get_arg::<u64>(Arg::Amount as u32).unwrap_or_revert());
+    let source_purse: PurseId =
+        match safe::get_arg::<Option<String>>(Arg::SourcePurse as u32).unwrap_or_revert() {
+            Some(purse_name) => safe::get_uref(&purse_name)
+                .unwrap_or_revert()
+                .as_uref()
+                .map(|uref| PurseId::new(*uref))
+                .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant),
+            None => safe::main_purse().unwrap_or_revert(),
+        };
     let bonding_purse = contract_api::create_purse();
-    let bond_amount: U512 = U512::from(contract_api::get_arg::<u64>(0));
-
-    match contract_api::transfer_from_purse_to_purse(source_purse, bonding_purse, bond_amount) {
-        PurseTransferResult::TransferSuccessful => contract_api::call_contract(
-            pos_pointer,
-            &(BOND_METHOD_NAME, bond_amount, bonding_purse),
-            &vec![Key::URef(bonding_purse.value())],
-        ),
 
-        PurseTransferResult::TransferError => contract_api::revert(1324),
-    }
-}
+    safe::transfer_from_purse_to_purse(source_purse, bonding_purse, bond_amount).unwrap_or_revert();
 
-fn unwrap_or_revert<T>(option: Option<T>, code: u32) -> T {
-    if let Some(value) = option {
-        value
-    } else {
-        contract_api::revert(code)
-    }
+    safe::call_contract(
+        pos_pointer,
+        &(BOND_METHOD_NAME, bond_amount, bonding_purse),
+        &vec![Key::URef(bonding_purse.value())],
+    )
+    .unwrap_or_revert()
 }
@@ -5,9 +5,11 @@ extern crate alloc;
 extern crate contract_ffi;
 
 use alloc::string::String;
+use contract_ffi::contract_api;
 use contract_ffi::contract_api::pointers::{ContractPointer, UPointer};
-use contract_ffi::contract_api::{self, PurseTransferResult};
+use contract_ffi::contract_api::safe::{self, ApiError};
 use contract_ffi::key::Key;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
 use contract_ffi::uref::AccessRights;
 use contract_ffi::value::account::PurseId;
 use contract_ffi::value::U512;
@@ -29,26 +31,32 @@ enum Error {
     InvalidPurse = 5,
 }
 
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn call() {
     let purse_name: String = contract_api::get_arg(Arg::PurseName as u32);
-    let purse_key = contract_api::get_uref(&purse_name)
-        .unwrap_or_else(|| contract_api::revert(Error::InvalidPurseName as u32));
-    let purse = match purse_key.as_uref() {
-        Some(uref) => PurseId::new(*uref),
-        None => contract_api::revert(Error::InvalidPurse as u32),
-    };
+    let purse_key =
+        contract_api::get_uref(&purse_name).unwrap_or_revert_with(Error::InvalidPurseName);
+    let purse = purse_key
+        .as_uref()
+        .map(|uref| PurseId::new(*uref))
+        .unwrap_or_revert_with(Error::InvalidPurse);
 
     let amount: U512 = contract_api::get_arg(Arg::Amount as u32);
 
     let pos_pointer: ContractPointer = {
         let outer: UPointer<Key> = contract_api::get_uref(POS_CONTRACT_NAME)
             .and_then(Key::to_u_ptr)
-            .unwrap_or_else(|| contract_api::revert(Error::GetPosInnerURef as u32));
+            .unwrap_or_revert_with(Error::GetPosInnerURef);
         if let Some(ContractPointer::URef(inner)) = contract_api::read::<Key>(outer).to_c_ptr() {
             ContractPointer::URef(UPointer::new(inner.0, AccessRights::READ))
         } else {
-            contract_api::revert(Error::GetPosOuterURef as u32);
+            safe::revert(Error::GetPosOuterURef);
         }
     };
 
@@ -61,9 +69,6 @@ pub extern "C" fn call() {
         &vec![Key::URef(purse.value())],
     );
 
-    if let PurseTransferResult::TransferError =
-        contract_api::transfer_from_purse_to_purse(purse, payment_purse, amount)
-    {
-        contract_api::revert(Error::Transfer as u32);
-    }
+    safe::transfer_from_purse_to_purse(purse, payment_purse, amount)
+        .unwrap_or_revert_with(Error::Transfer);
 }
@@ -0,0 +1,34 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api::safe::{self, ApiError};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::account::PurseId;
+use contract_ffi::value::U512;
+
+fn get_purse(name: &str) -> PurseId {
+    let key = safe::get_uref(name).unwrap_or_revert();
+    let uref = key
+        .as_uref()
+        .cloned()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    PurseId::new(uref)
+}
+
+/// Transfers motes between two purses held in the caller's named keys.
+///
+/// Reverts with the code of `ApiError::Transfer` if the transfer fails.
+#[no_mangle]
+pub extern "C" fn call() {
+    let source_name: String = safe::get_arg(0).unwrap_or_revert();
+    let target_name: String = safe::get_arg(1).unwrap_or_revert();
+    let amount: U512 = safe::get_arg(2).unwrap_or_revert();
+
+    let source = get_purse(&source_name);
+    let target = get_purse(&target_name);
+    safe::transfer_from_purse_to_purse(source, target, amount).unwrap_or_revert();
+}
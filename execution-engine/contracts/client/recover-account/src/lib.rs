@@ -0,0 +1,30 @@
+#![no_std]
+
+extern crate contract_ffi;
+
+use contract_ffi::contract_api;
+use contract_ffi::contract_api::safe::{self, ApiError};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::account::{AddKeyFailure, PublicKey, Weight};
+
+/// Replaces a lost key of the account with a new key of the supplied weight.
+///
+/// The deploy is signed by the remaining associated keys, which must weigh at least the key
+/// management threshold.  If the new key is already associated with the account, only its weight
+/// is updated.
+///
+/// Reverts with the code of the `ApiError` of the first key management operation which fails.
+#[no_mangle]
+pub extern "C" fn call() {
+    let lost_key: PublicKey = contract_api::get_arg(0);
+    let new_key: PublicKey = contract_api::get_arg(1);
+    let weight: Weight = contract_api::get_arg(2);
+
+    match safe::add_associated_key(new_key, weight) {
+        Err(ApiError::AddKey(AddKeyFailure::DuplicateKey)) => {
+            safe::update_associated_key(new_key, weight).unwrap_or_revert()
+        }
+        result => result.unwrap_or_revert(),
+    }
+    safe::remove_associated_key(lost_key).unwrap_or_revert();
+}
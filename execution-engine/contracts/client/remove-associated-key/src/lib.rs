@@ -0,0 +1,16 @@
+#![no_std]
+
+extern crate contract_ffi;
+
+use contract_ffi::contract_api::{self, safe};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::account::PublicKey;
+
+/// Removes the supplied public key from the keys associated with the account.
+///
+/// Reverts with the code of `ApiError::RemoveKey` if the key cannot be removed.
+#[no_mangle]
+pub extern "C" fn call() {
+    let public_key: PublicKey = contract_api::get_arg(0);
+    safe::remove_associated_key(public_key).unwrap_or_revert();
+}
@@ -0,0 +1,67 @@
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+extern crate contract_ffi;
+
+use contract_ffi::contract_api;
+use contract_ffi::contract_api::pointers::{ContractPointer, UPointer};
+use contract_ffi::contract_api::safe::{self, ApiError};
+use contract_ffi::key::Key;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::uref::AccessRights;
+use contract_ffi::value::account::PurseId;
+use contract_ffi::value::U512;
+
+const POS_CONTRACT_NAME: &str = "pos";
+const GET_PAYMENT_PURSE: &str = "get_payment_purse";
+const SET_REFUND_PURSE: &str = "set_refund_purse";
+
+enum Arg {
+    Purse = 0,
+    Amount = 1,
+}
+
+enum Error {
+    GetPosInnerURef = 1,
+    GetPosOuterURef = 2,
+    Transfer = 3,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+/// Pays for the deploy from the purse passed as the first argument rather than from the account's
+/// main purse, and makes that purse the refund purse. The purse must be writeable in the context
+/// of the deploying account, e.g. a purse held in its named keys.
+#[no_mangle]
+pub extern "C" fn call() {
+    let purse: PurseId = contract_api::get_arg(Arg::Purse as u32);
+    let amount: U512 = contract_api::get_arg(Arg::Amount as u32);
+
+    let pos_pointer: ContractPointer = {
+        let outer: UPointer<Key> = contract_api::get_uref(POS_CONTRACT_NAME)
+            .and_then(Key::to_u_ptr)
+            .unwrap_or_revert_with(Error::GetPosInnerURef);
+        if let Some(ContractPointer::URef(inner)) = contract_api::read::<Key>(outer).to_c_ptr() {
+            ContractPointer::URef(UPointer::new(inner.0, AccessRights::READ))
+        } else {
+            safe::revert(Error::GetPosOuterURef);
+        }
+    };
+
+    let payment_purse: PurseId =
+        contract_api::call_contract(pos_pointer.clone(), &(GET_PAYMENT_PURSE,), &vec![]);
+
+    contract_api::call_contract::<_, ()>(
+        pos_pointer,
+        &(SET_REFUND_PURSE, purse),
+        &vec![Key::URef(purse.value())],
+    );
+
+    safe::transfer_from_purse_to_purse(purse, payment_purse, amount)
+        .unwrap_or_revert_with(Error::Transfer);
+}
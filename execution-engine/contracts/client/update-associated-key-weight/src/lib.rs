@@ -0,0 +1,17 @@
+#![no_std]
+
+extern crate contract_ffi;
+
+use contract_ffi::contract_api::{self, safe};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::account::{PublicKey, Weight};
+
+/// Sets the weight of a public key already associated with the account.
+///
+/// Reverts with the code of `ApiError::UpdateKey` if the weight cannot be updated.
+#[no_mangle]
+pub extern "C" fn call() {
+    let public_key: PublicKey = contract_api::get_arg(0);
+    let weight: Weight = contract_api::get_arg(1);
+    safe::update_associated_key(public_key, weight).unwrap_or_revert();
+}
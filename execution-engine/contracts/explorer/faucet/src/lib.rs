@@ -28,7 +28,7 @@ pub extern "C" fn call() {
     } else {
         let u512_tokens = U512::from(TRANSFER_AMOUNT);
         match transfer_to_account(public_key, u512_tokens) {
-            TransferResult::TransferError => revert(2),
+            TransferResult::TransferError(_) => revert(2),
             _ => {
                 // Transfer successful; Store the fact of funding in the local state.
                 write_local(public_key, u512_tokens);
@@ -25,7 +25,7 @@ fn create_account_with_amount(account: PublicKey, amount: U512) {
         TransferResult::TransferredToExistingAccount => {
             contract_api::revert(Error::AccountAlreadyExists as u32)
         }
-        TransferResult::TransferError => contract_api::revert(Error::TransferError as u32),
+        TransferResult::TransferError(_) => contract_api::revert(Error::TransferError as u32),
     }
 }
 
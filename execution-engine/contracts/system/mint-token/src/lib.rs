@@ -19,8 +19,12 @@ use alloc::string::String;
 use core::convert::TryInto;
 
 use contract_ffi::contract_api;
+use contract_ffi::contract_api::pointers::UPointer;
 use contract_ffi::key::Key;
 use contract_ffi::system_contracts::mint::error::Error;
+use contract_ffi::system_contracts::mint::{
+    BURN_METHOD, READ_TOTAL_SUPPLY_METHOD, TOTAL_SUPPLY_KEY,
+};
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::KEY_SIZE;
 use contract_ffi::value::U512;
@@ -33,13 +37,31 @@ const SYSTEM_ACCOUNT: [u8; KEY_SIZE] = [0u8; KEY_SIZE];
 
 struct CLMint;
 
+impl CLMint {
+    fn total_supply_uref(&self) -> Option<UPointer<U512>> {
+        contract_api::get_uref(TOTAL_SUPPLY_KEY).and_then(Key::to_u_ptr)
+    }
+
+    /// Adds `amount` to the total supply, creating the uref which records it
+    /// on the first issuance of motes.
+    fn increase_total_supply(&self, amount: U512) {
+        match self.total_supply_uref() {
+            Some(total_supply_uref) => contract_api::add(total_supply_uref, amount),
+            None => {
+                let total_supply_uref: Key = contract_api::new_uref(amount).into();
+                contract_api::add_uref(TOTAL_SUPPLY_KEY, &total_supply_uref);
+            }
+        }
+    }
+}
+
 impl Mint<ARef<U512>, RAWRef<U512>> for CLMint {
     type PurseId = WithdrawId;
     type DepOnlyId = DepositId;
 
     fn mint(&self, initial_balance: U512) -> Result<Self::PurseId, Error> {
         let caller = contract_api::get_caller();
-        if !initial_balance.is_zero() && caller.value() != SYSTEM_ACCOUNT {
+        if !initial_balance.is_zero() && caller.to_account_address() != SYSTEM_ACCOUNT {
             return Err(Error::InvalidNonEmptyPurseCreation);
         }
 
@@ -64,6 +86,10 @@ impl Mint<ARef<U512>, RAWRef<U512>> for CLMint {
         // somehow.
         contract_api::write_local(purse_id.raw_id(), balance_uref);
 
+        if !initial_balance.is_zero() {
+            self.increase_total_supply(initial_balance);
+        }
+
         Ok(purse_id)
     }
 
@@ -74,6 +100,19 @@ impl Mint<ARef<U512>, RAWRef<U512>> for CLMint {
     fn dep_lookup(&self, p: Self::DepOnlyId) -> Option<ARef<U512>> {
         contract_api::read_local(p.raw_id()).and_then(|key: Key| key.try_into().ok())
     }
+
+    fn total_supply(&self) -> U512 {
+        self.total_supply_uref()
+            .map(contract_api::read)
+            .unwrap_or_else(U512::zero)
+    }
+
+    fn decrease_total_supply(&self, amount: U512) {
+        if let Some(total_supply_uref) = self.total_supply_uref() {
+            let total_supply: U512 = contract_api::read(total_supply_uref);
+            contract_api::write(total_supply_uref, total_supply - amount);
+        }
+    }
 }
 
 pub fn delegate() {
@@ -136,6 +175,30 @@ pub fn delegate() {
             contract_api::ret(&transfer_result, &vec![]);
         }
 
+        // argument: (URef, U512)
+        // return: Result<(), mint::error::Error>
+        BURN_METHOD => {
+            let source: URef = contract_api::get_arg(1);
+            let amount: U512 = contract_api::get_arg(2);
+
+            let source: WithdrawId = match WithdrawId::from_uref(source) {
+                Ok(withdraw_id) => withdraw_id,
+                Err(error) => {
+                    let burn_result: Result<(), Error> = Err(error.into());
+                    contract_api::ret(&burn_result, &vec![])
+                }
+            };
+
+            let burn_result = mint.burn(source, amount);
+            contract_api::ret(&burn_result, &vec![]);
+        }
+
+        // return: U512
+        READ_TOTAL_SUPPLY_METHOD => {
+            let total_supply = mint.total_supply();
+            contract_api::ret(&total_supply, &vec![])
+        }
+
         _ => panic!("Unknown method name!"),
     }
 }
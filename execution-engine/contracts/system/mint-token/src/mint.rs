@@ -15,6 +15,13 @@ where
     fn lookup(&self, p: Self::PurseId) -> Option<RW>;
     fn dep_lookup(&self, p: Self::DepOnlyId) -> Option<A>;
 
+    /// Returns the sum of the initial balances of all the purses minted so far,
+    /// less any motes burned since.
+    fn total_supply(&self) -> U512;
+
+    /// Removes `amount` from the total supply after motes have been burned.
+    fn decrease_total_supply(&self, amount: U512);
+
     fn create(&self) -> Self::PurseId {
         self.mint(U512::zero())
             .expect("Creating a zero balance purse should always be allowed.")
@@ -37,6 +44,18 @@ where
         dest_bal.add(amount);
         Ok(())
     }
+
+    fn burn(&self, source: Self::PurseId, amount: U512) -> Result<(), Error> {
+        let source_bal = self.lookup(source).ok_or(Error::SourceNotFound)?;
+        let source_value = source_bal.read();
+        if amount > source_value {
+            return Err(Error::InsufficientFunds);
+        }
+
+        source_bal.write(source_value - amount);
+        self.decrease_total_supply(amount);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -144,6 +163,18 @@ mod tests {
         fn dep_lookup(&self, p: Self::DepOnlyId) -> Option<Balance> {
             self.0.borrow().get(&p.0).map(Rc::clone)
         }
+
+        fn total_supply(&self) -> U512 {
+            self.0
+                .borrow()
+                .values()
+                .fold(U512::zero(), |total, balance| total + balance.get())
+        }
+
+        fn decrease_total_supply(&self, _amount: U512) {
+            // the total supply is derived from the balances, which `burn` has
+            // already reduced
+        }
     }
 
     #[test]
@@ -226,4 +257,67 @@ mod tests {
         // balance remains unchanged
         assert_eq!(balance1, b1);
     }
+
+    #[test]
+    fn total_supply_tracks_minted_motes() {
+        let mint = SimpleMint::new();
+        assert_eq!(U512::from(GENESIS_PURSE_AMOUNT), mint.total_supply());
+
+        let minted_amount = U512::from(50);
+        let purse = mint.mint(minted_amount).expect("minting should succeed");
+        mint.create();
+        assert_eq!(
+            U512::from(GENESIS_PURSE_AMOUNT) + minted_amount,
+            mint.total_supply()
+        );
+
+        // transfers move motes between purses without changing the supply
+        mint.transfer(GENESIS_PURSE, purse.to_dep(), U512::from(75))
+            .expect("transfer errored when it should not.");
+        assert_eq!(
+            U512::from(GENESIS_PURSE_AMOUNT) + minted_amount,
+            mint.total_supply()
+        );
+    }
+
+    #[test]
+    fn burn_success() {
+        let mint = SimpleMint::new();
+        let burn_amount = U512::from(50);
+
+        mint.burn(GENESIS_PURSE, burn_amount)
+            .expect("burn errored when it should not.");
+
+        let b1 = mint.balance(GENESIS_PURSE.0).unwrap();
+
+        assert_eq!(U512::from(GENESIS_PURSE_AMOUNT) - burn_amount, b1);
+        assert_eq!(
+            U512::from(GENESIS_PURSE_AMOUNT) - burn_amount,
+            mint.total_supply()
+        );
+    }
+
+    #[test]
+    fn burn_overdraft() {
+        let mint = SimpleMint::new();
+        let burn_amount = U512::from(1000);
+
+        assert_eq!(
+            Err(Error::InsufficientFunds),
+            mint.burn(GENESIS_PURSE, burn_amount)
+        );
+
+        let b1 = mint.balance(GENESIS_PURSE.0).unwrap();
+        // balance and supply remain unchanged
+        assert_eq!(U512::from(GENESIS_PURSE_AMOUNT), b1);
+        assert_eq!(U512::from(GENESIS_PURSE_AMOUNT), mint.total_supply());
+    }
+
+    #[test]
+    fn burn_source_not_exist() {
+        let mint = SimpleMint::new();
+        let purse = FullId(GENESIS_PURSE.0 + 1);
+
+        assert_eq!(Err(Error::SourceNotFound), mint.burn(purse, U512::from(75)));
+    }
 }
@@ -11,10 +11,14 @@ use contract_ffi::contract_api;
 use contract_ffi::contract_api::pointers::{ContractPointer, UPointer};
 use contract_ffi::key::Key;
 use contract_ffi::system_contracts::mint;
+use contract_ffi::system_contracts::pos::{
+    BondLimits, DelegationRate, DelegationRateLimits, DELEGATION_RATE_LIMITS_PREFIX,
+    VALIDATOR_BONDING_PURSE_PREFIX,
+};
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{PublicKey, PurseId};
 use contract_ffi::value::U512;
-use core::fmt::Write;
+use core::fmt::{Display, Write};
 
 const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
 const POS_BONDING_PURSE: &str = "pos_bonding_purse";
@@ -31,6 +35,12 @@ enum Error {
 enum Args {
     MintURef = 0,
     GenesisValidators = 1,
+    DelegationRates = 2,
+    GenesisDelegators = 3,
+    DelegationRateLimits = 4,
+    BondLimits = 5,
+    EraDuration = 6,
+    UnbondDelay = 7,
 }
 
 #[no_mangle]
@@ -46,32 +56,67 @@ pub extern "C" fn call() {
     let genesis_validators: BTreeMap<PublicKey, U512> =
         contract_api::get_arg(Args::GenesisValidators as u32);
 
+    let delegation_rates: BTreeMap<PublicKey, DelegationRate> =
+        contract_api::get_arg(Args::DelegationRates as u32);
+    let genesis_delegators: BTreeMap<PublicKey, U512> =
+        contract_api::get_arg(Args::GenesisDelegators as u32);
+    let delegation_rate_limits: DelegationRateLimits =
+        contract_api::get_arg(Args::DelegationRateLimits as u32);
+    let bond_limits: BondLimits = contract_api::get_arg(Args::BondLimits as u32);
+    let era_duration: u64 = contract_api::get_arg(Args::EraDuration as u32);
+    let unbond_delay: u64 = contract_api::get_arg(Args::UnbondDelay as u32);
+
     // Add genesis validators to PoS contract object.
     // For now, we are storing validators in `known_urefs` map of the PoS contract
     // in the form: key: "v_{validator_pk}_{validator_stake}", value: doesn't
     // matter.
     let mut known_urefs: BTreeMap<String, Key> = genesis_validators
         .iter()
-        .map(|(pub_key, balance)| {
-            let key_bytes = pub_key.value();
-            let mut hex_key = String::with_capacity(64);
-            for byte in &key_bytes[..32] {
-                write!(hex_key, "{:02x}", byte).unwrap();
-            }
-            let mut uref = String::new();
-            uref.write_fmt(format_args!("v_{}_{}", hex_key, balance))
-                .unwrap();
-            uref
-        })
+        .map(|(pub_key, balance)| encode_name("v", pub_key, balance))
         .map(|key| (key, PLACEHOLDER_KEY))
         .collect();
 
+    // Delegation rates and genesis delegators are stored the same way, in the form
+    // "r_{validator_pk}_{delegation_rate}" and "d_{delegator_pk}_{delegated_stake}".
+    delegation_rates
+        .iter()
+        .map(|(pub_key, rate)| encode_name("r", pub_key, rate))
+        .chain(
+            genesis_delegators
+                .iter()
+                .map(|(pub_key, stake)| encode_name("d", pub_key, stake)),
+        )
+        .for_each(|name| {
+            known_urefs.insert(name, PLACEHOLDER_KEY);
+        });
+
+    // The caps on changing delegation rates are stored in the form
+    // "pos_delegation_rate_limits_{max_increase_per_era}_{max_changes_per_era}".
+    let mut limits_name = String::from(DELEGATION_RATE_LIMITS_PREFIX);
+    limits_name
+        .write_fmt(format_args!(
+            "{}_{}",
+            delegation_rate_limits.max_increase_per_era(),
+            delegation_rate_limits.max_changes_per_era()
+        ))
+        .unwrap();
+    known_urefs.insert(limits_name, PLACEHOLDER_KEY);
+
+    // Each genesis validator's stake is held in a bonding purse of its own.
+    for (pub_key, stake) in genesis_validators.iter() {
+        let validator_bonding_purse = mint_purse(&mint, *stake);
+        let mut name = String::from(VALIDATOR_BONDING_PURSE_PREFIX);
+        name.push_str(&pub_key.to_hex());
+        known_urefs.insert(name, Key::URef(validator_bonding_purse.value()));
+    }
+
     // Include the mint contract in its known_urefs
     known_urefs.insert(String::from(MINT_NAME), Key::URef(mint_uref));
 
-    let total_bonds: U512 = genesis_validators.values().fold(U512::zero(), |x, y| x + y);
+    // The shared bonding purse holds the delegated stakes.
+    let total_delegations: U512 = genesis_delegators.values().fold(U512::zero(), |x, y| x + y);
 
-    let bonding_purse = mint_purse(&mint, total_bonds);
+    let bonding_purse = mint_purse(&mint, total_delegations);
     let payment_purse = mint_purse(&mint, U512::zero());
     let rewards_purse = mint_purse(&mint, U512::zero());
 
@@ -89,6 +134,13 @@ pub extern "C" fn call() {
     let contract = contract_api::fn_by_name("pos_ext", known_urefs);
     let uref: URef = contract_api::new_uref(contract).into();
 
+    // The bond limits, the era duration and the unbonding delay are kept in the local state of the
+    // PoS contract, so only it can set them.
+    let pos = ContractPointer::URef(UPointer::new(uref.addr(), AccessRights::READ));
+    contract_api::call_contract::<_, ()>(pos.clone(), &("set_bond_limits", bond_limits), &vec![]);
+    contract_api::call_contract::<_, ()>(pos.clone(), &("set_era_duration", era_duration), &vec![]);
+    contract_api::call_contract::<_, ()>(pos, &("set_unbond_delay", unbond_delay), &vec![]);
+
     contract_api::ret(&uref, &vec![uref]);
 }
 
@@ -100,3 +152,10 @@ fn mint_purse(mint: &ContractPointer, amount: U512) -> PurseId {
         .map(PurseId::new)
         .unwrap_or_else(|_| contract_api::revert(Error::MintFailure as u32))
 }
+
+fn encode_name<T: Display>(prefix: &str, pub_key: &PublicKey, value: T) -> String {
+    let mut name = String::new();
+    name.write_fmt(format_args!("{}_{}_{}", prefix, pub_key.to_hex(), value))
+        .unwrap();
+    name
+}
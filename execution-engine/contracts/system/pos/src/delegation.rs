@@ -0,0 +1,169 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt::Write;
+use core::result;
+
+use contract_ffi::bytesrepr::{self, FromBytes, ToBytes};
+use contract_ffi::contract_api;
+use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::{
+    DelegationRate, DelegationRateLimits, EraId, DELEGATION_RATE_LIMITS_PREFIX,
+};
+use contract_ffi::value::{account::PublicKey, Value};
+
+use crate::error::{Error, Result};
+
+/// The local state key under which the delegation rate changes are stored, each followed by the
+/// public key of its validator.
+const DELEGATION_RATE_CHANGES_KEY: u8 = 7;
+
+/// The changes a validator made to its delegation rate within an era.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DelegationRateChanges {
+    /// The era the changes were made in.
+    pub era: EraId,
+    /// The delegation rate before the first change of the era, or `None` if the validator had
+    /// not chosen one yet.
+    pub initial_rate: Option<DelegationRate>,
+    /// The number of changes made.
+    pub count: u32,
+}
+
+impl FromBytes for DelegationRateChanges {
+    fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
+        let (era, bytes) = EraId::from_bytes(bytes)?;
+        let (initial_rate, bytes) = Option::<DelegationRate>::from_bytes(bytes)?;
+        let (count, bytes) = u32::from_bytes(bytes)?;
+        let changes = DelegationRateChanges {
+            era,
+            initial_rate,
+            count,
+        };
+        Ok((changes, bytes))
+    }
+}
+
+impl ToBytes for DelegationRateChanges {
+    fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
+        Ok((self.era.to_bytes()?.into_iter())
+            .chain(self.initial_rate.to_bytes()?)
+            .chain(self.count.to_bytes()?)
+            .collect())
+    }
+}
+
+impl TryFrom<Value> for DelegationRateChanges {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<DelegationRateChanges> {
+        let bytes = match value {
+            Value::ByteArray(bytes) => bytes,
+            _ => return Err(Error::DelegationRateChangesNotStoredAsByteArray),
+        };
+        match DelegationRateChanges::from_bytes(&bytes) {
+            Ok((changes, rest)) if rest.is_empty() => Ok(changes),
+            _ => Err(Error::DelegationRateChangesDeserializationFailed),
+        }
+    }
+}
+
+impl Into<Value> for &DelegationRateChanges {
+    fn into(self) -> Value {
+        Value::ByteArray(self.to_bytes().expect("Serialization cannot fail"))
+    }
+}
+
+pub trait DelegationRateProvider {
+    /// Reads the delegation rate of `validator`, or `None` if it has not chosen one.
+    fn read_rate(validator: &PublicKey) -> Result<Option<DelegationRate>>;
+
+    /// Writes the delegation rate of `validator`.
+    fn write_rate(validator: &PublicKey, rate: DelegationRate);
+
+    /// Reads the caps on changing delegation rates set at genesis.
+    fn read_limits() -> Result<DelegationRateLimits>;
+
+    /// Reads the changes `validator` made to its delegation rate in the latest era it made any.
+    fn read_changes(validator: &PublicKey) -> Option<DelegationRateChanges>;
+
+    /// Writes the changes `validator` made to its delegation rate in the current era.
+    fn write_changes(validator: &PublicKey, changes: &DelegationRateChanges);
+}
+
+/// A `DelegationRateProvider` that reads and writes the delegation rates and the limits to/from
+/// the contract's known urefs, and the changes to/from its local state.
+pub struct ContractDelegationRates;
+
+impl DelegationRateProvider for ContractDelegationRates {
+    /// Reads the delegation rate from the uref named "r_{validator_pk}_{delegation_rate}".
+    fn read_rate(validator: &PublicKey) -> Result<Option<DelegationRate>> {
+        let prefix = rate_name_prefix(validator);
+        for (name, _) in contract_api::list_known_urefs() {
+            if name.starts_with(&prefix) {
+                return name[prefix.len()..]
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| Error::DelegationRateDeserializationFailed);
+            }
+        }
+        Ok(None)
+    }
+
+    fn write_rate(validator: &PublicKey, rate: DelegationRate) {
+        let prefix = rate_name_prefix(validator);
+        for (name, _) in contract_api::list_known_urefs() {
+            if name.starts_with(&prefix) {
+                contract_api::remove_uref(&name);
+            }
+        }
+        let mut name = prefix;
+        name.write_fmt(format_args!("{}", rate))
+            .expect("Writing to a string cannot fail");
+        contract_api::add_uref(&name, &Key::Hash([0; 32]));
+    }
+
+    /// Reads the limits from the uref named
+    /// "pos_delegation_rate_limits_{max_increase_per_era}_{max_changes_per_era}", falling back to
+    /// the default limits if there is none.
+    fn read_limits() -> Result<DelegationRateLimits> {
+        for (name, _) in contract_api::list_known_urefs() {
+            if !name.starts_with(DELEGATION_RATE_LIMITS_PREFIX) {
+                continue;
+            }
+            let mut split_limits = name[DELEGATION_RATE_LIMITS_PREFIX.len()..].split('_');
+            let max_increase_per_era = split_limits.next().and_then(|field| field.parse().ok());
+            let max_changes_per_era = split_limits.next().and_then(|field| field.parse().ok());
+            return match (
+                max_increase_per_era,
+                max_changes_per_era,
+                split_limits.next(),
+            ) {
+                (Some(max_increase_per_era), Some(max_changes_per_era), None) => {
+                    DelegationRateLimits::new(max_increase_per_era, max_changes_per_era)
+                        .ok_or(Error::DelegationRateLimitsDeserializationFailed)
+                }
+                _ => Err(Error::DelegationRateLimitsDeserializationFailed),
+            };
+        }
+        Ok(DelegationRateLimits::default())
+    }
+
+    fn read_changes(validator: &PublicKey) -> Option<DelegationRateChanges> {
+        contract_api::read_local((DELEGATION_RATE_CHANGES_KEY, *validator))
+    }
+
+    fn write_changes(validator: &PublicKey, changes: &DelegationRateChanges) {
+        contract_api::write_local((DELEGATION_RATE_CHANGES_KEY, *validator), changes);
+    }
+}
+
+/// Returns "r_{validator_pk}_", the prefix of the name of the uref holding the delegation rate of
+/// `validator`.
+fn rate_name_prefix(validator: &PublicKey) -> String {
+    let mut prefix = String::new();
+    prefix
+        .write_fmt(format_args!("r_{}_", validator.to_hex()))
+        .expect("Writing to a string cannot fail");
+    prefix
+}
@@ -0,0 +1,45 @@
+use contract_ffi::contract_api;
+use contract_ffi::system_contracts::pos::EraId;
+
+use crate::stakes::Stakes;
+
+/// The local state key under which the current era is stored.
+const CURRENT_ERA_KEY: u8 = 4;
+/// The local state key under which the snapshots are stored, each followed by the ID of its era.
+const ERA_SNAPSHOT_KEY: u8 = 6;
+
+pub trait EraProvider {
+    /// Reads the current era, or `None` if no era has started yet.
+    fn read_current_era() -> Option<EraId>;
+
+    /// Writes the current era.
+    fn write_current_era(era: EraId);
+
+    /// Reads the stakes bonded when `era` started, or `None` if `era` never started.
+    fn read_snapshot(era: EraId) -> Option<Stakes>;
+
+    /// Writes the stakes bonded when `era` started.
+    fn write_snapshot(era: EraId, stakes: &Stakes);
+}
+
+/// An `EraProvider` that reads and writes the current era and the snapshots to/from the
+/// contract's local state.
+pub struct EraLocal;
+
+impl EraProvider for EraLocal {
+    fn read_current_era() -> Option<EraId> {
+        contract_api::read_local(CURRENT_ERA_KEY)
+    }
+
+    fn write_current_era(era: EraId) {
+        contract_api::write_local(CURRENT_ERA_KEY, era);
+    }
+
+    fn read_snapshot(era: EraId) -> Option<Stakes> {
+        contract_api::read_local((ERA_SNAPSHOT_KEY, era))
+    }
+
+    fn write_snapshot(era: EraId, stakes: &Stakes) {
+        contract_api::write_local((ERA_SNAPSHOT_KEY, era), stakes);
+    }
+}
@@ -3,6 +3,8 @@
 #[macro_use]
 extern crate alloc;
 
+mod delegation;
+mod era;
 mod error;
 mod queue;
 mod stakes;
@@ -10,16 +12,23 @@ mod stakes;
 use alloc::string::String;
 use alloc::vec::Vec;
 
+use contract_ffi::bytesrepr::{self, ToBytes};
 use contract_ffi::contract_api;
 use contract_ffi::execution::Phase;
 use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::{
+    BondLimits, DelegationRate, EraId, FeeHandling, RefundRatio, DEFAULT_ERA_DURATION,
+    DEFAULT_UNBOND_DELAY, MAX_DELEGATION_RATE, VALIDATOR_BONDING_PURSE_PREFIX,
+};
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{BlockTime, PublicKey, PurseId};
 use contract_ffi::value::U512;
 
+use crate::delegation::{ContractDelegationRates, DelegationRateChanges, DelegationRateProvider};
+use crate::era::{EraLocal, EraProvider};
 use crate::error::{Error, PurseLookupError, Result, ResultExt};
-use crate::queue::{QueueEntry, QueueLocal, QueueProvider};
-use crate::stakes::{ContractStakes, StakesProvider};
+use crate::queue::{QueueEntry, QueueLocal, QueueProvider, Queues};
+use crate::stakes::{ContractStakes, Stakes, StakesProvider};
 
 /// Account used to run system functions (in particular `finalize_payment`).
 const SYSTEM_ACCOUNT: [u8; 32] = [0u8; 32];
@@ -43,12 +52,20 @@ const REFUND_PURSE_KEY: &str = "pos_refund_purse";
 /// The time from a bonding request until the bond becomes effective and part of
 /// the stake.
 const BOND_DELAY: u64 = 0;
-/// The time from an unbonding request until the stakes are paid out.
-const UNBOND_DELAY: u64 = 0;
+/// The local state key under which the unbonding delay is stored. Without one, unbonded stakes
+/// are paid out after `DEFAULT_UNBOND_DELAY`.
+const UNBOND_DELAY_KEY: u8 = 3;
+/// The local state key under which the era duration is stored. Without one, eras last
+/// `DEFAULT_ERA_DURATION`.
+const ERA_DURATION_KEY: u8 = 5;
+/// The local state key under which the bond limits are stored.
+const BOND_LIMITS_KEY: u8 = 9;
 /// The maximum number of pending bonding requests.
 const MAX_BOND_LEN: usize = 100;
 /// The maximum number of pending unbonding requests.
 const MAX_UNBOND_LEN: usize = 1000;
+/// The maximum number of bonding and unbonding requests applied to the stakes in a single step.
+const MAX_REQUESTS_PER_STEP: usize = 100;
 /// The maximum difference between the largest and the smallest stakes.
 // TODO: Should this be a percentage instead?
 // TODO: Pick a reasonable value.
@@ -70,6 +87,7 @@ fn bond<Q: QueueProvider, S: StakesProvider>(
     amount: U512,
     validator: PublicKey,
     timestamp: BlockTime,
+    bond_limits: &BondLimits,
 ) -> Result<()> {
     let mut queue = Q::read_bonding();
     if queue.0.len() >= MAX_BOND_LEN {
@@ -82,6 +100,7 @@ fn bond<Q: QueueProvider, S: StakesProvider>(
         stakes.bond(&entry.validator, entry.amount);
     }
     stakes.validate_bonding(&validator, amount)?;
+    stakes.validate_bond_limits(&validator, amount, bond_limits)?;
 
     queue.push(validator, amount, timestamp)?;
     Q::write_bonding(&queue);
@@ -89,7 +108,7 @@ fn bond<Q: QueueProvider, S: StakesProvider>(
 }
 
 /// Enqueues the deploy's creator for unbonding. Their vote weight as a
-/// validator is decreased immediately, but the funds will only be released
+/// validator is decreased in the next step, but the funds will only be released
 /// after a delay. If `maybe_amount` is `None`, all funds are enqueued for
 /// withdrawal, terminating the validator status.
 fn unbond<Q: QueueProvider, S: StakesProvider>(
@@ -97,44 +116,251 @@ fn unbond<Q: QueueProvider, S: StakesProvider>(
     validator: PublicKey,
     timestamp: BlockTime,
 ) -> Result<()> {
-    let mut queue = Q::read_unbonding();
+    let mut queue = Q::read_requested_unbonds();
     if queue.0.len() >= MAX_UNBOND_LEN {
         return Err(Error::TooManyEventsInQueue);
     }
+    // The unbond will join the unbonding queue, which may hold one request per validator.
+    if Q::read_unbonding()
+        .0
+        .iter()
+        .any(|entry| entry.validator == validator)
+    {
+        return Err(Error::MultipleRequests);
+    }
 
     let mut stakes = S::read()?;
+    // Simulate applying all earlier requests. The modified stakes are not written.
+    for entry in &Q::read_bonding().0 {
+        stakes.bond(&entry.validator, entry.amount);
+    }
+    for entry in &queue.0 {
+        apply_unbond(&mut stakes, entry);
+    }
     let payout = stakes.unbond(&validator, maybe_amount)?;
-    S::write(&stakes);
     // TODO: Make sure the destination is valid and the amount can be paid. The
     // actual payment will be made later, after the unbonding delay.
     // contract_api::transfer_dry_run(POS_PURSE, dest, amount)?;
     queue.push(validator, payout, timestamp)?;
-    Q::write_unbonding(&queue);
+    Q::write_requested_unbonds(&queue);
     Ok(())
 }
 
-/// Removes all due requests from the queues and applies them.
-fn step<Q: QueueProvider, S: StakesProvider>(timestamp: BlockTime) -> Result<Vec<QueueEntry>> {
+/// Applies up to `max_requests` due bonding and unbonding requests to the stakes, in the order
+/// they were made, and moves the applied unbonds to the unbonding queue. Returns the unbonds
+/// whose delay has passed and the dropped bonds, all of which need to be paid out.
+///
+/// A bonding request which no longer satisfies the spread or `bond_limits`, e.g. because earlier
+/// requests changed the stakes, is dropped and returned to be refunded. An unbonding request which
+/// can no longer be applied, e.g. because it would now unbond the last validator, is dropped,
+/// leaving the stakes unchanged.
+fn step<Q: QueueProvider, S: StakesProvider>(
+    timestamp: BlockTime,
+    unbond_delay: u64,
+    max_requests: usize,
+    bond_limits: &BondLimits,
+) -> Result<Vec<QueueEntry>> {
     let mut bonding_queue = Q::read_bonding();
+    let mut requested_unbonds = Q::read_requested_unbonds();
+    let bonds_due = BlockTime(timestamp.0.saturating_sub(BOND_DELAY));
+
+    let mut stakes = S::read()?;
     let mut unbonding_queue = Q::read_unbonding();
+    let mut refunds = Vec::new();
+    let mut applied = 0;
+    while applied < max_requests {
+        let bond_is_next = match (
+            bonding_queue.first_due(bonds_due),
+            requested_unbonds.first_due(timestamp),
+        ) {
+            (None, None) => break,
+            // On ties the bond goes first, as the unbond may have relied on it when requested.
+            (Some(bond), Some(unbond)) => bond.timestamp <= unbond.timestamp,
+            (bond, _) => bond.is_some(),
+        };
+        if bond_is_next {
+            let entry = bonding_queue.0.remove(0);
+            if stakes
+                .validate_bonding(&entry.validator, entry.amount)
+                .is_ok()
+                && stakes
+                    .validate_bond_limits(&entry.validator, entry.amount, bond_limits)
+                    .is_ok()
+            {
+                stakes.bond(&entry.validator, entry.amount);
+            } else {
+                refunds.push(entry);
+            }
+        } else {
+            let entry = requested_unbonds.0.remove(0);
+            if let Some(payout) = apply_unbond(&mut stakes, &entry) {
+                unbonding_queue.push(entry.validator, payout, entry.timestamp)?;
+            }
+        }
+        applied += 1;
+    }
+
+    if applied > 0 {
+        Q::write_bonding(&bonding_queue);
+        Q::write_requested_unbonds(&requested_unbonds);
+        Q::write_unbonding(&unbonding_queue);
+        S::write(&stakes);
+    }
 
-    let bonds = bonding_queue.pop_due(BlockTime(timestamp.0.saturating_sub(BOND_DELAY)));
-    let unbonds = unbonding_queue.pop_due(BlockTime(timestamp.0.saturating_sub(UNBOND_DELAY)));
+    refunds.extend(process_unbond_requests::<Q>(timestamp, unbond_delay));
+    Ok(refunds)
+}
+
+/// Deducts the unbonding request `entry` from `stakes`, returning the amount to pay out, or `None`
+/// if the request can't be applied to them, in which case `stakes` are left unchanged.
+fn apply_unbond(stakes: &mut Stakes, entry: &QueueEntry) -> Option<U512> {
+    let mut unbonded = stakes.clone();
+    let payout = unbonded.unbond(&entry.validator, Some(entry.amount)).ok()?;
+    *stakes = unbonded;
+    Some(payout)
+}
 
+/// Removes all unbonding requests that are at least `unbond_delay` old from the
+/// unbonding queue and returns them. The stakes of these entries have already
+/// been deducted, so they only need to be paid out.
+fn process_unbond_requests<Q: QueueProvider>(
+    timestamp: BlockTime,
+    unbond_delay: u64,
+) -> Vec<QueueEntry> {
+    let due_timestamp = match timestamp.0.checked_sub(unbond_delay) {
+        Some(due_timestamp) => BlockTime(due_timestamp),
+        // No request can have matured yet.
+        None => return Vec::new(),
+    };
+
+    let mut unbonding_queue = Q::read_unbonding();
+    let unbonds = unbonding_queue.pop_due(due_timestamp);
     if !unbonds.is_empty() {
         Q::write_unbonding(&unbonding_queue);
     }
+    unbonds
+}
 
-    if !bonds.is_empty() {
-        Q::write_bonding(&bonding_queue);
-        let mut stakes = S::read()?;
-        for entry in bonds {
-            stakes.bond(&entry.validator, entry.amount);
+/// Returns the era `timestamp` falls in, or `None` if the era duration is zero, in which case the
+/// first era never ends.
+fn era_at(timestamp: BlockTime, era_duration: u64) -> Option<EraId> {
+    timestamp.0.checked_div(era_duration)
+}
+
+/// Makes `era` the current era, snapshotting the stakes bonded at its start.
+fn enter_era<E: EraProvider, S: StakesProvider>(era: EraId) -> Result<()> {
+    let stakes = S::read()?;
+    E::write_snapshot(era, &stakes);
+    E::write_current_era(era);
+    Ok(())
+}
+
+/// Starts the era `timestamp` falls in if it is later than the current era, returning the
+/// current era. Called before the stakes change, so the first era starts with the first change if
+/// none has started yet.
+fn update_era<E: EraProvider, S: StakesProvider>(
+    timestamp: BlockTime,
+    era_duration: u64,
+) -> Result<EraId> {
+    let era = match (E::read_current_era(), era_at(timestamp, era_duration)) {
+        (None, era) => era.unwrap_or(0),
+        (Some(current_era), Some(era)) if era > current_era => era,
+        // Eras never go back, even if an earlier block time is passed.
+        (Some(current_era), _) => return Ok(current_era),
+    };
+    enter_era::<E, S>(era)?;
+    Ok(era)
+}
+
+/// Sets the delegation rate of `validator` in `era`. The rate may change at most
+/// `max_changes_per_era` times per era, and rise at most `max_increase_per_era` above the rate the
+/// era started with.
+fn set_delegation_rate<D: DelegationRateProvider, S: StakesProvider>(
+    validator: PublicKey,
+    rate: DelegationRate,
+    era: EraId,
+) -> Result<()> {
+    if rate > MAX_DELEGATION_RATE {
+        return Err(Error::DelegationRateTooHigh);
+    }
+    if !S::read()?.0.contains_key(&validator) {
+        return Err(Error::NotBonded);
+    }
+    let limits = D::read_limits()?;
+    let mut changes = match D::read_changes(&validator) {
+        Some(changes) if changes.era == era => changes,
+        _ => DelegationRateChanges {
+            era,
+            initial_rate: D::read_rate(&validator)?,
+            count: 0,
+        },
+    };
+    if changes.count >= limits.max_changes_per_era() {
+        return Err(Error::DelegationRateChangedTooOften);
+    }
+    // A validator may choose its first rate freely, as nobody delegated relying on an earlier one.
+    if let Some(initial_rate) = changes.initial_rate {
+        if rate > initial_rate.saturating_add(limits.max_increase_per_era()) {
+            return Err(Error::DelegationRateIncreaseTooLarge);
         }
-        S::write(&stakes);
     }
+    changes.count += 1;
+    D::write_rate(&validator, rate);
+    D::write_changes(&validator, &changes);
+    Ok(())
+}
+
+/// Returns the current era duration from the local state of the contract.
+fn get_era_duration() -> u64 {
+    contract_api::read_local(ERA_DURATION_KEY).unwrap_or(DEFAULT_ERA_DURATION)
+}
+
+/// Sets the era duration in the local state of the contract. Only the system account is allowed
+/// to change it, which the installer of the contract does at genesis.
+fn set_era_duration(era_duration: u64) {
+    let caller = contract_api::get_caller();
+    if caller.to_account_address() != SYSTEM_ACCOUNT {
+        contract_api::revert(Error::SystemFunctionCalledByUserAccount.into());
+    }
+    contract_api::write_local(ERA_DURATION_KEY, era_duration);
+}
+
+/// Returns the bond limits from the local state of the contract, or the default limits if none
+/// have been set.
+fn get_bond_limits() -> Result<BondLimits> {
+    match contract_api::read_local::<_, Vec<u8>>(BOND_LIMITS_KEY) {
+        Some(bytes) => {
+            bytesrepr::deserialize(&bytes).map_err(|_| Error::BondLimitsDeserializationFailed)
+        }
+        None => Ok(BondLimits::default()),
+    }
+}
+
+/// Sets the bond limits in the local state of the contract. Only the system account is allowed
+/// to change them, which the installer of the contract does at genesis.
+fn set_bond_limits(bond_limits: &BondLimits) {
+    let caller = contract_api::get_caller();
+    if caller.to_account_address() != SYSTEM_ACCOUNT {
+        contract_api::revert(Error::SystemFunctionCalledByUserAccount.into());
+    }
+    let bytes = bond_limits.to_bytes().expect("Serialization cannot fail");
+    contract_api::write_local(BOND_LIMITS_KEY, bytes);
+}
+
+/// Returns the current unbonding delay from the local state of the contract.
+fn get_unbond_delay() -> u64 {
+    contract_api::read_local(UNBOND_DELAY_KEY).unwrap_or(DEFAULT_UNBOND_DELAY)
+}
 
-    Ok(unbonds)
+/// Sets the unbonding delay in the local state of the contract. Only the
+/// system account is allowed to change it, which the installer of the contract
+/// does at genesis.
+fn set_unbond_delay(unbond_delay: u64) {
+    let caller = contract_api::get_caller();
+    if caller.to_account_address() != SYSTEM_ACCOUNT {
+        contract_api::revert(Error::SystemFunctionCalledByUserAccount.into());
+    }
+    contract_api::write_local(UNBOND_DELAY_KEY, unbond_delay);
 }
 
 /// Attempts to look up a purse from the known_urefs.
@@ -157,6 +383,23 @@ fn get_bonding_purse() -> Result<PurseId> {
     get_purse_id(BONDING_PURSE_KEY).map_err(PurseLookupError::bonding)
 }
 
+/// Returns the purse holding the bonds of `validator`: its own bonding purse if one was created
+/// at genesis, or the shared `bonding_purse` otherwise.
+fn get_validator_bonding_purse(validator: PublicKey, bonding_purse: PurseId) -> Result<PurseId> {
+    let name = format!("{}{}", VALIDATOR_BONDING_PURSE_PREFIX, validator.to_hex());
+    match get_purse_id(&name) {
+        Ok(purse_id) => Ok(purse_id),
+        Err(PurseLookupError::KeyNotFound) => Ok(bonding_purse),
+        Err(err) => Err(PurseLookupError::bonding(err)),
+    }
+}
+
+/// Pays out an unbonding request from the bonding purse of its validator.
+fn pay_unbond(entry: &QueueEntry, bonding_purse: PurseId) -> contract_api::TransferResult {
+    let source = get_validator_bonding_purse(entry.validator, bonding_purse).unwrap_or_revert();
+    contract_api::transfer_from_purse_to_account(source, entry.validator, entry.amount)
+}
+
 /// Returns the purse for holding validator earnings
 fn get_rewards_purse() -> Result<PurseId> {
     get_purse_id(REWARDS_PURSE_KEY).map_err(PurseLookupError::rewards)
@@ -184,15 +427,23 @@ fn get_refund_purse() -> Option<PurseId> {
     }
 }
 
-/// Transfers funds from the payment purse to the validator rewards
-/// purse, as well as to the refund purse, depending on how much
-/// was spent on the computation. This function maintains the invariant
-/// that the balance of the payment purse is zero at the beginning and
-/// end of each deploy and that the refund purse is unset at the beginning
+/// Disposes of the fees paid for a deploy from the payment purse as selected by
+/// `fee_handling`, and transfers the share of the unspent payment given by
+/// `refund_ratio` to the refund purse. The part of the unspent payment which is
+/// not refunded is treated as fees. Fees meant for the proposer go to the
+/// validator rewards purse if the proposer is unknown. This function maintains
+/// the invariant that the balance of the payment purse is zero at the beginning
+/// and end of each deploy and that the refund purse is unset at the beginning
 /// and end of each deploy.
-fn finalize_payment(amount_spent: U512, account: PublicKey) {
+fn finalize_payment(
+    amount_spent: U512,
+    account: PublicKey,
+    fee_handling: FeeHandling,
+    proposer: Option<PublicKey>,
+    refund_ratio: RefundRatio,
+) {
     let caller = contract_api::get_caller();
-    if caller.value() != SYSTEM_ACCOUNT {
+    if caller.to_account_address() != SYSTEM_ACCOUNT {
         contract_api::revert(Error::SystemFunctionCalledByUserAccount.into());
     }
 
@@ -202,23 +453,40 @@ fn finalize_payment(amount_spent: U512, account: PublicKey) {
     if total < amount_spent {
         contract_api::revert(Error::InsufficientPaymentForAmountSpent.into());
     }
-    let refund_amount = total - amount_spent;
+    let refund_amount = refund_ratio.apply(total - amount_spent);
+    let fees = total - refund_amount;
 
     let rewards_purse = get_rewards_purse().unwrap_or_revert();
     let refund_purse = get_refund_purse();
     contract_api::remove_uref(REFUND_PURSE_KEY); //unset refund purse after reading it
 
-    // pay validators
-    if let contract_api::PurseTransferResult::TransferError =
-        contract_api::transfer_from_purse_to_purse(payment_purse, rewards_purse, amount_spent)
-    {
-        contract_api::revert(Error::FailedTransferToRewardsPurse.into());
+    match (fee_handling, proposer) {
+        (FeeHandling::Burn, _) => {
+            if contract_api::burn(payment_purse, fees).is_err() {
+                contract_api::revert(Error::FailedToBurnFees.into());
+            }
+        }
+        (FeeHandling::PayToProposer, Some(proposer)) => {
+            if let contract_api::TransferResult::TransferError(_) =
+                contract_api::transfer_from_purse_to_account(payment_purse, proposer, fees)
+            {
+                contract_api::revert(Error::FailedTransferToProposer.into());
+            }
+        }
+        (FeeHandling::Accumulate, _) | (FeeHandling::PayToProposer, None) => {
+            // pay validators
+            if let contract_api::PurseTransferResult::TransferError(_) =
+                contract_api::transfer_from_purse_to_purse(payment_purse, rewards_purse, fees)
+            {
+                contract_api::revert(Error::FailedTransferToRewardsPurse.into());
+            }
+        }
     }
 
     // give refund
     if !refund_amount.is_zero() {
         if let Some(purse) = refund_purse {
-            if let contract_api::PurseTransferResult::TransferError =
+            if let contract_api::PurseTransferResult::TransferError(_) =
                 contract_api::transfer_from_purse_to_purse(payment_purse, purse, refund_amount)
             {
                 // on case of failure to transfer to refund purse we fall back on the account's
@@ -232,7 +500,7 @@ fn finalize_payment(amount_spent: U512, account: PublicKey) {
 }
 
 fn refund_to_account(payment_purse: PurseId, account: PublicKey, amount: U512) {
-    if let contract_api::TransferResult::TransferError =
+    if let contract_api::TransferResult::TransferError(_) =
         contract_api::transfer_from_purse_to_account(payment_purse, account, amount)
     {
         contract_api::revert(Error::FailedTransferToAccountPurse.into());
@@ -242,6 +510,8 @@ fn refund_to_account(payment_purse: PurseId, account: PublicKey, amount: U512) {
 pub fn delegate() {
     let method_name: String = contract_api::get_arg(0);
     let timestamp = contract_api::get_blocktime();
+    let unbond_delay = get_unbond_delay();
+    let era_duration = get_era_duration();
     let pos_purse = get_bonding_purse().unwrap_or_revert();
 
     match method_name.as_str() {
@@ -254,68 +524,122 @@ pub fn delegate() {
             }
             let source_uref: URef = contract_api::get_arg(2);
             let source = PurseId::new(source_uref);
-            // Transfer `amount` from the `source` purse to PoS internal purse.
-            // POS_PURSE is a constant, it is the PurseID of the proof-of-stake contract's
-            // own purse.
-            if contract_api::PurseTransferResult::TransferError
-                == contract_api::transfer_from_purse_to_purse(source, pos_purse, amount)
+            update_era::<EraLocal, ContractStakes>(timestamp, era_duration).unwrap_or_revert();
+            // Transfer `amount` from the `source` purse to the validator's bonding purse, which
+            // is PoS's own purse unless the validator was given one at genesis.
+            let bonding_purse =
+                get_validator_bonding_purse(validator, pos_purse).unwrap_or_revert();
+            if !contract_api::transfer_from_purse_to_purse(source, bonding_purse, amount)
+                .is_success()
             {
                 contract_api::revert(Error::BondTransferFailed.into());
             }
-            bond::<QueueLocal, ContractStakes>(amount, validator, timestamp).unwrap_or_revert();
-
-            // TODO: Remove this and set nonzero delays once the system calls `step` in each
-            // block.
-            let unbonds = step::<QueueLocal, ContractStakes>(timestamp).unwrap_or_revert();
-            for entry in unbonds {
-                contract_api::transfer_from_purse_to_account(
-                    pos_purse,
-                    entry.validator,
-                    entry.amount,
-                );
-            }
+            let bond_limits = get_bond_limits().unwrap_or_revert();
+            bond::<QueueLocal, ContractStakes>(amount, validator, timestamp, &bond_limits)
+                .unwrap_or_revert();
         }
         // Type of this method: `fn unbond(amount: Option<U512>)`
         "unbond" => {
             let validator = contract_api::get_caller();
             let maybe_amount = contract_api::get_arg(1);
+            update_era::<EraLocal, ContractStakes>(timestamp, era_duration).unwrap_or_revert();
             unbond::<QueueLocal, ContractStakes>(maybe_amount, validator, timestamp)
                 .unwrap_or_revert();
-
-            // TODO: Remove this and set nonzero delays once the system calls `step` in each
-            // block.
-            let unbonds = step::<QueueLocal, ContractStakes>(timestamp).unwrap_or_revert();
-            for entry in unbonds {
-                if contract_api::TransferResult::TransferError
-                    == contract_api::transfer_from_purse_to_account(
-                        pos_purse,
-                        entry.validator,
-                        entry.amount,
-                    )
-                {
-                    contract_api::revert(Error::UnbondTransferFailed.into());
-                }
-            }
         }
         // Type of this method: `fn step()`
         "step" => {
-            // This is called by the system in every block.
-            let unbonds = step::<QueueLocal, ContractStakes>(timestamp).unwrap_or_revert();
+            // This is called by the system in every block, and only the system account is allowed
+            // to call it.
+            let caller = contract_api::get_caller();
+            if caller.to_account_address() != SYSTEM_ACCOUNT {
+                contract_api::revert(Error::SystemFunctionCalledByUserAccount.into());
+            }
+            update_era::<EraLocal, ContractStakes>(timestamp, era_duration).unwrap_or_revert();
+            let bond_limits = get_bond_limits().unwrap_or_revert();
+            let payouts = step::<QueueLocal, ContractStakes>(
+                timestamp,
+                unbond_delay,
+                MAX_REQUESTS_PER_STEP,
+                &bond_limits,
+            )
+            .unwrap_or_revert();
 
             // Mateusz: Moved outside of `step` function so that it [step] can be unit
-            // tested.
-            for entry in unbonds {
+            // tested. Dropped bonds are refunded the same way as unbonds are paid out.
+            for entry in payouts {
                 // TODO: We currently ignore `TransferResult::TransferError`s here, since we
                 // can't recover from them and we shouldn't retry indefinitely.
                 // That would mean the contract just keeps the money forever,
                 // though.
-                contract_api::transfer_from_purse_to_account(
-                    pos_purse,
-                    entry.validator,
-                    entry.amount,
-                );
+                pay_unbond(&entry, pos_purse);
             }
         }
+        // Type of this method: `fn process_unbond_requests()`
+        "process_unbond_requests" => {
+            // Pays out all unbonding requests whose delay has passed. Anyone may call this,
+            // since the motes are only ever sent to the unbonded validators.
+            let unbonds = process_unbond_requests::<QueueLocal>(timestamp, unbond_delay);
+            for entry in unbonds {
+                if !pay_unbond(&entry, pos_purse).is_success() {
+                    contract_api::revert(Error::UnbondTransferFailed.into());
+                }
+            }
+        }
+        // Type of this method: `fn get_queues() -> Queues`, where `Queues` holds the pending bonds,
+        // the requested unbonds and the unbonds awaiting payout, each serialized as a list of
+        // `(PublicKey, U512, BlockTime)` entries.
+        "get_queues" => {
+            contract_api::ret(&Queues::read::<QueueLocal>(), &Vec::new());
+        }
+        // Type of this method: `fn get_unbond_delay() -> u64`
+        "get_unbond_delay" => {
+            contract_api::ret(&unbond_delay, &Vec::new());
+        }
+        // Type of this method: `fn set_unbond_delay(unbond_delay: u64)`
+        "set_unbond_delay" => {
+            let unbond_delay: u64 = contract_api::get_arg(1);
+            set_unbond_delay(unbond_delay);
+        }
+        // Type of this method: `fn set_delegation_rate(rate: DelegationRate)`
+        "set_delegation_rate" => {
+            let validator = contract_api::get_caller();
+            let rate: DelegationRate = contract_api::get_arg(1);
+            let era =
+                update_era::<EraLocal, ContractStakes>(timestamp, era_duration).unwrap_or_revert();
+            set_delegation_rate::<ContractDelegationRates, ContractStakes>(validator, rate, era)
+                .unwrap_or_revert();
+        }
+        // Type of this method: `fn get_era() -> Option<EraId>`
+        "get_era" => {
+            contract_api::ret(&EraLocal::read_current_era(), &Vec::new());
+        }
+        // Type of this method: `fn get_era_snapshot(era: EraId) -> Option<Stakes>`, where `Stakes`
+        // is the `BTreeMap<PublicKey, U512>` of the motes each validator had bonded when `era`
+        // started.
+        "get_era_snapshot" => {
+            let era: EraId = contract_api::get_arg(1);
+            let snapshot = EraLocal::read_snapshot(era).map(|stakes| stakes.0);
+            contract_api::ret(&snapshot, &Vec::new());
+        }
+        // Type of this method: `fn get_era_duration() -> u64`
+        "get_era_duration" => {
+            contract_api::ret(&era_duration, &Vec::new());
+        }
+        // Type of this method: `fn set_era_duration(era_duration: u64)`
+        "set_era_duration" => {
+            let era_duration: u64 = contract_api::get_arg(1);
+            set_era_duration(era_duration);
+        }
+        // Type of this method: `fn get_bond_limits() -> BondLimits`
+        "get_bond_limits" => {
+            let bond_limits = get_bond_limits().unwrap_or_revert();
+            contract_api::ret(&bond_limits, &Vec::new());
+        }
+        // Type of this method: `fn set_bond_limits(bond_limits: BondLimits)`
+        "set_bond_limits" => {
+            let bond_limits: BondLimits = contract_api::get_arg(1);
+            set_bond_limits(&bond_limits);
+        }
         "get_payment_purse" => {
             let purse = get_payment_purse().unwrap_or_revert();
             // Limit the access rights so only balance query and deposit are allowed.
@@ -344,7 +668,10 @@ pub fn delegate() {
         "finalize_payment" => {
             let amount_spent: U512 = contract_api::get_arg(1);
             let account: PublicKey = contract_api::get_arg(2);
-            finalize_payment(amount_spent, account);
+            let fee_handling: FeeHandling = contract_api::get_arg(3);
+            let proposer: Option<PublicKey> = contract_api::get_arg(4);
+            let refund_ratio: RefundRatio = contract_api::get_arg(5);
+            finalize_payment(amount_spent, account, fee_handling, proposer, refund_ratio);
         }
         _ => {}
     }
@@ -359,27 +686,43 @@ pub extern "C" fn call() {
 #[cfg(test)]
 mod tests {
     use std::cell::RefCell;
+    use std::collections::BTreeMap;
     use std::iter;
 
+    use contract_ffi::system_contracts::pos::{
+        BondLimits, DelegationRate, DelegationRateLimits, EraId, DEFAULT_UNBOND_DELAY,
+    };
     use contract_ffi::value::{
         account::{BlockTime, PublicKey},
         U512,
     };
 
-    use crate::error::Result;
+    use crate::delegation::{DelegationRateChanges, DelegationRateProvider};
+    use crate::era::EraProvider;
+    use crate::error::{Error, Result};
     use crate::queue::{Queue, QueueProvider};
     use crate::stakes::{Stakes, StakesProvider};
-    use crate::{bond, step, unbond, BOND_DELAY, UNBOND_DELAY};
+    use crate::{
+        bond, process_unbond_requests, set_delegation_rate, step, unbond, update_era, BOND_DELAY,
+        MAX_REQUESTS_PER_STEP,
+    };
 
     const KEY1: [u8; 32] = [1; 32];
     const KEY2: [u8; 32] = [2; 32];
+    const KEY3: [u8; 32] = [3; 32];
 
     thread_local! {
         static BONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
         static UNBONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
+        static REQUESTED_UNBONDS: RefCell<Queue> = RefCell::new(Queue(Default::default()));
         static STAKES: RefCell<Stakes> = RefCell::new(
             Stakes(iter::once((PublicKey::new(KEY1), U512::from(1_000))).collect())
         );
+        static CURRENT_ERA: RefCell<Option<EraId>> = RefCell::new(None);
+        static SNAPSHOTS: RefCell<BTreeMap<EraId, Stakes>> = RefCell::new(BTreeMap::new());
+        static RATES: RefCell<BTreeMap<PublicKey, DelegationRate>> = RefCell::new(BTreeMap::new());
+        static RATE_CHANGES: RefCell<BTreeMap<PublicKey, DelegationRateChanges>> =
+            RefCell::new(BTreeMap::new());
     }
 
     struct TestQueues;
@@ -393,6 +736,10 @@ mod tests {
             UNBONDING.with(|ub| ub.borrow().clone())
         }
 
+        fn read_requested_unbonds() -> Queue {
+            REQUESTED_UNBONDS.with(|ru| ru.borrow().clone())
+        }
+
         fn write_bonding(queue: &Queue) {
             BONDING.with(|b| b.replace(queue.clone()));
         }
@@ -400,6 +747,10 @@ mod tests {
         fn write_unbonding(queue: &Queue) {
             UNBONDING.with(|ub| ub.replace(queue.clone()));
         }
+
+        fn write_requested_unbonds(queue: &Queue) {
+            REQUESTED_UNBONDS.with(|ru| ru.replace(queue.clone()));
+        }
     }
 
     struct TestStakes;
@@ -414,6 +765,51 @@ mod tests {
         }
     }
 
+    struct TestEras;
+
+    impl EraProvider for TestEras {
+        fn read_current_era() -> Option<EraId> {
+            CURRENT_ERA.with(|era| *era.borrow())
+        }
+
+        fn write_current_era(era: EraId) {
+            CURRENT_ERA.with(|current_era| current_era.replace(Some(era)));
+        }
+
+        fn read_snapshot(era: EraId) -> Option<Stakes> {
+            SNAPSHOTS.with(|snapshots| snapshots.borrow().get(&era).cloned())
+        }
+
+        fn write_snapshot(era: EraId, stakes: &Stakes) {
+            SNAPSHOTS.with(|snapshots| snapshots.borrow_mut().insert(era, stakes.clone()));
+        }
+    }
+
+    struct TestDelegationRates;
+
+    impl DelegationRateProvider for TestDelegationRates {
+        fn read_rate(validator: &PublicKey) -> Result<Option<DelegationRate>> {
+            RATES.with(|rates| Ok(rates.borrow().get(validator).cloned()))
+        }
+
+        fn write_rate(validator: &PublicKey, rate: DelegationRate) {
+            RATES.with(|rates| rates.borrow_mut().insert(*validator, rate));
+        }
+
+        fn read_limits() -> Result<DelegationRateLimits> {
+            Ok(DelegationRateLimits::new(5, 2).unwrap())
+        }
+
+        fn read_changes(validator: &PublicKey) -> Option<DelegationRateChanges> {
+            RATE_CHANGES.with(|changes| changes.borrow().get(validator).cloned())
+        }
+
+        fn write_changes(validator: &PublicKey, changes: &DelegationRateChanges) {
+            RATE_CHANGES
+                .with(|rate_changes| rate_changes.borrow_mut().insert(*validator, *changes));
+        }
+    }
+
     fn assert_stakes(stakes: &[([u8; 32], usize)]) {
         let expected = Stakes(
             stakes
@@ -426,22 +822,273 @@ mod tests {
 
     #[test]
     fn test_bond_step_unbond() {
-        bond::<TestQueues, TestStakes>(U512::from(500), PublicKey::new(KEY2), BlockTime(1))
-            .expect("bond validator 2");
+        bond::<TestQueues, TestStakes>(
+            U512::from(500),
+            PublicKey::new(KEY2),
+            BlockTime(1),
+            &BondLimits::default(),
+        )
+        .expect("bond validator 2");
 
         // Bonding becomes effective only after the delay.
         assert_stakes(&[(KEY1, 1_000)]);
-        step::<TestQueues, TestStakes>(BlockTime(BOND_DELAY)).expect("step 1");
+        step::<TestQueues, TestStakes>(
+            BlockTime(BOND_DELAY),
+            DEFAULT_UNBOND_DELAY,
+            MAX_REQUESTS_PER_STEP,
+            &BondLimits::default(),
+        )
+        .expect("step 1");
         assert_stakes(&[(KEY1, 1_000)]);
-        step::<TestQueues, TestStakes>(BlockTime(1 + BOND_DELAY)).expect("step 2");
+        step::<TestQueues, TestStakes>(
+            BlockTime(1 + BOND_DELAY),
+            DEFAULT_UNBOND_DELAY,
+            MAX_REQUESTS_PER_STEP,
+            &BondLimits::default(),
+        )
+        .expect("step 2");
         assert_stakes(&[(KEY1, 1_000), (KEY2, 500)]);
 
         unbond::<TestQueues, TestStakes>(Some(U512::from(500)), PublicKey::new(KEY1), BlockTime(2))
             .expect("partly unbond validator 1");
 
-        // Unbonding becomes effective immediately.
-        assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
-        step::<TestQueues, TestStakes>(BlockTime(2 + UNBOND_DELAY)).expect("step 3");
+        // Unbonding becomes effective in the next step.
+        assert_stakes(&[(KEY1, 1_000), (KEY2, 500)]);
+        let unbonds = step::<TestQueues, TestStakes>(
+            BlockTime(2 + DEFAULT_UNBOND_DELAY),
+            DEFAULT_UNBOND_DELAY,
+            MAX_REQUESTS_PER_STEP,
+            &BondLimits::default(),
+        )
+        .expect("step 3");
+        assert_eq!(1, unbonds.len());
         assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
     }
+
+    #[test]
+    fn test_unbond_delay() {
+        const DELAY: u64 = 10;
+
+        unbond::<TestQueues, TestStakes>(Some(U512::from(100)), PublicKey::new(KEY1), BlockTime(5))
+            .expect("partly unbond validator 1");
+        assert!(step::<TestQueues, TestStakes>(
+            BlockTime(5),
+            DELAY,
+            MAX_REQUESTS_PER_STEP,
+            &BondLimits::default()
+        )
+        .expect("step 1")
+        .is_empty());
+        assert_stakes(&[(KEY1, 900)]);
+
+        // The unbonded motes are not released before the delay has passed.
+        assert!(process_unbond_requests::<TestQueues>(BlockTime(5), DELAY).is_empty());
+        assert!(process_unbond_requests::<TestQueues>(BlockTime(5 + DELAY - 1), DELAY).is_empty());
+        assert!(step::<TestQueues, TestStakes>(
+            BlockTime(5 + DELAY - 1),
+            DELAY,
+            MAX_REQUESTS_PER_STEP,
+            &BondLimits::default()
+        )
+        .expect("step 2")
+        .is_empty());
+
+        let unbonds = process_unbond_requests::<TestQueues>(BlockTime(5 + DELAY), DELAY);
+        assert_eq!(1, unbonds.len());
+        assert_eq!(PublicKey::new(KEY1), unbonds[0].validator);
+        assert_eq!(U512::from(100), unbonds[0].amount);
+
+        // Matured entries are removed from the queue.
+        assert!(process_unbond_requests::<TestQueues>(BlockTime(5 + DELAY), DELAY).is_empty());
+        assert_stakes(&[(KEY1, 900)]);
+    }
+
+    #[test]
+    fn test_era_snapshots_by_block_time() {
+        const ERA_DURATION: u64 = 10;
+
+        // The first era starts with the first change of the stakes.
+        update_era::<TestEras, TestStakes>(BlockTime(25), ERA_DURATION).expect("update era 1");
+        assert_eq!(Some(2), TestEras::read_current_era());
+        unbond::<TestQueues, TestStakes>(
+            Some(U512::from(100)),
+            PublicKey::new(KEY1),
+            BlockTime(25),
+        )
+        .expect("partly unbond validator 1");
+        step::<TestQueues, TestStakes>(
+            BlockTime(25),
+            0,
+            MAX_REQUESTS_PER_STEP,
+            &BondLimits::default(),
+        )
+        .expect("step");
+
+        // Changes within an era don't affect its snapshot.
+        update_era::<TestEras, TestStakes>(BlockTime(29), ERA_DURATION).expect("update era 2");
+        assert_eq!(Some(2), TestEras::read_current_era());
+        assert_eq!(
+            Some(Stakes(
+                iter::once((PublicKey::new(KEY1), U512::from(1_000))).collect()
+            )),
+            TestEras::read_snapshot(2)
+        );
+
+        // Eras without changes of the stakes are skipped.
+        update_era::<TestEras, TestStakes>(BlockTime(45), ERA_DURATION).expect("update era 3");
+        assert_eq!(Some(4), TestEras::read_current_era());
+        assert_eq!(None, TestEras::read_snapshot(3));
+        assert_eq!(
+            Some(Stakes(
+                iter::once((PublicKey::new(KEY1), U512::from(900))).collect()
+            )),
+            TestEras::read_snapshot(4)
+        );
+    }
+
+    #[test]
+    fn test_era_never_goes_back() {
+        // Without an era duration, the first era never ends.
+        update_era::<TestEras, TestStakes>(BlockTime(100), 0).expect("update era 1");
+        assert_eq!(Some(0), TestEras::read_current_era());
+        update_era::<TestEras, TestStakes>(BlockTime(200), 0).expect("update era 2");
+        assert_eq!(Some(0), TestEras::read_current_era());
+
+        // Eras never go back to the one an earlier block time falls in.
+        update_era::<TestEras, TestStakes>(BlockTime(35), 10).expect("update era 3");
+        assert_eq!(Some(3), TestEras::read_current_era());
+        update_era::<TestEras, TestStakes>(BlockTime(15), 10).expect("update era 4");
+        assert_eq!(Some(3), TestEras::read_current_era());
+    }
+
+    #[test]
+    fn test_set_delegation_rate() {
+        let validator = PublicKey::new(KEY1);
+        let set_rate = |rate, era| {
+            set_delegation_rate::<TestDelegationRates, TestStakes>(validator, rate, era)
+        };
+
+        assert_eq!(Err(Error::DelegationRateTooHigh), set_rate(101, 0));
+        assert_eq!(
+            Err(Error::NotBonded),
+            set_delegation_rate::<TestDelegationRates, TestStakes>(PublicKey::new(KEY2), 10, 0)
+        );
+
+        // The first rate may be chosen freely, but only two changes are allowed per era.
+        assert_eq!(Ok(()), set_rate(50, 0));
+        assert_eq!(Ok(()), set_rate(60, 0));
+        assert_eq!(Err(Error::DelegationRateChangedTooOften), set_rate(70, 0));
+        assert_eq!(Ok(Some(60)), TestDelegationRates::read_rate(&validator));
+
+        // Increases are capped relative to the rate the era started with.
+        assert_eq!(Err(Error::DelegationRateIncreaseTooLarge), set_rate(66, 1));
+        assert_eq!(Ok(()), set_rate(65, 1));
+        assert_eq!(Ok(()), set_rate(10, 1));
+        assert_eq!(Err(Error::DelegationRateChangedTooOften), set_rate(20, 1));
+
+        assert_eq!(Err(Error::DelegationRateIncreaseTooLarge), set_rate(16, 2));
+        assert_eq!(Ok(()), set_rate(15, 2));
+        assert_eq!(Ok(Some(15)), TestDelegationRates::read_rate(&validator));
+    }
+
+    #[test]
+    fn test_bond_limits_count_queued_bonds() {
+        let bond_limits = BondLimits::new(U512::from(100), 0, 2).unwrap();
+        assert_eq!(
+            Err(Error::BondBelowMinimum),
+            bond::<TestQueues, TestStakes>(
+                U512::from(99),
+                PublicKey::new(KEY2),
+                BlockTime(1),
+                &bond_limits
+            )
+        );
+        bond::<TestQueues, TestStakes>(
+            U512::from(100),
+            PublicKey::new(KEY2),
+            BlockTime(1),
+            &bond_limits,
+        )
+        .expect("bond validator 2");
+
+        // Validator 2 is not bonded yet, but its queued bond already counts towards the limit.
+        assert_eq!(
+            Err(Error::TooManyValidators),
+            bond::<TestQueues, TestStakes>(
+                U512::from(100),
+                PublicKey::new([3; 32]),
+                BlockTime(2),
+                &bond_limits
+            )
+        );
+    }
+
+    #[test]
+    fn test_step_applies_requests_in_order() {
+        bond::<TestQueues, TestStakes>(
+            U512::from(500),
+            PublicKey::new(KEY2),
+            BlockTime(1),
+            &BondLimits::default(),
+        )
+        .expect("bond validator 2");
+        // Unbonding may rely on a bond which is still queued.
+        unbond::<TestQueues, TestStakes>(Some(U512::from(200)), PublicKey::new(KEY2), BlockTime(1))
+            .expect("partly unbond validator 2");
+        bond::<TestQueues, TestStakes>(
+            U512::from(300),
+            PublicKey::new(KEY3),
+            BlockTime(2),
+            &BondLimits::default(),
+        )
+        .expect("bond validator 3");
+        assert_stakes(&[(KEY1, 1_000)]);
+
+        // Only two requests are applied per step here, the bond going before the unbond.
+        let unbonds = step::<TestQueues, TestStakes>(BlockTime(2), 0, 2, &BondLimits::default())
+            .expect("step 1");
+        assert_eq!(1, unbonds.len());
+        assert_eq!(PublicKey::new(KEY2), unbonds[0].validator);
+        assert_eq!(U512::from(200), unbonds[0].amount);
+        assert_stakes(&[(KEY1, 1_000), (KEY2, 300)]);
+        assert_eq!(1, TestQueues::read_bonding().0.len());
+        assert!(TestQueues::read_requested_unbonds().0.is_empty());
+
+        assert!(
+            step::<TestQueues, TestStakes>(BlockTime(2), 0, 2, &BondLimits::default())
+                .expect("step 2")
+                .is_empty()
+        );
+        assert_stakes(&[(KEY1, 1_000), (KEY2, 300), (KEY3, 300)]);
+        assert!(TestQueues::read_bonding().0.is_empty());
+    }
+
+    #[test]
+    fn test_step_refunds_bonds_exceeding_bond_limits() {
+        bond::<TestQueues, TestStakes>(
+            U512::from(500),
+            PublicKey::new(KEY2),
+            BlockTime(1),
+            &BondLimits::default(),
+        )
+        .expect("bond validator 2");
+        bond::<TestQueues, TestStakes>(
+            U512::from(300),
+            PublicKey::new(KEY3),
+            BlockTime(2),
+            &BondLimits::default(),
+        )
+        .expect("bond validator 3");
+
+        // The limits allow only one more validator by the time the bonds are applied.
+        let bond_limits = BondLimits::new(U512::from(100), 0, 2).unwrap();
+        let refunds =
+            step::<TestQueues, TestStakes>(BlockTime(2), 0, MAX_REQUESTS_PER_STEP, &bond_limits)
+                .expect("step");
+        assert_eq!(1, refunds.len());
+        assert_eq!(PublicKey::new(KEY3), refunds[0].validator);
+        assert_eq!(U512::from(300), refunds[0].amount);
+        assert_stakes(&[(KEY1, 1_000), (KEY2, 500)]);
+        assert!(TestQueues::read_bonding().0.is_empty());
+    }
 }
@@ -11,6 +11,7 @@ use crate::error::{Error, Result};
 
 const BONDING_KEY: u8 = 1;
 const UNBONDING_KEY: u8 = 2;
+const REQUESTED_UNBONDS_KEY: u8 = 8;
 
 /// A pending entry in the bonding or unbonding queue.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -64,11 +65,17 @@ pub trait QueueProvider {
     /// Reads unbonding queue.
     fn read_unbonding() -> Queue;
 
+    /// Reads the queue of unbonding requests not yet deducted from the stakes.
+    fn read_requested_unbonds() -> Queue;
+
     /// Writes bonding queue.
     fn write_bonding(queue: &Queue);
 
     /// Writes unbonding queue.
     fn write_unbonding(queue: &Queue);
+
+    /// Writes the queue of unbonding requests not yet deducted from the stakes.
+    fn write_requested_unbonds(queue: &Queue);
 }
 
 /// A `QueueProvider` that reads and writes the queue to/from the contract's
@@ -86,6 +93,11 @@ impl QueueProvider for QueueLocal {
         contract_api::read_local(UNBONDING_KEY).unwrap_or_default()
     }
 
+    /// Reads the requested unbonds from the local state of the contract.
+    fn read_requested_unbonds() -> Queue {
+        contract_api::read_local(REQUESTED_UNBONDS_KEY).unwrap_or_default()
+    }
+
     /// Writes bonding queue to the local state of the contract.
     fn write_bonding(queue: &Queue) {
         contract_api::write_local(BONDING_KEY, queue);
@@ -95,6 +107,11 @@ impl QueueProvider for QueueLocal {
     fn write_unbonding(queue: &Queue) {
         contract_api::write_local(UNBONDING_KEY, queue);
     }
+
+    /// Writes the requested unbonds to the local state of the contract.
+    fn write_requested_unbonds(queue: &Queue) {
+        contract_api::write_local(REQUESTED_UNBONDS_KEY, queue);
+    }
 }
 
 /// A queue of bonding or unbonding requests, sorted by timestamp in ascending
@@ -119,6 +136,11 @@ impl Queue {
         Ok(())
     }
 
+    /// Returns the first entry if it is at least as old as the specified timestamp.
+    pub fn first_due(&self, timestamp: BlockTime) -> Option<&QueueEntry> {
+        self.0.first().filter(|entry| entry.timestamp <= timestamp)
+    }
+
     /// Returns all queue entries at least as old as the specified timestamp.
     pub fn pop_due(&mut self, timestamp: BlockTime) -> Vec<QueueEntry> {
         let (older_than, rest) = self
@@ -176,13 +198,59 @@ impl ToBytes for Queue {
     }
 }
 
+/// The contents of all queues of the contract.
+#[derive(Clone, Default)]
+pub struct Queues {
+    /// The bonding requests not yet added to the stakes.
+    pub bonding: Queue,
+    /// The unbonding requests not yet deducted from the stakes.
+    pub requested_unbonds: Queue,
+    /// The unbonds deducted from the stakes, waiting to be paid out.
+    pub unbonding: Queue,
+}
+
+impl Queues {
+    /// Reads all queues from `Q`.
+    pub fn read<Q: QueueProvider>() -> Queues {
+        Queues {
+            bonding: Q::read_bonding(),
+            requested_unbonds: Q::read_requested_unbonds(),
+            unbonding: Q::read_unbonding(),
+        }
+    }
+}
+
+impl FromBytes for Queues {
+    fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
+        let (bonding, bytes) = Queue::from_bytes(bytes)?;
+        let (requested_unbonds, bytes) = Queue::from_bytes(bytes)?;
+        let (unbonding, bytes) = Queue::from_bytes(bytes)?;
+        let queues = Queues {
+            bonding,
+            requested_unbonds,
+            unbonding,
+        };
+        Ok((queues, bytes))
+    }
+}
+
+impl ToBytes for Queues {
+    fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
+        Ok((self.bonding.to_bytes()?.into_iter())
+            .chain(self.requested_unbonds.to_bytes()?)
+            .chain(self.unbonding.to_bytes()?)
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use contract_ffi::bytesrepr::{FromBytes, ToBytes};
     use contract_ffi::value::account::{BlockTime, PublicKey};
     use contract_ffi::value::U512;
 
     use crate::error::Error;
-    use crate::queue::{Queue, QueueEntry};
+    use crate::queue::{Queue, QueueEntry, Queues};
 
     const KEY1: [u8; 32] = [1; 32];
     const KEY2: [u8; 32] = [2; 32];
@@ -227,4 +295,46 @@ mod tests {
             queue.pop_due(BlockTime(105))
         );
     }
+
+    #[test]
+    fn test_first_due() {
+        let mut queue: Queue = Default::default();
+        assert_eq!(None, queue.first_due(BlockTime(100)));
+        assert_eq!(
+            Ok(()),
+            queue.push(PublicKey::new(KEY1), U512::from(5), BlockTime(100))
+        );
+        assert_eq!(None, queue.first_due(BlockTime(99)));
+        assert_eq!(
+            Some(&QueueEntry::new(
+                PublicKey::new(KEY1),
+                U512::from(5),
+                BlockTime(100)
+            )),
+            queue.first_due(BlockTime(100))
+        );
+    }
+
+    #[test]
+    fn test_queues_round_trip() {
+        let mut queues: Queues = Default::default();
+        assert_eq!(
+            Ok(()),
+            queues
+                .bonding
+                .push(PublicKey::new(KEY1), U512::from(5), BlockTime(100))
+        );
+        assert_eq!(
+            Ok(()),
+            queues
+                .unbonding
+                .push(PublicKey::new(KEY2), U512::from(6), BlockTime(101))
+        );
+        let bytes = queues.to_bytes().expect("should serialize");
+        let (deserialized, rest) = Queues::from_bytes(&bytes).expect("should deserialize");
+        assert!(rest.is_empty());
+        assert_eq!(queues.bonding.0, deserialized.bonding.0);
+        assert!(deserialized.requested_unbonds.0.is_empty());
+        assert_eq!(queues.unbonding.0, deserialized.unbonding.0);
+    }
 }
@@ -1,10 +1,16 @@
 use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
 use core::fmt::Write;
+use core::result;
 
+use contract_ffi::base16;
+use contract_ffi::bytesrepr::{self, FromBytes, ToBytes};
 use contract_ffi::contract_api;
 use contract_ffi::key::Key;
-use contract_ffi::value::{account::PublicKey, U512};
+use contract_ffi::system_contracts::pos::BondLimits;
+use contract_ffi::value::{account::PublicKey, Value, U512};
 
 use crate::error::{Error, Result};
 
@@ -31,12 +37,10 @@ impl StakesProvider for ContractStakes {
             let hex_key = split_name
                 .next()
                 .ok_or(Error::StakesKeyDeserializationFailed)?;
-            let mut key_bytes = [0u8; 32];
-            for i in 0..32 {
-                key_bytes[i] = u8::from_str_radix(&hex_key[2 * i..2 * (i + 1)], 16)
-                    .map_err(|_| Error::StakesKeyDeserializationFailed)?;
-            }
-            let pub_key = PublicKey::new(key_bytes);
+            let key_bytes =
+                base16::decode_lower(hex_key).map_err(|_| Error::StakesKeyDeserializationFailed)?;
+            let pub_key = PublicKey::try_from(key_bytes.as_slice())
+                .map_err(|_| Error::StakesKeyDeserializationFailed)?;
             let balance = split_name
                 .next()
                 .and_then(|b| U512::from_dec_str(b).ok())
@@ -56,11 +60,7 @@ impl StakesProvider for ContractStakes {
             .0
             .iter()
             .map(|(pub_key, balance)| {
-                let key_bytes = pub_key.value();
-                let mut hex_key = String::with_capacity(64);
-                for byte in &key_bytes[..32] {
-                    write!(hex_key, "{:02x}", byte).expect("Writing to a string cannot fail");
-                }
+                let hex_key = pub_key.to_hex();
                 let mut uref = String::new();
                 uref.write_fmt(format_args!("v_{}_{}", hex_key, balance))
                     .expect("Writing to a string cannot fail");
@@ -165,6 +165,23 @@ impl Stakes {
         Ok(())
     }
 
+    /// Returns an error if bonding `amount` would leave the validator with a stake outside the
+    /// bond limits, or make it one validator too many.
+    pub fn validate_bond_limits(
+        &self,
+        validator: &PublicKey,
+        amount: U512,
+        bond_limits: &BondLimits,
+    ) -> Result<()> {
+        match self.0.get(validator) {
+            Some(stake) => bond_limits.check_stake(*stake + amount),
+            None => {
+                bond_limits.check_stake(amount)?;
+                bond_limits.check_validator_count(self.0.len() + 1)
+            }
+        }
+    }
+
     /// Returns the minimum stake of the _other_ validators.
     fn min_without(&self, validator: &PublicKey) -> Option<U512> {
         self.0
@@ -193,9 +210,47 @@ impl Stakes {
     }
 }
 
+/// Era snapshots store the stakes in the contract's local state as a byte array.
+impl TryFrom<Value> for Stakes {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        let bytes = match value {
+            Value::ByteArray(bytes) => bytes,
+            _ => return Err(Error::EraSnapshotNotStoredAsByteArray),
+        };
+        match Stakes::from_bytes(&bytes) {
+            Ok((stakes, rest)) if rest.is_empty() => Ok(stakes),
+            _ => Err(Error::EraSnapshotDeserializationFailed),
+        }
+    }
+}
+
+impl Into<Value> for &Stakes {
+    fn into(self) -> Value {
+        Value::ByteArray(self.to_bytes().expect("Serialization cannot fail"))
+    }
+}
+
+impl FromBytes for Stakes {
+    fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
+        let (stakes, bytes) = BTreeMap::from_bytes(bytes)?;
+        Ok((Stakes(stakes), bytes))
+    }
+}
+
+impl ToBytes for Stakes {
+    fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use contract_ffi::value::{account::PublicKey, U512};
+    use core::convert::TryFrom;
+
+    use contract_ffi::system_contracts::pos::BondLimits;
+    use contract_ffi::value::{account::PublicKey, Value, U512};
 
     use crate::error::Error;
     use crate::stakes::Stakes;
@@ -223,6 +278,37 @@ mod tests {
         assert_eq!(new_stakes(&[(KEY1, 5), (KEY2, 100)]), stakes);
     }
 
+    #[test]
+    fn test_bond_limits() {
+        let stakes = new_stakes(&[(KEY1, 50), (KEY2, 100)]);
+        let bond_limits = BondLimits::new(U512::from(10), 20, 2).unwrap();
+        let key1 = PublicKey::new(KEY1);
+        let key3 = PublicKey::new([3; 32]);
+
+        assert_eq!(
+            Ok(()),
+            stakes.validate_bond_limits(&key1, U512::from(150), &bond_limits)
+        );
+        assert_eq!(
+            Err(Error::BondAboveMaximum),
+            stakes.validate_bond_limits(&key1, U512::from(151), &bond_limits)
+        );
+        assert_eq!(
+            Err(Error::TooManyValidators),
+            stakes.validate_bond_limits(&key3, U512::from(10), &bond_limits)
+        );
+
+        let bond_limits = BondLimits::new(U512::from(10), 20, 3).unwrap();
+        assert_eq!(
+            Err(Error::BondBelowMinimum),
+            stakes.validate_bond_limits(&key3, U512::from(9), &bond_limits)
+        );
+        assert_eq!(
+            Ok(()),
+            stakes.validate_bond_limits(&key3, U512::from(10), &bond_limits)
+        );
+    }
+
     #[test]
     fn test_bond_existing() {
         let mut stakes = new_stakes(&[(KEY1, 50), (KEY2, 100)]);
@@ -306,4 +392,19 @@ mod tests {
             "Failed to unbond the maximum amount."
         );
     }
+
+    #[test]
+    fn test_value_round_trip() {
+        let stakes = new_stakes(&[(KEY1, 999), (KEY2, 1)]);
+        let value: Value = (&stakes).into();
+        assert_eq!(Ok(stakes), Stakes::try_from(value));
+        assert_eq!(
+            Err(Error::EraSnapshotNotStoredAsByteArray),
+            Stakes::try_from(Value::UInt64(1))
+        );
+        assert_eq!(
+            Err(Error::EraSnapshotDeserializationFailed),
+            Stakes::try_from(Value::ByteArray(vec![1]))
+        );
+    }
 }
@@ -0,0 +1,33 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api::{self, safe};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::{CLValue, U512};
+
+const CL_VALUE_KEY: &str = "cl_value";
+const FLAG_KEY: &str = "flag";
+
+enum Error {
+    UnexpectedFlag = 1,
+}
+
+/// Stores the `(String, U512)` passed as a `CLValue` in arg 0 under a new uref, along with a
+/// `bool` flag which is read back to check it round-trips through global state.
+#[no_mangle]
+pub extern "C" fn call() {
+    let pair: (String, U512) = safe::get_typed_arg(0).unwrap_or_revert();
+    let cl_value = CLValue::from_t(&pair).unwrap_or_revert_with(safe::ApiError::Serialize);
+    let cl_value_pointer = contract_api::new_uref(cl_value);
+    contract_api::add_uref(CL_VALUE_KEY, &cl_value_pointer.into());
+
+    let flag_pointer = contract_api::new_uref(true);
+    contract_api::add_uref(FLAG_KEY, &flag_pointer.into());
+    if !contract_api::read(flag_pointer) {
+        safe::revert(safe::ApiError::User(Error::UnexpectedFlag as u16));
+    }
+}
@@ -0,0 +1,60 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api::{self, safe};
+use contract_ffi::key::Key;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::uref::{AccessRights, URef};
+
+const DICTIONARY_NAME: &str = "dictionary";
+const READ_ONLY_DICTIONARY_NAME: &str = "dictionary_read_only";
+const ITEM_KEY: &str = "greeting";
+const MISSING_ITEM_KEY: &str = "missing";
+
+enum Error {
+    UnexpectedItem = 1,
+    UnexpectedMissingItem = 2,
+}
+
+/// Creates a dictionary holding a greeting, along with a read-only reference to it.
+fn create() {
+    let seed = contract_api::new_dictionary(DICTIONARY_NAME);
+    contract_api::dictionary_put(seed, ITEM_KEY, String::from("hello"));
+
+    let item: Option<String> = safe::dictionary_get(seed, ITEM_KEY).unwrap_or_revert();
+    if item.as_ref().map(String::as_str) != Some("hello") {
+        safe::revert(safe::ApiError::User(Error::UnexpectedItem as u16));
+    }
+    let missing_item: Option<String> =
+        safe::dictionary_get(seed, MISSING_ITEM_KEY).unwrap_or_revert();
+    if missing_item.is_some() {
+        safe::revert(safe::ApiError::User(Error::UnexpectedMissingItem as u16));
+    }
+
+    let read_only_seed = URef::new(seed.addr(), AccessRights::READ);
+    contract_api::add_uref(READ_ONLY_DICTIONARY_NAME, &Key::URef(read_only_seed));
+}
+
+/// Tries to overwrite the greeting through the read-only reference.
+fn put_read_only() {
+    let key = safe::get_uref(READ_ONLY_DICTIONARY_NAME).unwrap_or_revert();
+    let seed = key
+        .as_uref()
+        .cloned()
+        .unwrap_or_revert_with(safe::ApiError::UnexpectedKeyVariant);
+    contract_api::dictionary_put(seed, ITEM_KEY, String::from("goodbye"));
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let action: String = safe::get_arg(0).unwrap_or_revert();
+    match action.as_str() {
+        "create" => create(),
+        "put_read_only" => put_read_only(),
+        _ => safe::revert(safe::ApiError::InvalidArgument),
+    }
+}
@@ -5,7 +5,7 @@ extern crate alloc;
 
 extern crate contract_ffi;
 
-use contract_ffi::contract_api::{get_arg, transfer_to_account, TransferResult};
+use contract_ffi::contract_api::{get_arg, transfer_to_account, TransferFailure, TransferResult};
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
 
@@ -15,5 +15,8 @@ pub extern "C" fn call() {
 
     let public_key = PublicKey::new([42; 32]);
     let result = transfer_to_account(public_key, amount);
-    assert_eq!(result, TransferResult::TransferError)
+    assert_eq!(
+        result,
+        TransferResult::TransferError(TransferFailure::InsufficientFunds)
+    )
 }
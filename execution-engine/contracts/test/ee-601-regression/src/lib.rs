@@ -49,7 +49,7 @@ pub extern "C" fn call() {
         let payment_purse: PurseId =
             contract_api::call_contract(pos_pointer, &(GET_PAYMENT_PURSE,), &vec![]);
 
-        if let PurseTransferResult::TransferError =
+        if let PurseTransferResult::TransferError(_) =
             contract_api::transfer_from_purse_to_purse(main_purse, payment_purse, amount)
         {
             contract_api::revert(Error::Transfer as u32);
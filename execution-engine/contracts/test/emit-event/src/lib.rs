@@ -0,0 +1,34 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api;
+
+const DONE_TOPIC: &str = "done";
+
+enum Arg {
+    Topic = 0,
+    Payload = 1,
+    RevertCode = 2,
+}
+
+/// Emits an event with the given topic and payload, followed by an event with
+/// the topic "done" and an empty payload.  Reverts afterwards with the given
+/// code, unless it is 0.
+#[no_mangle]
+pub extern "C" fn call() {
+    let topic: String = contract_api::get_arg(Arg::Topic as u32);
+    let payload: Vec<u8> = contract_api::get_arg(Arg::Payload as u32);
+    let revert_code: u32 = contract_api::get_arg(Arg::RevertCode as u32);
+
+    contract_api::emit_event(&topic, &payload);
+    contract_api::emit_event(DONE_TOPIC, &[]);
+
+    if revert_code != 0 {
+        contract_api::revert(revert_code);
+    }
+}
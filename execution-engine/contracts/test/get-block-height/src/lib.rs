@@ -0,0 +1,17 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use contract_ffi::contract_api;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let known_block_height: u64 = contract_api::get_arg(0);
+    let actual_block_height: u64 = contract_api::get_block_height();
+
+    assert_eq!(
+        actual_block_height, known_block_height,
+        "actual block height not known block height"
+    );
+}
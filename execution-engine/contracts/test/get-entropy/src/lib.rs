@@ -0,0 +1,25 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use contract_ffi::contract_api::{self, safe};
+use contract_ffi::key::Key;
+
+enum Error {
+    RepeatedEntropy = 1,
+}
+
+/// Stores the first seed returned by `get_entropy` under the account, after checking that the
+/// next one differs from it.
+#[no_mangle]
+pub extern "C" fn call() {
+    let first = contract_api::get_entropy();
+    let second = contract_api::get_entropy();
+    if first == second {
+        safe::revert(safe::ApiError::User(Error::RepeatedEntropy as u16));
+    }
+
+    let key: Key = contract_api::new_uref(first.to_vec()).into();
+    contract_api::add_uref("entropy", &key);
+}
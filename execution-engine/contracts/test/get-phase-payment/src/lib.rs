@@ -35,7 +35,7 @@ fn standard_payment(amount: U512) {
     let payment_purse: PurseId =
         contract_api::call_contract(pos_contract, &(GET_PAYMENT_PURSE,), &vec![]);
 
-    if let PurseTransferResult::TransferError =
+    if let PurseTransferResult::TransferError(_) =
         contract_api::transfer_from_purse_to_purse(main_purse, payment_purse, amount)
     {
         contract_api::revert(Error::TransferFromSourceToPayment as u32);
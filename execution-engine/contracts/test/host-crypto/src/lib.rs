@@ -0,0 +1,49 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api::crypto::{self, ED25519_SIGNATURE_LENGTH};
+use contract_ffi::contract_api::{self, safe};
+use contract_ffi::key::Key;
+use contract_ffi::value::account::ED25519_PUBLIC_KEY_LENGTH;
+
+enum Error {
+    InvalidPublicKeyLength = 1,
+    InvalidSignatureLength = 2,
+}
+
+fn store<T>(name: &str, value: T)
+where
+    contract_ffi::value::Value: From<T>,
+{
+    let key: Key = contract_api::new_uref(value).into();
+    contract_api::add_uref(name, &key);
+}
+
+/// Hashes the data passed as the first argument and checks the Ed25519 signature of it passed as
+/// the third argument, storing the results under the account.
+#[no_mangle]
+pub extern "C" fn call() {
+    let data: Vec<u8> = contract_api::get_arg(0);
+    let public_key_bytes: Vec<u8> = contract_api::get_arg(1);
+    let signature_bytes: Vec<u8> = contract_api::get_arg(2);
+
+    if public_key_bytes.len() != ED25519_PUBLIC_KEY_LENGTH {
+        safe::revert(safe::ApiError::User(Error::InvalidPublicKeyLength as u16));
+    }
+    if signature_bytes.len() != ED25519_SIGNATURE_LENGTH {
+        safe::revert(safe::ApiError::User(Error::InvalidSignatureLength as u16));
+    }
+    let mut public_key = [0u8; ED25519_PUBLIC_KEY_LENGTH];
+    public_key.copy_from_slice(&public_key_bytes);
+    let mut signature = [0u8; ED25519_SIGNATURE_LENGTH];
+    signature.copy_from_slice(&signature_bytes);
+
+    store("blake2b256", crypto::blake2b256(&data).to_vec());
+    store("sha256", crypto::sha256(&data).to_vec());
+    let valid = crypto::ed25519_verify(&public_key, &data, &signature);
+    store("ed25519_verify", valid as i32);
+}
@@ -7,7 +7,7 @@ extern crate contract_ffi;
 use alloc::string::String;
 
 use contract_ffi::contract_api::{
-    add, add_uref, get_uref, has_uref, list_known_urefs, new_uref, read, remove_uref, revert, write,
+    add, add_uref, get_uref, has_uref, list_named_keys, new_uref, read, remove_uref, revert, write,
 };
 use contract_ffi::key::Key;
 use contract_ffi::value::U512;
@@ -18,14 +18,14 @@ pub extern "C" fn call() {
 
     // Account starts with FOUR known urefs: genesis_acct, mint public uref, mint
     // private uref, pos public uref & pos private uref.
-    if list_known_urefs().len() != initi_uref_num {
+    if list_named_keys().len() != initi_uref_num {
         revert(201);
     }
 
     // Add new urefs
     let hello_world_uref1: Key = new_uref(String::from("Hello, world!")).into();
     add_uref("URef1", &hello_world_uref1);
-    assert_eq!(list_known_urefs().len(), initi_uref_num + 1);
+    assert_eq!(list_named_keys().len(), initi_uref_num + 1);
 
     // Verify if the uref is present
     assert!(has_uref("URef1"));
@@ -33,11 +33,11 @@ pub extern "C" fn call() {
     let big_value_uref: Key = new_uref(U512::max_value()).into();
     add_uref("URef2", &big_value_uref);
 
-    assert_eq!(list_known_urefs().len(), initi_uref_num + 2);
+    assert_eq!(list_named_keys().len(), initi_uref_num + 2);
 
     // Read data hidden behind `URef1` uref
     let hello_world: String = read(
-        list_known_urefs()
+        list_named_keys()
             .get("URef1")
             .expect("Unable to get URef1")
             .to_u_ptr()
@@ -84,5 +84,5 @@ pub extern "C" fn call() {
     // Cleaned up state
     assert!(!has_uref("URef1"));
     assert!(!has_uref("URef2"));
-    assert_eq!(list_known_urefs().len(), initi_uref_num);
+    assert_eq!(list_named_keys().len(), initi_uref_num);
 }
@@ -0,0 +1,53 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use contract_ffi::contract_api::{self, PurseTransferResult};
+use contract_ffi::system_contracts::mint;
+use contract_ffi::value::U512;
+
+#[repr(u32)]
+enum Error {
+    Transfer = 1,
+    Burn = 2,
+    BalanceNotFound = 3,
+    BalanceMismatch = 4,
+    TotalSupplyMismatch = 5,
+    OverdraftNotRejected = 6,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let purse_amount: U512 = contract_api::get_arg(0);
+    let burn_amount: U512 = contract_api::get_arg(1);
+
+    let purse = contract_api::create_purse();
+    if let PurseTransferResult::TransferError(_) =
+        contract_api::transfer_from_purse_to_purse(contract_api::main_purse(), purse, purse_amount)
+    {
+        contract_api::revert(Error::Transfer as u32);
+    }
+
+    let total_supply_before = contract_api::read_total_supply();
+
+    contract_api::burn(purse, burn_amount)
+        .unwrap_or_else(|_| contract_api::revert(Error::Burn as u32));
+
+    let balance = contract_api::get_balance(purse)
+        .unwrap_or_else(|| contract_api::revert(Error::BalanceNotFound as u32));
+    if balance != purse_amount - burn_amount {
+        contract_api::revert(Error::BalanceMismatch as u32);
+    }
+
+    if contract_api::read_total_supply() != total_supply_before - burn_amount {
+        contract_api::revert(Error::TotalSupplyMismatch as u32);
+    }
+
+    // burning more than the remaining balance must fail
+    if contract_api::burn(purse, balance + U512::one())
+        != Err(mint::error::Error::InsufficientFunds)
+    {
+        contract_api::revert(Error::OverdraftNotRejected as u32);
+    }
+}
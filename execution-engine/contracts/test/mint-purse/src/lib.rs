@@ -5,7 +5,6 @@ extern crate alloc;
 extern crate contract_ffi;
 
 use contract_ffi::contract_api;
-use contract_ffi::key::Key;
 use contract_ffi::system_contracts::mint;
 use contract_ffi::uref::URef;
 use contract_ffi::value::account::PurseId;
@@ -14,7 +13,6 @@ use contract_ffi::value::U512;
 #[repr(u32)]
 enum Error {
     PurseNotCreated = 1,
-    MintNotFound = 2,
     BalanceNotFound = 3,
     BalanceMismatch = 4,
 }
@@ -34,16 +32,7 @@ pub extern "C" fn call() {
     let new_purse =
         mint_purse(amount).unwrap_or_else(|_| contract_api::revert(Error::PurseNotCreated as u32));
 
-    let mint = contract_api::get_mint()
-        .unwrap_or_else(|| contract_api::revert(Error::MintNotFound as u32));
-
-    let balance: Option<U512> = contract_api::call_contract(
-        mint,
-        &("balance", new_purse),
-        &vec![Key::URef(new_purse.value())],
-    );
-
-    match balance {
+    match contract_api::get_balance(new_purse) {
         None => contract_api::revert(Error::BalanceNotFound as u32),
 
         Some(balance) if balance == amount => (),
@@ -0,0 +1,18 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use contract_ffi::contract_api;
+use contract_ffi::value::U512;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let expected_total_supply: U512 = contract_api::get_arg(0);
+    let actual_total_supply: U512 = contract_api::read_total_supply();
+
+    assert_eq!(
+        actual_total_supply, expected_total_supply,
+        "actual total supply not expected total supply"
+    );
+}
@@ -0,0 +1,43 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api::runtime_args::RuntimeArgs;
+use contract_ffi::contract_api::{self, safe};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+
+const ARG_A: &str = "a";
+const ARG_B: &str = "b";
+
+enum Error {
+    UnexpectedSum = 1,
+}
+
+#[no_mangle]
+pub extern "C" fn add() {
+    let a: u64 = safe::get_named_arg(ARG_A).unwrap_or_revert();
+    let b: u64 = safe::get_named_arg(ARG_B).unwrap_or_revert();
+    contract_api::ret(&(a + b), &Vec::new())
+}
+
+/// Calls `add` with the given arguments by name, leaving out `b` if it is `None`.
+#[no_mangle]
+pub extern "C" fn call() {
+    let a: u64 = safe::get_arg(0).unwrap_or_revert();
+    let b: Option<u64> = safe::get_arg(1).unwrap_or_revert();
+
+    let mut args = RuntimeArgs::new().with(ARG_A, a);
+    if let Some(b) = b {
+        args.insert(ARG_B, b);
+    }
+
+    let contract = contract_api::store_function("add", BTreeMap::new());
+    let sum: u64 = safe::call_contract_with_args(contract, &args, &Vec::new()).unwrap_or_revert();
+    if sum != a + b.unwrap_or_default() {
+        safe::revert(safe::ApiError::User(Error::UnexpectedSum as u16));
+    }
+}
@@ -4,15 +4,17 @@
 extern crate alloc;
 extern crate contract_ffi;
 
+use alloc::collections::BTreeMap;
 use alloc::prelude::v1::{String, Vec};
 
+use contract_ffi::bytesrepr::ToBytes;
 use contract_ffi::contract_api::pointers::{ContractPointer, UPointer};
 use contract_ffi::contract_api::{
-    call_contract, create_purse, get_arg, get_uref, main_purse, read, revert,
-    transfer_from_purse_to_account, transfer_from_purse_to_purse, PurseTransferResult,
-    TransferResult,
+    add_uref, call_contract, create_purse, get_arg, get_uref, main_purse, new_uref, read, revert,
+    transfer_from_purse_to_account, transfer_from_purse_to_purse,
 };
 use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::{DelegationRate, EraId};
 use contract_ffi::uref::AccessRights;
 use contract_ffi::value::account::{PublicKey, PurseId};
 use contract_ffi::value::U512;
@@ -23,6 +25,7 @@ enum Error {
     PurseToPurseTransfer = 1002,
     UnableToSeedAccount = 1003,
     UnknownCommand = 1004,
+    SnapshotSerialization = 1005,
 }
 
 fn purse_to_key(p: PurseId) -> Key {
@@ -52,13 +55,42 @@ fn unbond(pos: &ContractPointer, amount: Option<U512>) {
     call_contract::<_, ()>(pos.clone(), &(POS_UNBOND, amount), &Vec::<Key>::new());
 }
 
+fn step(pos: &ContractPointer) {
+    call_contract::<_, ()>(pos.clone(), &(POS_STEP,), &Vec::<Key>::new());
+}
+
+fn set_delegation_rate(pos: &ContractPointer, rate: DelegationRate) {
+    call_contract::<_, ()>(
+        pos.clone(),
+        &(POS_SET_DELEGATION_RATE, rate),
+        &Vec::<Key>::new(),
+    );
+}
+
+fn get_era_snapshot(pos: &ContractPointer, era: EraId) -> Option<BTreeMap<PublicKey, U512>> {
+    call_contract(
+        pos.clone(),
+        &(POS_GET_ERA_SNAPSHOT, era),
+        &Vec::<Key>::new(),
+    )
+}
+
 const POS_BOND: &str = "bond";
 const POS_UNBOND: &str = "unbond";
+const POS_STEP: &str = "step";
+const POS_GET_ERA_SNAPSHOT: &str = "get_era_snapshot";
+const POS_SET_DELEGATION_RATE: &str = "set_delegation_rate";
 
 const TEST_BOND: &str = "bond";
 const TEST_BOND_FROM_MAIN_PURSE: &str = "bond-from-main-purse";
 const TEST_SEED_NEW_ACCOUNT: &str = "seed_new_account";
 const TEST_UNBOND: &str = "unbond";
+const TEST_STEP: &str = "step";
+const TEST_GET_ERA_SNAPSHOT: &str = "get_era_snapshot";
+const TEST_SET_DELEGATION_RATE: &str = "set_delegation_rate";
+
+/// The name under which the serialized snapshot queried by `get_era_snapshot` is stored.
+const ERA_SNAPSHOT_KEY: &str = "era_snapshot";
 
 #[no_mangle]
 pub extern "C" fn call() {
@@ -71,9 +103,7 @@ pub extern "C" fn call() {
         let amount = get_arg(1);
         let p1 = create_purse();
 
-        if transfer_from_purse_to_purse(main_purse(), p1, amount)
-            == PurseTransferResult::TransferError
-        {
+        if !transfer_from_purse_to_purse(main_purse(), p1, amount).is_success() {
             revert(Error::PurseToPurseTransfer as u32);
         }
 
@@ -85,14 +115,24 @@ pub extern "C" fn call() {
     } else if command == TEST_SEED_NEW_ACCOUNT {
         let account: PublicKey = get_arg(1);
         let amount: U512 = get_arg(2);
-        if transfer_from_purse_to_account(main_purse(), account, amount)
-            == TransferResult::TransferError
-        {
+        if !transfer_from_purse_to_account(main_purse(), account, amount).is_success() {
             revert(Error::UnableToSeedAccount as u32);
         }
     } else if command == TEST_UNBOND {
         let maybe_amount: Option<U512> = get_arg(1);
         unbond(&pos_pointer, maybe_amount);
+    } else if command == TEST_STEP {
+        step(&pos_pointer);
+    } else if command == TEST_GET_ERA_SNAPSHOT {
+        let era: EraId = get_arg(1);
+        let snapshot = get_era_snapshot(&pos_pointer, era)
+            .to_bytes()
+            .unwrap_or_else(|_| revert(Error::SnapshotSerialization as u32));
+        let key: Key = new_uref(snapshot).into();
+        add_uref(ERA_SNAPSHOT_KEY, &key);
+    } else if command == TEST_SET_DELEGATION_RATE {
+        let rate: DelegationRate = get_arg(1);
+        set_delegation_rate(&pos_pointer, rate);
     } else {
         revert(Error::UnknownCommand as u32);
     }
@@ -16,7 +16,6 @@ enum Error {
     GetPosOuterURef = 1,
     GetPosInnerURef = 2,
     TransferFromSourceToPayment = 3,
-    TransferFromPaymentToSource = 4,
     GetBalance = 5,
     CheckBalance = 6,
 }
@@ -42,7 +41,7 @@ pub extern "C" fn call() {
         contract_api::call_contract(pos_pointer, &("get_payment_purse",), &Vec::new());
 
     // can deposit
-    if let PurseTransferResult::TransferError =
+    if let PurseTransferResult::TransferError(_) =
         contract_api::transfer_from_purse_to_purse(source_purse, payment_purse, payment_amount)
     {
         contract_api::revert(Error::TransferFromSourceToPayment as u32);
@@ -56,11 +55,4 @@ pub extern "C" fn call() {
     if payment_balance.saturating_sub(payment_fund) != payment_amount {
         contract_api::revert(Error::CheckBalance as u32)
     }
-
-    // cannot withdraw
-    if let PurseTransferResult::TransferSuccessful =
-        contract_api::transfer_from_purse_to_purse(payment_purse, source_purse, payment_amount)
-    {
-        contract_api::revert(Error::TransferFromPaymentToSource as u32);
-    }
 }
@@ -45,7 +45,7 @@ fn get_payment_purse(pos: &ContractPointer) -> PurseId {
 fn submit_payment(pos: &ContractPointer, amount: U512) {
     let payment_purse = get_payment_purse(pos);
     let main_purse = contract_api::main_purse();
-    if let PurseTransferResult::TransferError =
+    if let PurseTransferResult::TransferError(_) =
         contract_api::transfer_from_purse_to_purse(main_purse, payment_purse, amount)
     {
         contract_api::revert(99);
@@ -0,0 +1,24 @@
+#![no_std]
+
+extern crate contract_ffi;
+
+use core::ptr;
+
+use contract_ffi::contract_api;
+
+#[inline(never)]
+fn recurse(depth: u32) -> u32 {
+    if depth == 0 {
+        return 0;
+    }
+    let result = recurse(depth - 1);
+    // Reading `depth` back after the call keeps every frame alive, so the
+    // recursion can't be turned into a loop
+    unsafe { ptr::read_volatile(&depth) }.wrapping_add(result)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let depth: u32 = contract_api::get_arg(0);
+    recurse(depth);
+}
@@ -19,9 +19,7 @@ pub extern "C" fn call() {
     let destination_name: String = contract_api::get_arg(0);
     let source: PurseId = contract_api::main_purse();
     let destination = contract_api::create_purse();
-    if contract_api::transfer_from_purse_to_purse(source, destination, amount)
-        == contract_api::PurseTransferResult::TransferError
-    {
+    if !contract_api::transfer_from_purse_to_purse(source, destination, amount).is_success() {
         contract_api::revert(Error::PurseToPurseTransfer as u32);
     }
     contract_api::add_uref(&destination_name, &destination.value().into());
@@ -0,0 +1,44 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api::{self, safe};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::uref::{AccessRights, URef};
+use contract_ffi::value::account::PurseId;
+use contract_ffi::value::U512;
+
+const FORGED_PURSE_ADDR: [u8; 32] = [42; 32];
+const TRANSFER_AMOUNT: u64 = 1;
+
+fn attenuate(purse: PurseId, access_rights: AccessRights) -> PurseId {
+    PurseId::new(URef::new(purse.value().addr(), access_rights))
+}
+
+fn forged_purse() -> PurseId {
+    PurseId::new(URef::new(FORGED_PURSE_ADDR, AccessRights::READ_ADD_WRITE))
+}
+
+/// Transfers from the main purse to a new purse, or the other way round, with one side replaced
+/// according to `case`.
+#[no_mangle]
+pub extern "C" fn call() {
+    let case: String = safe::get_arg(0).unwrap_or_revert();
+    let main_purse = contract_api::main_purse();
+    let new_purse = contract_api::create_purse();
+
+    let (source, target) = match case.as_str() {
+        "forged_source" => (forged_purse(), main_purse),
+        "read_only_source" => (attenuate(main_purse, AccessRights::READ), new_purse),
+        "forged_target" => (main_purse, forged_purse()),
+        "read_only_target" => (main_purse, attenuate(new_purse, AccessRights::READ)),
+        "add_only_target" => (main_purse, attenuate(new_purse, AccessRights::ADD)),
+        _ => safe::revert(safe::ApiError::InvalidArgument),
+    };
+
+    safe::transfer_from_purse_to_purse(source, target, U512::from(TRANSFER_AMOUNT))
+        .unwrap_or_revert();
+}
@@ -0,0 +1,25 @@
+#![no_std]
+#![feature(cell_update)]
+
+extern crate alloc;
+extern crate contract_ffi;
+use contract_ffi::contract_api::{
+    add_associated_key, get_action_threshold, get_arg, revert, set_action_threshold,
+};
+use contract_ffi::value::account::{ActionType, AddKeyFailure, PublicKey, Weight};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    match add_associated_key(PublicKey::new([123; 32]), Weight::new(1)) {
+        Err(AddKeyFailure::DuplicateKey) => {}
+        Err(_) => revert(50),
+        Ok(_) => {}
+    };
+
+    let transfer_threshold: Weight = get_arg(0);
+    set_action_threshold(ActionType::Transfer, transfer_threshold).unwrap_or_else(|_| revert(100));
+
+    if get_action_threshold(ActionType::Transfer) != transfer_threshold {
+        revert(200);
+    }
+}
@@ -4,7 +4,7 @@
 extern crate alloc;
 extern crate contract_ffi;
 
-use contract_ffi::contract_api::{get_arg, TransferResult};
+use contract_ffi::contract_api::get_arg;
 use contract_ffi::value::U512;
 
 const TRANSFER_AMOUNT: u32 = 50_000_000 + 1000;
@@ -16,5 +16,5 @@ pub extern "C" fn call() {
 
     let result = contract_ffi::contract_api::transfer_to_account(public_key, amount);
 
-    assert_ne!(result, TransferResult::TransferError);
+    assert!(result.is_success());
 }
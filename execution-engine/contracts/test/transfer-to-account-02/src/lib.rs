@@ -4,7 +4,6 @@
 extern crate alloc;
 extern crate contract_ffi;
 
-use contract_ffi::contract_api::TransferResult;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
 
@@ -17,5 +16,5 @@ pub extern "C" fn call() {
 
     let result = contract_ffi::contract_api::transfer_to_account(public_key, amount);
 
-    assert_ne!(result, TransferResult::TransferError);
+    assert!(result.is_success());
 }
@@ -0,0 +1,36 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api::pointers::UPointer;
+use contract_ffi::contract_api::safe::{self, ApiError};
+use contract_ffi::key::Key;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::ContractPackage;
+
+const PACKAGE_NAME: &str = "versioned_contract";
+
+enum Error {
+    UnexpectedResult = 1,
+}
+
+/// Calls the given version of the package and reverts unless it returns the expected value.
+#[no_mangle]
+pub extern "C" fn call() {
+    let version: u32 = safe::get_arg(0).unwrap_or_revert();
+    let expected: u32 = safe::get_arg(1).unwrap_or_revert();
+
+    let package: UPointer<ContractPackage> = match safe::get_uref(PACKAGE_NAME).unwrap_or_revert() {
+        Key::URef(uref) => UPointer::from_uref(uref).unwrap_or_revert_with(ApiError::URefNotFound),
+        _ => safe::revert(ApiError::UnexpectedKeyVariant),
+    };
+    let result: u32 =
+        safe::call_versioned_contract(package, version, &(), &Vec::new()).unwrap_or_revert();
+
+    if result != expected {
+        safe::revert(ApiError::User(Error::UnexpectedResult as u16));
+    }
+}
@@ -0,0 +1,38 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api::pointers::UPointer;
+use contract_ffi::contract_api::safe::{self, ApiError};
+use contract_ffi::key::Key;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::ContractPackage;
+
+const DISABLE: &str = "disable";
+const ENABLE: &str = "enable";
+
+enum Error {
+    UnknownAction = 1,
+}
+
+/// Disables or enables the given version of the package known under the given name.
+#[no_mangle]
+pub extern "C" fn call() {
+    let action: String = safe::get_arg(0).unwrap_or_revert();
+    let package_name: String = safe::get_arg(1).unwrap_or_revert();
+    let version: u32 = safe::get_arg(2).unwrap_or_revert();
+
+    let package: UPointer<ContractPackage> = match safe::get_uref(&package_name).unwrap_or_revert()
+    {
+        Key::URef(uref) => UPointer::from_uref(uref).unwrap_or_revert_with(ApiError::URefNotFound),
+        _ => safe::revert(ApiError::UnexpectedKeyVariant),
+    };
+    match action.as_str() {
+        DISABLE => safe::disable_contract_version(package, version).unwrap_or_revert(),
+        ENABLE => safe::enable_contract_version(package, version).unwrap_or_revert(),
+        _ => safe::revert(ApiError::User(Error::UnknownAction as u16)),
+    }
+}
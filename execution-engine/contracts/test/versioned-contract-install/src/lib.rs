@@ -0,0 +1,29 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api;
+use contract_ffi::key::Key;
+use contract_ffi::uref::{AccessRights, URef};
+
+const PACKAGE_NAME: &str = "versioned_contract";
+const READ_ONLY_PACKAGE_NAME: &str = "versioned_contract_read_only";
+
+#[no_mangle]
+pub extern "C" fn version_1() {
+    contract_api::ret(&1u32, &Vec::new())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let package = contract_api::create_contract_package();
+    contract_api::add_contract_version(package.clone(), "version_1", BTreeMap::new());
+
+    let read_only_package = URef::new(package.0, AccessRights::READ);
+    contract_api::add_uref(PACKAGE_NAME, &package.into());
+    contract_api::add_uref(READ_ONLY_PACKAGE_NAME, &Key::URef(read_only_package));
+}
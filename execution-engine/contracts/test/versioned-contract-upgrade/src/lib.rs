@@ -0,0 +1,34 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api;
+use contract_ffi::contract_api::pointers::UPointer;
+use contract_ffi::key::Key;
+
+enum Error {
+    PackageNotFound = 1,
+    InvalidPackageKey = 2,
+}
+
+#[no_mangle]
+pub extern "C" fn version_2() {
+    contract_api::ret(&2u32, &Vec::new())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let package_name: String = contract_api::get_arg(0);
+    let package = match contract_api::get_uref(&package_name) {
+        Some(Key::URef(uref)) => UPointer::from_uref(uref)
+            .unwrap_or_else(|_| contract_api::revert(Error::InvalidPackageKey as u32)),
+        Some(_) => contract_api::revert(Error::InvalidPackageKey as u32),
+        None => contract_api::revert(Error::PackageNotFound as u32),
+    };
+    contract_api::add_contract_version(package, "version_2", BTreeMap::new());
+}
@@ -0,0 +1,14 @@
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+extern crate contract_ffi;
+
+use contract_ffi::contract_api;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // Writes a byte array of the requested length to a [66; 32] local key
+    let len: u64 = contract_api::get_arg(0);
+    contract_api::write_local([66u8; 32], vec![0u8; len as usize]);
+}
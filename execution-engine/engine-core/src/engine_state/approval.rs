@@ -0,0 +1,206 @@
+//! Signatures of deploy hashes by the keys authorizing a deploy.
+//!
+//! When the engine is configured to verify approvals, only the authorization keys which signed
+//! the deploy hash count toward the weight thresholds of the deploying account.
+
+use std::collections::BTreeSet;
+
+use ed25519_dalek::{PublicKey as Ed25519PublicKey, Signature as Ed25519Signature};
+use secp256k1::{
+    Message, PublicKey as Secp256k1PublicKey, Secp256k1, Signature as Secp256k1Signature,
+};
+
+use contract_ffi::value::account::PublicKey;
+
+/// A signature of a deploy hash, supposedly made with the secret key of `public_key`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Approval {
+    public_key: PublicKey,
+    signature: Vec<u8>,
+}
+
+impl Approval {
+    pub fn new(public_key: PublicKey, signature: Vec<u8>) -> Self {
+        Approval {
+            public_key,
+            signature,
+        }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /// Returns `true` if the signature is a valid signature of `deploy_hash` by the public key:
+    /// a 64 byte Ed25519 signature for Ed25519 keys, or a 64 byte compact ECDSA signature of the
+    /// deploy hash as the message digest for Secp256k1 keys.
+    pub fn verify(&self, deploy_hash: &[u8; 32]) -> bool {
+        match self.public_key {
+            PublicKey::Ed25519(key) => ed25519_verify(&key, deploy_hash, &self.signature),
+            PublicKey::Secp256k1(key) => secp256k1_verify(&key, deploy_hash, &self.signature),
+        }
+    }
+}
+
+fn ed25519_verify(public_key: &[u8], deploy_hash: &[u8; 32], signature: &[u8]) -> bool {
+    let public_key = match Ed25519PublicKey::from_bytes(public_key) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    let signature = match Ed25519Signature::from_bytes(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    public_key.verify(deploy_hash, &signature).is_ok()
+}
+
+fn secp256k1_verify(public_key: &[u8], deploy_hash: &[u8; 32], signature: &[u8]) -> bool {
+    let public_key = match Secp256k1PublicKey::from_slice(public_key) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    let signature = match Secp256k1Signature::from_compact(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let message = match Message::from_slice(deploy_hash) {
+        Ok(message) => message,
+        Err(_) => return false,
+    };
+    Secp256k1::verification_only()
+        .verify(&message, &signature, &public_key)
+        .is_ok()
+}
+
+/// Returns the keys of `authorization_keys` with a valid approval of `deploy_hash` among
+/// `approvals`.
+pub fn approved_keys(
+    deploy_hash: &[u8; 32],
+    authorization_keys: &BTreeSet<PublicKey>,
+    approvals: &[Approval],
+) -> BTreeSet<PublicKey> {
+    approvals
+        .iter()
+        .filter(|approval| authorization_keys.contains(&approval.public_key))
+        .filter(|approval| approval.verify(deploy_hash))
+        .map(Approval::public_key)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use ed25519_dalek::{Keypair, PublicKey as Ed25519PublicKey, SecretKey};
+    use secp256k1::{
+        Message, PublicKey as Secp256k1PublicKey, Secp256k1, SecretKey as Secp256k1SecretKey,
+    };
+
+    use contract_ffi::value::account::PublicKey;
+
+    use super::{approved_keys, Approval};
+
+    const DEPLOY_HASH: [u8; 32] = [42; 32];
+
+    fn keypair(seed: u8) -> Keypair {
+        let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = Ed25519PublicKey::from(&secret);
+        Keypair { secret, public }
+    }
+
+    fn public_key(keypair: &Keypair) -> PublicKey {
+        PublicKey::new(keypair.public.to_bytes())
+    }
+
+    fn approval(keypair: &Keypair, message: &[u8]) -> Approval {
+        let signature = keypair.sign(message).to_bytes().to_vec();
+        Approval::new(public_key(keypair), signature)
+    }
+
+    #[test]
+    fn should_verify_signature_of_deploy_hash() {
+        let keypair = keypair(1);
+        assert!(approval(&keypair, &DEPLOY_HASH).verify(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_not_verify_signature_of_other_message() {
+        let keypair = keypair(1);
+        assert!(!approval(&keypair, &[0; 32]).verify(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_not_verify_signature_by_other_key() {
+        let signer = keypair(1);
+        let other = keypair(2);
+        let mut approval = approval(&signer, &DEPLOY_HASH);
+        approval.public_key = public_key(&other);
+        assert!(!approval.verify(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_not_verify_malformed_signature() {
+        let keypair = keypair(1);
+        let approval = Approval::new(public_key(&keypair), vec![0; 10]);
+        assert!(!approval.verify(&DEPLOY_HASH));
+    }
+
+    fn secp256k1_approval(seed: u8, message: &[u8; 32]) -> Approval {
+        let secp = Secp256k1::new();
+        let secret_key = Secp256k1SecretKey::from_slice(&[seed; 32]).unwrap();
+        let public_key = Secp256k1PublicKey::from_secret_key(&secp, &secret_key).serialize();
+        let message = Message::from_slice(message).unwrap();
+        let signature = secp.sign(&message, &secret_key).serialize_compact();
+        Approval::new(PublicKey::Secp256k1(public_key), signature.to_vec())
+    }
+
+    #[test]
+    fn should_verify_secp256k1_signature_of_deploy_hash() {
+        assert!(secp256k1_approval(1, &DEPLOY_HASH).verify(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_not_verify_secp256k1_signature_of_other_message() {
+        assert!(!secp256k1_approval(1, &[0; 32]).verify(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_not_verify_secp256k1_signature_by_other_key() {
+        let mut approval = secp256k1_approval(1, &DEPLOY_HASH);
+        approval.public_key = secp256k1_approval(2, &DEPLOY_HASH).public_key;
+        assert!(!approval.verify(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_not_verify_malformed_secp256k1_signature() {
+        let mut approval = secp256k1_approval(1, &DEPLOY_HASH);
+        approval.signature.truncate(10);
+        assert!(!approval.verify(&DEPLOY_HASH));
+    }
+
+    #[test]
+    fn should_only_approve_signed_authorization_keys() {
+        let signer = keypair(1);
+        let non_signer = keypair(2);
+        let outsider = keypair(3);
+        let authorization_keys: BTreeSet<PublicKey> =
+            vec![public_key(&signer), public_key(&non_signer)]
+                .into_iter()
+                .collect();
+        let approvals = vec![
+            approval(&signer, &DEPLOY_HASH),
+            approval(&non_signer, &[0; 32]),
+            approval(&outsider, &DEPLOY_HASH),
+        ];
+
+        let expected: BTreeSet<PublicKey> = vec![public_key(&signer)].into_iter().collect();
+        assert_eq!(
+            approved_keys(&DEPLOY_HASH, &authorization_keys, &approvals),
+            expected
+        );
+    }
+}
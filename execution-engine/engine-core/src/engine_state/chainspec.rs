@@ -0,0 +1,558 @@
+//! Parsing of a chainspec, i.e. a `chainspec.toml` manifest along with the system contract
+//! installers and the accounts file it refers to, into a [`GenesisConfig`].
+//!
+//! Paths in the manifest are relative to the directory holding it. Each line of the accounts
+//! file has the form
+//! `public_key,balance,bonded_amount[,delegation_rate[,is_genesis_delegator]]`, where the public
+//! key is lower case hex and the amounts are in motes. Empty lines and lines starting with `#` are
+//! skipped.
+
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use failure::Fail;
+use serde::Deserialize;
+
+use contract_ffi::base16;
+use contract_ffi::system_contracts::pos::{
+    BondLimits, DelegationRate, DelegationRateLimits, RefundRatio, DEFAULT_ERA_DURATION,
+    DEFAULT_UNBOND_DELAY, MAX_DELEGATION_RATE,
+};
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_shared::key_limits::KeyLimits;
+use engine_shared::motes::Motes;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+
+use crate::engine_state::genesis::{GenesisAccount, GenesisConfig};
+use crate::engine_state::{DEFAULT_MIN_GAS_PRICE, DEFAULT_NATIVE_TRANSFER_COST};
+
+/// The bytes every wasm module starts with.
+const WASM_MAGIC: [u8; 4] = [0x00, 0x61, 0x73, 0x6d];
+
+#[derive(Fail, Debug)]
+pub enum Error {
+    #[fail(display = "Failed to read {:?}: {}", path, error)]
+    Io { path: PathBuf, error: io::Error },
+    #[fail(display = "Invalid chainspec manifest: {}", _0)]
+    Manifest(toml::de::Error),
+    #[fail(display = "Not a wasm module: {:?}", _0)]
+    InvalidWasm(PathBuf),
+    #[fail(display = "Invalid protocol version: {}", _0)]
+    InvalidProtocolVersion(u64),
+    #[fail(display = "Invalid refund ratio: {}/{}", numerator, denominator)]
+    InvalidRefundRatio { numerator: u64, denominator: u64 },
+    #[fail(display = "Invalid minimum gas price: {}", _0)]
+    InvalidMinGasPrice(u64),
+    #[fail(display = "Invalid native transfer cost: {}", _0)]
+    InvalidNativeTransferCost(u64),
+    #[fail(display = "Invalid maximum number of associated keys: {}", _0)]
+    InvalidMaxAssociatedKeys(u32),
+    #[fail(display = "Invalid maximum number of named keys: {}", _0)]
+    InvalidMaxNamedKeys(u32),
+    #[fail(display = "Invalid maximum delegation rate increase per era: {}", _0)]
+    InvalidMaxDelegationRateIncrease(DelegationRate),
+    #[fail(display = "Invalid minimum bond amount: {}", _0)]
+    InvalidMinBondAmount(u64),
+    #[fail(display = "Invalid era duration: {}", _0)]
+    InvalidEraDuration(u64),
+    #[fail(display = "Invalid wasm costs: {}", _0)]
+    InvalidWasmCosts(String),
+    #[fail(display = "Invalid account on line {}: {}", line, reason)]
+    InvalidAccount { line: usize, reason: String },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Manifest {
+    genesis: GenesisSection,
+    wasm_costs: Option<WasmCostsSection>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct GenesisSection {
+    name: String,
+    timestamp: u64,
+    protocol_version: u64,
+    mint_code_path: PathBuf,
+    pos_code_path: PathBuf,
+    initial_accounts_path: PathBuf,
+    refund_ratio: Option<RefundRatioSection>,
+    min_gas_price: Option<u64>,
+    native_transfer_cost: Option<u64>,
+    max_associated_keys: Option<u32>,
+    max_named_keys: Option<u32>,
+    delegation_rate_limits: Option<DelegationRateLimitsSection>,
+    min_bond_amount: Option<u64>,
+    max_bond_multiplier: Option<u64>,
+    max_validator_count: Option<u32>,
+    era_duration: Option<u64>,
+    unbond_delay: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RefundRatioSection {
+    numerator: u64,
+    denominator: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct DelegationRateLimitsSection {
+    max_increase_per_era: DelegationRate,
+    max_changes_per_era: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct WasmCostsSection {
+    regular: u32,
+    div: u32,
+    mul: u32,
+    mem: u32,
+    initial_mem: u32,
+    grow_mem: u32,
+    memcpy: u32,
+    max_stack_height: u32,
+    opcodes_mul: u32,
+    opcodes_div: u32,
+    storage_byte_cost: u32,
+    blake2b256_cost: u32,
+    sha256_cost: u32,
+    ed25519_verify_cost: u32,
+    secp256k1_recover_cost: u32,
+}
+
+impl TryFrom<WasmCostsSection> for WasmCosts {
+    type Error = Error;
+
+    fn try_from(section: WasmCostsSection) -> Result<Self, Self::Error> {
+        if section.opcodes_div == 0 {
+            return Err(Error::InvalidWasmCosts(String::from(
+                "opcodes-div must be positive",
+            )));
+        }
+        if section.max_stack_height == 0 {
+            return Err(Error::InvalidWasmCosts(String::from(
+                "max-stack-height must be positive",
+            )));
+        }
+        Ok(WasmCosts {
+            regular: section.regular,
+            div: section.div,
+            mul: section.mul,
+            mem: section.mem,
+            initial_mem: section.initial_mem,
+            grow_mem: section.grow_mem,
+            memcpy: section.memcpy,
+            max_stack_height: section.max_stack_height,
+            opcodes_mul: section.opcodes_mul,
+            opcodes_div: section.opcodes_div,
+            storage_byte_cost: section.storage_byte_cost,
+            blake2b256_cost: section.blake2b256_cost,
+            sha256_cost: section.sha256_cost,
+            ed25519_verify_cost: section.ed25519_verify_cost,
+            secp256k1_recover_cost: section.secp256k1_recover_cost,
+        })
+    }
+}
+
+/// Parses the chainspec whose manifest is at `manifest_path`.
+pub fn parse_chainspec<P: AsRef<Path>>(manifest_path: P) -> Result<GenesisConfig, Error> {
+    let manifest_path = manifest_path.as_ref();
+    let manifest: Manifest = {
+        let contents = read_to_string(manifest_path)?;
+        toml::from_str(&contents).map_err(Error::Manifest)?
+    };
+    let base_dir = manifest_path.parent().unwrap_or_else(|| Path::new(""));
+    let genesis = manifest.genesis;
+
+    if genesis.protocol_version == 0 {
+        return Err(Error::InvalidProtocolVersion(genesis.protocol_version));
+    }
+
+    let mint_installer_bytes = read_wasm(&base_dir.join(&genesis.mint_code_path))?;
+    let proof_of_stake_installer_bytes = read_wasm(&base_dir.join(&genesis.pos_code_path))?;
+
+    let accounts = {
+        let accounts_path = base_dir.join(&genesis.initial_accounts_path);
+        parse_accounts(&read_to_string(&accounts_path)?)?
+    };
+
+    let wasm_costs = match manifest.wasm_costs {
+        Some(section) => WasmCosts::try_from(section)?,
+        None => WasmCosts::default(),
+    };
+
+    let refund_ratio = match genesis.refund_ratio {
+        Some(RefundRatioSection {
+            numerator,
+            denominator,
+        }) => RefundRatio::new(numerator, denominator).ok_or(Error::InvalidRefundRatio {
+            numerator,
+            denominator,
+        })?,
+        None => RefundRatio::default(),
+    };
+
+    let min_gas_price = match genesis.min_gas_price {
+        Some(0) => return Err(Error::InvalidMinGasPrice(0)),
+        Some(min_gas_price) => min_gas_price,
+        None => DEFAULT_MIN_GAS_PRICE,
+    };
+
+    let native_transfer_cost = match genesis.native_transfer_cost {
+        Some(0) => return Err(Error::InvalidNativeTransferCost(0)),
+        Some(native_transfer_cost) => native_transfer_cost,
+        None => DEFAULT_NATIVE_TRANSFER_COST,
+    };
+
+    let key_limits = {
+        let defaults = KeyLimits::default();
+        let max_associated_keys = match genesis.max_associated_keys {
+            Some(0) => return Err(Error::InvalidMaxAssociatedKeys(0)),
+            Some(max_associated_keys) => max_associated_keys,
+            None => defaults.max_associated_keys(),
+        };
+        let max_named_keys = match genesis.max_named_keys {
+            Some(0) => return Err(Error::InvalidMaxNamedKeys(0)),
+            Some(max_named_keys) => max_named_keys,
+            None => defaults.max_named_keys(),
+        };
+        KeyLimits::new(max_associated_keys, max_named_keys)
+    };
+
+    let delegation_rate_limits = match genesis.delegation_rate_limits {
+        Some(DelegationRateLimitsSection {
+            max_increase_per_era,
+            max_changes_per_era,
+        }) => DelegationRateLimits::new(max_increase_per_era, max_changes_per_era).ok_or(
+            Error::InvalidMaxDelegationRateIncrease(max_increase_per_era),
+        )?,
+        None => DelegationRateLimits::default(),
+    };
+
+    let bond_limits = {
+        let defaults = BondLimits::default();
+        let min_bond_amount = match genesis.min_bond_amount {
+            Some(0) => return Err(Error::InvalidMinBondAmount(0)),
+            Some(min_bond_amount) => U512::from(min_bond_amount),
+            None => defaults.min_bond_amount(),
+        };
+        let max_bond_multiplier = genesis
+            .max_bond_multiplier
+            .unwrap_or_else(|| defaults.max_bond_multiplier());
+        let max_validator_count = genesis
+            .max_validator_count
+            .unwrap_or_else(|| defaults.max_validator_count());
+        BondLimits::new(min_bond_amount, max_bond_multiplier, max_validator_count)
+            .expect("minimum bond amount should be positive")
+    };
+
+    let era_duration = match genesis.era_duration {
+        Some(0) => return Err(Error::InvalidEraDuration(0)),
+        Some(era_duration) => era_duration,
+        None => DEFAULT_ERA_DURATION,
+    };
+
+    let unbond_delay = genesis.unbond_delay.unwrap_or(DEFAULT_UNBOND_DELAY);
+
+    Ok(GenesisConfig::new(
+        genesis.name,
+        genesis.timestamp,
+        genesis.protocol_version,
+        mint_installer_bytes,
+        proof_of_stake_installer_bytes,
+        accounts,
+        wasm_costs,
+        refund_ratio,
+        min_gas_price,
+        native_transfer_cost,
+        key_limits,
+    )
+    .with_delegation_rate_limits(delegation_rate_limits)
+    .with_bond_limits(bond_limits)
+    .with_era_duration(era_duration)
+    .with_unbond_delay(unbond_delay))
+}
+
+fn read_to_string(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|error| Error::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn read_wasm(path: &Path) -> Result<Vec<u8>, Error> {
+    let bytes = fs::read(path).map_err(|error| Error::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    if !bytes.starts_with(&WASM_MAGIC) {
+        return Err(Error::InvalidWasm(path.to_path_buf()));
+    }
+    Ok(bytes)
+}
+
+/// Parses the contents of an accounts file.
+fn parse_accounts(contents: &str) -> Result<Vec<GenesisAccount>, Error> {
+    let mut public_keys = BTreeSet::new();
+    let mut accounts = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |reason: &str| Error::InvalidAccount {
+            line: index + 1,
+            reason: reason.to_string(),
+        };
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() < 3 || fields.len() > 5 {
+            return Err(invalid("expected 3 to 5 fields"));
+        }
+
+        let public_key = base16::decode_lower(fields[0])
+            .ok()
+            .and_then(|bytes| PublicKey::try_from(bytes.as_slice()).ok())
+            .ok_or_else(|| invalid("invalid public key"))?;
+        if !public_keys.insert(public_key) {
+            return Err(invalid("duplicate public key"));
+        }
+        let balance = U512::from_dec_str(fields[1])
+            .map(Motes::new)
+            .map_err(|_| invalid("invalid balance"))?;
+        let bonded_amount = U512::from_dec_str(fields[2])
+            .map(Motes::new)
+            .map_err(|_| invalid("invalid bonded amount"))?;
+
+        let mut account = GenesisAccount::new(public_key, balance, bonded_amount);
+        if let Some(field) = fields.get(3).filter(|field| !field.is_empty()) {
+            let delegation_rate = field
+                .parse::<DelegationRate>()
+                .ok()
+                .filter(|rate| *rate <= MAX_DELEGATION_RATE)
+                .ok_or_else(|| invalid("invalid delegation rate"))?;
+            account = account.with_delegation_rate(delegation_rate);
+        }
+        if let Some(field) = fields.get(4) {
+            let is_genesis_delegator = field
+                .parse::<bool>()
+                .map_err(|_| invalid("invalid genesis delegator flag"))?;
+            if is_genesis_delegator {
+                account = account.into_genesis_delegator();
+            }
+        }
+        accounts.push(account);
+    }
+    Ok(accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use contract_ffi::system_contracts::pos::{BondLimits, DelegationRateLimits, RefundRatio};
+    use contract_ffi::value::account::PublicKey;
+    use contract_ffi::value::U512;
+    use engine_shared::key_limits::{KeyLimits, DEFAULT_MAX_ASSOCIATED_KEYS};
+    use engine_shared::motes::Motes;
+    use engine_wasm_prep::wasm_costs::WasmCosts;
+
+    use super::{parse_accounts, parse_chainspec, Error, WASM_MAGIC};
+
+    const ACCOUNTS: &str = "\
+# public key, balance, bonded amount, delegation rate, genesis delegator
+0101010101010101010101010101010101010101010101010101010101010101,1000,100,10
+0202020202020202020202020202020202020202020202020202020202020202,2000,200,,true
+0303030303030303030303030303030303030303030303030303030303030303,3000,0
+";
+
+    const MANIFEST: &str = r#"
+[genesis]
+name = "test-chain"
+timestamp = 1568805354071
+protocol-version = 1
+mint-code-path = "mint_install.wasm"
+pos-code-path = "pos_install.wasm"
+initial-accounts-path = "accounts.csv"
+native-transfer-cost = 5000
+max-named-keys = 100
+min-bond-amount = 50
+max-validator-count = 10
+era-duration = 3600000
+unbond-delay = 60000
+
+[genesis.refund-ratio]
+numerator = 1
+denominator = 2
+
+[genesis.delegation-rate-limits]
+max-increase-per-era = 3
+max-changes-per-era = 2
+
+[wasm-costs]
+regular = 1
+div = 16
+mul = 4
+mem = 2
+initial-mem = 4096
+grow-mem = 8192
+memcpy = 1
+max-stack-height = 65536
+opcodes-mul = 3
+opcodes-div = 8
+storage-byte-cost = 1
+blake2b256-cost = 200
+sha256-cost = 200
+ed25519-verify-cost = 2000
+secp256k1-recover-cost = 2000
+"#;
+
+    fn write_chainspec(dir: &Path, manifest: &str, pos_installer: &[u8]) {
+        fs::write(dir.join("chainspec.toml"), manifest).unwrap();
+        fs::write(dir.join("accounts.csv"), ACCOUNTS).unwrap();
+        fs::write(dir.join("mint_install.wasm"), &WASM_MAGIC).unwrap();
+        fs::write(dir.join("pos_install.wasm"), pos_installer).unwrap();
+    }
+
+    #[test]
+    fn should_parse_chainspec() {
+        let dir = tempfile::tempdir().unwrap();
+        write_chainspec(dir.path(), MANIFEST, &WASM_MAGIC);
+
+        let genesis_config = parse_chainspec(dir.path().join("chainspec.toml")).unwrap();
+
+        assert_eq!(genesis_config.name(), "test-chain");
+        assert_eq!(genesis_config.protocol_version(), 1);
+        assert_eq!(genesis_config.mint_installer_bytes(), &WASM_MAGIC);
+        assert_eq!(genesis_config.wasm_costs(), WasmCosts::default());
+        assert_eq!(
+            genesis_config.refund_ratio(),
+            RefundRatio::new(1, 2).unwrap()
+        );
+        assert_eq!(genesis_config.native_transfer_cost(), 5000);
+        assert_eq!(
+            genesis_config.key_limits(),
+            KeyLimits::new(DEFAULT_MAX_ASSOCIATED_KEYS, 100)
+        );
+        assert_eq!(
+            genesis_config.delegation_rate_limits(),
+            DelegationRateLimits::new(3, 2).unwrap()
+        );
+        assert_eq!(
+            genesis_config.bond_limits(),
+            BondLimits::new(U512::from(50), 0, 10).unwrap()
+        );
+        assert_eq!(genesis_config.era_duration(), 3_600_000);
+        assert_eq!(genesis_config.unbond_delay(), 60_000);
+
+        let accounts = genesis_config.accounts();
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts[0].public_key(), PublicKey::new([1u8; 32]));
+        assert_eq!(accounts[0].balance(), Motes::new(U512::from(1000)));
+        assert_eq!(accounts[0].bonded_amount(), Motes::new(U512::from(100)));
+        assert_eq!(accounts[0].delegation_rate(), Some(10));
+        assert_eq!(accounts[1].delegation_rate(), None);
+        assert!(accounts[1].is_genesis_delegator());
+        assert!(!accounts[2].is_genesis_delegator());
+    }
+
+    #[test]
+    fn should_reject_invalid_wasm() {
+        let dir = tempfile::tempdir().unwrap();
+        write_chainspec(dir.path(), MANIFEST, b"not wasm");
+
+        match parse_chainspec(dir.path().join("chainspec.toml")) {
+            Err(Error::InvalidWasm(path)) => assert_eq!(path, dir.path().join("pos_install.wasm")),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_refund_ratio() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = MANIFEST.replace("denominator = 2", "denominator = 0");
+        write_chainspec(dir.path(), &manifest, &WASM_MAGIC);
+
+        match parse_chainspec(dir.path().join("chainspec.toml")) {
+            Err(Error::InvalidRefundRatio {
+                numerator: 1,
+                denominator: 0,
+            }) => (),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn should_reject_zero_key_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = MANIFEST.replace("max-named-keys = 100", "max-named-keys = 0");
+        write_chainspec(dir.path(), &manifest, &WASM_MAGIC);
+
+        match parse_chainspec(dir.path().join("chainspec.toml")) {
+            Err(Error::InvalidMaxNamedKeys(0)) => (),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn should_reject_zero_min_bond_amount() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = MANIFEST.replace("min-bond-amount = 50", "min-bond-amount = 0");
+        write_chainspec(dir.path(), &manifest, &WASM_MAGIC);
+
+        match parse_chainspec(dir.path().join("chainspec.toml")) {
+            Err(Error::InvalidMinBondAmount(0)) => (),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn should_reject_zero_era_duration() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = MANIFEST.replace("era-duration = 3600000", "era-duration = 0");
+        write_chainspec(dir.path(), &manifest, &WASM_MAGIC);
+
+        match parse_chainspec(dir.path().join("chainspec.toml")) {
+            Err(Error::InvalidEraDuration(0)) => (),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn should_reject_too_high_delegation_rate_increase() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = MANIFEST.replace("max-increase-per-era = 3", "max-increase-per-era = 101");
+        write_chainspec(dir.path(), &manifest, &WASM_MAGIC);
+
+        match parse_chainspec(dir.path().join("chainspec.toml")) {
+            Err(Error::InvalidMaxDelegationRateIncrease(101)) => (),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_accounts() {
+        let too_high_rate =
+            "0101010101010101010101010101010101010101010101010101010101010101,1,1,101";
+        let short_key = "0101,1,1";
+        let duplicate = "\
+0101010101010101010101010101010101010101010101010101010101010101,1,1
+0101010101010101010101010101010101010101010101010101010101010101,2,2";
+
+        for (contents, expected_line) in &[(too_high_rate, 1), (short_key, 1), (duplicate, 2)] {
+            match parse_accounts(contents) {
+                Err(Error::InvalidAccount { line, .. }) => assert_eq!(line, *expected_line),
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use contract_ffi::key::Key;
+
+/// The keys written by the deploys preceding a deploy in the same exec request, recording which
+/// of them the deploy looks up.
+///
+/// Deploys in an exec request all run against the same prestate, so the only way a deploy can
+/// observe the deploys before it is through the keys it looks up here.
+pub struct PrecedingWrites<'a> {
+    writes: &'a HashSet<Key>,
+    reads: HashSet<Key>,
+}
+
+impl<'a> PrecedingWrites<'a> {
+    fn new(writes: &'a HashSet<Key>) -> Self {
+        PrecedingWrites {
+            writes,
+            reads: HashSet::new(),
+        }
+    }
+
+    /// Returns true if one of the preceding deploys wrote to `key`.
+    pub fn contains(&mut self, key: &Key) -> bool {
+        self.reads.insert(*key);
+        self.writes.contains(key)
+    }
+}
+
+/// The result of executing a deploy, along with the keys it read from and wrote to.
+type Executed<R> = (R, HashSet<Key>, HashSet<Key>);
+
+fn execute_after<T, R, E, F>(
+    execute: &F,
+    deploy: &T,
+    preceding_writes: &HashSet<Key>,
+) -> Result<Executed<R>, E>
+where
+    F: Fn(&T, &mut PrecedingWrites) -> Result<(R, HashSet<Key>), E>,
+{
+    let mut preceding_writes = PrecedingWrites::new(preceding_writes);
+    let (result, writes) = execute(deploy, &mut preceding_writes)?;
+    Ok((result, preceding_writes.reads, writes))
+}
+
+/// Executes the deploys of an exec request, optionally in parallel.
+///
+/// In parallel, every deploy is first executed optimistically as if no deploy preceded it.
+/// Deploys which looked up a key written by a deploy preceding them are then executed again, in
+/// order, so the results are the same as those of a serial execution.
+pub struct DeployScheduler {
+    thread_pool: Option<ThreadPool>,
+}
+
+impl DeployScheduler {
+    /// Creates a scheduler executing deploys on `thread_count` threads.  A thread count of zero or
+    /// one executes deploys serially.
+    pub fn new(thread_count: usize) -> Self {
+        let thread_pool = if thread_count > 1 {
+            ThreadPoolBuilder::new()
+                .num_threads(thread_count)
+                .build()
+                .ok()
+        } else {
+            None
+        };
+        DeployScheduler { thread_pool }
+    }
+
+    pub fn thread_count(&self) -> usize {
+        self.thread_pool
+            .as_ref()
+            .map_or(1, ThreadPool::current_num_threads)
+    }
+
+    /// Executes `deploys` with `execute`, which returns the result of a deploy along with the keys
+    /// it writes for the deploys following it.  Execution stops at the first error.
+    pub fn execute<T, R, E, F>(&self, deploys: &[T], execute: F) -> Result<Vec<R>, E>
+    where
+        T: Sync,
+        R: Send,
+        E: Send,
+        F: Fn(&T, &mut PrecedingWrites) -> Result<(R, HashSet<Key>), E> + Sync,
+    {
+        self.execute_and_report(deploys, execute, |_, _| ())
+    }
+
+    /// Like [`DeployScheduler::execute`], additionally passing each result along with the index
+    /// of its deploy to `on_result` as soon as it is final.  Results are reported in order.
+    pub fn execute_and_report<T, R, E, F, C>(
+        &self,
+        deploys: &[T],
+        execute: F,
+        mut on_result: C,
+    ) -> Result<Vec<R>, E>
+    where
+        T: Sync,
+        R: Send,
+        E: Send,
+        F: Fn(&T, &mut PrecedingWrites) -> Result<(R, HashSet<Key>), E> + Sync,
+        C: FnMut(usize, &R),
+    {
+        let mut preceding_writes: HashSet<Key> = HashSet::new();
+        let mut results = Vec::with_capacity(deploys.len());
+
+        let thread_pool = match self.thread_pool {
+            Some(ref thread_pool) if deploys.len() > 1 => thread_pool,
+            _ => {
+                for (index, deploy) in deploys.iter().enumerate() {
+                    let (result, _, writes) = execute_after(&execute, deploy, &preceding_writes)?;
+                    preceding_writes.extend(writes);
+                    on_result(index, &result);
+                    results.push(result);
+                }
+                return Ok(results);
+            }
+        };
+
+        let no_writes = HashSet::new();
+        let optimistic: Vec<Result<Executed<R>, E>> = thread_pool.install(|| {
+            deploys
+                .par_iter()
+                .map(|deploy| execute_after(&execute, deploy, &no_writes))
+                .collect()
+        });
+
+        for (index, (deploy, optimistic_result)) in deploys.iter().zip(optimistic).enumerate() {
+            let (result, writes) = match optimistic_result {
+                Ok((result, reads, writes)) if reads.is_disjoint(&preceding_writes) => {
+                    (result, writes)
+                }
+                // Conflicting or failed deploys are executed again after the deploys preceding
+                // them
+                _ => {
+                    let (result, _, writes) = execute_after(&execute, deploy, &preceding_writes)?;
+                    (result, writes)
+                }
+            };
+            preceding_writes.extend(writes);
+            on_result(index, &result);
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+}
+
+impl fmt::Debug for DeployScheduler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DeployScheduler")
+            .field("thread_count", &self.thread_count())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use contract_ffi::key::Key;
+
+    use super::{DeployScheduler, PrecedingWrites};
+
+    /// A deploy writing `write` if none of the keys in `reads` were written before it.
+    struct TestDeploy {
+        reads: Vec<Key>,
+        write: Key,
+    }
+
+    fn deploy(reads: &[u8], write: u8) -> TestDeploy {
+        TestDeploy {
+            reads: reads.iter().map(|byte| Key::Hash([*byte; 32])).collect(),
+            write: Key::Hash([write; 32]),
+        }
+    }
+
+    fn execute_all(
+        scheduler: &DeployScheduler,
+        deploys: &[TestDeploy],
+        execution_count: &AtomicUsize,
+    ) -> Vec<bool> {
+        scheduler
+            .execute(
+                deploys,
+                |deploy: &TestDeploy, preceding_writes: &mut PrecedingWrites| {
+                    execution_count.fetch_add(1, Ordering::SeqCst);
+                    let conflicts = deploy
+                        .reads
+                        .iter()
+                        .filter(|key| preceding_writes.contains(key))
+                        .count();
+                    let mut writes = HashSet::new();
+                    if conflicts == 0 {
+                        writes.insert(deploy.write);
+                    }
+                    Ok::<_, ()>((conflicts == 0, writes))
+                },
+            )
+            .expect("should execute")
+    }
+
+    #[test]
+    fn should_match_serial_execution() {
+        let deploys = vec![
+            deploy(&[1], 1),
+            deploy(&[2], 2),
+            deploy(&[1], 3),
+            deploy(&[3, 4], 4),
+            deploy(&[5], 5),
+        ];
+
+        let serial = execute_all(&DeployScheduler::new(1), &deploys, &AtomicUsize::new(0));
+        let parallel = execute_all(&DeployScheduler::new(4), &deploys, &AtomicUsize::new(0));
+
+        assert_eq!(serial, vec![true, true, false, true, true]);
+        assert_eq!(parallel, serial);
+    }
+
+    #[test]
+    fn should_only_execute_conflicting_deploys_again() {
+        let deploys = vec![deploy(&[1], 1), deploy(&[2], 2), deploy(&[1], 3)];
+        let execution_count = AtomicUsize::new(0);
+
+        execute_all(&DeployScheduler::new(4), &deploys, &execution_count);
+
+        assert_eq!(execution_count.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn should_stop_at_first_error() {
+        let scheduler = DeployScheduler::new(4);
+
+        let result = scheduler.execute(&[0u8, 1, 2], |deploy: &u8, _: &mut PrecedingWrites| {
+            if *deploy >= 1 {
+                Err(*deploy)
+            } else {
+                Ok((*deploy, HashSet::new()))
+            }
+        });
+
+        // Deploys 1 and 2 both fail, but execution stops at deploy 1
+        assert_eq!(result, Err(1));
+    }
+
+    #[test]
+    fn should_report_results_in_order() {
+        for &thread_count in &[1, 4] {
+            let mut reported = Vec::new();
+
+            let results = DeployScheduler::new(thread_count)
+                .execute_and_report(
+                    &[3u8, 1, 2],
+                    |deploy: &u8, _: &mut PrecedingWrites| Ok::<_, ()>((*deploy, HashSet::new())),
+                    |index, result| reported.push((index, *result)),
+                )
+                .expect("should execute");
+
+            assert_eq!(results, vec![3, 1, 2]);
+            assert_eq!(reported, vec![(0, 3), (1, 1), (2, 2)]);
+        }
+    }
+
+    #[test]
+    fn should_execute_serially_with_one_thread() {
+        assert_eq!(DeployScheduler::new(0).thread_count(), 1);
+        assert_eq!(DeployScheduler::new(1).thread_count(), 1);
+        assert_eq!(DeployScheduler::new(2).thread_count(), 2);
+    }
+}
@@ -1,7 +1,26 @@
+use contract_ffi::system_contracts::pos::FeeHandling;
+use engine_wasm_prep::module_limits::ModuleLimits;
+use engine_wasm_prep::FloatHandling;
+
+/// Default number of preprocessed modules kept in the module cache
+pub const DEFAULT_MODULE_CACHE_SIZE: usize = 16;
+
+/// Default number of threads executing the deploys of an exec request
+pub const DEFAULT_DEPLOY_THREADS: usize = 1;
+
 /// The runtime configuration of the execution engine
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
     use_payment_code: bool,
+    fee_handling: FeeHandling,
+    float_handling: FloatHandling,
+    module_limits: ModuleLimits,
+    module_cache_size: usize,
+    use_system_contracts: bool,
+    deploy_threads: usize,
+    verify_deploy_hashes: bool,
+    verify_approvals: bool,
+    restrict_system_account: bool,
 }
 
 impl EngineConfig {
@@ -19,12 +38,126 @@ impl EngineConfig {
     pub fn use_payment_code(&self) -> bool {
         self.use_payment_code
     }
+
+    /// Sets the `fee_handling` field to the given arg.
+    pub fn set_fee_handling(mut self, arg: FeeHandling) -> EngineConfig {
+        self.fee_handling = arg;
+        self
+    }
+
+    pub fn fee_handling(&self) -> FeeHandling {
+        self.fee_handling
+    }
+
+    /// Sets the `float_handling` field to the given arg.
+    pub fn set_float_handling(mut self, arg: FloatHandling) -> EngineConfig {
+        self.float_handling = arg;
+        self
+    }
+
+    pub fn float_handling(&self) -> FloatHandling {
+        self.float_handling
+    }
+
+    /// Sets the `module_limits` field to the given arg.
+    pub fn set_module_limits(mut self, arg: ModuleLimits) -> EngineConfig {
+        self.module_limits = arg;
+        self
+    }
+
+    pub fn module_limits(&self) -> ModuleLimits {
+        self.module_limits
+    }
+
+    /// Sets the `module_cache_size` field to the given arg.
+    pub fn set_module_cache_size(mut self, arg: usize) -> EngineConfig {
+        self.module_cache_size = arg;
+        self
+    }
+
+    pub fn module_cache_size(&self) -> usize {
+        self.module_cache_size
+    }
+
+    /// Sets the `use_system_contracts` field to the given arg.  When false, calls to the mint and
+    /// the Proof of Stake contract run their host-side implementations instead of their wasm.
+    pub fn set_use_system_contracts(mut self, arg: bool) -> EngineConfig {
+        self.use_system_contracts = arg;
+        self
+    }
+
+    pub fn use_system_contracts(&self) -> bool {
+        self.use_system_contracts
+    }
+
+    /// Sets the `deploy_threads` field to the given arg.  The deploys of an exec request are
+    /// executed in parallel when it is greater than one.
+    pub fn set_deploy_threads(mut self, arg: usize) -> EngineConfig {
+        self.deploy_threads = arg;
+        self
+    }
+
+    pub fn deploy_threads(&self) -> usize {
+        self.deploy_threads
+    }
+
+    /// Sets the `verify_deploy_hashes` field to the given arg.  When true, a deploy whose hash is
+    /// not the hash of its header and body fails with a precondition failure.
+    pub fn set_verify_deploy_hashes(mut self, arg: bool) -> EngineConfig {
+        self.verify_deploy_hashes = arg;
+        self
+    }
+
+    pub fn verify_deploy_hashes(&self) -> bool {
+        self.verify_deploy_hashes
+    }
+
+    /// Sets the `verify_approvals` field to the given arg.  When true, only the authorization
+    /// keys with a valid Ed25519 or Secp256k1 signature of the deploy hash among the approvals of
+    /// a deploy count toward the weight thresholds of the deploying account.
+    pub fn set_verify_approvals(mut self, arg: bool) -> EngineConfig {
+        self.verify_approvals = arg;
+        self
+    }
+
+    pub fn verify_approvals(&self) -> bool {
+        self.verify_approvals
+    }
+
+    /// Sets the `restrict_system_account` field to the given arg.  When true, deploys executing
+    /// as the system account fail with a precondition failure, so that only the engine itself
+    /// acts as the system account.  The engine does so outside of deploys, which the restriction
+    /// doesn't apply to, when:
+    ///
+    /// * running genesis, which installs the system contracts, mints the initial balances and sets
+    ///   the era duration, the unbonding delay and the bond limits of the PoS contract,
+    /// * upgrading the protocol,
+    /// * finalizing the payment of every deploy, and
+    /// * running the PoS step at the start of every block, which applies the due bonding and
+    ///   unbonding requests and enters new eras.
+    pub fn set_restrict_system_account(mut self, arg: bool) -> EngineConfig {
+        self.restrict_system_account = arg;
+        self
+    }
+
+    pub fn restrict_system_account(&self) -> bool {
+        self.restrict_system_account
+    }
 }
 
 impl Default for EngineConfig {
     fn default() -> Self {
         EngineConfig {
             use_payment_code: false,
+            fee_handling: FeeHandling::default(),
+            float_handling: FloatHandling::default(),
+            module_limits: ModuleLimits::default(),
+            module_cache_size: DEFAULT_MODULE_CACHE_SIZE,
+            use_system_contracts: true,
+            deploy_threads: DEFAULT_DEPLOY_THREADS,
+            verify_deploy_hashes: false,
+            verify_approvals: false,
+            restrict_system_account: true,
         }
     }
 }
@@ -1,9 +1,15 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
 use failure::Fail;
 
+use engine_shared::motes::Motes;
 use engine_shared::newtypes::Blake2bHash;
 
 use contract_ffi::bytesrepr;
-use contract_ffi::system_contracts::mint;
+use contract_ffi::system_contracts::{mint, pos};
+use contract_ffi::value::account::{PublicKey, Weight};
+use contract_ffi::value::Account;
 
 use crate::execution;
 
@@ -23,8 +29,12 @@ pub enum Error {
     StorageError(engine_storage::error::Error),
     #[fail(display = "Authorization failure: not authorized.")]
     AuthorizationError,
+    #[fail(display = "Authorization failure: {}", _0)]
+    AuthorizationFailure(AuthorizationFailure),
     #[fail(display = "Insufficient payment")]
     InsufficientPaymentError,
+    #[fail(display = "Insufficient balance: {} required, {} available", _0, _1)]
+    InsufficientBalance { required: Motes, available: Motes },
     #[fail(display = "Deploy error")]
     DeployError,
     #[fail(display = "Payment finalization error")]
@@ -35,6 +45,75 @@ pub enum Error {
     SerializationError(bytesrepr::Error),
     #[fail(display = "Mint error: {}", _0)]
     MintError(mint::error::Error),
+    #[fail(display = "Invalid protocol version: {}", _0)]
+    InvalidProtocolVersion(u64),
+    #[fail(display = "Deploy dependency has not been executed: {:?}", _0)]
+    MissingDeployDependency([u8; 32]),
+    #[fail(display = "Deploy expired at {}, block time {}", _0, _1)]
+    DeployExpired { expired_at: u64, blocktime: u64 },
+    #[fail(display = "Deploy has already been executed: {:?}", _0)]
+    DuplicateDeploy([u8; 32]),
+    #[fail(display = "Deploy timestamp {} is after block time {}", _0, _1)]
+    FutureDeploy { timestamp: u64, blocktime: u64 },
+    #[fail(
+        display = "Deploy hash does not match the deploy's header and body: {:?}",
+        _0
+    )]
+    InvalidDeployHash([u8; 32]),
+    #[fail(display = "Gas price {} is below the minimum gas price {}", _0, _1)]
+    GasPriceTooLow { gas_price: u64, min_gas_price: u64 },
+    #[fail(display = "Deploys cannot be executed as the system account")]
+    SystemAccountDeploy,
+    #[fail(display = "Genesis bonds violate the bond limits: {}", _0)]
+    InvalidGenesisBonds(pos::error::Error),
+}
+
+/// Details of why a deploy's authorization keys don't authorize it to act on behalf of an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationFailure {
+    /// Provided keys which are not associated with the account.
+    pub unknown_keys: Vec<PublicKey>,
+    /// Provided associated keys, if their cumulative weight is below the deployment threshold.
+    pub below_threshold_keys: Vec<PublicKey>,
+    pub required_weight: Weight,
+    pub provided_weight: Weight,
+}
+
+impl AuthorizationFailure {
+    pub fn new(account: &Account, authorization_keys: &BTreeSet<PublicKey>) -> Self {
+        let (known_keys, unknown_keys): (Vec<PublicKey>, Vec<PublicKey>) = authorization_keys
+            .iter()
+            .partition(|key| account.get_associated_key_weight(**key).is_some());
+        let provided_weight = known_keys
+            .iter()
+            .filter_map(|key| account.get_associated_key_weight(*key))
+            .fold(0u8, |acc, weight| acc.saturating_add(weight.value()));
+        let required_weight = *account.action_thresholds().deployment();
+        let below_threshold_keys = if provided_weight < required_weight.value() {
+            known_keys
+        } else {
+            Vec::new()
+        };
+        AuthorizationFailure {
+            unknown_keys,
+            below_threshold_keys,
+            required_weight,
+            provided_weight: Weight::new(provided_weight),
+        }
+    }
+}
+
+impl fmt::Display for AuthorizationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown keys {:?}, keys below threshold {:?}, required weight {}, provided weight {}",
+            self.unknown_keys,
+            self.below_threshold_keys,
+            self.required_weight.value(),
+            self.provided_weight.value()
+        )
+    }
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
@@ -4,15 +4,151 @@ use contract_ffi::key::Key;
 use engine_shared::transform::Transform;
 
 use super::op::Op;
+use crate::tracking_copy::utils;
+
+/// An event emitted by a contract through the `emit_event` host function.
+///
+/// Events are not written to global state; they are only reported alongside
+/// the effects of the execution which emitted them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    /// The base key of the context which emitted the event.
+    pub emitter: Key,
+    pub topic: String,
+    pub payload: Vec<u8>,
+}
+
+impl Event {
+    pub fn new(emitter: Key, topic: String, payload: Vec<u8>) -> Self {
+        Event {
+            emitter,
+            topic,
+            payload,
+        }
+    }
+}
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct ExecutionEffect {
     pub ops: HashMap<Key, Op>,
     pub transforms: HashMap<Key, Transform>,
+    /// Events in the order they were emitted.
+    pub events: Vec<Event>,
 }
 
 impl ExecutionEffect {
     pub fn new(ops: HashMap<Key, Op>, transforms: HashMap<Key, Transform>) -> Self {
-        ExecutionEffect { ops, transforms }
+        ExecutionEffect {
+            ops,
+            transforms,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn with_events(self, events: Vec<Event>) -> Self {
+        ExecutionEffect { events, ..self }
+    }
+
+    /// Returns true if `self` and `other`, the effects of two deploys executed against the same
+    /// prestate, can be applied in either order, i.e. every key they both operate on is either
+    /// only read or only added to by both.
+    pub fn commutes_with(&self, other: &ExecutionEffect) -> bool {
+        self.ops.iter().all(|(key, op)| {
+            other
+                .ops
+                .get(key)
+                .map_or(true, |other_op| op.commutes_with(other_op))
+        })
+    }
+
+    /// Merges `other` into `self`.  Transforms on the same key are added together, so that e.g.
+    /// several deploys adding to the same balance result in a single `Transform::AddUInt512`.
+    pub fn merge(mut self, other: ExecutionEffect) -> Self {
+        for (key, op) in other.ops {
+            utils::add(&mut self.ops, key, op);
+        }
+        for (key, transform) in other.transforms {
+            utils::add(&mut self.transforms, key, transform);
+        }
+        self.events.extend(other.events);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use contract_ffi::key::Key;
+    use contract_ffi::uref::{AccessRights, URef};
+    use contract_ffi::value::{Value, U512};
+    use engine_shared::transform::Transform;
+
+    use super::ExecutionEffect;
+    use crate::engine_state::op::Op;
+
+    fn balance_key() -> Key {
+        Key::URef(URef::new([1; 32], AccessRights::READ_ADD_WRITE))
+    }
+
+    fn other_key() -> Key {
+        Key::URef(URef::new([2; 32], AccessRights::READ_ADD_WRITE))
+    }
+
+    fn effect(entries: &[(Key, Op, Transform)]) -> ExecutionEffect {
+        let mut ops = HashMap::new();
+        let mut transforms = HashMap::new();
+        for (key, op, transform) in entries.iter().cloned() {
+            ops.insert(key, op);
+            transforms.insert(key, transform);
+        }
+        ExecutionEffect::new(ops, transforms)
+    }
+
+    fn add_to_balance(amount: u64) -> ExecutionEffect {
+        effect(&[(
+            balance_key(),
+            Op::Add,
+            Transform::AddUInt512(U512::from(amount)),
+        )])
+    }
+
+    #[test]
+    fn should_merge_additions_to_same_balance() {
+        let first = add_to_balance(1);
+        let second = add_to_balance(2);
+
+        assert!(first.commutes_with(&second));
+        assert_eq!(first.merge(second), add_to_balance(3));
+    }
+
+    #[test]
+    fn should_not_commute_write_with_addition() {
+        let write = effect(&[(
+            balance_key(),
+            Op::Write,
+            Transform::Write(Value::UInt512(U512::zero())),
+        )]);
+        let add = add_to_balance(1);
+
+        assert!(!write.commutes_with(&add));
+        assert!(!add.commutes_with(&write));
+    }
+
+    #[test]
+    fn should_commute_effects_on_different_keys() {
+        let write = effect(&[(
+            other_key(),
+            Op::Write,
+            Transform::Write(Value::UInt512(U512::zero())),
+        )]);
+        let add = add_to_balance(1);
+
+        assert!(write.commutes_with(&add));
+
+        let merged = write.merge(add);
+        assert_eq!(merged.transforms.len(), 2);
+        assert_eq!(merged.ops.get(&other_key()), Some(&Op::Write));
+        assert_eq!(merged.ops.get(&balance_key()), Some(&Op::Add));
     }
 }
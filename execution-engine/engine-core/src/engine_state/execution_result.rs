@@ -4,15 +4,15 @@ use crate::tracking_copy;
 
 use contract_ffi::key::Key;
 use contract_ffi::value::Value;
-use engine_shared::gas::Gas;
+use engine_shared::gas::{Gas, GasBreakdown, GasCategory, Rounding};
 use engine_shared::motes::Motes;
 use engine_shared::newtypes::CorrelationId;
 use engine_shared::transform::Transform;
 use engine_storage::global_state::StateReader;
 
-use super::execution_effect::ExecutionEffect;
+use super::error;
+use super::execution_effect::{Event, ExecutionEffect};
 use super::op::Op;
-use super::{error, CONV_RATE};
 
 #[derive(Debug)]
 pub enum ExecutionResult {
@@ -21,9 +21,14 @@ pub enum ExecutionResult {
         error: error::Error,
         effect: ExecutionEffect,
         cost: Gas,
+        cost_breakdown: GasBreakdown,
     },
     /// Execution was finished successfully
-    Success { effect: ExecutionEffect, cost: Gas },
+    Success {
+        effect: ExecutionEffect,
+        cost: Gas,
+        cost_breakdown: GasBreakdown,
+    },
 }
 
 impl ExecutionResult {
@@ -35,6 +40,7 @@ impl ExecutionResult {
             error,
             effect: Default::default(),
             cost: Gas::default(),
+            cost_breakdown: GasBreakdown::default(),
         }
     }
 
@@ -59,6 +65,14 @@ impl ExecutionResult {
         }
     }
 
+    /// Gas consumed, split by category. Its total matches [`ExecutionResult::cost`].
+    pub fn cost_breakdown(&self) -> GasBreakdown {
+        match self {
+            ExecutionResult::Failure { cost_breakdown, .. } => *cost_breakdown,
+            ExecutionResult::Success { cost_breakdown, .. } => *cost_breakdown,
+        }
+    }
+
     pub fn effect(&self) -> &ExecutionEffect {
         match self {
             ExecutionResult::Failure { effect, .. } => effect,
@@ -66,25 +80,45 @@ impl ExecutionResult {
         }
     }
 
-    pub fn with_cost(self, cost: Gas) -> Self {
+    pub fn with_cost(self, cost_breakdown: GasBreakdown) -> Self {
+        let cost = cost_breakdown.total();
         match self {
             ExecutionResult::Failure { error, effect, .. } => ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                cost_breakdown,
+            },
+            ExecutionResult::Success { effect, .. } => ExecutionResult::Success {
+                effect,
+                cost,
+                cost_breakdown,
             },
-            ExecutionResult::Success { effect, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
     pub fn with_effect(self, effect: ExecutionEffect) -> Self {
         match self {
-            ExecutionResult::Failure { error, cost, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
                 error,
+                cost,
+                cost_breakdown,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                cost_breakdown,
+            },
+            ExecutionResult::Success {
+                cost,
+                cost_breakdown,
+                ..
+            } => ExecutionResult::Success {
                 effect,
                 cost,
+                cost_breakdown,
             },
-            ExecutionResult::Success { cost, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 }
@@ -139,15 +173,19 @@ impl ExecutionResultBuilder {
     }
 
     pub fn total_cost(&self) -> Gas {
+        self.total_cost_breakdown().total()
+    }
+
+    pub fn total_cost_breakdown(&self) -> GasBreakdown {
         let payment_cost = self
             .payment_execution_result
             .as_ref()
-            .map(ExecutionResult::cost)
+            .map(ExecutionResult::cost_breakdown)
             .unwrap_or_default();
         let session_cost = self
             .session_execution_result
             .as_ref()
-            .map(ExecutionResult::cost)
+            .map(ExecutionResult::cost_breakdown)
             .unwrap_or_default();
         payment_cost + session_cost
     }
@@ -155,6 +193,7 @@ impl ExecutionResultBuilder {
     pub fn check_forced_transfer(
         &mut self,
         max_payment_cost: Motes,
+        gas_price: u64,
         account_main_purse_balance: Motes,
         payment_purse_balance: Motes,
         account_main_purse: Key,
@@ -165,12 +204,13 @@ impl ExecutionResultBuilder {
             None => return None,
         };
         let payment_result_cost = payment_result.cost();
+        let payment_result_cost_breakdown = payment_result.cost_breakdown();
         let payment_result_is_failure = payment_result.is_failure();
 
         // payment_code_spec_3_b_ii: if (balance of PoS pay purse) < (gas spent during
-        // payment code execution) * conv_rate, no session
+        // payment code execution) * gas_price, no session
         let insufficient_balance_to_continue =
-            payment_purse_balance < Motes::from_gas(payment_result_cost, CONV_RATE)?;
+            payment_purse_balance < Motes::from_gas(payment_result_cost, gas_price)?;
 
         // payment_code_spec_4: insufficient payment
         if !(insufficient_balance_to_continue || payment_result_is_failure) {
@@ -180,7 +220,10 @@ impl ExecutionResultBuilder {
         let mut ops = HashMap::new();
         let mut transforms = HashMap::new();
 
-        let new_balance = account_main_purse_balance - max_payment_cost;
+        // The main purse balance was checked to cover `max_payment_cost` before payment ran
+        let new_balance = account_main_purse_balance
+            .checked_sub(max_payment_cost)
+            .unwrap_or_default();
 
         let account_main_purse_normalize = account_main_purse.normalize();
         let rewards_purse_normalize = rewards_purse.normalize();
@@ -199,12 +242,19 @@ impl ExecutionResultBuilder {
 
         let error = error::Error::InsufficientPaymentError;
         let effect = ExecutionEffect::new(ops, transforms);
-        let cost = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+        // The whole of `max_payment_cost` is charged, so round up to never under-report it
+        let cost = Gas::from_motes(max_payment_cost, gas_price, Rounding::Ceil).unwrap_or_default();
+        // Whatever the payment code did not spend is forfeited by the forced transfer
+        let mut cost_breakdown = payment_result_cost_breakdown;
+        if let Some(forfeited) = cost.checked_sub(payment_result_cost) {
+            cost_breakdown.add(GasCategory::Transfers, forfeited);
+        }
 
         Some(ExecutionResult::Failure {
             error,
             effect,
             cost,
+            cost_breakdown,
         })
     }
 
@@ -213,13 +263,15 @@ impl ExecutionResultBuilder {
         reader: &R,
         correlation_id: CorrelationId,
     ) -> Result<ExecutionResult, ExecutionResultBuilderError> {
-        let cost = self.total_cost();
+        let cost_breakdown = self.total_cost_breakdown();
         let mut ops = HashMap::new();
         let mut transforms = HashMap::new();
+        let mut events = Vec::new();
 
         let mut ret: ExecutionResult = ExecutionResult::Success {
             effect: Default::default(),
-            cost,
+            cost: cost_breakdown.total(),
+            cost_breakdown,
         };
 
         match self.payment_execution_result {
@@ -227,7 +279,7 @@ impl ExecutionResultBuilder {
                 if result.is_failure() {
                     return Ok(result);
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingPaymentExecutionResult),
@@ -238,9 +290,9 @@ impl ExecutionResultBuilder {
         match self.session_execution_result {
             Some(result) => {
                 if result.is_failure() {
-                    ret = result.with_cost(cost);
+                    ret = result.with_cost(cost_breakdown);
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingSessionExecutionResult),
@@ -254,7 +306,7 @@ impl ExecutionResultBuilder {
                         error::Error::FinalizationError,
                     ));
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingFinalizeExecutionResult),
@@ -263,12 +315,13 @@ impl ExecutionResultBuilder {
         // Remove redundant writes to allow more opportunity to commute
         let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id);
 
-        Ok(ret.with_effect(reduced_effect))
+        Ok(ret.with_effect(reduced_effect.with_events(events)))
     }
 
     fn add_effects(
         ops: &mut HashMap<Key, Op>,
         transforms: &mut HashMap<Key, Transform>,
+        events: &mut Vec<Event>,
         effect: &ExecutionEffect,
     ) {
         for (k, op) in effect.ops.iter() {
@@ -277,6 +330,7 @@ impl ExecutionResultBuilder {
         for (k, t) in effect.transforms.iter() {
             tracking_copy::utils::add(transforms, *k, t.clone());
         }
+        events.extend(effect.events.iter().cloned());
     }
 
     /// In the case we are writing the same value as was there originally,
@@ -7,11 +7,17 @@ use crate::engine_state::execution_effect::ExecutionEffect;
 use crate::engine_state::op::Op;
 use crate::engine_state::utils::WasmiBytes;
 use crate::execution;
-use contract_ffi::bytesrepr::ToBytes;
+use contract_ffi::bytesrepr::{self, ToBytes};
 use contract_ffi::key::Key;
+use contract_ffi::system_contracts::mint;
+use contract_ffi::system_contracts::pos::{
+    self, BondLimits, DelegationRate, DelegationRateLimits, RefundRatio, DEFAULT_ERA_DURATION,
+    DEFAULT_UNBOND_DELAY,
+};
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{PublicKey, PurseId};
 use contract_ffi::value::{Account, Contract, Value, U512};
+use engine_shared::key_limits::KeyLimits;
 use engine_shared::motes::Motes;
 use engine_shared::newtypes::Blake2bHash;
 use engine_shared::transform::{Transform, TypeMismatch};
@@ -37,6 +43,7 @@ pub const MINT_GENESIS_ACCOUNT_BALANCE_UREF: &str = "mint_genesis_account_balanc
 pub const MINT_POS_BONDING_BALANCE_UREF: &str = "mint_pos_bonding_balance_uref";
 pub const MINT_POS_PAYMENT_BALANCE_UREF: &str = "mint_pos_payment_balance_uref";
 pub const MINT_POS_REWARDS_BALANCE_UREF: &str = "mint_pos_rewards_balance_uref";
+pub const MINT_TOTAL_SUPPLY_UREF: &str = "mint_total_supply_uref";
 
 /// Structure for tracking URefs generated in the genesis process.
 pub struct GenesisURefsSource(BTreeMap<&'static str, URef>);
@@ -90,6 +97,7 @@ impl Default for GenesisURefsSource {
             MINT_POS_REWARDS_BALANCE_UREF,
             create_uref(&mut address_generator),
         );
+        urefs_map.insert(MINT_TOTAL_SUPPLY_UREF, create_uref(&mut address_generator));
 
         GenesisURefsSource(urefs_map)
     }
@@ -211,6 +219,11 @@ fn create_mint_effects(
     let balance: Value = Value::UInt512(initial_motes);
     tmp.insert(balance_uref_key, balance);
 
+    // Create & store total supply
+    let total_supply_key: Key = urefs_source.get_uref(MINT_TOTAL_SUPPLY_UREF).into();
+    let total_supply: Value = Value::UInt512(initial_motes + pos_bonded_balance);
+    tmp.insert(total_supply_key, total_supply);
+
     // Create mint_contract
     let mint_known_urefs = {
         let mut ret: BTreeMap<String, Key> = BTreeMap::new();
@@ -219,6 +232,7 @@ fn create_mint_effects(
         ret.insert(pos_bonding_balance_key.as_string(), pos_bonding_balance_key);
         ret.insert(pos_payment_balance_key.as_string(), pos_payment_balance_key);
         ret.insert(pos_rewards_balance_key.as_string(), pos_rewards_balance_key);
+        ret.insert(String::from(mint::TOTAL_SUPPLY_KEY), total_supply_key);
         ret
     };
 
@@ -261,11 +275,7 @@ fn create_pos_effects(
     let mut known_urefs: BTreeMap<String, Key> = genesis_validators
         .iter()
         .map(|(pub_key, balance)| {
-            let key_bytes = pub_key.value();
-            let mut hex_key = String::with_capacity(64);
-            for byte in &key_bytes[..32] {
-                write!(hex_key, "{:02x}", byte).unwrap();
-            }
+            let hex_key = pub_key.to_hex();
             let mut uref = String::new();
             uref.write_fmt(format_args!("v_{}_{}", hex_key, balance))
                 .unwrap();
@@ -389,26 +399,33 @@ mod tests {
     use std::collections::btree_map::BTreeMap;
     use std::collections::HashMap;
 
-    use crate::engine_state::create_genesis_effects;
     use crate::engine_state::genesis::{
         GenesisURefsSource, GENESIS_ACCOUNT_PURSE, MINT_GENESIS_ACCOUNT_BALANCE_UREF,
         MINT_POS_BONDING_BALANCE_UREF, MINT_POS_PAYMENT_BALANCE_UREF,
         MINT_POS_REWARDS_BALANCE_UREF, MINT_PRIVATE_ADDRESS, MINT_PUBLIC_ADDRESS,
-        POS_PRIVATE_ADDRESS, POS_PUBLIC_ADDRESS,
+        MINT_TOTAL_SUPPLY_UREF, POS_PRIVATE_ADDRESS, POS_PUBLIC_ADDRESS,
     };
     use crate::engine_state::utils::{pos_validator_key, WasmiBytes};
+    use crate::engine_state::{create_genesis_effects, DEFAULT_NATIVE_TRANSFER_COST};
     use contract_ffi::key::Key;
+    use contract_ffi::system_contracts::mint;
+    use contract_ffi::system_contracts::pos::{
+        self, BondLimits, DelegationRate, DelegationRateLimits, RefundRatio,
+    };
     use contract_ffi::value::account::PublicKey;
     use contract_ffi::value::{Contract, Value, U512};
+    use engine_shared::key_limits::KeyLimits;
+    use engine_shared::motes::Motes;
     use engine_shared::test_utils;
     use engine_shared::transform::Transform;
     use engine_wasm_prep::wasm_costs::WasmCosts;
+    use num_traits::Zero;
 
-    use super::{create_local_key, POS_BONDING_PURSE};
+    use super::{create_local_key, GenesisAccount, GenesisConfig, POS_BONDING_PURSE};
 
     const GENESIS_ACCOUNT_ADDR: [u8; 32] = [6u8; 32];
     const PROTOCOL_VERSION: u64 = 1;
-    const EXPECTED_GENESIS_TRANSFORM_COUNT: usize = 13; // 11 writes for Mint and 2 for PoS.
+    const EXPECTED_GENESIS_TRANSFORM_COUNT: usize = 14; // 12 writes for Mint and 2 for PoS.
     const INITIAL_GENESIS_ACCOUNT_BALANCE: &str = "1000";
     const INITIAL_POS_VALIDATORS_BALANCE: &str = "15000";
 
@@ -567,6 +584,7 @@ mod tests {
             urefs_source.get_uref(MINT_POS_PAYMENT_BALANCE_UREF).into();
         let pos_rewards_balance_uref_key: Key =
             urefs_source.get_uref(MINT_POS_REWARDS_BALANCE_UREF).into();
+        let total_supply_uref_key: Key = urefs_source.get_uref(MINT_TOTAL_SUPPLY_UREF).into();
 
         let mint_known_urefs = {
             let mut ret: BTreeMap<String, Key> = BTreeMap::new();
@@ -583,6 +601,7 @@ mod tests {
                 pos_rewards_balance_uref_key,
             );
             ret.insert(balance_uref_key.as_string(), balance_uref_key);
+            ret.insert(String::from(mint::TOTAL_SUPPLY_KEY), total_supply_uref_key);
             ret
         };
 
@@ -704,6 +723,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_genesis_effects_stores_total_supply() {
+        let urefs_source = GenesisURefsSource::default();
+        let total_supply_uref_key =
+            Key::URef(urefs_source.get_uref(MINT_TOTAL_SUPPLY_UREF)).normalize();
+
+        let transforms = get_genesis_transforms();
+
+        let actual_total_supply = extract_transform_u512(&transforms, &total_supply_uref_key)
+            .expect("transform was not a write of a U512");
+
+        let expected_total_supply = get_initial_motes(INITIAL_GENESIS_ACCOUNT_BALANCE)
+            + get_initial_motes(INITIAL_POS_VALIDATORS_BALANCE);
+
+        assert_eq!(
+            actual_total_supply, expected_total_supply,
+            "Invalid total supply"
+        );
+    }
+
     #[test]
     fn create_genesis_effects_stores_genesis_account_at_genesis_account_addr() {
         let account_key = Key::Account(GENESIS_ACCOUNT_ADDR);
@@ -831,6 +870,146 @@ mod tests {
             "create_pos_effects should store POS_BONDING_PURSE in PoS contract's known urefs map."
         );
     }
+
+    #[test]
+    fn should_split_genesis_validators_and_delegators() {
+        let validator = GenesisAccount::new(
+            PublicKey::new([1u8; 32]),
+            Motes::new(U512::from(10)),
+            Motes::new(U512::from(5)),
+        )
+        .with_delegation_rate(20);
+        let delegator = GenesisAccount::new(
+            PublicKey::new([2u8; 32]),
+            Motes::new(U512::from(10)),
+            Motes::new(U512::from(3)),
+        )
+        .with_delegation_rate(30)
+        .into_genesis_delegator();
+        let unbonded = GenesisAccount::new(
+            PublicKey::new([3u8; 32]),
+            Motes::new(U512::from(10)),
+            Motes::zero(),
+        )
+        .with_delegation_rate(40);
+
+        let genesis_config = GenesisConfig::new(
+            String::from("test"),
+            0,
+            PROTOCOL_VERSION,
+            Vec::new(),
+            Vec::new(),
+            vec![validator, delegator, unbonded],
+            WasmCosts::default(),
+            RefundRatio::default(),
+            1,
+            DEFAULT_NATIVE_TRANSFER_COST,
+            KeyLimits::default(),
+        );
+
+        let validators: Vec<(PublicKey, Motes)> = genesis_config.get_bonded_validators().collect();
+        assert_eq!(
+            validators,
+            vec![(validator.public_key(), Motes::new(U512::from(5)))]
+        );
+
+        let delegation_rates: Vec<(PublicKey, DelegationRate)> =
+            genesis_config.get_delegation_rates().collect();
+        assert_eq!(delegation_rates, vec![(validator.public_key(), 20)]);
+
+        let delegators: Vec<(PublicKey, Motes)> = genesis_config.get_bonded_delegators().collect();
+        assert_eq!(
+            delegators,
+            vec![(delegator.public_key(), Motes::new(U512::from(3)))]
+        );
+    }
+
+    #[test]
+    fn should_validate_genesis_bonds() {
+        let account = |byte: u8, bonded_amount: u64| {
+            GenesisAccount::new(
+                PublicKey::new([byte; 32]),
+                Motes::new(U512::from(10)),
+                Motes::new(U512::from(bonded_amount)),
+            )
+        };
+        let config =
+            genesis_config_with_accounts(vec![account(1, 10), account(2, 30), account(3, 0)]);
+        assert_eq!(config.validate_bonds(), Ok(()));
+
+        let limits = BondLimits::new(U512::from(10), 3, 2).unwrap();
+        assert_eq!(
+            config.clone().with_bond_limits(limits).validate_bonds(),
+            Ok(())
+        );
+
+        let small_limits = BondLimits::new(U512::from(11), 3, 2).unwrap();
+        assert_eq!(
+            config
+                .clone()
+                .with_bond_limits(small_limits)
+                .validate_bonds(),
+            Err(pos::error::Error::BondBelowMinimum)
+        );
+
+        let large_limits = BondLimits::new(U512::from(10), 2, 2).unwrap();
+        assert_eq!(
+            config
+                .clone()
+                .with_bond_limits(large_limits)
+                .validate_bonds(),
+            Err(pos::error::Error::BondAboveMaximum)
+        );
+
+        let few_limits = BondLimits::new(U512::from(10), 3, 1).unwrap();
+        assert_eq!(
+            config.with_bond_limits(few_limits).validate_bonds(),
+            Err(pos::error::Error::TooManyValidators)
+        );
+    }
+
+    fn genesis_config_with_accounts(accounts: Vec<GenesisAccount>) -> GenesisConfig {
+        GenesisConfig::new(
+            String::from("test"),
+            0,
+            PROTOCOL_VERSION,
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+            accounts,
+            WasmCosts::default(),
+            RefundRatio::default(),
+            1,
+            DEFAULT_NATIVE_TRANSFER_COST,
+            KeyLimits::default(),
+        )
+    }
+
+    #[test]
+    fn genesis_config_hash_should_not_depend_on_account_order() {
+        let account_1 = GenesisAccount::new(
+            PublicKey::new([1u8; 32]),
+            Motes::new(U512::from(10)),
+            Motes::new(U512::from(5)),
+        );
+        let account_2 = GenesisAccount::new(
+            PublicKey::new([2u8; 32]),
+            Motes::new(U512::from(20)),
+            Motes::zero(),
+        );
+
+        let config = genesis_config_with_accounts(vec![account_1, account_2]);
+        let reversed_config = genesis_config_with_accounts(vec![account_2, account_1]);
+        assert_eq!(config, reversed_config);
+        assert_eq!(config.hash().unwrap(), reversed_config.hash().unwrap());
+
+        let other_config =
+            genesis_config_with_accounts(vec![account_1, account_2.with_delegation_rate(1)]);
+        assert_ne!(config.hash().unwrap(), other_config.hash().unwrap());
+
+        let limited_config = genesis_config_with_accounts(vec![account_1, account_2])
+            .with_delegation_rate_limits(DelegationRateLimits::new(0, 1).unwrap());
+        assert_ne!(config.hash().unwrap(), limited_config.hash().unwrap());
+    }
 }
 
 /* --- NEW GENESIS STARTS HERE --- */
@@ -840,6 +1019,8 @@ pub struct GenesisAccount {
     public_key: PublicKey,
     balance: Motes,
     bonded_amount: Motes,
+    delegation_rate: Option<DelegationRate>,
+    is_genesis_delegator: bool,
 }
 
 impl GenesisAccount {
@@ -848,9 +1029,23 @@ impl GenesisAccount {
             public_key,
             balance,
             bonded_amount,
+            delegation_rate: None,
+            is_genesis_delegator: false,
         }
     }
 
+    /// Sets the commission this account takes on delegated rewards once bonded as a validator.
+    pub fn with_delegation_rate(mut self, delegation_rate: DelegationRate) -> Self {
+        self.delegation_rate = Some(delegation_rate);
+        self
+    }
+
+    /// Marks the bonded amount of this account as delegated stake rather than a validator bond.
+    pub fn into_genesis_delegator(mut self) -> Self {
+        self.is_genesis_delegator = true;
+        self
+    }
+
     pub fn public_key(&self) -> PublicKey {
         self.public_key
     }
@@ -862,6 +1057,30 @@ impl GenesisAccount {
     pub fn bonded_amount(&self) -> Motes {
         self.bonded_amount
     }
+
+    pub fn delegation_rate(&self) -> Option<DelegationRate> {
+        self.delegation_rate
+    }
+
+    pub fn is_genesis_delegator(&self) -> bool {
+        self.is_genesis_delegator
+    }
+
+    /// Returns `true` if the account is bonded as a validator at genesis.
+    pub fn is_genesis_validator(&self) -> bool {
+        !self.is_genesis_delegator && self.bonded_amount > Motes::zero()
+    }
+}
+
+impl ToBytes for GenesisAccount {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = self.public_key.to_bytes()?;
+        ret.append(&mut self.balance.value().to_bytes()?);
+        ret.append(&mut self.bonded_amount.value().to_bytes()?);
+        ret.append(&mut self.delegation_rate.to_bytes()?);
+        ret.append(&mut self.is_genesis_delegator.to_bytes()?);
+        Ok(ret)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -873,9 +1092,18 @@ pub struct GenesisConfig {
     proof_of_stake_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    refund_ratio: RefundRatio,
+    min_gas_price: u64,
+    native_transfer_cost: u64,
+    key_limits: KeyLimits,
+    delegation_rate_limits: DelegationRateLimits,
+    bond_limits: BondLimits,
+    era_duration: u64,
+    unbond_delay: u64,
 }
 
 impl GenesisConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         name: String,
         timestamp: u64,
@@ -884,7 +1112,15 @@ impl GenesisConfig {
         proof_of_stake_installer_bytes: Vec<u8>,
         accounts: Vec<GenesisAccount>,
         wasm_costs: WasmCosts,
+        refund_ratio: RefundRatio,
+        min_gas_price: u64,
+        native_transfer_cost: u64,
+        key_limits: KeyLimits,
     ) -> Self {
+        // Genesis creates the accounts in this order, so it must not depend on the order in which
+        // they were given.
+        let mut accounts = accounts;
+        accounts.sort_by_key(GenesisAccount::public_key);
         GenesisConfig {
             name,
             timestamp,
@@ -893,9 +1129,48 @@ impl GenesisConfig {
             proof_of_stake_installer_bytes,
             accounts,
             wasm_costs,
+            refund_ratio,
+            min_gas_price,
+            native_transfer_cost,
+            key_limits,
+            delegation_rate_limits: DelegationRateLimits::default(),
+            bond_limits: BondLimits::default(),
+            era_duration: DEFAULT_ERA_DURATION,
+            unbond_delay: DEFAULT_UNBOND_DELAY,
         }
     }
 
+    /// Sets the caps on how the validators may change their delegation rates, instead of the
+    /// default ones.
+    pub fn with_delegation_rate_limits(
+        mut self,
+        delegation_rate_limits: DelegationRateLimits,
+    ) -> Self {
+        self.delegation_rate_limits = delegation_rate_limits;
+        self
+    }
+
+    /// Sets the limits on the stakes of the validators and on their number, instead of the
+    /// default ones.
+    pub fn with_bond_limits(mut self, bond_limits: BondLimits) -> Self {
+        self.bond_limits = bond_limits;
+        self
+    }
+
+    /// Sets the time in milliseconds an era of the Proof of Stake contract lasts, instead of
+    /// `DEFAULT_ERA_DURATION`.
+    pub fn with_era_duration(mut self, era_duration: u64) -> Self {
+        self.era_duration = era_duration;
+        self
+    }
+
+    /// Sets the time in milliseconds from an unbonding request until the Proof of Stake contract
+    /// pays out the unbonded stake, instead of `DEFAULT_UNBOND_DELAY`.
+    pub fn with_unbond_delay(mut self, unbond_delay: u64) -> Self {
+        self.unbond_delay = unbond_delay;
+        self
+    }
+
     pub fn name(&self) -> &str {
         self.name.as_str()
     }
@@ -920,10 +1195,86 @@ impl GenesisConfig {
         self.wasm_costs
     }
 
+    pub fn refund_ratio(&self) -> RefundRatio {
+        self.refund_ratio
+    }
+
+    pub fn min_gas_price(&self) -> u64 {
+        self.min_gas_price
+    }
+
+    /// Returns the gas charged for a native transfer.
+    pub fn native_transfer_cost(&self) -> u64 {
+        self.native_transfer_cost
+    }
+
+    /// Returns the limits on the keys accounts may hold.
+    pub fn key_limits(&self) -> KeyLimits {
+        self.key_limits
+    }
+
+    /// Returns the caps on how the validators may change their delegation rates.
+    pub fn delegation_rate_limits(&self) -> DelegationRateLimits {
+        self.delegation_rate_limits
+    }
+
+    /// Returns the limits on the stakes of the validators and on their number.
+    pub fn bond_limits(&self) -> BondLimits {
+        self.bond_limits
+    }
+
+    /// Returns the time in milliseconds an era of the Proof of Stake contract lasts.
+    pub fn era_duration(&self) -> u64 {
+        self.era_duration
+    }
+
+    /// Returns the time in milliseconds from an unbonding request until the Proof of Stake
+    /// contract pays out the unbonded stake.
+    pub fn unbond_delay(&self) -> u64 {
+        self.unbond_delay
+    }
+
+    /// Checks that the genesis validators respect the bond limits, the same way the Proof of Stake
+    /// contract checks the validators bonding later on.
+    pub fn validate_bonds(&self) -> Result<(), pos::error::Error> {
+        let mut validator_count = 0;
+        for (_, bonded_amount) in self.get_bonded_validators() {
+            self.bond_limits.check_stake(bonded_amount.value())?;
+            validator_count += 1;
+        }
+        self.bond_limits.check_validator_count(validator_count)
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (PublicKey, Motes)> + '_ {
+        self.accounts
+            .iter()
+            .filter(|genesis_account| genesis_account.is_genesis_validator())
+            .map(|genesis_account| {
+                (
+                    genesis_account.public_key(),
+                    genesis_account.bonded_amount(),
+                )
+            })
+    }
+
+    /// Returns the delegation rates of the genesis validators which set one.
+    pub fn get_delegation_rates(&self) -> impl Iterator<Item = (PublicKey, DelegationRate)> + '_ {
+        self.accounts.iter().filter_map(|genesis_account| {
+            if genesis_account.is_genesis_validator() {
+                genesis_account
+                    .delegation_rate()
+                    .map(|rate| (genesis_account.public_key(), rate))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the genesis delegators along with their delegated stake.
+    pub fn get_bonded_delegators(&self) -> impl Iterator<Item = (PublicKey, Motes)> + '_ {
         let zero = Motes::zero();
         self.accounts.iter().filter_map(move |genesis_account| {
-            if genesis_account.bonded_amount() > zero {
+            if genesis_account.is_genesis_delegator() && genesis_account.bonded_amount() > zero {
                 Some((
                     genesis_account.public_key(),
                     genesis_account.bonded_amount(),
@@ -934,7 +1285,32 @@ impl GenesisConfig {
         })
     }
 
+    /// Returns the accounts, sorted by public key.
     pub fn accounts(&self) -> &[GenesisAccount] {
         self.accounts.as_slice()
     }
+
+    /// Returns the hash of the whole config. Genesis seeds the generation of addresses with it,
+    /// so the genesis post-state depends on nothing but the config.
+    pub fn hash(&self) -> Result<Blake2bHash, bytesrepr::Error> {
+        let mut bytes = self.name.to_bytes()?;
+        bytes.append(&mut self.timestamp.to_bytes()?);
+        bytes.append(&mut self.protocol_version.to_bytes()?);
+        bytes.append(&mut self.mint_installer_bytes.to_bytes()?);
+        bytes.append(&mut self.proof_of_stake_installer_bytes.to_bytes()?);
+        bytes.append(&mut (self.accounts.len() as u32).to_bytes()?);
+        for account in &self.accounts {
+            bytes.append(&mut account.to_bytes()?);
+        }
+        bytes.append(&mut self.wasm_costs.to_bytes()?);
+        bytes.append(&mut self.refund_ratio.to_bytes()?);
+        bytes.append(&mut self.min_gas_price.to_bytes()?);
+        bytes.append(&mut self.native_transfer_cost.to_bytes()?);
+        bytes.append(&mut self.key_limits.to_bytes()?);
+        bytes.append(&mut self.delegation_rate_limits.to_bytes()?);
+        bytes.append(&mut self.bond_limits.to_bytes()?);
+        bytes.append(&mut self.era_duration.to_bytes()?);
+        bytes.append(&mut self.unbond_delay.to_bytes()?);
+        Ok(Blake2bHash::new(&bytes))
+    }
 }
@@ -1,10 +1,16 @@
+pub mod approval;
+pub mod chainspec;
+pub mod deploy_scheduler;
 pub mod engine_config;
 pub mod error;
 pub mod executable_deploy_item;
 pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
+pub mod module_cache;
+pub mod native_transfer;
 pub mod op;
+pub mod upgrade;
 pub mod utils;
 
 use std::cell::RefCell;
@@ -18,46 +24,161 @@ use contract_ffi::contract_api::argsparser::ArgsParser;
 use contract_ffi::execution::Phase;
 use contract_ffi::key::{Key, HASH_SIZE};
 use contract_ffi::system_contracts::mint;
+use contract_ffi::system_contracts::pos::{DelegationRate, RefundRatio};
 use contract_ffi::uref::URef;
 use contract_ffi::uref::{AccessRights, UREF_ADDR_SIZE};
 use contract_ffi::value::account::{BlockTime, PublicKey, PurseId};
-use contract_ffi::value::{Account, Value, U512};
-use engine_shared::gas::Gas;
+use contract_ffi::value::{Account, Contract, Value, U512};
+use engine_shared::gas::{Gas, Rounding};
+use engine_shared::key_limits::KeyLimits;
+use engine_shared::logging::log_context;
 use engine_shared::motes::Motes;
-use engine_shared::newtypes::{Blake2bHash, CorrelationId, Validated};
-use engine_shared::transform::Transform;
-use engine_storage::global_state::{CommitResult, StateProvider, StateReader};
+use engine_shared::newtypes::{Blake2bHash, Blake2bHasher, CorrelationId, Validated};
+use engine_shared::transform::{Transform, TypeMismatch};
+use engine_storage::global_state::executed_deploys::executed_deploy_value;
+pub use engine_storage::global_state::executed_deploys::{
+    executed_deploy_key, EXECUTED_DEPLOY_WINDOW_MILLIS,
+};
+use engine_storage::global_state::{
+    CommitResult, DiffResult, IntegrityReport, PruneResult, StateProofReader, StateProvider,
+    StateReader, TrieChunkResult,
+};
 use engine_storage::protocol_data::ProtocolData;
+use engine_storage::protocol_data_store::ProtocolVersion;
+use engine_storage::trie::TrieMerkleProof;
 use engine_wasm_prep::wasm_costs::WasmCosts;
 use engine_wasm_prep::{Preprocessor, WasmiPreprocessor};
 
+use self::approval::{approved_keys, Approval};
+use self::deploy_scheduler::DeployScheduler;
 pub use self::engine_config::EngineConfig;
-use self::error::{Error, RootNotFound};
+use self::error::{AuthorizationFailure, Error, RootNotFound};
 use self::executable_deploy_item::ExecutableDeployItem;
 use self::execution_result::ExecutionResult;
 use self::genesis::{create_genesis_effects, GenesisResult};
 use self::genesis::{GenesisAccount, GenesisConfig, POS_PAYMENT_PURSE, POS_REWARDS_PURSE};
+use self::module_cache::ModuleCache;
+use self::native_transfer::NativeTransfer;
+use self::op::Op;
+use self::upgrade::{UpgradeConfig, UpgradeResult};
 use self::utils::WasmiBytes;
 use crate::execution::AddressGenerator;
-use crate::execution::{self, Executor, WasmiExecutor, MINT_NAME, POS_NAME};
+use crate::execution::{self, Executor, SystemContract, WasmiExecutor, MINT_NAME, POS_NAME};
 use crate::tracking_copy::{TrackingCopy, TrackingCopyExt};
 use crate::KnownKeys;
 
 // TODO?: MAX_PAYMENT && CONV_RATE values are currently arbitrary w/ real values
 // TBD gas * CONV_RATE = motes
 pub const MAX_PAYMENT: u64 = 10_000_000;
+/// Gas price, in motes per unit of gas, the node bids for its deploys.
 pub const CONV_RATE: u64 = 10;
+/// Minimum gas price for protocol versions which do not configure one.
+pub const DEFAULT_MIN_GAS_PRICE: u64 = 1;
+/// Gas charged for a native transfer under protocol versions which do not configure a cost.
+pub const DEFAULT_NATIVE_TRANSFER_COST: u64 = 10_000;
 
 pub const SYSTEM_ACCOUNT_ADDR: [u8; 32] = [0u8; 32];
 
+/// Hashed to derive the address of the global state entry recording the protocol version a state
+/// was produced with.  Being a hash output, the address can't coincide with an account address or
+/// with any fixed sentinel.
+const PROTOCOL_VERSION_PREFIX: &[u8] = b"protocol_version";
+
+/// Prefix hashed to derive the address of the global state entry holding the protocol data.
+const PROTOCOL_DATA_PREFIX: &[u8] = b"protocol_data";
+
+/// Prefix hashed together with a deploy hash to derive the address of the global state entry
+/// permanently recording that the deploy has been executed.  Unlike the records rejecting replays,
+/// which are purged once the deploy has expired, it lets deploys depend on deploys executed in
+/// any earlier block.
+const DEPLOY_EXECUTION_PREFIX: &[u8] = b"deploy_execution";
+
+/// Prefix hashed together with the prestate hash of a block to seed the addresses generated while
+/// the PoS step runs at the start of the block.
+const STEP_PREFIX: &[u8] = b"step";
+
 const DEFAULT_SESSION_MOTES: u64 = 1_000_000_000;
 const GENESIS_INITIAL_BLOCKTIME: u64 = 0;
+const GENESIS_INITIAL_BLOCK_HEIGHT: u64 = 0;
 const MINT_METHOD_NAME: &str = "mint";
+const STEP_METHOD_NAME: &str = "step";
+
+/// Returns the key of the global state entry recording the protocol version a state was produced
+/// with.
+pub fn protocol_version_key() -> Key {
+    Key::Hash(Blake2bHash::new(PROTOCOL_VERSION_PREFIX).into())
+}
+
+/// Returns the key of the global state entry holding the serialized protocol data of the protocol
+/// version a state was produced with.  Being part of the state, it is synced and verified along
+/// with the rest of it.
+pub fn protocol_data_key() -> Key {
+    Key::Hash(Blake2bHash::new(PROTOCOL_DATA_PREFIX).into())
+}
+
+/// Returns the key of the global state entry permanently recording that the deploy with the given
+/// hash has been executed.
+pub fn deploy_execution_key(deploy_hash: [u8; 32]) -> Key {
+    let mut data = DEPLOY_EXECUTION_PREFIX.to_vec();
+    data.extend_from_slice(&deploy_hash);
+    Key::Hash(Blake2bHash::new(&data).into())
+}
+
+/// Returns the hash seeding the addresses generated by the PoS step run at the start of a block
+/// with the given prestate hash, standing in for the deploy hash a deploy would have.
+fn step_hash(prestate_hash: Blake2bHash) -> [u8; 32] {
+    let mut data = STEP_PREFIX.to_vec();
+    data.extend_from_slice(&prestate_hash.to_vec());
+    Blake2bHash::new(&data).into()
+}
+
+/// Returns the hash a deploy with the given serialized header and body is expected to have.
+pub fn hash_deploy(header: &[u8], body: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2bHasher::new();
+    hasher.update(header);
+    hasher.update(body);
+    hasher.finalize().into()
+}
+
+/// Tags the messages logged while a deploy executes with the correlation ID of its exec request and
+/// with its hash.  Deploys may execute on the threads of the deploy scheduler, so the tags are set
+/// by the thread executing the deploy until the returned guards are dropped.
+fn enter_deploy_log_context(
+    correlation_id: CorrelationId,
+    deploy_hash: &[u8],
+) -> (log_context::LogContextGuard, log_context::LogContextGuard) {
+    (
+        log_context::enter_correlation_id(correlation_id),
+        log_context::enter_deploy_hash(deploy_hash),
+    )
+}
+
+/// Adds the writes recording the execution of the deploy with the given hash, in a block with the
+/// given block time, to the effects of `execution_result`: the record rejecting its replays and
+/// the permanent one its dependents look up.
+fn record_executed_deploy(
+    execution_result: ExecutionResult,
+    blocktime: BlockTime,
+    deploy_hash: [u8; 32],
+) -> ExecutionResult {
+    let mut effect = execution_result.effect().to_owned();
+    let key = executed_deploy_key(blocktime, deploy_hash);
+    effect.ops.insert(key, Op::Write);
+    effect
+        .transforms
+        .insert(key, Transform::Write(executed_deploy_value(blocktime)));
+    let key = deploy_execution_key(deploy_hash);
+    effect.ops.insert(key, Op::Write);
+    effect.transforms.insert(key, Transform::Write(Value::Unit));
+    execution_result.with_effect(effect)
+}
 
 #[derive(Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
     state: S,
+    module_cache: ModuleCache,
+    deploy_scheduler: DeployScheduler,
 }
 
 pub enum GetBondedValidatorsError<E> {
@@ -66,19 +187,114 @@ pub enum GetBondedValidatorsError<E> {
     ProofOfStakeNotFound(Key),
 }
 
+/// Identifies the purse whose balance is read by [`EngineState::get_balance`].
+pub enum BalanceSource {
+    /// The main purse of the account under the given address.
+    Account([u8; 32]),
+    /// The purse under the given URef.
+    Purse(URef),
+}
+
+pub enum GetBalanceError {
+    PostStateHashNotFound(Blake2bHash),
+    EngineError(Error),
+}
+
+impl From<Error> for GetBalanceError {
+    fn from(error: Error) -> Self {
+        GetBalanceError::EngineError(error)
+    }
+}
+
+impl From<execution::Error> for GetBalanceError {
+    fn from(error: execution::Error) -> Self {
+        GetBalanceError::EngineError(error.into())
+    }
+}
+
+pub enum GetAccountError {
+    PostStateHashNotFound(Blake2bHash),
+    EngineError(Error),
+}
+
+impl From<Error> for GetAccountError {
+    fn from(error: Error) -> Self {
+        GetAccountError::EngineError(error)
+    }
+}
+
+impl From<execution::Error> for GetAccountError {
+    fn from(error: execution::Error) -> Self {
+        GetAccountError::EngineError(error.into())
+    }
+}
+
+pub enum GetContractError {
+    PostStateHashNotFound(Blake2bHash),
+    EngineError(Error),
+}
+
+impl From<Error> for GetContractError {
+    fn from(error: Error) -> Self {
+        GetContractError::EngineError(error)
+    }
+}
+
+impl From<execution::Error> for GetContractError {
+    fn from(error: execution::Error) -> Self {
+        GetContractError::EngineError(error.into())
+    }
+}
+
+pub enum GetProtocolDataError {
+    PostStateHashNotFound(Blake2bHash),
+    /// The state was produced before protocol versions were recorded in global state.
+    ProtocolVersionNotFound,
+    ProtocolDataNotFound(ProtocolVersion),
+    EngineError(Error),
+}
+
+impl From<Error> for GetProtocolDataError {
+    fn from(error: Error) -> Self {
+        GetProtocolDataError::EngineError(error)
+    }
+}
+
+impl From<execution::Error> for GetProtocolDataError {
+    fn from(error: execution::Error) -> Self {
+        GetProtocolDataError::EngineError(error.into())
+    }
+}
+
 impl<S> EngineState<S>
 where
     S: StateProvider,
     S::Error: Into<execution::Error>,
 {
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
-        EngineState { config, state }
+        let module_cache = ModuleCache::new(config.module_cache_size());
+        let deploy_scheduler = DeployScheduler::new(config.deploy_threads());
+        EngineState {
+            config,
+            state,
+            module_cache,
+            deploy_scheduler,
+        }
     }
 
     pub fn config(&self) -> &EngineConfig {
         &self.config
     }
 
+    pub fn deploy_scheduler(&self) -> &DeployScheduler {
+        &self.deploy_scheduler
+    }
+
+    /// Returns the root hash of the empty global state, which genesis is committed on top of.
+    pub fn empty_root(&self) -> Blake2bHash {
+        self.state.empty_root()
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn commit_genesis(
         &self,
@@ -90,10 +306,11 @@ where
         genesis_validators: Vec<(PublicKey, U512)>,
         protocol_version: u64,
     ) -> Result<GenesisResult, Error> {
+        let wasm_costs = WasmCosts::default();
         let mint_code = WasmiBytes::new(mint_code_bytes, WasmCosts::free())?;
         let pos_code = WasmiBytes::new(proof_of_stake_code_bytes, WasmCosts::free())?;
 
-        let effects = create_genesis_effects(
+        let mut effects = create_genesis_effects(
             genesis_account_addr,
             initial_motes,
             mint_code,
@@ -101,10 +318,35 @@ where
             genesis_validators,
             protocol_version,
         )?;
+        let protocol_data = ProtocolData::new(
+            wasm_costs,
+            RefundRatio::default(),
+            DEFAULT_MIN_GAS_PRICE,
+            DEFAULT_NATIVE_TRANSFER_COST,
+            KeyLimits::default(),
+        );
+        {
+            let key = protocol_version_key();
+            let value = Value::UInt64(protocol_version);
+            effects.ops.insert(key, Op::Write);
+            effects.transforms.insert(key, Transform::Write(value));
+        }
+        {
+            let key = protocol_data_key();
+            let value = Value::ByteArray(protocol_data.to_bytes()?);
+            effects.ops.insert(key, Op::Write);
+            effects.transforms.insert(key, Transform::Write(value));
+        }
         let prestate_hash = self.state.empty_root();
         let commit_result = self
             .state
-            .commit(correlation_id, prestate_hash, effects.transforms.to_owned())
+            .commit_with_protocol_data(
+                correlation_id,
+                prestate_hash,
+                effects.transforms.to_owned(),
+                protocol_version,
+                &protocol_data,
+            )
             .map_err(Into::into)?;
 
         let genesis_result = GenesisResult::from_commit_result(commit_result, effects);
@@ -118,8 +360,13 @@ where
         genesis_config: GenesisConfig,
     ) -> Result<GenesisResult, Error> {
         // Preliminaries
-        let executor = WasmiExecutor;
+        genesis_config
+            .validate_bonds()
+            .map_err(Error::InvalidGenesisBonds)?;
+        let executor = WasmiExecutor::new(self.config.use_system_contracts())
+            .set_key_limits(genesis_config.key_limits());
         let blocktime = BlockTime(GENESIS_INITIAL_BLOCKTIME);
+        let block_height = GENESIS_INITIAL_BLOCK_HEIGHT;
         let gas_limit = Gas::new(std::u64::MAX.into());
         let phase = Phase::System;
 
@@ -127,15 +374,20 @@ where
         let initial_root_hash = self.state.empty_root();
         let protocol_version = genesis_config.protocol_version();
         let wasm_costs = genesis_config.wasm_costs();
-        let preprocessor = WasmiPreprocessor::new(wasm_costs);
-
-        // Spec #2: Associate given CostTable with given ProtocolVersion.
-        {
-            let protocol_data = ProtocolData::new(wasm_costs);
-            self.state
-                .put_protocol_data(protocol_version, &protocol_data)
-                .map_err(Into::into)?
-        }
+        let preprocessor = WasmiPreprocessor::new(wasm_costs)
+            .set_float_handling(self.config.float_handling())
+            .set_module_limits(self.config.module_limits());
+
+        // Spec #2: Associate given CostTable, RefundRatio, minimum gas price, native transfer cost
+        // and KeyLimits with given ProtocolVersion.  They are stored together with the genesis
+        // post state in Spec #15.
+        let protocol_data = ProtocolData::new(
+            wasm_costs,
+            genesis_config.refund_ratio(),
+            genesis_config.min_gas_price(),
+            genesis_config.native_transfer_cost(),
+            genesis_config.key_limits(),
+        );
 
         // Spec #3: Create "virtual system account" object.
         let virtual_system_account = {
@@ -165,25 +417,10 @@ where
 
         tracking_copy.borrow_mut().write(key, value);
 
-        // Spec #4A: random number generator is seeded from the hash of GenesisConfig.name
-        // concatenated with GenesisConfig.timestamp (aka "deploy hash").
-        //
-        // @birchmd adds: "Probably we will want to include the hash of the cost table in there
-        // as well actually. Otherwise it has no direct effect on the genesis post
-        // state hash either."
-        let install_deploy_hash = {
-            let name: &[u8] = genesis_config.name().as_bytes();
-            let timestamp: &[u8] = &genesis_config.timestamp().to_le_bytes();
-            let wasm_costs_bytes: &[u8] = &wasm_costs.to_bytes()?;
-            let bytes: Vec<u8> = {
-                let mut ret = Vec::new();
-                ret.extend_from_slice(name);
-                ret.extend_from_slice(timestamp);
-                ret.extend_from_slice(wasm_costs_bytes);
-                ret
-            };
-            Blake2bHash::new(&bytes)
-        };
+        // Spec #4A: random number generator is seeded from the hash of the whole GenesisConfig
+        // (aka "deploy hash"), so that genesis depends on nothing but the config. The config
+        // keeps its accounts sorted, hence their order in the chainspec makes no difference.
+        let install_deploy_hash = genesis_config.hash()?;
 
         let address_generator = {
             let generator = AddressGenerator::new(install_deploy_hash.into(), phase);
@@ -211,8 +448,10 @@ where
                 &virtual_system_account,
                 authorization_keys,
                 blocktime,
+                block_height,
                 install_deploy_hash,
                 gas_limit,
+                wasm_costs,
                 address_generator,
                 protocol_version,
                 correlation_id,
@@ -221,8 +460,9 @@ where
             )?
         };
 
-        // Spec #7: Execute pos installer wasm code, passing the initially bonded validators as an
-        // argument
+        // Spec #7: Execute pos installer wasm code, passing the initially bonded validators, their
+        // delegation rates, the genesis delegators, the caps on changing delegation rates, the
+        // bond limits, the era duration and the unbonding delay as arguments
         let proof_of_stake_reference: URef = {
             let proof_of_stake_installer_module = {
                 let bytes = genesis_config.proof_of_stake_installer_bytes();
@@ -235,7 +475,22 @@ where
                     .get_bonded_validators()
                     .map(|(k, v)| (k, v.value()))
                     .collect();
-                let args = (mint_reference, bonded_validators);
+                let delegation_rates: BTreeMap<PublicKey, DelegationRate> =
+                    genesis_config.get_delegation_rates().collect();
+                let bonded_delegators: BTreeMap<PublicKey, U512> = genesis_config
+                    .get_bonded_delegators()
+                    .map(|(k, v)| (k, v.value()))
+                    .collect();
+                let args = (
+                    mint_reference,
+                    bonded_validators,
+                    delegation_rates,
+                    bonded_delegators,
+                    genesis_config.delegation_rate_limits(),
+                    genesis_config.bond_limits(),
+                    genesis_config.era_duration(),
+                    genesis_config.unbond_delay(),
+                );
                 ArgsParser::parse(&args)
                     .and_then(|args| args.to_bytes())
                     .expect("args should parse")
@@ -258,8 +513,10 @@ where
                 &virtual_system_account,
                 authorization_keys,
                 blocktime,
+                block_height,
                 install_deploy_hash,
                 gas_limit,
+                wasm_costs,
                 address_generator,
                 protocol_version,
                 correlation_id,
@@ -342,7 +599,7 @@ where
                 let base_key = Key::URef(mint_reference);
                 let authorization_keys: BTreeSet<PublicKey> = BTreeSet::new();
                 let account_public_key = account.public_key();
-                let purse_creation_deploy_hash = account_public_key.value();
+                let purse_creation_deploy_hash = account_public_key.to_account_address();
                 let address_generator = {
                     let generator = AddressGenerator::new(purse_creation_deploy_hash, phase);
                     Rc::new(RefCell::new(generator))
@@ -357,8 +614,10 @@ where
                     &virtual_system_account,
                     authorization_keys,
                     blocktime,
+                    block_height,
                     purse_creation_deploy_hash,
                     gas_limit,
+                    wasm_costs,
                     address_generator,
                     protocol_version,
                     correlation_id,
@@ -368,14 +627,14 @@ where
 
                 // ...and write that account to global state...
                 let key = {
-                    let key = Key::Account(account_public_key.value());
+                    let key = Key::Account(account_public_key.to_account_address());
                     Validated::new(key, Validated::valid).unwrap() // safe to unwrap
                 };
                 let value = {
                     let account_main_purse = mint_result?;
                     let purse_id = PurseId::new(account_main_purse);
-                    let value = Value::Account(Account::create(
-                        account_public_key.value(),
+                    let value = Value::Account(Account::create_for_public_key(
+                        account_public_key,
                         known_keys,
                         purse_id,
                     ));
@@ -386,15 +645,41 @@ where
             }
         }
 
-        // Spec #15: Commit the transforms.
+        // Record the protocol version the genesis state is produced with, and its protocol data.
+        {
+            let key = {
+                let key = protocol_version_key();
+                Validated::new(key, Validated::valid).unwrap() // safe to unwrap
+            };
+            let value = {
+                let value = Value::UInt64(protocol_version);
+                Validated::new(value, Validated::valid).unwrap() // safe to unwrap
+            };
+            tracking_copy.borrow_mut().write(key, value);
+        }
+        {
+            let key = {
+                let key = protocol_data_key();
+                Validated::new(key, Validated::valid).unwrap() // safe to unwrap
+            };
+            let value = {
+                let value = Value::ByteArray(protocol_data.to_bytes()?);
+                Validated::new(value, Validated::valid).unwrap() // safe to unwrap
+            };
+            tracking_copy.borrow_mut().write(key, value);
+        }
+
+        // Spec #15: Commit the transforms along with the protocol data.
         let effects = tracking_copy.borrow().effect();
 
         let commit_result = self
             .state
-            .commit(
+            .commit_with_protocol_data(
                 correlation_id,
                 initial_root_hash,
                 effects.transforms.to_owned(),
+                protocol_version,
+                &protocol_data,
             )
             .map_err(Into::into)?;
 
@@ -415,14 +700,21 @@ where
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub fn run_deploy_item<A, P: Preprocessor<A>, E: Executor<A>>(
+    pub fn run_deploy_item<A: Clone + Send + 'static, P: Preprocessor<A>, E: Executor<A>>(
         &self,
         session: ExecutableDeployItem,
         payment: ExecutableDeployItem,
         address: Key,
         authorization_keys: BTreeSet<PublicKey>,
+        approvals: Vec<Approval>,
         blocktime: BlockTime,
+        block_height: u64,
+        proposer: Option<PublicKey>,
         deploy_hash: [u8; 32],
+        dependencies: Vec<[u8; 32]>,
+        deploy_timestamp: u64,
+        ttl_millis: u32,
+        gas_price: u64,
         prestate_hash: Blake2bHash,
         protocol_version: u64,
         correlation_id: CorrelationId,
@@ -434,8 +726,15 @@ where
             payment,
             address,
             authorization_keys,
+            approvals,
             blocktime,
+            block_height,
+            proposer,
             deploy_hash,
+            dependencies,
+            deploy_timestamp,
+            ttl_millis,
+            gas_price,
             prestate_hash,
             protocol_version,
             correlation_id,
@@ -445,7 +744,7 @@ where
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub fn run_deploy<A, P: Preprocessor<A>, E: Executor<A>>(
+    pub fn run_deploy<A: Clone + Send + 'static, P: Preprocessor<A>, E: Executor<A>>(
         &self,
         session_module_bytes: &[u8],
         session_args: &[u8],
@@ -453,8 +752,15 @@ where
         payment_args: &[u8],
         address: Key,
         authorization_keys: BTreeSet<PublicKey>,
+        approvals: Vec<Approval>,
         blocktime: BlockTime,
+        block_height: u64,
+        proposer: Option<PublicKey>,
         deploy_hash: [u8; 32],
+        dependencies: Vec<[u8; 32]>,
+        deploy_timestamp: u64,
+        ttl_millis: u32,
+        gas_price: u64,
         prestate_hash: Blake2bHash,
         protocol_version: u64,
         correlation_id: CorrelationId,
@@ -475,8 +781,15 @@ where
             payment,
             address,
             authorization_keys,
+            approvals,
             blocktime,
+            block_height,
+            proposer,
             deploy_hash,
+            dependencies,
+            deploy_timestamp,
+            ttl_millis,
+            gas_price,
             prestate_hash,
             protocol_version,
             correlation_id,
@@ -485,17 +798,194 @@ where
         )
     }
 
-    pub fn get_module<A, P: Preprocessor<A>>(
+    /// Runs a deploy consisting of a native transfer rather than of payment and session code. It
+    /// goes through the same precondition checks as any other deploy, and is charged the native
+    /// transfer cost of `protocol_version` instead of the gas used by wasm.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_native_transfer(
+        &self,
+        transfer: NativeTransfer,
+        address: Key,
+        authorization_keys: BTreeSet<PublicKey>,
+        approvals: Vec<Approval>,
+        blocktime: BlockTime,
+        proposer: Option<PublicKey>,
+        deploy_hash: [u8; 32],
+        dependencies: Vec<[u8; 32]>,
+        deploy_timestamp: u64,
+        ttl_millis: u32,
+        gas_price: u64,
+        prestate_hash: Blake2bHash,
+        protocol_version: u64,
+        correlation_id: CorrelationId,
+    ) -> Result<ExecutionResult, RootNotFound> {
+        let _log_context = enter_deploy_log_context(correlation_id, &deploy_hash);
+
+        let authorization_keys =
+            self.approved_authorization_keys(&deploy_hash, authorization_keys, &approvals);
+
+        let (tracking_copy, account) = match self.validate_deploy(
+            address,
+            &authorization_keys,
+            blocktime,
+            deploy_hash,
+            dependencies,
+            deploy_timestamp,
+            ttl_millis,
+            gas_price,
+            prestate_hash,
+            protocol_version,
+            correlation_id,
+        )? {
+            Ok(validated) => validated,
+            Err(failure) => return Ok(failure),
+        };
+
+        let cost = match self.get_native_transfer_cost(protocol_version) {
+            Ok(native_transfer_cost) => Gas::from_u64(native_transfer_cost),
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+        };
+        // As for other deploys, nothing is charged if payment logic is turned off.
+        let fee = if self.config.use_payment_code() {
+            match Motes::from_gas(cost, gas_price) {
+                Some(fee) => fee,
+                None => {
+                    return Ok(ExecutionResult::precondition_failure(
+                        Error::InsufficientPaymentError,
+                    ))
+                }
+            }
+        } else {
+            Motes::zero()
+        };
+
+        match native_transfer::execute(
+            &mut *tracking_copy.borrow_mut(),
+            correlation_id,
+            &account,
+            &transfer,
+            cost,
+            fee,
+            self.config.fee_handling(),
+            proposer,
+        ) {
+            Ok(execution_result) => Ok(record_executed_deploy(
+                execution_result,
+                blocktime,
+                deploy_hash,
+            )),
+            Err(error) => Ok(ExecutionResult::precondition_failure(error)),
+        }
+    }
+
+    /// Runs the `step` method of the PoS contract as the system account, without committing its
+    /// effects.  The node runs it on the prestate of every block and commits its effects before
+    /// executing the deploys of the block.  The step applies the bonding and unbonding requests
+    /// which have become due, pays out the matured unbonds and enters the era `blocktime` falls in.
+    ///
+    /// Like finalizing payments, this runs directly rather than as a deploy, so it isn't subject
+    /// to `restrict_system_account`.  Returns `None` if the system contracts are not run as wasm,
+    /// as the host-side PoS has no step.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_step<A, P: Preprocessor<A>, E: Executor<A>>(
+        &self,
+        blocktime: BlockTime,
+        block_height: u64,
+        prestate_hash: Blake2bHash,
+        protocol_version: u64,
+        correlation_id: CorrelationId,
+        executor: &E,
+        preprocessor: &P,
+    ) -> Result<Option<ExecutionResult>, RootNotFound> {
+        if !self.config.use_system_contracts() {
+            return Ok(None);
+        }
+
+        let tracking_copy = match self.tracking_copy(prestate_hash) {
+            Err(error) => return Ok(Some(ExecutionResult::precondition_failure(error))),
+            Ok(None) => return Err(RootNotFound(prestate_hash)),
+            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
+        };
+
+        let system_account = match tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, SYSTEM_ACCOUNT_ADDR)
+        {
+            Ok(account) => account,
+            Err(error) => return Ok(Some(ExecutionResult::precondition_failure(error.into()))),
+        };
+
+        let wasm_costs = match self.get_wasm_costs(protocol_version) {
+            Ok(wasm_costs) => wasm_costs,
+            Err(error) => return Ok(Some(ExecutionResult::precondition_failure(error))),
+        };
+
+        let proof_of_stake_public_uref: Key = match system_account.urefs_lookup().get(POS_NAME) {
+            Some(uref) => uref.normalize(),
+            None => {
+                return Ok(Some(ExecutionResult::precondition_failure(
+                    Error::MissingSystemContractError(POS_NAME.to_string()),
+                )));
+            }
+        };
+
+        let proof_of_stake_info = match tracking_copy
+            .borrow_mut()
+            .get_system_contract_info(correlation_id, proof_of_stake_public_uref)
+        {
+            Ok(contract_info) => contract_info,
+            Err(error) => return Ok(Some(ExecutionResult::precondition_failure(error.into()))),
+        };
+
+        let proof_of_stake_module =
+            match preprocessor.deserialize(&proof_of_stake_info.module_bytes()) {
+                Ok(module) => module,
+                Err(error) => return Ok(Some(ExecutionResult::precondition_failure(error.into()))),
+            };
+
+        let args = ArgsParser::parse(&(STEP_METHOD_NAME,))
+            .and_then(|args| args.to_bytes())
+            .expect("args should parse");
+
+        let mut proof_of_stake_keys = proof_of_stake_info.contract().urefs_lookup().clone();
+        let mut authorization_keys = BTreeSet::new();
+        authorization_keys.insert(PublicKey::new(SYSTEM_ACCOUNT_ADDR));
+
+        Ok(Some(executor.exec_direct(
+            proof_of_stake_module,
+            &args,
+            &mut proof_of_stake_keys,
+            proof_of_stake_info.inner_key(),
+            &system_account,
+            authorization_keys,
+            blocktime,
+            block_height,
+            step_hash(prestate_hash),
+            Gas::from_u64(std::u64::MAX),
+            wasm_costs,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            Phase::System,
+        )))
+    }
+
+    pub fn get_module<A: Clone + Send + 'static, P: Preprocessor<A>>(
         &self,
         tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
         deploy_item: &ExecutableDeployItem,
         account: &Account,
         correlation_id: CorrelationId,
+        wasm_costs: WasmCosts,
         preprocessor: &P,
     ) -> Result<A, error::Error> {
         match deploy_item {
             ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
-                let module = preprocessor.preprocess(&module_bytes)?;
+                let module = self.module_cache.get_or_preprocess(
+                    &module_bytes,
+                    wasm_costs,
+                    |module_bytes| preprocessor.preprocess(module_bytes),
+                )?;
                 Ok(module)
             }
             ExecutableDeployItem::StoredContractByHash { hash, .. } => {
@@ -587,42 +1077,163 @@ where
         }
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn deploy<A, P: Preprocessor<A>, E: Executor<A>>(
+    /// Returns the authorization keys which count toward the weight thresholds of the deploying
+    /// account: all of them, or only those with a valid approval of the deploy hash among
+    /// `approvals` if the engine is configured to verify approvals.
+    fn approved_authorization_keys(
         &self,
-        session: ExecutableDeployItem,
-        payment: ExecutableDeployItem,
-        address: Key,
+        deploy_hash: &[u8; 32],
         authorization_keys: BTreeSet<PublicKey>,
+        approvals: &[Approval],
+    ) -> BTreeSet<PublicKey> {
+        if self.config.verify_approvals() {
+            approved_keys(deploy_hash, &authorization_keys, approvals)
+        } else {
+            authorization_keys
+        }
+    }
+
+    /// Runs the checks every deploy goes through before any of its code runs. Returns the tracking
+    /// copy of the prestate along with the deploying account, or the result of the deploy if it
+    /// fails a precondition.
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    fn validate_deploy(
+        &self,
+        address: Key,
+        authorization_keys: &BTreeSet<PublicKey>,
         blocktime: BlockTime,
         deploy_hash: [u8; 32],
+        dependencies: Vec<[u8; 32]>,
+        deploy_timestamp: u64,
+        ttl_millis: u32,
+        gas_price: u64,
         prestate_hash: Blake2bHash,
         protocol_version: u64,
         correlation_id: CorrelationId,
-        executor: &E,
-        preprocessor: &P,
-    ) -> Result<ExecutionResult, RootNotFound> {
-        // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
-
+    ) -> Result<
+        Result<(Rc<RefCell<TrackingCopy<S::Reader>>>, Account), ExecutionResult>,
+        RootNotFound,
+    > {
         // Create tracking copy (which functions as a deploy context)
         // validation_spec_2: prestate_hash check
         let tracking_copy = match self.tracking_copy(prestate_hash) {
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+            Err(error) => return Ok(Err(ExecutionResult::precondition_failure(error))),
             Ok(None) => return Err(RootNotFound(prestate_hash)),
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
+        // Reject deploys targeting a protocol version other than the one the prestate was
+        // produced with. States produced before protocol versions were recorded accept any.
+        match tracking_copy
+            .borrow_mut()
+            .get_protocol_version(correlation_id)
+        {
+            Ok(Some(state_protocol_version)) if state_protocol_version != protocol_version => {
+                return Ok(Err(ExecutionResult::precondition_failure(
+                    Error::InvalidProtocolVersion(protocol_version),
+                )));
+            }
+            Ok(_) => (),
+            Err(error) => return Ok(Err(ExecutionResult::precondition_failure(error.into()))),
+        }
+
+        // Reject deploys which have already been executed, so they cannot be replayed.  Deploys
+        // without a time to live never expire, so they outlive the records rejecting replays,
+        // which are purged, and the permanent record of their execution is looked up instead.
+        let is_deploy_executed = if ttl_millis > 0 {
+            tracking_copy
+                .borrow_mut()
+                .is_deploy_executed(correlation_id, blocktime, deploy_hash)
+        } else {
+            tracking_copy
+                .borrow_mut()
+                .is_deploy_execution_recorded(correlation_id, deploy_hash)
+        };
+        match is_deploy_executed {
+            Ok(false) => (),
+            Ok(true) => {
+                return Ok(Err(ExecutionResult::precondition_failure(
+                    Error::DuplicateDeploy(deploy_hash),
+                )));
+            }
+            Err(error) => return Ok(Err(ExecutionResult::precondition_failure(error.into()))),
+        }
+
+        // Reject deploys timestamped after the block, as those with a time to live could outlive
+        // the record rejecting their replays.
+        if deploy_timestamp > blocktime.0 {
+            return Ok(Err(ExecutionResult::precondition_failure(
+                Error::FutureDeploy {
+                    timestamp: deploy_timestamp,
+                    blocktime: blocktime.0,
+                },
+            )));
+        }
+
+        // Reject deploys whose time to live has elapsed by the time of the block.  Deploys without
+        // a time to live never expire.
+        if ttl_millis > 0 {
+            let expired_at = deploy_timestamp.saturating_add(u64::from(ttl_millis));
+            if expired_at < blocktime.0 {
+                return Ok(Err(ExecutionResult::precondition_failure(
+                    Error::DeployExpired {
+                        expired_at,
+                        blocktime: blocktime.0,
+                    },
+                )));
+            }
+        }
+
+        // Reject deploys bidding a gas price below the minimum of their protocol version.
+        let min_gas_price = match self.get_min_gas_price(protocol_version) {
+            Ok(min_gas_price) => min_gas_price,
+            Err(error) => return Ok(Err(ExecutionResult::precondition_failure(error))),
+        };
+        if gas_price < min_gas_price {
+            return Ok(Err(ExecutionResult::precondition_failure(
+                Error::GasPriceTooLow {
+                    gas_price,
+                    min_gas_price,
+                },
+            )));
+        }
+
+        // Reject deploys depending on deploys which have not been executed in the prestate.
+        for dependency in dependencies {
+            match tracking_copy
+                .borrow_mut()
+                .is_deploy_execution_recorded(correlation_id, dependency)
+            {
+                Ok(true) => (),
+                Ok(false) => {
+                    return Ok(Err(ExecutionResult::precondition_failure(
+                        Error::MissingDeployDependency(dependency),
+                    )));
+                }
+                Err(error) => return Ok(Err(ExecutionResult::precondition_failure(error.into()))),
+            }
+        }
+
         // Get addr bytes from `address` (which is actually a Key)
         // validation_spec_3: account validity
         let account_addr = match address.as_account() {
             Some(account_addr) => account_addr,
             None => {
-                return Ok(ExecutionResult::precondition_failure(
+                return Ok(Err(ExecutionResult::precondition_failure(
                     error::Error::AuthorizationError,
-                ))
+                )))
             }
         };
 
+        // Only the engine itself may act as the system account, as the system contracts trust it
+        // with operations such as finalizing payments and the PoS step.  Those don't run as
+        // deploys, so they don't go through this check, see `set_restrict_system_account`.
+        if self.config.restrict_system_account() && account_addr == SYSTEM_ACCOUNT_ADDR {
+            return Ok(Err(ExecutionResult::precondition_failure(
+                Error::SystemAccountDeploy,
+            )));
+        }
+
         // Get account from tracking copy
         // validation_spec_3: account validity
         let account: Account = match tracking_copy
@@ -631,29 +1242,83 @@ where
         {
             Ok(account) => account,
             Err(_) => {
-                return Ok(ExecutionResult::precondition_failure(
+                return Ok(Err(ExecutionResult::precondition_failure(
                     error::Error::AuthorizationError,
-                ));
+                )));
             }
         };
 
-        // Authorize using provided authorization keys
+        // Authorize using provided authorization keys and check their total weight against
+        // the deploy threshold
         // validation_spec_3: account validity
-        if authorization_keys.is_empty() || !account.can_authorize(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
-                crate::engine_state::error::Error::AuthorizationError,
-            ));
-        }
-
-        // Check total key weight against deploy threshold
         // validation_spec_4: deploy validity
-        if !account.can_deploy_with(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
-                // TODO?:this doesn't happen in execution any longer, should error variant be moved
-                execution::Error::DeploymentAuthorizationFailure.into(),
-            ));
+        if authorization_keys.is_empty()
+            || !account.can_authorize(authorization_keys)
+            || !account.can_deploy_with(authorization_keys)
+        {
+            return Ok(Err(ExecutionResult::precondition_failure(
+                Error::AuthorizationFailure(AuthorizationFailure::new(
+                    &account,
+                    authorization_keys,
+                )),
+            )));
         }
 
+        Ok(Ok((tracking_copy, account)))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn deploy<A: Clone + Send + 'static, P: Preprocessor<A>, E: Executor<A>>(
+        &self,
+        session: ExecutableDeployItem,
+        payment: ExecutableDeployItem,
+        address: Key,
+        authorization_keys: BTreeSet<PublicKey>,
+        approvals: Vec<Approval>,
+        blocktime: BlockTime,
+        block_height: u64,
+        proposer: Option<PublicKey>,
+        deploy_hash: [u8; 32],
+        dependencies: Vec<[u8; 32]>,
+        deploy_timestamp: u64,
+        ttl_millis: u32,
+        gas_price: u64,
+        prestate_hash: Blake2bHash,
+        protocol_version: u64,
+        correlation_id: CorrelationId,
+        executor: &E,
+        preprocessor: &P,
+    ) -> Result<ExecutionResult, RootNotFound> {
+        // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
+
+        let _log_context = enter_deploy_log_context(correlation_id, &deploy_hash);
+
+        let authorization_keys =
+            self.approved_authorization_keys(&deploy_hash, authorization_keys, &approvals);
+
+        let (tracking_copy, account) = match self.validate_deploy(
+            address,
+            &authorization_keys,
+            blocktime,
+            deploy_hash,
+            dependencies,
+            deploy_timestamp,
+            ttl_millis,
+            gas_price,
+            prestate_hash,
+            protocol_version,
+            correlation_id,
+        )? {
+            Ok(validated) => validated,
+            Err(failure) => return Ok(failure),
+        };
+        let account_addr = account.pub_key();
+
+        let wasm_costs = match self.get_wasm_costs(protocol_version) {
+            Ok(wasm_costs) => wasm_costs,
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+        };
+
         // Create session code `A` from provided session bytes
         // validation_spec_1: valid wasm bytes
         let session_module = match self.get_module(
@@ -661,6 +1326,7 @@ where
             &session,
             &account,
             correlation_id,
+            wasm_costs,
             preprocessor,
         ) {
             Ok(module) => module,
@@ -677,7 +1343,8 @@ where
 
             let session_motes = Motes::from_u64(DEFAULT_SESSION_MOTES);
 
-            let gas_limit = Gas::from_motes(session_motes, CONV_RATE).unwrap_or_default();
+            let gas_limit =
+                Gas::from_motes(session_motes, gas_price, Rounding::Floor).unwrap_or_default();
 
             // Session code execution
             let session_result = executor.exec(
@@ -687,15 +1354,21 @@ where
                 &account,
                 authorization_keys,
                 blocktime,
+                block_height,
                 deploy_hash,
                 gas_limit,
+                wasm_costs,
                 protocol_version,
                 correlation_id,
                 Rc::clone(&tracking_copy),
                 Phase::Session,
             );
 
-            return Ok(session_result);
+            return Ok(record_executed_deploy(
+                session_result,
+                blocktime,
+                deploy_hash,
+            ));
         }
 
         // --- REMOVE ABOVE --- //
@@ -815,7 +1488,10 @@ where
         // validation_spec_5: account main purse minimum balance
         if account_main_purse_balance < max_payment_cost {
             return Ok(ExecutionResult::precondition_failure(
-                Error::InsufficientPaymentError,
+                Error::InsufficientBalance {
+                    required: max_payment_cost,
+                    available: account_main_purse_balance,
+                },
             ));
         }
 
@@ -836,8 +1512,9 @@ where
         // Execute provided payment code
         let payment_result = {
             // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
-            // conv_rate)
-            let pay_gas_limit = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+            // gas_price)
+            let pay_gas_limit =
+                Gas::from_motes(max_payment_cost, gas_price, Rounding::Floor).unwrap_or_default();
 
             // Create payment code module from bytes
             // validation_spec_1: valid wasm bytes
@@ -846,6 +1523,7 @@ where
                 &payment,
                 &account,
                 correlation_id,
+                wasm_costs,
                 preprocessor,
             ) {
                 Ok(module) => module,
@@ -862,8 +1540,10 @@ where
                 &account,
                 authorization_keys.clone(),
                 blocktime,
+                block_height,
                 deploy_hash,
                 pay_gas_limit,
+                wasm_costs,
                 protocol_version,
                 correlation_id,
                 Rc::clone(&tracking_copy),
@@ -913,13 +1593,14 @@ where
             .set_payment_execution_result(payment_result)
             .check_forced_transfer(
                 max_payment_cost,
+                gas_price,
                 account_main_purse_balance,
                 payment_purse_balance,
                 account_main_purse_balance_key,
                 rewards_purse_balance_key,
             )
         {
-            return Ok(failure);
+            return Ok(record_executed_deploy(failure, blocktime, deploy_hash));
         }
 
         let post_payment_tc = tracking_copy.borrow();
@@ -928,12 +1609,13 @@ where
         // session_code_spec_2: execute session code
         let session_result = {
             // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during
-            // payment code execution) * conv_rate, yes session
-            // session_code_spec_1: gas limit = ((balance of PoS payment purse) / conv_rate)
+            // payment code execution) * gas_price, yes session
+            // session_code_spec_1: gas limit = ((balance of PoS payment purse) / gas_price)
             // - (gas spent during payment execution)
-            let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, CONV_RATE)
-                .unwrap_or_default()
-                - payment_result_cost;
+            let session_gas_limit: Gas =
+                Gas::from_motes(payment_purse_balance, gas_price, Rounding::Floor)
+                    .and_then(|gas| gas.checked_sub(payment_result_cost))
+                    .unwrap_or_default();
 
             executor.exec(
                 session_module,
@@ -942,8 +1624,10 @@ where
                 &account,
                 authorization_keys.clone(),
                 blocktime,
+                block_height,
                 deploy_hash,
                 session_gas_limit,
+                wasm_costs,
                 protocol_version,
                 correlation_id,
                 Rc::clone(&session_tc),
@@ -970,17 +1654,30 @@ where
             let post_session_tc = post_session_rc.borrow();
             let finalization_tc = Rc::new(RefCell::new(post_session_tc.fork()));
 
-            // validation_spec_1: valid wasm bytes
-            let proof_of_stake_module =
-                match preprocessor.deserialize(&proof_of_stake_info.module_bytes()) {
-                    Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
-                    Ok(module) => module,
-                };
+            let refund_ratio = match self.get_refund_ratio(protocol_version) {
+                Ok(refund_ratio) => refund_ratio,
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+            };
 
             let proof_of_stake_args = {
-                //((gas spent during payment code execution) + (gas spent during session code execution)) * conv_rate
-                let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), CONV_RATE).expect("motes overflow");
-                let args = ("finalize_payment", finalize_cost_motes.value(), account_addr);
+                //((gas spent during payment code execution) + (gas spent during session code execution)) * gas_price
+                let finalize_cost_motes: Motes =
+                    match Motes::from_gas(execution_result_builder.total_cost(), gas_price) {
+                        Some(motes) => motes,
+                        None => {
+                            return Ok(ExecutionResult::precondition_failure(
+                                Error::InsufficientPaymentError,
+                            ))
+                        }
+                    };
+                let args = (
+                    "finalize_payment",
+                    finalize_cost_motes.value(),
+                    account_addr,
+                    self.config.fee_handling(),
+                    proposer,
+                    refund_ratio,
+                );
                 ArgsParser::parse(&args)
                     .and_then(|args| args.to_bytes())
                     .expect("args should parse")
@@ -999,21 +1696,51 @@ where
             let base_key = proof_of_stake_info.inner_key();
             let gas_limit = Gas::from_u64(std::u64::MAX);
 
-            executor.exec_direct(
-                proof_of_stake_module,
-                &proof_of_stake_args,
-                &mut proof_of_stake_keys,
-                base_key,
-                &system_account,
-                authorization_keys.clone(),
-                blocktime,
-                deploy_hash,
-                gas_limit,
-                protocol_version,
-                correlation_id,
-                finalization_tc,
-                Phase::FinalizePayment,
-            )
+            if self.config.use_system_contracts() {
+                // validation_spec_1: valid wasm bytes
+                let proof_of_stake_module = match preprocessor
+                    .deserialize(&proof_of_stake_info.module_bytes())
+                {
+                    Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                    Ok(module) => module,
+                };
+
+                executor.exec_direct(
+                    proof_of_stake_module,
+                    &proof_of_stake_args,
+                    &mut proof_of_stake_keys,
+                    base_key,
+                    &system_account,
+                    authorization_keys.clone(),
+                    blocktime,
+                    block_height,
+                    deploy_hash,
+                    gas_limit,
+                    wasm_costs,
+                    protocol_version,
+                    correlation_id,
+                    finalization_tc,
+                    Phase::FinalizePayment,
+                )
+            } else {
+                executor.exec_system_contract(
+                    SystemContract::ProofOfStake,
+                    &proof_of_stake_args,
+                    &mut proof_of_stake_keys,
+                    base_key,
+                    &system_account,
+                    authorization_keys.clone(),
+                    blocktime,
+                    block_height,
+                    deploy_hash,
+                    gas_limit,
+                    wasm_costs,
+                    protocol_version,
+                    correlation_id,
+                    finalization_tc,
+                    Phase::FinalizePayment,
+                )
+            }
         };
 
         execution_result_builder.set_finalize_execution_result(finalize_result);
@@ -1026,7 +1753,7 @@ where
         // NOTE: payment_code_spec_5_a is enforced in execution_result_builder.build()
         // payment_code_spec_6: return properly combined set of transforms and
         // appropriate error
-        Ok(ret)
+        Ok(record_executed_deploy(ret, blocktime, deploy_hash))
     }
 
     pub fn apply_effect(
@@ -1038,6 +1765,470 @@ where
         self.state.commit(correlation_id, prestate_hash, effects)
     }
 
+    /// Returns Merkle proofs of the values read when querying `path` from `base_key` at
+    /// `root_hash` state: a proof of the value under `base_key`, followed by proofs of the values
+    /// under each named key followed along `path`.
+    ///
+    /// The proofs stop at the first key which cannot be resolved.  Returns `None` if `root_hash`
+    /// is not found.
+    pub fn get_query_proofs(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        base_key: Key,
+        path: &[String],
+    ) -> Result<Option<Vec<TrieMerkleProof<Key, Value>>>, Error> {
+        let reader = match self
+            .state
+            .checkout(root_hash)
+            .map_err(Into::<execution::Error>::into)?
+        {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+
+        let mut ret = Vec::with_capacity(path.len() + 1);
+        let mut current_key = base_key.normalize();
+        let mut names = path.iter();
+
+        loop {
+            let proof = match reader
+                .read_with_proof(correlation_id, &current_key)
+                .map_err(Into::<execution::Error>::into)?
+            {
+                Some(proof) => proof,
+                None => break,
+            };
+            let maybe_next_key = names.next().and_then(|name| match proof.value() {
+                Value::Account(account) => {
+                    account.urefs_lookup().get(name).map(|key| key.normalize())
+                }
+                Value::Contract(contract) => {
+                    contract.urefs_lookup().get(name).map(|key| key.normalize())
+                }
+                _ => None,
+            });
+            ret.push(proof);
+            match maybe_next_key {
+                Some(next_key) => current_key = next_key,
+                None => break,
+            }
+        }
+
+        Ok(Some(ret))
+    }
+
+    /// Deletes all trie values which are not reachable from `retain_roots`, reclaiming the space
+    /// used by historical states which are no longer needed.
+    pub fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retain_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, S::Error> {
+        self.state.prune(correlation_id, retain_roots)
+    }
+
+    /// Returns the keys whose values differ between the states at `prestate_hash` and
+    /// `poststate_hash`, e.g. to find where the states computed by two nodes diverge.
+    pub fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        poststate_hash: Blake2bHash,
+    ) -> Result<DiffResult, S::Error> {
+        self.state
+            .diff(correlation_id, prestate_hash, poststate_hash)
+    }
+
+    /// Exports the next chunk of the trie values making up the state at `root_hash`, so that a
+    /// new node can download that state instead of replaying all the blocks leading to it.
+    pub fn get_trie_chunk(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        cursor: &[Blake2bHash],
+    ) -> Result<TrieChunkResult, S::Error> {
+        self.state.get_trie_chunk(correlation_id, root_hash, cursor)
+    }
+
+    /// Imports a trie value exported by [`EngineState::get_trie_chunk`] on another node, returning
+    /// the hash it is stored under.
+    pub fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_bytes: &[u8],
+    ) -> Result<Blake2bHash, S::Error> {
+        self.state.put_trie(correlation_id, trie_bytes)
+    }
+
+    /// Returns the hashes of the trie values referenced from `root_hash` which are not in global
+    /// state yet, so that a node syncing the state at `root_hash` can request exactly those.
+    pub fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<Vec<Blake2bHash>, S::Error> {
+        self.state.missing_trie_keys(correlation_id, root_hash)
+    }
+
+    /// Walks the tries under `root_hashes`, reporting corrupt and dangling trie values so that
+    /// damage to the store can be detected before it leads to a wrong post state hash.
+    pub fn verify_integrity(
+        &self,
+        correlation_id: CorrelationId,
+        root_hashes: &[Blake2bHash],
+    ) -> Result<IntegrityReport, S::Error> {
+        self.state.verify_integrity(correlation_id, root_hashes)
+    }
+
+    /// Reads the balance of a purse at `root_hash` state directly from the mint's local state,
+    /// without executing any wasm.
+    ///
+    /// The mint is resolved through the known urefs of the account owning the purse, or through
+    /// the system account's known urefs if only a purse URef is given.
+    pub fn get_balance(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        balance_source: BalanceSource,
+    ) -> Result<Motes, GetBalanceError> {
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(GetBalanceError::PostStateHashNotFound(root_hash)),
+        };
+
+        let (account_addr, maybe_purse) = match balance_source {
+            BalanceSource::Account(account_addr) => (account_addr, None),
+            BalanceSource::Purse(purse) => (SYSTEM_ACCOUNT_ADDR, Some(purse)),
+        };
+
+        let account = tracking_copy.get_account(correlation_id, account_addr)?;
+
+        let purse_key = Key::URef(maybe_purse.unwrap_or_else(|| account.purse_id().value()));
+
+        let mint_contract_uref =
+            get_system_contract_uref(&mut tracking_copy, correlation_id, &account, MINT_NAME)?;
+
+        let balance_key =
+            tracking_copy.get_purse_balance_key(correlation_id, mint_contract_uref, purse_key)?;
+
+        let balance = tracking_copy.get_purse_balance(correlation_id, balance_key)?;
+
+        Ok(balance)
+    }
+
+    /// Reads the account under `account_addr` at `root_hash` state, returning `None` if there is
+    /// no account under that address.
+    pub fn read_account(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        account_addr: [u8; 32],
+    ) -> Result<Option<Account>, GetAccountError> {
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(GetAccountError::PostStateHashNotFound(root_hash)),
+        };
+
+        match tracking_copy.get_account(correlation_id, account_addr) {
+            Ok(account) => Ok(Some(account)),
+            Err(execution::Error::KeyNotFound(_)) => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Reads the contract under `key` at `root_hash` state, returning `None` if nothing is stored
+    /// under `key`.
+    pub fn read_contract(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        key: Key,
+    ) -> Result<Option<Contract>, GetContractError> {
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(GetContractError::PostStateHashNotFound(root_hash)),
+        };
+
+        match tracking_copy.get_contract(correlation_id, key) {
+            Ok(contract) => Ok(Some(contract)),
+            Err(execution::Error::KeyNotFound(_)) => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Applies a protocol upgrade on top of the upgrade's pre-state.
+    ///
+    /// The mint and proof of stake contracts known to the system account are replaced with the
+    /// given bytes (or kept as they are) and stamped with the new protocol version in a single
+    /// commit.  The wasm costs of the new protocol version are stored only if that commit
+    /// succeeds.
+    pub fn commit_upgrade(
+        &self,
+        correlation_id: CorrelationId,
+        upgrade_config: UpgradeConfig,
+    ) -> Result<UpgradeResult, Error> {
+        let pre_state_hash = upgrade_config.pre_state_hash();
+        let current_protocol_version = upgrade_config.current_protocol_version();
+        let new_protocol_version = upgrade_config.new_protocol_version();
+
+        if new_protocol_version <= current_protocol_version {
+            return Err(Error::InvalidProtocolVersion(new_protocol_version));
+        }
+
+        let mut tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(UpgradeResult::RootNotFound),
+        };
+
+        if let Some(state_protocol_version) = tracking_copy.get_protocol_version(correlation_id)? {
+            if state_protocol_version != current_protocol_version {
+                return Err(Error::InvalidProtocolVersion(current_protocol_version));
+            }
+        }
+
+        let current_protocol_data = match self
+            .state
+            .get_protocol_data(current_protocol_version)
+            .map_err(Into::into)?
+        {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(current_protocol_version)),
+        };
+
+        // Costs, the refund ratio, the minimum gas price, the native transfer cost and the key
+        // limits carry over from the current protocol version unless the upgrade changes them.
+        let wasm_costs = upgrade_config
+            .new_wasm_costs()
+            .unwrap_or_else(|| *current_protocol_data.wasm_costs());
+        let refund_ratio = upgrade_config
+            .new_refund_ratio()
+            .unwrap_or_else(|| current_protocol_data.refund_ratio());
+        let min_gas_price = upgrade_config
+            .new_min_gas_price()
+            .unwrap_or_else(|| current_protocol_data.min_gas_price());
+        let native_transfer_cost = upgrade_config
+            .new_native_transfer_cost()
+            .unwrap_or_else(|| current_protocol_data.native_transfer_cost());
+        let key_limits = {
+            let current_key_limits = current_protocol_data.key_limits();
+            KeyLimits::new(
+                upgrade_config
+                    .new_max_associated_keys()
+                    .unwrap_or_else(|| current_key_limits.max_associated_keys()),
+                upgrade_config
+                    .new_max_named_keys()
+                    .unwrap_or_else(|| current_key_limits.max_named_keys()),
+            )
+        };
+
+        let preprocessor = WasmiPreprocessor::new(wasm_costs)
+            .set_float_handling(self.config.float_handling())
+            .set_module_limits(self.config.module_limits());
+
+        let system_account = tracking_copy.get_account(correlation_id, SYSTEM_ACCOUNT_ADDR)?;
+
+        let system_contracts = vec![
+            (MINT_NAME, upgrade_config.new_mint_bytes()),
+            (POS_NAME, upgrade_config.new_proof_of_stake_bytes()),
+        ];
+
+        for (name, maybe_new_bytes) in system_contracts {
+            let contract_key = {
+                let uref = get_system_contract_uref(
+                    &mut tracking_copy,
+                    correlation_id,
+                    &system_account,
+                    name,
+                )?;
+                Key::URef(uref).normalize()
+            };
+
+            let (current_bytes, known_urefs, _) = tracking_copy
+                .get_contract(correlation_id, contract_key)?
+                .destructure();
+
+            let bytes = match maybe_new_bytes {
+                Some(new_bytes) => {
+                    // Reject replacement code which couldn't be executed under the new costs.
+                    preprocessor.preprocess(new_bytes)?;
+                    new_bytes.to_vec()
+                }
+                None => current_bytes,
+            };
+
+            let key = Validated::new(contract_key, Validated::valid).unwrap(); // safe to unwrap
+            let value = {
+                let value =
+                    Value::Contract(Contract::new(bytes, known_urefs, new_protocol_version));
+                Validated::new(value, Validated::valid).unwrap() // safe to unwrap
+            };
+
+            tracking_copy.write(key, value);
+        }
+
+        let protocol_data = ProtocolData::new(
+            wasm_costs,
+            refund_ratio,
+            min_gas_price,
+            native_transfer_cost,
+            key_limits,
+        );
+
+        {
+            let key = {
+                let key = protocol_version_key();
+                Validated::new(key, Validated::valid).unwrap() // safe to unwrap
+            };
+            let value = {
+                let value = Value::UInt64(new_protocol_version);
+                Validated::new(value, Validated::valid).unwrap() // safe to unwrap
+            };
+            tracking_copy.write(key, value);
+        }
+        {
+            let key = {
+                let key = protocol_data_key();
+                Validated::new(key, Validated::valid).unwrap() // safe to unwrap
+            };
+            let value = {
+                let value = Value::ByteArray(protocol_data.to_bytes()?);
+                Validated::new(value, Validated::valid).unwrap() // safe to unwrap
+            };
+            tracking_copy.write(key, value);
+        }
+
+        let effects = tracking_copy.effect();
+        let commit_result = self
+            .state
+            .commit_with_protocol_data(
+                correlation_id,
+                pre_state_hash,
+                effects.transforms.to_owned(),
+                new_protocol_version,
+                &protocol_data,
+            )
+            .map_err(Into::into)?;
+
+        let upgrade_result = UpgradeResult::from_commit_result(commit_result, effects);
+
+        Ok(upgrade_result)
+    }
+
+    /// Gets the protocol data stored for `protocol_version`.
+    ///
+    /// Protocol data is only ever seeded by genesis and upgrades, so a protocol version without
+    /// any is not supported by this node.
+    fn get_stored_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<ProtocolData, Error> {
+        match self
+            .state
+            .get_protocol_data(protocol_version)
+            .map_err(Into::into)?
+        {
+            Some(protocol_data) => Ok(protocol_data),
+            None => Err(Error::InvalidProtocolVersion(protocol_version)),
+        }
+    }
+
+    /// Gets the wasm costs associated with `protocol_version`.
+    pub fn get_wasm_costs(&self, protocol_version: ProtocolVersion) -> Result<WasmCosts, Error> {
+        let protocol_data = self.get_stored_protocol_data(protocol_version)?;
+        Ok(*protocol_data.wasm_costs())
+    }
+
+    /// Gets the refund ratio associated with `protocol_version`.
+    pub fn get_refund_ratio(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<RefundRatio, Error> {
+        let protocol_data = self.get_stored_protocol_data(protocol_version)?;
+        Ok(protocol_data.refund_ratio())
+    }
+
+    /// Gets the minimum gas price associated with `protocol_version`.
+    pub fn get_min_gas_price(&self, protocol_version: ProtocolVersion) -> Result<u64, Error> {
+        let protocol_data = self.get_stored_protocol_data(protocol_version)?;
+        Ok(protocol_data.min_gas_price())
+    }
+
+    /// Gets the gas charged for a native transfer under `protocol_version`.
+    pub fn get_native_transfer_cost(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<u64, Error> {
+        let protocol_data = self.get_stored_protocol_data(protocol_version)?;
+        Ok(protocol_data.native_transfer_cost())
+    }
+
+    /// Gets the limits on the keys accounts may hold under `protocol_version`.
+    pub fn get_key_limits(&self, protocol_version: ProtocolVersion) -> Result<KeyLimits, Error> {
+        let protocol_data = self.get_stored_protocol_data(protocol_version)?;
+        Ok(protocol_data.key_limits())
+    }
+
+    /// Gets the protocol version recorded at `root_hash` state along with its protocol data.
+    pub fn get_protocol_data(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<(ProtocolVersion, ProtocolData), GetProtocolDataError> {
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(GetProtocolDataError::PostStateHashNotFound(root_hash)),
+        };
+
+        let protocol_version = match tracking_copy.get_protocol_version(correlation_id)? {
+            Some(protocol_version) => protocol_version,
+            None => return Err(GetProtocolDataError::ProtocolVersionNotFound),
+        };
+
+        match self
+            .state
+            .get_protocol_data(protocol_version)
+            .map_err(Into::<execution::Error>::into)?
+        {
+            Some(protocol_data) => Ok((protocol_version, protocol_data)),
+            None => Err(GetProtocolDataError::ProtocolDataNotFound(protocol_version)),
+        }
+    }
+
+    /// Stores the protocol data recorded at `root_hash` state for the protocol version recorded
+    /// there, returning both.
+    ///
+    /// A node which synced the state at `root_hash` from another node, instead of executing the
+    /// blocks leading to it, calls this to be able to execute deploys on top of it.  The protocol
+    /// data is part of the synced trie, so it is verified against `root_hash` like the rest of the
+    /// state.
+    pub fn import_protocol_data(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<(ProtocolVersion, ProtocolData), GetProtocolDataError> {
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(GetProtocolDataError::PostStateHashNotFound(root_hash)),
+        };
+
+        let protocol_version = match tracking_copy.get_protocol_version(correlation_id)? {
+            Some(protocol_version) => protocol_version,
+            None => return Err(GetProtocolDataError::ProtocolVersionNotFound),
+        };
+
+        let protocol_data = match tracking_copy.get_protocol_data(correlation_id)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(GetProtocolDataError::ProtocolDataNotFound(protocol_version)),
+        };
+
+        self.state
+            .put_protocol_data(protocol_version, &protocol_data)
+            .map_err(Into::<execution::Error>::into)?;
+
+        Ok((protocol_version, protocol_data))
+    }
+
     /// Calculates bonded validators at `root_hash` state.
     pub fn get_bonded_validators(
         &self,
@@ -1066,3 +2257,38 @@ where
             })
     }
 }
+
+/// Resolves the uref of the contract stored under the system contract known to `account` as
+/// `name`.
+///
+/// Accounts created by the legacy genesis process refer to system contracts through a public uref
+/// storing the key of the contract, while accounts created from a chainspec refer to the
+/// contracts directly.
+fn get_system_contract_uref<R>(
+    tracking_copy: &mut TrackingCopy<R>,
+    correlation_id: CorrelationId,
+    account: &Account,
+    name: &str,
+) -> Result<URef, Error>
+where
+    R: StateReader<Key, Value>,
+    R::Error: Into<execution::Error>,
+{
+    let key = match account.urefs_lookup().get(name) {
+        Some(key) => key.normalize(),
+        None => return Err(Error::MissingSystemContractError(name.to_string())),
+    };
+    let maybe_value = tracking_copy
+        .get(correlation_id, &key)
+        .map_err(Into::<execution::Error>::into)?;
+    match maybe_value {
+        Some(Value::Key(Key::URef(inner_uref))) => Ok(inner_uref),
+        Some(Value::Contract(_)) => Ok(*key.as_uref().expect("should be a uref")),
+        Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+            "Value::Key or Value::Contract".to_string(),
+            other.type_string(),
+        ))
+        .into()),
+        None => Err(execution::Error::KeyNotFound(key).into()),
+    }
+}
@@ -0,0 +1,190 @@
+use std::any::Any;
+use std::fmt;
+
+use linked_hash_map::LinkedHashMap;
+use parking_lot::Mutex;
+
+use engine_shared::newtypes::Blake2bHash;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::PreprocessingError;
+
+/// Identifies a preprocessed module by the hash of its original bytes and the
+/// wasm costs used to instrument it.
+type ModuleCacheKey = (Blake2bHash, WasmCosts);
+
+/// A least-recently-used cache of preprocessed modules, so that identical
+/// wasm (e.g. standard payment code) isn't validated and instrumented again on
+/// every exec.
+pub struct ModuleCache {
+    capacity: usize,
+    modules: Mutex<LinkedHashMap<ModuleCacheKey, Box<dyn Any + Send>>>,
+}
+
+impl ModuleCache {
+    /// Creates a cache holding at most `capacity` modules.  A capacity of zero
+    /// disables caching.
+    pub fn new(capacity: usize) -> Self {
+        ModuleCache {
+            capacity,
+            modules: Mutex::new(LinkedHashMap::new()),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.modules.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cached module prepared from `module_bytes` with `wasm_costs`,
+    /// or prepares it with `preprocess` and caches the result.  Modules which
+    /// fail to preprocess are not cached.
+    pub fn get_or_preprocess<A, F>(
+        &self,
+        module_bytes: &[u8],
+        wasm_costs: WasmCosts,
+        preprocess: F,
+    ) -> Result<A, PreprocessingError>
+    where
+        A: Clone + Send + 'static,
+        F: FnOnce(&[u8]) -> Result<A, PreprocessingError>,
+    {
+        if self.capacity == 0 {
+            return preprocess(module_bytes);
+        }
+
+        let key = (Blake2bHash::new(module_bytes), wasm_costs);
+
+        let cached = self
+            .modules
+            .lock()
+            .get_refresh(&key)
+            .and_then(|module| module.downcast_ref::<A>().cloned());
+        if let Some(module) = cached {
+            return Ok(module);
+        }
+
+        let module = preprocess(module_bytes)?;
+
+        let mut modules = self.modules.lock();
+        modules.insert(key, Box::new(module.clone()));
+        while modules.len() > self.capacity {
+            modules.pop_front();
+        }
+
+        Ok(module)
+    }
+}
+
+impl fmt::Debug for ModuleCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ModuleCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use engine_wasm_prep::wasm_costs::WasmCosts;
+    use engine_wasm_prep::PreprocessingError;
+
+    use super::ModuleCache;
+
+    fn get(
+        cache: &ModuleCache,
+        module_bytes: &[u8],
+        wasm_costs: WasmCosts,
+        preprocess_count: &Cell<usize>,
+    ) -> Vec<u8> {
+        cache
+            .get_or_preprocess(module_bytes, wasm_costs, |bytes| {
+                preprocess_count.set(preprocess_count.get() + 1);
+                Ok(bytes.to_vec())
+            })
+            .expect("should preprocess")
+    }
+
+    #[test]
+    fn should_preprocess_identical_module_once() {
+        let cache = ModuleCache::new(2);
+        let preprocess_count = Cell::new(0);
+
+        assert_eq!(
+            get(&cache, &[1], WasmCosts::default(), &preprocess_count),
+            vec![1]
+        );
+        assert_eq!(
+            get(&cache, &[1], WasmCosts::default(), &preprocess_count),
+            vec![1]
+        );
+
+        assert_eq!(preprocess_count.get(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn should_preprocess_again_with_different_wasm_costs() {
+        let cache = ModuleCache::new(2);
+        let preprocess_count = Cell::new(0);
+
+        get(&cache, &[1], WasmCosts::default(), &preprocess_count);
+        get(&cache, &[1], WasmCosts::free(), &preprocess_count);
+
+        assert_eq!(preprocess_count.get(), 2);
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_module() {
+        let cache = ModuleCache::new(2);
+        let preprocess_count = Cell::new(0);
+
+        get(&cache, &[1], WasmCosts::default(), &preprocess_count);
+        get(&cache, &[2], WasmCosts::default(), &preprocess_count);
+        // Refreshes [1], leaving [2] as the least recently used
+        get(&cache, &[1], WasmCosts::default(), &preprocess_count);
+        get(&cache, &[3], WasmCosts::default(), &preprocess_count);
+        assert_eq!(preprocess_count.get(), 3);
+        assert_eq!(cache.len(), 2);
+
+        get(&cache, &[1], WasmCosts::default(), &preprocess_count);
+        assert_eq!(preprocess_count.get(), 3);
+
+        get(&cache, &[2], WasmCosts::default(), &preprocess_count);
+        assert_eq!(preprocess_count.get(), 4);
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_capacity() {
+        let cache = ModuleCache::new(0);
+        let preprocess_count = Cell::new(0);
+
+        get(&cache, &[1], WasmCosts::default(), &preprocess_count);
+        get(&cache, &[1], WasmCosts::default(), &preprocess_count);
+
+        assert_eq!(preprocess_count.get(), 2);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn should_not_cache_failed_preprocessing() {
+        let cache = ModuleCache::new(2);
+
+        let result: Result<Vec<u8>, PreprocessingError> =
+            cache.get_or_preprocess(&[1], WasmCosts::default(), |_| {
+                Err(PreprocessingError::NoExportSection)
+            });
+
+        assert_eq!(result, Err(PreprocessingError::NoExportSection));
+        assert!(cache.is_empty());
+    }
+}
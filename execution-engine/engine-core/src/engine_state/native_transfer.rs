@@ -0,0 +1,303 @@
+//! Transfers of motes executed by the engine itself rather than by payment and session code.
+//!
+//! A native transfer moves motes straight between the balances kept by the mint and charges the
+//! flat native transfer cost of the protocol version, so it needs neither wasm execution nor calls
+//! into the system contracts.
+
+use contract_ffi::bytesrepr::{self, ToBytes};
+use contract_ffi::key::Key;
+use contract_ffi::system_contracts::mint::{self, TOTAL_SUPPLY_KEY};
+use contract_ffi::system_contracts::pos::FeeHandling;
+use contract_ffi::uref::URef;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::{Account, Value, U512};
+use engine_shared::gas::{Gas, GasBreakdown, GasCategory};
+use engine_shared::motes::Motes;
+use engine_shared::newtypes::{CorrelationId, Validated};
+use engine_storage::global_state::StateReader;
+
+use crate::engine_state::error::Error;
+use crate::engine_state::execution_effect::Event;
+use crate::engine_state::execution_result::ExecutionResult;
+use crate::engine_state::genesis::POS_REWARDS_PURSE;
+use crate::execution::{self, MINT_NAME, POS_NAME};
+use crate::tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt};
+
+/// Topic of the event reported for every successful native transfer. Its payload is the address
+/// of the source purse, the target public key, the amount and the optional id, serialized in
+/// that order.
+pub const NATIVE_TRANSFER_EVENT_TOPIC: &str = "native_transfer";
+
+/// A transfer of motes to the main purse of an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeTransfer {
+    source: Option<URef>,
+    target: PublicKey,
+    amount: U512,
+    id: Option<u64>,
+}
+
+impl NativeTransfer {
+    /// Creates a transfer of `amount` motes to the main purse of `target`. The motes are taken
+    /// from `source`, which must be a writeable purse held in the named keys of the deploying
+    /// account, or from the account's main purse if `source` is `None`.
+    pub fn new(source: Option<URef>, target: PublicKey, amount: U512, id: Option<u64>) -> Self {
+        NativeTransfer {
+            source,
+            target,
+            amount,
+            id,
+        }
+    }
+
+    pub fn source(&self) -> Option<URef> {
+        self.source
+    }
+
+    pub fn target(&self) -> PublicKey {
+        self.target
+    }
+
+    pub fn amount(&self) -> U512 {
+        self.amount
+    }
+
+    /// Returns the identifier chosen by the sender, reported in the transfer event.
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+}
+
+/// Executes `transfer` on behalf of `account`, charging it `fee` from its main purse.
+///
+/// Returns an error, to be reported as a precondition failure, if the transfer can't be attempted
+/// at all, e.g. because the account can't pay the fee. A transfer which fails once the fee is
+/// charged, e.g. because the source purse holds too few motes, results in a failure whose effect
+/// is charging the fee.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute<R>(
+    tracking_copy: &mut TrackingCopy<R>,
+    correlation_id: CorrelationId,
+    account: &Account,
+    transfer: &NativeTransfer,
+    cost: Gas,
+    fee: Motes,
+    fee_handling: FeeHandling,
+    proposer: Option<PublicKey>,
+) -> Result<ExecutionResult, Error>
+where
+    R: StateReader<Key, Value>,
+    R::Error: Into<execution::Error>,
+{
+    let mint_key = system_contract_key(account, MINT_NAME)?;
+    let mint_info = tracking_copy.get_system_contract_info(correlation_id, mint_key)?;
+    // Safe to unwrap, as `get_system_contract_info` checks that the key is a URef.
+    let mint_inner_uref = *mint_info.inner_key().as_uref().unwrap();
+
+    let source = match transfer.source() {
+        None => account.purse_id().value(),
+        Some(source) => find_writeable_purse(account, source)?,
+    };
+
+    let main_purse_balance_key = tracking_copy.get_purse_balance_key(
+        correlation_id,
+        mint_inner_uref,
+        Key::URef(account.purse_id().value()),
+    )?;
+    let main_purse_balance =
+        tracking_copy.get_purse_balance(correlation_id, main_purse_balance_key)?;
+    if main_purse_balance < fee {
+        return Err(Error::InsufficientBalance {
+            required: fee,
+            available: main_purse_balance,
+        });
+    }
+
+    let cost_breakdown = GasBreakdown::new(GasCategory::Transfers, cost);
+
+    if !fee.value().is_zero() {
+        write_balance(
+            tracking_copy,
+            main_purse_balance_key,
+            main_purse_balance - fee,
+        );
+        match (fee_handling, proposer) {
+            (FeeHandling::Burn, _) => {
+                let total_supply_key = mint_info
+                    .contract()
+                    .urefs_lookup()
+                    .get(TOTAL_SUPPLY_KEY)
+                    .cloned()
+                    .ok_or_else(|| Error::MintError(mint::error::Error::InvalidURef))?;
+                let total_supply =
+                    tracking_copy.get_purse_balance(correlation_id, total_supply_key)?;
+                write_balance(tracking_copy, total_supply_key, total_supply - fee);
+            }
+            (FeeHandling::PayToProposer, Some(proposer)) => {
+                let proposer_purse_balance_key =
+                    match tracking_copy.get_account(correlation_id, proposer.value()) {
+                        Ok(proposer_account) => tracking_copy.get_purse_balance_key(
+                            correlation_id,
+                            mint_inner_uref,
+                            Key::URef(proposer_account.purse_id().value()),
+                        )?,
+                        // A proposer without an account can't be paid directly, so its fees stay in
+                        // the rewards purse.
+                        Err(_) => rewards_purse_balance_key(
+                            tracking_copy,
+                            correlation_id,
+                            account,
+                            mint_inner_uref,
+                        )?,
+                    };
+                add_balance(
+                    tracking_copy,
+                    correlation_id,
+                    proposer_purse_balance_key,
+                    fee,
+                )?;
+            }
+            (FeeHandling::Accumulate, _) | (FeeHandling::PayToProposer, None) => {
+                let rewards_purse_balance_key = rewards_purse_balance_key(
+                    tracking_copy,
+                    correlation_id,
+                    account,
+                    mint_inner_uref,
+                )?;
+                add_balance(
+                    tracking_copy,
+                    correlation_id,
+                    rewards_purse_balance_key,
+                    fee,
+                )?;
+            }
+        }
+    }
+
+    let fee_effect = tracking_copy.effect();
+    let failure = |error: mint::error::Error| ExecutionResult::Failure {
+        error: Error::MintError(error),
+        effect: fee_effect.clone(),
+        cost,
+        cost_breakdown,
+    };
+
+    let target_purse = match tracking_copy.get_account(correlation_id, transfer.target().value()) {
+        Ok(target_account) => target_account.purse_id().value(),
+        Err(_) => return Ok(failure(mint::error::Error::DestNotFound)),
+    };
+    let source_balance_key =
+        tracking_copy.get_purse_balance_key(correlation_id, mint_inner_uref, Key::URef(source))?;
+    let source_balance = tracking_copy.get_purse_balance(correlation_id, source_balance_key)?;
+    let amount = Motes::new(transfer.amount());
+    if source_balance < amount {
+        return Ok(failure(mint::error::Error::InsufficientFunds));
+    }
+    let target_balance_key = tracking_copy.get_purse_balance_key(
+        correlation_id,
+        mint_inner_uref,
+        Key::URef(target_purse),
+    )?;
+
+    write_balance(tracking_copy, source_balance_key, source_balance - amount);
+    add_balance(tracking_copy, correlation_id, target_balance_key, amount)?;
+
+    let payload = event_payload(source, transfer)?;
+    tracking_copy.emit_event(Event::new(
+        Key::Account(account.pub_key()),
+        NATIVE_TRANSFER_EVENT_TOPIC.to_string(),
+        payload,
+    ));
+
+    Ok(ExecutionResult::Success {
+        effect: tracking_copy.effect(),
+        cost,
+        cost_breakdown,
+    })
+}
+
+fn system_contract_key(account: &Account, name: &str) -> Result<Key, Error> {
+    account
+        .urefs_lookup()
+        .get(name)
+        .map(|key| key.normalize())
+        .ok_or_else(|| Error::MissingSystemContractError(name.to_string()))
+}
+
+/// Returns the purse of the account's named keys which `source` refers to, provided the account
+/// may withdraw from it.
+fn find_writeable_purse(account: &Account, source: URef) -> Result<URef, Error> {
+    account
+        .urefs_lookup()
+        .values()
+        .filter_map(Key::as_uref)
+        .find(|known_uref| known_uref.addr() == source.addr() && known_uref.is_writeable())
+        .cloned()
+        .ok_or_else(|| Error::ExecError(execution::Error::ForgedReference(source)))
+}
+
+fn rewards_purse_balance_key<R>(
+    tracking_copy: &mut TrackingCopy<R>,
+    correlation_id: CorrelationId,
+    account: &Account,
+    mint_inner_uref: URef,
+) -> Result<Key, Error>
+where
+    R: StateReader<Key, Value>,
+    R::Error: Into<execution::Error>,
+{
+    let proof_of_stake_key = system_contract_key(account, POS_NAME)?;
+    let proof_of_stake_info =
+        tracking_copy.get_system_contract_info(correlation_id, proof_of_stake_key)?;
+    let rewards_purse = proof_of_stake_info
+        .contract()
+        .urefs_lookup()
+        .get(POS_REWARDS_PURSE)
+        .cloned()
+        .ok_or(Error::DeployError)?;
+    let balance_key =
+        tracking_copy.get_purse_balance_key(correlation_id, mint_inner_uref, rewards_purse)?;
+    Ok(balance_key)
+}
+
+fn write_balance<R: StateReader<Key, Value>>(
+    tracking_copy: &mut TrackingCopy<R>,
+    balance_key: Key,
+    balance: Motes,
+) {
+    let key = Validated::new(balance_key, Validated::valid).unwrap(); // safe to unwrap
+    let value = Validated::new(Value::UInt512(balance.value()), Validated::valid).unwrap(); // safe to unwrap
+    tracking_copy.write(key, value);
+}
+
+fn add_balance<R>(
+    tracking_copy: &mut TrackingCopy<R>,
+    correlation_id: CorrelationId,
+    balance_key: Key,
+    amount: Motes,
+) -> Result<(), Error>
+where
+    R: StateReader<Key, Value>,
+    R::Error: Into<execution::Error>,
+{
+    let key = Validated::new(balance_key, Validated::valid).unwrap(); // safe to unwrap
+    let value = Validated::new(Value::UInt512(amount.value()), Validated::valid).unwrap(); // safe to unwrap
+    match tracking_copy
+        .add(correlation_id, key, value)
+        .map_err(Into::into)?
+    {
+        AddResult::Success => Ok(()),
+        AddResult::KeyNotFound(key) => Err(execution::Error::KeyNotFound(key).into()),
+        AddResult::TypeMismatch(type_mismatch) => {
+            Err(execution::Error::TypeMismatch(type_mismatch).into())
+        }
+    }
+}
+
+fn event_payload(source: URef, transfer: &NativeTransfer) -> Result<Vec<u8>, bytesrepr::Error> {
+    let mut payload = source.addr().to_bytes()?;
+    payload.append(&mut transfer.target().to_bytes()?);
+    payload.append(&mut transfer.amount().to_bytes()?);
+    payload.append(&mut transfer.id().to_bytes()?);
+    Ok(payload)
+}
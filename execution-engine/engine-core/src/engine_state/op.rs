@@ -6,6 +6,19 @@ pub enum Op {
     NoOp,
 }
 
+impl Op {
+    /// Returns true if operations `self` and `other` on the same key, made by two deploys against
+    /// the same prestate, give the same result whichever order the deploys are applied in.
+    pub fn commutes_with(&self, other: &Op) -> bool {
+        match (self, other) {
+            (_, Op::NoOp) | (Op::NoOp, _) => true,
+            (Op::Read, Op::Read) => true,
+            (Op::Add, Op::Add) => true,
+            _ => false,
+        }
+    }
+}
+
 impl std::ops::Add for Op {
     type Output = Op;
 
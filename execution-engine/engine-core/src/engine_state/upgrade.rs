@@ -0,0 +1,142 @@
+use std::fmt;
+
+use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::RefundRatio;
+use engine_shared::newtypes::Blake2bHash;
+use engine_shared::transform::TypeMismatch;
+use engine_storage::global_state::CommitResult;
+use engine_storage::protocol_data_store::ProtocolVersion;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+
+use crate::engine_state::execution_effect::ExecutionEffect;
+
+/// Describes a protocol upgrade applied on top of a given pre-state.  The node is responsible for
+/// requesting it at the upgrade's activation point.
+pub struct UpgradeConfig {
+    pre_state_hash: Blake2bHash,
+    current_protocol_version: ProtocolVersion,
+    new_protocol_version: ProtocolVersion,
+    new_mint_bytes: Option<Vec<u8>>,
+    new_proof_of_stake_bytes: Option<Vec<u8>>,
+    new_wasm_costs: Option<WasmCosts>,
+    new_refund_ratio: Option<RefundRatio>,
+    new_min_gas_price: Option<u64>,
+    new_native_transfer_cost: Option<u64>,
+    new_max_associated_keys: Option<u32>,
+    new_max_named_keys: Option<u32>,
+}
+
+impl UpgradeConfig {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        current_protocol_version: ProtocolVersion,
+        new_protocol_version: ProtocolVersion,
+        new_mint_bytes: Option<Vec<u8>>,
+        new_proof_of_stake_bytes: Option<Vec<u8>>,
+        new_wasm_costs: Option<WasmCosts>,
+        new_refund_ratio: Option<RefundRatio>,
+        new_min_gas_price: Option<u64>,
+        new_native_transfer_cost: Option<u64>,
+        new_max_associated_keys: Option<u32>,
+        new_max_named_keys: Option<u32>,
+    ) -> Self {
+        UpgradeConfig {
+            pre_state_hash,
+            current_protocol_version,
+            new_protocol_version,
+            new_mint_bytes,
+            new_proof_of_stake_bytes,
+            new_wasm_costs,
+            new_refund_ratio,
+            new_min_gas_price,
+            new_native_transfer_cost,
+            new_max_associated_keys,
+            new_max_named_keys,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn current_protocol_version(&self) -> ProtocolVersion {
+        self.current_protocol_version
+    }
+
+    pub fn new_protocol_version(&self) -> ProtocolVersion {
+        self.new_protocol_version
+    }
+
+    pub fn new_mint_bytes(&self) -> Option<&[u8]> {
+        self.new_mint_bytes.as_ref().map(Vec::as_slice)
+    }
+
+    pub fn new_proof_of_stake_bytes(&self) -> Option<&[u8]> {
+        self.new_proof_of_stake_bytes.as_ref().map(Vec::as_slice)
+    }
+
+    pub fn new_wasm_costs(&self) -> Option<WasmCosts> {
+        self.new_wasm_costs
+    }
+
+    pub fn new_refund_ratio(&self) -> Option<RefundRatio> {
+        self.new_refund_ratio
+    }
+
+    pub fn new_min_gas_price(&self) -> Option<u64> {
+        self.new_min_gas_price
+    }
+
+    pub fn new_native_transfer_cost(&self) -> Option<u64> {
+        self.new_native_transfer_cost
+    }
+
+    pub fn new_max_associated_keys(&self) -> Option<u32> {
+        self.new_max_associated_keys
+    }
+
+    pub fn new_max_named_keys(&self) -> Option<u32> {
+        self.new_max_named_keys
+    }
+}
+
+pub enum UpgradeResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+    },
+}
+
+impl fmt::Display for UpgradeResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            UpgradeResult::RootNotFound => write!(f, "Root not found"),
+            UpgradeResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            UpgradeResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            UpgradeResult::Success {
+                post_state_hash,
+                effect,
+            } => write!(f, "Success: {} {:?}", post_state_hash, effect),
+        }
+    }
+}
+
+impl UpgradeResult {
+    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => UpgradeResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => UpgradeResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => UpgradeResult::TypeMismatch(type_mismatch),
+            CommitResult::Success(post_state_hash) => UpgradeResult::Success {
+                post_state_hash,
+                effect,
+            },
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use parity_wasm::elements::Serialize;
 
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
 use crate::engine_state;
-use contract_ffi::key::addr_to_hex;
+use contract_ffi::base16;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
 use engine_wasm_prep::wasm_costs::WasmCosts;
@@ -28,7 +31,7 @@ impl Into<Vec<u8>> for WasmiBytes {
 
 /// Helper function to create validator labels as they are constructed in PoS.
 pub fn pos_validator_key(pk: PublicKey, stakes: U512) -> String {
-    let public_key_hex: String = addr_to_hex(&pk.value());
+    let public_key_hex: String = pk.to_hex();
     // This is how PoS contract stores validator keys in its known_urefs map.
     format!("v_{}_{}", public_key_hex, stakes)
 }
@@ -40,31 +43,45 @@ pub fn pos_validator_to_tuple(pos_bond: &str) -> Option<(PublicKey, U512)> {
         None
     } else {
         let hex_key: &str = split_bond.next()?;
-        if hex_key.len() != 64 {
-            return None;
-        }
-        let mut key_bytes = [0u8; 32];
-        for i in 0..32 {
-            key_bytes[i] = u8::from_str_radix(&hex_key[2 * i..2 * (i + 1)], 16).ok()?;
-        }
-        let pub_key = PublicKey::new(key_bytes);
+        let key_bytes = base16::decode_lower(hex_key).ok()?;
+        let pub_key = PublicKey::try_from(key_bytes.as_slice()).ok()?;
         let balance = split_bond.next().and_then(|b| U512::from_dec_str(b).ok())?;
         Some((pub_key, balance))
     }
 }
 
+/// Returns the validators whose stake differs between the `before` and `after` bonded validator
+/// sets, along with their stake in `after`. Validators which are no longer bonded have a zero
+/// stake.
+#[allow(clippy::implicit_hasher)]
+pub fn bonded_validator_changes(
+    before: &HashMap<PublicKey, U512>,
+    after: &HashMap<PublicKey, U512>,
+) -> HashMap<PublicKey, U512> {
+    let unbonded = before
+        .keys()
+        .filter(|public_key| !after.contains_key(public_key))
+        .map(|public_key| (*public_key, U512::zero()));
+    let changed = after
+        .iter()
+        .filter(|(public_key, stake)| before.get(public_key) != Some(stake))
+        .map(|(public_key, stake)| (*public_key, *stake));
+    unbonded.chain(changed).collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use contract_ffi::key::addr_to_hex;
-    use contract_ffi::value::account::PublicKey;
+    use contract_ffi::value::account::{PublicKey, SECP256K1_PUBLIC_KEY_LENGTH};
     use contract_ffi::value::U512;
 
-    use super::{pos_validator_key, pos_validator_to_tuple};
+    use std::collections::HashMap;
+
+    use super::{bonded_validator_changes, pos_validator_key, pos_validator_to_tuple};
 
     #[test]
     fn should_to_string_pos_validator() {
         let public_key = PublicKey::new([1u8; 32]);
-        let hex_public_key = addr_to_hex(&public_key.value());
+        let hex_public_key = public_key.to_hex();
         let stake = U512::from(100);
         let expected = format!("v_{}_{}", hex_public_key, stake);
         assert_eq!(pos_validator_key(public_key, stake), expected);
@@ -73,7 +90,7 @@ mod tests {
     #[test]
     fn should_parse_string_to_validator_tuple() {
         let public_key = PublicKey::new([1u8; 32]);
-        let hex_public_key = addr_to_hex(&public_key.value());
+        let hex_public_key = public_key.to_hex();
         let stake = U512::from(100);
         let strng = format!("v_{}_{}", hex_public_key, stake);
 
@@ -84,9 +101,51 @@ mod tests {
         assert_eq!(parsed_stake, stake);
     }
 
+    #[test]
+    fn should_parse_secp256k1_validator_tuple() {
+        let public_key = PublicKey::secp256k1([2u8; SECP256K1_PUBLIC_KEY_LENGTH]);
+        let stake = U512::from(100);
+        let strng = pos_validator_key(public_key, stake);
+
+        let parsed = pos_validator_to_tuple(&strng);
+        assert_eq!(parsed, Some((public_key, stake)));
+    }
+
     #[test]
     fn should_not_parse_string_to_validator_tuple() {
         let not_validator_stake = "v_10_ab".to_string();
         assert!(pos_validator_to_tuple(&not_validator_stake).is_none());
     }
+
+    #[test]
+    fn should_report_bonded_validator_changes() {
+        let unchanged = PublicKey::new([1u8; 32]);
+        let rebonded = PublicKey::new([2u8; 32]);
+        let unbonded = PublicKey::new([3u8; 32]);
+        let bonded = PublicKey::new([4u8; 32]);
+
+        let before: HashMap<PublicKey, U512> = vec![
+            (unchanged, U512::from(100)),
+            (rebonded, U512::from(200)),
+            (unbonded, U512::from(300)),
+        ]
+        .into_iter()
+        .collect();
+        let after: HashMap<PublicKey, U512> = vec![
+            (unchanged, U512::from(100)),
+            (rebonded, U512::from(250)),
+            (bonded, U512::from(400)),
+        ]
+        .into_iter()
+        .collect();
+
+        let expected: HashMap<PublicKey, U512> = vec![
+            (rebonded, U512::from(250)),
+            (unbonded, U512::zero()),
+            (bonded, U512::from(400)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(bonded_validator_changes(&before, &after), expected);
+    }
 }
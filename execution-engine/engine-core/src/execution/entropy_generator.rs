@@ -0,0 +1,82 @@
+use contract_ffi::contract_api::ENTROPY_LENGTH;
+use contract_ffi::execution::Phase;
+use contract_ffi::value::account::BlockTime;
+use engine_shared::newtypes::Blake2bHasher;
+
+/// An [`EntropyGenerator`] derives a sequence of deterministic 32-byte seeds for the contracts of
+/// a deploy.
+///
+/// Each seed is the Blake2b hash of the deploy hash, the block time, the [`Phase`] and the number
+/// of seeds generated before it, so re-executing the deploy in the same block yields the same
+/// sequence, while payment and session code get different ones.
+pub struct EntropyGenerator {
+    deploy_hash: [u8; 32],
+    blocktime: BlockTime,
+    phase: Phase,
+    counter: u64,
+}
+
+impl EntropyGenerator {
+    pub fn new(deploy_hash: [u8; 32], blocktime: BlockTime, phase: Phase) -> EntropyGenerator {
+        EntropyGenerator {
+            deploy_hash,
+            blocktime,
+            phase,
+            counter: 0,
+        }
+    }
+
+    pub fn next_entropy(&mut self) -> [u8; ENTROPY_LENGTH] {
+        let mut hasher = Blake2bHasher::new();
+        hasher.update(&self.deploy_hash);
+        hasher.update(&self.blocktime.0.to_le_bytes());
+        hasher.update(&[self.phase as u8]);
+        hasher.update(&self.counter.to_le_bytes());
+        self.counter += 1;
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use contract_ffi::execution::Phase;
+    use contract_ffi::value::account::BlockTime;
+
+    use super::EntropyGenerator;
+
+    const DEPLOY_HASH: [u8; 32] = [1u8; 32];
+    const BLOCKTIME: BlockTime = BlockTime(42);
+
+    #[test]
+    fn should_generate_same_entropy_for_same_inputs() {
+        let mut generator_a = EntropyGenerator::new(DEPLOY_HASH, BLOCKTIME, Phase::Session);
+        let mut generator_b = EntropyGenerator::new(DEPLOY_HASH, BLOCKTIME, Phase::Session);
+
+        for _ in 0..3 {
+            assert_eq!(generator_a.next_entropy(), generator_b.next_entropy());
+        }
+    }
+
+    #[test]
+    fn should_generate_different_entropy_on_each_call() {
+        let mut generator = EntropyGenerator::new(DEPLOY_HASH, BLOCKTIME, Phase::Session);
+
+        assert_ne!(generator.next_entropy(), generator.next_entropy());
+    }
+
+    #[test]
+    fn should_generate_different_entropy_for_different_inputs() {
+        let entropy = EntropyGenerator::new(DEPLOY_HASH, BLOCKTIME, Phase::Session).next_entropy();
+
+        let other_deploy =
+            EntropyGenerator::new([2u8; 32], BLOCKTIME, Phase::Session).next_entropy();
+        let other_blocktime =
+            EntropyGenerator::new(DEPLOY_HASH, BlockTime(43), Phase::Session).next_entropy();
+        let other_phase =
+            EntropyGenerator::new(DEPLOY_HASH, BLOCKTIME, Phase::Payment).next_entropy();
+
+        assert_ne!(entropy, other_deploy);
+        assert_ne!(entropy, other_blocktime);
+        assert_ne!(entropy, other_phase);
+    }
+}
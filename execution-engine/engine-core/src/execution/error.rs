@@ -42,8 +42,122 @@ pub enum Error {
     SetThresholdFailure(SetThresholdFailure),
     SystemContractError(system_contracts::error::Error),
     DeploymentAuthorizationFailure,
+    TransferAuthorizationFailure,
     ExpectedReturnValue,
     UnexpectedReturnValue,
+    /// Adding a named key would take the account past the given number of named keys.
+    MaxNamedKeysLimit(u32),
+}
+
+/// Revert values of the mint errors start at this value.
+pub const MINT_ERROR_BASE: u32 = 0x2_0000;
+
+/// Revert values of the Proof of Stake errors start at this value.
+pub const POS_ERROR_BASE: u32 = 0x3_0000;
+
+/// A stable code identifying the kind of an [`Error`].
+///
+/// The codes are reported to the node, so the code of an existing variant should never change;
+/// new variants should take unused codes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ErrorCode {
+    Unknown = 0,
+    /// The contract reverted; see [`Error::revert_value`].
+    Revert = 1,
+    GasLimit = 2,
+    ForgedReference = 3,
+    InvalidAccessRights = 4,
+    KeyNotFound = 5,
+    AccountNotFound = 6,
+    TypeMismatch = 7,
+    URefNotFound = 8,
+    FunctionNotFound = 9,
+    ArgIndexOutOfBounds = 10,
+    Interpreter = 11,
+    Storage = 12,
+    Serialization = 13,
+    InvalidWasm = 14,
+    KeyManagementFailure = 15,
+    DeploymentAuthorizationFailure = 16,
+    TransferAuthorizationFailure = 17,
+    InvalidReturnValue = 18,
+    /// The mint failed; the revert value is `MINT_ERROR_BASE` plus the mint error.
+    MintError = 19,
+    /// The Proof of Stake contract reverted; the revert value is `POS_ERROR_BASE` plus its code.
+    ProofOfStakeError = 20,
+    /// The account already holds the most named keys the protocol allows.
+    MaxNamedKeysLimit = 21,
+}
+
+impl Error {
+    /// Returns the error raised by the host while executing a contract, which the interpreter
+    /// wraps in `Error::Interpreter` each time it leaves a contract.
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Error::Interpreter(error) => match error
+                .as_host_error()
+                .and_then(|host_error| host_error.downcast_ref::<Error>())
+            {
+                Some(host_error) => host_error.root_cause(),
+                None => self,
+            },
+            _ => self,
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self.root_cause() {
+            Error::Interpreter(_) | Error::Rng(_) | Error::ResolverError(_) => {
+                ErrorCode::Interpreter
+            }
+            Error::Storage(_) => ErrorCode::Storage,
+            Error::BytesRepr(_) => ErrorCode::Serialization,
+            Error::KeyNotFound(_) => ErrorCode::KeyNotFound,
+            Error::AccountNotFound(_) => ErrorCode::AccountNotFound,
+            Error::TypeMismatch(_) => ErrorCode::TypeMismatch,
+            Error::InvalidAccess { .. } => ErrorCode::InvalidAccessRights,
+            Error::ForgedReference(_) => ErrorCode::ForgedReference,
+            Error::ArgIndexOutOfBounds(_) => ErrorCode::ArgIndexOutOfBounds,
+            Error::URefNotFound(_) => ErrorCode::URefNotFound,
+            Error::FunctionNotFound(_) => ErrorCode::FunctionNotFound,
+            Error::ParityWasm(_) => ErrorCode::InvalidWasm,
+            Error::GasLimit => ErrorCode::GasLimit,
+            Error::Ret(_) => ErrorCode::Unknown,
+            Error::Revert(_) => ErrorCode::Revert,
+            Error::AddKeyFailure(_)
+            | Error::RemoveKeyFailure(_)
+            | Error::UpdateKeyFailure(_)
+            | Error::SetThresholdFailure(_) => ErrorCode::KeyManagementFailure,
+            Error::SystemContractError(system_contracts::error::Error::MintError(_)) => {
+                ErrorCode::MintError
+            }
+            Error::SystemContractError(system_contracts::error::Error::ProofOfStakeError(_)) => {
+                ErrorCode::ProofOfStakeError
+            }
+            Error::DeploymentAuthorizationFailure => ErrorCode::DeploymentAuthorizationFailure,
+            Error::TransferAuthorizationFailure => ErrorCode::TransferAuthorizationFailure,
+            Error::ExpectedReturnValue | Error::UnexpectedReturnValue => {
+                ErrorCode::InvalidReturnValue
+            }
+            Error::MaxNamedKeysLimit(_) => ErrorCode::MaxNamedKeysLimit,
+        }
+    }
+
+    /// Returns the value the contract reverted with, with the errors of the system contracts
+    /// mapped to their dedicated ranges.
+    pub fn revert_value(&self) -> Option<u32> {
+        match self.root_cause() {
+            Error::Revert(status) => Some(*status),
+            Error::SystemContractError(system_contracts::error::Error::MintError(error)) => {
+                Some(MINT_ERROR_BASE + *error as u32)
+            }
+            Error::SystemContractError(system_contracts::error::Error::ProofOfStakeError(
+                status,
+            )) => Some(POS_ERROR_BASE + *status),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -119,3 +233,56 @@ impl From<system_contracts::error::Error> for Error {
         Error::SystemContractError(error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use contract_ffi::key::Key;
+    use contract_ffi::system_contracts;
+    use contract_ffi::system_contracts::mint;
+
+    use super::{Error, ErrorCode, MINT_ERROR_BASE, POS_ERROR_BASE};
+
+    fn wrapped(error: Error) -> Error {
+        Error::Interpreter(wasmi::Error::Trap(error.into()))
+    }
+
+    #[test]
+    fn should_report_revert_value() {
+        let error = Error::Revert(65636);
+        assert_eq!(error.code(), ErrorCode::Revert);
+        assert_eq!(error.revert_value(), Some(65636));
+    }
+
+    #[test]
+    fn should_map_system_contract_errors_into_dedicated_ranges() {
+        let mint_error: Error =
+            system_contracts::error::Error::MintError(mint::error::Error::SourceNotFound).into();
+        assert_eq!(mint_error.code(), ErrorCode::MintError);
+        assert_eq!(mint_error.revert_value(), Some(MINT_ERROR_BASE + 1));
+
+        let pos_error: Error = system_contracts::error::Error::ProofOfStakeError(7).into();
+        assert_eq!(pos_error.code(), ErrorCode::ProofOfStakeError);
+        assert_eq!(pos_error.revert_value(), Some(POS_ERROR_BASE + 7));
+    }
+
+    #[test]
+    fn should_report_code_of_error_raised_in_nested_contract() {
+        let error = wrapped(wrapped(Error::KeyNotFound(Key::Hash([1; 32]))));
+        assert_eq!(error.code(), ErrorCode::KeyNotFound);
+        assert_eq!(error.revert_value(), None);
+
+        let error = wrapped(Error::Revert(3));
+        assert_eq!(error.code(), ErrorCode::Revert);
+        assert_eq!(error.revert_value(), Some(3));
+    }
+
+    #[test]
+    fn should_not_report_revert_value_of_other_errors() {
+        assert_eq!(Error::GasLimit.code(), ErrorCode::GasLimit);
+        assert_eq!(Error::GasLimit.revert_value(), None);
+
+        let error = wrapped(Error::MaxNamedKeysLimit(10));
+        assert_eq!(error.code(), ErrorCode::MaxNamedKeysLimit);
+        assert_eq!(error.revert_value(), None);
+    }
+}
@@ -10,14 +10,17 @@ use contract_ffi::key::Key;
 use contract_ffi::uref::AccessRights;
 use contract_ffi::value::account::{BlockTime, PublicKey};
 use contract_ffi::value::{Account, Value};
-use engine_shared::gas::Gas;
+use engine_shared::gas::{Gas, GasBreakdown, GasCategory};
+use engine_shared::key_limits::KeyLimits;
 use engine_shared::newtypes::CorrelationId;
 use engine_storage::global_state::StateReader;
+use engine_wasm_prep::wasm_costs::WasmCosts;
 
+use crate::engine_state::execution_effect::ExecutionEffect;
 use crate::engine_state::execution_result::ExecutionResult;
 
 use super::Error;
-use super::{extract_access_rights_from_keys, instance_and_memory, Runtime};
+use super::{extract_access_rights_from_keys, instance_and_memory, Runtime, SystemContract};
 use crate::execution::address_generator::AddressGenerator;
 use crate::execution::FN_STORE_ID_INITIAL;
 use crate::runtime_context::RuntimeContext;
@@ -34,8 +37,10 @@ pub trait Executor<A> {
         account: &Account,
         authorized_keys: BTreeSet<PublicKey>,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
+        wasm_costs: WasmCosts,
         protocol_version: u64,
         correlation_id: CorrelationId,
         tc: Rc<RefCell<TrackingCopy<R>>>,
@@ -54,8 +59,34 @@ pub trait Executor<A> {
         account: &Account,
         authorization_keys: BTreeSet<PublicKey>,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
+        wasm_costs: WasmCosts,
+        protocol_version: u64,
+        correlation_id: CorrelationId,
+        state: Rc<RefCell<TrackingCopy<R>>>,
+        phase: Phase,
+    ) -> ExecutionResult
+    where
+        R::Error: Into<Error>;
+
+    /// Like `exec_direct`, but runs the host-side implementation of
+    /// `system_contract` instead of a wasm module.
+    #[allow(clippy::too_many_arguments)]
+    fn exec_system_contract<R: StateReader<Key, Value>>(
+        &self,
+        system_contract: SystemContract,
+        args: &[u8],
+        keys: &mut BTreeMap<String, Key>,
+        base_key: Key,
+        account: &Account,
+        authorization_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        block_height: u64,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        wasm_costs: WasmCosts,
         protocol_version: u64,
         correlation_id: CorrelationId,
         state: Rc<RefCell<TrackingCopy<R>>>,
@@ -74,8 +105,10 @@ pub trait Executor<A> {
         account: &Account,
         authorization_keys: BTreeSet<PublicKey>,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
+        wasm_costs: WasmCosts,
         address_generator: Rc<RefCell<AddressGenerator>>,
         protocol_version: u64,
         correlation_id: CorrelationId,
@@ -87,7 +120,91 @@ pub trait Executor<A> {
         T: FromBytes;
 }
 
-pub struct WasmiExecutor;
+#[derive(Debug, Copy, Clone)]
+pub struct WasmiExecutor {
+    use_system_contracts: bool,
+    key_limits: KeyLimits,
+}
+
+impl WasmiExecutor {
+    /// Creates an executor which runs calls to the mint and the Proof of Stake
+    /// contract natively unless `use_system_contracts` is set.
+    pub fn new(use_system_contracts: bool) -> Self {
+        WasmiExecutor {
+            use_system_contracts,
+            key_limits: KeyLimits::default(),
+        }
+    }
+
+    /// Sets the limits on the keys accounts may hold while contracts run.
+    pub fn set_key_limits(mut self, key_limits: KeyLimits) -> Self {
+        self.key_limits = key_limits;
+        self
+    }
+}
+
+impl Default for WasmiExecutor {
+    fn default() -> Self {
+        WasmiExecutor::new(true)
+    }
+}
+
+/// Turns the outcome of a contract run by `exec_direct` into an `ExecutionResult`.
+fn direct_execution_result<R: StateReader<Key, Value>>(
+    result: Result<(), wasmi::Error>,
+    runtime: &Runtime<R>,
+    effects_snapshot: ExecutionEffect,
+) -> ExecutionResult
+where
+    R::Error: Into<Error>,
+{
+    match result {
+        Ok(_) => ExecutionResult::Success {
+            effect: runtime.context().effect(),
+            cost: runtime.context().gas_counter(),
+            cost_breakdown: runtime.context().gas_breakdown(),
+        },
+        Err(e) => {
+            if let Some(host_error) = e.as_host_error() {
+                // `ret` Trap is a success; downcast and attempt to extract result
+                let downcasted_error = host_error.downcast_ref::<Error>().unwrap();
+                match downcasted_error {
+                    Error::Ret(ref _ret_urefs) => {
+                        // NOTE: currently, ExecutionResult does not include runtime.result or
+                        // extra urefs  and thus we cannot get back
+                        // a value from the executed contract...
+                        // TODO?: add ability to include extra_urefs and runtime.result to
+                        // ExecutionResult::Success
+
+                        return ExecutionResult::Success {
+                            effect: runtime.context().effect(),
+                            cost: runtime.context().gas_counter(),
+                            cost_breakdown: runtime.context().gas_breakdown(),
+                        };
+                    }
+                    Error::Revert(status) => {
+                        // Propagate revert as revert, instead of passing it as
+                        // InterpreterError.
+                        return ExecutionResult::Failure {
+                            error: Error::Revert(*status).into(),
+                            effect: effects_snapshot,
+                            cost: runtime.context().gas_counter(),
+                            cost_breakdown: runtime.context().gas_breakdown(),
+                        };
+                    }
+                    _ => {}
+                }
+            }
+
+            ExecutionResult::Failure {
+                error: Error::Interpreter(e).into(),
+                effect: effects_snapshot,
+                cost: runtime.context().gas_counter(),
+                cost_breakdown: runtime.context().gas_breakdown(),
+            }
+        }
+    }
+}
 
 macro_rules! on_fail_charge {
     ($fn:expr) => {
@@ -104,10 +221,12 @@ macro_rules! on_fail_charge {
             Ok(res) => res,
             Err(e) => {
                 let exec_err: crate::execution::Error = e.into();
+                let cost_breakdown: engine_shared::gas::GasBreakdown = $cost;
                 return ExecutionResult::Failure {
                     error: exec_err.into(),
                     effect: Default::default(),
-                    cost: $cost,
+                    cost: cost_breakdown.total(),
+                    cost_breakdown,
                 };
             }
         }
@@ -117,10 +236,12 @@ macro_rules! on_fail_charge {
             Ok(res) => res,
             Err(e) => {
                 let exec_err: crate::execution::Error = e.into();
+                let cost_breakdown: engine_shared::gas::GasBreakdown = $cost;
                 return ExecutionResult::Failure {
                     error: exec_err.into(),
                     effect: $effect,
-                    cost: $cost,
+                    cost: cost_breakdown.total(),
+                    cost_breakdown,
                 };
             }
         }
@@ -136,8 +257,10 @@ impl Executor<Module> for WasmiExecutor {
         account: &Account,
         authorized_keys: BTreeSet<PublicKey>,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
+        wasm_costs: WasmCosts,
         protocol_version: u64,
         correlation_id: CorrelationId,
         tc: Rc<RefCell<TrackingCopy<R>>>,
@@ -166,12 +289,12 @@ impl Executor<Module> for WasmiExecutor {
             // https://casperlabs.atlassian.net/browse/EE-239
             on_fail_charge!(
                 bytesrepr::deserialize(args),
-                Gas::from_u64(args.len() as u64),
+                GasBreakdown::new(GasCategory::HostCalls, Gas::from_u64(args.len() as u64)),
                 effects_snapshot
             )
         };
 
-        let context = RuntimeContext::new(
+        let mut context = RuntimeContext::new(
             tc,
             &mut uref_lookup_local,
             known_urefs,
@@ -180,6 +303,7 @@ impl Executor<Module> for WasmiExecutor {
             &account,
             base_key,
             blocktime,
+            block_height,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -189,17 +313,25 @@ impl Executor<Module> for WasmiExecutor {
             correlation_id,
             phase,
         );
-
-        let mut runtime = Runtime::new(memory, parity_module, context);
+        context.set_key_limits(self.key_limits);
+
+        let mut runtime = Runtime::new(
+            memory,
+            parity_module,
+            wasm_costs,
+            context,
+            self.use_system_contracts,
+        );
         on_fail_charge!(
             instance.invoke_export("call", &[], &mut runtime),
-            runtime.context().gas_counter(),
+            runtime.context().gas_breakdown(),
             effects_snapshot
         );
 
         ExecutionResult::Success {
             effect: runtime.context().effect(),
             cost: runtime.context().gas_counter(),
+            cost_breakdown: runtime.context().gas_breakdown(),
         }
     }
 
@@ -212,8 +344,10 @@ impl Executor<Module> for WasmiExecutor {
         account: &Account,
         authorization_keys: BTreeSet<PublicKey>,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
+        wasm_costs: WasmCosts,
         protocol_version: u64,
         correlation_id: CorrelationId,
         state: Rc<RefCell<TrackingCopy<R>>>,
@@ -241,12 +375,12 @@ impl Executor<Module> for WasmiExecutor {
         } else {
             on_fail_charge!(
                 bytesrepr::deserialize(args),
-                Gas::from_u64(args.len() as u64),
+                GasBreakdown::new(GasCategory::HostCalls, Gas::from_u64(args.len() as u64)),
                 effects_snapshot
             )
         };
 
-        let context = RuntimeContext::new(
+        let mut context = RuntimeContext::new(
             state,
             &mut uref_lookup,
             known_urefs,
@@ -255,6 +389,7 @@ impl Executor<Module> for WasmiExecutor {
             &account,
             base_key,
             blocktime,
+            block_height,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -264,54 +399,97 @@ impl Executor<Module> for WasmiExecutor {
             correlation_id,
             phase,
         );
+        context.set_key_limits(self.key_limits);
 
         let (instance, memory) =
             on_fail_charge!(instance_and_memory(parity_module.clone(), protocol_version));
 
-        let mut runtime = Runtime::new(memory, parity_module, context);
+        let mut runtime = Runtime::new(
+            memory,
+            parity_module,
+            wasm_costs,
+            context,
+            self.use_system_contracts,
+        );
 
-        match instance.invoke_export("call", &[], &mut runtime) {
-            Ok(_) => ExecutionResult::Success {
-                effect: runtime.context().effect(),
-                cost: runtime.context().gas_counter(),
-            },
-            Err(e) => {
-                if let Some(host_error) = e.as_host_error() {
-                    // `ret` Trap is a success; downcast and attempt to extract result
-                    let downcasted_error = host_error.downcast_ref::<Error>().unwrap();
-                    match downcasted_error {
-                        Error::Ret(ref _ret_urefs) => {
-                            // NOTE: currently, ExecutionResult does not include runtime.result or
-                            // extra urefs  and thus we cannot get back
-                            // a value from the executed contract...
-                            // TODO?: add ability to include extra_urefs and runtime.result to
-                            // ExecutionResult::Success
-
-                            return ExecutionResult::Success {
-                                effect: runtime.context().effect(),
-                                cost: runtime.context().gas_counter(),
-                            };
-                        }
-                        Error::Revert(status) => {
-                            // Propagate revert as revert, instead of passing it as
-                            // InterpreterError.
-                            return ExecutionResult::Failure {
-                                error: Error::Revert(*status).into(),
-                                effect: effects_snapshot,
-                                cost: runtime.context().gas_counter(),
-                            };
-                        }
-                        _ => {}
-                    }
-                }
+        let result = instance
+            .invoke_export("call", &[], &mut runtime)
+            .map(|_| ());
 
-                ExecutionResult::Failure {
-                    error: Error::Interpreter(e).into(),
-                    effect: effects_snapshot,
-                    cost: runtime.context().gas_counter(),
-                }
-            }
-        }
+        direct_execution_result(result, &runtime, effects_snapshot)
+    }
+
+    fn exec_system_contract<R: StateReader<Key, Value>>(
+        &self,
+        system_contract: SystemContract,
+        args: &[u8],
+        keys: &mut BTreeMap<String, Key>,
+        base_key: Key,
+        account: &Account,
+        authorization_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        block_height: u64,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        wasm_costs: WasmCosts,
+        protocol_version: u64,
+        correlation_id: CorrelationId,
+        state: Rc<RefCell<TrackingCopy<R>>>,
+        phase: Phase,
+    ) -> ExecutionResult
+    where
+        R::Error: Into<Error>,
+    {
+        let mut uref_lookup = keys.clone();
+        let known_urefs: HashMap<Address, HashSet<AccessRights>> =
+            extract_access_rights_from_keys(uref_lookup.values().cloned());
+
+        let address_generator = {
+            let address_generator = AddressGenerator::new(deploy_hash, phase);
+            Rc::new(RefCell::new(address_generator))
+        };
+        let gas_counter = Gas::default();
+
+        let effects_snapshot = state.borrow().effect();
+
+        let args: Vec<Vec<u8>> = if args.is_empty() {
+            Vec::new()
+        } else {
+            on_fail_charge!(
+                bytesrepr::deserialize(args),
+                GasBreakdown::new(GasCategory::HostCalls, Gas::from_u64(args.len() as u64)),
+                effects_snapshot
+            )
+        };
+
+        let mut context = RuntimeContext::new(
+            state,
+            &mut uref_lookup,
+            known_urefs,
+            args,
+            authorization_keys,
+            &account,
+            base_key,
+            blocktime,
+            block_height,
+            deploy_hash,
+            gas_limit,
+            gas_counter,
+            FN_STORE_ID_INITIAL,
+            address_generator,
+            protocol_version,
+            correlation_id,
+            phase,
+        );
+        context.set_key_limits(self.key_limits);
+
+        let mut runtime = on_fail_charge!(Runtime::new_native(wasm_costs, context));
+
+        let result = runtime
+            .call_system_contract(system_contract)
+            .map_err(wasmi::Error::Trap);
+
+        direct_execution_result(result, &runtime, effects_snapshot)
     }
 
     fn better_exec<R: StateReader<Key, Value>, T>(
@@ -323,8 +501,10 @@ impl Executor<Module> for WasmiExecutor {
         account: &Account,
         authorization_keys: BTreeSet<PublicKey>,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
+        wasm_costs: WasmCosts,
         address_generator: Rc<RefCell<AddressGenerator>>,
         protocol_version: u64,
         correlation_id: CorrelationId,
@@ -345,7 +525,7 @@ impl Executor<Module> for WasmiExecutor {
 
         let gas_counter = Gas::default();
 
-        let runtime_context = RuntimeContext::new(
+        let mut runtime_context = RuntimeContext::new(
             state,
             keys,
             known_keys.clone(),
@@ -354,6 +534,7 @@ impl Executor<Module> for WasmiExecutor {
             account,
             base_key,
             blocktime,
+            block_height,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -363,10 +544,17 @@ impl Executor<Module> for WasmiExecutor {
             correlation_id,
             phase,
         );
+        runtime_context.set_key_limits(self.key_limits);
 
         let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
 
-        let mut runtime = Runtime::new(memory, module, runtime_context);
+        let mut runtime = Runtime::new(
+            memory,
+            module,
+            wasm_costs,
+            runtime_context,
+            self.use_system_contracts,
+        );
 
         let return_error: wasmi::Error = match instance.invoke_export("call", &[], &mut runtime) {
             Err(error) => error,
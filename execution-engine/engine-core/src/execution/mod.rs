@@ -1,16 +1,20 @@
 mod address_generator;
+mod entropy_generator;
 mod error;
 #[macro_use]
 mod executor;
 mod runtime;
 #[cfg(test)]
 mod tests;
+pub mod trace;
 
 pub use self::address_generator::AddressGenerator;
-pub use self::error::Error;
+pub use self::entropy_generator::EntropyGenerator;
+pub use self::error::{Error, ErrorCode, MINT_ERROR_BASE, POS_ERROR_BASE};
 pub use self::executor::{Executor, WasmiExecutor};
 pub use self::runtime::{
-    extract_access_rights_from_keys, extract_access_rights_from_urefs, instance_and_memory, Runtime,
+    extract_access_rights_from_keys, extract_access_rights_from_urefs, instance_and_memory,
+    Runtime, SystemContract,
 };
 
 pub const MINT_NAME: &str = "mint";
@@ -0,0 +1,115 @@
+//! Host-side implementations of the cryptographic primitives exposed to contracts by
+//! `contract_api::crypto`.
+
+use ed25519_dalek::{PublicKey as Ed25519PublicKey, Signature};
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, Secp256k1};
+use sha2::{Digest, Sha256};
+
+use contract_ffi::contract_api::crypto::DIGEST_LENGTH;
+use contract_ffi::value::account::SECP256K1_PUBLIC_KEY_LENGTH;
+use engine_shared::newtypes::Blake2bHash;
+
+pub fn blake2b256(data: &[u8]) -> [u8; DIGEST_LENGTH] {
+    Blake2bHash::new(data).into()
+}
+
+pub fn sha256(data: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut digest = [0u8; DIGEST_LENGTH];
+    digest.copy_from_slice(&Sha256::digest(data));
+    digest
+}
+
+/// Returns `true` if `signature` is a valid Ed25519 signature of `message` by `public_key`.
+/// Malformed keys and signatures are never valid.
+pub fn ed25519_verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let public_key = match Ed25519PublicKey::from_bytes(public_key) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    let signature = match Signature::from_bytes(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    public_key.verify(message, &signature).is_ok()
+}
+
+/// Recovers the compressed Secp256k1 public key which made the compact `signature` of
+/// `message_hash`, or returns `None` if the signature or recovery id is malformed.
+pub fn secp256k1_recover(
+    message_hash: &[u8],
+    signature: &[u8],
+    recovery_id: i32,
+) -> Option<[u8; SECP256K1_PUBLIC_KEY_LENGTH]> {
+    let message = Message::from_slice(message_hash).ok()?;
+    let recovery_id = RecoveryId::from_i32(recovery_id).ok()?;
+    let signature = RecoverableSignature::from_compact(signature, recovery_id).ok()?;
+    let public_key = Secp256k1::verification_only()
+        .recover(&message, &signature)
+        .ok()?;
+    Some(public_key.serialize())
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Keypair, PublicKey as Ed25519PublicKey, SecretKey};
+    use secp256k1::{Message, PublicKey, Secp256k1, SecretKey as Secp256k1SecretKey};
+
+    use contract_ffi::base16;
+
+    const MESSAGE: &[u8] = b"abc";
+
+    #[test]
+    fn should_compute_known_digests() {
+        assert_eq!(
+            base16::encode_lower(&super::blake2b256(&[])),
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+        assert_eq!(
+            base16::encode_lower(&super::sha256(MESSAGE)),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn should_verify_ed25519_signature() {
+        let secret = SecretKey::from_bytes(&[1u8; 32]).unwrap();
+        let public = Ed25519PublicKey::from(&secret);
+        let keypair = Keypair { secret, public };
+        let signature = keypair.sign(MESSAGE).to_bytes();
+
+        assert!(super::ed25519_verify(
+            public.as_bytes(),
+            MESSAGE,
+            &signature
+        ));
+        assert!(!super::ed25519_verify(
+            public.as_bytes(),
+            b"abd",
+            &signature
+        ));
+        assert!(!super::ed25519_verify(
+            public.as_bytes(),
+            MESSAGE,
+            &signature[1..]
+        ));
+    }
+
+    #[test]
+    fn should_recover_secp256k1_public_key() {
+        let secp = Secp256k1::new();
+        let secret_key = Secp256k1SecretKey::from_slice(&[1u8; 32]).unwrap();
+        let message_hash = super::sha256(MESSAGE);
+        let message = Message::from_slice(&message_hash).unwrap();
+        let (recovery_id, signature) = secp
+            .sign_recoverable(&message, &secret_key)
+            .serialize_compact();
+        let expected = PublicKey::from_secret_key(&secp, &secret_key).serialize();
+
+        assert_eq!(
+            super::secp256k1_recover(&message_hash, &signature, recovery_id.to_i32()),
+            Some(expected)
+        );
+        assert_eq!(super::secp256k1_recover(&message_hash, &signature, 4), None);
+    }
+}
@@ -12,6 +12,7 @@ use engine_storage::global_state::StateReader;
 
 use super::args::Args;
 use super::{Error, Runtime};
+use crate::execution::trace;
 use crate::resolvers::v1_function_index::FunctionIndex;
 
 impl<'a, R: StateReader<Key, Value>> Externals for Runtime<'a, R>
@@ -24,6 +25,42 @@ where
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
+
+        // The gas metering injected into every contract is not a call made by the contract
+        if func == FunctionIndex::GasFuncIndex {
+            return self.invoke_host_function(func, args);
+        }
+
+        let traced_call = trace::begin_call(func.name(), || {
+            args.as_ref()
+                .iter()
+                .map(|arg| format!("{:?}", arg))
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+        let gas_before = self.context.gas_counter();
+        let result = self.invoke_host_function(func, args);
+        if let Some(traced_call) = traced_call {
+            let gas = self
+                .context
+                .gas_counter()
+                .checked_sub(gas_before)
+                .unwrap_or_default();
+            trace::end_call(traced_call, gas);
+        }
+        result
+    }
+}
+
+impl<'a, R: StateReader<Key, Value>> Runtime<'a, R>
+where
+    R::Error: Into<Error>,
+{
+    fn invoke_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
         match func {
             FunctionIndex::ReadFuncIndex => {
                 // args(0) = pointer to key in Wasm memory
@@ -223,6 +260,13 @@ where
                 Ok(None)
             }
 
+            FunctionIndex::GetBlockHeightIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.get_block_height(dest_ptr)?;
+                Ok(None)
+            }
+
             FunctionIndex::GasFuncIndex => {
                 let gas: u32 = Args::parse(args)?;
                 self.gas(Gas::from_u64(gas.into()))?;
@@ -417,6 +461,147 @@ where
                 self.get_phase(dest_ptr)?;
                 Ok(None)
             }
+
+            FunctionIndex::EmitEventIndex => {
+                // args(0) = pointer to serialized topic in Wasm memory
+                // args(1) = size of serialized topic
+                // args(2) = pointer to payload in Wasm memory
+                // args(3) = size of payload
+                let (topic_ptr, topic_size, payload_ptr, payload_size) = Args::parse(args)?;
+                self.emit_event(topic_ptr, topic_size, payload_ptr, payload_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::AddContractVersionIndex => {
+                // args(0) = pointer to the key of the contract package in Wasm memory
+                // args(1) = size of the key
+                // args(2) = pointer to function name in Wasm memory
+                // args(3) = size of the name
+                // args(4) = pointer to additional unforgable names
+                //           to be saved with the function body
+                // args(5) = size of the additional unforgable names
+                let (package_ptr, package_size, name_ptr, name_size, urefs_ptr, urefs_size) =
+                    Args::parse(args)?;
+                let version = self.add_contract_version(
+                    package_ptr,
+                    package_size,
+                    name_ptr,
+                    name_size,
+                    urefs_ptr,
+                    urefs_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(version as i32)))
+            }
+
+            FunctionIndex::CallContractWithNamedArgsIndex => {
+                // args(0) = pointer to key where contract is at in global state
+                // args(1) = size of key
+                // args(2) = pointer to serialized named arguments in Wasm memory
+                // args(3) = size of named arguments
+                // args(4) = pointer to extra supplied urefs
+                // args(5) = size of extra urefs
+                let (key_ptr, key_size, args_ptr, args_size, extra_urefs_ptr, extra_urefs_size) =
+                    Args::parse(args)?;
+
+                // We have to explicitly tell rustc what type we expect as it cannot infer it
+                // otherwise.
+                let _args_size_u32: u32 = args_size;
+                let _extra_urefs_size_u32: u32 = extra_urefs_size;
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+                let urefs_bytes =
+                    self.bytes_from_mem(extra_urefs_ptr, extra_urefs_size as usize)?;
+
+                let size =
+                    self.call_contract_with_named_args(key_contract, args_bytes, urefs_bytes)?;
+                Ok(Some(RuntimeValue::I32(size as i32)))
+            }
+
+            FunctionIndex::LoadNamedArgIndex => {
+                // args(0) = pointer to the name of the host runtime arg to load
+                // args(1) = size of the name
+                let (name_ptr, name_size) = Args::parse(args)?;
+                let size = self.load_named_arg(name_ptr, name_size)?;
+                Ok(Some(RuntimeValue::I32(size)))
+            }
+
+            FunctionIndex::DictionaryGetIndex => {
+                // args(0) = pointer to the dictionary seed in Wasm memory
+                // args(1) = size of the seed
+                // args(2) = pointer to the item key in Wasm memory
+                // args(3) = size of the item key
+                let (seed_ptr, seed_size, item_key_ptr, item_key_size) = Args::parse(args)?;
+                let size = self.dictionary_get(seed_ptr, seed_size, item_key_ptr, item_key_size)?;
+                Ok(Some(RuntimeValue::I32(size as i32)))
+            }
+
+            FunctionIndex::DictionaryPutIndex => {
+                // args(0) = pointer to the dictionary seed in Wasm memory
+                // args(1) = size of the seed
+                // args(2) = pointer to the item key in Wasm memory
+                // args(3) = size of the item key
+                // args(4) = pointer to value
+                // args(5) = size of value
+                let (seed_ptr, seed_size, item_key_ptr, item_key_size, value_ptr, value_size) =
+                    Args::parse(args)?;
+                self.dictionary_put(
+                    seed_ptr,
+                    seed_size,
+                    item_key_ptr,
+                    item_key_size,
+                    value_ptr,
+                    value_size,
+                )?;
+                Ok(None)
+            }
+
+            FunctionIndex::Blake2b256Index => {
+                // args(0) = pointer to the data in Wasm memory
+                // args(1) = size of the data
+                // args(2) = pointer to the output buffer for the digest
+                let (data_ptr, data_size, dest_ptr) = Args::parse(args)?;
+                self.blake2b256(data_ptr, data_size, dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::Sha256Index => {
+                // args(0) = pointer to the data in Wasm memory
+                // args(1) = size of the data
+                // args(2) = pointer to the output buffer for the digest
+                let (data_ptr, data_size, dest_ptr) = Args::parse(args)?;
+                self.sha256(data_ptr, data_size, dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::Ed25519VerifyIndex => {
+                // args(0) = pointer to the public key in Wasm memory
+                // args(1) = pointer to the message in Wasm memory
+                // args(2) = size of the message
+                // args(3) = pointer to the signature in Wasm memory
+                let (public_key_ptr, message_ptr, message_size, signature_ptr) = Args::parse(args)?;
+                let valid =
+                    self.ed25519_verify(public_key_ptr, message_ptr, message_size, signature_ptr)?;
+                Ok(Some(RuntimeValue::I32(valid as i32)))
+            }
+
+            FunctionIndex::Secp256k1RecoverIndex => {
+                // args(0) = pointer to the message hash in Wasm memory
+                // args(1) = pointer to the signature in Wasm memory
+                // args(2) = recovery id
+                // args(3) = pointer to the output buffer for the public key
+                let (message_hash_ptr, signature_ptr, recovery_id, dest_ptr) = Args::parse(args)?;
+                let recovered =
+                    self.secp256k1_recover(message_hash_ptr, signature_ptr, recovery_id, dest_ptr)?;
+                Ok(Some(RuntimeValue::I32(recovered as i32)))
+            }
+
+            FunctionIndex::GetEntropyIndex => {
+                // args(0) = pointer to Wasm memory where to write the 32-byte seed
+                let dest_ptr = Args::parse(args)?;
+                self.get_entropy(dest_ptr)?;
+                Ok(None)
+            }
         }
     }
 }
@@ -1,5 +1,7 @@
 mod args;
+mod crypto;
 mod externals;
+mod native;
 
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
@@ -9,16 +11,23 @@ use itertools::Itertools;
 use parity_wasm::elements::Module;
 use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
 
-use contract_ffi::bytesrepr::{deserialize, ToBytes, U32_SIZE};
+use contract_ffi::bytesrepr::{self, deserialize, ToBytes, U32_SIZE, U8_SIZE};
 use contract_ffi::contract_api::argsparser::ArgsParser;
-use contract_ffi::contract_api::{PurseTransferResult, TransferResult};
+use contract_ffi::contract_api::crypto::{
+    DIGEST_LENGTH, ED25519_SIGNATURE_LENGTH, SECP256K1_SIGNATURE_LENGTH,
+};
+use contract_ffi::contract_api::runtime_args::RuntimeArgs;
+use contract_ffi::contract_api::{PurseTransferResult, TransferFailure, TransferResult};
 use contract_ffi::key::Key;
 use contract_ffi::system_contracts::{self, mint};
 use contract_ffi::uref::{AccessRights, URef};
-use contract_ffi::value::account::{ActionType, PublicKey, PurseId, Weight, PUBLIC_KEY_SIZE};
-use contract_ffi::value::{Account, Value, U512};
-use engine_shared::gas::Gas;
+use contract_ffi::value::account::{
+    ActionType, PublicKey, PurseId, Weight, ED25519_PUBLIC_KEY_LENGTH,
+};
+use contract_ffi::value::{Account, ContractPackage, Value, U512};
+use engine_shared::gas::{Gas, GasCategory};
 use engine_storage::global_state::StateReader;
+use engine_wasm_prep::wasm_costs::WasmCosts;
 
 use super::{Error, MINT_NAME, POS_NAME};
 use crate::execution::Error::{KeyNotFound, URefNotFound};
@@ -27,12 +36,16 @@ use crate::resolvers::memory_resolver::MemoryResolver;
 use crate::runtime_context::RuntimeContext;
 use crate::Address;
 
+pub use self::native::SystemContract;
+
 pub struct Runtime<'a, R> {
     memory: MemoryRef,
     module: Module,
     result: Vec<u8>,
     host_buf: Vec<u8>,
+    wasm_costs: WasmCosts,
     context: RuntimeContext<'a, R>,
+    use_system_contracts: bool,
 }
 
 /// Rename function called `name` in the `module` to `call`.
@@ -121,53 +134,68 @@ pub fn extract_access_rights_from_keys<I: IntoIterator<Item = Key>>(
         .collect()
 }
 
-fn sub_call<R: StateReader<Key, Value>>(
-    parity_module: Module,
+/// Describes why a transfer through the mint failed.
+fn transfer_failure(error: Error) -> TransferFailure {
+    match error {
+        Error::SystemContractError(system_contracts::error::Error::MintError(error)) => {
+            error.into()
+        }
+        Error::ForgedReference(_) => TransferFailure::ForgedReference,
+        _ => TransferFailure::Other,
+    }
+}
+
+/// Creates the context in which a contract called from `current_runtime` runs.
+fn sub_call_context<'b, R: StateReader<Key, Value>>(
     args: Vec<Vec<u8>>,
-    refs: &mut BTreeMap<String, Key>,
+    refs: &'b mut BTreeMap<String, Key>,
     key: Key,
-    current_runtime: &mut Runtime<R>,
+    current_runtime: &Runtime<'b, R>,
     // Unforgable references passed across the call boundary from caller to callee
     //(necessary if the contract takes a uref argument).
     extra_urefs: Vec<Key>,
     protocol_version: u64,
-) -> Result<Vec<u8>, Error>
+) -> RuntimeContext<'b, R>
 where
     R::Error: Into<Error>,
 {
-    let (instance, memory) = instance_and_memory(parity_module.clone(), protocol_version)?;
-
     let known_urefs = extract_access_rights_from_keys(refs.values().cloned().chain(extra_urefs));
 
-    let mut runtime = Runtime {
-        memory,
-        module: parity_module,
-        result: Vec::new(),
-        host_buf: Vec::new(),
-        context: RuntimeContext::new(
-            current_runtime.context.state(),
-            refs,
-            known_urefs,
-            args,
-            current_runtime.context.authorization_keys().clone(),
-            &current_runtime.context.account(),
-            key,
-            current_runtime.context.get_blocktime(),
-            current_runtime.context.get_deployhash(),
-            current_runtime.context.gas_limit(),
-            current_runtime.context.gas_counter(),
-            current_runtime.context.fn_store_id(),
-            current_runtime.context.address_generator(),
-            protocol_version,
-            current_runtime.context.correlation_id(),
-            current_runtime.context.phase(),
-        ),
-    };
-
-    let result = instance.invoke_export("call", &[], &mut runtime);
+    let mut context = RuntimeContext::new(
+        current_runtime.context.state(),
+        refs,
+        known_urefs,
+        args,
+        current_runtime.context.authorization_keys().clone(),
+        current_runtime.context.account(),
+        key,
+        current_runtime.context.get_blocktime(),
+        current_runtime.context.get_block_height(),
+        current_runtime.context.get_deployhash(),
+        current_runtime.context.gas_limit(),
+        current_runtime.context.gas_counter(),
+        current_runtime.context.fn_store_id(),
+        current_runtime.context.address_generator(),
+        protocol_version,
+        current_runtime.context.correlation_id(),
+        current_runtime.context.phase(),
+    );
+    context.set_entropy_generator(current_runtime.context.entropy_generator());
+    context.set_key_limits(current_runtime.context.key_limits());
+    context
+}
 
+/// Turns the outcome of a called contract into the bytes it returned.
+fn sub_call_result<R: StateReader<Key, Value>>(
+    result: Result<(), wasmi::Error>,
+    callee_result: Vec<u8>,
+    current_runtime: &mut Runtime<R>,
+) -> Result<Vec<u8>, Error>
+where
+    R::Error: Into<Error>,
+{
     match result {
-        Ok(_) => Ok(runtime.result),
+        Ok(_) => Ok(callee_result),
         Err(e) => {
             if let Some(host_error) = e.as_host_error() {
                 // If the "error" was in fact a trap caused by calling `ret` then
@@ -180,7 +208,7 @@ where
                         let ret_urefs_map: HashMap<Address, HashSet<AccessRights>> =
                             extract_access_rights_from_urefs(ret_urefs.clone());
                         current_runtime.context.add_urefs(ret_urefs_map);
-                        return Ok(runtime.result);
+                        return Ok(callee_result);
                     }
                     Error::Revert(status) => {
                         // Propagate revert as revert, instead of passing it as
@@ -195,18 +223,66 @@ where
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn sub_call<R: StateReader<Key, Value>>(
+    parity_module: Module,
+    args: Vec<Vec<u8>>,
+    named_args: RuntimeArgs,
+    refs: &mut BTreeMap<String, Key>,
+    key: Key,
+    current_runtime: &mut Runtime<R>,
+    extra_urefs: Vec<Key>,
+    protocol_version: u64,
+) -> Result<Vec<u8>, Error>
+where
+    R::Error: Into<Error>,
+{
+    let (instance, memory) = instance_and_memory(parity_module.clone(), protocol_version)?;
+
+    let mut context = sub_call_context(
+        args,
+        refs,
+        key,
+        current_runtime,
+        extra_urefs,
+        protocol_version,
+    );
+    context.set_named_args(named_args);
+    let mut runtime = Runtime::new(
+        memory,
+        parity_module,
+        current_runtime.wasm_costs,
+        context,
+        current_runtime.use_system_contracts,
+    );
+
+    let result = instance
+        .invoke_export("call", &[], &mut runtime)
+        .map(|_| ());
+
+    sub_call_result(result, runtime.result, current_runtime)
+}
+
 impl<'a, R: StateReader<Key, Value>> Runtime<'a, R>
 where
     R::Error: Into<Error>,
 {
     #[allow(clippy::too_many_arguments)]
-    pub fn new(memory: MemoryRef, module: Module, context: RuntimeContext<'a, R>) -> Self {
+    pub fn new(
+        memory: MemoryRef,
+        module: Module,
+        wasm_costs: WasmCosts,
+        context: RuntimeContext<'a, R>,
+        use_system_contracts: bool,
+    ) -> Self {
         Runtime {
             memory,
             module,
             result: Vec::new(),
             host_buf: Vec::new(),
+            wasm_costs,
             context,
+            use_system_contracts,
         }
     }
 
@@ -218,12 +294,12 @@ where
         &self.context
     }
 
-    /// Charge specified amount of gas
+    /// Charge specified amount of gas, attributing it to `category`
     ///
     /// Returns false if gas limit exceeded and true if not.
     /// Intuition about the return value sense is to aswer the question 'are we
     /// allowed to continue?'
-    fn charge_gas(&mut self, amount: Gas) -> bool {
+    fn charge_gas(&mut self, category: GasCategory, amount: Gas) -> bool {
         let prev = self.context.gas_counter();
         match prev.checked_add(amount) {
             // gas charge overflow protection
@@ -231,19 +307,43 @@ where
             Some(val) if val > self.context.gas_limit() => false,
             Some(val) => {
                 self.context.set_gas_counter(val);
+                self.context.add_gas_breakdown(category, amount);
                 true
             }
         }
     }
 
     fn gas(&mut self, amount: Gas) -> Result<(), Trap> {
-        if self.charge_gas(amount) {
+        if self.charge_gas(GasCategory::Opcodes, amount) {
             Ok(())
         } else {
             Err(Error::GasLimit.into())
         }
     }
 
+    /// Charges for writing `num_bytes` bytes to global state at the `storage_byte_cost` of the
+    /// current wasm costs.
+    fn charge_storage_bytes(&mut self, num_bytes: usize) -> Result<(), Error> {
+        let amount = u64::from(self.wasm_costs.storage_byte_cost)
+            .checked_mul(num_bytes as u64)
+            .map(Gas::from_u64)
+            .ok_or(Error::GasLimit)?;
+        if self.charge_gas(GasCategory::StorageWrites, amount) {
+            Ok(())
+        } else {
+            Err(Error::GasLimit)
+        }
+    }
+
+    /// Charges the per-call `cost` of a host function.
+    fn charge_host_call(&mut self, cost: u32) -> Result<(), Error> {
+        if self.charge_gas(GasCategory::HostCalls, Gas::from_u64(cost.into())) {
+            Ok(())
+        } else {
+            Err(Error::GasLimit)
+        }
+    }
+
     fn bytes_from_mem(&self, ptr: u32, size: usize) -> Result<Vec<u8>, Error> {
         self.memory.get(ptr, size).map_err(Into::into)
     }
@@ -254,6 +354,29 @@ where
         deserialize(&bytes).map_err(Into::into)
     }
 
+    fn uref_from_mem(&self, uref_ptr: u32, uref_size: u32) -> Result<URef, Error> {
+        let bytes = self.bytes_from_mem(uref_ptr, uref_size as usize)?;
+        deserialize(&bytes).map_err(Into::into)
+    }
+
+    /// Reads a serialized public key from Wasm memory.  The length of the key
+    /// depends on its encoding and algorithm, so the length prefix is read
+    /// first, followed by the algorithm tag if the key is tagged.
+    fn public_key_from_mem(&self, public_key_ptr: u32) -> Result<PublicKey, Error> {
+        let prefix: u32 = {
+            let bytes = self.bytes_from_mem(public_key_ptr, U32_SIZE)?;
+            deserialize(&bytes).map_err(Error::BytesRepr)?
+        };
+        let size = PublicKey::serialized_length(prefix, || {
+            self.bytes_from_mem(public_key_ptr, U32_SIZE + U8_SIZE)
+                .ok()
+                .map(|bytes| bytes[U32_SIZE])
+        })
+        .ok_or(Error::BytesRepr(bytesrepr::Error::FormattingError))?;
+        let bytes = self.bytes_from_mem(public_key_ptr, size)?;
+        deserialize(&bytes).map_err(Error::BytesRepr)
+    }
+
     /// Reads value (defined as `value_ptr` and `value_size` tuple) from Wasm
     /// memory.
     fn value_from_mem(&mut self, value_ptr: u32, value_size: u32) -> Result<Value, Error> {
@@ -355,6 +478,21 @@ where
         Ok(())
     }
 
+    /// Records an event with the topic at [topic_ptr] and the raw payload at
+    /// [payload_ptr] in the Wasm memory.
+    fn emit_event(
+        &mut self,
+        topic_ptr: u32,
+        topic_size: u32,
+        payload_ptr: u32,
+        payload_size: u32,
+    ) -> Result<(), Trap> {
+        let topic = self.string_from_mem(topic_ptr, topic_size)?;
+        let payload = self.bytes_from_mem(payload_ptr, payload_size as usize)?;
+        self.context.emit_event(topic, payload);
+        Ok(())
+    }
+
     /// Writes caller (deploy) account public key to [dest_ptr] in the Wasm
     /// memory.
     fn get_caller(&mut self, dest_ptr: u32) -> Result<(), Trap> {
@@ -386,6 +524,26 @@ where
             .map_err(|e| Error::Interpreter(e).into())
     }
 
+    /// Writes current block height to [dest_ptr] in Wasm memory.
+    fn get_block_height(&self, dest_ptr: u32) -> Result<(), Trap> {
+        let block_height = self
+            .context
+            .get_block_height()
+            .to_bytes()
+            .map_err(Error::BytesRepr)?;
+        self.memory
+            .set(dest_ptr, &block_height)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Writes the next deterministic 32-byte seed of the deploy to [dest_ptr] in Wasm memory.
+    fn get_entropy(&mut self, dest_ptr: u32) -> Result<(), Trap> {
+        let entropy = self.context.next_entropy();
+        self.memory
+            .set(dest_ptr, &entropy)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
     pub fn set_mem_from_buf(&mut self, dest_ptr: u32) -> Result<(), Trap> {
         self.memory
             .set(dest_ptr, &self.host_buf)
@@ -423,6 +581,19 @@ where
         }
     }
 
+    /// Load the argument passed under `name` by `call_contract_with_named_args`
+    /// into the runtime buffer so that a subsequent `get_arg` can return it to
+    /// the caller. Returns -1 if there is no argument under `name`.
+    pub fn load_named_arg(&mut self, name_ptr: u32, name_size: u32) -> Result<i32, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let arg_bytes = match self.context.named_args().get(&name) {
+            Some(value) => value.to_bytes().map_err(Error::BytesRepr)?,
+            None => return Ok(-1),
+        };
+        self.host_buf = arg_bytes;
+        Ok(self.host_buf.len() as i32)
+    }
+
     /// Calls contract living under a `key`, with supplied `args` and extra
     /// `urefs`.
     pub fn call_contract(
@@ -431,40 +602,74 @@ where
         args_bytes: Vec<u8>,
         urefs_bytes: Vec<u8>,
     ) -> Result<usize, Error> {
-        let (args, module, mut refs, protocol_version) = {
-            match self.context.read_gs(&key)? {
-                None => Err(Error::KeyNotFound(key)),
-                Some(value) => {
-                    if let Value::Contract(contract) = value {
-                        let args: Vec<Vec<u8>> = deserialize(&args_bytes)?;
-                        let module = parity_wasm::deserialize_buffer(contract.bytes())?;
-
-                        Ok((
-                            args,
-                            module,
-                            contract.urefs_lookup().clone(),
-                            contract.protocol_version(),
-                        ))
-                    } else {
-                        Err(Error::FunctionNotFound(format!(
-                            "Value at {:?} is not a contract",
-                            key
-                        )))
-                    }
-                }
+        let args: Vec<Vec<u8>> = deserialize(&args_bytes)?;
+        self.call_contract_with(key, args, RuntimeArgs::new(), urefs_bytes)
+    }
+
+    /// Calls contract living under a `key`, with arguments passed by name and
+    /// extra `urefs`.
+    pub fn call_contract_with_named_args(
+        &mut self,
+        key: Key,
+        named_args_bytes: Vec<u8>,
+        urefs_bytes: Vec<u8>,
+    ) -> Result<usize, Error> {
+        let named_args: RuntimeArgs = deserialize(&named_args_bytes)?;
+        self.call_contract_with(key, Vec::new(), named_args, urefs_bytes)
+    }
+
+    fn call_contract_with(
+        &mut self,
+        key: Key,
+        args: Vec<Vec<u8>>,
+        named_args: RuntimeArgs,
+        urefs_bytes: Vec<u8>,
+    ) -> Result<usize, Error> {
+        let contract = match self.context.read_gs(&key)? {
+            None => return Err(Error::KeyNotFound(key)),
+            Some(Value::Contract(contract)) => contract,
+            Some(_) => {
+                return Err(Error::FunctionNotFound(format!(
+                    "Value at {:?} is not a contract",
+                    key
+                )))
             }
-        }?;
+        };
+        let mut refs = contract.urefs_lookup().clone();
+        let protocol_version = contract.protocol_version();
 
         let extra_urefs = self.context.deserialize_keys(&urefs_bytes)?;
-        let result = sub_call(
-            module,
-            args,
-            &mut refs,
-            key,
-            self,
-            extra_urefs,
-            protocol_version,
-        )?;
+        let result = match self.native_system_contract(key, &args) {
+            Some(system_contract) => native::sub_call(
+                system_contract,
+                args,
+                &mut refs,
+                key,
+                self,
+                extra_urefs,
+                protocol_version,
+            ),
+            None => {
+                let module = parity_wasm::deserialize_buffer(contract.bytes())?;
+                sub_call(
+                    module,
+                    args,
+                    named_args,
+                    &mut refs,
+                    key,
+                    self,
+                    extra_urefs,
+                    protocol_version,
+                )
+            }
+        };
+        let result = match result {
+            // Reverts of the Proof of Stake contract are reported as its errors
+            Err(Error::Revert(status)) if self.is_pos_contract(key) => {
+                Err(system_contracts::error::Error::ProofOfStakeError(status).into())
+            }
+            result => result,
+        }?;
         self.host_buf = result;
         Ok(self.host_buf.len())
     }
@@ -499,10 +704,40 @@ where
             urefs,
             self.context.protocol_version(),
         );
-        let new_hash = self.context.store_contract(contract.into())?;
+        let value = Value::Contract(contract);
+        self.charge_storage_bytes(value.to_bytes().map_err(Error::BytesRepr)?.len())?;
+        let new_hash = self.context.store_contract(value)?;
         Ok(new_hash)
     }
 
+    /// Stores the function exported under the given name as the next version of the contract
+    /// package under the given key, and returns the new version number.
+    ///
+    /// Only the holders of a writeable reference to the package can add versions to it.
+    fn add_contract_version(
+        &mut self,
+        package_ptr: u32,
+        package_size: u32,
+        name_ptr: u32,
+        name_size: u32,
+        urefs_ptr: u32,
+        urefs_size: u32,
+    ) -> Result<u32, Trap> {
+        let package_key = self.key_from_mem(package_ptr, package_size)?;
+        let mut package: ContractPackage = self.context.read_gs_typed(&package_key)?;
+        let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
+        let urefs = {
+            let bytes = self.bytes_from_mem(urefs_ptr, urefs_size as usize)?;
+            bytesrepr::deserialize(&bytes).map_err(Error::BytesRepr)?
+        };
+        let contract_hash = self.store_function(fn_bytes, urefs)?;
+        let version = package.add_version(Key::Hash(contract_hash));
+        let value = Value::ContractPackage(package);
+        self.charge_storage_bytes(value.to_bytes().map_err(Error::BytesRepr)?.len())?;
+        self.context.write_gs(package_key, value)?;
+        Ok(version)
+    }
+
     /// Writes function address (`hash_bytes`) into the Wasm memory (at
     /// `dest_ptr` pointer).
     fn function_address(&mut self, hash_bytes: [u8; 32], dest_ptr: u32) -> Result<(), Trap> {
@@ -514,6 +749,7 @@ where
     /// Generates new unforgable reference and adds it to the context's
     /// known_uref set.
     pub fn new_uref(&mut self, key_ptr: u32, value_ptr: u32, value_size: u32) -> Result<(), Trap> {
+        self.charge_storage_bytes(value_size as usize)?;
         let value = self.value_from_mem(value_ptr, value_size)?; // read initial value from memory
         let key = self.context.new_uref(value)?;
         self.memory
@@ -529,6 +765,7 @@ where
         value_ptr: u32,
         value_size: u32,
     ) -> Result<(), Trap> {
+        self.charge_storage_bytes(value_size as usize)?;
         let key = self.key_from_mem(key_ptr, key_size)?;
         let value = self.value_from_mem(value_ptr, value_size)?;
         self.context.write_gs(key, value).map_err(Into::into)
@@ -543,6 +780,7 @@ where
         value_ptr: u32,
         value_size: u32,
     ) -> Result<(), Trap> {
+        self.charge_storage_bytes(value_size as usize)?;
         let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
         let value = self.value_from_mem(value_ptr, value_size)?;
         self.context.write_ls(&key_bytes, value).map_err(Into::into)
@@ -556,6 +794,7 @@ where
         value_ptr: u32,
         value_size: u32,
     ) -> Result<(), Trap> {
+        self.charge_storage_bytes(value_size as usize)?;
         let key = self.key_from_mem(key_ptr, key_size)?;
         let value = self.value_from_mem(value_ptr, value_size)?;
         self.context.add_gs(key, value).map_err(Into::into)
@@ -584,6 +823,98 @@ where
         Ok(self.host_buf.len())
     }
 
+    /// Reads the item stored under a key in the dictionary with the given seed.
+    pub fn dictionary_get(
+        &mut self,
+        seed_ptr: u32,
+        seed_size: u32,
+        item_key_ptr: u32,
+        item_key_size: u32,
+    ) -> Result<usize, Trap> {
+        let seed = self.uref_from_mem(seed_ptr, seed_size)?;
+        let item_key = self.string_from_mem(item_key_ptr, item_key_size)?;
+        let value: Option<Value> = self.context.read_dictionary(seed, &item_key)?;
+        let value_bytes = value.to_bytes().map_err(Error::BytesRepr)?;
+        self.host_buf = value_bytes;
+        Ok(self.host_buf.len())
+    }
+
+    /// Writes `value` under a key in the dictionary with the given seed.
+    pub fn dictionary_put(
+        &mut self,
+        seed_ptr: u32,
+        seed_size: u32,
+        item_key_ptr: u32,
+        item_key_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<(), Trap> {
+        self.charge_storage_bytes(value_size as usize)?;
+        let seed = self.uref_from_mem(seed_ptr, seed_size)?;
+        let item_key = self.string_from_mem(item_key_ptr, item_key_size)?;
+        let value = self.value_from_mem(value_ptr, value_size)?;
+        self.context
+            .write_dictionary(seed, &item_key, value)
+            .map_err(Into::into)
+    }
+
+    /// Writes the Blake2b-256 digest of the data in Wasm memory to [dest_ptr].
+    fn blake2b256(&mut self, data_ptr: u32, data_size: u32, dest_ptr: u32) -> Result<(), Trap> {
+        self.charge_host_call(self.wasm_costs.blake2b256_cost)?;
+        let data = self.bytes_from_mem(data_ptr, data_size as usize)?;
+        self.memory
+            .set(dest_ptr, &crypto::blake2b256(&data))
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Writes the SHA-256 digest of the data in Wasm memory to [dest_ptr].
+    fn sha256(&mut self, data_ptr: u32, data_size: u32, dest_ptr: u32) -> Result<(), Trap> {
+        self.charge_host_call(self.wasm_costs.sha256_cost)?;
+        let data = self.bytes_from_mem(data_ptr, data_size as usize)?;
+        self.memory
+            .set(dest_ptr, &crypto::sha256(&data))
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Checks an Ed25519 signature of a message, reading the key, message and signature from
+    /// Wasm memory.
+    fn ed25519_verify(
+        &mut self,
+        public_key_ptr: u32,
+        message_ptr: u32,
+        message_size: u32,
+        signature_ptr: u32,
+    ) -> Result<bool, Trap> {
+        self.charge_host_call(self.wasm_costs.ed25519_verify_cost)?;
+        let public_key = self.bytes_from_mem(public_key_ptr, ED25519_PUBLIC_KEY_LENGTH)?;
+        let message = self.bytes_from_mem(message_ptr, message_size as usize)?;
+        let signature = self.bytes_from_mem(signature_ptr, ED25519_SIGNATURE_LENGTH)?;
+        Ok(crypto::ed25519_verify(&public_key, &message, &signature))
+    }
+
+    /// Recovers the Secp256k1 public key which signed a message hash and writes it to
+    /// [dest_ptr].  Returns `false`, leaving [dest_ptr] untouched, if no key could be recovered.
+    fn secp256k1_recover(
+        &mut self,
+        message_hash_ptr: u32,
+        signature_ptr: u32,
+        recovery_id: u32,
+        dest_ptr: u32,
+    ) -> Result<bool, Trap> {
+        self.charge_host_call(self.wasm_costs.secp256k1_recover_cost)?;
+        let message_hash = self.bytes_from_mem(message_hash_ptr, DIGEST_LENGTH)?;
+        let signature = self.bytes_from_mem(signature_ptr, SECP256K1_SIGNATURE_LENGTH)?;
+        match crypto::secp256k1_recover(&message_hash, &signature, recovery_id as i32) {
+            Some(public_key) => {
+                self.memory
+                    .set(dest_ptr, &public_key)
+                    .map_err(Error::Interpreter)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
     /// Reverts contract execution with a status specified.
     pub fn revert(&mut self, status: u32) -> Trap {
         Error::Revert(status).into()
@@ -594,14 +925,7 @@ where
     }
 
     fn add_associated_key(&mut self, public_key_ptr: u32, weight_value: u8) -> Result<i32, Trap> {
-        let public_key = {
-            // Public key as serialized bytes
-            let source_serialized =
-                self.bytes_from_mem(public_key_ptr, PUBLIC_KEY_SIZE + U32_SIZE)?;
-            // Public key deserialized
-            let source: PublicKey = deserialize(&source_serialized).map_err(Error::BytesRepr)?;
-            source
-        };
+        let public_key = self.public_key_from_mem(public_key_ptr)?;
         let weight = Weight::new(weight_value);
 
         match self.context.add_associated_key(public_key, weight) {
@@ -617,14 +941,7 @@ where
     }
 
     fn remove_associated_key(&mut self, public_key_ptr: u32) -> Result<i32, Trap> {
-        let public_key = {
-            // Public key as serialized bytes
-            let source_serialized =
-                self.bytes_from_mem(public_key_ptr, PUBLIC_KEY_SIZE + U32_SIZE)?;
-            // Public key deserialized
-            let source: PublicKey = deserialize(&source_serialized).map_err(Error::BytesRepr)?;
-            source
-        };
+        let public_key = self.public_key_from_mem(public_key_ptr)?;
         match self.context.remove_associated_key(public_key) {
             Ok(_) => Ok(0),
             Err(Error::RemoveKeyFailure(e)) => Ok(e as i32),
@@ -637,14 +954,7 @@ where
         public_key_ptr: u32,
         weight_value: u8,
     ) -> Result<i32, Trap> {
-        let public_key = {
-            // Public key as serialized bytes
-            let source_serialized =
-                self.bytes_from_mem(public_key_ptr, PUBLIC_KEY_SIZE + U32_SIZE)?;
-            // Public key deserialized
-            let source: PublicKey = deserialize(&source_serialized).map_err(Error::BytesRepr)?;
-            source
-        };
+        let public_key = self.public_key_from_mem(public_key_ptr)?;
         let weight = Weight::new(weight_value);
 
         match self.context.update_associated_key(public_key, weight) {
@@ -713,6 +1023,16 @@ where
         Ok(internal_mint_uref)
     }
 
+    /// Returns true if `key` is the key of the Proof of Stake contract.
+    fn is_pos_contract(&mut self, key: Key) -> bool {
+        let addr = match key {
+            Key::URef(uref) => uref.addr(),
+            _ => return false,
+        };
+        self.context.contains_uref(POS_NAME)
+            && self.get_pos_contract_uref().ok().map(|uref| uref.addr()) == Some(addr)
+    }
+
     /// Calls the "create" method on the mint contract at the given mint
     /// contract key
     fn mint_create(&mut self, mint_contract_key: Key) -> Result<PurseId, Error> {
@@ -776,19 +1096,20 @@ where
         let pos_contract_uref = self.get_pos_contract_uref()?;
         let mint_contract_key = Key::URef(mint_contract_uref);
         let pos_contract_key = Key::URef(pos_contract_uref);
-        let target_addr = target.value();
-        let target_key = Key::Account(target_addr);
+        let target_key = Key::Account(target.to_account_address());
 
         // A precondition check that verifies that the transfer can be done
         // as the source purse has enough funds to cover the transfer.
         if amount > self.get_balance(source)?.unwrap_or_default() {
-            return Ok(TransferResult::TransferError);
+            return Ok(TransferResult::TransferError(
+                TransferFailure::InsufficientFunds,
+            ));
         }
 
         let target_purse_id = self.mint_create(mint_contract_key)?;
 
         if source == target_purse_id {
-            return Ok(TransferResult::TransferError);
+            return Ok(TransferResult::TransferError(TransferFailure::Other));
         }
 
         match self.mint_transfer(mint_contract_key, source, target_purse_id, amount) {
@@ -814,11 +1135,11 @@ where
                     }
                 })
                 .collect();
-                let account = Account::create(target_addr, known_urefs, target_purse_id);
+                let account = Account::create_for_public_key(target, known_urefs, target_purse_id);
                 self.context.write_account(target_key, account)?;
                 Ok(TransferResult::TransferredToNewAccount)
             }
-            Err(_) => Ok(TransferResult::TransferError),
+            Err(error) => Ok(TransferResult::TransferError(transfer_failure(error))),
         }
     }
 
@@ -838,7 +1159,7 @@ where
 
         match self.mint_transfer(mint_contract_key, source, target, amount) {
             Ok(_) => Ok(TransferResult::TransferredToExistingAccount),
-            Err(_) => Ok(TransferResult::TransferError),
+            Err(error) => Ok(TransferResult::TransferError(transfer_failure(error))),
         }
     }
 
@@ -861,7 +1182,8 @@ where
         target: PublicKey,
         amount: U512,
     ) -> Result<TransferResult, Error> {
-        let target_key = Key::Account(target.value());
+        self.context.validate_transfer_authorization()?;
+        let target_key = Key::Account(target.to_account_address());
         // Look up the account at the given public key's address
         match self.context.read_account(&target_key)? {
             None => {
@@ -894,6 +1216,7 @@ where
         amount_ptr: u32,
         amount_size: u32,
     ) -> Result<PurseTransferResult, Error> {
+        self.context.validate_transfer_authorization()?;
         let source: PurseId = {
             let bytes = self.bytes_from_mem(source_ptr, source_size as usize)?;
             deserialize(&bytes).map_err(Error::BytesRepr)?
@@ -909,11 +1232,25 @@ where
             deserialize(&bytes).map_err(Error::BytesRepr)?
         };
 
+        self.context
+            .validate_purse_transfer(source.value(), target.value())?;
+
+        self.transfer_purse_to_purse(source, target, amount)
+    }
+
+    /// Transfers `amount` of motes from `source` purse to `target` purse through
+    /// the mint.
+    fn transfer_purse_to_purse(
+        &mut self,
+        source: PurseId,
+        target: PurseId,
+        amount: U512,
+    ) -> Result<PurseTransferResult, Error> {
         let mint_contract_key = Key::URef(self.get_mint_contract_uref()?);
 
         match self.mint_transfer(mint_contract_key, source, target, amount) {
             Ok(_) => Ok(PurseTransferResult::TransferSuccessful),
-            Err(_) => Ok(PurseTransferResult::TransferError),
+            Err(error) => Ok(PurseTransferResult::TransferError(transfer_failure(error))),
         }
     }
 
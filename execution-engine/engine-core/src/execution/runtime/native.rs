@@ -0,0 +1,623 @@
+//! Host-side implementations of the mint and Proof of Stake contracts, run in
+//! place of their wasm when the engine is configured not to use the system
+//! contracts.  Every method performs the same operations on its runtime
+//! context, in the same order, as the corresponding method of the wasm
+//! contract, so that both paths produce identical effects.
+
+use std::collections::BTreeMap;
+
+use parity_wasm::elements::Module;
+use wasmi::memory_units::Pages;
+use wasmi::{MemoryInstance, Trap, TrapKind};
+
+use contract_ffi::bytesrepr::{deserialize, FromBytes, ToBytes};
+use contract_ffi::contract_api::argsparser::ArgsParser;
+use contract_ffi::contract_api::{PurseTransferResult, TransferResult};
+use contract_ffi::execution::Phase;
+use contract_ffi::key::Key;
+use contract_ffi::system_contracts::mint::{
+    self, BURN_METHOD, READ_TOTAL_SUPPLY_METHOD, TOTAL_SUPPLY_KEY,
+};
+use contract_ffi::system_contracts::pos::error::Error as PosError;
+use contract_ffi::system_contracts::pos::{FeeHandling, RefundRatio, DEFAULT_UNBOND_DELAY};
+use contract_ffi::uref::{AccessRights, URef};
+use contract_ffi::value::account::{PublicKey, PurseId};
+use contract_ffi::value::{Value, U512};
+use engine_storage::global_state::StateReader;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+
+use super::{sub_call_context, sub_call_result, Runtime};
+use crate::execution::{Error, MINT_NAME};
+use crate::runtime_context::RuntimeContext;
+
+const SYSTEM_ACCOUNT: [u8; 32] = [0u8; 32];
+
+const BONDING_PURSE_KEY: &str = "pos_bonding_purse";
+const PAYMENT_PURSE_KEY: &str = "pos_payment_purse";
+const REWARDS_PURSE_KEY: &str = "pos_rewards_purse";
+const REFUND_PURSE_KEY: &str = "pos_refund_purse";
+const UNBOND_DELAY_KEY: u8 = 3;
+
+/// Proof of Stake methods which are only implemented by the wasm contract.
+const WASM_ONLY_POS_METHODS: [&str; 12] = [
+    "bond",
+    "unbond",
+    "step",
+    "process_unbond_requests",
+    "get_queues",
+    "set_delegation_rate",
+    "get_era",
+    "get_era_snapshot",
+    "get_era_duration",
+    "set_era_duration",
+    "get_bond_limits",
+    "set_bond_limits",
+];
+
+/// A system contract with a host-side implementation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SystemContract {
+    Mint,
+    ProofOfStake,
+}
+
+/// The trap hit by a wasm system contract when it panics.
+fn unreachable() -> Trap {
+    Trap::new(TrapKind::Unreachable)
+}
+
+fn revert(error: PosError) -> Trap {
+    Error::Revert(error.into()).into()
+}
+
+/// Calls the host-side implementation of `system_contract` from
+/// `current_runtime`, like `sub_call` does for a wasm contract.
+pub(super) fn sub_call<R: StateReader<Key, Value>>(
+    system_contract: SystemContract,
+    args: Vec<Vec<u8>>,
+    refs: &mut BTreeMap<String, Key>,
+    key: Key,
+    current_runtime: &mut Runtime<R>,
+    extra_urefs: Vec<Key>,
+    protocol_version: u64,
+) -> Result<Vec<u8>, Error>
+where
+    R::Error: Into<Error>,
+{
+    let context = sub_call_context(
+        args,
+        refs,
+        key,
+        current_runtime,
+        extra_urefs,
+        protocol_version,
+    );
+    let mut runtime = Runtime::new_native(current_runtime.wasm_costs, context)?;
+
+    let result = runtime
+        .call_system_contract(system_contract)
+        .map_err(wasmi::Error::Trap);
+
+    sub_call_result(result, runtime.result, current_runtime)
+}
+
+impl<'a, R: StateReader<Key, Value>> Runtime<'a, R>
+where
+    R::Error: Into<Error>,
+{
+    /// Creates a runtime without a wasm module, for running system contracts
+    /// natively.
+    pub fn new_native(
+        wasm_costs: WasmCosts,
+        context: RuntimeContext<'a, R>,
+    ) -> Result<Self, Error> {
+        let memory = MemoryInstance::alloc(Pages(0), Some(Pages(0)))?;
+        Ok(Runtime::new(
+            memory,
+            Module::default(),
+            wasm_costs,
+            context,
+            false,
+        ))
+    }
+
+    /// Returns the system contract stored under `key` if calls to it should
+    /// run natively.
+    pub(super) fn native_system_contract(
+        &mut self,
+        key: Key,
+        args: &[Vec<u8>],
+    ) -> Option<SystemContract> {
+        if self.use_system_contracts {
+            return None;
+        }
+        let addr = match key {
+            Key::URef(uref) => uref.addr(),
+            _ => return None,
+        };
+
+        if self.context.contains_uref(MINT_NAME)
+            && self.get_mint_contract_uref().ok().map(|uref| uref.addr()) == Some(addr)
+        {
+            return Some(SystemContract::Mint);
+        }
+
+        if self.is_pos_contract(key) {
+            let method_name: Option<String> =
+                args.first().and_then(|bytes| deserialize(bytes).ok());
+            let wasm_only = method_name
+                .map(|name| WASM_ONLY_POS_METHODS.contains(&name.as_str()))
+                .unwrap_or(false);
+            if !wasm_only {
+                return Some(SystemContract::ProofOfStake);
+            }
+        }
+
+        None
+    }
+
+    /// Runs the method of `system_contract` named by the first argument of the
+    /// current context.  Like the `call` export of the wasm contract, this
+    /// either returns normally or traps, with `Error::Ret` if the method
+    /// returned a value.
+    pub fn call_system_contract(&mut self, system_contract: SystemContract) -> Result<(), Trap> {
+        match system_contract {
+            SystemContract::Mint => self.call_mint(),
+            SystemContract::ProofOfStake => self.call_proof_of_stake(),
+        }
+    }
+
+    fn native_arg<T: FromBytes>(&self, i: usize) -> Result<T, Trap> {
+        let bytes = self
+            .context
+            .args()
+            .get(i)
+            .ok_or_else(|| Trap::from(Error::ArgIndexOutOfBounds(i)))?;
+        deserialize(bytes).map_err(|_| unreachable())
+    }
+
+    /// Stores `value` as the result of the call and returns the trap which ends
+    /// it, like the `ret` host function.
+    fn native_ret<T: ToBytes>(&mut self, value: &T, urefs: Vec<URef>) -> Trap {
+        let result = value
+            .to_bytes()
+            .map_err(Error::BytesRepr)
+            .and_then(|bytes| {
+                urefs
+                    .iter()
+                    .try_for_each(|uref| self.context.validate_uref(uref))?;
+                Ok(bytes)
+            });
+        match result {
+            Ok(bytes) => {
+                self.result = bytes;
+                Error::Ret(urefs).into()
+            }
+            Err(error) => error.into(),
+        }
+    }
+
+    fn read_u512(&mut self, uref: URef) -> Result<U512, Trap> {
+        match self.context.read_gs(&Key::URef(uref))? {
+            Some(Value::UInt512(value)) => Ok(value),
+            _ => Err(unreachable()),
+        }
+    }
+
+    fn call_mint(&mut self) -> Result<(), Trap> {
+        let method_name: String = self.native_arg(0)?;
+
+        match method_name.as_str() {
+            "mint" => {
+                let amount: U512 = self.native_arg(1)?;
+                let maybe_purse_key = self
+                    .mint_purse(amount)?
+                    .map(|addr| URef::new(addr, AccessRights::READ_ADD_WRITE));
+                let urefs = maybe_purse_key.iter().cloned().collect();
+                Err(self.native_ret(&maybe_purse_key, urefs))
+            }
+            "create" => {
+                let addr = self.mint_purse(U512::zero())?.map_err(|_| unreachable())?;
+                let purse_key = URef::new(addr, AccessRights::READ_ADD_WRITE);
+                Err(self.native_ret(&purse_key, vec![purse_key]))
+            }
+            "balance" => {
+                let key: URef = self.native_arg(1)?;
+                let purse_addr = self.withdraw_id(key).map_err(|_| unreachable())?;
+                let balance = match self.mint_lookup(purse_addr, AccessRights::READ_ADD_WRITE)? {
+                    Some(balance_uref) => Some(self.read_u512(balance_uref)?),
+                    None => None,
+                };
+                Err(self.native_ret(&balance, Vec::new()))
+            }
+            "transfer" => {
+                let source: URef = self.native_arg(1)?;
+                let target: URef = self.native_arg(2)?;
+                let amount: U512 = self.native_arg(3)?;
+                let transfer_result = match (self.withdraw_id(source), self.deposit_id(target)) {
+                    (Err(error), _) | (_, Err(error)) => Err(error),
+                    (Ok(source), Ok(target)) => {
+                        self.mint_transfer_balance(source, target, amount)?
+                    }
+                };
+                Err(self.native_ret(&transfer_result, Vec::new()))
+            }
+            BURN_METHOD => {
+                let source: URef = self.native_arg(1)?;
+                let amount: U512 = self.native_arg(2)?;
+                let burn_result = match self.withdraw_id(source) {
+                    Err(error) => Err(error),
+                    Ok(source) => self.mint_burn_balance(source, amount)?,
+                };
+                Err(self.native_ret(&burn_result, Vec::new()))
+            }
+            READ_TOTAL_SUPPLY_METHOD => {
+                let total_supply = match self.total_supply_uref() {
+                    Some(total_supply_uref) => self.read_u512(total_supply_uref)?,
+                    None => U512::zero(),
+                };
+                Err(self.native_ret(&total_supply, Vec::new()))
+            }
+            _ => Err(unreachable()),
+        }
+    }
+
+    /// Checks that `uref` is known to the mint and may be withdrawn from.
+    fn withdraw_id(&self, uref: URef) -> Result<[u8; 32], mint::error::Error> {
+        if self.context.validate_keys(&Value::from(uref)).is_err() {
+            return Err(mint::error::Error::InvalidURef);
+        }
+        if uref.is_writeable() {
+            Ok(uref.addr())
+        } else {
+            Err(mint::error::Error::InvalidAccessRights)
+        }
+    }
+
+    /// Checks that `uref` is known to the mint and may be deposited into.
+    fn deposit_id(&self, uref: URef) -> Result<[u8; 32], mint::error::Error> {
+        if self.context.validate_keys(&Value::from(uref)).is_err() {
+            return Err(mint::error::Error::InvalidURef);
+        }
+        if uref.is_addable() {
+            Ok(uref.addr())
+        } else {
+            Err(mint::error::Error::InvalidAccessRights)
+        }
+    }
+
+    /// Looks up the balance uref of the purse at `purse_addr`, requiring it to
+    /// carry at least `access_rights`.
+    fn mint_lookup(
+        &mut self,
+        purse_addr: [u8; 32],
+        access_rights: AccessRights,
+    ) -> Result<Option<URef>, Trap> {
+        let key_bytes = purse_addr.to_bytes().map_err(Error::BytesRepr)?;
+        match self.context.read_ls(&key_bytes)? {
+            None => Ok(None),
+            Some(Value::Key(Key::URef(uref))) if uref.access_rights() >= Some(access_rights) => {
+                Ok(Some(URef::new(uref.addr(), access_rights)))
+            }
+            Some(Value::Key(_)) => Ok(None),
+            Some(_) => Err(unreachable()),
+        }
+    }
+
+    fn total_supply_uref(&self) -> Option<URef> {
+        match self.context.get_uref(TOTAL_SUPPLY_KEY) {
+            Some(Key::URef(uref)) if uref.access_rights().is_some() => Some(*uref),
+            _ => None,
+        }
+    }
+
+    /// Creates a purse holding `initial_balance`, returning its address.
+    fn mint_purse(
+        &mut self,
+        initial_balance: U512,
+    ) -> Result<Result<[u8; 32], mint::error::Error>, Trap> {
+        let caller = self.context.get_caller();
+        if !initial_balance.is_zero() && caller.to_account_address() != SYSTEM_ACCOUNT {
+            return Ok(Err(mint::error::Error::InvalidNonEmptyPurseCreation));
+        }
+
+        let balance_key = self.context.new_uref(Value::UInt512(initial_balance))?;
+        let purse_key = match self.context.new_uref(Value::Unit)? {
+            Key::URef(uref) => uref,
+            _ => return Err(unreachable()),
+        };
+        let purse_addr = purse_key.addr();
+        let purse_uref_name = purse_key.remove_access_rights().as_string();
+
+        // store balance uref so that the runtime knows the mint has full access
+        self.context.add_uref(purse_uref_name, balance_key)?;
+
+        let key_bytes = purse_addr.to_bytes().map_err(Error::BytesRepr)?;
+        self.context.write_ls(&key_bytes, Value::Key(balance_key))?;
+
+        if !initial_balance.is_zero() {
+            match self.total_supply_uref() {
+                Some(total_supply_uref) => self.context.add_gs(
+                    Key::URef(total_supply_uref),
+                    Value::UInt512(initial_balance),
+                )?,
+                None => {
+                    let total_supply_key =
+                        self.context.new_uref(Value::UInt512(initial_balance))?;
+                    self.context
+                        .add_uref(TOTAL_SUPPLY_KEY.to_string(), total_supply_key)?;
+                }
+            }
+        }
+
+        Ok(Ok(purse_addr))
+    }
+
+    fn mint_transfer_balance(
+        &mut self,
+        source: [u8; 32],
+        target: [u8; 32],
+        amount: U512,
+    ) -> Result<Result<(), mint::error::Error>, Trap> {
+        let source_balance = match self.mint_lookup(source, AccessRights::READ_ADD_WRITE)? {
+            Some(source_balance) => source_balance,
+            None => return Ok(Err(mint::error::Error::SourceNotFound)),
+        };
+        let source_value = self.read_u512(source_balance)?;
+        if amount > source_value {
+            return Ok(Err(mint::error::Error::InsufficientFunds));
+        }
+
+        let target_balance = match self.mint_lookup(target, AccessRights::ADD)? {
+            Some(target_balance) => target_balance,
+            None => return Ok(Err(mint::error::Error::DestNotFound)),
+        };
+        self.context.write_gs(
+            Key::URef(source_balance),
+            Value::UInt512(source_value - amount),
+        )?;
+        self.context
+            .add_gs(Key::URef(target_balance), Value::UInt512(amount))?;
+        Ok(Ok(()))
+    }
+
+    fn mint_burn_balance(
+        &mut self,
+        source: [u8; 32],
+        amount: U512,
+    ) -> Result<Result<(), mint::error::Error>, Trap> {
+        let source_balance = match self.mint_lookup(source, AccessRights::READ_ADD_WRITE)? {
+            Some(source_balance) => source_balance,
+            None => return Ok(Err(mint::error::Error::SourceNotFound)),
+        };
+        let source_value = self.read_u512(source_balance)?;
+        if amount > source_value {
+            return Ok(Err(mint::error::Error::InsufficientFunds));
+        }
+
+        self.context.write_gs(
+            Key::URef(source_balance),
+            Value::UInt512(source_value - amount),
+        )?;
+        if let Some(total_supply_uref) = self.total_supply_uref() {
+            let total_supply = self.read_u512(total_supply_uref)?;
+            let total_supply = total_supply.checked_sub(amount).ok_or_else(unreachable)?;
+            self.context
+                .write_gs(Key::URef(total_supply_uref), Value::UInt512(total_supply))?;
+        }
+        Ok(Ok(()))
+    }
+
+    fn call_proof_of_stake(&mut self) -> Result<(), Trap> {
+        let method_name: String = self.native_arg(0)?;
+        let unbond_delay = self.unbond_delay()?;
+        self.pos_purse(
+            BONDING_PURSE_KEY,
+            PosError::BondingPurseNotFound,
+            PosError::BondingPurseKeyUnexpectedType,
+        )?;
+
+        match method_name.as_str() {
+            "get_unbond_delay" => Err(self.native_ret(&unbond_delay, Vec::new())),
+            "set_unbond_delay" => {
+                let unbond_delay: u64 = self.native_arg(1)?;
+                self.check_system_account()?;
+                let key_bytes = UNBOND_DELAY_KEY.to_bytes().map_err(Error::BytesRepr)?;
+                self.context
+                    .write_ls(&key_bytes, Value::UInt64(unbond_delay))?;
+                Ok(())
+            }
+            "get_payment_purse" => {
+                let purse = self.pos_purse(
+                    PAYMENT_PURSE_KEY,
+                    PosError::PaymentPurseNotFound,
+                    PosError::PaymentPurseKeyUnexpectedType,
+                )?;
+                // Limit the access rights so only balance query and deposit are allowed.
+                let rights_controlled_purse =
+                    PurseId::new(URef::new(purse.value().addr(), AccessRights::READ_ADD));
+                Err(self.native_ret(
+                    &rights_controlled_purse,
+                    vec![rights_controlled_purse.value()],
+                ))
+            }
+            "set_refund_purse" => {
+                let purse_id: PurseId = self.native_arg(1)?;
+                if let Phase::Payment = self.context.phase() {
+                    self.context
+                        .add_uref(REFUND_PURSE_KEY.to_string(), Key::URef(purse_id.value()))?;
+                    Ok(())
+                } else {
+                    Err(revert(PosError::SetRefundPurseCalledOutsidePayment))
+                }
+            }
+            "get_refund_purse" => {
+                let result = self
+                    .refund_purse()?
+                    .map(|purse| purse.value().remove_access_rights());
+                if let Some(uref) = result {
+                    Err(self.native_ret(&Some(PurseId::new(uref)), vec![uref]))
+                } else {
+                    Err(self.native_ret(&result, Vec::new()))
+                }
+            }
+            "finalize_payment" => {
+                let amount_spent: U512 = self.native_arg(1)?;
+                let account: PublicKey = self.native_arg(2)?;
+                let fee_handling: FeeHandling = self.native_arg(3)?;
+                let proposer: Option<PublicKey> = self.native_arg(4)?;
+                let refund_ratio: RefundRatio = self.native_arg(5)?;
+                self.finalize_payment(amount_spent, account, fee_handling, proposer, refund_ratio)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn unbond_delay(&mut self) -> Result<u64, Trap> {
+        let key_bytes = UNBOND_DELAY_KEY.to_bytes().map_err(Error::BytesRepr)?;
+        match self.context.read_ls(&key_bytes)? {
+            None => Ok(DEFAULT_UNBOND_DELAY),
+            Some(Value::UInt64(unbond_delay)) => Ok(unbond_delay),
+            Some(_) => Err(unreachable()),
+        }
+    }
+
+    fn check_system_account(&self) -> Result<(), Trap> {
+        if self.context.get_caller().to_account_address() != SYSTEM_ACCOUNT {
+            return Err(revert(PosError::SystemFunctionCalledByUserAccount));
+        }
+        Ok(())
+    }
+
+    /// Looks up the purse the Proof of Stake contract stores under `name`.
+    fn pos_purse(
+        &self,
+        name: &str,
+        not_found: PosError,
+        unexpected_type: PosError,
+    ) -> Result<PurseId, Trap> {
+        match self.context.get_uref(name) {
+            Some(Key::URef(uref)) => Ok(PurseId::new(*uref)),
+            Some(_) => Err(revert(unexpected_type)),
+            None => Err(revert(not_found)),
+        }
+    }
+
+    fn refund_purse(&self) -> Result<Option<PurseId>, Trap> {
+        match self.context.get_uref(REFUND_PURSE_KEY) {
+            Some(Key::URef(uref)) => Ok(Some(PurseId::new(*uref))),
+            Some(_) => Err(revert(PosError::RefundPurseKeyUnexpectedType)),
+            None => Ok(None),
+        }
+    }
+
+    /// Calls the "burn" method of the mint, like `contract_api::burn`.
+    fn call_mint_burn(
+        &mut self,
+        purse_id: PurseId,
+        amount: U512,
+    ) -> Result<Result<(), mint::error::Error>, Trap> {
+        let mint_contract_key =
+            Key::URef(self.get_mint_contract_uref().map_err(|_| unreachable())?);
+        let purse = purse_id.value();
+        let args_bytes = ArgsParser::parse(&(BURN_METHOD, purse, amount))
+            .and_then(|args| args.to_bytes())
+            .map_err(Error::BytesRepr)?;
+        let urefs_bytes = vec![Key::URef(purse)]
+            .to_bytes()
+            .map_err(Error::BytesRepr)?;
+
+        self.call_contract(mint_contract_key, args_bytes, urefs_bytes)?;
+
+        deserialize(&self.host_buf).map_err(|_| unreachable())
+    }
+
+    fn finalize_payment(
+        &mut self,
+        amount_spent: U512,
+        account: PublicKey,
+        fee_handling: FeeHandling,
+        proposer: Option<PublicKey>,
+        refund_ratio: RefundRatio,
+    ) -> Result<(), Trap> {
+        self.check_system_account()?;
+
+        let payment_purse = self.pos_purse(
+            PAYMENT_PURSE_KEY,
+            PosError::PaymentPurseNotFound,
+            PosError::PaymentPurseKeyUnexpectedType,
+        )?;
+        let total = self
+            .get_balance(payment_purse)?
+            .ok_or_else(|| revert(PosError::PaymentPurseBalanceNotFound))?;
+        if total < amount_spent {
+            return Err(revert(PosError::InsufficientPaymentForAmountSpent));
+        }
+        let refund_amount = refund_ratio.apply(total - amount_spent);
+        let fees = total - refund_amount;
+
+        let rewards_purse = self.pos_purse(
+            REWARDS_PURSE_KEY,
+            PosError::RewardsPurseNotFound,
+            PosError::RewardsPurseKeyUnexpectedType,
+        )?;
+        let refund_purse = self.refund_purse()?;
+        // unset refund purse after reading it
+        self.context.remove_uref(REFUND_PURSE_KEY)?;
+
+        match (fee_handling, proposer) {
+            (FeeHandling::Burn, _) => {
+                if self.call_mint_burn(payment_purse, fees)?.is_err() {
+                    return Err(revert(PosError::FailedToBurnFees));
+                }
+            }
+            (FeeHandling::PayToProposer, Some(proposer)) => {
+                if let TransferResult::TransferError(_) =
+                    self.transfer_from_purse_to_account(payment_purse, proposer, fees)?
+                {
+                    return Err(revert(PosError::FailedTransferToProposer));
+                }
+            }
+            (FeeHandling::Accumulate, _) | (FeeHandling::PayToProposer, None) => {
+                if let PurseTransferResult::TransferError(_) =
+                    self.native_transfer_purse_to_purse(payment_purse, rewards_purse, fees)?
+                {
+                    return Err(revert(PosError::FailedTransferToRewardsPurse));
+                }
+            }
+        }
+
+        if !refund_amount.is_zero() {
+            let refunded_to_purse = match refund_purse {
+                Some(purse) => {
+                    // on refund failure, fall back to the account's main purse
+                    let transfer_result =
+                        self.native_transfer_purse_to_purse(payment_purse, purse, refund_amount)?;
+                    transfer_result == PurseTransferResult::TransferSuccessful
+                }
+                None => false,
+            };
+            if !refunded_to_purse {
+                if let TransferResult::TransferError(_) =
+                    self.transfer_from_purse_to_account(payment_purse, account, refund_amount)?
+                {
+                    return Err(revert(PosError::FailedTransferToAccountPurse));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Transfers between purses like the `transfer_from_purse_to_purse` host
+    /// function.
+    fn native_transfer_purse_to_purse(
+        &mut self,
+        source: PurseId,
+        target: PurseId,
+        amount: U512,
+    ) -> Result<PurseTransferResult, Trap> {
+        self.context.validate_transfer_authorization()?;
+        Ok(self.transfer_purse_to_purse(source, target, amount)?)
+    }
+}
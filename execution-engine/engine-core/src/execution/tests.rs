@@ -1,4 +1,4 @@
-use engine_shared::gas::Gas;
+use engine_shared::gas::{Gas, GasBreakdown, GasCategory};
 
 use super::Error;
 use crate::engine_state::execution_result::ExecutionResult;
@@ -8,10 +8,11 @@ fn on_fail_charge_test_helper<T>(
     success_cost: Gas,
     error_cost: Gas,
 ) -> ExecutionResult {
-    let _result = on_fail_charge!(f(), error_cost);
+    let _result = on_fail_charge!(f(), GasBreakdown::new(GasCategory::Opcodes, error_cost));
     ExecutionResult::Success {
         effect: Default::default(),
         cost: success_cost,
+        cost_breakdown: GasBreakdown::new(GasCategory::Opcodes, success_cost),
     }
 }
 
@@ -32,7 +33,14 @@ fn on_fail_charge_err_laziness_test() {
         error_cost,
     ) {
         ExecutionResult::Success { .. } => panic!("Should fail"),
-        ExecutionResult::Failure { cost, .. } => assert_eq!(cost, error_cost),
+        ExecutionResult::Failure {
+            cost,
+            cost_breakdown,
+            ..
+        } => {
+            assert_eq!(cost, error_cost);
+            assert_eq!(cost_breakdown.opcodes(), error_cost);
+        }
     }
 }
 #[test]
@@ -43,7 +51,8 @@ fn on_fail_charge_with_action() {
     use engine_shared::transform::Transform;
     let f = || {
         let input: Result<(), Error> = Err(Error::GasLimit);
-        on_fail_charge!(input, Gas::from_u64(456), {
+        let error_cost = GasBreakdown::new(GasCategory::Opcodes, Gas::from_u64(456));
+        on_fail_charge!(input, error_cost, {
             let mut effect = ExecutionEffect::default();
 
             effect.ops.insert(Key::Hash([42u8; 32]), Op::Read);
@@ -56,6 +65,7 @@ fn on_fail_charge_with_action() {
         ExecutionResult::Success {
             effect: Default::default(),
             cost: Gas::default(),
+            cost_breakdown: GasBreakdown::default(),
         }
     };
     match f() {
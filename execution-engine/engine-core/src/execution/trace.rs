@@ -0,0 +1,130 @@
+//! Opt-in recording of the host functions called while a deploy executes, for debugging contracts.
+//!
+//! Recording is started by the thread about to execute a deploy.  The contracts of a deploy
+//! execute on that thread, including the contracts they call, so every host function call of the
+//! deploy is recorded until the recording is finished.
+
+use std::cell::RefCell;
+
+use engine_shared::gas::Gas;
+
+/// A host function call recorded while tracing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HostCall {
+    /// The name the host function is imported by.
+    pub name: &'static str,
+    /// The wasm values the host function was called with.
+    pub args: String,
+    /// The gas charged while the host function ran, including by the contracts it called.
+    pub gas: Gas,
+}
+
+thread_local! {
+    static HOST_CALLS: RefCell<Option<Vec<HostCall>>> = RefCell::new(None);
+}
+
+/// Records the host function calls of the current thread until finished.
+#[must_use = "recording stops when dropped"]
+pub struct Recording {
+    previous: Option<Vec<HostCall>>,
+    finished: bool,
+}
+
+impl Recording {
+    /// Stops recording and returns the host function calls recorded, in the order they were made.
+    pub fn finish(mut self) -> Vec<HostCall> {
+        self.finished = true;
+        let previous = self.previous.take();
+        HOST_CALLS
+            .with(|host_calls| host_calls.replace(previous))
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        if !self.finished {
+            let previous = self.previous.take();
+            HOST_CALLS.with(|host_calls| host_calls.replace(previous));
+        }
+    }
+}
+
+/// Starts recording the host function calls of the current thread.
+pub fn start() -> Recording {
+    let previous = HOST_CALLS.with(|host_calls| host_calls.replace(Some(Vec::new())));
+    Recording {
+        previous,
+        finished: false,
+    }
+}
+
+/// Records the call of host function `name` if the current thread is recording, returning the
+/// index to pass to [`end_call`] once it returns.
+pub(crate) fn begin_call(name: &'static str, args: impl FnOnce() -> String) -> Option<usize> {
+    HOST_CALLS.with(|host_calls| {
+        host_calls.borrow_mut().as_mut().map(|host_calls| {
+            host_calls.push(HostCall {
+                name,
+                args: args(),
+                gas: Gas::default(),
+            });
+            host_calls.len() - 1
+        })
+    })
+}
+
+/// Sets the gas charged by the host function call recorded at `index`.
+pub(crate) fn end_call(index: usize, gas: Gas) {
+    HOST_CALLS.with(|host_calls| {
+        if let Some(host_call) = host_calls
+            .borrow_mut()
+            .as_mut()
+            .and_then(|host_calls| host_calls.get_mut(index))
+        {
+            host_call.gas = gas;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_record_only_while_recording() {
+        assert_eq!(begin_call("read_value", String::new), None);
+
+        let recording = start();
+        let index = begin_call("call_contract", || "I32(1)".to_string()).expect("should record");
+        let nested_index = begin_call("write", String::new).expect("should record");
+        end_call(nested_index, Gas::from_u64(2));
+        end_call(index, Gas::from_u64(5));
+
+        assert_eq!(
+            recording.finish(),
+            vec![
+                HostCall {
+                    name: "call_contract",
+                    args: "I32(1)".to_string(),
+                    gas: Gas::from_u64(5),
+                },
+                HostCall {
+                    name: "write",
+                    args: String::new(),
+                    gas: Gas::from_u64(2),
+                },
+            ]
+        );
+        assert_eq!(begin_call("read_value", String::new), None);
+    }
+
+    #[test]
+    fn should_stop_recording_when_dropped() {
+        {
+            let _recording = start();
+            assert!(begin_call("read_value", String::new).is_some());
+        }
+        assert_eq!(begin_call("read_value", String::new), None);
+    }
+}
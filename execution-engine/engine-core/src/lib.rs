@@ -14,6 +14,8 @@ extern crate parking_lot;
 extern crate pwasm_utils;
 extern crate rand;
 extern crate rand_chacha;
+extern crate serde;
+extern crate toml;
 extern crate wasmi;
 
 // internal dependencies
@@ -28,6 +30,8 @@ extern crate engine_wasm_prep;
 extern crate matches;
 #[cfg(test)]
 extern crate proptest;
+#[cfg(test)]
+extern crate tempfile;
 
 #[macro_use]
 extern crate num_derive;
@@ -38,6 +42,7 @@ use std::collections::BTreeMap;
 
 pub mod engine_state;
 pub mod execution;
+pub mod replay;
 pub mod resolvers;
 pub mod runtime_context;
 pub mod tracking_copy;
@@ -0,0 +1,173 @@
+//! Comparison of the effects of re-executed deploys with the effects recorded when they were first
+//! executed, to pinpoint why peers computed different post state hashes for the same block.
+
+use std::collections::{BTreeSet, HashMap};
+
+use contract_ffi::key::Key;
+use engine_shared::transform::Transform;
+
+/// A key whose transform differs between the recorded and the replayed effects of a deploy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransformDivergence {
+    pub key: Key,
+    /// The recorded transform, `None` if only the replay transformed `key`.
+    pub recorded: Option<Transform>,
+    /// The replayed transform, `None` if only the recording transformed `key`.
+    pub replayed: Option<Transform>,
+}
+
+/// The transforms of the deploy at `deploy_index` which diverge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeployDivergence {
+    pub deploy_index: usize,
+    pub transforms: Vec<TransformDivergence>,
+}
+
+/// The outcome of comparing the effects of the deploys of a replayed exec request with recorded
+/// ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    pub recorded_count: usize,
+    pub replayed_count: usize,
+    /// The deploys whose effects diverge, in order.  Deploys which were only recorded or only
+    /// replayed diverge on all their transforms.
+    pub divergences: Vec<DeployDivergence>,
+}
+
+impl ReplayReport {
+    /// Compares the transforms of each deploy, in order.
+    pub fn new(
+        recorded: &[HashMap<Key, Transform>],
+        replayed: &[HashMap<Key, Transform>],
+    ) -> ReplayReport {
+        let empty = HashMap::new();
+        let deploy_count = recorded.len().max(replayed.len());
+        let divergences = (0..deploy_count)
+            .filter_map(|deploy_index| {
+                let transforms = diff_transforms(
+                    recorded.get(deploy_index).unwrap_or(&empty),
+                    replayed.get(deploy_index).unwrap_or(&empty),
+                );
+                if transforms.is_empty() {
+                    None
+                } else {
+                    Some(DeployDivergence {
+                        deploy_index,
+                        transforms,
+                    })
+                }
+            })
+            .collect();
+        ReplayReport {
+            recorded_count: recorded.len(),
+            replayed_count: replayed.len(),
+            divergences,
+        }
+    }
+
+    /// Returns `true` if the replay reproduced the recorded effects of every deploy.
+    pub fn is_match(&self) -> bool {
+        self.recorded_count == self.replayed_count && self.divergences.is_empty()
+    }
+}
+
+/// Returns the keys `recorded` and `replayed` transform differently, ordered by key.
+pub fn diff_transforms(
+    recorded: &HashMap<Key, Transform>,
+    replayed: &HashMap<Key, Transform>,
+) -> Vec<TransformDivergence> {
+    let keys: BTreeSet<&Key> = recorded.keys().chain(replayed.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let recorded = recorded.get(key);
+            let replayed = replayed.get(key);
+            if recorded == replayed {
+                None
+            } else {
+                Some(TransformDivergence {
+                    key: *key,
+                    recorded: recorded.cloned(),
+                    replayed: replayed.cloned(),
+                })
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use contract_ffi::value::Value;
+
+    use super::*;
+
+    const KEY_1: Key = Key::Hash([1; 32]);
+    const KEY_2: Key = Key::Hash([2; 32]);
+    const KEY_3: Key = Key::Hash([3; 32]);
+
+    fn transforms(entries: &[(Key, Transform)]) -> HashMap<Key, Transform> {
+        entries.iter().cloned().collect()
+    }
+
+    #[test]
+    fn should_match_identical_effects() {
+        let effects = vec![transforms(&[(KEY_1, Transform::AddUInt64(1))])];
+
+        let report = ReplayReport::new(&effects, &effects);
+        assert!(report.is_match());
+        assert!(report.divergences.is_empty());
+    }
+
+    #[test]
+    fn should_report_diverging_transforms_by_key() {
+        let recorded = vec![
+            transforms(&[(KEY_1, Transform::AddUInt64(1))]),
+            transforms(&[
+                (KEY_1, Transform::Write(Value::Int32(1))),
+                (KEY_2, Transform::Identity),
+            ]),
+        ];
+        let replayed = vec![
+            transforms(&[(KEY_1, Transform::AddUInt64(1))]),
+            transforms(&[
+                (KEY_1, Transform::Write(Value::Int32(2))),
+                (KEY_3, Transform::Identity),
+            ]),
+        ];
+
+        let report = ReplayReport::new(&recorded, &replayed);
+        assert!(!report.is_match());
+        assert_eq!(
+            report.divergences,
+            vec![DeployDivergence {
+                deploy_index: 1,
+                transforms: vec![
+                    TransformDivergence {
+                        key: KEY_1,
+                        recorded: Some(Transform::Write(Value::Int32(1))),
+                        replayed: Some(Transform::Write(Value::Int32(2))),
+                    },
+                    TransformDivergence {
+                        key: KEY_2,
+                        recorded: Some(Transform::Identity),
+                        replayed: None,
+                    },
+                    TransformDivergence {
+                        key: KEY_3,
+                        recorded: None,
+                        replayed: Some(Transform::Identity),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn should_not_match_different_deploy_counts() {
+        let recorded = vec![HashMap::new(), HashMap::new()];
+        let replayed = vec![HashMap::new()];
+
+        let report = ReplayReport::new(&recorded, &replayed);
+        assert!(report.divergences.is_empty());
+        assert!(!report.is_match());
+    }
+}
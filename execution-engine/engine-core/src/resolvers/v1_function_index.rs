@@ -41,6 +41,75 @@ pub enum FunctionIndex {
     TransferFromPurseToPurseIndex = 34,
     GetBalanceIndex = 35,
     GetPhaseIndex = 36,
+    EmitEventIndex = 37,
+    GetBlockHeightIndex = 38,
+    AddContractVersionIndex = 39,
+    CallContractWithNamedArgsIndex = 40,
+    LoadNamedArgIndex = 41,
+    DictionaryGetIndex = 42,
+    DictionaryPutIndex = 43,
+    Blake2b256Index = 44,
+    Sha256Index = 45,
+    Ed25519VerifyIndex = 46,
+    Secp256k1RecoverIndex = 47,
+    GetEntropyIndex = 48,
+}
+
+impl FunctionIndex {
+    /// Returns the name contracts import the host function by.
+    pub fn name(&self) -> &'static str {
+        match self {
+            FunctionIndex::WriteFuncIndex => "write",
+            FunctionIndex::WriteLocalFuncIndex => "write_local",
+            FunctionIndex::ReadFuncIndex => "read_value",
+            FunctionIndex::ReadLocalFuncIndex => "read_value_local",
+            FunctionIndex::AddFuncIndex => "add",
+            FunctionIndex::NewFuncIndex => "new_uref",
+            FunctionIndex::GetReadFuncIndex => "get_read",
+            FunctionIndex::SerFnFuncIndex => "serialize_function",
+            FunctionIndex::GetFnFuncIndex => "get_function",
+            FunctionIndex::LoadArgFuncIndex => "load_arg",
+            FunctionIndex::GetArgFuncIndex => "get_arg",
+            FunctionIndex::RetFuncIndex => "ret",
+            FunctionIndex::GetCallResultFuncIndex => "get_call_result",
+            FunctionIndex::CallContractFuncIndex => "call_contract",
+            FunctionIndex::GetURefFuncIndex => "get_uref",
+            FunctionIndex::GasFuncIndex => "gas",
+            FunctionIndex::HasURefFuncIndex => "has_uref_name",
+            FunctionIndex::AddURefFuncIndex => "add_uref",
+            FunctionIndex::StoreFnIndex => "store_function",
+            FunctionIndex::ProtocolVersionFuncIndex => "protocol_version",
+            FunctionIndex::IsValidFnIndex => "is_valid",
+            FunctionIndex::RevertFuncIndex => "revert",
+            FunctionIndex::AddAssociatedKeyFuncIndex => "add_associated_key",
+            FunctionIndex::RemoveAssociatedKeyFuncIndex => "remove_associated_key",
+            FunctionIndex::UpdateAssociatedKeyFuncIndex => "update_associated_key",
+            FunctionIndex::SetActionThresholdFuncIndex => "set_action_threshold",
+            FunctionIndex::SerKnownURefs => "serialize_known_urefs",
+            FunctionIndex::ListKnownURefsIndex => "list_known_urefs",
+            FunctionIndex::RemoveURef => "remove_uref",
+            FunctionIndex::GetCallerIndex => "get_caller",
+            FunctionIndex::GetBlocktimeIndex => "get_blocktime",
+            FunctionIndex::CreatePurseIndex => "create_purse",
+            FunctionIndex::TransferToAccountIndex => "transfer_to_account",
+            FunctionIndex::TransferFromPurseToAccountIndex => "transfer_from_purse_to_account",
+            FunctionIndex::TransferFromPurseToPurseIndex => "transfer_from_purse_to_purse",
+            FunctionIndex::GetBalanceIndex => "get_balance",
+            FunctionIndex::GetPhaseIndex => "get_phase",
+            FunctionIndex::EmitEventIndex => "emit_event",
+            FunctionIndex::GetBlockHeightIndex => "get_block_height",
+            FunctionIndex::AddContractVersionIndex => "add_contract_version",
+            FunctionIndex::CallContractWithNamedArgsIndex => "call_contract_with_named_args",
+            FunctionIndex::LoadNamedArgIndex => "load_named_arg",
+            FunctionIndex::DictionaryGetIndex => "dictionary_get",
+            FunctionIndex::DictionaryPutIndex => "dictionary_put",
+            FunctionIndex::Blake2b256Index => "blake2b256",
+            FunctionIndex::Sha256Index => "sha256",
+            FunctionIndex::Ed25519VerifyIndex => "ed25519_verify",
+            FunctionIndex::Secp256k1RecoverIndex => "secp256k1_recover",
+            FunctionIndex::GetEntropyIndex => "get_entropy",
+        }
+    }
 }
 
 impl Into<usize> for FunctionIndex {
@@ -63,6 +132,12 @@ mod tests {
     use super::FunctionIndex;
     use std::convert::TryFrom;
 
+    #[test]
+    fn should_name_host_functions() {
+        assert_eq!(FunctionIndex::ReadFuncIndex.name(), "read_value");
+        assert_eq!(FunctionIndex::GetEntropyIndex.name(), "get_entropy");
+    }
+
     #[test]
     fn primitive_to_enum() {
         let element = FunctionIndex::try_from(20).expect("Unable to create enum from number");
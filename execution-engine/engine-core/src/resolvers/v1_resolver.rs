@@ -189,6 +189,54 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 1][..], None),
                 FunctionIndex::GetPhaseIndex.into(),
             ),
+            "emit_event" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], None),
+                FunctionIndex::EmitEventIndex.into(),
+            ),
+            "get_block_height" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], None),
+                FunctionIndex::GetBlockHeightIndex.into(),
+            ),
+            "add_contract_version" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
+                FunctionIndex::AddContractVersionIndex.into(),
+            ),
+            "call_contract_with_named_args" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
+                FunctionIndex::CallContractWithNamedArgsIndex.into(),
+            ),
+            "load_named_arg" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::LoadNamedArgIndex.into(),
+            ),
+            "dictionary_get" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::DictionaryGetIndex.into(),
+            ),
+            "dictionary_put" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 6][..], None),
+                FunctionIndex::DictionaryPutIndex.into(),
+            ),
+            "blake2b256" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], None),
+                FunctionIndex::Blake2b256Index.into(),
+            ),
+            "sha256" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], None),
+                FunctionIndex::Sha256Index.into(),
+            ),
+            "ed25519_verify" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::Ed25519VerifyIndex.into(),
+            ),
+            "secp256k1_recover" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::Secp256k1RecoverIndex.into(),
+            ),
+            "get_entropy" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], None),
+                FunctionIndex::GetEntropyIndex.into(),
+            ),
             _ => {
                 return Err(InterpreterError::Function(format!(
                     "host module doesn't export function with name {}",
@@ -8,6 +8,8 @@ use blake2::digest::{Input, VariableOutput};
 use blake2::VarBlake2b;
 
 use contract_ffi::bytesrepr::{deserialize, ToBytes};
+use contract_ffi::contract_api::runtime_args::RuntimeArgs;
+use contract_ffi::contract_api::ENTROPY_LENGTH;
 use contract_ffi::execution::Phase;
 use contract_ffi::key::{Key, LOCAL_SEED_SIZE};
 use contract_ffi::uref::{AccessRights, URef};
@@ -16,12 +18,13 @@ use contract_ffi::value::account::{
     SetThresholdFailure, UpdateKeyFailure, Weight,
 };
 use contract_ffi::value::{Contract, Value};
-use engine_shared::gas::Gas;
+use engine_shared::gas::{Gas, GasBreakdown, GasCategory};
+use engine_shared::key_limits::KeyLimits;
 use engine_shared::newtypes::{CorrelationId, Validated};
 use engine_storage::global_state::StateReader;
 
-use crate::engine_state::execution_effect::ExecutionEffect;
-use crate::execution::{AddressGenerator, Error};
+use crate::engine_state::execution_effect::{Event, ExecutionEffect};
+use crate::execution::{AddressGenerator, EntropyGenerator, Error};
 use crate::tracking_copy::{AddResult, TrackingCopy};
 use crate::Address;
 
@@ -38,16 +41,23 @@ pub struct RuntimeContext<'a, R> {
     // Original account for read only tasks taken before execution
     account: &'a Account,
     args: Vec<Vec<u8>>,
+    named_args: RuntimeArgs,
     authorization_keys: BTreeSet<PublicKey>,
     // Key pointing to the entity we are currently running
     //(could point at an account or contract in the global state)
     base_key: Key,
     blocktime: BlockTime,
+    block_height: u64,
     deploy_hash: [u8; 32],
     gas_limit: Gas,
     gas_counter: Gas,
+    // Gas charged so far by this context, split by category
+    gas_breakdown: GasBreakdown,
     fn_store_id: u32,
     address_generator: Rc<RefCell<AddressGenerator>>,
+    // Shared with the contexts of contracts called from this one
+    entropy_generator: Rc<RefCell<EntropyGenerator>>,
+    key_limits: KeyLimits,
     protocol_version: u64,
     correlation_id: CorrelationId,
     phase: Phase,
@@ -67,6 +77,7 @@ where
         account: &'a Account,
         base_key: Key,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
         gas_counter: Gas,
@@ -76,20 +87,30 @@ where
         correlation_id: CorrelationId,
         phase: Phase,
     ) -> Self {
+        let entropy_generator = Rc::new(RefCell::new(EntropyGenerator::new(
+            deploy_hash,
+            blocktime,
+            phase,
+        )));
         RuntimeContext {
             state,
             uref_lookup,
             known_urefs,
             args,
+            named_args: RuntimeArgs::new(),
             account,
             authorization_keys,
             blocktime,
+            block_height,
             deploy_hash,
             base_key,
             gas_limit,
             gas_counter,
+            gas_breakdown: GasBreakdown::default(),
             fn_store_id,
             address_generator,
+            entropy_generator,
+            key_limits: KeyLimits::default(),
             protocol_version,
             correlation_id,
             phase,
@@ -200,6 +221,10 @@ where
         self.blocktime
     }
 
+    pub fn get_block_height(&self) -> u64 {
+        self.block_height
+    }
+
     pub fn get_deployhash(&self) -> [u8; 32] {
         self.deploy_hash
     }
@@ -216,10 +241,43 @@ where
         &self.args
     }
 
+    pub fn named_args(&self) -> &RuntimeArgs {
+        &self.named_args
+    }
+
+    /// Sets the arguments passed by name to the contract running in this context.
+    pub fn set_named_args(&mut self, named_args: RuntimeArgs) {
+        self.named_args = named_args;
+    }
+
     pub fn address_generator(&self) -> Rc<RefCell<AddressGenerator>> {
         Rc::clone(&self.address_generator)
     }
 
+    pub fn entropy_generator(&self) -> Rc<RefCell<EntropyGenerator>> {
+        Rc::clone(&self.entropy_generator)
+    }
+
+    /// Makes this context draw entropy from `entropy_generator`, so that a called contract
+    /// continues the sequence of its caller instead of repeating it.
+    pub fn set_entropy_generator(&mut self, entropy_generator: Rc<RefCell<EntropyGenerator>>) {
+        self.entropy_generator = entropy_generator;
+    }
+
+    pub fn key_limits(&self) -> KeyLimits {
+        self.key_limits
+    }
+
+    /// Makes this context enforce `key_limits` when keys are added to the account it runs as.
+    pub fn set_key_limits(&mut self, key_limits: KeyLimits) {
+        self.key_limits = key_limits;
+    }
+
+    /// Returns the next deterministic 32-byte seed of the deploy.
+    pub fn next_entropy(&mut self) -> [u8; ENTROPY_LENGTH] {
+        self.entropy_generator.borrow_mut().next_entropy()
+    }
+
     pub fn state(&self) -> Rc<RefCell<TrackingCopy<R>>> {
         Rc::clone(&self.state)
     }
@@ -236,6 +294,14 @@ where
         self.gas_counter = new_gas_counter;
     }
 
+    pub fn gas_breakdown(&self) -> GasBreakdown {
+        self.gas_breakdown
+    }
+
+    pub fn add_gas_breakdown(&mut self, category: GasCategory, amount: Gas) {
+        self.gas_breakdown.add(category, amount);
+    }
+
     pub fn inc_fn_store_id(&mut self) {
         self.fn_store_id += 1;
     }
@@ -298,6 +364,16 @@ where
 
     /// Adds `key` to the map of named keys of current context.
     pub fn add_uref(&mut self, name: String, key: Key) -> Result<(), Error> {
+        // Only accounts are limited; contracts such as the mint record a named key per purse.
+        if let Key::Account(_) = self.base_key() {
+            let max_named_keys = self.key_limits.max_named_keys();
+            if !self.uref_lookup.contains_key(&name)
+                && self.uref_lookup.len() >= max_named_keys as usize
+            {
+                return Err(Error::MaxNamedKeysLimit(max_named_keys));
+            }
+        }
+
         // No need to perform actual validation on the base key because an account or
         // contract (i.e. the element stored under `base_key`) is allowed to add
         // new named keys to itself.
@@ -344,6 +420,38 @@ where
         Ok(())
     }
 
+    /// Reads the item stored under `item_key` in the dictionary with the given
+    /// seed, which must be a known uref with read access.
+    pub fn read_dictionary(&mut self, seed: URef, item_key: &str) -> Result<Option<Value>, Error> {
+        let seed_key = Key::URef(seed);
+        self.validate_readable(&seed_key)?;
+        self.validate_key(&seed_key)?;
+        let validated_key = Validated::new(Key::dictionary(seed, item_key), Validated::valid)?;
+        self.state
+            .borrow_mut()
+            .read(self.correlation_id, &validated_key)
+            .map_err(Into::into)
+    }
+
+    /// Writes `value` under `item_key` in the dictionary with the given seed,
+    /// which must be a known uref with write access.
+    pub fn write_dictionary(
+        &mut self,
+        seed: URef,
+        item_key: &str,
+        value: Value,
+    ) -> Result<(), Error> {
+        let seed_key = Key::URef(seed);
+        self.validate_writeable(&seed_key)?;
+        self.validate_key(&seed_key)?;
+        let validated_key = Validated::new(Key::dictionary(seed, item_key), Validated::valid)?;
+        let validated_value = Validated::new(value, |value| self.validate_keys(&value))?;
+        self.state
+            .borrow_mut()
+            .write(validated_key, validated_value);
+        Ok(())
+    }
+
     pub fn read_gs(&mut self, key: &Key) -> Result<Option<Value>, Error> {
         let validated_key = Validated::new(*key, |key| {
             self.validate_readable(&key).and(self.validate_key(&key))
@@ -452,6 +560,12 @@ where
         self.state.borrow_mut().effect()
     }
 
+    /// Records an event emitted by the current context under the given topic.
+    pub fn emit_event(&mut self, topic: String, payload: Vec<u8>) {
+        let event = Event::new(self.base_key(), topic, payload);
+        self.state.borrow_mut().emit_event(event);
+    }
+
     /// Validates whether keys used in the `value` are not forged.
     pub fn validate_keys(&self, value: &Value) -> Result<(), Error> {
         match value {
@@ -479,9 +593,29 @@ where
                 .urefs_lookup()
                 .values()
                 .try_for_each(|key| self.validate_key(key)),
+            Value::ContractPackage(package) => package
+                .versions()
+                .values()
+                .try_for_each(|key| self.validate_key(key)),
+            Value::CLValue(cl_value) => cl_value
+                .keys()?
+                .iter()
+                .try_for_each(|key| self.validate_key(key)),
         }
     }
 
+    /// Validates that the current context may withdraw from the `source` purse
+    /// and deposit into the `target` purse, i.e. that neither is forged, the
+    /// source is writeable and the target is addable.
+    pub fn validate_purse_transfer(&self, source: URef, target: URef) -> Result<(), Error> {
+        let source_key = Key::URef(source);
+        self.validate_key(&source_key)?;
+        self.validate_writeable(&source_key)?;
+        let target_key = Key::URef(target);
+        self.validate_key(&target_key)?;
+        self.validate_addable(&target_key)
+    }
+
     /// Validates whether key is not forged (whether it can be found in the
     /// `known_urefs`) and whether the version of a key that contract wants
     /// to use, has access rights that are less powerful than access rights'
@@ -656,8 +790,9 @@ where
         let mut account: Account = self.read_gs_typed(&key)?;
 
         // Exit early in case of error without updating global state
+        let max_associated_keys = self.key_limits.max_associated_keys() as usize;
         account
-            .add_associated_key(public_key, weight)
+            .add_associated_key_with_limit(public_key, weight, max_associated_keys)
             .map_err(Error::from)?;
 
         let validated_uref = Validated::new(key, Validated::valid)?;
@@ -751,6 +886,25 @@ where
         Ok(())
     }
 
+    /// Checks whether the authorization keys meet the transfer threshold of
+    /// the account.
+    ///
+    /// Only session code running in the account's own context is subject to
+    /// the threshold; payment code has to be able to pay for the deploy with
+    /// the keys which passed the deployment threshold.
+    pub fn validate_transfer_authorization(&self) -> Result<(), Error> {
+        if self.phase != Phase::Session || self.base_key() != Key::Account(self.account().pub_key())
+        {
+            return Ok(());
+        }
+
+        if !self.account().can_transfer_with(&self.authorization_keys) {
+            return Err(Error::TransferAuthorizationFailure);
+        }
+
+        Ok(())
+    }
+
     pub fn set_action_threshold(
         &mut self,
         action_type: ActionType,
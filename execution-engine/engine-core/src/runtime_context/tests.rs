@@ -14,6 +14,7 @@ use contract_ffi::value::account::{
 };
 use contract_ffi::value::{self, Account, Contract, Value};
 use engine_shared::gas::Gas;
+use engine_shared::key_limits::KeyLimits;
 use engine_shared::newtypes::CorrelationId;
 use engine_shared::transform::Transform;
 use engine_storage::global_state::in_memory::{InMemoryGlobalState, InMemoryGlobalStateView};
@@ -114,6 +115,7 @@ fn mock_runtime_context<'a>(
         &account,
         base_key,
         BlockTime(0),
+        0,
         [1u8; 32],
         Gas::default(),
         Gas::default(),
@@ -417,6 +419,7 @@ fn contract_key_addable_valid() {
         &account,
         contract_key,
         BlockTime(0),
+        0,
         DEPLOY_HASH,
         Gas::default(),
         Gas::default(),
@@ -473,6 +476,7 @@ fn contract_key_addable_invalid() {
         &account,
         other_contract_key,
         BlockTime(0),
+        0,
         DEPLOY_HASH,
         Gas::default(),
         Gas::default(),
@@ -807,6 +811,48 @@ fn should_verify_ownership_before_setting_action_threshold() {
     let _ = test(known_urefs, query);
 }
 
+#[test]
+fn should_limit_associated_keys() {
+    let known_urefs = HashMap::new();
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context.set_key_limits(KeyLimits::new(2, 10));
+
+        runtime_context
+            .add_associated_key(PublicKey::new([42; 32]), Weight::new(1))
+            .expect("should add key up to the limit");
+
+        match runtime_context.add_associated_key(PublicKey::new([43; 32]), Weight::new(1)) {
+            Err(Error::AddKeyFailure(AddKeyFailure::MaxKeysLimit)) => (),
+            other => panic!("expected MaxKeysLimit, got {:?}", other),
+        }
+
+        Ok(())
+    };
+    test(known_urefs, query).unwrap();
+}
+
+#[test]
+fn should_limit_named_keys_of_account() {
+    let known_urefs = HashMap::new();
+    let (key, account) = mock_account([0u8; 32]);
+    let address_generator = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let mut uref_map = iter::once((String::from("Foo"), Key::Hash([1; 32]))).collect();
+    let mut runtime_context =
+        mock_runtime_context(&account, key, &mut uref_map, known_urefs, address_generator);
+    runtime_context.set_key_limits(KeyLimits::new(10, 1));
+
+    // Replacing the key under an existing name doesn't grow the account
+    runtime_context
+        .add_uref(String::from("Foo"), Key::Hash([2; 32]))
+        .expect("should replace named key");
+
+    match runtime_context.add_uref(String::from("Bar"), Key::Hash([3; 32])) {
+        Err(Error::MaxNamedKeysLimit(1)) => (),
+        other => panic!("expected MaxNamedKeysLimit, got {:?}", other),
+    }
+    assert!(!runtime_context.contains_uref("Bar"));
+}
+
 #[test]
 fn can_roundtrip_key_value_pairs_into_local_state() {
     let known_urefs = HashMap::new();
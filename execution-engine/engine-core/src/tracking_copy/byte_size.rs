@@ -2,7 +2,7 @@ use std::collections::btree_map::BTreeMap;
 
 use contract_ffi::bytesrepr::I32_SIZE;
 use contract_ffi::key::Key;
-use contract_ffi::value::{Account, Contract, Value};
+use contract_ffi::value::{Account, CLValue, Contract, ContractPackage, Value};
 
 /// Returns byte size of the element - both heap size and stack size.
 pub trait ByteSize {
@@ -68,6 +68,8 @@ impl ByteSize for Value {
                 Value::NamedKey(name, _key) => name.heap_size(),
                 Value::Account(account) => account.heap_size(),
                 Value::Contract(contract) => contract.heap_size(),
+                Value::ContractPackage(package) => package.heap_size(),
+                Value::CLValue(cl_value) => cl_value.heap_size(),
             }
     }
 }
@@ -96,6 +98,21 @@ impl HeapSizeOf for Contract {
     }
 }
 
+// NOTE: Versions are stored inline in the tree's nodes, so we count their stack size.
+impl HeapSizeOf for ContractPackage {
+    fn heap_size(&self) -> usize {
+        self.versions().len() * (std::mem::size_of::<u32>() + std::mem::size_of::<Key>())
+            + self.disabled_versions().len() * std::mem::size_of::<u32>()
+    }
+}
+
+// NOTE: We're ignoring the boxed inner types of a `CLType`.
+impl HeapSizeOf for CLValue {
+    fn heap_size(&self) -> usize {
+        self.inner_bytes().len()
+    }
+}
+
 // NOTE: We're ignoring size of the tree's nodes.
 impl<K: HeapSizeOf, V: HeapSizeOf> HeapSizeOf for BTreeMap<K, V> {
     fn heap_size(&self) -> usize {
@@ -1,12 +1,18 @@
-use contract_ffi::bytesrepr::ToBytes;
+use contract_ffi::bytesrepr::{deserialize, ToBytes};
 use contract_ffi::key::Key;
 use contract_ffi::uref::URef;
+use contract_ffi::value::account::BlockTime;
 use contract_ffi::value::{Account, Contract, Value};
 use engine_shared::motes::Motes;
 use engine_shared::newtypes::CorrelationId;
 use engine_shared::transform::TypeMismatch;
 use engine_storage::global_state::StateReader;
+use engine_storage::protocol_data::ProtocolData;
 
+use crate::engine_state::{
+    deploy_execution_key, executed_deploy_key, protocol_data_key, protocol_version_key,
+    EXECUTED_DEPLOY_WINDOW_MILLIS,
+};
 use crate::execution;
 use crate::tracking_copy::{QueryResult, TrackingCopy};
 
@@ -83,6 +89,35 @@ pub trait TrackingCopyExt<R> {
         correlation_id: CorrelationId,
         key: Key,
     ) -> Result<Contract, Self::Error>;
+
+    /// Gets the protocol version recorded in global state, if any
+    fn get_protocol_version(
+        &mut self,
+        correlation_id: CorrelationId,
+    ) -> Result<Option<u64>, Self::Error>;
+
+    /// Gets the protocol data recorded in global state, if any
+    fn get_protocol_data(
+        &mut self,
+        correlation_id: CorrelationId,
+    ) -> Result<Option<ProtocolData>, Self::Error>;
+
+    /// Checks whether the deploy with the given hash has been executed in the window of block time
+    /// of `blocktime` or in the previous one
+    fn is_deploy_executed(
+        &mut self,
+        correlation_id: CorrelationId,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+    ) -> Result<bool, Self::Error>;
+
+    /// Checks whether the execution of the deploy with the given hash is permanently recorded,
+    /// whenever it was executed
+    fn is_deploy_execution_recorded(
+        &mut self,
+        correlation_id: CorrelationId,
+        deploy_hash: [u8; 32],
+    ) -> Result<bool, Self::Error>;
 }
 
 impl<R: StateReader<Key, Value>> TrackingCopyExt<R> for TrackingCopy<R>
@@ -122,11 +157,13 @@ where
             .query(correlation_id, balance_mapping_key, &[])
             .map_err(Into::into)?
         {
-            QueryResult::Success(Value::Key(key)) => Ok(key),
-            QueryResult::Success(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
-                "Value::Key".to_string(),
-                other.type_string(),
-            ))),
+            QueryResult::Success {
+                value: Value::Key(key),
+                ..
+            } => Ok(key),
+            QueryResult::Success { value: other, .. } => Err(execution::Error::TypeMismatch(
+                TypeMismatch::new("Value::Key".to_string(), other.type_string()),
+            )),
             QueryResult::ValueNotFound(msg) => Err(execution::Error::URefNotFound(msg)),
         }
     }
@@ -141,11 +178,13 @@ where
             Err(_) => return Err(execution::Error::KeyNotFound(key)),
         };
         match query_result {
-            QueryResult::Success(Value::UInt512(balance)) => Ok(Motes::new(balance)),
-            QueryResult::Success(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
-                "Value::UInt512".to_string(),
-                other.type_string(),
-            ))),
+            QueryResult::Success {
+                value: Value::UInt512(balance),
+                ..
+            } => Ok(Motes::new(balance)),
+            QueryResult::Success { value: other, .. } => Err(execution::Error::TypeMismatch(
+                TypeMismatch::new("Value::UInt512".to_string(), other.type_string()),
+            )),
             QueryResult::ValueNotFound(_) => Err(execution::Error::KeyNotFound(key)),
         }
     }
@@ -187,4 +226,64 @@ where
         };
         Ok(contract)
     }
+    fn get_protocol_version(
+        &mut self,
+        correlation_id: CorrelationId,
+    ) -> Result<Option<u64>, Self::Error> {
+        let key = protocol_version_key();
+        match self.get(correlation_id, &key).map_err(Into::into)? {
+            Some(Value::UInt64(protocol_version)) => Ok(Some(protocol_version)),
+            Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "Value::UInt64".to_string(),
+                other.type_string(),
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    fn get_protocol_data(
+        &mut self,
+        correlation_id: CorrelationId,
+    ) -> Result<Option<ProtocolData>, Self::Error> {
+        let key = protocol_data_key();
+        match self.get(correlation_id, &key).map_err(Into::into)? {
+            Some(Value::ByteArray(bytes)) => Ok(Some(deserialize(&bytes)?)),
+            Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "Value::ByteArray".to_string(),
+                other.type_string(),
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    fn is_deploy_executed(
+        &mut self,
+        correlation_id: CorrelationId,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+    ) -> Result<bool, Self::Error> {
+        let previous_blocktime =
+            BlockTime(blocktime.0.saturating_sub(EXECUTED_DEPLOY_WINDOW_MILLIS));
+        for blocktime in &[blocktime, previous_blocktime] {
+            let key = executed_deploy_key(*blocktime, deploy_hash);
+            if self
+                .get(correlation_id, &key)
+                .map_err(Into::into)?
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn is_deploy_execution_recorded(
+        &mut self,
+        correlation_id: CorrelationId,
+        deploy_hash: [u8; 32],
+    ) -> Result<bool, Self::Error> {
+        let key = deploy_execution_key(deploy_hash);
+        let maybe_value = self.get(correlation_id, &key).map_err(Into::into)?;
+        Ok(maybe_value.is_some())
+    }
 }
@@ -11,12 +11,14 @@ use linked_hash_map::LinkedHashMap;
 use parking_lot::Mutex;
 
 use contract_ffi::key::Key;
+use contract_ffi::uref::AccessRights;
 use contract_ffi::value::Value;
 use engine_shared::newtypes::{CorrelationId, Validated};
 use engine_shared::transform::{self, Transform, TypeMismatch};
 use engine_storage::global_state::StateReader;
+use engine_storage::metrics;
 
-use crate::engine_state::execution_effect::ExecutionEffect;
+use crate::engine_state::execution_effect::{Event, ExecutionEffect};
 use crate::engine_state::op::Op;
 
 pub use self::ext::TrackingCopyExt;
@@ -25,7 +27,13 @@ use self::meter::Meter;
 
 #[derive(Debug)]
 pub enum QueryResult {
-    Success(Value),
+    Success {
+        value: Value,
+        /// The access rights to the URef the value is stored under, as held by the base key or
+        /// the account or contract naming it at the end of the path.  `None` if the value is not
+        /// stored under a URef, or the URef carries no access rights.
+        access_rights: Option<AccessRights>,
+    },
     ValueNotFound(String),
 }
 
@@ -95,6 +103,7 @@ pub struct TrackingCopy<R> {
     cache: TrackingCopyCache<HeapSize>,
     ops: HashMap<Key, Op>,
     fns: HashMap<Key, Transform>,
+    events: Vec<Event>,
 }
 
 #[derive(Debug)]
@@ -113,6 +122,7 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
                                                                  * limit? */
             ops: HashMap::new(),
             fns: HashMap::new(),
+            events: Vec::new(),
         }
     }
 
@@ -142,8 +152,10 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
         k: &Key,
     ) -> Result<Option<Value>, R::Error> {
         if let Some(value) = self.cache.get(k) {
+            metrics::storage_metrics().record_cache_hit();
             return Ok(Some(value.to_owned()));
         }
+        metrics::storage_metrics().record_cache_miss();
         if let Some(value) = self.reader.read(correlation_id, k)? {
             self.cache.insert_read(*k, value.to_owned());
             Ok(Some(value))
@@ -221,8 +233,14 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
         }
     }
 
+    /// Records an event emitted by a contract. Events are reported in the
+    /// effect, but have no impact on global state.
+    pub fn emit_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
     pub fn effect(&self) -> ExecutionEffect {
-        ExecutionEffect::new(self.ops.clone(), self.fns.clone())
+        ExecutionEffect::new(self.ops.clone(), self.fns.clone()).with_events(self.events.clone())
     }
 
     pub fn query(
@@ -241,13 +259,13 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
             ))),
             Some(base_value) => {
                 let result = path.iter().enumerate().try_fold(
-                    base_value,
+                    (base_key, base_value),
                     // We encode the two possible short-circuit conditions with
                     // Result<(usize, String), Error>, where the Ok(_) case corresponds to
                     // QueryResult::ValueNotFound and Err(_) corresponds to
                     // a storage-related error. The information in the Ok(_) case is used
                     // to build an informative error message about why the query was not successful.
-                    |current_value, (i, name)| -> Result<Value, Result<(usize, String), R::Error>> {
+                    |(current_key, current_value), (i, name)| -> Result<(Key, Value), Result<(usize, String), R::Error>> {
                         match current_value {
                             Value::Account(account) => {
                                 if let Some(key) = account.urefs_lookup().get(name) {
@@ -267,6 +285,17 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
                                 }
                             }
 
+                            // A uref holding `Unit` is the seed of a dictionary, whose items are
+                            // looked up by their key.
+                            Value::Unit => match current_key {
+                                Key::URef(seed) => {
+                                    let key = Key::dictionary(seed, name);
+                                    let validated_key = Validated::new(key, Validated::valid)?;
+                                    self.read_key_or_stop(correlation_id, validated_key, i)
+                                }
+                                _ => Err(Ok((i, format!("Name {} cannot be followed from a Unit value which is not a dictionary seed. Value found at path:", name)))),
+                            },
+
                             other => Err(
                                 Ok((i, format!("Name {} cannot be followed from value {:?} because it is neither an account nor contract. Value found at path:", name, other)))
                                 ),
@@ -275,7 +304,10 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
                 );
 
                 match result {
-                    Ok(value) => Ok(QueryResult::Success(value)),
+                    Ok((key, value)) => Ok(QueryResult::Success {
+                        value,
+                        access_rights: key.as_uref().and_then(|uref| uref.access_rights()),
+                    }),
                     Err(Ok((i, s))) => Ok(QueryResult::ValueNotFound(
                         self.error_path_msg(base_key, path, s, i),
                     )),
@@ -290,10 +322,10 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
         correlation_id: CorrelationId,
         key: Validated<Key>,
         i: usize,
-    ) -> Result<Value, Result<(usize, String), R::Error>> {
+    ) -> Result<(Key, Value), Result<(usize, String), R::Error>> {
         match self.read(correlation_id, &key) {
             // continue recursion
-            Ok(Some(value)) => Ok(value),
+            Ok(Some(value)) => Ok((*key, value)),
             // key not found in the global state; stop recursion
             Ok(None) => Err(Ok((i, format!("Name {:?} not found: ", *key)))),
             // global state access error; stop recursion
@@ -360,7 +360,7 @@ proptest! {
         let view = gs.checkout(root_hash).unwrap().unwrap();
         let mut tc = TrackingCopy::new(view);
         let empty_path = Vec::new();
-        if let Ok(QueryResult::Success(result)) = tc.query(correlation_id, k, &empty_path) {
+        if let Ok(QueryResult::Success { value: result, .. }) = tc.query(correlation_id, k, &empty_path) {
             assert_eq!(v, result);
         } else {
             panic!("Query failed when it should not have!");
@@ -394,7 +394,7 @@ proptest! {
         let view = gs.checkout(root_hash).unwrap().unwrap();
         let mut tc = TrackingCopy::new(view);
         let path = vec!(name.clone());
-        if let Ok(QueryResult::Success(result)) = tc.query(correlation_id, contract_key, &path) {
+        if let Ok(QueryResult::Success { value: result, .. }) = tc.query(correlation_id, contract_key, &path) {
             assert_eq!(v, result);
         } else {
             panic!("Query failed when it should not have!");
@@ -437,8 +437,9 @@ proptest! {
         let view = gs.checkout(root_hash).unwrap().unwrap();
         let mut tc = TrackingCopy::new(view);
         let path = vec!(name.clone());
-        if let Ok(QueryResult::Success(result)) = tc.query(correlation_id, account_key, &path) {
+        if let Ok(QueryResult::Success { value: result, access_rights }) = tc.query(correlation_id, account_key, &path) {
             assert_eq!(v, result);
+            assert_eq!(access_rights, k.as_uref().and_then(|uref| uref.access_rights()));
         } else {
             panic!("Query failed when it should not have!");
         }
@@ -490,12 +491,42 @@ proptest! {
         let view = gs.checkout(root_hash).unwrap().unwrap();
         let mut tc = TrackingCopy::new(view);
         let path = vec!(contract_name, state_name);
-        if let Ok(QueryResult::Success(result)) = tc.query(correlation_id, account_key, &path) {
+        if let Ok(QueryResult::Success { value: result, access_rights }) = tc.query(correlation_id, account_key, &path) {
             assert_eq!(v, result);
+            assert_eq!(access_rights, k.as_uref().and_then(|uref| uref.access_rights()));
         } else {
             panic!("Query failed when it should not have!");
         }
     }
+
+    #[test]
+    fn query_dictionary_item(
+        seed in uref_arb(), // seed of the dictionary
+        v in value_arb(), // value of the item
+        item_key in "\\PC*", // key of the item
+        missing_item_key in "\\PC*",
+    ) {
+        let correlation_id = CorrelationId::new();
+        let seed_key = Key::URef(seed);
+        let item_storage_key = Key::dictionary(seed, &item_key);
+
+        let (gs, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &[
+            (seed_key, Value::Unit),
+            (item_storage_key, v.to_owned()),
+        ]).unwrap();
+        let view = gs.checkout(root_hash).unwrap().unwrap();
+        let mut tc = TrackingCopy::new(view);
+        if let Ok(QueryResult::Success { value: result, .. }) = tc.query(correlation_id, seed_key, &[item_key.clone()]) {
+            assert_eq!(v, result);
+        } else {
+            panic!("Query failed when it should not have!");
+        }
+
+        if missing_item_key != item_key {
+            let result = tc.query(correlation_id, seed_key, &[missing_item_key]);
+            assert_matches!(result, Ok(QueryResult::ValueNotFound(_)));
+        }
+    }
 }
 
 #[test]
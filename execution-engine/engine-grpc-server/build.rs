@@ -1,12 +1,14 @@
 extern crate protoc_rust_grpc;
 
 fn main() {
+    println!("cargo:rerun-if-changed=../../protobuf/grpc/health/v1/health.proto");
     println!("cargo:rerun-if-changed=../../protobuf/io/casperlabs/casper/consensus/state.proto");
     println!("cargo:rerun-if-changed=../../protobuf/io/casperlabs/ipc/ipc.proto");
     println!("cargo:rerun-if-changed=../../protobuf/io/casperlabs/ipc/transforms.proto");
     protoc_rust_grpc::run(protoc_rust_grpc::Args {
         out_dir: "src/engine_server",
         input: &[
+            "../../protobuf/grpc/health/v1/health.proto",
             "../../protobuf/io/casperlabs/casper/consensus/state.proto",
             "../../protobuf/io/casperlabs/ipc/ipc.proto",
             "../../protobuf/io/casperlabs/ipc/transforms.proto",
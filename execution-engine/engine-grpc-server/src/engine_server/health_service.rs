@@ -0,0 +1,217 @@
+//! The standard `grpc.health.v1.Health` service, reporting whether the engine is ready to serve.
+
+use std::sync::{Arc, Mutex};
+
+use futures::sync::mpsc;
+use futures::Stream;
+
+use super::health::{HealthCheckRequest, HealthCheckResponse, HealthCheckResponse_ServingStatus};
+use super::health_grpc::Health;
+
+/// The name the execution engine service is checked by, as opposed to the whole server which is
+/// checked by the empty name.
+pub const EXECUTION_ENGINE_SERVICE_NAME: &str = "io.casperlabs.ipc.ExecutionEngineService";
+
+struct State {
+    serving: bool,
+    watchers: Vec<mpsc::UnboundedSender<HealthCheckResponse>>,
+}
+
+/// Reports the server and the execution engine service as serving once [`set_serving`] was called
+/// with `true`, and as not serving until then.
+///
+/// Clones share their status, so a clone can be registered with each server while the original is
+/// kept to update it.
+///
+/// [`set_serving`]: HealthService::set_serving
+#[derive(Clone)]
+pub struct HealthService {
+    state: Arc<Mutex<State>>,
+}
+
+impl HealthService {
+    pub fn new() -> Self {
+        HealthService {
+            state: Arc::new(Mutex::new(State {
+                serving: false,
+                watchers: Vec::new(),
+            })),
+        }
+    }
+
+    pub fn is_serving(&self) -> bool {
+        self.state
+            .lock()
+            .expect("health state lock poisoned")
+            .serving
+    }
+
+    /// Sets whether the engine is serving, notifying the clients watching the status of the change.
+    pub fn set_serving(&self, serving: bool) {
+        let mut state = self.state.lock().expect("health state lock poisoned");
+        if state.serving == serving {
+            return;
+        }
+        state.serving = serving;
+        let response = response(serving_status(serving));
+        // Drops the watchers whose clients went away
+        state
+            .watchers
+            .retain(|watcher| watcher.unbounded_send(response.clone()).is_ok());
+    }
+}
+
+impl Default for HealthService {
+    fn default() -> Self {
+        HealthService::new()
+    }
+}
+
+fn is_known_service(service: &str) -> bool {
+    service.is_empty() || service == EXECUTION_ENGINE_SERVICE_NAME
+}
+
+fn serving_status(serving: bool) -> HealthCheckResponse_ServingStatus {
+    if serving {
+        HealthCheckResponse_ServingStatus::SERVING
+    } else {
+        HealthCheckResponse_ServingStatus::NOT_SERVING
+    }
+}
+
+fn response(status: HealthCheckResponse_ServingStatus) -> HealthCheckResponse {
+    let mut response = HealthCheckResponse::new();
+    response.set_status(status);
+    response
+}
+
+impl Health for HealthService {
+    fn check(
+        &self,
+        _request_options: grpc::RequestOptions,
+        request: HealthCheckRequest,
+    ) -> grpc::SingleResponse<HealthCheckResponse> {
+        if !is_known_service(request.get_service()) {
+            return grpc::SingleResponse::err(grpc::Error::GrpcMessage(grpc::GrpcMessageError {
+                grpc_status: grpc::GrpcStatus::NotFound as i32,
+                grpc_message: format!("unknown service: {}", request.get_service()),
+            }));
+        }
+        grpc::SingleResponse::completed(response(serving_status(self.is_serving())))
+    }
+
+    fn watch(
+        &self,
+        _request_options: grpc::RequestOptions,
+        request: HealthCheckRequest,
+    ) -> grpc::StreamingResponse<HealthCheckResponse> {
+        let (sender, receiver) = mpsc::unbounded();
+        if is_known_service(request.get_service()) {
+            let mut state = self.state.lock().expect("health state lock poisoned");
+            let _ = sender.unbounded_send(response(serving_status(state.serving)));
+            state.watchers.push(sender);
+        } else {
+            // The services of this server are fixed, so the stream ends as the status can't change
+            let _ =
+                sender.unbounded_send(response(HealthCheckResponse_ServingStatus::SERVICE_UNKNOWN));
+        }
+        grpc::StreamingResponse::no_metadata(
+            receiver.map_err(|()| grpc::Error::Other("health watch closed")),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::health::{
+        HealthCheckRequest, HealthCheckResponse, HealthCheckResponse_ServingStatus,
+    };
+    use super::super::health_grpc::Health;
+    use super::{HealthService, EXECUTION_ENGINE_SERVICE_NAME};
+
+    fn request(service: &str) -> HealthCheckRequest {
+        let mut request = HealthCheckRequest::new();
+        request.set_service(service.to_string());
+        request
+    }
+
+    fn check(health: &HealthService, service: &str) -> HealthCheckResponse_ServingStatus {
+        health
+            .check(grpc::RequestOptions::new(), request(service))
+            .wait_drop_metadata()
+            .expect("should check")
+            .get_status()
+    }
+
+    #[test]
+    fn should_not_serve_until_set_serving() {
+        let health = HealthService::new();
+        assert_eq!(
+            check(&health, ""),
+            HealthCheckResponse_ServingStatus::NOT_SERVING
+        );
+
+        health.clone().set_serving(true);
+        assert_eq!(
+            check(&health, ""),
+            HealthCheckResponse_ServingStatus::SERVING
+        );
+        assert_eq!(
+            check(&health, EXECUTION_ENGINE_SERVICE_NAME),
+            HealthCheckResponse_ServingStatus::SERVING
+        );
+
+        health.set_serving(false);
+        assert_eq!(
+            check(&health, ""),
+            HealthCheckResponse_ServingStatus::NOT_SERVING
+        );
+    }
+
+    #[test]
+    fn should_not_find_unknown_service() {
+        let health = HealthService::new();
+        let result = health
+            .check(grpc::RequestOptions::new(), request("unknown"))
+            .wait_drop_metadata();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_stream_status_changes_to_watchers() {
+        let health = HealthService::new();
+        let statuses = health.watch(grpc::RequestOptions::new(), request(""));
+
+        health.set_serving(true);
+        health.set_serving(true);
+        health.set_serving(false);
+
+        let received: Vec<_> = statuses
+            .wait_drop_metadata()
+            .take(3)
+            .map(|response| response.expect("should watch").get_status())
+            .collect();
+        assert_eq!(
+            received,
+            vec![
+                HealthCheckResponse_ServingStatus::NOT_SERVING,
+                HealthCheckResponse_ServingStatus::SERVING,
+                HealthCheckResponse_ServingStatus::NOT_SERVING,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_unknown_service_to_watchers() {
+        let health = HealthService::new();
+        let received: Vec<_> = health
+            .watch(grpc::RequestOptions::new(), request("unknown"))
+            .wait_drop_metadata()
+            .map(|response| response.expect("should watch").get_status())
+            .collect();
+        assert_eq!(
+            received,
+            vec![HealthCheckResponse_ServingStatus::SERVICE_UNKNOWN]
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! A service enforcing the request limits of a [`ServerConfig`] in front of another one.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use futures::sync::mpsc;
+use futures::{Sink, Stream};
+use protobuf::Message;
+
+use super::ipc;
+use super::ipc_grpc::ExecutionEngineService;
+use super::server_config::ServerConfig;
+use super::ExecuteStreaming;
+
+/// Counts the exec requests executing at once, up to a maximum.
+#[derive(Debug, Clone)]
+pub struct ExecPermits {
+    in_flight: Arc<AtomicUsize>,
+    max: usize,
+}
+
+impl ExecPermits {
+    pub fn new(max: usize) -> Self {
+        ExecPermits {
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            max,
+        }
+    }
+
+    /// Returns a permit to execute, or `None` if `max` exec requests are already executing.  The
+    /// permit is given back when dropped.
+    pub fn try_acquire(&self) -> Option<ExecPermit> {
+        let mut in_flight = self.in_flight.load(Ordering::SeqCst);
+        loop {
+            if in_flight >= self.max {
+                return None;
+            }
+            match self.in_flight.compare_exchange(
+                in_flight,
+                in_flight + 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => {
+                    return Some(ExecPermit {
+                        in_flight: Arc::clone(&self.in_flight),
+                    })
+                }
+                Err(current) => in_flight = current,
+            }
+        }
+    }
+
+    /// Returns the number of exec requests currently executing.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+}
+
+/// Allows one exec request to execute while held.
+#[derive(Debug)]
+pub struct ExecPermit {
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for ExecPermit {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn resource_exhausted(message: String) -> grpc::Error {
+    grpc::Error::GrpcMessage(grpc::GrpcMessageError {
+        grpc_status: grpc::GrpcStatus::ResourceExhausted as i32,
+        grpc_message: message,
+    })
+}
+
+/// Returns an error if `request` serializes to more than `max_message_size` bytes.
+pub fn check_message_size<M: Message>(
+    request: &M,
+    max_message_size: usize,
+) -> Result<(), grpc::Error> {
+    let size = request.compute_size() as usize;
+    if size > max_message_size {
+        return Err(resource_exhausted(format!(
+            "request of {} bytes exceeds the max message size of {} bytes",
+            size, max_message_size
+        )));
+    }
+    Ok(())
+}
+
+/// Wraps an [`ExecutionEngineService`], rejecting requests larger than the max message size and
+/// exec requests beyond the max number of concurrent execs.
+///
+/// Streamed exec requests are executed on a thread of their own, so that their responses are sent
+/// while the remaining deploys execute.  The thread holds its exec permit until the client has
+/// received all but the last response.
+pub struct LimitedService<E> {
+    service: Arc<E>,
+    max_message_size: usize,
+    exec_permits: ExecPermits,
+}
+
+impl<E> LimitedService<E> {
+    pub fn new(service: E, server_config: &ServerConfig) -> Self {
+        LimitedService {
+            service: Arc::new(service),
+            max_message_size: server_config.max_message_size(),
+            exec_permits: ExecPermits::new(server_config.max_concurrent_execs()),
+        }
+    }
+
+    pub fn exec_permits(&self) -> &ExecPermits {
+        &self.exec_permits
+    }
+
+    fn acquire_exec_permit(&self) -> Result<ExecPermit, grpc::Error> {
+        self.exec_permits.try_acquire().ok_or_else(|| {
+            resource_exhausted(format!(
+                "{} exec requests are already executing",
+                self.exec_permits.in_flight()
+            ))
+        })
+    }
+}
+
+// Clones share the wrapped service and the exec permits
+impl<E> Clone for LimitedService<E> {
+    fn clone(&self) -> Self {
+        LimitedService {
+            service: Arc::clone(&self.service),
+            max_message_size: self.max_message_size,
+            exec_permits: self.exec_permits.clone(),
+        }
+    }
+}
+
+/// Implements methods forwarding requests which are within the max message size.
+macro_rules! forward {
+    ($($method:ident($request:ty) -> $response:ty;)*) => {
+        $(
+            fn $method(
+                &self,
+                request_options: grpc::RequestOptions,
+                request: $request,
+            ) -> grpc::SingleResponse<$response> {
+                if let Err(error) = check_message_size(&request, self.max_message_size) {
+                    return grpc::SingleResponse::err(error);
+                }
+                self.service.$method(request_options, request)
+            }
+        )*
+    };
+}
+
+/// Like `forward!`, additionally holding an exec permit while the request is handled.
+macro_rules! forward_exec {
+    ($($method:ident($request:ty) -> $response:ty;)*) => {
+        $(
+            fn $method(
+                &self,
+                request_options: grpc::RequestOptions,
+                request: $request,
+            ) -> grpc::SingleResponse<$response> {
+                if let Err(error) = check_message_size(&request, self.max_message_size) {
+                    return grpc::SingleResponse::err(error);
+                }
+                let _permit = match self.acquire_exec_permit() {
+                    Ok(permit) => permit,
+                    Err(error) => return grpc::SingleResponse::err(error),
+                };
+                self.service.$method(request_options, request)
+            }
+        )*
+    };
+}
+
+impl<E> ExecutionEngineService for LimitedService<E>
+where
+    E: ExecutionEngineService + ExecuteStreaming + Send + Sync + 'static,
+{
+    fn execute_stream(
+        &self,
+        _request_options: grpc::RequestOptions,
+        request: ipc::ExecuteRequest,
+    ) -> grpc::StreamingResponse<ipc::ExecuteStreamResponse> {
+        if let Err(error) = check_message_size(&request, self.max_message_size) {
+            return grpc::StreamingResponse::err(error);
+        }
+        let permit = match self.acquire_exec_permit() {
+            Ok(permit) => permit,
+            Err(error) => return grpc::StreamingResponse::err(error),
+        };
+
+        let service = Arc::clone(&self.service);
+        // Sending blocks until the client has received the previous response, so responses don't
+        // pile up in memory ahead of a slow client
+        let (sender, receiver) = mpsc::channel(0);
+        thread::spawn(move || {
+            let _permit = permit;
+            let mut sender = sender.wait();
+            service.execute_streaming(request, &mut |response| {
+                // Fails only if the client went away, in which case nobody needs the response
+                let _ = sender.send(response);
+            });
+        });
+
+        grpc::StreamingResponse::no_metadata(
+            receiver.map_err(|()| grpc::Error::Other("execute stream closed")),
+        )
+    }
+
+    forward_exec! {
+        exec(ipc::ExecRequest) -> ipc::ExecResponse;
+        execute(ipc::ExecuteRequest) -> ipc::ExecuteResponse;
+        speculative_exec(ipc::SpeculativeExecRequest) -> ipc::SpeculativeExecResponse;
+        step(ipc::StepRequest) -> ipc::StepResponse;
+        replay(ipc::ReplayRequest) -> ipc::ReplayResponse;
+    }
+
+    forward! {
+        commit(ipc::CommitRequest) -> ipc::CommitResponse;
+        query(ipc::QueryRequest) -> ipc::QueryResponse;
+        batch_query(ipc::BatchQueryRequest) -> ipc::BatchQueryResponse;
+        validate(ipc::ValidateRequest) -> ipc::ValidateResponse;
+        run_genesis(ipc::GenesisRequest) -> ipc::GenesisResponse;
+        run_genesis_with_chainspec(ipc::ChainSpec_GenesisConfig) -> ipc::GenesisResponse;
+        query_validators(ipc::QueryValidatorsRequest) -> ipc::QueryValidatorsResponse;
+        get_balance(ipc::GetBalanceRequest) -> ipc::GetBalanceResponse;
+        get_account(ipc::GetAccountRequest) -> ipc::GetAccountResponse;
+        get_contract(ipc::GetContractRequest) -> ipc::GetContractResponse;
+        upgrade(ipc::UpgradeRequest) -> ipc::UpgradeResponse;
+        get_protocol_data(ipc::GetProtocolDataRequest) -> ipc::GetProtocolDataResponse;
+        prune(ipc::PruneRequest) -> ipc::PruneResponse;
+        state_diff(ipc::StateDiffRequest) -> ipc::StateDiffResponse;
+        get_trie_chunk(ipc::GetTrieChunkRequest) -> ipc::GetTrieChunkResponse;
+        put_trie(ipc::PutTrieRequest) -> ipc::PutTrieResponse;
+        import_protocol_data(ipc::ImportProtocolDataRequest) -> ipc::ImportProtocolDataResponse;
+        missing_trie_keys(ipc::MissingTrieKeysRequest) -> ipc::MissingTrieKeysResponse;
+        scrape_metrics(ipc::ScrapeMetricsRequest) -> ipc::ScrapeMetricsResponse;
+        verify_integrity(ipc::VerifyIntegrityRequest) -> ipc::VerifyIntegrityResponse;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ipc;
+    use super::{check_message_size, ExecPermits};
+
+    #[test]
+    fn should_not_exceed_max_exec_permits() {
+        let exec_permits = ExecPermits::new(2);
+
+        let first = exec_permits
+            .try_acquire()
+            .expect("should acquire first permit");
+        let second = exec_permits
+            .try_acquire()
+            .expect("should acquire second permit");
+        assert_eq!(exec_permits.in_flight(), 2);
+        assert!(exec_permits.try_acquire().is_none());
+
+        drop(first);
+        assert_eq!(exec_permits.in_flight(), 1);
+        let _third = exec_permits
+            .try_acquire()
+            .expect("should acquire released permit");
+        assert!(exec_permits.try_acquire().is_none());
+
+        drop(second);
+        assert_eq!(exec_permits.in_flight(), 1);
+    }
+
+    #[test]
+    fn should_share_exec_permits_between_clones() {
+        let exec_permits = ExecPermits::new(1);
+        let _permit = exec_permits.try_acquire().expect("should acquire permit");
+        assert!(exec_permits.clone().try_acquire().is_none());
+    }
+
+    #[test]
+    fn should_reject_messages_above_max_size() {
+        let mut request = ipc::QueryRequest::new();
+        request.set_state_hash(vec![0u8; 32]);
+
+        assert!(check_message_size(&request, 1024).is_ok());
+        assert!(check_message_size(&request, 10).is_err());
+    }
+}
@@ -5,26 +5,42 @@ use std::string::ToString;
 
 use protobuf::{ProtobufEnum, RepeatedField};
 
+use contract_ffi::system_contracts;
+use contract_ffi::system_contracts::pos::{
+    BondLimits, DelegationRate, DelegationRateLimits, RefundRatio, DEFAULT_ERA_DURATION,
+    MAX_DELEGATION_RATE,
+};
 use contract_ffi::uref::URef;
 use contract_ffi::value::account::{
     AccountActivity, ActionThresholds, AssociatedKeys, BlockTime, PublicKey, PurseId, Weight,
     KEY_SIZE,
 };
-use contract_ffi::value::U512;
-use engine_core::engine_state::error::{Error as EngineError, RootNotFound};
+use contract_ffi::value::{CLType, CLValue, U512};
+use engine_core::engine_state::approval::Approval;
+use engine_core::engine_state::error::{AuthorizationFailure, Error as EngineError, RootNotFound};
 use engine_core::engine_state::executable_deploy_item::ExecutableDeployItem;
-use engine_core::engine_state::execution_effect::ExecutionEffect;
+use engine_core::engine_state::execution_effect::{Event, ExecutionEffect};
 use engine_core::engine_state::execution_result::ExecutionResult;
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
+use engine_core::engine_state::native_transfer::NativeTransfer;
 use engine_core::engine_state::op::Op;
-use engine_core::execution::Error as ExecutionError;
+use engine_core::engine_state::upgrade::UpgradeConfig;
+use engine_core::engine_state::{
+    BalanceSource, DEFAULT_MIN_GAS_PRICE, DEFAULT_NATIVE_TRANSFER_COST,
+};
+use engine_core::execution::trace::HostCall;
+use engine_core::execution::{Error as ExecutionError, ErrorCode};
+use engine_core::replay::TransformDivergence;
 use engine_core::tracking_copy::utils;
+use engine_shared::gas::GasBreakdown;
+use engine_shared::key_limits::KeyLimits;
 use engine_shared::logging;
 use engine_shared::logging::log_level;
 use engine_shared::motes::Motes;
 use engine_shared::newtypes::Blake2bHash;
 use engine_shared::transform::{self, TypeMismatch};
 use engine_storage::global_state::{CommitResult, StateProvider};
+use engine_storage::trie::TrieMerkleProof;
 
 use crate::engine_server::ipc::{ChainSpec_CostTable, ChainSpec_GenesisAccount};
 use crate::engine_server::{ipc, state, transforms};
@@ -197,6 +213,171 @@ impl TryFrom<&super::state::Contract> for contract_ffi::value::Contract {
     }
 }
 
+impl From<contract_ffi::value::ContractPackage> for super::state::ContractPackage {
+    fn from(package: contract_ffi::value::ContractPackage) -> Self {
+        let versions = package
+            .versions()
+            .iter()
+            .map(|(version, contract)| {
+                let mut ipc_version = super::state::ContractPackage_Version::new();
+                ipc_version.set_version(*version);
+                ipc_version.set_contract(contract.into());
+                ipc_version
+            })
+            .collect();
+        let mut ipc_package = super::state::ContractPackage::new();
+        ipc_package.set_versions(versions);
+        ipc_package.set_disabled_versions(package.disabled_versions().iter().cloned().collect());
+        ipc_package
+    }
+}
+
+impl TryFrom<&super::state::ContractPackage> for contract_ffi::value::ContractPackage {
+    type Error = ParsingError;
+
+    fn try_from(value: &super::state::ContractPackage) -> Result<Self, Self::Error> {
+        let versions = value
+            .get_versions()
+            .iter()
+            .map(|version| {
+                version
+                    .get_contract()
+                    .try_into()
+                    .map(|contract| (version.get_version(), contract))
+            })
+            .collect::<Result<BTreeMap<u32, contract_ffi::key::Key>, ParsingError>>()?;
+        let disabled_versions = value.get_disabled_versions().iter().cloned().collect();
+        Ok(contract_ffi::value::ContractPackage::from_parts(
+            versions,
+            disabled_versions,
+        ))
+    }
+}
+
+impl From<&CLType> for super::state::CLType {
+    fn from(cl_type: &CLType) -> Self {
+        let mut ipc_cl_type = super::state::CLType::new();
+        match cl_type {
+            CLType::Bool => ipc_cl_type.set_simple_type(state::CLType_Simple::BOOL),
+            CLType::I32 => ipc_cl_type.set_simple_type(state::CLType_Simple::I32),
+            CLType::U8 => ipc_cl_type.set_simple_type(state::CLType_Simple::U8),
+            CLType::U32 => ipc_cl_type.set_simple_type(state::CLType_Simple::U32),
+            CLType::U64 => ipc_cl_type.set_simple_type(state::CLType_Simple::U64),
+            CLType::U128 => ipc_cl_type.set_simple_type(state::CLType_Simple::U128),
+            CLType::U256 => ipc_cl_type.set_simple_type(state::CLType_Simple::U256),
+            CLType::U512 => ipc_cl_type.set_simple_type(state::CLType_Simple::U512),
+            CLType::Unit => ipc_cl_type.set_simple_type(state::CLType_Simple::UNIT),
+            CLType::String => ipc_cl_type.set_simple_type(state::CLType_Simple::STRING),
+            CLType::Key => ipc_cl_type.set_simple_type(state::CLType_Simple::KEY),
+            CLType::URef => ipc_cl_type.set_simple_type(state::CLType_Simple::UREF),
+            CLType::Option(inner) => {
+                let mut option = state::CLType_Option::new();
+                option.set_inner(inner.as_ref().into());
+                ipc_cl_type.set_option_type(option);
+            }
+            CLType::List(inner) => {
+                let mut list = state::CLType_List::new();
+                list.set_inner(inner.as_ref().into());
+                ipc_cl_type.set_list_type(list);
+            }
+            CLType::Result { ok, err } => {
+                let mut result = state::CLType_Result::new();
+                result.set_ok(ok.as_ref().into());
+                result.set_err(err.as_ref().into());
+                ipc_cl_type.set_result_type(result);
+            }
+            CLType::Map { key, value } => {
+                let mut map = state::CLType_Map::new();
+                map.set_key(key.as_ref().into());
+                map.set_value(value.as_ref().into());
+                ipc_cl_type.set_map_type(map);
+            }
+            CLType::Tuple2(t1, t2) => {
+                let mut tuple = state::CLType_Tuple2::new();
+                tuple.set_type0(t1.as_ref().into());
+                tuple.set_type1(t2.as_ref().into());
+                ipc_cl_type.set_tuple2_type(tuple);
+            }
+        }
+        ipc_cl_type
+    }
+}
+
+impl TryFrom<&super::state::CLType> for CLType {
+    type Error = ParsingError;
+
+    fn try_from(value: &super::state::CLType) -> Result<Self, Self::Error> {
+        let boxed = |ipc_cl_type: &state::CLType| -> Result<Box<CLType>, ParsingError> {
+            Ok(Box::new(ipc_cl_type.try_into()?))
+        };
+        if value.has_simple_type() {
+            let cl_type = match value.get_simple_type() {
+                state::CLType_Simple::BOOL => CLType::Bool,
+                state::CLType_Simple::I32 => CLType::I32,
+                state::CLType_Simple::U8 => CLType::U8,
+                state::CLType_Simple::U32 => CLType::U32,
+                state::CLType_Simple::U64 => CLType::U64,
+                state::CLType_Simple::U128 => CLType::U128,
+                state::CLType_Simple::U256 => CLType::U256,
+                state::CLType_Simple::U512 => CLType::U512,
+                state::CLType_Simple::UNIT => CLType::Unit,
+                state::CLType_Simple::STRING => CLType::String,
+                state::CLType_Simple::KEY => CLType::Key,
+                state::CLType_Simple::UREF => CLType::URef,
+            };
+            Ok(cl_type)
+        } else if value.has_option_type() {
+            Ok(CLType::Option(boxed(value.get_option_type().get_inner())?))
+        } else if value.has_list_type() {
+            Ok(CLType::List(boxed(value.get_list_type().get_inner())?))
+        } else if value.has_result_type() {
+            let result = value.get_result_type();
+            Ok(CLType::Result {
+                ok: boxed(result.get_ok())?,
+                err: boxed(result.get_err())?,
+            })
+        } else if value.has_map_type() {
+            let map = value.get_map_type();
+            Ok(CLType::Map {
+                key: boxed(map.get_key())?,
+                value: boxed(map.get_value())?,
+            })
+        } else if value.has_tuple2_type() {
+            let tuple = value.get_tuple2_type();
+            Ok(CLType::Tuple2(
+                boxed(tuple.get_type0())?,
+                boxed(tuple.get_type1())?,
+            ))
+        } else {
+            parse_error(format!(
+                "IPC CLType {:?} couldn't be parsed to domain representation.",
+                value
+            ))
+        }
+    }
+}
+
+impl From<CLValue> for super::state::CLValue {
+    fn from(cl_value: CLValue) -> Self {
+        let mut ipc_cl_value = super::state::CLValue::new();
+        ipc_cl_value.set_cl_type(cl_value.cl_type().into());
+        ipc_cl_value.set_serialized_value(cl_value.inner_bytes().to_vec());
+        ipc_cl_value
+    }
+}
+
+impl TryFrom<&super::state::CLValue> for CLValue {
+    type Error = ParsingError;
+
+    fn try_from(value: &super::state::CLValue) -> Result<Self, Self::Error> {
+        let cl_type = value.get_cl_type().try_into()?;
+        Ok(CLValue::from_parts(
+            cl_type,
+            value.get_serialized_value().to_vec(),
+        ))
+    }
+}
+
 impl From<contract_ffi::value::Value> for super::state::Value {
     fn from(v: contract_ffi::value::Value) -> Self {
         let mut tv = super::state::Value::new();
@@ -241,6 +422,10 @@ impl From<contract_ffi::value::Value> for super::state::Value {
             }
             contract_ffi::value::Value::Unit => tv.set_unit(state::Unit::new()),
             contract_ffi::value::Value::UInt64(num) => tv.set_long_value(num),
+            contract_ffi::value::Value::ContractPackage(package) => {
+                tv.set_contract_package(package.into());
+            }
+            contract_ffi::value::Value::CLValue(cl_value) => tv.set_cl_value(cl_value.into()),
         };
         tv
     }
@@ -286,6 +471,13 @@ impl TryFrom<&super::state::Value> for contract_ffi::value::Value {
             Ok(contract_ffi::value::Value::Unit)
         } else if value.has_long_value() {
             Ok(contract_ffi::value::Value::UInt64(value.get_long_value()))
+        } else if value.has_contract_package() {
+            let package: contract_ffi::value::ContractPackage =
+                value.get_contract_package().try_into()?;
+            Ok(contract_ffi::value::Value::ContractPackage(package))
+        } else if value.has_cl_value() {
+            let cl_value: CLValue = value.get_cl_value().try_into()?;
+            Ok(contract_ffi::value::Value::CLValue(cl_value))
         } else {
             parse_error(format!(
                 "IPC Value {:?} couldn't be parsed to domain representation.",
@@ -304,7 +496,7 @@ impl From<contract_ffi::value::account::Account> for super::state::Account {
             .get_associated_keys()
             .map(|(key, weight)| {
                 let mut ipc_associated_key = super::state::Account_AssociatedKey::new();
-                ipc_associated_key.set_public_key(key.value().to_vec());
+                ipc_associated_key.set_public_key(key.as_bytes().to_vec());
                 ipc_associated_key.set_weight(u32::from(weight.value()));
                 ipc_associated_key
             })
@@ -317,6 +509,7 @@ impl From<contract_ffi::value::account::Account> for super::state::Account {
             tmp.set_deployment_threshold(u32::from(
                 account.action_thresholds().deployment().value(),
             ));
+            tmp.set_transfer_threshold(u32::from(account.action_thresholds().transfer().value()));
             tmp
         };
         ipc_account.set_action_thresholds(action_thresholds);
@@ -378,6 +571,7 @@ impl TryFrom<&super::state::Account> for contract_ffi::value::account::Account {
             ActionThresholds::new(
                 Weight::new(action_thresholds_ipc.get_deployment_threshold() as u8),
                 Weight::new(action_thresholds_ipc.get_key_management_threshold() as u8),
+                Weight::new(action_thresholds_ipc.get_transfer_threshold() as u8),
             )
             .map_err(ParsingError::custom)?
         };
@@ -683,9 +877,12 @@ impl From<(contract_ffi::key::Key, transform::Transform)> for super::transforms:
 impl From<ExecutionEffect> for super::ipc::ExecutionEffect {
     fn from(ee: ExecutionEffect) -> super::ipc::ExecutionEffect {
         let mut eff = super::ipc::ExecutionEffect::new();
-        let ipc_ops: Vec<super::ipc::OpEntry> = ee
-            .ops
-            .iter()
+        // The effects are held in hash maps, so they are sorted by key to keep the response
+        // independent of their iteration order.
+        let ops: BTreeMap<_, _> = ee.ops.iter().collect();
+        let transforms: BTreeMap<_, _> = ee.transforms.into_iter().collect();
+        let ipc_ops: Vec<super::ipc::OpEntry> = ops
+            .into_iter()
             .map(|(k, o)| {
                 let mut op_entry = super::ipc::OpEntry::new();
                 let ipc_key = k.into();
@@ -696,13 +893,25 @@ impl From<ExecutionEffect> for super::ipc::ExecutionEffect {
             })
             .collect();
         let ipc_tran: Vec<super::transforms::TransformEntry> =
-            ee.transforms.into_iter().map(Into::into).collect();
+            transforms.into_iter().map(Into::into).collect();
+        let ipc_events: Vec<super::ipc::Event> = ee.events.into_iter().map(Into::into).collect();
         eff.set_op_map(protobuf::RepeatedField::from_vec(ipc_ops));
         eff.set_transform_map(protobuf::RepeatedField::from_vec(ipc_tran));
+        eff.set_events(protobuf::RepeatedField::from_vec(ipc_events));
         eff
     }
 }
 
+impl From<Event> for super::ipc::Event {
+    fn from(event: Event) -> super::ipc::Event {
+        let mut ipc_event = super::ipc::Event::new();
+        ipc_event.set_emitter((&event.emitter).into());
+        ipc_event.set_topic(event.topic);
+        ipc_event.set_payload(event.payload);
+        ipc_event
+    }
+}
+
 impl From<RootNotFound> for ipc::RootNotFound {
     fn from(err: RootNotFound) -> ipc::RootNotFound {
         let RootNotFound(missing_root_hash) = err;
@@ -722,12 +931,58 @@ impl From<TypeMismatch> for transforms::TypeMismatch {
     }
 }
 
+impl From<GasBreakdown> for ipc::DeployResult_GasBreakdown {
+    fn from(gas_breakdown: GasBreakdown) -> Self {
+        let mut ipc_gas_breakdown = ipc::DeployResult_GasBreakdown::new();
+        // TODO: gas should be BIGINT; see https://casperlabs.atlassian.net/browse/EE-649
+        ipc_gas_breakdown.set_opcodes(gas_breakdown.opcodes().as_u64());
+        ipc_gas_breakdown.set_storage_writes(gas_breakdown.storage_writes().as_u64());
+        ipc_gas_breakdown.set_host_calls(gas_breakdown.host_calls().as_u64());
+        ipc_gas_breakdown.set_transfers(gas_breakdown.transfers().as_u64());
+        ipc_gas_breakdown
+    }
+}
+
+impl From<HostCall> for ipc::DeployResult_HostCall {
+    fn from(host_call: HostCall) -> Self {
+        let mut ipc_host_call = ipc::DeployResult_HostCall::new();
+        ipc_host_call.set_name(host_call.name.to_string());
+        ipc_host_call.set_args(host_call.args);
+        ipc_host_call.set_gas(host_call.gas.as_u64());
+        ipc_host_call
+    }
+}
+
+impl From<TransformDivergence> for ipc::TransformDivergence {
+    fn from(divergence: TransformDivergence) -> Self {
+        let mut ipc_divergence = ipc::TransformDivergence::new();
+        ipc_divergence.set_key((&divergence.key).into());
+        if let Some(recorded) = divergence.recorded {
+            ipc_divergence.set_recorded(recorded.into());
+        }
+        if let Some(replayed) = divergence.replayed {
+            ipc_divergence.set_replayed(replayed.into());
+        }
+        ipc_divergence
+    }
+}
+
+impl From<ErrorCode> for ipc::DeployError_ErrorCode {
+    fn from(code: ErrorCode) -> Self {
+        // The codes of both enums are kept equal
+        ipc::DeployError_ErrorCode::from_i32(code as i32)
+            .unwrap_or(ipc::DeployError_ErrorCode::UNKNOWN)
+    }
+}
+
 impl From<ExecutionResult> for ipc::DeployResult {
     fn from(er: ExecutionResult) -> ipc::DeployResult {
-        match er {
+        let cost_breakdown = er.cost_breakdown();
+        let mut deploy_result = match er {
             ExecutionResult::Success {
                 effect: effects,
                 cost,
+                ..
             } => {
                 let ipc_ee = effects.into();
                 let mut deploy_result = ipc::DeployResult::new();
@@ -742,6 +997,7 @@ impl From<ExecutionResult> for ipc::DeployResult {
                 error: err,
                 effect,
                 cost,
+                ..
             } => {
                 match err {
                     // TODO(mateusz.gorski): Fix error model for the storage errors.
@@ -763,11 +1019,14 @@ impl From<ExecutionResult> for ipc::DeployResult {
                         ExecutionError::DeploymentAuthorizationFailure,
                     ) => precondition_failure(error.to_string()),
                     EngineError::StorageError(storage_err) => {
-                        execution_error(storage_err.to_string(), cost.as_u64(), effect)
+                        let msg = storage_err.to_string();
+                        let error = ExecutionError::Storage(storage_err);
+                        coded_execution_error(msg, &error, cost.as_u64(), effect)
                     }
                     error @ EngineError::AuthorizationError => {
                         precondition_failure(error.to_string())
                     }
+                    EngineError::AuthorizationFailure(failure) => authorization_failure(failure),
                     EngineError::MissingSystemContractError(msg) => {
                         execution_error(msg, cost.as_u64(), effect)
                     }
@@ -775,21 +1034,56 @@ impl From<ExecutionResult> for ipc::DeployResult {
                         let msg = error.to_string();
                         execution_error(msg, cost.as_u64(), effect)
                     }
-                    error @ EngineError::DeployError => {
+                    error @ EngineError::InsufficientBalance { .. } => {
                         let msg = error.to_string();
                         execution_error(msg, cost.as_u64(), effect)
                     }
-                    error @ EngineError::FinalizationError => {
+                    error @ EngineError::DeployError => {
                         let msg = error.to_string();
                         execution_error(msg, cost.as_u64(), effect)
                     }
-                    error @ EngineError::SerializationError(_) => {
+                    error @ EngineError::FinalizationError => {
                         let msg = error.to_string();
                         execution_error(msg, cost.as_u64(), effect)
                     }
-                    error @ EngineError::MintError(_) => {
-                        let msg = error.to_string();
-                        execution_error(msg, cost.as_u64(), effect)
+                    EngineError::SerializationError(serialization_err) => {
+                        let msg =
+                            EngineError::SerializationError(serialization_err.clone()).to_string();
+                        let error = ExecutionError::BytesRepr(serialization_err);
+                        coded_execution_error(msg, &error, cost.as_u64(), effect)
+                    }
+                    EngineError::MintError(mint_err) => {
+                        let msg = EngineError::MintError(mint_err).to_string();
+                        let error =
+                            ExecutionError::from(system_contracts::error::Error::from(mint_err));
+                        coded_execution_error(msg, &error, cost.as_u64(), effect)
+                    }
+                    error @ EngineError::InvalidProtocolVersion(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::MissingDeployDependency(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::DeployExpired { .. } => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::DuplicateDeploy(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::FutureDeploy { .. } => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::InvalidDeployHash(_) => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::GasPriceTooLow { .. } => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::SystemAccountDeploy => {
+                        precondition_failure(error.to_string())
+                    }
+                    error @ EngineError::InvalidGenesisBonds(_) => {
+                        precondition_failure(error.to_string())
                     }
                     EngineError::ExecError(exec_error) => match exec_error {
                         ExecutionError::GasLimit => {
@@ -810,59 +1104,20 @@ impl From<ExecutionResult> for ipc::DeployResult {
                             deploy_result.set_execution_result(exec_result);
                             deploy_result
                         }
-                        ExecutionError::KeyNotFound(key) => {
-                            let msg = format!("Key {:?} not found.", key);
-                            execution_error(msg, cost.as_u64(), effect)
-                        }
-                        ExecutionError::Revert(status) => {
-                            let error_msg = format!("Exit code: {}", status);
-                            execution_error(error_msg, cost.as_u64(), effect)
-                        }
-                        ExecutionError::Interpreter(error) => {
-                            // If the error happens during contract execution it's mapped to
-                            // HostError and wrapped in Interpreter
-                            // error, so we may end up with
-                            // InterpreterError(HostError(InterpreterError))).
-                            // In order to provide clear error messages we have to downcast and
-                            // match on the inner error, otherwise we
-                            // end up with `Host(Trap(Trap(TrapKind:InterpreterError)))`.
-                            // TODO: This really should be happening in the `Executor::exec`.
-                            match error.as_host_error() {
-                                Some(host_error) => {
-                                    let downcasted_error =
-                                        host_error.downcast_ref::<ExecutionError>().unwrap();
-                                    match downcasted_error {
-                                        ExecutionError::Revert(status) => {
-                                            let errors_msg = format!("Exit code: {}", status);
-                                            execution_error(errors_msg, cost.as_u64(), effect)
-                                        }
-                                        ExecutionError::KeyNotFound(key) => {
-                                            let errors_msg = format!("Key {:?} not found.", key);
-                                            execution_error(errors_msg, cost.as_u64(), effect)
-                                        }
-                                        other => execution_error(
-                                            format!("{:?}", other),
-                                            cost.as_u64(),
-                                            effect,
-                                        ),
-                                    }
-                                }
-
-                                None => {
-                                    let msg = format!("{:?}", error);
-                                    execution_error(msg, cost.as_u64(), effect)
-                                }
-                            }
-                        }
-                        // TODO(mateusz.gorski): Be more specific about execution errors
                         other => {
-                            let msg = format!("{:?}", other);
-                            execution_error(msg, cost.as_u64(), effect)
+                            let msg = exec_error_message(&other);
+                            coded_execution_error(msg, &other, cost.as_u64(), effect)
                         }
                     },
                 }
             }
+        };
+        if deploy_result.has_execution_result() {
+            deploy_result
+                .mut_execution_result()
+                .set_cost_breakdown(cost_breakdown.into());
         }
+        deploy_result
     }
 }
 
@@ -961,6 +1216,67 @@ impl From<ipc::DeployPayload_oneof_payload> for ExecutableDeployItem {
     }
 }
 
+impl TryFrom<ipc::GetBalanceRequest_oneof_purse> for BalanceSource {
+    type Error = MappingError;
+
+    fn try_from(purse: ipc::GetBalanceRequest_oneof_purse) -> Result<Self, Self::Error> {
+        match purse {
+            ipc::GetBalanceRequest_oneof_purse::account_address(account_address) => {
+                if account_address.len() != KEY_SIZE {
+                    return Err(MappingError::invalid_public_key_length(
+                        account_address.len(),
+                    ));
+                }
+                let mut addr = [0u8; KEY_SIZE];
+                addr.copy_from_slice(&account_address);
+                Ok(BalanceSource::Account(addr))
+            }
+            ipc::GetBalanceRequest_oneof_purse::purse_uref(purse_uref) => {
+                let uref: URef = (&purse_uref).try_into()?;
+                Ok(BalanceSource::Purse(uref))
+            }
+        }
+    }
+}
+
+impl TryFrom<&ipc::NativeTransfer> for NativeTransfer {
+    type Error = MappingError;
+
+    fn try_from(native_transfer: &ipc::NativeTransfer) -> Result<Self, Self::Error> {
+        let source = if native_transfer.has_source() {
+            let uref: URef = native_transfer.get_source().try_into()?;
+            Some(uref)
+        } else {
+            None
+        };
+        let target = {
+            let tmp = native_transfer.get_target();
+            match tmp.try_into() {
+                Ok(public_key) => public_key,
+                Err(_) => return Err(MappingError::invalid_public_key_length(tmp.len())),
+            }
+        };
+        let amount = native_transfer.get_amount().try_into()?;
+        let id = Some(native_transfer.get_id()).filter(|id| *id > 0);
+        Ok(NativeTransfer::new(source, target, amount, id))
+    }
+}
+
+impl TryFrom<&ipc::Approval> for Approval {
+    type Error = MappingError;
+
+    fn try_from(approval: &ipc::Approval) -> Result<Self, Self::Error> {
+        let public_key = {
+            let tmp = approval.get_public_key();
+            match tmp.try_into() {
+                Ok(public_key) => public_key,
+                Err(_) => return Err(MappingError::invalid_public_key_length(tmp.len())),
+            }
+        };
+        Ok(Approval::new(public_key, approval.get_signature().to_vec()))
+    }
+}
+
 impl TryFrom<ipc::ChainSpec_GenesisAccount> for GenesisAccount {
     type Error = MappingError;
 
@@ -978,7 +1294,19 @@ impl TryFrom<ipc::ChainSpec_GenesisAccount> for GenesisAccount {
             .get_bonded_amount()
             .try_into()
             .map(Motes::new)?;
-        Ok(GenesisAccount::new(public_key, balance, bonded_amount))
+        let mut account = GenesisAccount::new(public_key, balance, bonded_amount);
+        if genesis_account.has_delegation_rate() {
+            let value = genesis_account.get_delegation_rate().get_value();
+            let delegation_rate = DelegationRate::try_from(value)
+                .ok()
+                .filter(|rate| *rate <= MAX_DELEGATION_RATE)
+                .ok_or_else(|| ParsingError(format!("Invalid delegation rate: {}", value)))?;
+            account = account.with_delegation_rate(delegation_rate);
+        }
+        if genesis_account.get_is_genesis_delegator() {
+            account = account.into_genesis_delegator();
+        }
+        Ok(account)
     }
 }
 
@@ -986,7 +1314,7 @@ impl From<GenesisAccount> for ipc::ChainSpec_GenesisAccount {
     fn from(account: GenesisAccount) -> Self {
         let mut ret = ipc::ChainSpec_GenesisAccount::new();
 
-        ret.set_public_key(account.public_key().value().to_vec());
+        ret.set_public_key(account.public_key().as_bytes().to_vec());
 
         let mut bonded_amount = state::BigInt::new();
         bonded_amount.set_bit_width(512);
@@ -997,6 +1325,13 @@ impl From<GenesisAccount> for ipc::ChainSpec_GenesisAccount {
         balance.set_bit_width(512);
         balance.set_value(account.balance().to_string());
         ret.set_balance(balance);
+
+        if let Some(delegation_rate) = account.delegation_rate() {
+            let mut rate = ipc::ChainSpec_DelegationRate::new();
+            rate.set_value(delegation_rate.into());
+            ret.set_delegation_rate(rate);
+        }
+        ret.set_is_genesis_delegator(account.is_genesis_delegator());
         ret
     }
 }
@@ -1013,6 +1348,11 @@ impl From<ipc::ChainSpec_CostTable_WasmCosts> for WasmCosts {
         let max_stack_height = wasm_costs.get_max_stack_height();
         let opcodes_mul = wasm_costs.get_opcodes_mul();
         let opcodes_div = wasm_costs.get_opcodes_div();
+        let storage_byte_cost = wasm_costs.get_storage_byte_cost();
+        let blake2b256_cost = wasm_costs.get_blake2b256_cost();
+        let sha256_cost = wasm_costs.get_sha256_cost();
+        let ed25519_verify_cost = wasm_costs.get_ed25519_verify_cost();
+        let secp256k1_recover_cost = wasm_costs.get_secp256k1_recover_cost();
         WasmCosts {
             regular,
             div,
@@ -1024,6 +1364,11 @@ impl From<ipc::ChainSpec_CostTable_WasmCosts> for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            storage_byte_cost,
+            blake2b256_cost,
+            sha256_cost,
+            ed25519_verify_cost,
+            secp256k1_recover_cost,
         }
     }
 }
@@ -1041,10 +1386,95 @@ impl From<WasmCosts> for ipc::ChainSpec_CostTable_WasmCosts {
         cost_table.set_max_stack_height(wasm_costs.max_stack_height);
         cost_table.set_opcodes_mul(wasm_costs.opcodes_mul);
         cost_table.set_opcodes_div(wasm_costs.opcodes_div);
+        cost_table.set_storage_byte_cost(wasm_costs.storage_byte_cost);
+        cost_table.set_blake2b256_cost(wasm_costs.blake2b256_cost);
+        cost_table.set_sha256_cost(wasm_costs.sha256_cost);
+        cost_table.set_ed25519_verify_cost(wasm_costs.ed25519_verify_cost);
+        cost_table.set_secp256k1_recover_cost(wasm_costs.secp256k1_recover_cost);
         cost_table
     }
 }
 
+impl TryFrom<ipc::ChainSpec_RefundRatio> for RefundRatio {
+    type Error = MappingError;
+
+    fn try_from(refund_ratio: ipc::ChainSpec_RefundRatio) -> Result<Self, Self::Error> {
+        let numerator = refund_ratio.get_numerator();
+        let denominator = refund_ratio.get_denominator();
+        RefundRatio::new(numerator, denominator).ok_or_else(|| {
+            ParsingError(format!(
+                "Invalid refund ratio: {}/{}",
+                numerator, denominator
+            ))
+            .into()
+        })
+    }
+}
+
+impl From<RefundRatio> for ipc::ChainSpec_RefundRatio {
+    fn from(refund_ratio: RefundRatio) -> Self {
+        let mut ret = ipc::ChainSpec_RefundRatio::new();
+        ret.set_numerator(refund_ratio.numerator());
+        ret.set_denominator(refund_ratio.denominator());
+        ret
+    }
+}
+
+impl TryFrom<ipc::ChainSpec_DelegationRateLimits> for DelegationRateLimits {
+    type Error = MappingError;
+
+    fn try_from(limits: ipc::ChainSpec_DelegationRateLimits) -> Result<Self, Self::Error> {
+        let max_increase_per_era = limits.get_max_increase_per_era();
+        DelegationRate::try_from(max_increase_per_era)
+            .ok()
+            .and_then(|max_increase_per_era| {
+                DelegationRateLimits::new(max_increase_per_era, limits.get_max_changes_per_era())
+            })
+            .ok_or_else(|| {
+                ParsingError(format!(
+                    "Invalid maximum delegation rate increase per era: {}",
+                    max_increase_per_era
+                ))
+                .into()
+            })
+    }
+}
+
+impl From<DelegationRateLimits> for ipc::ChainSpec_DelegationRateLimits {
+    fn from(limits: DelegationRateLimits) -> Self {
+        let mut ret = ipc::ChainSpec_DelegationRateLimits::new();
+        ret.set_max_increase_per_era(limits.max_increase_per_era().into());
+        ret.set_max_changes_per_era(limits.max_changes_per_era());
+        ret
+    }
+}
+
+impl TryFrom<ipc::ChainSpec_BondLimits> for BondLimits {
+    type Error = MappingError;
+
+    fn try_from(bond_limits: ipc::ChainSpec_BondLimits) -> Result<Self, Self::Error> {
+        let min_bond_amount: U512 = bond_limits.get_min_bond_amount().try_into()?;
+        BondLimits::new(
+            min_bond_amount,
+            bond_limits.get_max_bond_multiplier(),
+            bond_limits.get_max_validator_count(),
+        )
+        .ok_or_else(|| {
+            ParsingError(format!("Invalid minimum bond amount: {}", min_bond_amount)).into()
+        })
+    }
+}
+
+impl From<BondLimits> for ipc::ChainSpec_BondLimits {
+    fn from(bond_limits: BondLimits) -> Self {
+        let mut ret = ipc::ChainSpec_BondLimits::new();
+        ret.set_min_bond_amount(bond_limits.min_bond_amount().into());
+        ret.set_max_bond_multiplier(bond_limits.max_bond_multiplier());
+        ret.set_max_validator_count(bond_limits.max_validator_count());
+        ret
+    }
+}
+
 impl TryFrom<ipc::ChainSpec_GenesisConfig> for GenesisConfig {
     type Error = MappingError;
 
@@ -1060,7 +1490,53 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig> for GenesisConfig {
             .cloned()
             .map(TryInto::try_into)
             .collect::<Result<Vec<GenesisAccount>, Self::Error>>()?;
-        let wasm_costs = genesis_config.get_costs().get_wasm().to_owned().into();
+        let wasm_costs = if genesis_config.has_costs() && genesis_config.get_costs().has_wasm() {
+            genesis_config.get_costs().get_wasm().to_owned().into()
+        } else {
+            WasmCosts::default()
+        };
+        let refund_ratio = if genesis_config.has_refund_ratio() {
+            genesis_config.get_refund_ratio().to_owned().try_into()?
+        } else {
+            RefundRatio::default()
+        };
+        let min_gas_price = match genesis_config.get_min_gas_price() {
+            0 => DEFAULT_MIN_GAS_PRICE,
+            min_gas_price => min_gas_price,
+        };
+        let native_transfer_cost = match genesis_config.get_native_transfer_cost() {
+            0 => DEFAULT_NATIVE_TRANSFER_COST,
+            native_transfer_cost => native_transfer_cost,
+        };
+        let key_limits = {
+            let defaults = KeyLimits::default();
+            let max_associated_keys = match genesis_config.get_max_associated_keys() {
+                0 => defaults.max_associated_keys(),
+                max_associated_keys => max_associated_keys,
+            };
+            let max_named_keys = match genesis_config.get_max_named_keys() {
+                0 => defaults.max_named_keys(),
+                max_named_keys => max_named_keys,
+            };
+            KeyLimits::new(max_associated_keys, max_named_keys)
+        };
+        let delegation_rate_limits = if genesis_config.has_delegation_rate_limits() {
+            genesis_config
+                .get_delegation_rate_limits()
+                .to_owned()
+                .try_into()?
+        } else {
+            DelegationRateLimits::default()
+        };
+        let bond_limits = if genesis_config.has_bond_limits() {
+            genesis_config.get_bond_limits().to_owned().try_into()?
+        } else {
+            BondLimits::default()
+        };
+        let era_duration = match genesis_config.get_era_duration() {
+            0 => DEFAULT_ERA_DURATION,
+            era_duration => era_duration,
+        };
         Ok(GenesisConfig::new(
             name,
             timestamp,
@@ -1069,7 +1545,15 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig> for GenesisConfig {
             proof_of_stake_initializer_bytes,
             accounts,
             wasm_costs,
-        ))
+            refund_ratio,
+            min_gas_price,
+            native_transfer_cost,
+            key_limits,
+        )
+        .with_delegation_rate_limits(delegation_rate_limits)
+        .with_bond_limits(bond_limits)
+        .with_era_duration(era_duration)
+        .with_unbond_delay(genesis_config.get_unbond_delay()))
     }
 }
 
@@ -1099,10 +1583,89 @@ impl From<GenesisConfig> for ipc::ChainSpec_GenesisConfig {
             cost_table.set_wasm(genesis_config.wasm_costs().into());
             ret.set_costs(cost_table);
         }
+        ret.set_refund_ratio(genesis_config.refund_ratio().into());
+        ret.set_min_gas_price(genesis_config.min_gas_price());
+        ret.set_native_transfer_cost(genesis_config.native_transfer_cost());
+        ret.set_max_associated_keys(genesis_config.key_limits().max_associated_keys());
+        ret.set_max_named_keys(genesis_config.key_limits().max_named_keys());
+        ret.set_delegation_rate_limits(genesis_config.delegation_rate_limits().into());
+        ret.set_bond_limits(genesis_config.bond_limits().into());
+        ret.set_era_duration(genesis_config.era_duration());
+        ret.set_unbond_delay(genesis_config.unbond_delay());
         ret
     }
 }
 
+impl TryFrom<ipc::UpgradeRequest> for UpgradeConfig {
+    type Error = MappingError;
+
+    fn try_from(upgrade_request: ipc::UpgradeRequest) -> Result<Self, Self::Error> {
+        let pre_state_hash: Blake2bHash = {
+            let tmp = upgrade_request.get_parent_state_hash();
+            tmp.try_into().map_err(|_| {
+                ParsingError(format!("Invalid parent state hash length: {}", tmp.len()))
+            })?
+        };
+        let current_protocol_version = upgrade_request.get_protocol_version().get_value();
+        let upgrade_point = upgrade_request.get_upgrade_point();
+        let new_protocol_version = upgrade_point.get_protocol_version().get_value();
+        let new_mint_bytes = Some(upgrade_point.get_new_mint_code())
+            .filter(|bytes| !bytes.is_empty())
+            .map(<[u8]>::to_vec);
+        let new_proof_of_stake_bytes = Some(upgrade_point.get_new_pos_code())
+            .filter(|bytes| !bytes.is_empty())
+            .map(<[u8]>::to_vec);
+        let new_wasm_costs = if upgrade_point.has_new_costs() {
+            Some(upgrade_point.get_new_costs().get_wasm().to_owned().into())
+        } else {
+            None
+        };
+        let new_refund_ratio = if upgrade_point.has_new_refund_ratio() {
+            Some(upgrade_point.get_new_refund_ratio().to_owned().try_into()?)
+        } else {
+            None
+        };
+        let new_min_gas_price =
+            Some(upgrade_point.get_new_min_gas_price()).filter(|min_gas_price| *min_gas_price > 0);
+        let new_native_transfer_cost = Some(upgrade_point.get_new_native_transfer_cost())
+            .filter(|native_transfer_cost| *native_transfer_cost > 0);
+        let new_max_associated_keys = Some(upgrade_point.get_new_max_associated_keys())
+            .filter(|max_associated_keys| *max_associated_keys > 0);
+        let new_max_named_keys = Some(upgrade_point.get_new_max_named_keys())
+            .filter(|max_named_keys| *max_named_keys > 0);
+        Ok(UpgradeConfig::new(
+            pre_state_hash,
+            current_protocol_version,
+            new_protocol_version,
+            new_mint_bytes,
+            new_proof_of_stake_bytes,
+            new_wasm_costs,
+            new_refund_ratio,
+            new_min_gas_price,
+            new_native_transfer_cost,
+            new_max_associated_keys,
+            new_max_named_keys,
+        ))
+    }
+}
+
+impl TryFrom<TrieMerkleProof<contract_ffi::key::Key, contract_ffi::value::Value>>
+    for ipc::TrieMerkleProof
+{
+    type Error = contract_ffi::bytesrepr::Error;
+
+    fn try_from(
+        proof: TrieMerkleProof<contract_ffi::key::Key, contract_ffi::value::Value>,
+    ) -> Result<Self, Self::Error> {
+        let path = proof.path_bytes()?;
+        let mut ret = ipc::TrieMerkleProof::new();
+        ret.set_key(proof.key().into());
+        ret.set_value(proof.into_value().into());
+        ret.set_path(path.into());
+        Ok(ret)
+    }
+}
+
 /// Constructs an instance of [[ipc::DeployResult]] with an error set to
 /// [[ipc::DeployError_PreconditionFailure]].
 fn precondition_failure(msg: String) -> ipc::DeployResult {
@@ -1113,6 +1676,25 @@ fn precondition_failure(msg: String) -> ipc::DeployResult {
     deploy_result
 }
 
+/// Constructs an instance of [[ipc::DeployResult]] with an error set to
+/// [[ipc::DeployError_PreconditionFailure]] carrying the details of an authorization failure.
+fn authorization_failure(failure: AuthorizationFailure) -> ipc::DeployResult {
+    let msg = EngineError::AuthorizationFailure(failure.clone()).to_string();
+    let mut deploy_result = precondition_failure(msg);
+    let to_bytes = |keys: Vec<PublicKey>| {
+        RepeatedField::from_vec(keys.iter().map(|key| key.as_bytes().to_vec()).collect())
+    };
+    let mut detail = ipc::DeployResult_AuthorizationFailure::new();
+    detail.set_unknown_keys(to_bytes(failure.unknown_keys));
+    detail.set_below_threshold_keys(to_bytes(failure.below_threshold_keys));
+    detail.set_required_weight(failure.required_weight.value().into());
+    detail.set_provided_weight(failure.provided_weight.value().into());
+    deploy_result
+        .mut_precondition_failure()
+        .set_authorization_failure(detail);
+    deploy_result
+}
+
 /// Constructs an instance of [[ipc::DeployResult]] with error set to
 /// [[ipc::DeployError_ExecutionError]].
 fn execution_error(msg: String, cost: u64, effect: ExecutionEffect) -> ipc::DeployResult {
@@ -1135,9 +1717,45 @@ fn execution_error(msg: String, cost: u64, effect: ExecutionEffect) -> ipc::Depl
     deploy_result
 }
 
+/// Constructs an instance of [[ipc::DeployResult]] with error set to
+/// [[ipc::DeployError_ExecutionError]] carrying the code and revert value of `error`.
+fn coded_execution_error(
+    msg: String,
+    error: &ExecutionError,
+    cost: u64,
+    effect: ExecutionEffect,
+) -> ipc::DeployResult {
+    let mut deploy_result = execution_error(msg, cost, effect);
+    let exec_error = deploy_result
+        .mut_execution_result()
+        .mut_error()
+        .mut_exec_error();
+    exec_error.set_code(error.code().into());
+    if let Some(value) = error.revert_value() {
+        let mut revert_value = ipc::DeployError_RevertValue::new();
+        revert_value.set_value(value);
+        exec_error.set_revert_value(revert_value);
+    }
+    deploy_result
+}
+
+/// Describes the error raised by the host while executing a contract, rather than the
+/// interpreter errors wrapping it.
+fn exec_error_message(error: &ExecutionError) -> String {
+    match error.root_cause() {
+        ExecutionError::Revert(status) => format!("Exit code: {}", status),
+        ExecutionError::KeyNotFound(key) => format!("Key {:?} not found.", key),
+        ExecutionError::SystemContractError(system_contracts::error::Error::ProofOfStakeError(
+            status,
+        )) => format!("Proof of Stake error: {}", status),
+        ExecutionError::Interpreter(error) => format!("{:?}", error),
+        other => format!("{:?}", other),
+    }
+}
+
 pub fn to_domain_validators(bond: &ipc::Bond) -> Result<(PublicKey, U512), String> {
     let pk = PublicKey::try_from(bond.get_validator_public_key())
-        .map_err(|_| "Public key has to be exactly 32 bytes long.")?;
+        .map_err(|_| "Public key has to be either 32 (Ed25519) or 33 (Secp256k1) bytes long.")?;
     match bond.get_stake().try_into() {
         Ok(bond) => Ok((pk, bond)),
         Err(err) => {
@@ -1147,25 +1765,46 @@ pub fn to_domain_validators(bond: &ipc::Bond) -> Result<(PublicKey, U512), Strin
     }
 }
 
+pub fn to_grpc_bond(public_key: &PublicKey, stake: U512) -> ipc::Bond {
+    let mut bond = ipc::Bond::new();
+    bond.set_validator_public_key(public_key.as_bytes().to_vec());
+    bond.set_stake(stake.into());
+    bond
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use std::convert::TryInto;
+    use std::convert::{TryFrom, TryInto};
 
     use proptest::prelude::*;
 
-    use contract_ffi::gens::{account_arb, contract_arb, key_arb, uref_map_arb, value_arb};
+    use contract_ffi::gens::{
+        account_arb, cl_type_arb, contract_arb, contract_package_arb, key_arb, uref_map_arb,
+        value_arb,
+    };
     use contract_ffi::key::Key;
+    use contract_ffi::system_contracts::pos::{
+        BondLimits, DelegationRateLimits, DEFAULT_ERA_DURATION,
+    };
+    use contract_ffi::system_contracts::{self, mint};
     use contract_ffi::uref::{AccessRights, URef};
+    use contract_ffi::value::account::{PublicKey, Weight};
+    use contract_ffi::value::{CLType, U512};
     use engine_core::engine_state::error::Error::ExecError;
-    use engine_core::engine_state::error::{Error as EngineError, RootNotFound};
-    use engine_core::engine_state::execution_effect::ExecutionEffect;
+    use engine_core::engine_state::error::{
+        AuthorizationFailure, Error as EngineError, RootNotFound,
+    };
+    use engine_core::engine_state::execution_effect::{Event, ExecutionEffect};
     use engine_core::engine_state::execution_result::ExecutionResult;
-    use engine_core::execution::Error;
-    use engine_shared::gas::Gas;
+    use engine_core::engine_state::genesis::GenesisConfig;
+    use engine_core::execution::{Error, MINT_ERROR_BASE, POS_ERROR_BASE};
+    use engine_shared::gas::{Gas, GasBreakdown, GasCategory};
+    use engine_shared::key_limits::KeyLimits;
     use engine_shared::newtypes::Blake2bHash;
     use engine_shared::transform::gens::transform_arb;
     use engine_shared::transform::Transform;
+    use engine_wasm_prep::wasm_costs::WasmCosts;
 
     use crate::engine_server::mappings::CommitTransforms;
 
@@ -1214,11 +1853,14 @@ mod tests {
         let execution_result: ExecutionResult = ExecutionResult::Success {
             effect: execution_effect,
             cost,
+            cost_breakdown: GasBreakdown::new(GasCategory::Opcodes, cost),
         };
         let mut ipc_deploy_result: ipc::DeployResult = execution_result.into();
         assert!(ipc_deploy_result.has_execution_result());
         let mut success = ipc_deploy_result.take_execution_result();
         assert_eq!(success.get_cost(), cost.as_u64());
+        assert_eq!(success.get_cost_breakdown().get_opcodes(), cost.as_u64());
+        assert_eq!(success.get_cost_breakdown().get_storage_writes(), 0);
 
         // Extract transform map from the IPC message and parse it back to the domain
         let ipc_transforms: HashMap<Key, Transform> = {
@@ -1233,11 +1875,61 @@ mod tests {
         assert_eq!(&input_transforms, &ipc_transforms);
     }
 
+    #[test]
+    fn deploy_result_to_ipc_should_keep_events_in_order() {
+        let emitter = Key::Account([1u8; 32]);
+        let events = vec![
+            Event::new(emitter, "first".to_string(), vec![1, 2, 3]),
+            Event::new(emitter, "second".to_string(), vec![]),
+        ];
+        let execution_effect = ExecutionEffect::default().with_events(events);
+        let execution_result = ExecutionResult::Success {
+            effect: execution_effect,
+            cost: Gas::from_u64(1),
+            cost_breakdown: GasBreakdown::new(GasCategory::Opcodes, Gas::from_u64(1)),
+        };
+        let mut ipc_deploy_result: ipc::DeployResult = execution_result.into();
+        let ipc_events = ipc_deploy_result
+            .take_execution_result()
+            .take_effects()
+            .take_events()
+            .into_vec();
+
+        assert_eq!(ipc_events.len(), 2);
+        assert_eq!(ipc_events[0].get_topic(), "first");
+        assert_eq!(ipc_events[0].get_payload(), &[1, 2, 3][..]);
+        assert_eq!(ipc_events[1].get_topic(), "second");
+        let ipc_emitter: Key = ipc_events[0].get_emitter().try_into().unwrap();
+        assert_eq!(ipc_emitter, emitter);
+    }
+
+    #[test]
+    fn authorization_failure_to_ipc_should_keep_details() {
+        let failure = AuthorizationFailure {
+            unknown_keys: vec![PublicKey::new([1u8; 32])],
+            below_threshold_keys: vec![PublicKey::new([2u8; 32])],
+            required_weight: Weight::new(3),
+            provided_weight: Weight::new(2),
+        };
+        let execution_result =
+            into_execution_failure(EngineError::AuthorizationFailure(failure), Gas::default());
+        let ipc_deploy_result: ipc::DeployResult = execution_result.into();
+        assert!(ipc_deploy_result.has_precondition_failure());
+        let precondition_failure = ipc_deploy_result.get_precondition_failure();
+        assert!(precondition_failure.has_authorization_failure());
+        let detail = precondition_failure.get_authorization_failure();
+        assert_eq!(detail.get_unknown_keys(), &[vec![1u8; 32]][..]);
+        assert_eq!(detail.get_below_threshold_keys(), &[vec![2u8; 32]][..]);
+        assert_eq!(detail.get_required_weight(), 3);
+        assert_eq!(detail.get_provided_weight(), 2);
+    }
+
     fn into_execution_failure<E: Into<EngineError>>(error: E, cost: Gas) -> ExecutionResult {
         ExecutionResult::Failure {
             error: error.into(),
             effect: Default::default(),
             cost,
+            cost_breakdown: GasBreakdown::new(GasCategory::Opcodes, cost),
         }
     }
 
@@ -1246,6 +1938,10 @@ mod tests {
         let ipc_deploy_result: ipc::DeployResult = execution_failure.into();
         assert!(ipc_deploy_result.has_execution_result());
         let success = ipc_deploy_result.get_execution_result();
+        assert_eq!(
+            success.get_cost_breakdown().get_opcodes(),
+            expected_cost.as_u64()
+        );
         Gas::from_u64(success.get_cost())
     }
 
@@ -1311,15 +2007,174 @@ mod tests {
             error: ExecError(revert_error),
             effect: Default::default(),
             cost: Gas::from_u64(10),
+            cost_breakdown: GasBreakdown::new(GasCategory::Opcodes, Gas::from_u64(10)),
         };
         let ipc_result: ipc::DeployResult = exec_result.into();
         assert!(ipc_result.has_execution_result());
         let ipc_execution_result = ipc_result.get_execution_result();
         assert_eq!(ipc_execution_result.cost, 10);
+        let ipc_exec_error = ipc_execution_result.get_error().get_exec_error();
+        assert_eq!(ipc_exec_error.message, "Exit code: 10");
+        assert_eq!(ipc_exec_error.code, ipc::DeployError_ErrorCode::REVERT);
+        assert_eq!(ipc_exec_error.get_revert_value().value, 10);
+    }
+
+    fn map_exec_error(error: EngineError) -> ipc::DeployError_ExecutionError {
+        let exec_result = ExecutionResult::Failure {
+            error,
+            effect: Default::default(),
+            cost: Gas::from_u64(10),
+            cost_breakdown: GasBreakdown::new(GasCategory::Opcodes, Gas::from_u64(10)),
+        };
+        let ipc_result: ipc::DeployResult = exec_result.into();
+        ipc_result
+            .get_execution_result()
+            .get_error()
+            .get_exec_error()
+            .to_owned()
+    }
+
+    #[test]
+    fn system_contract_errors_map_to_dedicated_ranges() {
+        let ipc_exec_error = map_exec_error(ExecError(Error::SystemContractError(
+            system_contracts::error::Error::ProofOfStakeError(7),
+        )));
+        assert_eq!(ipc_exec_error.message, "Proof of Stake error: 7");
+        assert_eq!(
+            ipc_exec_error.code,
+            ipc::DeployError_ErrorCode::PROOF_OF_STAKE_ERROR
+        );
+        assert_eq!(ipc_exec_error.get_revert_value().value, POS_ERROR_BASE + 7);
+
+        let ipc_exec_error = map_exec_error(EngineError::MintError(
+            mint::error::Error::InsufficientFunds,
+        ));
+        assert_eq!(ipc_exec_error.code, ipc::DeployError_ErrorCode::MINT_ERROR);
+        assert_eq!(ipc_exec_error.get_revert_value().value, MINT_ERROR_BASE);
+    }
+
+    #[test]
+    fn other_errors_map_to_codes_without_revert_value() {
+        let ipc_exec_error = map_exec_error(ExecError(Error::ForgedReference(URef::new(
+            [1; 32],
+            AccessRights::READ,
+        ))));
+        assert_eq!(
+            ipc_exec_error.code,
+            ipc::DeployError_ErrorCode::FORGED_REFERENCE
+        );
+        assert!(!ipc_exec_error.has_revert_value());
+    }
+
+    #[test]
+    fn genesis_config_without_costs_should_use_default_wasm_costs() {
+        let genesis_config: GenesisConfig = ipc::ChainSpec_GenesisConfig::new()
+            .try_into()
+            .expect("should map genesis config");
+        assert_eq!(genesis_config.wasm_costs(), WasmCosts::default());
+    }
+
+    #[test]
+    fn genesis_config_should_use_default_for_unset_key_limits() {
+        let mut ipc_genesis_config = ipc::ChainSpec_GenesisConfig::new();
+        ipc_genesis_config.set_max_named_keys(100);
+        let genesis_config: GenesisConfig = ipc_genesis_config
+            .try_into()
+            .expect("should map genesis config");
+        let defaults = KeyLimits::default();
+        assert_eq!(
+            genesis_config.key_limits(),
+            KeyLimits::new(defaults.max_associated_keys(), 100)
+        );
+    }
+
+    #[test]
+    fn genesis_config_should_map_delegation_rate_limits() {
+        let genesis_config: GenesisConfig = ipc::ChainSpec_GenesisConfig::new()
+            .try_into()
+            .expect("should map genesis config");
         assert_eq!(
-            ipc_execution_result.get_error().get_exec_error().message,
-            "Exit code: 10"
+            genesis_config.delegation_rate_limits(),
+            DelegationRateLimits::default()
         );
+
+        let limits = DelegationRateLimits::new(3, 2).unwrap();
+        let ipc_genesis_config: ipc::ChainSpec_GenesisConfig =
+            genesis_config.with_delegation_rate_limits(limits).into();
+        let genesis_config: GenesisConfig = ipc_genesis_config
+            .try_into()
+            .expect("should map genesis config");
+        assert_eq!(genesis_config.delegation_rate_limits(), limits);
+
+        let mut ipc_limits = ipc::ChainSpec_DelegationRateLimits::new();
+        ipc_limits.set_max_increase_per_era(256);
+        assert!(DelegationRateLimits::try_from(ipc_limits).is_err());
+    }
+
+    #[test]
+    fn genesis_config_should_map_bond_limits() {
+        let genesis_config: GenesisConfig = ipc::ChainSpec_GenesisConfig::new()
+            .try_into()
+            .expect("should map genesis config");
+        assert_eq!(genesis_config.bond_limits(), BondLimits::default());
+
+        let bond_limits = BondLimits::new(U512::from(10), 3, 2).unwrap();
+        let ipc_genesis_config: ipc::ChainSpec_GenesisConfig =
+            genesis_config.with_bond_limits(bond_limits).into();
+        let genesis_config: GenesisConfig = ipc_genesis_config
+            .try_into()
+            .expect("should map genesis config");
+        assert_eq!(genesis_config.bond_limits(), bond_limits);
+
+        let mut ipc_bond_limits: ipc::ChainSpec_BondLimits = bond_limits.into();
+        ipc_bond_limits.set_min_bond_amount(U512::zero().into());
+        assert!(BondLimits::try_from(ipc_bond_limits).is_err());
+    }
+
+    #[test]
+    fn genesis_config_should_map_era_duration() {
+        let genesis_config: GenesisConfig = ipc::ChainSpec_GenesisConfig::new()
+            .try_into()
+            .expect("should map genesis config");
+        assert_eq!(genesis_config.era_duration(), DEFAULT_ERA_DURATION);
+
+        let ipc_genesis_config: ipc::ChainSpec_GenesisConfig =
+            genesis_config.with_era_duration(1000).into();
+        let genesis_config: GenesisConfig = ipc_genesis_config
+            .try_into()
+            .expect("should map genesis config");
+        assert_eq!(genesis_config.era_duration(), 1000);
+    }
+
+    #[test]
+    fn genesis_config_should_map_unbond_delay() {
+        let ipc_genesis_config: ipc::ChainSpec_GenesisConfig =
+            GenesisConfig::try_from(ipc::ChainSpec_GenesisConfig::new())
+                .expect("should map genesis config")
+                .with_unbond_delay(1000)
+                .into();
+        let genesis_config: GenesisConfig = ipc_genesis_config
+            .try_into()
+            .expect("should map genesis config");
+        assert_eq!(genesis_config.unbond_delay(), 1000);
+    }
+
+    #[test]
+    fn genesis_config_should_use_wasm_costs_from_chainspec() {
+        let wasm_costs = WasmCosts {
+            storage_byte_cost: 7,
+            ..WasmCosts::default()
+        };
+        let mut ipc_genesis_config = ipc::ChainSpec_GenesisConfig::new();
+        {
+            let mut cost_table = ipc::ChainSpec_CostTable::new();
+            cost_table.set_wasm(wasm_costs.into());
+            ipc_genesis_config.set_costs(cost_table);
+        }
+        let genesis_config: GenesisConfig = ipc_genesis_config
+            .try_into()
+            .expect("should map genesis config");
+        assert_eq!(genesis_config.wasm_costs(), wasm_costs);
     }
 
     proptest! {
@@ -1356,6 +2211,22 @@ mod tests {
             assert_eq!(contract, contract_back)
         }
 
+        #[test]
+        fn contract_package_roundtrip(package in contract_package_arb()) {
+            let ipc_package: super::state::ContractPackage = package.clone().into();
+            let package_back = (&ipc_package).try_into()
+                .expect("Transforming state::ContractPackage into domain ContractPackage should succeed.");
+            assert_eq!(package, package_back)
+        }
+
+        #[test]
+        fn cl_type_roundtrip(cl_type in cl_type_arb()) {
+            let ipc_cl_type: super::state::CLType = (&cl_type).into();
+            let cl_type_back: CLType = (&ipc_cl_type).try_into()
+                .expect("Transforming state::CLType into domain CLType should succeed.");
+            assert_eq!(cl_type, cl_type_back)
+        }
+
         #[test]
         fn value_roundtrip(value in value_arb()) {
             let ipc_value: super::state::Value = value.clone().into();
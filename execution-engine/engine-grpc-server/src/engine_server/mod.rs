@@ -1,50 +1,113 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::fmt::Debug;
-use std::io::ErrorKind;
+use std::fs;
 use std::marker::{Send, Sync};
+use std::os::unix::fs::PermissionsExt;
 use std::time::Instant;
 
 use contract_ffi::key::Key;
 use contract_ffi::value::account::{BlockTime, PublicKey};
 use contract_ffi::value::U512;
-use engine_core::engine_state::error::Error as EngineError;
+use engine_core::engine_state::approval::Approval;
+use engine_core::engine_state::deploy_scheduler::PrecedingWrites;
+use engine_core::engine_state::error::{Error as EngineError, RootNotFound};
+use engine_core::engine_state::execution_effect::ExecutionEffect;
 use engine_core::engine_state::execution_result::ExecutionResult;
 use engine_core::engine_state::genesis::{GenesisConfig, GenesisURefsSource};
-use engine_core::engine_state::{genesis::GenesisResult, EngineState, GetBondedValidatorsError};
-use engine_core::execution::{Executor, WasmiExecutor};
+use engine_core::engine_state::native_transfer::NativeTransfer;
+use engine_core::engine_state::upgrade::{UpgradeConfig, UpgradeResult};
+use engine_core::engine_state::utils::bonded_validator_changes;
+use engine_core::engine_state::{
+    deploy_execution_key, executed_deploy_key, genesis::GenesisResult, hash_deploy, BalanceSource,
+    EngineState, GetAccountError, GetBalanceError, GetBondedValidatorsError, GetContractError,
+    GetProtocolDataError,
+};
+use engine_core::execution::{trace, Executor, WasmiExecutor};
+use engine_core::replay::ReplayReport;
 use engine_core::tracking_copy::QueryResult;
 use engine_shared::logging;
-use engine_shared::logging::{log_duration, log_info};
+use engine_shared::logging::{log_context, log_duration, log_info};
 use engine_shared::newtypes::{Blake2bHash, CorrelationId};
-use engine_storage::global_state::{CommitResult, StateProvider};
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_shared::socket::Socket;
+use engine_storage::global_state::{
+    CommitResult, DiffResult, PruneResult, StateProvider, TrieChunkResult,
+};
+use engine_storage::metrics;
+use engine_storage::protocol_data::ProtocolData;
 use engine_wasm_prep::{Preprocessor, WasmiPreprocessor};
 
+use self::health_service::HealthService;
 use self::ipc_grpc::ExecutionEngineService;
+use self::limited_service::LimitedService;
 use self::mappings::*;
+use self::read_only_service::ReadOnlyService;
+use self::server_config::ServerConfig;
 use crate::engine_server::ipc::CommitResponse;
 
+pub mod health;
+pub mod health_grpc;
+pub mod health_service;
 pub mod ipc;
 pub mod ipc_grpc;
+pub mod limited_service;
 pub mod mappings;
+pub mod read_only_service;
+pub mod server_config;
 pub mod state;
 pub mod transforms;
 
 const EXPECTED_PUBLIC_KEY_LENGTH: usize = 32;
+const EXPECTED_DEPLOY_HASH_LENGTH: usize = 32;
 
 const METRIC_DURATION_COMMIT: &str = "commit_duration";
 const METRIC_DURATION_EXEC: &str = "exec_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
+const METRIC_DURATION_BATCH_QUERY: &str = "batch_query_duration";
 const METRIC_DURATION_VALIDATE: &str = "validate_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
+const METRIC_DURATION_QUERY_VALIDATORS: &str = "query_validators_duration";
+const METRIC_DURATION_GET_BALANCE: &str = "get_balance_duration";
+const METRIC_DURATION_GET_ACCOUNT: &str = "get_account_duration";
+const METRIC_DURATION_GET_CONTRACT: &str = "get_contract_duration";
+const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
+const METRIC_DURATION_GET_PROTOCOL_DATA: &str = "get_protocol_data_duration";
+const METRIC_DURATION_PRUNE: &str = "prune_duration";
+const METRIC_DURATION_SPECULATIVE_EXEC: &str = "speculative_exec_duration";
+const METRIC_DURATION_STEP: &str = "step_duration";
+const METRIC_DURATION_STATE_DIFF: &str = "state_diff_duration";
+const METRIC_DURATION_GET_TRIE_CHUNK: &str = "get_trie_chunk_duration";
+const METRIC_DURATION_PUT_TRIE: &str = "put_trie_duration";
+const METRIC_DURATION_IMPORT_PROTOCOL_DATA: &str = "import_protocol_data_duration";
+const METRIC_DURATION_MISSING_TRIE_KEYS: &str = "missing_trie_keys_duration";
+const METRIC_DURATION_SCRAPE_METRICS: &str = "scrape_metrics_duration";
+const METRIC_DURATION_VERIFY_INTEGRITY: &str = "verify_integrity_duration";
+const METRIC_DURATION_REPLAY: &str = "replay_duration";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
+const TAG_RESPONSE_BATCH_QUERY: &str = "batch_query_response";
 const TAG_RESPONSE_VALIDATE: &str = "validate_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
+const TAG_RESPONSE_QUERY_VALIDATORS: &str = "query_validators_response";
+const TAG_RESPONSE_GET_BALANCE: &str = "get_balance_response";
+const TAG_RESPONSE_GET_ACCOUNT: &str = "get_account_response";
+const TAG_RESPONSE_GET_CONTRACT: &str = "get_contract_response";
+const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
+const TAG_RESPONSE_GET_PROTOCOL_DATA: &str = "get_protocol_data_response";
+const TAG_RESPONSE_PRUNE: &str = "prune_response";
+const TAG_RESPONSE_SPECULATIVE_EXEC: &str = "speculative_exec_response";
+const TAG_RESPONSE_STEP: &str = "step_response";
+const TAG_RESPONSE_STATE_DIFF: &str = "state_diff_response";
+const TAG_RESPONSE_GET_TRIE_CHUNK: &str = "get_trie_chunk_response";
+const TAG_RESPONSE_PUT_TRIE: &str = "put_trie_response";
+const TAG_RESPONSE_IMPORT_PROTOCOL_DATA: &str = "import_protocol_data_response";
+const TAG_RESPONSE_MISSING_TRIE_KEYS: &str = "missing_trie_keys_response";
+const TAG_RESPONSE_SCRAPE_METRICS: &str = "scrape_metrics_response";
+const TAG_RESPONSE_VERIFY_INTEGRITY: &str = "verify_integrity_response";
+const TAG_RESPONSE_REPLAY: &str = "replay_response";
 
 // Idea is that Engine will represent the core of the execution engine project.
 // It will act as an entry point for execution of Wasm binaries.
@@ -53,7 +116,7 @@ const TAG_RESPONSE_GENESIS: &str = "genesis_response";
 // (outer layer) leading to cleaner design.
 impl<S> ipc_grpc::ExecutionEngineService for EngineState<S>
 where
-    S: StateProvider,
+    S: StateProvider + Sync,
     EngineError: From<S::Error>,
     S::Error: Into<engine_core::execution::Error> + Debug,
 {
@@ -97,7 +160,19 @@ where
             Ok(Some(tracking_copy)) => tracking_copy,
         };
 
-        let key = match query_request.get_base_key().try_into() {
+        let formatted_base_key = query_request.get_formatted_base_key();
+        let key: Result<Key, ParsingError> = if formatted_base_key.is_empty() {
+            query_request.get_base_key().try_into()
+        } else {
+            Key::from_formatted_str(formatted_base_key).map_err(|error| {
+                ParsingError(format!(
+                    "Invalid formatted key {}: {}",
+                    formatted_base_key, error
+                ))
+            })
+        };
+
+        let key = match key {
             Err(ParsingError(err_msg)) => {
                 logging::log_error(&err_msg);
                 let mut result = ipc::QueryResponse::new();
@@ -115,32 +190,136 @@ where
 
         let path = query_request.get_path();
 
-        let response = match tracking_copy.query(correlation_id, key, path) {
-            Err(err) => {
-                let mut result = ipc::QueryResponse::new();
-                let error = format!("{:?}", err);
+        let mut response =
+            grpc_response_from_query_result(tracking_copy.query(correlation_id, key, path));
+
+        if response.has_success() && query_request.get_include_proofs() {
+            match self.get_query_proofs(correlation_id, state_hash, key, path) {
+                Ok(Some(proofs)) => {
+                    let proofs: Result<Vec<ipc::TrieMerkleProof>, _> =
+                        proofs.into_iter().map(TryInto::try_into).collect();
+                    match proofs {
+                        Ok(proofs) => response.set_proofs(proofs.into()),
+                        Err(error) => {
+                            let error = format!("Error serializing proofs: {:?}", error);
+                            logging::log_error(&error);
+                            response.set_failure(error);
+                        }
+                    }
+                }
+                Ok(None) => {
+                    let error = format!("Root not found: {:?}", state_hash);
+                    logging::log_warning(&error);
+                    response.set_failure(error);
+                }
+                Err(error) => {
+                    let error = format!("{:?}", error);
+                    logging::log_error(&error);
+                    response.set_failure(error);
+                }
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_QUERY,
+            TAG_RESPONSE_QUERY,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn batch_query(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        batch_query_request: ipc::BatchQueryRequest,
+    ) -> grpc::SingleResponse<ipc::BatchQueryResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let state_hash_bytes = batch_query_request.get_state_hash();
+        let state_hash: Blake2bHash = match state_hash_bytes.try_into() {
+            Ok(state_hash) => state_hash,
+            Err(_) => {
+                let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
                 logging::log_error(&error);
+                let mut result = ipc::BatchQueryResponse::new();
                 result.set_failure(error);
-                result
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_BATCH_QUERY,
+                    "state_hash_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
             }
-            Ok(QueryResult::ValueNotFound(full_path)) => {
-                let mut result = ipc::QueryResponse::new();
-                let error = format!("Value not found: {:?}", full_path);
-                logging::log_warning(&error);
+        };
+
+        // All queries share a single tracking copy, so trie values read while
+        // resolving one query are cached for the following ones.
+        let mut tracking_copy = match self.tracking_copy(state_hash) {
+            Err(storage_error) => {
+                let mut result = ipc::BatchQueryResponse::new();
+                let error = format!("Error during checkout out Trie: {:?}", storage_error);
+                logging::log_error(&error);
                 result.set_failure(error);
-                result
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_BATCH_QUERY,
+                    "tracking_copy_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
             }
-            Ok(QueryResult::Success(value)) => {
-                let mut result = ipc::QueryResponse::new();
-                result.set_success(value.into());
-                result
+            Ok(None) => {
+                logging::log_warning(&format!("Root not found: {:?}", state_hash));
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(state_hash.to_vec());
+                let mut result = ipc::BatchQueryResponse::new();
+                result.set_missing_state(root_not_found);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_BATCH_QUERY,
+                    "tracking_copy_root_not_found",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
             }
+            Ok(Some(tracking_copy)) => tracking_copy,
+        };
+
+        let mut query_responses = Vec::with_capacity(batch_query_request.get_queries().len());
+
+        for query in batch_query_request.get_queries() {
+            let query_response = match query.get_base_key().try_into() {
+                Err(ParsingError(err_msg)) => {
+                    logging::log_error(&err_msg);
+                    let mut result = ipc::QueryResponse::new();
+                    result.set_failure(err_msg);
+                    result
+                }
+                Ok(key) => grpc_response_from_query_result(tracking_copy.query(
+                    correlation_id,
+                    key,
+                    query.get_path(),
+                )),
+            };
+            query_responses.push(query_response);
+        }
+
+        let response = {
+            let mut batch_query_result = ipc::BatchQueryResult::new();
+            batch_query_result.set_results(query_responses.into());
+            let mut result = ipc::BatchQueryResponse::new();
+            result.set_success(batch_query_result);
+            result
         };
 
         log_duration(
             correlation_id,
-            METRIC_DURATION_QUERY,
-            TAG_RESPONSE_QUERY,
+            METRIC_DURATION_BATCH_QUERY,
+            TAG_RESPONSE_BATCH_QUERY,
             start.elapsed(),
         );
 
@@ -153,7 +332,8 @@ where
         exec_request: ipc::ExecRequest,
     ) -> grpc::SingleResponse<ipc::ExecResponse> {
         let start = Instant::now();
-        let correlation_id = CorrelationId::new();
+        let correlation_id = exec_correlation_id(exec_request.get_correlation_id());
+        let _log_context = log_context::enter_correlation_id(correlation_id);
 
         let protocol_version = exec_request.get_protocol_version();
 
@@ -161,33 +341,34 @@ where
         let prestate_hash: Blake2bHash = exec_request.get_parent_state_hash().try_into().unwrap();
 
         let blocktime = BlockTime(exec_request.get_block_time());
-
-        // TODO: don't unwrap
-        let wasm_costs = WasmCosts::from_version(protocol_version.value).unwrap();
+        let block_height = exec_request.get_block_height();
+        // Without a valid proposer, fees meant for the proposer stay in the rewards purse.
+        let proposer = PublicKey::try_from(exec_request.get_proposer()).ok();
 
         let deploys = exec_request.get_deploys();
 
-        let preprocessor: WasmiPreprocessor = WasmiPreprocessor::new(wasm_costs);
-
-        let executor = WasmiExecutor;
-
-        let deploys_result: Result<Vec<ipc::DeployResult>, ipc::RootNotFound> = run_deploys(
-            &self,
-            &executor,
-            &preprocessor,
-            prestate_hash,
-            blocktime,
-            deploys,
-            protocol_version,
-            correlation_id,
-        );
+        let deploys_result: Result<Vec<DeployOutcome>, ipc::RootNotFound> =
+            match protocol_executor(&self, protocol_version) {
+                Ok((executor, preprocessor)) => run_deploys(
+                    &self,
+                    &executor,
+                    &preprocessor,
+                    prestate_hash,
+                    blocktime,
+                    block_height,
+                    proposer,
+                    deploys,
+                    protocol_version,
+                    correlation_id,
+                    exec_request.get_trace(),
+                ),
+                Err(error) => Ok(precondition_failures(deploys.len(), error)),
+            };
 
         let exec_response = match deploys_result {
             Ok(deploy_results) => {
                 let mut exec_response = ipc::ExecResponse::new();
-                let mut exec_result = ipc::ExecResult::new();
-                exec_result.set_deploy_results(protobuf::RepeatedField::from_vec(deploy_results));
-                exec_response.set_success(exec_result);
+                exec_response.set_success(exec_result(deploy_results));
                 exec_response
             }
             Err(error) => {
@@ -213,60 +394,163 @@ where
         _request_options: ::grpc::RequestOptions,
         exec_request: ipc::ExecuteRequest,
     ) -> grpc::SingleResponse<ipc::ExecuteResponse> {
-        let start = Instant::now();
-        let correlation_id = CorrelationId::new();
-
-        let protocol_version = exec_request.get_protocol_version();
+        let exec_response = execute_request(self, exec_request, |_, _| ());
 
-        // TODO: don't unwrap
-        let prestate_hash: Blake2bHash = exec_request.get_parent_state_hash().try_into().unwrap();
-
-        let blocktime = BlockTime(exec_request.get_block_time());
+        grpc::SingleResponse::completed(exec_response)
+    }
 
-        // TODO: don't unwrap
-        let wasm_costs = WasmCosts::from_version(protocol_version.value).unwrap();
+    fn execute_stream(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        _exec_request: ipc::ExecuteRequest,
+    ) -> grpc::StreamingResponse<ipc::ExecuteStreamResponse> {
+        // Responses can't be sent before this returns, so collecting them here would only send
+        // them once all deploys have executed.  Streaming is left to `LimitedService`, see
+        // `ExecuteStreaming`.
+        grpc::StreamingResponse::err(grpc::Error::GrpcMessage(grpc::GrpcMessageError {
+            grpc_status: grpc::GrpcStatus::Unimplemented as i32,
+            grpc_message: "execute_stream is only served through a LimitedService".to_string(),
+        }))
+    }
 
-        let deploys = exec_request.get_deploys();
+    fn speculative_exec(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        speculative_exec_request: ipc::SpeculativeExecRequest,
+    ) -> grpc::SingleResponse<ipc::SpeculativeExecResponse> {
+        let start = Instant::now();
+        let correlation_id = exec_correlation_id(speculative_exec_request.get_correlation_id());
+        let _log_context = log_context::enter_correlation_id(correlation_id);
+
+        let protocol_version = speculative_exec_request.get_protocol_version();
+
+        let prestate_hash_bytes = speculative_exec_request.get_parent_state_hash();
+        let prestate_hash: Blake2bHash = match prestate_hash_bytes.try_into() {
+            Ok(prestate_hash) => prestate_hash,
+            Err(_) => {
+                logging::log_error("speculative exec error: invalid prestate hash");
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(prestate_hash_bytes.to_vec());
+                let mut response = ipc::SpeculativeExecResponse::new();
+                response.set_missing_parent(root_not_found);
+                return grpc::SingleResponse::completed(response);
+            }
+        };
 
-        let preprocessor: WasmiPreprocessor = WasmiPreprocessor::new(wasm_costs);
+        let blocktime = BlockTime(speculative_exec_request.get_block_time());
+        let block_height = speculative_exec_request.get_block_height();
+        let proposer = PublicKey::try_from(speculative_exec_request.get_proposer()).ok();
+
+        // The effects of the deploy are only returned, never committed
+        let deploy_result: Result<Vec<DeployOutcome>, ipc::RootNotFound> =
+            match protocol_executor(&self, protocol_version) {
+                Ok((executor, preprocessor)) => execute_deploys(
+                    &self,
+                    &executor,
+                    &preprocessor,
+                    prestate_hash,
+                    blocktime,
+                    block_height,
+                    proposer,
+                    std::slice::from_ref(speculative_exec_request.get_deploy()),
+                    protocol_version,
+                    correlation_id,
+                    speculative_exec_request.get_trace(),
+                    |_, _| (),
+                ),
+                Err(error) => Ok(precondition_failures(1, error)),
+            };
 
-        let executor = WasmiExecutor;
+        let mut response = ipc::SpeculativeExecResponse::new();
+        match deploy_result {
+            Ok(mut deploy_outcomes) => {
+                let (deploy_result, _) = deploy_outcomes.remove(0);
+                response.set_success(deploy_result);
+            }
+            Err(error) => {
+                logging::log_error("speculative exec error: RootNotFound");
+                response.set_missing_parent(error);
+            }
+        }
 
-        let deploys_result: Result<Vec<ipc::DeployResult>, ipc::RootNotFound> = execute_deploys(
-            &self,
-            &executor,
-            &preprocessor,
-            prestate_hash,
-            blocktime,
-            deploys,
-            protocol_version,
+        log_duration(
             correlation_id,
+            METRIC_DURATION_SPECULATIVE_EXEC,
+            TAG_RESPONSE_SPECULATIVE_EXEC,
+            start.elapsed(),
         );
 
-        let exec_response = match deploys_result {
-            Ok(deploy_results) => {
-                let mut exec_response = ipc::ExecuteResponse::new();
-                let mut exec_result = ipc::ExecResult::new();
-                exec_result.set_deploy_results(protobuf::RepeatedField::from_vec(deploy_results));
-                exec_response.set_success(exec_result);
-                exec_response
-            }
-            Err(error) => {
-                logging::log_error("deploy results error: RootNotFound");
-                let mut exec_response = ipc::ExecuteResponse::new();
-                exec_response.set_missing_parent(error);
-                exec_response
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn step(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        step_request: ipc::StepRequest,
+    ) -> grpc::SingleResponse<ipc::StepResponse> {
+        let start = Instant::now();
+        let correlation_id = exec_correlation_id(step_request.get_correlation_id());
+        let _log_context = log_context::enter_correlation_id(correlation_id);
+
+        let protocol_version = step_request.get_protocol_version();
+
+        let prestate_hash_bytes = step_request.get_parent_state_hash();
+        let prestate_hash: Blake2bHash = match prestate_hash_bytes.try_into() {
+            Ok(prestate_hash) => prestate_hash,
+            Err(_) => {
+                logging::log_error("step error: invalid prestate hash");
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(prestate_hash_bytes.to_vec());
+                let mut response = ipc::StepResponse::new();
+                response.set_missing_parent(root_not_found);
+                return grpc::SingleResponse::completed(response);
             }
         };
 
+        let blocktime = BlockTime(step_request.get_block_time());
+        let block_height = step_request.get_block_height();
+
+        // The effects of the step are only returned, the node commits them
+        let step_result =
+            protocol_executor(&self, protocol_version).map(|(executor, preprocessor)| {
+                self.run_step(
+                    blocktime,
+                    block_height,
+                    prestate_hash,
+                    protocol_version.value,
+                    correlation_id,
+                    &executor,
+                    &preprocessor,
+                )
+            });
+
+        let mut response = ipc::StepResponse::new();
+        match step_result {
+            Ok(Ok(None)) => response.set_success(ipc::StepResult::new()),
+            Ok(Ok(Some(ExecutionResult::Success { effect, .. }))) => {
+                let mut step_result = ipc::StepResult::new();
+                step_result.set_effect(effect.into());
+                response.set_success(step_result);
+            }
+            Ok(Ok(Some(ExecutionResult::Failure { error, .. }))) | Err(error) => {
+                let error_message = error.to_string();
+                logging::log_error(&format!("step error: {}", error_message));
+                response.set_failure(error_message);
+            }
+            Ok(Err(error)) => {
+                logging::log_error("step error: RootNotFound");
+                response.set_missing_parent(error.into());
+            }
+        }
+
         log_duration(
             correlation_id,
-            METRIC_DURATION_EXEC,
-            TAG_RESPONSE_EXEC,
+            METRIC_DURATION_STEP,
+            TAG_RESPONSE_STEP,
             start.elapsed(),
         );
 
-        grpc::SingleResponse::completed(exec_response)
+        grpc::SingleResponse::completed(response)
     }
 
     fn commit(
@@ -300,12 +584,18 @@ where
                     commit_result
                 {
                     let pos_key = Key::URef(GenesisURefsSource::default().get_pos_address());
+                    // A prestate without bonded validators makes every validator bonded in the
+                    // poststate a change.
+                    let previous_bonded_validators = self
+                        .get_bonded_validators(prestate_hash, &pos_key, correlation_id)
+                        .unwrap_or_default();
                     let bonded_validators_res =
                         self.get_bonded_validators(poststate_hash, &pos_key, correlation_id);
                     bonded_validators_and_commit_result::<S>(
                         prestate_hash,
                         poststate_hash,
                         commit_result,
+                        &previous_bonded_validators,
                         bonded_validators_res,
                     )
                 } else {
@@ -596,225 +886,1815 @@ where
 
         grpc::SingleResponse::completed(genesis_response)
     }
-}
-
-#[allow(clippy::too_many_arguments)]
-fn run_deploys<A, S, E, P>(
-    engine_state: &EngineState<S>,
-    executor: &E,
-    preprocessor: &P,
-    prestate_hash: Blake2bHash,
-    blocktime: BlockTime,
-    deploys: &[ipc::Deploy],
-    protocol_version: &state::ProtocolVersion,
-    correlation_id: CorrelationId,
-) -> Result<Vec<ipc::DeployResult>, ipc::RootNotFound>
-where
-    S: StateProvider,
-    E: Executor<A>,
-    P: Preprocessor<A>,
-    EngineError: From<S::Error>,
-    S::Error: Into<engine_core::execution::Error>,
-{
-    // We want to treat RootNotFound error differently b/c it should short-circuit
-    // the execution of ALL deploys within the block. This is because all of them
-    // share the same prestate and all of them would fail.
-    // Iterator (Result<_, _> + collect()) will short circuit the execution
-    // when run_deploy returns Err.
-    deploys
-        .iter()
-        .map(|deploy| {
-            let session = deploy.get_session();
-            let session_module_bytes = &session.code;
-            let session_args = &session.args;
-
-            let payment = deploy.get_payment();
-            let payment_module_bytes = &payment.code;
-            let payment_args = &payment.args;
-
-            let address = {
-                let address_len = deploy.address.len();
-                if address_len != EXPECTED_PUBLIC_KEY_LENGTH {
-                    let err = EngineError::InvalidPublicKeyLength {
-                        expected: EXPECTED_PUBLIC_KEY_LENGTH,
-                        actual: address_len,
-                    };
-                    let failure = ExecutionResult::precondition_failure(err);
-                    return Ok(failure.into());
-                }
-                let mut dest = [0; EXPECTED_PUBLIC_KEY_LENGTH];
-                dest.copy_from_slice(&deploy.address);
-                Key::Account(dest)
-            };
 
-            // Parse all authorization keys from IPC into a vector
-            let authorized_keys: BTreeSet<PublicKey> = {
-                let maybe_keys: Result<BTreeSet<_>, EngineError> = deploy
-                    .authorization_keys
-                    .iter()
-                    .map(|key_bytes| {
-                        // Try to convert an element of bytes into a possibly
-                        // valid PublicKey with error handling
-                        PublicKey::try_from(key_bytes.as_slice()).map_err(|_| {
-                            EngineError::InvalidPublicKeyLength {
-                                expected: EXPECTED_PUBLIC_KEY_LENGTH,
-                                actual: key_bytes.len(),
-                            }
-                        })
-                    })
-                    .collect();
-
-                match maybe_keys {
-                    Ok(keys) => keys,
-                    Err(error) => return Ok(ExecutionResult::precondition_failure(error).into()),
-                }
-            };
+    fn query_validators(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        query_validators_request: ipc::QueryValidatorsRequest,
+    ) -> grpc::SingleResponse<ipc::QueryValidatorsResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
 
-            let deploy_hash = {
-                let mut buff = [0u8; 32];
-                let hash_slice = deploy.get_deploy_hash();
-                buff.copy_from_slice(hash_slice);
-                buff
-            };
-            let protocol_version = protocol_version.value;
-            engine_state
-                .run_deploy(
-                    session_module_bytes,
-                    session_args,
-                    payment_module_bytes,
-                    payment_args,
-                    address,
-                    authorized_keys,
-                    blocktime,
-                    deploy_hash,
-                    prestate_hash,
-                    protocol_version,
+        let state_hash_bytes = query_validators_request.get_state_hash();
+        let state_hash: Blake2bHash = match state_hash_bytes.try_into() {
+            Ok(state_hash) => state_hash,
+            Err(_) => {
+                let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
+                logging::log_error(&error);
+                let mut result = ipc::QueryValidatorsResponse::new();
+                result.set_failure(error);
+                log_duration(
                     correlation_id,
-                    executor,
-                    preprocessor,
-                )
-                .map(Into::into)
-                .map_err(Into::into)
-        })
-        .collect()
-}
+                    METRIC_DURATION_QUERY_VALIDATORS,
+                    "state_hash_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
 
-#[allow(clippy::too_many_arguments)]
-fn execute_deploys<A, S, E, P>(
-    engine_state: &EngineState<S>,
-    executor: &E,
-    preprocessor: &P,
-    prestate_hash: Blake2bHash,
-    blocktime: BlockTime,
-    deploys: &[ipc::DeployItem],
-    protocol_version: &state::ProtocolVersion,
-    correlation_id: CorrelationId,
-) -> Result<Vec<ipc::DeployResult>, ipc::RootNotFound>
-where
-    S: StateProvider,
-    E: Executor<A>,
-    P: Preprocessor<A>,
-    EngineError: From<S::Error>,
-    S::Error: Into<engine_core::execution::Error>,
-{
-    // We want to treat RootNotFound error differently b/c it should short-circuit
-    // the execution of ALL deploys within the block. This is because all of them
-    // share the same prestate and all of them would fail.
-    // Iterator (Result<_, _> + collect()) will short circuit the execution
-    // when run_deploy returns Err.
-    deploys
-        .iter()
-        .map(|deploy| {
-            let session_payload = match deploy.get_session().to_owned().payload {
-                Some(payload) => payload.into(),
-                None => {
-                    return Ok(
-                        ExecutionResult::precondition_failure(EngineError::DeployError).into(),
-                    )
-                }
-            };
+        let pos_key = Key::URef(GenesisURefsSource::default().get_pos_address());
 
-            let payment_payload = match deploy.get_payment().to_owned().payload {
-                Some(payload) => payload.into(),
-                None => {
-                    return Ok(
-                        ExecutionResult::precondition_failure(EngineError::DeployError).into(),
-                    )
-                }
-            };
+        let response = match self.get_bonded_validators(state_hash, &pos_key, correlation_id) {
+            Ok(bonded_validators) => {
+                let grpc_bonded_validators = bonded_validators
+                    .iter()
+                    .map(|(public_key, stake)| to_grpc_bond(public_key, *stake))
+                    .collect::<Vec<ipc::Bond>>()
+                    .into();
+                let mut query_validators_result = ipc::QueryValidatorsResult::new();
+                query_validators_result.set_bonded_validators(grpc_bonded_validators);
+                let mut result = ipc::QueryValidatorsResponse::new();
+                result.set_success(query_validators_result);
+                result
+            }
+            Err(GetBondedValidatorsError::PostStateHashNotFound(root_hash)) => {
+                logging::log_warning(&format!("Root not found: {:?}", root_hash));
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(root_hash.to_vec());
+                let mut result = ipc::QueryValidatorsResponse::new();
+                result.set_missing_state(root_not_found);
+                result
+            }
+            Err(GetBondedValidatorsError::ProofOfStakeNotFound(key)) => {
+                let error = format!("Proof of stake contract not found under {:?}", key);
+                logging::log_error(&error);
+                let mut result = ipc::QueryValidatorsResponse::new();
+                result.set_failure(error);
+                result
+            }
+            Err(GetBondedValidatorsError::StateError(error)) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::QueryValidatorsResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
 
-            let address = {
-                let address_len = deploy.address.len();
-                if address_len != EXPECTED_PUBLIC_KEY_LENGTH {
-                    let err = EngineError::InvalidPublicKeyLength {
-                        expected: EXPECTED_PUBLIC_KEY_LENGTH,
-                        actual: address_len,
-                    };
-                    let failure = ExecutionResult::precondition_failure(err);
-                    return Ok(failure.into());
-                }
-                let mut dest = [0; EXPECTED_PUBLIC_KEY_LENGTH];
-                dest.copy_from_slice(&deploy.address);
-                Key::Account(dest)
-            };
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_QUERY_VALIDATORS,
+            TAG_RESPONSE_QUERY_VALIDATORS,
+            start.elapsed(),
+        );
 
-            // Parse all authorization keys from IPC into a vector
-            let authorization_keys: BTreeSet<PublicKey> = {
-                let maybe_keys: Result<BTreeSet<_>, EngineError> = deploy
-                    .authorization_keys
-                    .iter()
-                    .map(|key_bytes| {
-                        // Try to convert an element of bytes into a possibly
-                        // valid PublicKey with error handling
-                        PublicKey::try_from(key_bytes.as_slice()).map_err(|_| {
-                            EngineError::InvalidPublicKeyLength {
-                                expected: EXPECTED_PUBLIC_KEY_LENGTH,
-                                actual: key_bytes.len(),
-                            }
-                        })
-                    })
-                    .collect();
-
-                match maybe_keys {
-                    Ok(keys) => keys,
-                    Err(error) => return Ok(ExecutionResult::precondition_failure(error).into()),
-                }
-            };
+        grpc::SingleResponse::completed(response)
+    }
 
-            let deploy_hash = {
-                let mut buff = [0u8; 32];
-                let hash_slice = deploy.get_deploy_hash();
-                buff.copy_from_slice(hash_slice);
-                buff
-            };
+    fn get_balance(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        get_balance_request: ipc::GetBalanceRequest,
+    ) -> grpc::SingleResponse<ipc::GetBalanceResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
 
-            let protocol_version = protocol_version.value;
-            engine_state
-                .run_deploy_item(
-                    session_payload,
-                    payment_payload,
-                    address,
-                    authorization_keys,
-                    blocktime,
-                    deploy_hash,
-                    prestate_hash,
-                    protocol_version,
+        let state_hash_bytes = get_balance_request.get_state_hash();
+        let state_hash: Blake2bHash = match state_hash_bytes.try_into() {
+            Ok(state_hash) => state_hash,
+            Err(_) => {
+                let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
+                logging::log_error(&error);
+                let mut result = ipc::GetBalanceResponse::new();
+                result.set_failure(error);
+                log_duration(
                     correlation_id,
-                    executor,
-                    preprocessor,
-                )
-                .map(Into::into)
-                .map_err(Into::into)
-        })
+                    METRIC_DURATION_GET_BALANCE,
+                    "state_hash_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let balance_source: BalanceSource = match get_balance_request
+            .purse
+            .clone()
+            .ok_or_else(|| "Missing purse".to_string())
+            .and_then(|purse| BalanceSource::try_from(purse).map_err(|error| error.to_string()))
+        {
+            Ok(balance_source) => balance_source,
+            Err(error) => {
+                logging::log_error(&error);
+                let mut result = ipc::GetBalanceResponse::new();
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GET_BALANCE,
+                    "purse_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let response = match self.get_balance(correlation_id, state_hash, balance_source) {
+            Ok(balance) => {
+                let mut result = ipc::GetBalanceResponse::new();
+                result.set_balance(balance.value().into());
+                result
+            }
+            Err(GetBalanceError::PostStateHashNotFound(root_hash)) => {
+                logging::log_warning(&format!("Root not found: {:?}", root_hash));
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(root_hash.to_vec());
+                let mut result = ipc::GetBalanceResponse::new();
+                result.set_missing_state(root_not_found);
+                result
+            }
+            Err(GetBalanceError::EngineError(error)) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::GetBalanceResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_BALANCE,
+            TAG_RESPONSE_GET_BALANCE,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn get_account(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        get_account_request: ipc::GetAccountRequest,
+    ) -> grpc::SingleResponse<ipc::GetAccountResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let state_hash_bytes = get_account_request.get_state_hash();
+        let state_hash: Blake2bHash = match state_hash_bytes.try_into() {
+            Ok(state_hash) => state_hash,
+            Err(_) => {
+                let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
+                logging::log_error(&error);
+                let mut result = ipc::GetAccountResponse::new();
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GET_ACCOUNT,
+                    "state_hash_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let account_address_bytes = get_account_request.get_account_address();
+        let account_address: [u8; 32] = match account_address_bytes.try_into() {
+            Ok(account_address) => account_address,
+            Err(_) => {
+                let error = format!(
+                    "Invalid account address length: {}",
+                    account_address_bytes.len()
+                );
+                logging::log_error(&error);
+                let mut result = ipc::GetAccountResponse::new();
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GET_ACCOUNT,
+                    "account_address_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let response = match self.read_account(correlation_id, state_hash, account_address) {
+            Ok(Some(account)) => {
+                let mut result = ipc::GetAccountResponse::new();
+                result.set_account(account.into());
+                result
+            }
+            Ok(None) => {
+                let mut account_not_found = ipc::AccountNotFound::new();
+                account_not_found.set_account_address(account_address.to_vec());
+                let mut result = ipc::GetAccountResponse::new();
+                result.set_missing_account(account_not_found);
+                result
+            }
+            Err(GetAccountError::PostStateHashNotFound(root_hash)) => {
+                logging::log_warning(&format!("Root not found: {:?}", root_hash));
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(root_hash.to_vec());
+                let mut result = ipc::GetAccountResponse::new();
+                result.set_missing_state(root_not_found);
+                result
+            }
+            Err(GetAccountError::EngineError(error)) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::GetAccountResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_ACCOUNT,
+            TAG_RESPONSE_GET_ACCOUNT,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn get_contract(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        get_contract_request: ipc::GetContractRequest,
+    ) -> grpc::SingleResponse<ipc::GetContractResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let state_hash_bytes = get_contract_request.get_state_hash();
+        let state_hash: Blake2bHash = match state_hash_bytes.try_into() {
+            Ok(state_hash) => state_hash,
+            Err(_) => {
+                let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
+                logging::log_error(&error);
+                let mut result = ipc::GetContractResponse::new();
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GET_CONTRACT,
+                    "state_hash_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let contract_key: Key = match get_contract_request.get_contract_key().try_into() {
+            Ok(contract_key) => contract_key,
+            Err(ParsingError(error)) => {
+                logging::log_error(&error);
+                let mut result = ipc::GetContractResponse::new();
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GET_CONTRACT,
+                    "contract_key_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let response = match self.read_contract(correlation_id, state_hash, contract_key) {
+            Ok(Some(contract)) => {
+                let mut contract: state::Contract = contract.into();
+                let body = contract.take_body();
+                let mut contract_info = ipc::ContractInfo::new();
+                contract_info.set_named_keys(contract.take_known_urefs());
+                contract_info.set_protocol_version(contract.take_protocol_version());
+                contract_info.set_body_hash(Blake2bHash::new(&body).to_vec());
+                if get_contract_request.get_include_body() {
+                    contract_info.set_body(body);
+                }
+                let mut result = ipc::GetContractResponse::new();
+                result.set_contract(contract_info);
+                result
+            }
+            Ok(None) => {
+                let mut contract_not_found = ipc::ContractNotFound::new();
+                contract_not_found.set_contract_key((&contract_key).into());
+                let mut result = ipc::GetContractResponse::new();
+                result.set_missing_contract(contract_not_found);
+                result
+            }
+            Err(GetContractError::PostStateHashNotFound(root_hash)) => {
+                logging::log_warning(&format!("Root not found: {:?}", root_hash));
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(root_hash.to_vec());
+                let mut result = ipc::GetContractResponse::new();
+                result.set_missing_state(root_not_found);
+                result
+            }
+            Err(GetContractError::EngineError(error)) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::GetContractResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_CONTRACT,
+            TAG_RESPONSE_GET_CONTRACT,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn upgrade(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        upgrade_request: ipc::UpgradeRequest,
+    ) -> grpc::SingleResponse<ipc::UpgradeResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let upgrade_config: UpgradeConfig = match upgrade_request.try_into() {
+            Ok(upgrade_config) => upgrade_config,
+            Err(error) => {
+                let err_msg = error.to_string();
+                logging::log_error(&err_msg);
+
+                let mut upgrade_response = ipc::UpgradeResponse::new();
+                let mut upgrade_deploy_error = ipc::UpgradeDeployError::new();
+                upgrade_deploy_error.set_message(err_msg);
+                upgrade_response.set_failed_deploy(upgrade_deploy_error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_UPGRADE,
+                    "upgrade_request_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(upgrade_response);
+            }
+        };
+
+        let new_protocol_version = upgrade_config.new_protocol_version();
+
+        let upgrade_response = match self.commit_upgrade(correlation_id, upgrade_config) {
+            Ok(UpgradeResult::Success {
+                post_state_hash,
+                effect,
+            }) => {
+                let success_message = format!(
+                    "upgrade to protocol version {} successful: {}",
+                    new_protocol_version, post_state_hash
+                );
+                log_info(&success_message);
+
+                let mut upgrade_response = ipc::UpgradeResponse::new();
+                let mut upgrade_result = ipc::UpgradeResult::new();
+                upgrade_result.set_post_state_hash(post_state_hash.to_vec());
+                upgrade_result.set_effect(effect.into());
+                upgrade_response.set_success(upgrade_result);
+                upgrade_response
+            }
+            Ok(upgrade_result) => {
+                let err_msg = upgrade_result.to_string();
+                logging::log_error(&err_msg);
+
+                let mut upgrade_response = ipc::UpgradeResponse::new();
+                let mut upgrade_deploy_error = ipc::UpgradeDeployError::new();
+                upgrade_deploy_error.set_message(err_msg);
+                upgrade_response.set_failed_deploy(upgrade_deploy_error);
+                upgrade_response
+            }
+            Err(err) => {
+                let err_msg = err.to_string();
+                logging::log_error(&err_msg);
+
+                let mut upgrade_response = ipc::UpgradeResponse::new();
+                let mut upgrade_deploy_error = ipc::UpgradeDeployError::new();
+                upgrade_deploy_error.set_message(err_msg);
+                upgrade_response.set_failed_deploy(upgrade_deploy_error);
+                upgrade_response
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_UPGRADE,
+            TAG_RESPONSE_UPGRADE,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(upgrade_response)
+    }
+
+    fn get_protocol_data(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        get_protocol_data_request: ipc::GetProtocolDataRequest,
+    ) -> grpc::SingleResponse<ipc::GetProtocolDataResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let state_hash_bytes = get_protocol_data_request.get_state_hash();
+        let state_hash: Blake2bHash = match state_hash_bytes.try_into() {
+            Ok(state_hash) => state_hash,
+            Err(_) => {
+                let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
+                logging::log_error(&error);
+                let mut result = ipc::GetProtocolDataResponse::new();
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GET_PROTOCOL_DATA,
+                    "state_hash_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let response = match self.get_protocol_data(correlation_id, state_hash) {
+            Ok((protocol_version, protocol_data)) => {
+                let mut result = ipc::GetProtocolDataResponse::new();
+                result.set_success(protocol_data_result(protocol_version, &protocol_data));
+                result
+            }
+            Err(GetProtocolDataError::PostStateHashNotFound(root_hash)) => {
+                logging::log_warning(&format!("Root not found: {:?}", root_hash));
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(root_hash.to_vec());
+                let mut result = ipc::GetProtocolDataResponse::new();
+                result.set_missing_state(root_not_found);
+                result
+            }
+            Err(GetProtocolDataError::ProtocolVersionNotFound) => {
+                let error = format!("No protocol version recorded at {:?}", state_hash);
+                logging::log_error(&error);
+                let mut result = ipc::GetProtocolDataResponse::new();
+                result.set_failure(error);
+                result
+            }
+            Err(GetProtocolDataError::ProtocolDataNotFound(protocol_version)) => {
+                let error = format!("No protocol data for protocol version {}", protocol_version);
+                logging::log_error(&error);
+                let mut result = ipc::GetProtocolDataResponse::new();
+                result.set_failure(error);
+                result
+            }
+            Err(GetProtocolDataError::EngineError(error)) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::GetProtocolDataResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_PROTOCOL_DATA,
+            TAG_RESPONSE_GET_PROTOCOL_DATA,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn prune(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        prune_request: ipc::PruneRequest,
+    ) -> grpc::SingleResponse<ipc::PruneResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut retain_roots: Vec<Blake2bHash> = Vec::new();
+        for state_hash_bytes in prune_request.get_retain_state_hashes() {
+            match state_hash_bytes.as_slice().try_into() {
+                Ok(state_hash) => retain_roots.push(state_hash),
+                Err(_) => {
+                    let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
+                    logging::log_error(&error);
+                    let mut result = ipc::PruneResponse::new();
+                    result.set_failure(error);
+                    log_duration(
+                        correlation_id,
+                        METRIC_DURATION_PRUNE,
+                        "state_hash_parsing_error",
+                        start.elapsed(),
+                    );
+                    return grpc::SingleResponse::completed(result);
+                }
+            }
+        }
+
+        let response = match self.prune(correlation_id, &retain_roots) {
+            Ok(PruneResult::Success(pruned_count)) => {
+                log_info(&format!("Pruned {} trie values", pruned_count));
+                let mut prune_result = ipc::PruneResult::new();
+                prune_result.set_pruned_count(pruned_count as u64);
+                let mut result = ipc::PruneResponse::new();
+                result.set_success(prune_result);
+                result
+            }
+            Ok(PruneResult::RootNotFound(root_hash)) => {
+                logging::log_warning(&format!("Root not found: {:?}", root_hash));
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(root_hash.to_vec());
+                let mut result = ipc::PruneResponse::new();
+                result.set_missing_state(root_not_found);
+                result
+            }
+            Ok(PruneResult::MissingNode(trie_hash)) => {
+                logging::log_error(&format!("Trie node not found: {:?}", trie_hash));
+                let mut trie_node_not_found = ipc::TrieNodeNotFound::new();
+                trie_node_not_found.set_hash(trie_hash.to_vec());
+                let mut result = ipc::PruneResponse::new();
+                result.set_missing_node(trie_node_not_found);
+                result
+            }
+            Err(error) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::PruneResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_PRUNE,
+            TAG_RESPONSE_PRUNE,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn state_diff(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        state_diff_request: ipc::StateDiffRequest,
+    ) -> grpc::SingleResponse<ipc::StateDiffResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut state_hashes: Vec<Blake2bHash> = Vec::with_capacity(2);
+        for state_hash_bytes in &[
+            state_diff_request.get_prestate_hash(),
+            state_diff_request.get_poststate_hash(),
+        ] {
+            match (*state_hash_bytes).try_into() {
+                Ok(state_hash) => state_hashes.push(state_hash),
+                Err(_) => {
+                    let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
+                    logging::log_error(&error);
+                    let mut result = ipc::StateDiffResponse::new();
+                    result.set_failure(error);
+                    log_duration(
+                        correlation_id,
+                        METRIC_DURATION_STATE_DIFF,
+                        "state_hash_parsing_error",
+                        start.elapsed(),
+                    );
+                    return grpc::SingleResponse::completed(result);
+                }
+            }
+        }
+
+        let response = match self.diff(correlation_id, state_hashes[0], state_hashes[1]) {
+            Ok(DiffResult::Success(keys)) => {
+                let mut state_diff_result = ipc::StateDiffResult::new();
+                state_diff_result.set_keys(keys.iter().map(Into::into).collect());
+                let mut result = ipc::StateDiffResponse::new();
+                result.set_success(state_diff_result);
+                result
+            }
+            Ok(DiffResult::RootNotFound(root_hash)) => {
+                logging::log_warning(&format!("Root not found: {:?}", root_hash));
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(root_hash.to_vec());
+                let mut result = ipc::StateDiffResponse::new();
+                result.set_missing_state(root_not_found);
+                result
+            }
+            Ok(DiffResult::MissingNode(trie_hash)) => {
+                logging::log_error(&format!("Trie node not found: {:?}", trie_hash));
+                let mut trie_node_not_found = ipc::TrieNodeNotFound::new();
+                trie_node_not_found.set_hash(trie_hash.to_vec());
+                let mut result = ipc::StateDiffResponse::new();
+                result.set_missing_node(trie_node_not_found);
+                result
+            }
+            Err(error) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::StateDiffResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_STATE_DIFF,
+            TAG_RESPONSE_STATE_DIFF,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn get_trie_chunk(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        get_trie_chunk_request: ipc::GetTrieChunkRequest,
+    ) -> grpc::SingleResponse<ipc::GetTrieChunkResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        // The state hash comes first, followed by the cursor.
+        let mut hashes: Vec<Blake2bHash> = Vec::new();
+        for hash_bytes in std::iter::once(get_trie_chunk_request.get_state_hash()).chain(
+            get_trie_chunk_request
+                .get_cursor()
+                .iter()
+                .map(Vec::as_slice),
+        ) {
+            match hash_bytes.try_into() {
+                Ok(hash) => hashes.push(hash),
+                Err(_) => {
+                    let error = format!("Invalid hash length: {}", hash_bytes.len());
+                    logging::log_error(&error);
+                    let mut result = ipc::GetTrieChunkResponse::new();
+                    result.set_failure(error);
+                    log_duration(
+                        correlation_id,
+                        METRIC_DURATION_GET_TRIE_CHUNK,
+                        "state_hash_parsing_error",
+                        start.elapsed(),
+                    );
+                    return grpc::SingleResponse::completed(result);
+                }
+            }
+        }
+
+        let response = match self.get_trie_chunk(correlation_id, hashes[0], &hashes[1..]) {
+            Ok(TrieChunkResult::Success(trie_chunk)) => {
+                let mut trie_chunk_result = ipc::TrieChunk::new();
+                trie_chunk_result.set_tries(trie_chunk.tries.into());
+                trie_chunk_result
+                    .set_cursor(trie_chunk.cursor.iter().map(|hash| hash.to_vec()).collect());
+                let mut result = ipc::GetTrieChunkResponse::new();
+                result.set_success(trie_chunk_result);
+                result
+            }
+            Ok(TrieChunkResult::RootNotFound(root_hash)) => {
+                logging::log_warning(&format!("Root not found: {:?}", root_hash));
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(root_hash.to_vec());
+                let mut result = ipc::GetTrieChunkResponse::new();
+                result.set_missing_state(root_not_found);
+                result
+            }
+            Err(error) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::GetTrieChunkResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_TRIE_CHUNK,
+            TAG_RESPONSE_GET_TRIE_CHUNK,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn put_trie(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        put_trie_request: ipc::PutTrieRequest,
+    ) -> grpc::SingleResponse<ipc::PutTrieResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let response = match self.put_trie(correlation_id, put_trie_request.get_trie()) {
+            Ok(trie_hash) => {
+                let mut result = ipc::PutTrieResponse::new();
+                result.set_trie_hash(trie_hash.to_vec());
+                result
+            }
+            Err(error) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::PutTrieResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_PUT_TRIE,
+            TAG_RESPONSE_PUT_TRIE,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn import_protocol_data(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        import_protocol_data_request: ipc::ImportProtocolDataRequest,
+    ) -> grpc::SingleResponse<ipc::ImportProtocolDataResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let state_hash_bytes = import_protocol_data_request.get_state_hash();
+        let state_hash: Blake2bHash = match state_hash_bytes.try_into() {
+            Ok(state_hash) => state_hash,
+            Err(_) => {
+                let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
+                logging::log_error(&error);
+                let mut result = ipc::ImportProtocolDataResponse::new();
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_IMPORT_PROTOCOL_DATA,
+                    "state_hash_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let response = match self.import_protocol_data(correlation_id, state_hash) {
+            Ok((protocol_version, protocol_data)) => {
+                let mut result = ipc::ImportProtocolDataResponse::new();
+                result.set_success(protocol_data_result(protocol_version, &protocol_data));
+                result
+            }
+            Err(GetProtocolDataError::PostStateHashNotFound(root_hash)) => {
+                logging::log_warning(&format!("Root not found: {:?}", root_hash));
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(root_hash.to_vec());
+                let mut result = ipc::ImportProtocolDataResponse::new();
+                result.set_missing_state(root_not_found);
+                result
+            }
+            Err(GetProtocolDataError::ProtocolVersionNotFound) => {
+                let error = format!("No protocol version recorded at {:?}", state_hash);
+                logging::log_error(&error);
+                let mut result = ipc::ImportProtocolDataResponse::new();
+                result.set_failure(error);
+                result
+            }
+            Err(GetProtocolDataError::ProtocolDataNotFound(protocol_version)) => {
+                let error = format!(
+                    "No protocol data for protocol version {} recorded at {:?}",
+                    protocol_version, state_hash
+                );
+                logging::log_error(&error);
+                let mut result = ipc::ImportProtocolDataResponse::new();
+                result.set_failure(error);
+                result
+            }
+            Err(GetProtocolDataError::EngineError(error)) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::ImportProtocolDataResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_IMPORT_PROTOCOL_DATA,
+            TAG_RESPONSE_IMPORT_PROTOCOL_DATA,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        missing_trie_keys_request: ipc::MissingTrieKeysRequest,
+    ) -> grpc::SingleResponse<ipc::MissingTrieKeysResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let state_hash_bytes = missing_trie_keys_request.get_state_hash();
+        let state_hash: Blake2bHash = match state_hash_bytes.try_into() {
+            Ok(state_hash) => state_hash,
+            Err(_) => {
+                let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
+                logging::log_error(&error);
+                let mut result = ipc::MissingTrieKeysResponse::new();
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_MISSING_TRIE_KEYS,
+                    "state_hash_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let response = match self.missing_trie_keys(correlation_id, state_hash) {
+            Ok(missing_trie_keys) => {
+                let mut missing_trie_keys_result = ipc::MissingTrieKeysResult::new();
+                missing_trie_keys_result
+                    .set_trie_hashes(missing_trie_keys.iter().map(|hash| hash.to_vec()).collect());
+                let mut result = ipc::MissingTrieKeysResponse::new();
+                result.set_success(missing_trie_keys_result);
+                result
+            }
+            Err(error) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::MissingTrieKeysResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_MISSING_TRIE_KEYS,
+            TAG_RESPONSE_MISSING_TRIE_KEYS,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn scrape_metrics(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        _scrape_metrics_request: ipc::ScrapeMetricsRequest,
+    ) -> grpc::SingleResponse<ipc::ScrapeMetricsResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = ipc::ScrapeMetricsResponse::new();
+        response.set_text(metrics::scrape_metrics());
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_SCRAPE_METRICS,
+            TAG_RESPONSE_SCRAPE_METRICS,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn verify_integrity(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        verify_integrity_request: ipc::VerifyIntegrityRequest,
+    ) -> grpc::SingleResponse<ipc::VerifyIntegrityResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut root_hashes: Vec<Blake2bHash> = Vec::new();
+        for state_hash_bytes in verify_integrity_request.get_state_hashes() {
+            match state_hash_bytes.as_slice().try_into() {
+                Ok(state_hash) => root_hashes.push(state_hash),
+                Err(_) => {
+                    let error = format!("Invalid state hash length: {}", state_hash_bytes.len());
+                    logging::log_error(&error);
+                    let mut result = ipc::VerifyIntegrityResponse::new();
+                    result.set_failure(error);
+                    log_duration(
+                        correlation_id,
+                        METRIC_DURATION_VERIFY_INTEGRITY,
+                        "state_hash_parsing_error",
+                        start.elapsed(),
+                    );
+                    return grpc::SingleResponse::completed(result);
+                }
+            }
+        }
+
+        let response = match self.verify_integrity(correlation_id, &root_hashes) {
+            Ok(report) => {
+                if report.is_ok() {
+                    log_info(&format!("Verified {} trie values", report.checked));
+                } else {
+                    logging::log_warning(&format!(
+                        "Found {} corrupt and {} dangling trie values",
+                        report.corrupt.len(),
+                        report.dangling.len()
+                    ));
+                }
+                let mut verify_integrity_result = ipc::VerifyIntegrityResult::new();
+                verify_integrity_result.set_checked_count(report.checked as u64);
+                verify_integrity_result.set_corrupt_trie_hashes(
+                    report.corrupt.iter().map(|hash| hash.to_vec()).collect(),
+                );
+                verify_integrity_result.set_dangling_trie_hashes(
+                    report.dangling.iter().map(|hash| hash.to_vec()).collect(),
+                );
+                let mut result = ipc::VerifyIntegrityResponse::new();
+                result.set_success(verify_integrity_result);
+                result
+            }
+            Err(error) => {
+                let error = format!("{:?}", error);
+                logging::log_error(&error);
+                let mut result = ipc::VerifyIntegrityResponse::new();
+                result.set_failure(error);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_VERIFY_INTEGRITY,
+            TAG_RESPONSE_VERIFY_INTEGRITY,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+
+    fn replay(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        replay_request: ipc::ReplayRequest,
+    ) -> grpc::SingleResponse<ipc::ReplayResponse> {
+        let start = Instant::now();
+
+        let failure = |error: String, correlation_id: CorrelationId| {
+            logging::log_error(&error);
+            let mut result = ipc::ReplayResponse::new();
+            result.set_failure(error);
+            log_duration(
+                correlation_id,
+                METRIC_DURATION_REPLAY,
+                "replay_request_parsing_error",
+                start.elapsed(),
+            );
+            grpc::SingleResponse::completed(result)
+        };
+
+        let mut exec_request: ipc::ExecuteRequest =
+            match protobuf::parse_from_bytes(replay_request.get_execute_request()) {
+                Ok(exec_request) => exec_request,
+                Err(error) => {
+                    return failure(
+                        format!("Invalid execute request: {}", error),
+                        CorrelationId::new(),
+                    )
+                }
+            };
+        // Shared with `execute_request`, so the replay is logged under a single correlation ID
+        let correlation_id = exec_correlation_id(exec_request.get_correlation_id());
+        exec_request.set_correlation_id(correlation_id.to_string());
+        let _log_context = log_context::enter_correlation_id(correlation_id);
+
+        let prestate_hash_bytes = replay_request.get_prestate_hash();
+        if let Err(error) = Blake2bHash::try_from(prestate_hash_bytes) {
+            return failure(
+                format!("Invalid prestate hash: {:?}", error),
+                correlation_id,
+            );
+        }
+        exec_request.set_parent_state_hash(prestate_hash_bytes.to_vec());
+
+        let recorded = match replay_request
+            .get_recorded_effects()
+            .iter()
+            .map(|effect| CommitTransforms::try_from(effect.get_transform_map()))
+            .map(|transforms| transforms.map(CommitTransforms::value))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(recorded) => recorded,
+            Err(error) => {
+                return failure(
+                    format!("Invalid recorded effects: {:?}", error),
+                    correlation_id,
+                )
+            }
+        };
+
+        let deploy_hashes: Vec<Vec<u8>> = exec_request
+            .get_deploys()
+            .iter()
+            .map(|deploy| deploy.get_deploy_hash().to_vec())
+            .collect();
+
+        // The effects of the deploys are only compared, never committed
+        let mut exec_response = execute_request(self, exec_request, |_, _| ());
+
+        let response = if exec_response.has_missing_parent() {
+            let mut result = ipc::ReplayResponse::new();
+            result.set_missing_parent(exec_response.take_missing_parent());
+            result
+        } else {
+            // The replayed effects are parsed back from the deploy results, so they are compared
+            // with the recorded ones exactly as peers exchange them
+            let replayed = exec_response
+                .get_success()
+                .get_deploy_results()
+                .iter()
+                .map(|deploy_result| {
+                    let effects = deploy_result.get_execution_result().get_effects();
+                    CommitTransforms::try_from(effects.get_transform_map())
+                        .map(CommitTransforms::value)
+                })
+                .collect::<Result<Vec<_>, _>>();
+            match replayed {
+                Ok(replayed) => {
+                    let report = ReplayReport::new(&recorded, &replayed);
+                    if report.is_match() {
+                        log_info(&format!(
+                            "Replay reproduced the effects of {} deploys",
+                            report.replayed_count
+                        ));
+                    } else {
+                        logging::log_warning(&format!(
+                            "Replay diverged from the recorded effects of {} deploys",
+                            report.divergences.len()
+                        ));
+                    }
+                    let mut replay_result = ipc::ReplayResult::new();
+                    replay_result.set_recorded_count(report.recorded_count as u32);
+                    replay_result.set_replayed_count(report.replayed_count as u32);
+                    replay_result.set_divergences(
+                        report
+                            .divergences
+                            .into_iter()
+                            .map(|divergence| {
+                                let mut deploy_divergence = ipc::DeployDivergence::new();
+                                deploy_divergence.set_deploy_index(divergence.deploy_index as u32);
+                                if let Some(deploy_hash) =
+                                    deploy_hashes.get(divergence.deploy_index)
+                                {
+                                    deploy_divergence.set_deploy_hash(deploy_hash.clone());
+                                }
+                                deploy_divergence.set_transforms(
+                                    divergence.transforms.into_iter().map(Into::into).collect(),
+                                );
+                                deploy_divergence
+                            })
+                            .collect(),
+                    );
+                    let mut result = ipc::ReplayResponse::new();
+                    result.set_success(replay_result);
+                    result
+                }
+                Err(error) => {
+                    let error = format!("{:?}", error);
+                    logging::log_error(&error);
+                    let mut result = ipc::ReplayResponse::new();
+                    result.set_failure(error);
+                    result
+                }
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_REPLAY,
+            TAG_RESPONSE_REPLAY,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(response)
+    }
+}
+
+fn grpc_response_from_query_result<E: Debug>(
+    query_result: Result<QueryResult, E>,
+) -> ipc::QueryResponse {
+    match query_result {
+        Err(err) => {
+            let mut result = ipc::QueryResponse::new();
+            let error = format!("{:?}", err);
+            logging::log_error(&error);
+            result.set_failure(error);
+            result
+        }
+        Ok(QueryResult::ValueNotFound(full_path)) => {
+            let mut result = ipc::QueryResponse::new();
+            let error = format!("Value not found: {:?}", full_path);
+            logging::log_warning(&error);
+            result.set_failure(error);
+            result
+        }
+        Ok(QueryResult::Success {
+            value,
+            access_rights,
+        }) => {
+            let mut result = ipc::QueryResponse::new();
+            result.set_success(value.into());
+            if let Some(access_rights) = access_rights {
+                result.set_access_rights(
+                    state::Key_URef_AccessRights::from_i32(access_rights.bits().into())
+                        .unwrap_or_default(),
+                );
+            }
+            result
+        }
+    }
+}
+
+/// Parses the hashes of the deploys a deploy depends on, leaving out the ones which were already
+/// executed earlier in the same request.
+fn parse_dependencies(
+    dependencies: &[Vec<u8>],
+    preceding_writes: &mut PrecedingWrites,
+) -> Result<Vec<[u8; 32]>, EngineError> {
+    let mut ret = Vec::new();
+    for dependency in dependencies {
+        let dependency_len = dependency.len();
+        if dependency_len != EXPECTED_DEPLOY_HASH_LENGTH {
+            return Err(EngineError::InvalidHashLength {
+                expected: EXPECTED_DEPLOY_HASH_LENGTH,
+                actual: dependency_len,
+            });
+        }
+        let mut dependency_hash = [0u8; EXPECTED_DEPLOY_HASH_LENGTH];
+        dependency_hash.copy_from_slice(dependency);
+        if !preceding_writes.contains(&deploy_execution_key(dependency_hash)) {
+            ret.push(dependency_hash);
+        }
+    }
+    Ok(ret)
+}
+
+/// Parses the approvals of a deploy.
+fn parse_approvals(approvals: &[ipc::Approval]) -> Result<Vec<Approval>, EngineError> {
+    approvals
+        .iter()
+        .map(|approval| {
+            approval.try_into().map_err(|error| match error {
+                MappingError::InvalidPublicKeyLength { expected, actual } => {
+                    EngineError::InvalidPublicKeyLength { expected, actual }
+                }
+                MappingError::ParsingError(_) => EngineError::DeployError,
+            })
+        })
+        .collect()
+}
+
+/// Parses the native transfer a deploy carries instead of payment and session code.
+fn parse_native_transfer(
+    native_transfer: &ipc::NativeTransfer,
+) -> Result<NativeTransfer, EngineError> {
+    native_transfer.try_into().map_err(|error| match error {
+        MappingError::InvalidPublicKeyLength { expected, actual } => {
+            EngineError::InvalidPublicKeyLength { expected, actual }
+        }
+        MappingError::ParsingError(_) => EngineError::DeployError,
+    })
+}
+
+/// The fields the deploys of exec requests and of execute requests have in common.
+trait IpcDeploy {
+    fn address(&self) -> &[u8];
+    fn authorization_keys(&self) -> &[Vec<u8>];
+    fn deploy_hash(&self) -> &[u8];
+    fn header(&self) -> &[u8];
+    fn body(&self) -> &[u8];
+    fn approvals(&self) -> &[ipc::Approval];
+    fn dependencies(&self) -> &[Vec<u8>];
+    fn timestamp(&self) -> u64;
+    fn ttl_millis(&self) -> u32;
+    fn gas_price(&self) -> u64;
+    fn native_transfer(&self) -> Option<&ipc::NativeTransfer>;
+}
+
+macro_rules! ipc_deploy_impl {
+    ($deploy_type:ty) => {
+        impl IpcDeploy for $deploy_type {
+            fn address(&self) -> &[u8] {
+                self.get_address()
+            }
+
+            fn authorization_keys(&self) -> &[Vec<u8>] {
+                self.get_authorization_keys()
+            }
+
+            fn deploy_hash(&self) -> &[u8] {
+                self.get_deploy_hash()
+            }
+
+            fn header(&self) -> &[u8] {
+                self.get_header()
+            }
+
+            fn body(&self) -> &[u8] {
+                self.get_body()
+            }
+
+            fn approvals(&self) -> &[ipc::Approval] {
+                self.get_approvals()
+            }
+
+            fn dependencies(&self) -> &[Vec<u8>] {
+                self.get_dependencies()
+            }
+
+            fn timestamp(&self) -> u64 {
+                self.get_timestamp()
+            }
+
+            fn ttl_millis(&self) -> u32 {
+                self.get_ttl_millis()
+            }
+
+            fn gas_price(&self) -> u64 {
+                self.get_gas_price()
+            }
+
+            fn native_transfer(&self) -> Option<&ipc::NativeTransfer> {
+                if self.has_native_transfer() {
+                    Some(self.get_native_transfer())
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+ipc_deploy_impl!(ipc::Deploy);
+ipc_deploy_impl!(ipc::DeployItem);
+
+/// The fields of a deploy of an exec or an execute request, parsed into domain types.
+struct ParsedDeploy {
+    address: Key,
+    authorization_keys: BTreeSet<PublicKey>,
+    deploy_hash: [u8; 32],
+    approvals: Vec<Approval>,
+    dependencies: Vec<[u8; 32]>,
+    timestamp: u64,
+    ttl_millis: u32,
+    gas_price: u64,
+    native_transfer: Option<NativeTransfer>,
+}
+
+/// Parses the fields of a deploy of an exec or an execute request, checking the preconditions
+/// which don't depend on the prestate: its hash is verified if `verify_deploy_hash` is set, and
+/// the deploy must not have been executed earlier in the same request.
+fn parse_deploy<D: IpcDeploy>(
+    deploy: &D,
+    blocktime: BlockTime,
+    verify_deploy_hash: bool,
+    preceding_writes: &mut PrecedingWrites,
+) -> Result<ParsedDeploy, EngineError> {
+    let address = {
+        let address_len = deploy.address().len();
+        if address_len != EXPECTED_PUBLIC_KEY_LENGTH {
+            return Err(EngineError::InvalidPublicKeyLength {
+                expected: EXPECTED_PUBLIC_KEY_LENGTH,
+                actual: address_len,
+            });
+        }
+        let mut dest = [0; EXPECTED_PUBLIC_KEY_LENGTH];
+        dest.copy_from_slice(deploy.address());
+        Key::Account(dest)
+    };
+
+    // Parse all authorization keys from IPC into a set
+    let authorization_keys = deploy
+        .authorization_keys()
+        .iter()
+        .map(|key_bytes| {
+            // Try to convert an element of bytes into a possibly
+            // valid PublicKey with error handling
+            PublicKey::try_from(key_bytes.as_slice()).map_err(|_| {
+                EngineError::InvalidPublicKeyLength {
+                    expected: EXPECTED_PUBLIC_KEY_LENGTH,
+                    actual: key_bytes.len(),
+                }
+            })
+        })
+        .collect::<Result<BTreeSet<_>, _>>()?;
+
+    let deploy_hash = {
+        let deploy_hash_len = deploy.deploy_hash().len();
+        if deploy_hash_len != EXPECTED_DEPLOY_HASH_LENGTH {
+            return Err(EngineError::InvalidHashLength {
+                expected: EXPECTED_DEPLOY_HASH_LENGTH,
+                actual: deploy_hash_len,
+            });
+        }
+        let mut buff = [0u8; EXPECTED_DEPLOY_HASH_LENGTH];
+        buff.copy_from_slice(deploy.deploy_hash());
+        buff
+    };
+
+    if verify_deploy_hash && deploy_hash != hash_deploy(deploy.header(), deploy.body()) {
+        return Err(EngineError::InvalidDeployHash(deploy_hash));
+    }
+
+    let approvals = parse_approvals(deploy.approvals())?;
+
+    if preceding_writes.contains(&executed_deploy_key(blocktime, deploy_hash)) {
+        return Err(EngineError::DuplicateDeploy(deploy_hash));
+    }
+
+    let dependencies = parse_dependencies(deploy.dependencies(), preceding_writes)?;
+
+    let native_transfer = match deploy.native_transfer() {
+        Some(native_transfer) => Some(parse_native_transfer(native_transfer)?),
+        None => None,
+    };
+
+    Ok(ParsedDeploy {
+        address,
+        authorization_keys,
+        deploy_hash,
+        approvals,
+        dependencies,
+        timestamp: deploy.timestamp(),
+        ttl_millis: deploy.ttl_millis(),
+        gas_price: deploy.gas_price(),
+        native_transfer,
+    })
+}
+
+/// Executes a deploy of an exec or an execute request, returning its outcome along with the keys
+/// it wrote.  Once parsed, the deploy is run as a native transfer if it is one, and by `run_code`
+/// otherwise, which fails with a precondition failure if the code of the deploy is invalid.
+#[allow(clippy::too_many_arguments)]
+fn execute_deploy<S, D, F>(
+    engine_state: &EngineState<S>,
+    deploy: &D,
+    prestate_hash: Blake2bHash,
+    blocktime: BlockTime,
+    proposer: Option<PublicKey>,
+    protocol_version: u64,
+    correlation_id: CorrelationId,
+    trace_host_calls: bool,
+    preceding_writes: &mut PrecedingWrites,
+    run_code: F,
+) -> Result<(DeployOutcome, HashSet<Key>), ipc::RootNotFound>
+where
+    S: StateProvider,
+    S::Error: Into<engine_core::execution::Error>,
+    D: IpcDeploy,
+    F: FnOnce(ParsedDeploy) -> Result<Result<ExecutionResult, RootNotFound>, EngineError>,
+{
+    let verify_deploy_hash = engine_state.config().verify_deploy_hashes();
+    let mut parsed_deploy =
+        match parse_deploy(deploy, blocktime, verify_deploy_hash, preceding_writes) {
+            Ok(parsed_deploy) => parsed_deploy,
+            Err(error) => return precondition_failure(error),
+        };
+    let gas_price = parsed_deploy.gas_price;
+    let recording = if trace_host_calls {
+        Some(trace::start())
+    } else {
+        None
+    };
+    let execution_result = match parsed_deploy.native_transfer.take() {
+        Some(transfer) => engine_state.run_native_transfer(
+            transfer,
+            parsed_deploy.address,
+            parsed_deploy.authorization_keys,
+            parsed_deploy.approvals,
+            blocktime,
+            proposer,
+            parsed_deploy.deploy_hash,
+            parsed_deploy.dependencies,
+            parsed_deploy.timestamp,
+            parsed_deploy.ttl_millis,
+            parsed_deploy.gas_price,
+            prestate_hash,
+            protocol_version,
+            correlation_id,
+        ),
+        None => match run_code(parsed_deploy) {
+            Ok(execution_result) => execution_result,
+            Err(error) => return precondition_failure(error),
+        },
+    }
+    .map_err(Into::<ipc::RootNotFound>::into)?;
+    let effect = execution_result.effect().to_owned();
+    let writes = effect.transforms.keys().cloned().collect();
+    let mut deploy_result = with_gas_price(execution_result.into(), gas_price);
+    if let Some(recording) = recording {
+        deploy_result.set_host_calls(recording.finish().into_iter().map(Into::into).collect());
+    }
+    Ok(((deploy_result, Some(effect)), writes))
+}
+
+/// The result of a deploy, along with its effects if it was executed.
+type DeployOutcome = (ipc::DeployResult, Option<ExecutionEffect>);
+
+/// Executes exec requests, passing each response of an `execute_stream` call to `on_response` as
+/// soon as it is available.
+///
+/// gRPC handlers only get to send their responses once they return, so this is used by
+/// [`LimitedService`] to execute streamed requests on another thread.  The `execute_stream`
+/// handler of [`EngineState`] itself fails with `Unimplemented`.
+pub trait ExecuteStreaming {
+    fn execute_streaming(
+        &self,
+        exec_request: ipc::ExecuteRequest,
+        on_response: &mut dyn FnMut(ipc::ExecuteStreamResponse),
+    );
+}
+
+impl<S> ExecuteStreaming for EngineState<S>
+where
+    S: StateProvider + Sync,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error> + Debug,
+{
+    fn execute_streaming(
+        &self,
+        exec_request: ipc::ExecuteRequest,
+        on_response: &mut dyn FnMut(ipc::ExecuteStreamResponse),
+    ) {
+        let exec_response = execute_request(self, exec_request, |_, (deploy_result, _)| {
+            let mut response = ipc::ExecuteStreamResponse::new();
+            response.set_deploy_result(deploy_result.clone());
+            on_response(response);
+        });
+
+        let mut response = ipc::ExecuteStreamResponse::new();
+        response.set_done(exec_response);
+        on_response(response);
+    }
+}
+
+/// Returns the correlation ID the caller of an exec request passed, or a new one if it passed none
+/// or an invalid one.
+fn exec_correlation_id(correlation_id: &str) -> CorrelationId {
+    if correlation_id.is_empty() {
+        return CorrelationId::new();
+    }
+    correlation_id.parse().unwrap_or_else(|_| {
+        let new_correlation_id = CorrelationId::new();
+        logging::log_warning(&format!(
+            "invalid correlation id {:?}, using {} instead",
+            correlation_id, new_correlation_id
+        ));
+        new_correlation_id
+    })
+}
+
+/// Executes the deploys of `exec_request`, passing the outcome of each deploy to
+/// `on_deploy_outcome` as soon as it is final.
+fn execute_request<S, C>(
+    engine_state: &EngineState<S>,
+    exec_request: ipc::ExecuteRequest,
+    mut on_deploy_outcome: C,
+) -> ipc::ExecuteResponse
+where
+    S: StateProvider + Sync,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error> + Debug,
+    C: FnMut(usize, &DeployOutcome),
+{
+    let start = Instant::now();
+    let correlation_id = exec_correlation_id(exec_request.get_correlation_id());
+    let _log_context = log_context::enter_correlation_id(correlation_id);
+
+    let protocol_version = exec_request.get_protocol_version();
+
+    // TODO: don't unwrap
+    let prestate_hash: Blake2bHash = exec_request.get_parent_state_hash().try_into().unwrap();
+
+    let blocktime = BlockTime(exec_request.get_block_time());
+    let block_height = exec_request.get_block_height();
+    // Without a valid proposer, fees meant for the proposer stay in the rewards purse.
+    let proposer = PublicKey::try_from(exec_request.get_proposer()).ok();
+
+    let deploys = exec_request.get_deploys();
+
+    let deploys_result: Result<Vec<DeployOutcome>, ipc::RootNotFound> =
+        match protocol_executor(engine_state, protocol_version) {
+            Ok((executor, preprocessor)) => execute_deploys(
+                engine_state,
+                &executor,
+                &preprocessor,
+                prestate_hash,
+                blocktime,
+                block_height,
+                proposer,
+                deploys,
+                protocol_version,
+                correlation_id,
+                exec_request.get_trace(),
+                on_deploy_outcome,
+            ),
+            Err(error) => {
+                let deploy_outcomes = precondition_failures(deploys.len(), error);
+                for (index, deploy_outcome) in deploy_outcomes.iter().enumerate() {
+                    on_deploy_outcome(index, deploy_outcome);
+                }
+                Ok(deploy_outcomes)
+            }
+        };
+
+    let exec_response = match deploys_result {
+        Ok(deploy_results) => {
+            let mut exec_response = ipc::ExecuteResponse::new();
+            exec_response.set_success(exec_result(deploy_results));
+            exec_response
+        }
+        Err(error) => {
+            logging::log_error("deploy results error: RootNotFound");
+            let mut exec_response = ipc::ExecuteResponse::new();
+            exec_response.set_missing_parent(error);
+            exec_response
+        }
+    };
+
+    log_duration(
+        correlation_id,
+        METRIC_DURATION_EXEC,
+        TAG_RESPONSE_EXEC,
+        start.elapsed(),
+    );
+
+    exec_response
+}
+
+/// Builds the response describing `protocol_data`, the protocol data of `protocol_version`.
+fn protocol_data_result(
+    protocol_version: u64,
+    protocol_data: &ProtocolData,
+) -> ipc::ProtocolDataResult {
+    let mut protocol_data_result = ipc::ProtocolDataResult::new();
+    protocol_data_result.set_protocol_version({
+        let mut tmp = state::ProtocolVersion::new();
+        tmp.set_value(protocol_version);
+        tmp
+    });
+    protocol_data_result.set_costs({
+        let mut tmp = ipc::ChainSpec_CostTable::new();
+        tmp.set_wasm((*protocol_data.wasm_costs()).into());
+        tmp
+    });
+    protocol_data_result.set_refund_ratio(protocol_data.refund_ratio().into());
+    protocol_data_result.set_min_gas_price(protocol_data.min_gas_price());
+    protocol_data_result.set_native_transfer_cost(protocol_data.native_transfer_cost());
+    let key_limits = protocol_data.key_limits();
+    protocol_data_result.set_max_associated_keys(key_limits.max_associated_keys());
+    protocol_data_result.set_max_named_keys(key_limits.max_named_keys());
+    protocol_data_result
+}
+
+/// Returns the executor and preprocessor deploys targeting `protocol_version` are executed with,
+/// set up with the wasm costs and key limits of the protocol version.
+fn protocol_executor<S>(
+    engine_state: &EngineState<S>,
+    protocol_version: &state::ProtocolVersion,
+) -> Result<(WasmiExecutor, WasmiPreprocessor), EngineError>
+where
+    S: StateProvider,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error>,
+{
+    let wasm_costs = engine_state.get_wasm_costs(protocol_version.value)?;
+    let key_limits = engine_state.get_key_limits(protocol_version.value)?;
+
+    let preprocessor = WasmiPreprocessor::new(wasm_costs)
+        .set_float_handling(engine_state.config().float_handling())
+        .set_module_limits(engine_state.config().module_limits());
+
+    let executor =
+        WasmiExecutor::new(engine_state.config().use_system_contracts()).set_key_limits(key_limits);
+
+    Ok((executor, preprocessor))
+}
+
+/// Returns the outcomes of `deploy_count` deploys all failing a precondition with `error`, such
+/// as targeting a protocol version the engine has no protocol data for.
+fn precondition_failures(deploy_count: usize, error: EngineError) -> Vec<DeployOutcome> {
+    let deploy_result: ipc::DeployResult = ExecutionResult::precondition_failure(error).into();
+    (0..deploy_count)
+        .map(|_| (deploy_result.clone(), None))
         .collect()
 }
 
+/// Returns the result of a deploy failing a precondition, which writes nothing.
+fn precondition_failure(
+    error: EngineError,
+) -> Result<(DeployOutcome, HashSet<Key>), ipc::RootNotFound> {
+    let deploy_result = ExecutionResult::precondition_failure(error).into();
+    Ok(((deploy_result, None), HashSet::new()))
+}
+
+/// Builds the result of an exec request from the outcomes of its deploys, merging in order the
+/// effects of the deploys which commute with the ones merged before them.
+fn exec_result(deploy_outcomes: Vec<DeployOutcome>) -> ipc::ExecResult {
+    let mut deploy_results = Vec::with_capacity(deploy_outcomes.len());
+    let mut merged_effect = ExecutionEffect::default();
+    let mut merged_deploys = Vec::new();
+    for (index, (deploy_result, effect)) in deploy_outcomes.into_iter().enumerate() {
+        if let Some(effect) = effect {
+            if merged_effect.commutes_with(&effect) {
+                merged_effect = merged_effect.merge(effect);
+                merged_deploys.push(index as u32);
+            }
+        }
+        deploy_results.push(deploy_result);
+    }
+
+    let mut exec_result = ipc::ExecResult::new();
+    exec_result.set_deploy_results(protobuf::RepeatedField::from_vec(deploy_results));
+    exec_result.set_merged_effect(merged_effect.into());
+    exec_result.set_merged_deploys(merged_deploys);
+    exec_result
+}
+
+/// Records the gas price a deploy was charged at in its execution result, if it has one.
+fn with_gas_price(mut deploy_result: ipc::DeployResult, gas_price: u64) -> ipc::DeployResult {
+    if deploy_result.has_execution_result() {
+        deploy_result
+            .mut_execution_result()
+            .set_gas_price(gas_price);
+    }
+    deploy_result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_deploys<A, S, E, P>(
+    engine_state: &EngineState<S>,
+    executor: &E,
+    preprocessor: &P,
+    prestate_hash: Blake2bHash,
+    blocktime: BlockTime,
+    block_height: u64,
+    proposer: Option<PublicKey>,
+    deploys: &[ipc::Deploy],
+    protocol_version: &state::ProtocolVersion,
+    correlation_id: CorrelationId,
+    trace_host_calls: bool,
+) -> Result<Vec<DeployOutcome>, ipc::RootNotFound>
+where
+    S: StateProvider + Sync,
+    E: Executor<A> + Sync,
+    A: Clone + Send + 'static,
+    P: Preprocessor<A> + Sync,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error>,
+{
+    // We want to treat RootNotFound error differently b/c it should short-circuit
+    // the execution of ALL deploys within the block. This is because all of them
+    // share the same prestate and all of them would fail.
+    // The scheduler stops executing deploys when run_deploy returns Err.
+    engine_state
+        .deploy_scheduler()
+        .execute(deploys, |deploy, preceding_writes| {
+            execute_deploy(
+                engine_state,
+                deploy,
+                prestate_hash,
+                blocktime,
+                proposer,
+                protocol_version.value,
+                correlation_id,
+                trace_host_calls,
+                preceding_writes,
+                |parsed_deploy| {
+                    let session = deploy.get_session();
+                    let payment = deploy.get_payment();
+                    Ok(engine_state.run_deploy(
+                        &session.code,
+                        &session.args,
+                        &payment.code,
+                        &payment.args,
+                        parsed_deploy.address,
+                        parsed_deploy.authorization_keys,
+                        parsed_deploy.approvals,
+                        blocktime,
+                        block_height,
+                        proposer,
+                        parsed_deploy.deploy_hash,
+                        parsed_deploy.dependencies,
+                        parsed_deploy.timestamp,
+                        parsed_deploy.ttl_millis,
+                        parsed_deploy.gas_price,
+                        prestate_hash,
+                        protocol_version.value,
+                        correlation_id,
+                        executor,
+                        preprocessor,
+                    ))
+                },
+            )
+        })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_deploys<A, S, E, P, C>(
+    engine_state: &EngineState<S>,
+    executor: &E,
+    preprocessor: &P,
+    prestate_hash: Blake2bHash,
+    blocktime: BlockTime,
+    block_height: u64,
+    proposer: Option<PublicKey>,
+    deploys: &[ipc::DeployItem],
+    protocol_version: &state::ProtocolVersion,
+    correlation_id: CorrelationId,
+    trace_host_calls: bool,
+    on_deploy_outcome: C,
+) -> Result<Vec<DeployOutcome>, ipc::RootNotFound>
+where
+    S: StateProvider + Sync,
+    E: Executor<A> + Sync,
+    A: Clone + Send + 'static,
+    P: Preprocessor<A> + Sync,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error>,
+    C: FnMut(usize, &DeployOutcome),
+{
+    // We want to treat RootNotFound error differently b/c it should short-circuit
+    // the execution of ALL deploys within the block. This is because all of them
+    // share the same prestate and all of them would fail.
+    // The scheduler stops executing deploys when run_deploy returns Err.
+    engine_state.deploy_scheduler().execute_and_report(
+        deploys,
+        |deploy, preceding_writes| {
+            execute_deploy(
+                engine_state,
+                deploy,
+                prestate_hash,
+                blocktime,
+                proposer,
+                protocol_version.value,
+                correlation_id,
+                trace_host_calls,
+                preceding_writes,
+                |parsed_deploy| {
+                    let session_payload = match deploy.get_session().to_owned().payload {
+                        Some(payload) => payload.into(),
+                        None => return Err(EngineError::DeployError),
+                    };
+                    let payment_payload = match deploy.get_payment().to_owned().payload {
+                        Some(payload) => payload.into(),
+                        None => return Err(EngineError::DeployError),
+                    };
+                    Ok(engine_state.run_deploy_item(
+                        session_payload,
+                        payment_payload,
+                        parsed_deploy.address,
+                        parsed_deploy.authorization_keys,
+                        parsed_deploy.approvals,
+                        blocktime,
+                        block_height,
+                        proposer,
+                        parsed_deploy.deploy_hash,
+                        parsed_deploy.dependencies,
+                        parsed_deploy.timestamp,
+                        parsed_deploy.ttl_millis,
+                        parsed_deploy.gas_price,
+                        prestate_hash,
+                        protocol_version.value,
+                        correlation_id,
+                        executor,
+                        preprocessor,
+                    ))
+                },
+            )
+        },
+        on_deploy_outcome,
+    )
+}
+
 // TODO: Refactor.
 #[allow(clippy::implicit_hasher)]
 pub fn bonded_validators_and_commit_result<S>(
     prestate_hash: Blake2bHash,
     poststate_hash: Blake2bHash,
     commit_result: Result<CommitResult, S::Error>,
+    previous_bonded_validators: &HashMap<PublicKey, U512>,
     bonded_validators: Result<HashMap<PublicKey, U512>, GetBondedValidatorsError<S::Error>>,
 ) -> CommitResponse
 where
@@ -825,19 +2705,21 @@ where
         Ok(bonded_validators) => {
             let mut grpc_response =
                 grpc_response_from_commit_result::<S>(prestate_hash, commit_result);
+            let grpc_bonded_validator_changes =
+                bonded_validator_changes(previous_bonded_validators, &bonded_validators)
+                    .iter()
+                    .map(|(pk, bond)| to_grpc_bond(pk, *bond))
+                    .collect::<Vec<ipc::Bond>>()
+                    .into();
             let grpc_bonded_validators = bonded_validators
                 .iter()
-                .map(|(pk, bond)| {
-                    let mut ipc_bond = ipc::Bond::new();
-                    ipc_bond.set_stake((*bond).into());
-                    ipc_bond.set_validator_public_key(pk.value().to_vec());
-                    ipc_bond
-                })
+                .map(|(pk, bond)| to_grpc_bond(pk, *bond))
                 .collect::<Vec<ipc::Bond>>()
                 .into();
-            grpc_response
-                .mut_success() // We know it's a success because of the check few lines earlier.
-                .set_bonded_validators(grpc_bonded_validators);
+            // We know it's a success because of the check few lines earlier.
+            let commit_result = grpc_response.mut_success();
+            commit_result.set_bonded_validators(grpc_bonded_validators);
+            commit_result.set_bonded_validator_changes(grpc_bonded_validator_changes);
             grpc_response
         }
         Err(GetBondedValidatorsError::StateError(error)) => {
@@ -869,23 +2751,109 @@ where
     }
 }
 
-// Helper method which returns single DeployResult that is set to be a
-// WasmError.
-pub fn new<E: ExecutionEngineService + Sync + Send + 'static>(
-    socket: &str,
+/// Builds and starts gRPC servers serving `e` on the Unix socket of `server_config`, and also on
+/// its TCP address if set.  The servers share the limits of `server_config`, and each serves the
+/// `grpc.health.v1.Health` service reporting the status set on `health`.
+///
+/// The TCP address is served through a [`ReadOnlyService`], as unlike the Unix socket, access to
+/// it can't be restricted with file permissions.  As the node can't execute deploys through it,
+/// a config with a TCP address but no Unix socket is rejected.
+pub fn new<E: ExecutionEngineService + ExecuteStreaming + Sync + Send + 'static>(
+    server_config: &ServerConfig,
+    health: &HealthService,
     e: E,
-) -> grpc::ServerBuilder {
-    let socket_path = std::path::Path::new(socket);
+) -> Result<Vec<grpc::Server>, grpc::Error> {
+    let socket = server_config.unix_socket().ok_or(grpc::Error::Other(
+        "a Unix socket is required to execute deploys, the TCP address only serves reads",
+    ))?;
+    let service = LimitedService::new(e, server_config);
+    let mut servers = Vec::new();
+
+    Socket::new(socket.to_owned())
+        .remove_file()
+        .map_err(grpc::Error::Io)?;
+
+    let mut server = server_builder(server_config, health, service.clone());
+    server.http.set_unix_addr(socket.to_owned())?;
+    servers.push(server.build()?);
+    if let Some(mode) = server_config.socket_permissions() {
+        // The socket file is created when the server binds
+        fs::set_permissions(socket, fs::Permissions::from_mode(mode)).map_err(grpc::Error::Io)?;
+    }
 
-    if let Err(e) = std::fs::remove_file(socket_path) {
-        if e.kind() != ErrorKind::NotFound {
-            panic!("failed to remove old socket file: {:?}", e);
-        }
+    if let Some(tcp_address) = server_config.tcp_address() {
+        let mut server = server_builder(server_config, health, ReadOnlyService::new(service));
+        server.http.set_addr(tcp_address)?;
+        servers.push(server.build()?);
     }
 
+    Ok(servers)
+}
+
+fn server_builder<E: ExecutionEngineService + Sync + Send + 'static>(
+    server_config: &ServerConfig,
+    health: &HealthService,
+    service: E,
+) -> grpc::ServerBuilder {
     let mut server = grpc::ServerBuilder::new_plain();
-    server.http.set_unix_addr(socket.to_owned()).unwrap();
-    server.http.set_cpu_pool_threads(1);
-    server.add_service(ipc_grpc::ExecutionEngineServiceServer::new_service_def(e));
     server
+        .http
+        .set_cpu_pool_threads(server_config.worker_threads());
+    server.add_service(ipc_grpc::ExecutionEngineServiceServer::new_service_def(service));
+    server.add_service(health_grpc::HealthServer::new_service_def(health.clone()));
+    server
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    use engine_core::engine_state::{EngineConfig, EngineState};
+    use engine_storage::global_state::in_memory::InMemoryGlobalState;
+
+    use super::health_service::HealthService;
+    use super::server_config::ServerConfig;
+
+    fn engine_state() -> EngineState<InMemoryGlobalState> {
+        let global_state = InMemoryGlobalState::empty().expect("should create global state");
+        EngineState::new(global_state, EngineConfig::new())
+    }
+
+    #[test]
+    fn should_replace_old_socket_file_with_socket_of_requested_permissions() {
+        let dir = tempfile::tempdir().expect("should create temp dir");
+        let socket = dir.path().join("engine.sock");
+        fs::write(&socket, b"old socket").expect("should write old socket file");
+        let server_config = ServerConfig::new()
+            .set_unix_socket(socket.to_str().expect("should be utf-8").to_owned())
+            .set_socket_permissions(0o600);
+
+        let servers = super::new(&server_config, &HealthService::new(), engine_state())
+            .expect("should start server");
+        assert_eq!(servers.len(), 1);
+
+        let metadata = fs::metadata(&socket).expect("should create socket file");
+        assert!(metadata.file_type().is_socket());
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    }
+
+    #[test]
+    fn should_return_error_if_only_tcp_address_is_set() {
+        let server_config = ServerConfig::new()
+            .set_tcp_address("127.0.0.1:0".parse().expect("should parse socket address"));
+
+        let result = super::new(&server_config, &HealthService::new(), engine_state());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_return_error_if_old_socket_file_cannot_be_removed() {
+        let dir = tempfile::tempdir().expect("should create temp dir");
+        let server_config = ServerConfig::new()
+            .set_unix_socket(dir.path().to_str().expect("should be utf-8").to_owned());
+
+        let result = super::new(&server_config, &HealthService::new(), engine_state());
+        assert!(result.is_err());
+    }
 }
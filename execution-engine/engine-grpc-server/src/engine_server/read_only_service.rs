@@ -0,0 +1,142 @@
+//! A service serving only the requests which read the state of the engine in front of another one.
+//!
+//! Addresses reachable by other processes than the node, such as the TCP address, are served
+//! through it, so that only the node can execute deploys and change the global state.
+
+use super::ipc;
+use super::ipc_grpc::ExecutionEngineService;
+
+fn permission_denied(method: &str) -> grpc::Error {
+    grpc::Error::GrpcMessage(grpc::GrpcMessageError {
+        grpc_status: grpc::GrpcStatus::PermissionDenied as i32,
+        grpc_message: format!("{} is not served on this address", method),
+    })
+}
+
+/// Wraps an [`ExecutionEngineService`], forwarding the requests which read the global state and
+/// rejecting the ones which execute deploys or change the global state.
+#[derive(Clone)]
+pub struct ReadOnlyService<E> {
+    service: E,
+}
+
+impl<E> ReadOnlyService<E> {
+    pub fn new(service: E) -> Self {
+        ReadOnlyService { service }
+    }
+}
+
+/// Implements methods forwarding requests to the wrapped service.
+macro_rules! forward {
+    ($($method:ident($request:ty) -> $response:ty;)*) => {
+        $(
+            fn $method(
+                &self,
+                request_options: grpc::RequestOptions,
+                request: $request,
+            ) -> grpc::SingleResponse<$response> {
+                self.service.$method(request_options, request)
+            }
+        )*
+    };
+}
+
+/// Implements methods rejecting requests with a `PERMISSION_DENIED` status.
+macro_rules! deny {
+    ($($method:ident($request:ty) -> $response:ty;)*) => {
+        $(
+            fn $method(
+                &self,
+                _request_options: grpc::RequestOptions,
+                _request: $request,
+            ) -> grpc::SingleResponse<$response> {
+                grpc::SingleResponse::err(permission_denied(stringify!($method)))
+            }
+        )*
+    };
+}
+
+impl<E: ExecutionEngineService> ExecutionEngineService for ReadOnlyService<E> {
+    fn execute_stream(
+        &self,
+        _request_options: grpc::RequestOptions,
+        _request: ipc::ExecuteRequest,
+    ) -> grpc::StreamingResponse<ipc::ExecuteStreamResponse> {
+        grpc::StreamingResponse::err(permission_denied("execute_stream"))
+    }
+
+    forward! {
+        query(ipc::QueryRequest) -> ipc::QueryResponse;
+        batch_query(ipc::BatchQueryRequest) -> ipc::BatchQueryResponse;
+        validate(ipc::ValidateRequest) -> ipc::ValidateResponse;
+        query_validators(ipc::QueryValidatorsRequest) -> ipc::QueryValidatorsResponse;
+        get_balance(ipc::GetBalanceRequest) -> ipc::GetBalanceResponse;
+        get_account(ipc::GetAccountRequest) -> ipc::GetAccountResponse;
+        get_contract(ipc::GetContractRequest) -> ipc::GetContractResponse;
+        get_protocol_data(ipc::GetProtocolDataRequest) -> ipc::GetProtocolDataResponse;
+        speculative_exec(ipc::SpeculativeExecRequest) -> ipc::SpeculativeExecResponse;
+        state_diff(ipc::StateDiffRequest) -> ipc::StateDiffResponse;
+        get_trie_chunk(ipc::GetTrieChunkRequest) -> ipc::GetTrieChunkResponse;
+        missing_trie_keys(ipc::MissingTrieKeysRequest) -> ipc::MissingTrieKeysResponse;
+        scrape_metrics(ipc::ScrapeMetricsRequest) -> ipc::ScrapeMetricsResponse;
+        verify_integrity(ipc::VerifyIntegrityRequest) -> ipc::VerifyIntegrityResponse;
+    }
+
+    deny! {
+        exec(ipc::ExecRequest) -> ipc::ExecResponse;
+        execute(ipc::ExecuteRequest) -> ipc::ExecuteResponse;
+        step(ipc::StepRequest) -> ipc::StepResponse;
+        replay(ipc::ReplayRequest) -> ipc::ReplayResponse;
+        commit(ipc::CommitRequest) -> ipc::CommitResponse;
+        run_genesis(ipc::GenesisRequest) -> ipc::GenesisResponse;
+        run_genesis_with_chainspec(ipc::ChainSpec_GenesisConfig) -> ipc::GenesisResponse;
+        upgrade(ipc::UpgradeRequest) -> ipc::UpgradeResponse;
+        prune(ipc::PruneRequest) -> ipc::PruneResponse;
+        put_trie(ipc::PutTrieRequest) -> ipc::PutTrieResponse;
+        import_protocol_data(ipc::ImportProtocolDataRequest) -> ipc::ImportProtocolDataResponse;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_core::engine_state::{EngineConfig, EngineState};
+    use engine_storage::global_state::in_memory::InMemoryGlobalState;
+
+    use super::super::ipc;
+    use super::super::ipc_grpc::ExecutionEngineService;
+    use super::ReadOnlyService;
+
+    fn read_only_service() -> ReadOnlyService<EngineState<InMemoryGlobalState>> {
+        let global_state = InMemoryGlobalState::empty().expect("should create global state");
+        ReadOnlyService::new(EngineState::new(global_state, EngineConfig::new()))
+    }
+
+    #[test]
+    fn should_forward_queries() {
+        let mut request = ipc::QueryRequest::new();
+        request.set_state_hash(vec![0u8; 32]);
+
+        let response = read_only_service()
+            .query(grpc::RequestOptions::new(), request)
+            .wait_drop_metadata();
+        assert!(response.is_ok());
+    }
+
+    #[test]
+    fn should_deny_commits() {
+        let mut request = ipc::CommitRequest::new();
+        request.set_prestate_hash(vec![0u8; 32]);
+
+        let error = read_only_service()
+            .commit(grpc::RequestOptions::new(), request)
+            .wait_drop_metadata()
+            .expect_err("should deny commit");
+        match error {
+            grpc::Error::GrpcMessage(message) => assert_eq!(
+                message.grpc_status,
+                grpc::GrpcStatus::PermissionDenied as i32
+            ),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
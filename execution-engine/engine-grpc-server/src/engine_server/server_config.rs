@@ -0,0 +1,115 @@
+//! The configuration of the gRPC server in front of the execution engine.
+
+use std::net::SocketAddr;
+
+/// Default number of threads handling requests.
+pub const DEFAULT_WORKER_THREADS: usize = 1;
+
+/// Default number of exec requests executed at once.
+pub const DEFAULT_MAX_CONCURRENT_EXECS: usize = 1;
+
+/// Default max size of a request, in bytes.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 128 * 1024 * 1024;
+
+/// The settings the gRPC server is started with.
+///
+/// The server listens on the Unix socket, and also on the TCP address if set, serving the same
+/// engine on each of them.  Only the requests reading the global state are served on the TCP
+/// address, so the Unix socket is required.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    unix_socket: Option<String>,
+    socket_permissions: Option<u32>,
+    tcp_address: Option<SocketAddr>,
+    worker_threads: usize,
+    max_concurrent_execs: usize,
+    max_message_size: usize,
+}
+
+impl ServerConfig {
+    /// Creates a new server configuration with default parameters.
+    pub fn new() -> ServerConfig {
+        Default::default()
+    }
+
+    /// Sets the `unix_socket` field to the given arg, the path of a Unix socket to listen on.  A
+    /// file left at that path by a previous run is removed.
+    pub fn set_unix_socket(mut self, arg: String) -> ServerConfig {
+        self.unix_socket = Some(arg);
+        self
+    }
+
+    pub fn unix_socket(&self) -> Option<&str> {
+        self.unix_socket.as_ref().map(String::as_str)
+    }
+
+    /// Sets the `socket_permissions` field to the given arg, the mode the Unix socket file is set
+    /// to once created, e.g. `0o660`.  When unset, it keeps the default permissions.
+    pub fn set_socket_permissions(mut self, arg: u32) -> ServerConfig {
+        self.socket_permissions = Some(arg);
+        self
+    }
+
+    pub fn socket_permissions(&self) -> Option<u32> {
+        self.socket_permissions
+    }
+
+    /// Sets the `tcp_address` field to the given arg, a TCP address to listen on.  As anyone able
+    /// to connect to it can send requests, only the requests reading the global state are served
+    /// there.
+    pub fn set_tcp_address(mut self, arg: SocketAddr) -> ServerConfig {
+        self.tcp_address = Some(arg);
+        self
+    }
+
+    pub fn tcp_address(&self) -> Option<SocketAddr> {
+        self.tcp_address
+    }
+
+    /// Sets the `worker_threads` field to the given arg.  Each address listened on gets this many
+    /// threads.
+    pub fn set_worker_threads(mut self, arg: usize) -> ServerConfig {
+        self.worker_threads = arg;
+        self
+    }
+
+    pub fn worker_threads(&self) -> usize {
+        self.worker_threads
+    }
+
+    /// Sets the `max_concurrent_execs` field to the given arg.  Exec requests arriving while this
+    /// many are executing are rejected, which keeps the remaining worker threads free for queries.
+    /// The limit is shared by all the addresses listened on.
+    pub fn set_max_concurrent_execs(mut self, arg: usize) -> ServerConfig {
+        self.max_concurrent_execs = arg;
+        self
+    }
+
+    pub fn max_concurrent_execs(&self) -> usize {
+        self.max_concurrent_execs
+    }
+
+    /// Sets the `max_message_size` field to the given arg.  Larger requests are rejected before
+    /// they are handled.
+    pub fn set_max_message_size(mut self, arg: usize) -> ServerConfig {
+        self.max_message_size = arg;
+        self
+    }
+
+    pub fn max_message_size(&self) -> usize {
+        self.max_message_size
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            unix_socket: None,
+            socket_permissions: None,
+            tcp_address: None,
+            worker_threads: DEFAULT_WORKER_THREADS,
+            max_concurrent_execs: DEFAULT_MAX_CONCURRENT_EXECS,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+        }
+    }
+}
@@ -3,6 +3,7 @@ extern crate engine_core;
 extern crate engine_shared;
 extern crate engine_storage;
 extern crate engine_wasm_prep;
+extern crate futures;
 extern crate grpc;
 extern crate lmdb;
 extern crate proptest;
@@ -11,5 +12,7 @@ extern crate wabt;
 
 #[cfg(test)]
 extern crate parity_wasm;
+#[cfg(test)]
+extern crate tempfile;
 
 pub mod engine_server;
@@ -7,12 +7,17 @@ extern crate lazy_static;
 extern crate lmdb;
 
 extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
 extern crate engine_core;
 extern crate engine_shared;
 extern crate engine_storage;
+extern crate engine_wasm_prep;
 
 use std::collections::btree_map::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt::Debug;
 use std::fs;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -20,28 +25,48 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use clap::{App, Arg, ArgMatches};
+use contract_ffi::base16;
+use contract_ffi::system_contracts::pos::FeeHandling;
 use dirs::home_dir;
+use engine_core::engine_state::engine_config::{DEFAULT_DEPLOY_THREADS, DEFAULT_MODULE_CACHE_SIZE};
+use engine_core::engine_state::error::Error as EngineError;
 use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_core::execution;
+use engine_wasm_prep::FloatHandling;
 use lmdb::DatabaseFlags;
 
 use engine_shared::logging::log_settings::{LogLevelFilter, LogSettings};
 use engine_shared::logging::{log_level, log_settings};
+use engine_shared::newtypes::Blake2bHash;
 use engine_shared::os::get_page_size;
 use engine_shared::{logging, socket};
 use engine_storage::global_state::lmdb::LmdbGlobalState;
+use engine_storage::global_state::rocksdb::RocksDbGlobalState;
+use engine_storage::global_state::StateProvider;
+use engine_storage::storage_config::{StorageConfig, DEFAULT_MAX_READERS, DEFAULT_TRIE_CACHE_SIZE};
 use engine_storage::transaction_source::lmdb::LmdbEnvironment;
+use engine_storage::transaction_source::rocksdb::RocksDbEnvironment;
 use engine_storage::trie_store::lmdb::LmdbTrieStore;
+use engine_storage::trie_store::rocksdb::RocksDbTrieStore;
 
 use casperlabs_engine_grpc_server::engine_server;
+use casperlabs_engine_grpc_server::engine_server::health_service::HealthService;
+use casperlabs_engine_grpc_server::engine_server::server_config::{
+    ServerConfig, DEFAULT_MAX_CONCURRENT_EXECS, DEFAULT_MAX_MESSAGE_SIZE, DEFAULT_WORKER_THREADS,
+};
 use engine_storage::protocol_data_store::lmdb::LmdbProtocolDataStore;
+use engine_storage::protocol_data_store::rocksdb::RocksDbProtocolDataStore;
 
 // exe / proc
 const PROC_NAME: &str = "casperlabs-engine-grpc-server";
 const APP_NAME: &str = "CasperLabs Execution Engine Server";
 const SERVER_START_MESSAGE: &str = "starting Execution Engine Server";
 const SERVER_LISTENING_TEMPLATE: &str = "{listener} is listening on socket: {socket}";
+const SERVER_LISTENING_TCP_TEMPLATE: &str = "{listener} is listening on address: {address}";
 const SERVER_START_EXPECT: &str = "failed to start Execution Engine Server";
 const SERVER_STOP_MESSAGE: &str = "stopping Execution Engine Server";
+const SERVER_NOT_READY_MESSAGE: &str =
+    "global state is not loadable, reporting Execution Engine Server as not serving";
 
 // data-dir / lmdb
 const ARG_DATA_DIR: &str = "data-dir";
@@ -56,6 +81,19 @@ const LMDB_ENVIRONMENT_EXPECT: &str = "Could not create LmdbEnvironment";
 const LMDB_TRIE_STORE_EXPECT: &str = "Could not create LmdbTrieStore";
 const LMDB_PROTOCOL_DATA_STORE_EXPECT: &str = "Could not create LmdbProtocolDataStore";
 const LMDB_GLOBAL_STATE_EXPECT: &str = "Could not create LmdbGlobalState";
+const ROCKSDB_DIR: &str = "rocksdb";
+const ROCKSDB_ENVIRONMENT_EXPECT: &str = "Could not create RocksDbEnvironment";
+const ROCKSDB_TRIE_STORE_EXPECT: &str = "Could not create RocksDbTrieStore";
+const ROCKSDB_PROTOCOL_DATA_STORE_EXPECT: &str = "Could not create RocksDbProtocolDataStore";
+const ROCKSDB_GLOBAL_STATE_EXPECT: &str = "Could not create RocksDbGlobalState";
+
+// storage-backend
+const ARG_STORAGE_BACKEND: &str = "storage-backend";
+const ARG_STORAGE_BACKEND_VALUE: &str = "BACKEND";
+const ARG_STORAGE_BACKEND_HELP: &str =
+    "Sets the database global state is kept in [ lmdb | rocksdb ]";
+const STORAGE_BACKEND_LMDB: &str = "lmdb";
+const STORAGE_BACKEND_ROCKSDB: &str = "rocksdb";
 
 // pages / lmdb
 const ARG_PAGES: &str = "pages";
@@ -68,12 +106,58 @@ const GET_PAGES_EXPECT: &str = "Could not parse pages argument";
 // 805306368000 / 4096 = 196608000
 const DEFAULT_PAGES: usize = 196_608_000;
 
+// max-readers / lmdb
+const ARG_MAX_READERS: &str = "max-readers";
+const ARG_MAX_READERS_VALUE: &str = "NUM";
+const ARG_MAX_READERS_HELP: &str = "Sets the max number of concurrent read transactions on lmdb";
+const GET_MAX_READERS_EXPECT: &str = "Could not parse max-readers argument";
+
+// no-sync / lmdb
+const ARG_NO_SYNC: &str = "no-sync";
+const ARG_NO_SYNC_HELP: &str =
+    "Disables flushing lmdb commits to disk, so the last commits may be lost on a system crash";
+
+// write-map / lmdb
+const ARG_WRITE_MAP: &str = "write-map";
+const ARG_WRITE_MAP_HELP: &str = "Makes lmdb write to a writeable memory map";
+
+// auto-resize / lmdb
+const ARG_AUTO_RESIZE: &str = "auto-resize";
+const ARG_AUTO_RESIZE_HELP: &str = "Doubles lmdb's mmap whenever it is full instead of failing";
+
+// trie-cache-size / lmdb
+const ARG_TRIE_CACHE_SIZE: &str = "trie-cache-size";
+const ARG_TRIE_CACHE_SIZE_VALUE: &str = "NUM";
+const ARG_TRIE_CACHE_SIZE_HELP: &str =
+    "Sets the number of trie nodes cached across exec requests, 0 disables the cache";
+const GET_TRIE_CACHE_SIZE_EXPECT: &str = "Could not parse trie-cache-size argument";
+
 // socket
 const ARG_SOCKET: &str = "socket";
 const ARG_SOCKET_HELP: &str = "socket file";
 const ARG_SOCKET_EXPECT: &str = "socket required";
-const REMOVING_SOCKET_FILE_MESSAGE: &str = "removing old socket file";
-const REMOVING_SOCKET_FILE_EXPECT: &str = "failed to remove old socket file";
+
+// socket-permissions
+const ARG_SOCKET_PERMISSIONS: &str = "socket-permissions";
+const ARG_SOCKET_PERMISSIONS_VALUE: &str = "MODE";
+const ARG_SOCKET_PERMISSIONS_HELP: &str =
+    "Sets the permissions of the socket file as an octal mode, e.g. 660";
+const GET_SOCKET_PERMISSIONS_EXPECT: &str = "Could not parse socket-permissions argument";
+
+// tcp-address
+const ARG_TCP_ADDRESS: &str = "tcp-address";
+const ARG_TCP_ADDRESS_VALUE: &str = "HOST:PORT";
+const ARG_TCP_ADDRESS_HELP: &str =
+    "Also listens on the given TCP address, serving only queries of the global state, e.g. for \
+     tooling running next to the node";
+const GET_TCP_ADDRESS_EXPECT: &str = "Could not parse tcp-address argument";
+
+// last-known-root
+const ARG_LAST_KNOWN_ROOT: &str = "last-known-root";
+const ARG_LAST_KNOWN_ROOT_VALUE: &str = "HASH";
+const ARG_LAST_KNOWN_ROOT_HELP: &str =
+    "Reports the server as serving only if the global state at the given hex root hash is loadable";
+const GET_LAST_KNOWN_ROOT_EXPECT: &str = "Could not parse last-known-root argument";
 
 // loglevel
 const ARG_LOG_LEVEL: &str = "loglevel";
@@ -85,10 +169,82 @@ const ARG_USE_PAYMENT_CODE: &str = "use-payment-code";
 const ARG_USE_PAYMENT_CODE_SHORT: &str = "x";
 const ARG_USE_PAYMENT_CODE_HELP: &str = "Enables the use of payment code";
 
+// fee-handling
+const ARG_FEE_HANDLING: &str = "fee-handling";
+const ARG_FEE_HANDLING_VALUE: &str = "POLICY";
+const ARG_FEE_HANDLING_HELP: &str =
+    "Sets where the fees paid for deploys go [ accumulate | burn | pay-to-proposer ]";
+const FEE_HANDLING_ACCUMULATE: &str = "accumulate";
+const FEE_HANDLING_BURN: &str = "burn";
+const FEE_HANDLING_PAY_TO_PROPOSER: &str = "pay-to-proposer";
+
+// float-handling
+const ARG_FLOAT_HANDLING: &str = "float-handling";
+const ARG_FLOAT_HANDLING_VALUE: &str = "POLICY";
+const ARG_FLOAT_HANDLING_HELP: &str =
+    "Sets how contracts using floating point instructions are treated [ reject | canonicalize-nans ]";
+const FLOAT_HANDLING_REJECT: &str = "reject";
+const FLOAT_HANDLING_CANONICALIZE_NANS: &str = "canonicalize-nans";
+
+// module-cache-size
+const ARG_MODULE_CACHE_SIZE: &str = "module-cache-size";
+const ARG_MODULE_CACHE_SIZE_VALUE: &str = "NUM";
+const ARG_MODULE_CACHE_SIZE_HELP: &str =
+    "Sets the max number of preprocessed wasm modules to cache, 0 disables the cache";
+const GET_MODULE_CACHE_SIZE_EXPECT: &str = "Could not parse module-cache-size argument";
+
+// deploy-threads
+const ARG_DEPLOY_THREADS: &str = "deploy-threads";
+const ARG_DEPLOY_THREADS_VALUE: &str = "NUM";
+const ARG_DEPLOY_THREADS_HELP: &str =
+    "Sets the number of threads executing the deploys of an exec request, 1 executes them serially";
+const GET_DEPLOY_THREADS_EXPECT: &str = "Could not parse deploy-threads argument";
+
+// verify-deploy-hashes
+const ARG_VERIFY_DEPLOY_HASHES: &str = "verify-deploy-hashes";
+const ARG_VERIFY_DEPLOY_HASHES_HELP: &str =
+    "Rejects deploys whose hash is not the hash of their header and body";
+
+// verify-approvals
+const ARG_VERIFY_APPROVALS: &str = "verify-approvals";
+const ARG_VERIFY_APPROVALS_HELP: &str =
+    "Counts only the authorization keys which signed the deploy hash toward the key weights";
+
+// allow-system-account-deploys
+const ARG_ALLOW_SYSTEM_ACCOUNT_DEPLOYS: &str = "allow-system-account-deploys";
+const ARG_ALLOW_SYSTEM_ACCOUNT_DEPLOYS_HELP: &str =
+    "Allows deploys to execute as the system account, which is otherwise reserved to the engine";
+
+// worker-threads
+const ARG_WORKER_THREADS: &str = "worker-threads";
+const ARG_WORKER_THREADS_VALUE: &str = "NUM";
+const ARG_WORKER_THREADS_HELP: &str = "Sets the number of threads handling gRPC requests";
+const GET_WORKER_THREADS_EXPECT: &str = "Could not parse worker-threads argument";
+
+// max-concurrent-execs
+const ARG_MAX_CONCURRENT_EXECS: &str = "max-concurrent-execs";
+const ARG_MAX_CONCURRENT_EXECS_VALUE: &str = "NUM";
+const ARG_MAX_CONCURRENT_EXECS_HELP: &str =
+    "Sets the max number of exec requests executed at once, further ones are rejected";
+const GET_MAX_CONCURRENT_EXECS_EXPECT: &str = "Could not parse max-concurrent-execs argument";
+
+// max-message-size
+const ARG_MAX_MESSAGE_SIZE: &str = "max-message-size";
+const ARG_MAX_MESSAGE_SIZE_VALUE: &str = "BYTES";
+const ARG_MAX_MESSAGE_SIZE_HELP: &str =
+    "Sets the max size of a gRPC request, larger ones are rejected";
+const GET_MAX_MESSAGE_SIZE_EXPECT: &str = "Could not parse max-message-size argument";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
 const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
 
+/// The database global state is kept in.
+enum StorageBackend {
+    Lmdb,
+    RocksDb,
+}
+
 // Command line arguments instance
 lazy_static! {
     static ref ARG_MATCHES: clap::ArgMatches<'static> = get_args();
@@ -108,22 +264,31 @@ fn main() {
 
     let matches: &clap::ArgMatches = &*ARG_MATCHES;
 
-    let socket = get_socket(matches);
-
-    match socket.remove_file() {
-        Err(e) => panic!("{}: {:?}", REMOVING_SOCKET_FILE_EXPECT, e),
-        Ok(_) => logging::log_info(REMOVING_SOCKET_FILE_MESSAGE),
-    };
-
     let data_dir = get_data_dir(matches);
 
-    let map_size = get_map_size(matches);
+    let storage_backend = get_storage_backend(matches);
+
+    let storage_config = get_storage_config(matches);
 
     let engine_config: EngineConfig = get_engine_config(matches);
 
-    let _server = get_grpc_server(&socket, data_dir, map_size, engine_config);
+    let server_config = get_server_config(matches);
+
+    let last_known_root = get_last_known_root(matches);
 
-    log_listening_message(&socket);
+    let health = HealthService::new();
+
+    let _servers = get_grpc_servers(
+        data_dir,
+        storage_backend,
+        storage_config,
+        engine_config,
+        &server_config,
+        &health,
+        last_known_root,
+    );
+
+    log_listening_messages(&server_config);
 
     let interval = Duration::from_secs(RUNNABLE_CHECK_INTERVAL_SECONDS);
 
@@ -133,6 +298,8 @@ fn main() {
         std::thread::park_timeout(interval);
     }
 
+    health.set_serving(false);
+
     logging::log_info(SERVER_STOP_MESSAGE);
 }
 
@@ -175,6 +342,15 @@ fn get_args() -> ArgMatches<'static> {
                 .help(ARG_DATA_DIR_HELP)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name(ARG_STORAGE_BACKEND)
+                .long(ARG_STORAGE_BACKEND)
+                .takes_value(true)
+                .value_name(ARG_STORAGE_BACKEND_VALUE)
+                .possible_values(&[STORAGE_BACKEND_LMDB, STORAGE_BACKEND_ROCKSDB])
+                .default_value(STORAGE_BACKEND_LMDB)
+                .help(ARG_STORAGE_BACKEND_HELP),
+        )
         .arg(
             Arg::with_name(ARG_PAGES)
                 .short(ARG_PAGES_SHORT)
@@ -183,12 +359,134 @@ fn get_args() -> ArgMatches<'static> {
                 .help(ARG_PAGES_HELP)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name(ARG_MAX_READERS)
+                .long(ARG_MAX_READERS)
+                .value_name(ARG_MAX_READERS_VALUE)
+                .help(ARG_MAX_READERS_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_NO_SYNC)
+                .long(ARG_NO_SYNC)
+                .help(ARG_NO_SYNC_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_WRITE_MAP)
+                .long(ARG_WRITE_MAP)
+                .help(ARG_WRITE_MAP_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_AUTO_RESIZE)
+                .long(ARG_AUTO_RESIZE)
+                .help(ARG_AUTO_RESIZE_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_TRIE_CACHE_SIZE)
+                .long(ARG_TRIE_CACHE_SIZE)
+                .value_name(ARG_TRIE_CACHE_SIZE_VALUE)
+                .help(ARG_TRIE_CACHE_SIZE_HELP)
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name(ARG_USE_PAYMENT_CODE)
                 .short(ARG_USE_PAYMENT_CODE_SHORT)
                 .long(ARG_USE_PAYMENT_CODE)
                 .help(ARG_USE_PAYMENT_CODE_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_FEE_HANDLING)
+                .long(ARG_FEE_HANDLING)
+                .takes_value(true)
+                .value_name(ARG_FEE_HANDLING_VALUE)
+                .possible_values(&[
+                    FEE_HANDLING_ACCUMULATE,
+                    FEE_HANDLING_BURN,
+                    FEE_HANDLING_PAY_TO_PROPOSER,
+                ])
+                .default_value(FEE_HANDLING_ACCUMULATE)
+                .help(ARG_FEE_HANDLING_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_FLOAT_HANDLING)
+                .long(ARG_FLOAT_HANDLING)
+                .takes_value(true)
+                .value_name(ARG_FLOAT_HANDLING_VALUE)
+                .possible_values(&[FLOAT_HANDLING_REJECT, FLOAT_HANDLING_CANONICALIZE_NANS])
+                .default_value(FLOAT_HANDLING_REJECT)
+                .help(ARG_FLOAT_HANDLING_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MODULE_CACHE_SIZE)
+                .long(ARG_MODULE_CACHE_SIZE)
+                .takes_value(true)
+                .value_name(ARG_MODULE_CACHE_SIZE_VALUE)
+                .help(ARG_MODULE_CACHE_SIZE_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_DEPLOY_THREADS)
+                .long(ARG_DEPLOY_THREADS)
+                .takes_value(true)
+                .value_name(ARG_DEPLOY_THREADS_VALUE)
+                .help(ARG_DEPLOY_THREADS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_VERIFY_DEPLOY_HASHES)
+                .long(ARG_VERIFY_DEPLOY_HASHES)
+                .help(ARG_VERIFY_DEPLOY_HASHES_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_VERIFY_APPROVALS)
+                .long(ARG_VERIFY_APPROVALS)
+                .help(ARG_VERIFY_APPROVALS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_ALLOW_SYSTEM_ACCOUNT_DEPLOYS)
+                .long(ARG_ALLOW_SYSTEM_ACCOUNT_DEPLOYS)
+                .help(ARG_ALLOW_SYSTEM_ACCOUNT_DEPLOYS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_WORKER_THREADS)
+                .long(ARG_WORKER_THREADS)
+                .takes_value(true)
+                .value_name(ARG_WORKER_THREADS_VALUE)
+                .help(ARG_WORKER_THREADS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_CONCURRENT_EXECS)
+                .long(ARG_MAX_CONCURRENT_EXECS)
+                .takes_value(true)
+                .value_name(ARG_MAX_CONCURRENT_EXECS_VALUE)
+                .help(ARG_MAX_CONCURRENT_EXECS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_MESSAGE_SIZE)
+                .long(ARG_MAX_MESSAGE_SIZE)
+                .takes_value(true)
+                .value_name(ARG_MAX_MESSAGE_SIZE_VALUE)
+                .help(ARG_MAX_MESSAGE_SIZE_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_SOCKET_PERMISSIONS)
+                .long(ARG_SOCKET_PERMISSIONS)
+                .takes_value(true)
+                .value_name(ARG_SOCKET_PERMISSIONS_VALUE)
+                .help(ARG_SOCKET_PERMISSIONS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_TCP_ADDRESS)
+                .long(ARG_TCP_ADDRESS)
+                .takes_value(true)
+                .value_name(ARG_TCP_ADDRESS_VALUE)
+                .help(ARG_TCP_ADDRESS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_LAST_KNOWN_ROOT)
+                .long(ARG_LAST_KNOWN_ROOT)
+                .takes_value(true)
+                .value_name(ARG_LAST_KNOWN_ROOT_VALUE)
+                .help(ARG_LAST_KNOWN_ROOT_HELP),
+        )
         .arg(
             Arg::with_name(ARG_SOCKET)
                 .required(true)
@@ -231,6 +529,14 @@ fn get_data_dir(matches: &ArgMatches) -> PathBuf {
     buf
 }
 
+/// Parses storage-backend argument
+fn get_storage_backend(matches: &ArgMatches) -> StorageBackend {
+    match matches.value_of(ARG_STORAGE_BACKEND) {
+        Some(STORAGE_BACKEND_ROCKSDB) => StorageBackend::RocksDb,
+        _ => StorageBackend::Lmdb,
+    }
+}
+
 ///  Parses pages argument and returns map size
 fn get_map_size(matches: &ArgMatches) -> usize {
     let page_size = get_page_size().unwrap();
@@ -241,34 +547,178 @@ fn get_map_size(matches: &ArgMatches) -> usize {
     page_size * pages
 }
 
-/// Parses `use-payment-code` argument and returns an [`EngineConfig`].
+/// Parses `pages`, `max-readers`, `no-sync`, `write-map` and `auto-resize` arguments and returns
+/// a [`StorageConfig`].
+fn get_storage_config(matches: &ArgMatches) -> StorageConfig {
+    let max_readers = matches
+        .value_of(ARG_MAX_READERS)
+        .map_or(Ok(DEFAULT_MAX_READERS), u32::from_str)
+        .expect(GET_MAX_READERS_EXPECT);
+    let trie_cache_size = matches
+        .value_of(ARG_TRIE_CACHE_SIZE)
+        .map_or(Ok(DEFAULT_TRIE_CACHE_SIZE), usize::from_str)
+        .expect(GET_TRIE_CACHE_SIZE_EXPECT);
+    StorageConfig::new()
+        .set_map_size(get_map_size(matches))
+        .set_max_readers(max_readers)
+        .set_no_sync(matches.is_present(ARG_NO_SYNC))
+        .set_write_map(matches.is_present(ARG_WRITE_MAP))
+        .set_auto_resize(matches.is_present(ARG_AUTO_RESIZE))
+        .set_trie_cache_size(trie_cache_size)
+}
+
+/// Parses `use-payment-code`, `fee-handling`, `float-handling`,
+/// `module-cache-size`, `deploy-threads`, `verify-deploy-hashes`,
+/// `verify-approvals` and `allow-system-account-deploys` arguments and returns an
+/// [`EngineConfig`].
 fn get_engine_config(matches: &ArgMatches) -> EngineConfig {
     let use_payment_code = matches.is_present(ARG_USE_PAYMENT_CODE);
-    EngineConfig::new().set_use_payment_code(use_payment_code)
+    let fee_handling = match matches.value_of(ARG_FEE_HANDLING) {
+        Some(FEE_HANDLING_BURN) => FeeHandling::Burn,
+        Some(FEE_HANDLING_PAY_TO_PROPOSER) => FeeHandling::PayToProposer,
+        _ => FeeHandling::Accumulate,
+    };
+    let float_handling = match matches.value_of(ARG_FLOAT_HANDLING) {
+        Some(FLOAT_HANDLING_CANONICALIZE_NANS) => FloatHandling::CanonicalizeNaNs,
+        _ => FloatHandling::Reject,
+    };
+    let module_cache_size = matches
+        .value_of(ARG_MODULE_CACHE_SIZE)
+        .map_or(Ok(DEFAULT_MODULE_CACHE_SIZE), usize::from_str)
+        .expect(GET_MODULE_CACHE_SIZE_EXPECT);
+    let deploy_threads = matches
+        .value_of(ARG_DEPLOY_THREADS)
+        .map_or(Ok(DEFAULT_DEPLOY_THREADS), usize::from_str)
+        .expect(GET_DEPLOY_THREADS_EXPECT);
+    EngineConfig::new()
+        .set_use_payment_code(use_payment_code)
+        .set_fee_handling(fee_handling)
+        .set_float_handling(float_handling)
+        .set_module_cache_size(module_cache_size)
+        .set_deploy_threads(deploy_threads)
+        .set_verify_deploy_hashes(matches.is_present(ARG_VERIFY_DEPLOY_HASHES))
+        .set_verify_approvals(matches.is_present(ARG_VERIFY_APPROVALS))
+        .set_restrict_system_account(!matches.is_present(ARG_ALLOW_SYSTEM_ACCOUNT_DEPLOYS))
+}
+
+/// Parses `socket`, `socket-permissions`, `tcp-address`, `worker-threads`, `max-concurrent-execs`
+/// and `max-message-size` arguments and returns a [`ServerConfig`].
+fn get_server_config(matches: &ArgMatches) -> ServerConfig {
+    let worker_threads = matches
+        .value_of(ARG_WORKER_THREADS)
+        .map_or(Ok(DEFAULT_WORKER_THREADS), usize::from_str)
+        .expect(GET_WORKER_THREADS_EXPECT);
+    let max_concurrent_execs = matches
+        .value_of(ARG_MAX_CONCURRENT_EXECS)
+        .map_or(Ok(DEFAULT_MAX_CONCURRENT_EXECS), usize::from_str)
+        .expect(GET_MAX_CONCURRENT_EXECS_EXPECT);
+    let max_message_size = matches
+        .value_of(ARG_MAX_MESSAGE_SIZE)
+        .map_or(Ok(DEFAULT_MAX_MESSAGE_SIZE), usize::from_str)
+        .expect(GET_MAX_MESSAGE_SIZE_EXPECT);
+    let mut server_config = ServerConfig::new()
+        .set_worker_threads(worker_threads)
+        .set_max_concurrent_execs(max_concurrent_execs)
+        .set_max_message_size(max_message_size)
+        .set_unix_socket(get_socket(matches).value());
+    if let Some(mode) = matches.value_of(ARG_SOCKET_PERMISSIONS) {
+        let mode = u32::from_str_radix(mode, 8).expect(GET_SOCKET_PERMISSIONS_EXPECT);
+        server_config = server_config.set_socket_permissions(mode);
+    }
+    if let Some(tcp_address) = matches.value_of(ARG_TCP_ADDRESS) {
+        let tcp_address = SocketAddr::from_str(tcp_address).expect(GET_TCP_ADDRESS_EXPECT);
+        server_config = server_config.set_tcp_address(tcp_address);
+    }
+    server_config
 }
 
-/// Builds and returns a gRPC server.
-fn get_grpc_server(
-    socket: &socket::Socket,
+/// Parses last-known-root argument
+fn get_last_known_root(matches: &ArgMatches) -> Option<Blake2bHash> {
+    matches.value_of(ARG_LAST_KNOWN_ROOT).map(|root| {
+        let bytes = base16::decode_lower(root).expect(GET_LAST_KNOWN_ROOT_EXPECT);
+        Blake2bHash::try_from(bytes.as_slice()).expect(GET_LAST_KNOWN_ROOT_EXPECT)
+    })
+}
+
+/// Builds and returns the gRPC servers, one per address listened on, serving global state kept in
+/// the given storage backend.
+fn get_grpc_servers(
     data_dir: PathBuf,
-    map_size: usize,
+    storage_backend: StorageBackend,
+    storage_config: StorageConfig,
     engine_config: EngineConfig,
-) -> grpc::Server {
-    let engine_state = get_engine_state(data_dir, map_size, engine_config);
+    server_config: &ServerConfig,
+    health: &HealthService,
+    last_known_root: Option<Blake2bHash>,
+) -> Vec<grpc::Server> {
+    match storage_backend {
+        StorageBackend::Lmdb => {
+            let engine_state = get_engine_state(data_dir, storage_config, engine_config);
+            start_grpc_servers(engine_state, server_config, health, last_known_root)
+        }
+        StorageBackend::RocksDb => {
+            let engine_state = get_rocksdb_engine_state(data_dir, engine_config);
+            start_grpc_servers(engine_state, server_config, health, last_known_root)
+        }
+    }
+}
+
+/// Starts and returns the gRPC servers for `engine_state`.  `health` reports them as serving if
+/// the global state is loadable.
+fn start_grpc_servers<S>(
+    engine_state: EngineState<S>,
+    server_config: &ServerConfig,
+    health: &HealthService,
+    last_known_root: Option<Blake2bHash>,
+) -> Vec<grpc::Server>
+where
+    S: StateProvider + Send + Sync + 'static,
+    EngineError: From<S::Error>,
+    S::Error: Into<execution::Error> + Debug,
+{
+    let ready = is_global_state_loadable(&engine_state, last_known_root);
+
+    let servers =
+        engine_server::new(server_config, health, engine_state).expect(SERVER_START_EXPECT);
+
+    if ready {
+        health.set_serving(true);
+    } else {
+        logging::log_error(SERVER_NOT_READY_MESSAGE);
+    }
 
-    engine_server::new(socket.as_str(), engine_state)
-        .build()
-        .expect(SERVER_START_EXPECT)
+    servers
+}
+
+/// Returns `true` if the empty global state and, if given, the global state at `last_known_root`
+/// can be checked out, i.e. the global state was opened and is usable
+fn is_global_state_loadable<S>(
+    engine_state: &EngineState<S>,
+    last_known_root: Option<Blake2bHash>,
+) -> bool
+where
+    S: StateProvider,
+    EngineError: From<S::Error>,
+    S::Error: Into<execution::Error>,
+{
+    let empty_root = engine_state.empty_root();
+    std::iter::once(empty_root)
+        .chain(last_known_root)
+        .all(|root| match engine_state.tracking_copy(root) {
+            Ok(tracking_copy) => tracking_copy.is_some(),
+            Err(_) => false,
+        })
 }
 
 /// Builds and returns engine global state
 fn get_engine_state(
     data_dir: PathBuf,
-    map_size: usize,
+    storage_config: StorageConfig,
     engine_config: EngineConfig,
 ) -> EngineState<LmdbGlobalState> {
     let environment = {
-        let ret = LmdbEnvironment::new(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
+        let ret =
+            LmdbEnvironment::with_config(&data_dir, storage_config).expect(LMDB_ENVIRONMENT_EXPECT);
         Arc::new(ret)
     };
 
@@ -290,6 +740,36 @@ fn get_engine_state(
     EngineState::new(global_state, engine_config)
 }
 
+/// Builds and returns engine global state kept in RocksDB, under its own directory of the data
+/// directory
+fn get_rocksdb_engine_state(
+    mut data_dir: PathBuf,
+    engine_config: EngineConfig,
+) -> EngineState<RocksDbGlobalState> {
+    data_dir.push(ROCKSDB_DIR);
+
+    let environment = {
+        let ret = RocksDbEnvironment::new(&data_dir).expect(ROCKSDB_ENVIRONMENT_EXPECT);
+        Arc::new(ret)
+    };
+
+    let trie_store = {
+        let ret = RocksDbTrieStore::new(&environment, None).expect(ROCKSDB_TRIE_STORE_EXPECT);
+        Arc::new(ret)
+    };
+
+    let protocol_data_store = {
+        let ret = RocksDbProtocolDataStore::new(&environment, None)
+            .expect(ROCKSDB_PROTOCOL_DATA_STORE_EXPECT);
+        Arc::new(ret)
+    };
+
+    let global_state = RocksDbGlobalState::empty(environment, trie_store, protocol_data_store)
+        .expect(ROCKSDB_GLOBAL_STATE_EXPECT);
+
+    EngineState::new(global_state, engine_config)
+}
+
 /// Builds and returns log_settings
 fn get_log_settings() -> log_settings::LogSettings {
     let matches: &clap::ArgMatches = &*ARG_MATCHES;
@@ -299,16 +779,26 @@ fn get_log_settings() -> log_settings::LogSettings {
     LogSettings::new(PROC_NAME, log_level_filter)
 }
 
-/// Logs listening on socket message
-fn log_listening_message(socket: &socket::Socket) {
+/// Logs a listening message for each address listened on
+fn log_listening_messages(server_config: &ServerConfig) {
+    if let Some(socket) = server_config.unix_socket() {
+        log_listening_message(SERVER_LISTENING_TEMPLATE, "socket", socket.to_owned());
+    }
+    if let Some(tcp_address) = server_config.tcp_address() {
+        log_listening_message(
+            SERVER_LISTENING_TCP_TEMPLATE,
+            "address",
+            tcp_address.to_string(),
+        );
+    }
+}
+
+/// Logs listening message
+fn log_listening_message(template: &str, key: &str, value: String) {
     let mut properties: BTreeMap<String, String> = BTreeMap::new();
 
     properties.insert("listener".to_string(), PROC_NAME.to_owned());
-    properties.insert("socket".to_string(), socket.value());
+    properties.insert(key.to_string(), value);
 
-    logging::log_details(
-        log_level::LogLevel::Info,
-        (&*SERVER_LISTENING_TEMPLATE).to_string(),
-        properties,
-    );
+    logging::log_details(log_level::LogLevel::Info, template.to_string(), properties);
 }
@@ -5,6 +5,18 @@ use num::Zero;
 
 use crate::motes::Motes;
 
+/// How a conversion from motes to gas treats motes which don't make up a whole unit of gas.
+///
+/// Charges are rounded up and limits or refunds are rounded down, so that the system never
+/// under-charges a deploy for the gas it used.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Rounding {
+    /// Round towards zero; used for gas limits and refunds.
+    Floor,
+    /// Round away from zero; used when charging.
+    Ceil,
+}
+
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Gas(U512);
 
@@ -17,17 +29,38 @@ impl Gas {
         self.0
     }
 
-    pub fn from_motes(motes: Motes, conv_rate: u64) -> Option<Self> {
-        motes
-            .value()
-            .checked_div(U512::from(conv_rate))
-            .map(Self::new)
+    /// Converts `motes` to gas at `conv_rate` motes per unit of gas, rounding any remainder as
+    /// given by `rounding`.  Returns `None` if `conv_rate` is zero.
+    pub fn from_motes(motes: Motes, conv_rate: u64, rounding: Rounding) -> Option<Self> {
+        let conv_rate = U512::from(conv_rate);
+        let floor = motes.value().checked_div(conv_rate)?;
+        let gas = match rounding {
+            Rounding::Ceil if floor * conv_rate < motes.value() => floor + U512::one(),
+            _ => floor,
+        };
+        Some(Self::new(gas))
     }
 
     pub fn checked_add(&self, rhs: Self) -> Option<Self> {
         self.0.checked_add(rhs.value()).map(Self::new)
     }
 
+    pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.value()).map(Self::new)
+    }
+
+    pub fn checked_mul(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.value()).map(Self::new)
+    }
+
+    pub fn checked_div(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_div(rhs.value()).map(Self::new)
+    }
+
+    pub fn saturating_add(&self, rhs: Self) -> Self {
+        Self::new(self.0.saturating_add(rhs.value()))
+    }
+
     // TODO: remove when possible; see https://casperlabs.atlassian.net/browse/EE-649
     pub fn as_u64(&self) -> u64 {
         self.0.as_u64()
@@ -39,6 +72,18 @@ impl Gas {
     }
 }
 
+impl From<u64> for Gas {
+    fn from(value: u64) -> Self {
+        Gas::new(U512::from(value))
+    }
+}
+
+impl From<U512> for Gas {
+    fn from(value: U512) -> Self {
+        Gas::new(value)
+    }
+}
+
 impl fmt::Display for Gas {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self.0)
@@ -91,11 +136,95 @@ impl Zero for Gas {
     }
 }
 
+/// The kinds of work a deploy is charged gas for.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GasCategory {
+    /// Execution of wasm instructions, as metered by the injected `gas` calls.
+    Opcodes,
+    /// Bytes written to global state.
+    StorageWrites,
+    /// Calls into host functions other than the metering function.
+    HostCalls,
+    /// Transfers of motes between purses.
+    Transfers,
+}
+
+/// Gas consumed by a deploy, split by [`GasCategory`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct GasBreakdown {
+    opcodes: Gas,
+    storage_writes: Gas,
+    host_calls: Gas,
+    transfers: Gas,
+}
+
+impl GasBreakdown {
+    pub fn new(category: GasCategory, amount: Gas) -> Self {
+        let mut breakdown = GasBreakdown::default();
+        breakdown.add(category, amount);
+        breakdown
+    }
+
+    pub fn opcodes(&self) -> Gas {
+        self.opcodes
+    }
+
+    pub fn storage_writes(&self) -> Gas {
+        self.storage_writes
+    }
+
+    pub fn host_calls(&self) -> Gas {
+        self.host_calls
+    }
+
+    pub fn transfers(&self) -> Gas {
+        self.transfers
+    }
+
+    pub fn get(&self, category: GasCategory) -> Gas {
+        match category {
+            GasCategory::Opcodes => self.opcodes,
+            GasCategory::StorageWrites => self.storage_writes,
+            GasCategory::HostCalls => self.host_calls,
+            GasCategory::Transfers => self.transfers,
+        }
+    }
+
+    pub fn add(&mut self, category: GasCategory, amount: Gas) {
+        let gas = match category {
+            GasCategory::Opcodes => &mut self.opcodes,
+            GasCategory::StorageWrites => &mut self.storage_writes,
+            GasCategory::HostCalls => &mut self.host_calls,
+            GasCategory::Transfers => &mut self.transfers,
+        };
+        *gas = *gas + amount;
+    }
+
+    /// Sum of all categories.
+    pub fn total(&self) -> Gas {
+        self.opcodes + self.storage_writes + self.host_calls + self.transfers
+    }
+}
+
+impl std::ops::Add for GasBreakdown {
+    type Output = GasBreakdown;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        GasBreakdown {
+            opcodes: self.opcodes + rhs.opcodes,
+            storage_writes: self.storage_writes + rhs.storage_writes,
+            host_calls: self.host_calls + rhs.host_calls,
+            transfers: self.transfers + rhs.transfers,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::gas::Gas;
+    use crate::gas::{Gas, GasBreakdown, GasCategory, Rounding};
     use crate::motes::Motes;
     use contract_ffi::value::U512;
+    use num::Zero;
 
     #[test]
     fn should_be_able_to_get_instance_of_gas() {
@@ -163,11 +292,78 @@ mod tests {
     #[test]
     fn should_be_able_to_convert_from_mote() {
         let mote = Motes::new(U512::from(100));
-        let gas = Gas::from_motes(mote, 10).expect("should have gas");
+        let gas = Gas::from_motes(mote, 10, Rounding::Floor).expect("should have gas");
         let expected_gas = Gas::new(U512::from(10));
         assert_eq!(gas, expected_gas, "should be equal")
     }
 
+    #[test]
+    fn should_support_checked_arithmetic() {
+        let one = Gas::from(1);
+        let two = Gas::from(2);
+        assert_eq!(two.checked_sub(one), Some(one));
+        assert_eq!(
+            one.checked_sub(two),
+            None,
+            "should be none due to underflow"
+        );
+        assert_eq!(Gas::from(U512::MAX).checked_mul(two), None);
+        assert_eq!(two.checked_mul(two), Some(Gas::from(4)));
+        assert_eq!(two.checked_div(Gas::zero()), None);
+        assert_eq!(two.checked_div(two), Some(one));
+    }
+
+    #[test]
+    fn should_saturate_on_overflowing_add() {
+        let max = Gas::from(U512::MAX);
+        assert_eq!(max.saturating_add(Gas::from(1)), max);
+        assert_eq!(Gas::from(1).saturating_add(Gas::from(1)), Gas::from(2));
+    }
+
+    #[test]
+    fn should_convert_from_u64_and_u512() {
+        assert_eq!(Gas::from(10), Gas::new(U512::from(10)));
+        assert_eq!(Gas::from(U512::from(10)), Gas::from_u64(10));
+    }
+
+    #[test]
+    fn should_round_conversion_from_motes() {
+        let motes = Motes::from(25);
+        let floor = Gas::from_motes(motes, 10, Rounding::Floor).expect("should have gas");
+        let ceil = Gas::from_motes(motes, 10, Rounding::Ceil).expect("should have gas");
+        assert_eq!(floor, Gas::from(2));
+        assert_eq!(ceil, Gas::from(3));
+
+        let exact = Motes::from(20);
+        assert_eq!(
+            Gas::from_motes(exact, 10, Rounding::Floor),
+            Gas::from_motes(exact, 10, Rounding::Ceil)
+        );
+    }
+
+    #[test]
+    fn should_never_undercharge() {
+        // Charging the ceiling of the gas bought by some motes always costs at least those motes,
+        // while a limit or refund taken at the floor never costs more than them.
+        for conv_rate in 1..=13 {
+            for value in 0..100 {
+                let motes = Motes::from(value);
+                let ceil = Gas::from_motes(motes, conv_rate, Rounding::Ceil).unwrap();
+                let floor = Gas::from_motes(motes, conv_rate, Rounding::Floor).unwrap();
+                assert!(Motes::from_gas(ceil, conv_rate).unwrap() >= motes);
+                assert!(Motes::from_gas(floor, conv_rate).unwrap() <= motes);
+                assert!(ceil.value() - floor.value() <= U512::one());
+            }
+        }
+    }
+
+    #[test]
+    fn should_not_overflow_rounding_up_max_motes() {
+        let motes = Motes::from(U512::MAX);
+        let ceil = Gas::from_motes(motes, 2, Rounding::Ceil).expect("should have gas");
+        assert_eq!(ceil, Gas::from(U512::MAX / 2 + 1));
+    }
+
     #[test]
     fn should_be_able_to_default() {
         let gas = Gas::default();
@@ -200,7 +396,31 @@ mod tests {
     fn should_support_checked_div_from_motes() {
         let motes = Motes::new(U512::zero());
         let conv_rate = 0;
-        let maybe = Gas::from_motes(motes, conv_rate);
+        let maybe = Gas::from_motes(motes, conv_rate, Rounding::Floor);
         assert!(maybe.is_none(), "should be none due to divide by zero");
     }
+
+    #[test]
+    fn should_accumulate_gas_per_category() {
+        let mut breakdown = GasBreakdown::default();
+        breakdown.add(GasCategory::Opcodes, Gas::from_u64(1));
+        breakdown.add(GasCategory::Opcodes, Gas::from_u64(2));
+        breakdown.add(GasCategory::StorageWrites, Gas::from_u64(10));
+        breakdown.add(GasCategory::Transfers, Gas::from_u64(100));
+        assert_eq!(breakdown.opcodes(), Gas::from_u64(3));
+        assert_eq!(breakdown.storage_writes(), Gas::from_u64(10));
+        assert_eq!(breakdown.host_calls(), Gas::default());
+        assert_eq!(breakdown.transfers(), Gas::from_u64(100));
+        assert_eq!(breakdown.total(), Gas::from_u64(113));
+    }
+
+    #[test]
+    fn should_add_two_breakdowns_per_category() {
+        let left = GasBreakdown::new(GasCategory::Opcodes, Gas::from_u64(5));
+        let right = GasBreakdown::new(GasCategory::HostCalls, Gas::from_u64(7));
+        let sum = left + right;
+        assert_eq!(sum.get(GasCategory::Opcodes), Gas::from_u64(5));
+        assert_eq!(sum.get(GasCategory::HostCalls), Gas::from_u64(7));
+        assert_eq!(sum.total(), left.total() + right.total());
+    }
 }
@@ -0,0 +1,108 @@
+use contract_ffi::bytesrepr::{self, FromBytes, ToBytes, U32_SIZE};
+use contract_ffi::value::account::MAX_KEYS;
+
+/// Most associated keys an account may hold under protocol versions which do not configure a
+/// limit, matching the limit accounts have always been subject to.
+pub const DEFAULT_MAX_ASSOCIATED_KEYS: u32 = MAX_KEYS as u32;
+
+/// Most named keys an account may hold under protocol versions which do not configure a limit.
+/// Named keys used to be unbounded, so by default they still are.
+pub const DEFAULT_MAX_NAMED_KEYS: u32 = u32::max_value();
+
+pub const KEY_LIMITS_SIZE_SERIALIZED: usize = 2 * U32_SIZE;
+
+/// Bounds on the number of keys an account may accumulate, keeping its global state entry from
+/// growing without limit.
+///
+/// The limits only stop accounts from adding keys; accounts already holding more keys than allowed
+/// keep them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct KeyLimits {
+    max_associated_keys: u32,
+    max_named_keys: u32,
+}
+
+impl KeyLimits {
+    pub fn new(max_associated_keys: u32, max_named_keys: u32) -> Self {
+        KeyLimits {
+            max_associated_keys,
+            max_named_keys,
+        }
+    }
+
+    /// Returns the most associated keys an account may hold.
+    pub fn max_associated_keys(&self) -> u32 {
+        self.max_associated_keys
+    }
+
+    /// Returns the most named keys an account may hold.
+    pub fn max_named_keys(&self) -> u32 {
+        self.max_named_keys
+    }
+}
+
+impl Default for KeyLimits {
+    fn default() -> Self {
+        KeyLimits::new(DEFAULT_MAX_ASSOCIATED_KEYS, DEFAULT_MAX_NAMED_KEYS)
+    }
+}
+
+impl ToBytes for KeyLimits {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret: Vec<u8> = Vec::with_capacity(KEY_LIMITS_SIZE_SERIALIZED);
+        ret.append(&mut self.max_associated_keys.to_bytes()?);
+        ret.append(&mut self.max_named_keys.to_bytes()?);
+        Ok(ret)
+    }
+}
+
+impl FromBytes for KeyLimits {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (max_associated_keys, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (max_named_keys, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((KeyLimits::new(max_associated_keys, max_named_keys), rem))
+    }
+}
+
+pub mod gens {
+    use proptest::prop_compose;
+
+    use super::KeyLimits;
+
+    prop_compose! {
+        pub fn key_limits_arb()(
+            max_associated_keys in 1u32..,
+            max_named_keys in 1u32..,
+        ) -> KeyLimits {
+            KeyLimits::new(max_associated_keys, max_named_keys)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use crate::test_utils;
+
+    use super::{gens, KeyLimits, DEFAULT_MAX_ASSOCIATED_KEYS, DEFAULT_MAX_NAMED_KEYS};
+
+    #[test]
+    fn should_default_to_previous_limits() {
+        let key_limits = KeyLimits::default();
+        assert_eq!(
+            key_limits.max_associated_keys(),
+            DEFAULT_MAX_ASSOCIATED_KEYS
+        );
+        assert_eq!(key_limits.max_named_keys(), DEFAULT_MAX_NAMED_KEYS);
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            key_limits in gens::key_limits_arb()
+        ) {
+            assert!(test_utils::test_serialization_roundtrip(&key_limits));
+        }
+    }
+}
@@ -13,6 +13,7 @@ extern crate parity_wasm;
 
 #[macro_use]
 pub mod gas;
+pub mod key_limits;
 pub mod logging;
 pub mod motes;
 pub mod newtypes;
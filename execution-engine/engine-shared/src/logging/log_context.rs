@@ -0,0 +1,96 @@
+//! Properties added to every message logged by the current thread, e.g. the correlation ID of the
+//! request being handled.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use crate::newtypes::CorrelationId;
+
+pub const CORRELATION_ID_KEY: &str = "correlation_id";
+pub const DEPLOY_HASH_KEY: &str = "deploy_hash";
+
+thread_local! {
+    static PROPERTIES: RefCell<BTreeMap<String, String>> = RefCell::new(BTreeMap::new());
+}
+
+/// Restores the property it was returned for to its previous value when dropped.
+#[must_use = "the property is removed again when the guard is dropped"]
+pub struct LogContextGuard {
+    key: String,
+    previous: Option<String>,
+}
+
+impl Drop for LogContextGuard {
+    fn drop(&mut self) {
+        PROPERTIES.with(|properties| {
+            let mut properties = properties.borrow_mut();
+            match self.previous.take() {
+                Some(previous) => properties.insert(self.key.clone(), previous),
+                None => properties.remove(&self.key),
+            };
+        })
+    }
+}
+
+/// Adds `key` with `value` to the properties of the messages logged by the current thread until
+/// the returned guard is dropped.
+pub fn enter(key: &str, value: String) -> LogContextGuard {
+    let previous =
+        PROPERTIES.with(|properties| properties.borrow_mut().insert(key.to_string(), value));
+    LogContextGuard {
+        key: key.to_string(),
+        previous,
+    }
+}
+
+/// Tags the messages logged by the current thread with `correlation_id` until the returned guard
+/// is dropped.
+pub fn enter_correlation_id(correlation_id: CorrelationId) -> LogContextGuard {
+    enter(CORRELATION_ID_KEY, correlation_id.to_string())
+}
+
+/// Tags the messages logged by the current thread with `deploy_hash` until the returned guard is
+/// dropped.
+pub fn enter_deploy_hash(deploy_hash: &[u8]) -> LogContextGuard {
+    enter(DEPLOY_HASH_KEY, base16::encode_lower(deploy_hash))
+}
+
+/// Returns the properties the current thread's messages are tagged with.
+pub fn properties() -> BTreeMap<String, String> {
+    PROPERTIES.with(|properties| properties.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn should_restore_previous_properties() {
+        let correlation_id = CorrelationId::new();
+        let _correlation_id_guard = enter_correlation_id(correlation_id);
+        {
+            let _deploy_hash_guard = enter_deploy_hash(&[1, 2]);
+            let _override_guard = enter(CORRELATION_ID_KEY, "overridden".to_string());
+
+            let properties = properties();
+            assert_eq!(properties[CORRELATION_ID_KEY], "overridden");
+            assert_eq!(properties[DEPLOY_HASH_KEY], "0102");
+        }
+
+        let properties = properties();
+        assert_eq!(properties[CORRELATION_ID_KEY], correlation_id.to_string());
+        assert!(!properties.contains_key(DEPLOY_HASH_KEY));
+    }
+
+    #[test]
+    fn should_not_share_properties_between_threads() {
+        let _guard = enter_correlation_id(CorrelationId::new());
+
+        let other_thread_properties = thread::spawn(properties)
+            .join()
+            .expect("should join thread");
+        assert!(other_thread_properties.is_empty());
+    }
+}
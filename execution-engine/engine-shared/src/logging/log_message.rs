@@ -6,6 +6,7 @@ use std::hash::{Hash, Hasher};
 use chrono::{DateTime, SecondsFormat, Utc};
 use serde::Serialize;
 
+use crate::logging::log_context;
 use crate::logging::log_level::{LogLevel, LogPriority};
 use crate::logging::log_settings::{HostName, LogSettingsProvider, ProcessId, ProcessName};
 use crate::semver::SemVer;
@@ -29,6 +30,8 @@ pub struct LogMessage {
 }
 
 impl LogMessage {
+    /// Creates a message of `properties`, also tagged with the properties of the current thread's
+    /// [`log_context`] which `properties` doesn't override.
     pub fn new_props<T: ?Sized>(
         log_settings_provider: &T,
         log_level: LogLevel,
@@ -42,6 +45,9 @@ impl LogMessage {
         properties
             .entry(MESSAGE_TEMPLATE_KEY.to_string())
             .or_insert_with(|| message_template.clone());
+        for (key, value) in log_context::properties() {
+            properties.entry(key).or_insert(value);
+        }
         let message_type = MessageType::new(MESSAGE_TYPE.to_string());
         let message_type_version = SemVer::V1_0_0;
         let process_id = log_settings_provider.get_process_id();
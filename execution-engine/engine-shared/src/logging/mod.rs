@@ -7,6 +7,7 @@ use crate::logging::logger::initialize_terminal_logger;
 use crate::newtypes::CorrelationId;
 use crate::utils::jsonify;
 
+pub mod log_context;
 pub mod log_level;
 pub mod log_message;
 pub mod log_settings;
@@ -153,7 +154,10 @@ pub fn log_metric(
         metric, tag, metric_value, milliseconds_since_epoch
     );
 
-    properties.insert("correlation_id".to_string(), correlation_id.to_string());
+    properties.insert(
+        log_context::CORRELATION_ID_KEY.to_string(),
+        correlation_id.to_string(),
+    );
 
     properties.insert("time-series-data".to_string(), tsd_metric);
 
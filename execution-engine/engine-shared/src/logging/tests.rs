@@ -236,3 +236,39 @@ fn should_set_and_get_log_settings_provider() {
 
     let _r = handle.join();
 }
+
+#[test]
+fn should_tag_messages_with_log_context() {
+    setup();
+
+    let correlation_id = CorrelationId::new();
+
+    let message_id = {
+        let _correlation_id_guard = log_context::enter_correlation_id(correlation_id);
+        let _deploy_hash_guard = log_context::enter_deploy_hash(&[42; 32]);
+        log_info("this is tagged with the log context").expect("expected message_id")
+    };
+
+    let message = logger::BUFFERED_LOGGER
+        .extract(&message_id.value())
+        .expect("expected message");
+
+    assert_eq!(
+        message.properties.get(log_context::CORRELATION_ID_KEY),
+        Some(&correlation_id.to_string()),
+        "should have correlation_id"
+    );
+    assert_eq!(
+        message.properties.get(log_context::DEPLOY_HASH_KEY),
+        Some(&base16::encode_lower(&[42; 32])),
+        "should have deploy_hash"
+    );
+
+    let untagged_id = log_info("this is not tagged").expect("expected message_id");
+    let untagged = logger::BUFFERED_LOGGER
+        .extract(&untagged_id.value())
+        .expect("expected message");
+    assert!(!untagged
+        .properties
+        .contains_key(log_context::CORRELATION_ID_KEY));
+}
@@ -1,10 +1,78 @@
 use std::fmt;
+use std::str::FromStr;
 
 use contract_ffi::value::U512;
 use num::Zero;
 
 use crate::gas::Gas;
 
+/// A unit in which an amount of motes can be written, worth `10^decimals` motes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Denomination {
+    symbol: &'static str,
+    decimals: u32,
+}
+
+impl Denomination {
+    pub const MOTES: Denomination = Denomination {
+        symbol: "motes",
+        decimals: 0,
+    };
+
+    pub const CLX: Denomination = Denomination {
+        symbol: "CLX",
+        decimals: 9,
+    };
+
+    pub fn new(symbol: &'static str, decimals: u32) -> Denomination {
+        Denomination { symbol, decimals }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        self.symbol
+    }
+
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    /// Returns the number of motes in one unit of this denomination, or `None` if that doesn't
+    /// fit in a `U512`.
+    fn factor(&self) -> Option<U512> {
+        (0..self.decimals).try_fold(U512::one(), |acc, _| acc.checked_mul(U512::from(10)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMotesError {
+    /// The amount is not a decimal number, optionally using `_` as a digit separator.
+    InvalidAmount(String),
+    /// The amount is followed by a symbol none of the accepted denominations use.
+    UnknownDenomination(String),
+    /// The amount has more decimal places than its denomination allows, i.e. it is not a whole
+    /// number of motes.
+    TooManyDecimals { max: u32, actual: usize },
+    /// The amount does not fit in a `U512` number of motes.
+    Overflow,
+}
+
+impl fmt::Display for ParseMotesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMotesError::InvalidAmount(amount) => write!(f, "invalid amount: {}", amount),
+            ParseMotesError::UnknownDenomination(symbol) => {
+                write!(f, "unknown denomination: {}", symbol)
+            }
+            ParseMotesError::TooManyDecimals { max, actual } => write!(
+                f,
+                "too many decimal places: at most {} allowed, {} given",
+                max, actual
+            ),
+            ParseMotesError::Overflow => write!(f, "amount overflows U512 motes"),
+        }
+    }
+}
+
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Motes(U512);
 
@@ -17,6 +85,22 @@ impl Motes {
         self.0.checked_add(rhs.value()).map(Self::new)
     }
 
+    pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.value()).map(Self::new)
+    }
+
+    pub fn checked_mul(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.value()).map(Self::new)
+    }
+
+    pub fn checked_div(&self, rhs: Self) -> Option<Self> {
+        self.0.checked_div(rhs.value()).map(Self::new)
+    }
+
+    pub fn saturating_add(&self, rhs: Self) -> Self {
+        Self::new(self.0.saturating_add(rhs.value()))
+    }
+
     pub fn value(&self) -> U512 {
         self.0
     }
@@ -31,11 +115,104 @@ impl Motes {
     pub fn from_u64(value: u64) -> Motes {
         Motes(U512::from(value))
     }
+
+    /// Formats the amount in `denomination`, e.g. "2.5 CLX", omitting trailing zero decimals.
+    pub fn format_as(&self, denomination: Denomination) -> String {
+        let (whole, fraction) = match denomination.factor() {
+            Some(factor) => (self.0 / factor, self.0 % factor),
+            // A unit worth more than U512::MAX motes: every amount is a fraction of one unit
+            None => (U512::zero(), self.0),
+        };
+        if fraction.is_zero() {
+            return format!("{} {}", whole, denomination.symbol);
+        }
+        let fraction = format!(
+            "{:0>width$}",
+            fraction.to_string(),
+            width = denomination.decimals as usize
+        );
+        format!(
+            "{}.{} {}",
+            whole,
+            fraction.trim_end_matches('0'),
+            denomination.symbol
+        )
+    }
+
+    /// Parses an amount such as "1_000_000 motes" or "2.5 CLX", where the symbol must be one of
+    /// `denominations`.  An amount without a symbol is taken to be in motes.
+    pub fn parse(input: &str, denominations: &[Denomination]) -> Result<Motes, ParseMotesError> {
+        let mut parts = input.split_whitespace();
+        let amount = parts
+            .next()
+            .ok_or_else(|| ParseMotesError::InvalidAmount(input.to_string()))?;
+        let denomination = match (parts.next(), parts.next()) {
+            (None, _) => Denomination::MOTES,
+            (Some(symbol), None) => *denominations
+                .iter()
+                .find(|denomination| denomination.symbol.eq_ignore_ascii_case(symbol))
+                .ok_or_else(|| ParseMotesError::UnknownDenomination(symbol.to_string()))?,
+            (Some(_), Some(_)) => return Err(ParseMotesError::InvalidAmount(input.to_string())),
+        };
+
+        let invalid_amount = || ParseMotesError::InvalidAmount(amount.to_string());
+        let digits: String = amount.chars().filter(|c| *c != '_').collect();
+        let (whole, fraction) = match digits.find('.') {
+            Some(index) => (&digits[..index], &digits[index + 1..]),
+            None => (digits.as_str(), ""),
+        };
+        if whole.is_empty() || (digits.contains('.') && fraction.is_empty()) {
+            return Err(invalid_amount());
+        }
+        if fraction.len() > denomination.decimals as usize {
+            return Err(ParseMotesError::TooManyDecimals {
+                max: denomination.decimals,
+                actual: fraction.len(),
+            });
+        }
+
+        let padded_fraction = format!(
+            "{:0<width$}",
+            fraction,
+            width = denomination.decimals as usize
+        );
+        let value =
+            format!("{}{}", whole, padded_fraction)
+                .chars()
+                .try_fold(U512::zero(), |acc, c| {
+                    let digit = c.to_digit(10).ok_or_else(invalid_amount)?;
+                    acc.checked_mul(U512::from(10))
+                        .and_then(|acc| acc.checked_add(U512::from(digit)))
+                        .ok_or(ParseMotesError::Overflow)
+                })?;
+        Ok(Motes::new(value))
+    }
+}
+
+impl From<u64> for Motes {
+    fn from(value: u64) -> Self {
+        Motes::new(U512::from(value))
+    }
+}
+
+impl From<U512> for Motes {
+    fn from(value: U512) -> Self {
+        Motes::new(value)
+    }
 }
 
 impl fmt::Display for Motes {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self.0)
+        write!(f, "{} {}", self.0, Denomination::MOTES.symbol)
+    }
+}
+
+impl FromStr for Motes {
+    type Err = ParseMotesError;
+
+    /// Parses an amount in motes or CLX; see [`Motes::parse`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Motes::parse(s, &[Denomination::MOTES, Denomination::CLX])
     }
 }
 
@@ -88,8 +265,9 @@ impl Zero for Motes {
 #[cfg(test)]
 mod tests {
     use crate::gas::Gas;
-    use crate::motes::Motes;
+    use crate::motes::{Denomination, Motes, ParseMotesError};
     use contract_ffi::value::U512;
+    use num::Zero;
 
     #[test]
     fn should_be_able_to_get_instance_of_motes() {
@@ -178,6 +356,117 @@ mod tests {
         assert_eq!(motes, expected_motes, "should be equal")
     }
 
+    #[test]
+    fn should_support_checked_arithmetic() {
+        let one = Motes::from(1);
+        let two = Motes::from(2);
+        assert_eq!(two.checked_sub(one), Some(one));
+        assert_eq!(
+            one.checked_sub(two),
+            None,
+            "should be none due to underflow"
+        );
+        assert_eq!(Motes::from(U512::MAX).checked_mul(two), None);
+        assert_eq!(two.checked_mul(two), Some(Motes::from(4)));
+        assert_eq!(two.checked_div(Motes::zero()), None);
+        assert_eq!(two.checked_div(two), Some(one));
+    }
+
+    #[test]
+    fn should_saturate_on_overflowing_add() {
+        let max = Motes::from(U512::MAX);
+        assert_eq!(max.saturating_add(Motes::from(1)), max);
+        assert_eq!(
+            Motes::from(1).saturating_add(Motes::from(1)),
+            Motes::from(2)
+        );
+    }
+
+    #[test]
+    fn should_convert_from_u64_and_u512() {
+        assert_eq!(Motes::from(10), Motes::new(U512::from(10)));
+        assert_eq!(Motes::from(U512::from(10)), Motes::from_u64(10));
+    }
+
+    #[test]
+    fn should_display_motes_with_symbol() {
+        assert_eq!(Motes::from(1_000_000).to_string(), "1000000 motes");
+        assert_eq!(Motes::zero().to_string(), "0 motes");
+    }
+
+    #[test]
+    fn should_format_as_denomination() {
+        let motes = Motes::from(2_500_000_000);
+        assert_eq!(motes.format_as(Denomination::MOTES), "2500000000 motes");
+        assert_eq!(motes.format_as(Denomination::CLX), "2.5 CLX");
+        assert_eq!(
+            Motes::from(3_000_000_000).format_as(Denomination::CLX),
+            "3 CLX"
+        );
+        assert_eq!(
+            Motes::from(1).format_as(Denomination::CLX),
+            "0.000000001 CLX"
+        );
+
+        let custom = Denomination::new("CLX", 2);
+        assert_eq!(Motes::from(250).format_as(custom), "2.5 CLX");
+    }
+
+    #[test]
+    fn should_parse_denominations() {
+        assert_eq!("1_000_000 motes".parse(), Ok(Motes::from(1_000_000)));
+        assert_eq!("1000000".parse(), Ok(Motes::from(1_000_000)));
+        assert_eq!("2.5 CLX".parse(), Ok(Motes::from(2_500_000_000)));
+        assert_eq!("2.5 clx".parse(), Ok(Motes::from(2_500_000_000)));
+        assert_eq!(
+            Motes::parse("2.5 CLX", &[Denomination::new("CLX", 2)]),
+            Ok(Motes::from(250))
+        );
+    }
+
+    #[test]
+    fn should_round_trip_formatted_motes() {
+        for value in &[0, 1, 999_999_999, 1_000_000_000, 1_234_567_890_123] {
+            let motes = Motes::from(*value);
+            for denomination in &[Denomination::MOTES, Denomination::CLX] {
+                let formatted = motes.format_as(*denomination);
+                assert_eq!(formatted.parse(), Ok(motes), "{}", formatted);
+            }
+        }
+        let max = Motes::from(U512::MAX);
+        assert_eq!(max.to_string().parse(), Ok(max));
+    }
+
+    #[test]
+    fn should_reject_invalid_motes_strings() {
+        assert_eq!(
+            "".parse::<Motes>(),
+            Err(ParseMotesError::InvalidAmount("".to_string()))
+        );
+        assert_eq!(
+            "1.5 motes".parse::<Motes>(),
+            Err(ParseMotesError::TooManyDecimals { max: 0, actual: 1 })
+        );
+        assert_eq!(
+            "0.0000000001 CLX".parse::<Motes>(),
+            Err(ParseMotesError::TooManyDecimals { max: 9, actual: 10 })
+        );
+        assert_eq!(
+            "5 ETH".parse::<Motes>(),
+            Err(ParseMotesError::UnknownDenomination("ETH".to_string()))
+        );
+        assert_eq!(
+            "-5 motes".parse::<Motes>(),
+            Err(ParseMotesError::InvalidAmount("-5".to_string()))
+        );
+        assert_eq!(
+            "1. CLX".parse::<Motes>(),
+            Err(ParseMotesError::InvalidAmount("1.".to_string()))
+        );
+        let too_big = format!("{}0", U512::MAX);
+        assert_eq!(too_big.parse::<Motes>(), Err(ParseMotesError::Overflow));
+    }
+
     #[test]
     fn should_be_able_to_default() {
         let motes = Motes::default();
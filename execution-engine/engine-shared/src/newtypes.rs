@@ -3,6 +3,7 @@ use core::array::TryFromSliceError;
 use std::convert::TryFrom;
 use std::fmt;
 use std::ops::Deref;
+use std::str::FromStr;
 
 use blake2::digest::{Input, VariableOutput};
 use blake2::VarBlake2b;
@@ -20,12 +21,9 @@ pub struct Blake2bHash([u8; BLAKE2B_DIGEST_LENGTH]);
 impl Blake2bHash {
     /// Creates a 32-byte BLAKE2b hash digest from a given a piece of data
     pub fn new(data: &[u8]) -> Self {
-        let mut ret = [0u8; BLAKE2B_DIGEST_LENGTH];
-        // Safe to unwrap here because our digest length is constant and valid
-        let mut hasher = VarBlake2b::new(BLAKE2B_DIGEST_LENGTH).unwrap();
-        hasher.input(data);
-        hasher.variable_result(|hash| ret.clone_from_slice(hash));
-        Blake2bHash(ret)
+        let mut hasher = Blake2bHasher::new();
+        hasher.update(data);
+        hasher.finalize()
     }
 
     /// Converts the underlying BLAKE2b hash digest array to a `Vec`
@@ -34,6 +32,35 @@ impl Blake2bHash {
     }
 }
 
+/// Computes a [`Blake2bHash`] of data which is supplied in pieces, so that it needn't be
+/// assembled into a single buffer first
+pub struct Blake2bHasher(VarBlake2b);
+
+impl Blake2bHasher {
+    pub fn new() -> Self {
+        // Safe to unwrap here because our digest length is constant and valid
+        Blake2bHasher(VarBlake2b::new(BLAKE2B_DIGEST_LENGTH).unwrap())
+    }
+
+    /// Feeds the next piece of data to the hasher
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.input(data);
+    }
+
+    /// Returns the digest of all the data fed to the hasher
+    pub fn finalize(self) -> Blake2bHash {
+        let mut ret = [0u8; BLAKE2B_DIGEST_LENGTH];
+        self.0.variable_result(|hash| ret.clone_from_slice(hash));
+        Blake2bHash(ret)
+    }
+}
+
+impl Default for Blake2bHasher {
+    fn default() -> Self {
+        Blake2bHasher::new()
+    }
+}
+
 impl core::fmt::LowerHex for Blake2bHash {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         let hex_string = base16::encode_lower(&self.to_vec());
@@ -149,6 +176,16 @@ impl CorrelationId {
     }
 }
 
+impl FromStr for CorrelationId {
+    type Err = uuid::parser::ParseError;
+
+    /// Parses a correlation ID passed by a caller, in the hyphenated UUID format it is displayed
+    /// in.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s).map(CorrelationId)
+    }
+}
+
 impl fmt::Display for CorrelationId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self.0)
@@ -157,7 +194,7 @@ impl fmt::Display for CorrelationId {
 
 #[cfg(test)]
 mod tests {
-    use crate::newtypes::{Blake2bHash, CorrelationId};
+    use crate::newtypes::{Blake2bHash, Blake2bHasher, CorrelationId};
     use crate::utils;
     use std::hash::{Hash, Hasher};
 
@@ -196,6 +233,18 @@ mod tests {
         )
     }
 
+    #[test]
+    fn should_parse_displayed_correlation_id() {
+        let correlation_id = CorrelationId::new();
+
+        let parsed: CorrelationId = correlation_id
+            .to_string()
+            .parse()
+            .expect("should parse correlation id");
+        assert_eq!(parsed, correlation_id);
+        assert!("not-a-uuid".parse::<CorrelationId>().is_err());
+    }
+
     #[test]
     fn should_support_to_json() {
         let correlation_id = CorrelationId::new();
@@ -308,4 +357,57 @@ mod tests {
             "0x0000000000000000000000000000000000000000000000000000000000000000"
         )
     }
+
+    #[test]
+    fn hasher_should_match_known_digest() {
+        let hasher = Blake2bHasher::new();
+        assert_eq!(
+            format!("{:x}", hasher.finalize()),
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+    }
+
+    mod proptests {
+        use blake2::digest::{Input, VariableOutput};
+        use blake2::VarBlake2b;
+        use proptest::collection::vec;
+        use proptest::prelude::*;
+
+        use crate::newtypes::{Blake2bHash, Blake2bHasher, BLAKE2B_DIGEST_LENGTH};
+
+        /// The digest as computed before the introduction of `Blake2bHasher`.
+        fn one_shot_digest(data: &[u8]) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+            let mut ret = [0u8; BLAKE2B_DIGEST_LENGTH];
+            let mut hasher = VarBlake2b::new(BLAKE2B_DIGEST_LENGTH).unwrap();
+            hasher.input(data);
+            hasher.variable_result(|hash| ret.clone_from_slice(hash));
+            ret
+        }
+
+        proptest! {
+            #[test]
+            fn new_should_match_one_shot_digest(data in vec(any::<u8>(), 0..1000)) {
+                assert_eq!(Blake2bHash::new(&data).0, one_shot_digest(&data));
+            }
+
+            #[test]
+            fn split_updates_should_match_one_shot_digest(
+                data in vec(any::<u8>(), 0..1000),
+                splits in vec(any::<usize>(), 0..10),
+            ) {
+                let mut splits: Vec<usize> = splits
+                    .into_iter()
+                    .map(|split| split % (data.len() + 1))
+                    .collect();
+                splits.sort();
+                let mut hasher = Blake2bHasher::new();
+                let mut start = 0;
+                for split in splits.into_iter().chain(std::iter::once(data.len())) {
+                    hasher.update(&data[start..split]);
+                    start = split;
+                }
+                assert_eq!(hasher.finalize().0, one_shot_digest(&data));
+            }
+        }
+    }
 }
@@ -0,0 +1,170 @@
+#[macro_use]
+extern crate criterion;
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use criterion::measurement::WallTime;
+use criterion::{BatchSize, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
+use lmdb::DatabaseFlags;
+use tempfile::TempDir;
+
+use casperlabs_engine_storage::global_state::in_memory::InMemoryGlobalState;
+use casperlabs_engine_storage::global_state::lmdb::LmdbGlobalState;
+use casperlabs_engine_storage::global_state::{CommitResult, StateProvider, StateReader};
+use casperlabs_engine_storage::protocol_data_store::lmdb::LmdbProtocolDataStore;
+use casperlabs_engine_storage::storage_config::StorageConfig;
+use casperlabs_engine_storage::transaction_source::lmdb::LmdbEnvironment;
+use casperlabs_engine_storage::trie_store::lmdb::LmdbTrieStore;
+use contract_ffi::key::Key;
+use contract_ffi::value::Value;
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use engine_shared::transform::Transform;
+
+/// Number of keys written before measuring, so commits and reads traverse a deep trie.
+const PREPOPULATED_KEYS: u64 = 10_000;
+/// Number of keys committed at once while prepopulating.
+const PREPOPULATE_BATCH_SIZE: u64 = 1_000;
+const COMMIT_BATCH_SIZES: [u64; 3] = [10, 100, 1_000];
+const READS_PER_ITERATION: u64 = 100;
+/// Stride between the prepopulated keys read, coprime to their number so all of them get read.
+const READ_STRIDE: u64 = 7_919;
+
+/// Returns the `index`th key, spread over the trie like the hashes most keys are.
+fn key_at(index: u64) -> Key {
+    Key::Hash(Blake2bHash::new(&index.to_le_bytes()).into())
+}
+
+fn writes(indices: impl Iterator<Item = u64>) -> HashMap<Key, Transform> {
+    indices
+        .map(|index| (key_at(index), Transform::Write(Value::UInt64(index))))
+        .collect()
+}
+
+fn commit<S>(state: &S, root: Blake2bHash, effects: HashMap<Key, Transform>) -> Blake2bHash
+where
+    S: StateProvider,
+    S::Error: Debug,
+{
+    match state
+        .commit(CorrelationId::new(), root, effects)
+        .expect("should commit")
+    {
+        CommitResult::Success(post_state_hash) => post_state_hash,
+        commit_result => panic!("commit should succeed, got: {:?}", commit_result),
+    }
+}
+
+/// Writes [`PREPOPULATED_KEYS`] keys on top of `empty_root` and returns the resulting root.
+fn prepopulate<S>(state: &S, empty_root: Blake2bHash) -> Blake2bHash
+where
+    S: StateProvider,
+    S::Error: Debug,
+{
+    (0..PREPOPULATED_KEYS)
+        .step_by(PREPOPULATE_BATCH_SIZE as usize)
+        .fold(empty_root, |root, start| {
+            let end = (start + PREPOPULATE_BATCH_SIZE).min(PREPOPULATED_KEYS);
+            commit(state, root, writes(start..end))
+        })
+}
+
+fn bench_commit<S>(
+    group: &mut BenchmarkGroup<WallTime>,
+    backend: &str,
+    state: &S,
+    root: Blake2bHash,
+) where
+    S: StateProvider,
+    S::Error: Debug,
+{
+    // Keys not written yet, so each measured commit adds new leaves instead of rewriting them
+    let mut next_index = PREPOPULATED_KEYS;
+
+    for &batch_size in COMMIT_BATCH_SIZES.iter() {
+        group.throughput(Throughput::Elements(batch_size));
+        group.bench_with_input(
+            BenchmarkId::new(backend, batch_size),
+            &batch_size,
+            |b, &batch_size| {
+                b.iter_batched(
+                    || {
+                        let effects = writes(next_index..next_index + batch_size);
+                        next_index += batch_size;
+                        effects
+                    },
+                    |effects| commit(state, root, effects),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+}
+
+fn bench_read<S>(group: &mut BenchmarkGroup<WallTime>, backend: &str, state: &S, root: Blake2bHash)
+where
+    S: StateProvider,
+    S::Error: Debug,
+{
+    let keys: Vec<Key> = (0..PREPOPULATED_KEYS).map(key_at).collect();
+    let reader = state
+        .checkout(root)
+        .expect("should checkout")
+        .expect("root should exist");
+    let correlation_id = CorrelationId::new();
+    let mut position = 0;
+
+    group.throughput(Throughput::Elements(READS_PER_ITERATION));
+    group.bench_function(backend, |b| {
+        b.iter(|| {
+            for _ in 0..READS_PER_ITERATION {
+                position = (position + READ_STRIDE) % PREPOPULATED_KEYS;
+                reader
+                    .read(correlation_id, &keys[position as usize])
+                    .expect("should read")
+                    .expect("key should exist");
+            }
+        })
+    });
+}
+
+fn new_lmdb_state(data_dir: &TempDir) -> LmdbGlobalState {
+    let environment = Arc::new(
+        LmdbEnvironment::with_config(&data_dir.path().to_path_buf(), StorageConfig::new())
+            .expect("should create LmdbEnvironment"),
+    );
+    let trie_store = Arc::new(
+        LmdbTrieStore::new(&environment, None, DatabaseFlags::empty())
+            .expect("should create LmdbTrieStore"),
+    );
+    let protocol_data_store = Arc::new(
+        LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty())
+            .expect("should create LmdbProtocolDataStore"),
+    );
+    LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
+        .expect("should create LmdbGlobalState")
+}
+
+pub fn global_state_bench(c: &mut Criterion) {
+    let in_memory_state = InMemoryGlobalState::empty().expect("should create global state");
+    let in_memory_root = prepopulate(&in_memory_state, in_memory_state.empty_root_hash);
+
+    let data_dir = TempDir::new().expect("should create temp dir");
+    let lmdb_state = new_lmdb_state(&data_dir);
+    let lmdb_root = prepopulate(&lmdb_state, lmdb_state.empty_root_hash);
+
+    let mut group = c.benchmark_group("trie_commit");
+    group.sample_size(10);
+    bench_commit(&mut group, "in_memory", &in_memory_state, in_memory_root);
+    bench_commit(&mut group, "lmdb", &lmdb_state, lmdb_root);
+    group.finish();
+
+    let mut group = c.benchmark_group("trie_read");
+    bench_read(&mut group, "in_memory", &in_memory_state, in_memory_root);
+    bench_read(&mut group, "lmdb", &lmdb_state, lmdb_root);
+    group.finish();
+}
+
+criterion_group!(benches, global_state_bench);
+criterion_main!(benches);
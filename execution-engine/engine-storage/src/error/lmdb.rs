@@ -6,13 +6,16 @@ use wasmi;
 
 use contract_ffi::bytesrepr;
 
-use super::in_memory;
+use super::{in_memory, rocksdb};
 
 #[derive(Debug, Clone, Fail, PartialEq, Eq)]
 pub enum Error {
     #[fail(display = "{}", _0)]
     Lmdb(#[fail(cause)] lmdb_external::Error),
 
+    #[fail(display = "{}", _0)]
+    RocksDb(#[fail(cause)] rocksdb::Error),
+
     #[fail(display = "{}", _0)]
     BytesRepr(#[fail(cause)] bytesrepr::Error),
 
@@ -48,3 +51,12 @@ impl From<in_memory::Error> for Error {
         }
     }
 }
+
+impl From<rocksdb::Error> for Error {
+    fn from(error: rocksdb::Error) -> Self {
+        match error {
+            rocksdb::Error::PoisonError => Error::PoisonError,
+            error => Error::RocksDb(error),
+        }
+    }
+}
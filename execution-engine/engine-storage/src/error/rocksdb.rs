@@ -0,0 +1,28 @@
+use std::sync;
+
+use failure::Fail;
+use rocksdb as rocksdb_external;
+
+#[derive(Debug, Clone, Fail, PartialEq, Eq)]
+pub enum Error {
+    #[fail(display = "{}", _0)]
+    RocksDb(String),
+
+    #[fail(display = "Column family not found: {}", _0)]
+    ColumnFamilyNotFound(String),
+
+    #[fail(display = "Another thread panicked while holding a lock")]
+    PoisonError,
+}
+
+impl From<rocksdb_external::Error> for Error {
+    fn from(error: rocksdb_external::Error) -> Self {
+        Error::RocksDb(error.into_string())
+    }
+}
+
+impl<T> From<sync::PoisonError<T>> for Error {
+    fn from(_error: sync::PoisonError<T>) -> Self {
+        Error::PoisonError
+    }
+}
@@ -0,0 +1,97 @@
+//! The addressing of the global state entries recording executed deploys, which reject replays.
+//!
+//! The entries are kept in three slots, one per window of block time.  The slots of the current
+//! and the previous window are looked up, and the third one is purged whenever effects recording
+//! a deploy executed in a new window are committed.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use contract_ffi::bytesrepr::{self, ToBytes};
+use contract_ffi::key::{Key, HASH_SIZE};
+use contract_ffi::value::account::BlockTime;
+use contract_ffi::value::Value;
+use engine_shared::newtypes::Blake2bHash;
+use engine_shared::transform::Transform;
+
+/// Prefix hashed together with a deploy hash to derive the address of the global state entry
+/// recording that the deploy has been executed.  Hashed on its own, it gives the tag the addresses
+/// of all such entries start with.
+const EXECUTED_DEPLOY_PREFIX: &[u8] = b"executed_deploy";
+
+/// The length of the tag the addresses of the entries recording executed deploys start with.
+const EXECUTED_DEPLOY_TAG_LENGTH: usize = 8;
+
+/// The number of slots the entries recording executed deploys are kept in.
+const EXECUTED_DEPLOY_SLOTS: u64 = 3;
+
+/// The length in milliseconds of the windows of block time the executed deploys are recorded in.
+/// It exceeds the longest time to live a deploy can have, so a deploy has expired by the time the
+/// record of its execution is purged.
+pub const EXECUTED_DEPLOY_WINDOW_MILLIS: u64 = 1 << 32;
+
+/// Returns the address prefix of the entries recording the deploys executed in the given window
+/// of block time, or in any window sharing its slot.
+fn slot_prefix(window: u64) -> [u8; EXECUTED_DEPLOY_TAG_LENGTH + 1] {
+    let tag: [u8; HASH_SIZE] = Blake2bHash::new(EXECUTED_DEPLOY_PREFIX).into();
+    let mut ret = [0u8; EXECUTED_DEPLOY_TAG_LENGTH + 1];
+    ret[..EXECUTED_DEPLOY_TAG_LENGTH].copy_from_slice(&tag[..EXECUTED_DEPLOY_TAG_LENGTH]);
+    ret[EXECUTED_DEPLOY_TAG_LENGTH] = (window % EXECUTED_DEPLOY_SLOTS) as u8;
+    ret
+}
+
+/// Returns the key of the global state entry recording that the deploy with the given hash has
+/// been executed in a block with the given block time.
+pub fn executed_deploy_key(blocktime: BlockTime, deploy_hash: [u8; 32]) -> Key {
+    let slot_prefix = slot_prefix(blocktime.0 / EXECUTED_DEPLOY_WINDOW_MILLIS);
+    let mut data = EXECUTED_DEPLOY_PREFIX.to_vec();
+    data.extend_from_slice(&deploy_hash);
+    let hash: [u8; HASH_SIZE] = Blake2bHash::new(&data).into();
+    let mut addr = [0u8; HASH_SIZE];
+    addr[..slot_prefix.len()].copy_from_slice(&slot_prefix);
+    addr[slot_prefix.len()..].copy_from_slice(&hash[..HASH_SIZE - slot_prefix.len()]);
+    Key::Hash(addr)
+}
+
+/// Returns the value of the entry recording a deploy executed in a block with the given block
+/// time.
+pub fn executed_deploy_value(blocktime: BlockTime) -> Value {
+    Value::UInt64(blocktime.0)
+}
+
+/// Returns the window of block time the deploy whose execution is recorded by writing `transform`
+/// to `key` was executed in, or `None` if the write records no such thing.
+fn executed_deploy_window(key: &Key, transform: &Transform) -> Option<u64> {
+    match (key, transform) {
+        (Key::Hash(addr), Transform::Write(Value::UInt64(blocktime))) => {
+            let window = blocktime / EXECUTED_DEPLOY_WINDOW_MILLIS;
+            if addr.starts_with(&slot_prefix(window)) {
+                Some(window)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the latest window of block time `effects` record a deploy executed in, if any.
+pub fn latest_window<H: BuildHasher>(effects: &HashMap<Key, Transform, H>) -> Option<u64> {
+    effects
+        .iter()
+        .filter_map(|(key, transform)| executed_deploy_window(key, transform))
+        .max()
+}
+
+/// Returns the prefix of the serialized keys of the entries to purge once a deploy executes in
+/// the given window of block time: those of the deploys executed two or more windows earlier.
+/// Those deploys have expired, so the entries are no longer needed to reject their replays.
+pub fn stale_key_prefix(window: u64) -> Result<Vec<u8>, bytesrepr::Error> {
+    // The slot following the current window's is the one of the window before the previous one.
+    let stale_slot_prefix = slot_prefix(window + 1);
+    let mut addr = [0u8; HASH_SIZE];
+    addr[..stale_slot_prefix.len()].copy_from_slice(&stale_slot_prefix);
+    let mut ret = Key::Hash(addr).to_bytes()?;
+    ret.truncate(ret.len() - (HASH_SIZE - stale_slot_prefix.len()));
+    Ok(ret)
+}
@@ -8,8 +8,12 @@ use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 use engine_shared::transform::Transform;
 
 use crate::error::{self, in_memory};
-use crate::global_state::StateReader;
-use crate::global_state::{commit, CommitResult, StateProvider};
+use crate::global_state::{
+    commit, commit_batch, commit_with_protocol_data, diff, get_trie_chunk, missing_trie_keys,
+    prune, put_trie, verify_integrity, CommitBatchResult, CommitResult, DiffResult,
+    IntegrityReport, PruneResult, StateProvider, TrieChunkResult,
+};
+use crate::global_state::{StateProofReader, StateReader};
 use crate::protocol_data::ProtocolData;
 use crate::protocol_data_store::in_memory::InMemoryProtocolDataStore;
 use crate::protocol_data_store::ProtocolVersion;
@@ -17,10 +21,10 @@ use crate::store::Store;
 use crate::transaction_source::in_memory::{InMemoryEnvironment, InMemoryReadTransaction};
 use crate::transaction_source::{Transaction, TransactionSource};
 use crate::trie::operations::create_hashed_empty_trie;
-use crate::trie::Trie;
+use crate::trie::{Trie, TrieMerkleProof};
 use crate::trie_store::in_memory::InMemoryTrieStore;
 use crate::trie_store::operations;
-use crate::trie_store::operations::{read, ReadResult, WriteResult};
+use crate::trie_store::operations::{read, read_with_proof, ReadResult, WriteResult};
 
 pub struct InMemoryGlobalState {
     pub environment: Arc<InMemoryEnvironment>,
@@ -127,6 +131,29 @@ impl StateReader<Key, Value> for InMemoryGlobalStateView {
     }
 }
 
+impl StateProofReader<Key, Value> for InMemoryGlobalStateView {
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, Value>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<Key, Value, _, _, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
 impl StateProvider for InMemoryGlobalState {
     type Error = error::Error;
 
@@ -160,6 +187,39 @@ impl StateProvider for InMemoryGlobalState {
         Ok(commit_result)
     }
 
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, HashMap<Key, Transform>)>,
+    ) -> Result<CommitBatchResult, Self::Error> {
+        commit_batch::<InMemoryEnvironment, InMemoryTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            batch,
+        )
+    }
+
+    fn commit_with_protocol_data(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: HashMap<Key, Transform>,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<CommitResult, Self::Error> {
+        commit_with_protocol_data::<InMemoryEnvironment, InMemoryTrieStore, _, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            &*self.protocol_data_store,
+            correlation_id,
+            prestate_hash,
+            effects,
+            protocol_version,
+            protocol_data,
+        )
+    }
+
     fn put_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -184,6 +244,96 @@ impl StateProvider for InMemoryGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retain_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error> {
+        let retain_roots = [retain_roots, &[self.empty_root_hash]].concat();
+        let prune_result = prune::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            &retain_roots,
+        )?;
+        Ok(prune_result)
+    }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        poststate_hash: Blake2bHash,
+    ) -> Result<DiffResult, Self::Error> {
+        let diff_result = diff::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            poststate_hash,
+        )?;
+        Ok(diff_result)
+    }
+
+    fn get_trie_chunk(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        cursor: &[Blake2bHash],
+    ) -> Result<TrieChunkResult, Self::Error> {
+        let trie_chunk_result =
+            get_trie_chunk::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                root_hash,
+                cursor,
+            )?;
+        Ok(trie_chunk_result)
+    }
+
+    fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_bytes: &[u8],
+    ) -> Result<Blake2bHash, Self::Error> {
+        let trie_hash = put_trie::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            trie_bytes,
+        )?;
+        Ok(trie_hash)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let missing_trie_keys =
+            missing_trie_keys::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                root_hash,
+            )?;
+        Ok(missing_trie_keys)
+    }
+
+    fn verify_integrity(
+        &self,
+        correlation_id: CorrelationId,
+        root_hashes: &[Blake2bHash],
+    ) -> Result<IntegrityReport, Self::Error> {
+        verify_integrity::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            root_hashes,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -328,6 +478,115 @@ mod tests {
         );
     }
 
+    #[test]
+    fn commit_with_protocol_data_stores_protocol_data_only_on_success() {
+        use contract_ffi::system_contracts::pos::RefundRatio;
+        use engine_shared::key_limits::KeyLimits;
+        use engine_wasm_prep::wasm_costs::WasmCosts;
+
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let protocol_data = ProtocolData::new(
+            WasmCosts::default(),
+            RefundRatio::default(),
+            1,
+            10_000,
+            KeyLimits::default(),
+        );
+        let effects = || -> HashMap<Key, Transform> {
+            create_test_pairs_updated()
+                .iter()
+                .cloned()
+                .map(|TestPair { key, value }| (key, Transform::Write(value)))
+                .collect()
+        };
+
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        match state
+            .commit_with_protocol_data(correlation_id, fake_hash, effects(), 1, &protocol_data)
+            .unwrap()
+        {
+            CommitResult::RootNotFound => (),
+            _ => panic!("commit should have failed"),
+        }
+        assert_eq!(None, state.get_protocol_data(1).unwrap());
+
+        match state
+            .commit_with_protocol_data(correlation_id, root_hash, effects(), 2, &protocol_data)
+            .unwrap()
+        {
+            CommitResult::Success(_) => (),
+            _ => panic!("commit failed"),
+        }
+        assert_eq!(Some(protocol_data), state.get_protocol_data(2).unwrap());
+    }
+
+    #[test]
+    fn commit_batch_purges_stale_executed_deploy_records() {
+        use crate::global_state::executed_deploys::{
+            executed_deploy_key, executed_deploy_value, EXECUTED_DEPLOY_WINDOW_MILLIS,
+        };
+        use contract_ffi::value::account::BlockTime;
+
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let record = |blocktime: BlockTime, deploy_hash: [u8; 32]| -> (Key, Transform) {
+            (
+                executed_deploy_key(blocktime, deploy_hash),
+                Transform::Write(executed_deploy_value(blocktime)),
+            )
+        };
+        let stale_record = record(BlockTime(0), [1u8; 32]);
+        let previous_record = record(BlockTime(EXECUTED_DEPLOY_WINDOW_MILLIS), [2u8; 32]);
+        let current_record = record(BlockTime(2 * EXECUTED_DEPLOY_WINDOW_MILLIS), [3u8; 32]);
+
+        let earlier_effects: HashMap<Key, Transform> =
+            vec![stale_record.clone(), previous_record.clone()]
+                .into_iter()
+                .collect();
+        let intermediate_hash = match state
+            .commit(correlation_id, root_hash, earlier_effects)
+            .unwrap()
+        {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+
+        let current_effects: HashMap<Key, Transform> =
+            vec![current_record.clone()].into_iter().collect();
+        let updated_hash = match state
+            .commit_batch(correlation_id, vec![(intermediate_hash, current_effects)])
+            .unwrap()
+        {
+            CommitBatchResult::Success(hashes) => hashes[0],
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        assert_eq!(
+            None,
+            updated_checkout
+                .read(correlation_id, &stale_record.0)
+                .unwrap()
+        );
+        for (key, transform) in vec![previous_record, current_record] {
+            let value = match transform {
+                Transform::Write(value) => value,
+                _ => panic!("records should be writes"),
+            };
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        for TestPair { key, value } in TEST_PAIRS.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
+
     #[test]
     fn initial_state_has_the_expected_hash() {
         let correlation_id = CorrelationId::new();
@@ -6,12 +6,17 @@ use lmdb;
 
 use contract_ffi::key::Key;
 use contract_ffi::value::Value;
+use engine_shared::logging::log_info;
 use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 use engine_shared::transform::Transform;
 
 use crate::error;
-use crate::global_state::StateReader;
-use crate::global_state::{commit, CommitResult, StateProvider};
+use crate::global_state::{
+    commit, commit_batch, commit_with_protocol_data, diff, get_trie_chunk, missing_trie_keys,
+    prune, put_trie, verify_integrity, CommitBatchResult, CommitResult, DiffResult,
+    IntegrityReport, PruneResult, StateProvider, TrieChunkResult,
+};
+use crate::global_state::{StateProofReader, StateReader};
 use crate::protocol_data::ProtocolData;
 use crate::protocol_data_store::lmdb::LmdbProtocolDataStore;
 use crate::protocol_data_store::ProtocolVersion;
@@ -19,21 +24,24 @@ use crate::store::Store;
 use crate::transaction_source::lmdb::LmdbEnvironment;
 use crate::transaction_source::{Transaction, TransactionSource};
 use crate::trie::operations::create_hashed_empty_trie;
-use crate::trie::Trie;
+use crate::trie::{Trie, TrieMerkleProof};
+use crate::trie_store::cache::{CachingTrieStore, TrieCache};
 use crate::trie_store::lmdb::LmdbTrieStore;
-use crate::trie_store::operations::{read, ReadResult};
+use crate::trie_store::operations::{read, read_with_proof, ReadResult};
 
 pub struct LmdbGlobalState {
     pub environment: Arc<LmdbEnvironment>,
     pub trie_store: Arc<LmdbTrieStore>,
     pub protocol_data_store: Arc<LmdbProtocolDataStore>,
     pub empty_root_hash: Blake2bHash,
+    pub trie_cache: Arc<TrieCache<Key, Value>>,
 }
 
 /// Represents a "view" of global state at a particular root hash.
 pub struct LmdbGlobalStateView {
     pub environment: Arc<LmdbEnvironment>,
     pub store: Arc<LmdbTrieStore>,
+    pub trie_cache: Arc<TrieCache<Key, Value>>,
     pub root_hash: Blake2bHash,
 }
 
@@ -59,7 +67,8 @@ impl LmdbGlobalState {
         ))
     }
 
-    /// Creates a state from an existing environment, store, and root_hash.
+    /// Creates a state from an existing environment, store, and root_hash.  Its trie cache is
+    /// sized according to the environment's config.
     /// Intended to be used for testing.
     pub(crate) fn new(
         environment: Arc<LmdbEnvironment>,
@@ -67,11 +76,33 @@ impl LmdbGlobalState {
         protocol_data_store: Arc<LmdbProtocolDataStore>,
         empty_root_hash: Blake2bHash,
     ) -> Self {
+        let trie_cache = Arc::new(TrieCache::new(environment.config().trie_cache_size()));
         LmdbGlobalState {
             environment,
             trie_store,
             protocol_data_store,
             empty_root_hash,
+            trie_cache,
+        }
+    }
+
+    /// Runs `write`, doubling the memory map and running it again each time it fails because the
+    /// map is full, provided the environment is configured to resize automatically.
+    fn with_map_growth<T, F>(&self, mut write: F) -> Result<T, error::Error>
+    where
+        F: FnMut() -> Result<T, error::Error>,
+    {
+        loop {
+            match write() {
+                Err(error::Error::Lmdb(lmdb::Error::MapFull))
+                    if self.environment.config().auto_resize() =>
+                {
+                    let map_size = self.environment.map_size() * 2;
+                    log_info(&format!("LMDB map full, resizing it to {} bytes", map_size));
+                    self.environment.set_map_size(map_size)?;
+                }
+                result => return result,
+            }
         }
     }
 }
@@ -81,10 +112,11 @@ impl StateReader<Key, Value> for LmdbGlobalStateView {
 
     fn read(&self, correlation_id: CorrelationId, key: &Key) -> Result<Option<Value>, Self::Error> {
         let txn = self.environment.create_read_txn()?;
-        let ret = match read::<Key, Value, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
+        let store = CachingTrieStore::new(self.store.deref(), self.trie_cache.deref());
+        let ret = match read::<Key, Value, _, _, Self::Error>(
             correlation_id,
             &txn,
-            self.store.deref(),
+            &store,
             &self.root_hash,
             key,
         )? {
@@ -97,6 +129,30 @@ impl StateReader<Key, Value> for LmdbGlobalStateView {
     }
 }
 
+impl StateProofReader<Key, Value> for LmdbGlobalStateView {
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, Value>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let store = CachingTrieStore::new(self.store.deref(), self.trie_cache.deref());
+        let ret = match read_with_proof::<Key, Value, _, _, Self::Error>(
+            correlation_id,
+            &txn,
+            &store,
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
 impl StateProvider for LmdbGlobalState {
     type Error = error::Error;
 
@@ -108,6 +164,7 @@ impl StateProvider for LmdbGlobalState {
         let maybe_state = maybe_root.map(|_| LmdbGlobalStateView {
             environment: Arc::clone(&self.environment),
             store: Arc::clone(&self.trie_store),
+            trie_cache: Arc::clone(&self.trie_cache),
             root_hash: state_hash,
         });
         txn.commit()?;
@@ -120,14 +177,72 @@ impl StateProvider for LmdbGlobalState {
         prestate_hash: Blake2bHash,
         effects: HashMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error> {
-        let commit_result = commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
-            &self.environment,
-            &self.trie_store,
-            correlation_id,
-            prestate_hash,
-            effects,
-        )?;
-        Ok(commit_result)
+        if !self.environment.config().auto_resize() {
+            return commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                prestate_hash,
+                effects,
+            );
+        }
+        // The effects are consumed by each attempt, so a copy is kept in case the map fills up.
+        self.with_map_growth(|| {
+            commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                prestate_hash,
+                effects.clone(),
+            )
+        })
+    }
+
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, HashMap<Key, Transform>)>,
+    ) -> Result<CommitBatchResult, Self::Error> {
+        if !self.environment.config().auto_resize() {
+            return commit_batch::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                batch,
+            );
+        }
+        // As in `commit`, each attempt consumes the batch.
+        self.with_map_growth(|| {
+            commit_batch::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                batch.clone(),
+            )
+        })
+    }
+
+    fn commit_with_protocol_data(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: HashMap<Key, Transform>,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<CommitResult, Self::Error> {
+        // As in `commit`, each attempt consumes the effects.
+        self.with_map_growth(|| {
+            commit_with_protocol_data::<LmdbEnvironment, LmdbTrieStore, _, _, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                &*self.protocol_data_store,
+                correlation_id,
+                prestate_hash,
+                effects.clone(),
+                protocol_version,
+                protocol_data,
+            )
+        })
     }
 
     fn put_protocol_data(
@@ -135,10 +250,12 @@ impl StateProvider for LmdbGlobalState {
         protocol_version: ProtocolVersion,
         protocol_data: &ProtocolData,
     ) -> Result<(), Self::Error> {
-        let mut txn = self.environment.create_read_write_txn()?;
-        self.protocol_data_store
-            .put(&mut txn, &protocol_version, protocol_data)?;
-        txn.commit().map_err(Into::into)
+        self.with_map_growth(|| {
+            let mut txn = self.environment.create_read_write_txn()?;
+            self.protocol_data_store
+                .put(&mut txn, &protocol_version, protocol_data)?;
+            txn.commit().map_err(Into::into)
+        })
     }
 
     fn get_protocol_data(
@@ -154,13 +271,110 @@ impl StateProvider for LmdbGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retain_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error> {
+        let retain_roots = [retain_roots, &[self.empty_root_hash]].concat();
+        let prune_result = self.with_map_growth(|| {
+            prune::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                &retain_roots,
+            )
+        });
+        // Pruned trie values mustn't outlive their deletion in the cache.
+        self.trie_cache.clear();
+        prune_result
+    }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        poststate_hash: Blake2bHash,
+    ) -> Result<DiffResult, Self::Error> {
+        let diff_result = diff::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            poststate_hash,
+        )?;
+        Ok(diff_result)
+    }
+
+    fn get_trie_chunk(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        cursor: &[Blake2bHash],
+    ) -> Result<TrieChunkResult, Self::Error> {
+        let trie_chunk_result = get_trie_chunk::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            root_hash,
+            cursor,
+        )?;
+        Ok(trie_chunk_result)
+    }
+
+    fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_bytes: &[u8],
+    ) -> Result<Blake2bHash, Self::Error> {
+        self.with_map_growth(|| {
+            put_trie::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                trie_bytes,
+            )
+        })
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let missing_trie_keys = missing_trie_keys::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            root_hash,
+        )?;
+        Ok(missing_trie_keys)
+    }
+
+    fn verify_integrity(
+        &self,
+        correlation_id: CorrelationId,
+        root_hashes: &[Blake2bHash],
+    ) -> Result<IntegrityReport, Self::Error> {
+        verify_integrity::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            root_hashes,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use lmdb::DatabaseFlags;
-    use tempfile::tempdir;
+    use tempfile::{tempdir, TempDir};
 
+    use crate::global_state::in_memory::InMemoryGlobalState;
+    use crate::storage_config::StorageConfig;
     use crate::trie_store::operations::{write, WriteResult};
     use crate::TEST_MAP_SIZE;
 
@@ -240,6 +454,36 @@ mod tests {
         (ret, current_root)
     }
 
+    fn create_empty_state(storage_config: StorageConfig) -> (TempDir, LmdbGlobalState) {
+        let temp_dir = tempdir().unwrap();
+        let environment = Arc::new(
+            LmdbEnvironment::with_config(&temp_dir.path().to_path_buf(), storage_config).unwrap(),
+        );
+        let trie_store =
+            Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        let protocol_data_store = Arc::new(
+            LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        (temp_dir, state)
+    }
+
+    /// Returns a storage configuration whose map is too small for [`create_large_effects`].
+    fn small_map_storage_config() -> StorageConfig {
+        let page_size = engine_shared::os::get_page_size().unwrap();
+        StorageConfig::new().set_map_size(page_size * 16)
+    }
+
+    fn create_large_effects() -> HashMap<Key, Transform> {
+        let page_size = engine_shared::os::get_page_size().unwrap();
+        let mut effects = HashMap::new();
+        effects.insert(
+            Key::Hash([3u8; 32]),
+            Transform::Write(Value::ByteArray(vec![7u8; page_size * 64])),
+        );
+        effects
+    }
+
     #[test]
     fn reads_from_a_checkout_return_expected_values() {
         let correlation_id = CorrelationId::new();
@@ -330,4 +574,415 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn commit_batch_chains_commits() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+        let (other_state, other_root_hash) = create_test_state();
+
+        let first_effects: HashMap<Key, Transform> = {
+            let mut tmp = HashMap::new();
+            tmp.insert(
+                test_pairs_updated[0].key,
+                Transform::Write(test_pairs_updated[0].value.to_owned()),
+            );
+            tmp
+        };
+        let second_effects: HashMap<Key, Transform> = {
+            let mut tmp = HashMap::new();
+            for TestPair { key, value } in &test_pairs_updated[1..] {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+
+        let intermediate_hash = match other_state
+            .commit(correlation_id, other_root_hash, first_effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+        let updated_hash = match other_state
+            .commit(correlation_id, intermediate_hash, second_effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+
+        let batch = vec![
+            (root_hash, first_effects),
+            (intermediate_hash, second_effects),
+        ];
+        match state.commit_batch(correlation_id, batch).unwrap() {
+            CommitBatchResult::Success(hashes) => {
+                assert_eq!(hashes, vec![intermediate_hash, updated_hash])
+            }
+            result => panic!("commit batch failed: {}", result),
+        }
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn commit_batch_commits_nothing_on_failure() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: HashMap<Key, Transform> = {
+            let mut tmp = HashMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        let batch = vec![(root_hash, effects.clone()), (fake_hash, effects.clone())];
+        match state.commit_batch(correlation_id, batch).unwrap() {
+            CommitBatchResult::Failure {
+                index: 1,
+                result: CommitResult::RootNotFound,
+            } => (),
+            result => panic!("unexpected result: {}", result),
+        }
+
+        let (other_state, other_root_hash) = create_test_state();
+        let updated_hash = match other_state
+            .commit(correlation_id, other_root_hash, effects)
+            .unwrap()
+        {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+        assert!(state.checkout(updated_hash).unwrap().is_none());
+    }
+
+    #[test]
+    fn verify_integrity_reports_dangling_roots() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        let report = state
+            .verify_integrity(correlation_id, &[root_hash])
+            .unwrap();
+        assert!(report.is_ok());
+        assert!(report.checked > 0);
+
+        let report = state
+            .verify_integrity(correlation_id, &[root_hash, fake_hash])
+            .unwrap();
+        assert!(report.corrupt.is_empty());
+        assert_eq!(report.dangling, vec![fake_hash]);
+    }
+
+    #[test]
+    fn prune_removes_unreachable_tries() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: HashMap<Key, Transform> = {
+            let mut tmp = HashMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+
+        match state.prune(correlation_id, &[updated_hash]).unwrap() {
+            PruneResult::Success(pruned) => assert!(pruned > 0),
+            _ => panic!("prune failed"),
+        }
+
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        assert!(state.checkout(state.empty_root_hash).unwrap().is_some());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn reads_from_a_checkout_fill_the_trie_cache() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        assert!(state.trie_cache.is_empty());
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in TEST_PAIRS.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+        let cached = state.trie_cache.len();
+        assert!(cached > 0);
+
+        // Another checkout of the same root reads the same trie values, all from the cache.
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in TEST_PAIRS.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+        assert_eq!(state.trie_cache.len(), cached);
+    }
+
+    #[test]
+    fn reads_from_a_checkout_skip_a_disabled_trie_cache() {
+        let correlation_id = CorrelationId::new();
+        let (_temp_dir, state) = create_empty_state(
+            StorageConfig::new()
+                .set_map_size(*TEST_MAP_SIZE)
+                .set_trie_cache_size(0),
+        );
+        let effects: HashMap<Key, Transform> = TEST_PAIRS
+            .iter()
+            .map(|TestPair { key, value }| (*key, Transform::Write(value.to_owned())))
+            .collect();
+        let root_hash = match state
+            .commit(correlation_id, state.empty_root_hash, effects)
+            .unwrap()
+        {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in TEST_PAIRS.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+        assert!(state.trie_cache.is_empty());
+    }
+
+    #[test]
+    fn prune_clears_the_trie_cache() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        checkout.read(correlation_id, &TEST_PAIRS[0].key).unwrap();
+        assert!(!state.trie_cache.is_empty());
+
+        match state.prune(correlation_id, &[]).unwrap() {
+            PruneResult::Success(pruned) => assert!(pruned > 0),
+            _ => panic!("prune failed"),
+        }
+
+        assert!(state.trie_cache.is_empty());
+    }
+
+    #[test]
+    fn prune_fails_if_unknown_root_is_given() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        let result = state
+            .prune(correlation_id, &[root_hash, fake_hash])
+            .unwrap();
+        assert_eq!(result, PruneResult::RootNotFound(fake_hash));
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in TEST_PAIRS.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn prune_and_diff_fail_if_a_trie_node_is_missing() {
+        let correlation_id = CorrelationId::new();
+        let (source, root_hash) = create_test_state();
+        let (_temp_dir, state) =
+            create_empty_state(StorageConfig::new().set_map_size(*TEST_MAP_SIZE));
+
+        // Import only the root, leaving the trie values below it missing.
+        let root_bytes = match source
+            .get_trie_chunk(correlation_id, root_hash, &[])
+            .unwrap()
+        {
+            TrieChunkResult::Success(chunk) => chunk.tries[0].to_owned(),
+            _ => panic!("get_trie_chunk failed"),
+        };
+        state.put_trie(correlation_id, &root_bytes).unwrap();
+        assert!(state.checkout(root_hash).unwrap().is_some());
+
+        match state.prune(correlation_id, &[root_hash]).unwrap() {
+            PruneResult::MissingNode(_) => (),
+            _ => panic!("prune should have failed"),
+        }
+        assert!(state.checkout(root_hash).unwrap().is_some());
+
+        match state
+            .diff(correlation_id, state.empty_root_hash, root_hash)
+            .unwrap()
+        {
+            DiffResult::MissingNode(_) => (),
+            _ => panic!("diff should have failed"),
+        }
+    }
+
+    #[test]
+    fn diff_returns_keys_with_differing_values() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let effects: HashMap<Key, Transform> = {
+            let mut tmp = HashMap::new();
+            // Rewriting a value with itself leaves its key out of the diff.
+            tmp.insert(
+                TEST_PAIRS[0].key,
+                Transform::Write(TEST_PAIRS[0].value.clone()),
+            );
+            tmp.insert(TEST_PAIRS[1].key, Transform::Write(Value::Int32(-2)));
+            tmp.insert(Key::Account([3u8; 32]), Transform::Write(Value::Int32(3)));
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+
+        let expected_keys: BTreeSet<Key> = vec![TEST_PAIRS[1].key, Key::Account([3u8; 32])]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            state.diff(correlation_id, root_hash, updated_hash).unwrap(),
+            DiffResult::Success(expected_keys.clone())
+        );
+        assert_eq!(
+            state.diff(correlation_id, updated_hash, root_hash).unwrap(),
+            DiffResult::Success(expected_keys)
+        );
+        assert_eq!(
+            state.diff(correlation_id, root_hash, root_hash).unwrap(),
+            DiffResult::Success(BTreeSet::new())
+        );
+    }
+
+    #[test]
+    fn diff_fails_if_unknown_root_is_given() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        let result = state.diff(correlation_id, root_hash, fake_hash).unwrap();
+        assert_eq!(result, DiffResult::RootNotFound(fake_hash));
+    }
+
+    #[test]
+    fn exported_tries_can_be_imported_into_empty_state() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let target = InMemoryGlobalState::empty().unwrap();
+        assert!(target.checkout(root_hash).unwrap().is_none());
+
+        let mut cursor = Vec::new();
+        loop {
+            let chunk = match state
+                .get_trie_chunk(correlation_id, root_hash, &cursor)
+                .unwrap()
+            {
+                TrieChunkResult::Success(chunk) => chunk,
+                _ => panic!("get_trie_chunk failed"),
+            };
+            for trie_bytes in &chunk.tries {
+                target.put_trie(correlation_id, trie_bytes).unwrap();
+            }
+            if chunk.cursor.is_empty() {
+                break;
+            }
+            cursor = chunk.cursor;
+        }
+
+        let checkout = target.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in TEST_PAIRS.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn get_trie_chunk_fails_if_unknown_root_is_given() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        let result = state
+            .get_trie_chunk(correlation_id, fake_hash, &[])
+            .unwrap();
+        assert_eq!(result, TrieChunkResult::RootNotFound(fake_hash));
+
+        let result = state
+            .get_trie_chunk(correlation_id, root_hash, &[fake_hash])
+            .unwrap();
+        assert_eq!(result, TrieChunkResult::RootNotFound(fake_hash));
+    }
+
+    #[test]
+    fn put_trie_fails_if_bytes_are_not_a_trie() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        assert!(state.put_trie(correlation_id, &[42u8; 3]).is_err());
+    }
+
+    #[test]
+    fn commit_fails_if_map_is_full_and_auto_resize_is_disabled() {
+        let correlation_id = CorrelationId::new();
+        let (_temp_dir, state) = create_empty_state(small_map_storage_config());
+        let empty_root_hash = state.empty_root();
+
+        match state.commit(correlation_id, empty_root_hash, create_large_effects()) {
+            Err(error::Error::Lmdb(lmdb::Error::MapFull)) => (),
+            result => panic!("expected the map to be full, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn commit_resizes_full_map_if_auto_resize_is_enabled() {
+        let correlation_id = CorrelationId::new();
+        let storage_config = small_map_storage_config().set_auto_resize(true);
+        let (_temp_dir, state) = create_empty_state(storage_config);
+        let empty_root_hash = state.empty_root();
+        let effects = create_large_effects();
+
+        let updated_hash = match state
+            .commit(correlation_id, empty_root_hash, effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+        assert!(state.environment.map_size() > storage_config.map_size());
+
+        let checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for (key, transform) in effects {
+            match transform {
+                Transform::Write(value) => {
+                    assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap())
+                }
+                _ => panic!("unexpected transform"),
+            }
+        }
+    }
 }
@@ -1,30 +1,56 @@
+pub mod executed_deploys;
 pub mod in_memory;
 pub mod lmdb;
+pub mod rocksdb;
+pub mod scratch;
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::hash::BuildHasher;
 use std::time::Instant;
 
+use contract_ffi::bytesrepr::{self, ToBytes};
 use contract_ffi::key::Key;
 use contract_ffi::value::Value;
 use engine_shared::logging::{log_duration, log_metric, GAUGE};
 use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 use engine_shared::transform::{self, Transform, TypeMismatch};
 
+use crate::metrics;
 use crate::protocol_data::ProtocolData;
 use crate::protocol_data_store::ProtocolVersion;
-use crate::transaction_source::{Transaction, TransactionSource};
-use crate::trie::Trie;
-use crate::trie_store::operations::{read, write, ReadResult, WriteResult};
+use crate::transaction_source::{Deletable, Readable, Transaction, TransactionSource, Writable};
+use crate::trie::{self, Trie, TrieMerkleProof};
+use crate::trie_store::operations::{
+    self, reachable, read, write, DeleteResult, ReachableResult, ReadResult, WriteResult,
+};
 use crate::trie_store::TrieStore;
 
+pub use crate::trie_store::operations::IntegrityReport;
+
 const GLOBAL_STATE_COMMIT_READS: &str = "global_state_commit_reads";
 const GLOBAL_STATE_COMMIT_WRITES: &str = "global_state_commit_writes";
 const GLOBAL_STATE_COMMIT_DURATION: &str = "global_state_commit_duration";
 const GLOBAL_STATE_COMMIT_READ_DURATION: &str = "global_state_commit_read_duration";
 const GLOBAL_STATE_COMMIT_WRITE_DURATION: &str = "global_state_commit_write_duration";
+const GLOBAL_STATE_COMMIT_BATCH_DURATION: &str = "global_state_commit_batch_duration";
+const GLOBAL_STATE_COMMIT_BATCH_SIZE: &str = "global_state_commit_batch_size";
+const GLOBAL_STATE_PRUNE_DURATION: &str = "global_state_prune_duration";
+const GLOBAL_STATE_PRUNE_DELETES: &str = "global_state_prune_deletes";
+const GLOBAL_STATE_DIFF_DURATION: &str = "global_state_diff_duration";
+const GLOBAL_STATE_DIFF_KEYS: &str = "global_state_diff_keys";
+const GLOBAL_STATE_GET_TRIE_CHUNK_DURATION: &str = "global_state_get_trie_chunk_duration";
+const GLOBAL_STATE_PUT_TRIE_DURATION: &str = "global_state_put_trie_duration";
 const COMMIT: &str = "commit";
+const COMMIT_BATCH: &str = "commit_batch";
+const PRUNE: &str = "prune";
+const DIFF: &str = "diff";
+const GET_TRIE_CHUNK: &str = "get_trie_chunk";
+const PUT_TRIE: &str = "put_trie";
+
+/// The number of bytes of serialized trie values after which a trie chunk is cut off.  A chunk
+/// always holds at least one trie value, however large.
+pub const TRIE_CHUNK_SIZE_LIMIT: usize = 1024 * 1024;
 
 /// A reader of state
 pub trait StateReader<K, V> {
@@ -35,6 +61,17 @@ pub trait StateReader<K, V> {
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
 }
 
+/// A reader of state which can prove the values it reads
+pub trait StateProofReader<K, V>: StateReader<K, V> {
+    /// Returns the state value from the corresponding key along with a Merkle
+    /// proof of its inclusion in the state
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<TrieMerkleProof<K, V>>, Self::Error>;
+}
+
 #[derive(Debug)]
 pub enum CommitResult {
     RootNotFound,
@@ -66,9 +103,103 @@ impl From<transform::Error> for CommitResult {
     }
 }
 
+#[derive(Debug)]
+pub enum CommitBatchResult {
+    /// The post state hash of each commit of the batch, in order.
+    Success(Vec<Blake2bHash>),
+    /// The commit at `index` in the batch failed with `result`.  Nothing was committed.
+    Failure { index: usize, result: CommitResult },
+}
+
+impl fmt::Display for CommitBatchResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            CommitBatchResult::Success(hashes) => write!(f, "Success: {} commits", hashes.len()),
+            CommitBatchResult::Failure { index, result } => {
+                write!(f, "Failure of commit {}: {}", index, result)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PruneResult {
+    /// One of the roots to retain does not exist.  Nothing was pruned.
+    RootNotFound(Blake2bHash),
+    /// A trie value reachable from one of the roots to retain does not exist, i.e. the state
+    /// under that root is incomplete.  Nothing was pruned.
+    MissingNode(Blake2bHash),
+    /// The number of trie values which were deleted.
+    Success(usize),
+}
+
+impl fmt::Display for PruneResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            PruneResult::RootNotFound(hash) => write!(f, "Root not found: {}", hash),
+            PruneResult::MissingNode(hash) => write!(f, "Trie node not found: {}", hash),
+            PruneResult::Success(pruned) => write!(f, "Success: {} trie values pruned", pruned),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DiffResult {
+    /// One of the roots to compare does not exist.
+    RootNotFound(Blake2bHash),
+    /// A trie value reachable from one of the roots to compare does not exist.
+    MissingNode(Blake2bHash),
+    /// The keys whose values differ between the two roots, including keys present under only
+    /// one of them.
+    Success(BTreeSet<Key>),
+}
+
+impl fmt::Display for DiffResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            DiffResult::RootNotFound(hash) => write!(f, "Root not found: {}", hash),
+            DiffResult::MissingNode(hash) => write!(f, "Trie node not found: {}", hash),
+            DiffResult::Success(keys) => write!(f, "Success: {} keys differ", keys.len()),
+        }
+    }
+}
+
+/// A part of the trie values reachable from a root, exported so that another node can import
+/// them with [`StateProvider::put_trie`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct TrieChunk {
+    /// The serialized trie values.  Each one is stored under the hash of its bytes, so the
+    /// receiver can check it against the pointer of its parent, or against the root hash.
+    pub tries: Vec<Vec<u8>>,
+    /// The hashes of the trie values left to export, to be passed when requesting the next chunk.
+    /// Empty once every trie value has been exported.
+    pub cursor: Vec<Blake2bHash>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TrieChunkResult {
+    /// The root, or one of the trie values the cursor points to, does not exist.
+    RootNotFound(Blake2bHash),
+    Success(TrieChunk),
+}
+
+impl fmt::Display for TrieChunkResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            TrieChunkResult::RootNotFound(hash) => write!(f, "Root not found: {}", hash),
+            TrieChunkResult::Success(chunk) => write!(
+                f,
+                "Success: {} trie values exported, {} pending",
+                chunk.tries.len(),
+                chunk.cursor.len()
+            ),
+        }
+    }
+}
+
 pub trait StateProvider {
     type Error;
-    type Reader: StateReader<Key, Value, Error = Self::Error>;
+    type Reader: StateReader<Key, Value, Error = Self::Error> + StateProofReader<Key, Value>;
 
     /// Checkouts to the post state of a specific block.
     fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error>;
@@ -82,6 +213,26 @@ pub trait StateProvider {
         effects: HashMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error>;
 
+    /// Applies the changes of each element of `batch` to its state hash, atomically, and returns
+    /// the new post state hashes.
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, HashMap<Key, Transform>)>,
+    ) -> Result<CommitBatchResult, Self::Error>;
+
+    /// Applies changes and stores the protocol data for `protocol_version` atomically, returning
+    /// the new post state hash.  Used by genesis and upgrades, whose post state is only usable
+    /// together with the protocol data they install.
+    fn commit_with_protocol_data(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        effects: HashMap<Key, Transform>,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<CommitResult, Self::Error>;
+
     fn put_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -94,6 +245,56 @@ pub trait StateProvider {
     ) -> Result<Option<ProtocolData>, Self::Error>;
 
     fn empty_root(&self) -> Blake2bHash;
+
+    /// Deletes all trie values which are not reachable from the given roots.
+    /// The empty root is always retained.
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retain_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error>;
+
+    /// Returns the keys whose values differ between the states under the two given roots.
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        poststate_hash: Blake2bHash,
+    ) -> Result<DiffResult, Self::Error>;
+
+    /// Exports the next chunk of the trie values reachable from `root_hash`, starting from the
+    /// root if `cursor` is empty, or from the cursor returned with the previous chunk otherwise.
+    fn get_trie_chunk(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        cursor: &[Blake2bHash],
+    ) -> Result<TrieChunkResult, Self::Error>;
+
+    /// Imports a serialized trie value exported by [`StateProvider::get_trie_chunk`], returning the
+    /// hash it is stored under.
+    fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_bytes: &[u8],
+    ) -> Result<Blake2bHash, Self::Error>;
+
+    /// Returns the hashes of the trie values referenced from `root_hash` which are not in the
+    /// store, including `root_hash` itself if it is not.  The state at `root_hash` is complete
+    /// once none are returned.
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<Vec<Blake2bHash>, Self::Error>;
+
+    /// Walks the tries under `root_hashes`, reporting stored trie values whose contents don't
+    /// match their hash and referenced trie values which are not in the store.
+    fn verify_integrity(
+        &self,
+        correlation_id: CorrelationId,
+        root_hashes: &[Blake2bHash],
+    ) -> Result<IntegrityReport, Self::Error>;
 }
 
 pub fn commit<'a, R, S, H, E>(
@@ -111,9 +312,146 @@ where
     H: BuildHasher,
 {
     let mut txn = environment.create_read_write_txn()?;
+
+    let start = Instant::now();
+
+    let commit_result =
+        apply_effects::<_, _, _, E>(correlation_id, &mut txn, store, prestate_hash, effects)?;
+    if let CommitResult::Success(_) = commit_result {
+        txn.commit()?;
+
+        log_duration(
+            correlation_id,
+            GLOBAL_STATE_COMMIT_DURATION,
+            COMMIT,
+            start.elapsed(),
+        );
+        metrics::storage_metrics().record_commit(start.elapsed());
+    }
+
+    Ok(commit_result)
+}
+
+/// Commits `effects` to `prestate_hash` and stores `protocol_data` at `protocol_version`, within a
+/// single transaction.  The protocol data is only stored if the commit succeeds, so a failure part
+/// way through never leaves one without the other.
+#[allow(clippy::too_many_arguments)]
+pub fn commit_with_protocol_data<'a, R, S, P, H, E>(
+    environment: &'a R,
+    store: &S,
+    protocol_data_store: &P,
+    correlation_id: CorrelationId,
+    prestate_hash: Blake2bHash,
+    effects: HashMap<Key, Transform, H>,
+    protocol_version: ProtocolVersion,
+    protocol_data: &ProtocolData,
+) -> Result<CommitResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    P: Store<ProtocolVersion, ProtocolData, Handle = S::Handle>,
+    P::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<P::Error> + From<contract_ffi::bytesrepr::Error>,
+    H: BuildHasher,
+{
+    let mut txn = environment.create_read_write_txn()?;
+
+    let start = Instant::now();
+
+    let commit_result =
+        apply_effects::<_, _, _, E>(correlation_id, &mut txn, store, prestate_hash, effects)?;
+    if let CommitResult::Success(_) = commit_result {
+        protocol_data_store.put(&mut txn, &protocol_version, protocol_data)?;
+        txn.commit()?;
+
+        log_duration(
+            correlation_id,
+            GLOBAL_STATE_COMMIT_DURATION,
+            COMMIT,
+            start.elapsed(),
+        );
+        metrics::storage_metrics().record_commit(start.elapsed());
+    }
+
+    Ok(commit_result)
+}
+
+/// Commits each set of effects of `batch` to its prestate hash, all within a single transaction.
+///
+/// A prestate hash may be the post state hash of an earlier commit of the batch, so that the
+/// effects of consecutive blocks can be replayed at once.  If any commit fails, the transaction
+/// is aborted: either the whole batch is committed or none of it is.
+pub fn commit_batch<'a, R, S, H, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    batch: Vec<(Blake2bHash, HashMap<Key, Transform, H>)>,
+) -> Result<CommitBatchResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+    H: BuildHasher,
+{
+    let mut txn = environment.create_read_write_txn()?;
+
+    let start = Instant::now();
+    let batch_size = batch.len();
+    let mut poststate_hashes = Vec::with_capacity(batch_size);
+
+    for (index, (prestate_hash, effects)) in batch.into_iter().enumerate() {
+        match apply_effects::<_, _, _, E>(correlation_id, &mut txn, store, prestate_hash, effects)?
+        {
+            CommitResult::Success(poststate_hash) => poststate_hashes.push(poststate_hash),
+            result => return Ok(CommitBatchResult::Failure { index, result }),
+        }
+    }
+
+    txn.commit()?;
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_BATCH_DURATION,
+        COMMIT_BATCH,
+        start.elapsed(),
+    );
+    metrics::storage_metrics().record_commit(start.elapsed());
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_BATCH_SIZE,
+        COMMIT_BATCH,
+        GAUGE,
+        batch_size as f64,
+    );
+
+    Ok(CommitBatchResult::Success(poststate_hashes))
+}
+
+/// Applies `effects` to the state under `prestate_hash` within `txn`, returning the post state
+/// hash.  The transaction is left to the caller to commit.
+///
+/// If the effects record executed deploys, the records which are no longer needed to reject
+/// replays are purged from the post state, whichever way the effects are committed.
+fn apply_effects<T, S, H, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    prestate_hash: Blake2bHash,
+    effects: HashMap<Key, Transform, H>,
+) -> Result<CommitResult, E>
+where
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+    H: BuildHasher,
+{
     let mut current_root = prestate_hash;
 
-    let maybe_root: Option<Trie<Key, Value>> = store.get(&txn, &current_root)?;
+    let maybe_root: Option<Trie<Key, Value>> = store.get(&*txn, &current_root)?;
 
     if maybe_root.is_none() {
         return Ok(CommitResult::RootNotFound);
@@ -123,8 +461,10 @@ where
     let mut reads: i32 = 0;
     let mut writes: i32 = 0;
 
+    let maybe_executed_deploy_window = executed_deploys::latest_window(&effects);
+
     for (key, transform) in effects.into_iter() {
-        let read_result = read::<_, _, _, _, E>(correlation_id, &txn, store, &current_root, &key)?;
+        let read_result = read::<_, _, _, _, E>(correlation_id, &*txn, store, &current_root, &key)?;
 
         log_duration(
             correlation_id,
@@ -148,7 +488,7 @@ where
         };
 
         let write_result =
-            write::<_, _, _, _, E>(correlation_id, &mut txn, store, &current_root, &key, &value)?;
+            write::<_, _, _, _, E>(correlation_id, txn, store, &current_root, &key, &value)?;
 
         log_duration(
             correlation_id,
@@ -167,14 +507,20 @@ where
         }
     }
 
-    txn.commit()?;
-
-    log_duration(
-        correlation_id,
-        GLOBAL_STATE_COMMIT_DURATION,
-        COMMIT,
-        start.elapsed(),
-    );
+    if let Some(window) = maybe_executed_deploy_window {
+        let stale_key_prefix = executed_deploys::stale_key_prefix(window)?;
+        match operations::delete_prefix::<Key, Value, _, _, E>(
+            correlation_id,
+            txn,
+            store,
+            &current_root,
+            &stale_key_prefix,
+        )? {
+            DeleteResult::Deleted(root_hash) => current_root = root_hash,
+            DeleteResult::DoesNotExist => (),
+            _x @ DeleteResult::RootNotFound => panic!(stringify!(_x)),
+        }
+    }
 
     log_metric(
         correlation_id,
@@ -194,3 +540,286 @@ where
 
     Ok(CommitResult::Success(current_root))
 }
+
+pub fn prune<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    retain_roots: &[Blake2bHash],
+) -> Result<PruneResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+
+    for root in retain_roots {
+        let maybe_root: Option<Trie<Key, Value>> = store.get(&txn, root)?;
+        if maybe_root.is_none() {
+            return Ok(PruneResult::RootNotFound(*root));
+        }
+    }
+
+    let start = Instant::now();
+
+    let retained: HashSet<Vec<u8>> = {
+        let hashes =
+            match reachable::<Key, Value, _, _, E>(correlation_id, &txn, store, retain_roots)? {
+                ReachableResult::Found(hashes) => hashes,
+                ReachableResult::MissingNode(hash) => return Ok(PruneResult::MissingNode(hash)),
+            };
+        let mut ret = HashSet::with_capacity(hashes.len());
+        for hash in hashes {
+            ret.insert(hash.to_bytes()?);
+        }
+        ret
+    };
+
+    let mut deletes: usize = 0;
+
+    for key in txn.keys(store.handle())? {
+        if !retained.contains(&key) {
+            txn.delete(store.handle(), &key)?;
+            deletes += 1;
+        }
+    }
+
+    txn.commit()?;
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_PRUNE_DURATION,
+        PRUNE,
+        start.elapsed(),
+    );
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_PRUNE_DELETES,
+        PRUNE,
+        GAUGE,
+        deletes as f64,
+    );
+
+    Ok(PruneResult::Success(deletes))
+}
+
+/// Returns the keys whose values differ between the states under `prestate_hash` and
+/// `poststate_hash`.
+///
+/// Subtries shared by both roots have the same hash, so only the trie values reachable from
+/// exactly one of the roots are inspected.  Every leaf among them holds a key which was written,
+/// overwritten or is missing on the other side.
+pub fn diff<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    prestate_hash: Blake2bHash,
+    poststate_hash: Blake2bHash,
+) -> Result<DiffResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let txn = environment.create_read_txn()?;
+
+    for root in &[prestate_hash, poststate_hash] {
+        let maybe_root: Option<Trie<Key, Value>> = store.get(&txn, root)?;
+        if maybe_root.is_none() {
+            return Ok(DiffResult::RootNotFound(*root));
+        }
+    }
+
+    let start = Instant::now();
+
+    let pre_hashes =
+        match reachable::<Key, Value, _, _, E>(correlation_id, &txn, store, &[prestate_hash])? {
+            ReachableResult::Found(hashes) => hashes,
+            ReachableResult::MissingNode(hash) => return Ok(DiffResult::MissingNode(hash)),
+        };
+    let post_hashes =
+        match reachable::<Key, Value, _, _, E>(correlation_id, &txn, store, &[poststate_hash])? {
+            ReachableResult::Found(hashes) => hashes,
+            ReachableResult::MissingNode(hash) => return Ok(DiffResult::MissingNode(hash)),
+        };
+
+    let mut keys = BTreeSet::new();
+    for hash in pre_hashes.symmetric_difference(&post_hashes) {
+        if let Some(Trie::Leaf { key, .. }) = store.get(&txn, hash)? {
+            keys.insert(key);
+        }
+    }
+
+    txn.commit()?;
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_DIFF_DURATION,
+        DIFF,
+        start.elapsed(),
+    );
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_DIFF_KEYS,
+        DIFF,
+        GAUGE,
+        keys.len() as f64,
+    );
+
+    Ok(DiffResult::Success(keys))
+}
+
+/// Exports the trie values reachable from `root_hash`, or from `cursor` if it is not empty, in
+/// depth-first order until [`TRIE_CHUNK_SIZE_LIMIT`] bytes have been collected.
+pub fn get_trie_chunk<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    root_hash: Blake2bHash,
+    cursor: &[Blake2bHash],
+) -> Result<TrieChunkResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    let txn = environment.create_read_txn()?;
+
+    let start = Instant::now();
+
+    let mut pending: Vec<Blake2bHash> = if cursor.is_empty() {
+        vec![root_hash]
+    } else {
+        cursor.to_vec()
+    };
+    let mut tries: Vec<Vec<u8>> = Vec::new();
+    let mut chunk_size: usize = 0;
+
+    while chunk_size < TRIE_CHUNK_SIZE_LIMIT {
+        let hash = match pending.pop() {
+            Some(hash) => hash,
+            None => break,
+        };
+        let trie: Trie<Key, Value> = match store.get(&txn, &hash)? {
+            Some(trie) => trie,
+            None => return Ok(TrieChunkResult::RootNotFound(hash)),
+        };
+        match &trie {
+            Trie::Leaf { .. } => (),
+            Trie::Node { pointer_block } => {
+                for index in (0..trie::RADIX).rev() {
+                    if let Some(pointer) = pointer_block[index] {
+                        pending.push(*pointer.hash());
+                    }
+                }
+            }
+            Trie::Extension { pointer, .. } => pending.push(*pointer.hash()),
+        }
+        let trie_bytes = trie.to_bytes()?;
+        chunk_size += trie_bytes.len();
+        tries.push(trie_bytes);
+    }
+
+    txn.commit()?;
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_GET_TRIE_CHUNK_DURATION,
+        GET_TRIE_CHUNK,
+        start.elapsed(),
+    );
+
+    Ok(TrieChunkResult::Success(TrieChunk {
+        tries,
+        cursor: pending,
+    }))
+}
+
+/// Stores a serialized trie value under the hash of its bytes, which is returned.
+///
+/// Fails if `trie_bytes` is not a valid trie value.  Whether the values it points to are present
+/// isn't checked, so trie values can be imported in any order.
+pub fn put_trie<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    trie_bytes: &[u8],
+) -> Result<Blake2bHash, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    let start = Instant::now();
+
+    let trie: Trie<Key, Value> = bytesrepr::deserialize(trie_bytes)?;
+    let trie_hash = Blake2bHash::new(trie_bytes);
+
+    let mut txn = environment.create_read_write_txn()?;
+    store.put(&mut txn, &trie_hash, &trie)?;
+    txn.commit()?;
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_PUT_TRIE_DURATION,
+        PUT_TRIE,
+        start.elapsed(),
+    );
+
+    Ok(trie_hash)
+}
+
+/// Returns the hashes of the trie values referenced from `root_hash` which are not in `store`.
+pub fn missing_trie_keys<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    root_hash: Blake2bHash,
+) -> Result<Vec<Blake2bHash>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let missing = operations::missing_trie_keys::<Key, Value, _, _, E>(
+        correlation_id,
+        &txn,
+        store,
+        root_hash,
+    )?;
+    txn.commit()?;
+    Ok(missing)
+}
+
+/// Checks the tries under `root_hashes` in `store` for corrupt and dangling trie values.
+pub fn verify_integrity<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    root_hashes: &[Blake2bHash],
+) -> Result<IntegrityReport, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let report = operations::verify_integrity::<Key, Value, _, _, E>(
+        correlation_id,
+        &txn,
+        store,
+        root_hashes,
+    )?;
+    txn.commit()?;
+    Ok(report)
+}
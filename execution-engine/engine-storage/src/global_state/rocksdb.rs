@@ -0,0 +1,664 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::Value;
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use engine_shared::transform::Transform;
+
+use crate::error;
+use crate::global_state::{
+    commit, commit_batch, commit_with_protocol_data, diff, get_trie_chunk, missing_trie_keys,
+    prune, put_trie, verify_integrity, CommitBatchResult, CommitResult, DiffResult,
+    IntegrityReport, PruneResult, StateProvider, TrieChunkResult,
+};
+use crate::global_state::{StateProofReader, StateReader};
+use crate::protocol_data::ProtocolData;
+use crate::protocol_data_store::rocksdb::RocksDbProtocolDataStore;
+use crate::protocol_data_store::ProtocolVersion;
+use crate::store::Store;
+use crate::transaction_source::rocksdb::{RocksDbEnvironment, RocksDbReadTransaction};
+use crate::transaction_source::{Transaction, TransactionSource};
+use crate::trie::operations::create_hashed_empty_trie;
+use crate::trie::{Trie, TrieMerkleProof};
+use crate::trie_store::operations::{read, read_with_proof, ReadResult};
+use crate::trie_store::rocksdb::RocksDbTrieStore;
+
+/// Global state backed by RocksDB.
+///
+/// Tries and protocol data are stored in separate column families of a single
+/// database, allowing operators to benefit from RocksDB's compaction when
+/// keeping large histories.
+pub struct RocksDbGlobalState {
+    pub environment: Arc<RocksDbEnvironment>,
+    pub trie_store: Arc<RocksDbTrieStore>,
+    pub protocol_data_store: Arc<RocksDbProtocolDataStore>,
+    pub empty_root_hash: Blake2bHash,
+}
+
+/// Represents a "view" of global state at a particular root hash.
+pub struct RocksDbGlobalStateView {
+    pub environment: Arc<RocksDbEnvironment>,
+    pub store: Arc<RocksDbTrieStore>,
+    pub root_hash: Blake2bHash,
+}
+
+impl RocksDbGlobalState {
+    /// Creates an empty state from an existing environment and trie_store.
+    pub fn empty(
+        environment: Arc<RocksDbEnvironment>,
+        trie_store: Arc<RocksDbTrieStore>,
+        protocol_data_store: Arc<RocksDbProtocolDataStore>,
+    ) -> Result<Self, error::Error> {
+        let root_hash: Blake2bHash = {
+            let (root_hash, root) = create_hashed_empty_trie::<Key, Value>()?;
+            let mut txn = environment.create_read_write_txn()?;
+            trie_store.put(&mut txn, &root_hash, &root)?;
+            txn.commit()?;
+            root_hash
+        };
+        Ok(RocksDbGlobalState::new(
+            environment,
+            trie_store,
+            protocol_data_store,
+            root_hash,
+        ))
+    }
+
+    /// Creates a state from an existing environment, store, and root_hash.
+    /// Intended to be used for testing.
+    pub(crate) fn new(
+        environment: Arc<RocksDbEnvironment>,
+        trie_store: Arc<RocksDbTrieStore>,
+        protocol_data_store: Arc<RocksDbProtocolDataStore>,
+        empty_root_hash: Blake2bHash,
+    ) -> Self {
+        RocksDbGlobalState {
+            environment,
+            trie_store,
+            protocol_data_store,
+            empty_root_hash,
+        }
+    }
+}
+
+impl StateReader<Key, Value> for RocksDbGlobalStateView {
+    type Error = error::Error;
+
+    fn read(&self, correlation_id: CorrelationId, key: &Key) -> Result<Option<Value>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read::<Key, Value, RocksDbReadTransaction, RocksDbTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(value) => Some(value),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("RocksDbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
+impl StateProofReader<Key, Value> for RocksDbGlobalStateView {
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, Value>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<Key, Value, _, _, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("RocksDbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
+impl StateProvider for RocksDbGlobalState {
+    type Error = error::Error;
+
+    type Reader = RocksDbGlobalStateView;
+
+    fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let maybe_root: Option<Trie<Key, Value>> = self.trie_store.get(&txn, &state_hash)?;
+        let maybe_state = maybe_root.map(|_| RocksDbGlobalStateView {
+            environment: Arc::clone(&self.environment),
+            store: Arc::clone(&self.trie_store),
+            root_hash: state_hash,
+        });
+        txn.commit()?;
+        Ok(maybe_state)
+    }
+
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: HashMap<Key, Transform>,
+    ) -> Result<CommitResult, Self::Error> {
+        let commit_result = commit::<RocksDbEnvironment, RocksDbTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            effects,
+        )?;
+        Ok(commit_result)
+    }
+
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, HashMap<Key, Transform>)>,
+    ) -> Result<CommitBatchResult, Self::Error> {
+        commit_batch::<RocksDbEnvironment, RocksDbTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            batch,
+        )
+    }
+
+    fn commit_with_protocol_data(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: HashMap<Key, Transform>,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<CommitResult, Self::Error> {
+        commit_with_protocol_data::<RocksDbEnvironment, RocksDbTrieStore, _, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            &*self.protocol_data_store,
+            correlation_id,
+            prestate_hash,
+            effects,
+            protocol_version,
+            protocol_data,
+        )
+    }
+
+    fn put_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        self.protocol_data_store
+            .put(&mut txn, &protocol_version, protocol_data)?;
+        txn.commit().map_err(Into::into)
+    }
+
+    fn get_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ProtocolData>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.protocol_data_store.get(&txn, &protocol_version)?;
+        txn.commit()?;
+        Ok(result)
+    }
+
+    fn empty_root(&self) -> Blake2bHash {
+        self.empty_root_hash
+    }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retain_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error> {
+        let retain_roots = [retain_roots, &[self.empty_root_hash]].concat();
+        let prune_result = prune::<RocksDbEnvironment, RocksDbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            &retain_roots,
+        )?;
+        Ok(prune_result)
+    }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        poststate_hash: Blake2bHash,
+    ) -> Result<DiffResult, Self::Error> {
+        let diff_result = diff::<RocksDbEnvironment, RocksDbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            poststate_hash,
+        )?;
+        Ok(diff_result)
+    }
+
+    fn get_trie_chunk(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        cursor: &[Blake2bHash],
+    ) -> Result<TrieChunkResult, Self::Error> {
+        let trie_chunk_result = get_trie_chunk::<RocksDbEnvironment, RocksDbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            root_hash,
+            cursor,
+        )?;
+        Ok(trie_chunk_result)
+    }
+
+    fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_bytes: &[u8],
+    ) -> Result<Blake2bHash, Self::Error> {
+        let trie_hash = put_trie::<RocksDbEnvironment, RocksDbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            trie_bytes,
+        )?;
+        Ok(trie_hash)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let missing_trie_keys =
+            missing_trie_keys::<RocksDbEnvironment, RocksDbTrieStore, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                root_hash,
+            )?;
+        Ok(missing_trie_keys)
+    }
+
+    fn verify_integrity(
+        &self,
+        correlation_id: CorrelationId,
+        root_hashes: &[Blake2bHash],
+    ) -> Result<IntegrityReport, Self::Error> {
+        verify_integrity::<RocksDbEnvironment, RocksDbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            root_hashes,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use tempfile::{tempdir, TempDir};
+
+    use crate::global_state::in_memory::InMemoryGlobalState;
+    use crate::trie_store::operations::{write, WriteResult};
+
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct TestPair {
+        key: Key,
+        value: Value,
+    }
+
+    const TEST_PAIRS: [TestPair; 2] = [
+        TestPair {
+            key: Key::Account([1u8; 32]),
+            value: Value::Int32(1),
+        },
+        TestPair {
+            key: Key::Account([2u8; 32]),
+            value: Value::Int32(2),
+        },
+    ];
+
+    fn create_test_pairs_updated() -> [TestPair; 3] {
+        [
+            TestPair {
+                key: Key::Account([1u8; 32]),
+                value: Value::String("one".to_string()),
+            },
+            TestPair {
+                key: Key::Account([2u8; 32]),
+                value: Value::String("two".to_string()),
+            },
+            TestPair {
+                key: Key::Account([3u8; 32]),
+                value: Value::Int32(3),
+            },
+        ]
+    }
+
+    // Unlike LMDB, RocksDB creates new files as it runs, so the temporary
+    // directory must outlive the state.
+    fn create_test_state() -> (TempDir, RocksDbGlobalState, Blake2bHash) {
+        let correlation_id = CorrelationId::new();
+        let temp_dir = tempdir().unwrap();
+        let environment =
+            Arc::new(RocksDbEnvironment::new(&temp_dir.path().to_path_buf()).unwrap());
+        let trie_store = Arc::new(RocksDbTrieStore::new(&environment, None).unwrap());
+        let protocol_data_store =
+            Arc::new(RocksDbProtocolDataStore::new(&environment, None).unwrap());
+        let ret = RocksDbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        let mut current_root = ret.empty_root_hash;
+        {
+            let mut txn = ret.environment.create_read_write_txn().unwrap();
+
+            for TestPair { key, value } in &TEST_PAIRS {
+                match write::<_, _, _, RocksDbTrieStore, error::Error>(
+                    correlation_id,
+                    &mut txn,
+                    &ret.trie_store,
+                    &current_root,
+                    key,
+                    value,
+                )
+                .unwrap()
+                {
+                    WriteResult::Written(root_hash) => {
+                        current_root = root_hash;
+                    }
+                    WriteResult::AlreadyExists => (),
+                    WriteResult::RootNotFound => panic!("RocksDbGlobalState has invalid root"),
+                }
+            }
+
+            txn.commit().unwrap();
+        }
+        (temp_dir, ret, current_root)
+    }
+
+    #[test]
+    fn reads_from_a_checkout_return_expected_values() {
+        let correlation_id = CorrelationId::new();
+        let (_temp_dir, state, root_hash) = create_test_state();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in TEST_PAIRS.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn checkout_fails_if_unknown_hash_is_given() {
+        let (_temp_dir, state, _) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        let result = state.checkout(fake_hash).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn commit_updates_state() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (_temp_dir, state, root_hash) = create_test_state();
+
+        let effects: HashMap<Key, Transform> = {
+            let mut tmp = HashMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn commit_updates_state_and_original_state_stays_intact() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (_temp_dir, state, root_hash) = create_test_state();
+
+        let effects: HashMap<Key, Transform> = {
+            let mut tmp = HashMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+
+        let original_checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in TEST_PAIRS.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                original_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert_eq!(
+            None,
+            original_checkout
+                .read(correlation_id, &test_pairs_updated[2].key)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn prune_removes_unreachable_tries() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (_temp_dir, state, root_hash) = create_test_state();
+
+        let effects: HashMap<Key, Transform> = {
+            let mut tmp = HashMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+
+        match state.prune(correlation_id, &[updated_hash]).unwrap() {
+            PruneResult::Success(pruned) => assert!(pruned > 0),
+            _ => panic!("prune failed"),
+        }
+
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        assert!(state.checkout(state.empty_root_hash).unwrap().is_some());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn prune_fails_if_unknown_root_is_given() {
+        let correlation_id = CorrelationId::new();
+        let (_temp_dir, state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        let result = state
+            .prune(correlation_id, &[root_hash, fake_hash])
+            .unwrap();
+        assert_eq!(result, PruneResult::RootNotFound(fake_hash));
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in TEST_PAIRS.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn diff_returns_keys_with_differing_values() {
+        let correlation_id = CorrelationId::new();
+        let (_temp_dir, state, root_hash) = create_test_state();
+
+        let effects: HashMap<Key, Transform> = {
+            let mut tmp = HashMap::new();
+            // Rewriting a value with itself leaves its key out of the diff.
+            tmp.insert(
+                TEST_PAIRS[0].key,
+                Transform::Write(TEST_PAIRS[0].value.clone()),
+            );
+            tmp.insert(TEST_PAIRS[1].key, Transform::Write(Value::Int32(-2)));
+            tmp.insert(Key::Account([3u8; 32]), Transform::Write(Value::Int32(3)));
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+
+        let expected_keys: BTreeSet<Key> = vec![TEST_PAIRS[1].key, Key::Account([3u8; 32])]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            state.diff(correlation_id, root_hash, updated_hash).unwrap(),
+            DiffResult::Success(expected_keys.clone())
+        );
+        assert_eq!(
+            state.diff(correlation_id, updated_hash, root_hash).unwrap(),
+            DiffResult::Success(expected_keys)
+        );
+        assert_eq!(
+            state.diff(correlation_id, root_hash, root_hash).unwrap(),
+            DiffResult::Success(BTreeSet::new())
+        );
+    }
+
+    #[test]
+    fn diff_fails_if_unknown_root_is_given() {
+        let correlation_id = CorrelationId::new();
+        let (_temp_dir, state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        let result = state.diff(correlation_id, root_hash, fake_hash).unwrap();
+        assert_eq!(result, DiffResult::RootNotFound(fake_hash));
+    }
+
+    #[test]
+    fn exported_tries_can_be_imported_into_empty_state() {
+        let correlation_id = CorrelationId::new();
+        let (_temp_dir, state, root_hash) = create_test_state();
+        let target = InMemoryGlobalState::empty().unwrap();
+        assert!(target.checkout(root_hash).unwrap().is_none());
+
+        let mut cursor = Vec::new();
+        loop {
+            let chunk = match state
+                .get_trie_chunk(correlation_id, root_hash, &cursor)
+                .unwrap()
+            {
+                TrieChunkResult::Success(chunk) => chunk,
+                _ => panic!("get_trie_chunk failed"),
+            };
+            for trie_bytes in &chunk.tries {
+                target.put_trie(correlation_id, trie_bytes).unwrap();
+            }
+            if chunk.cursor.is_empty() {
+                break;
+            }
+            cursor = chunk.cursor;
+        }
+
+        let checkout = target.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in TEST_PAIRS.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn get_trie_chunk_fails_if_unknown_root_is_given() {
+        let correlation_id = CorrelationId::new();
+        let (_temp_dir, state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        let result = state
+            .get_trie_chunk(correlation_id, fake_hash, &[])
+            .unwrap();
+        assert_eq!(result, TrieChunkResult::RootNotFound(fake_hash));
+
+        let result = state
+            .get_trie_chunk(correlation_id, root_hash, &[fake_hash])
+            .unwrap();
+        assert_eq!(result, TrieChunkResult::RootNotFound(fake_hash));
+    }
+
+    #[test]
+    fn put_trie_fails_if_bytes_are_not_a_trie() {
+        let correlation_id = CorrelationId::new();
+        let (_temp_dir, state, root_hash) = create_test_state();
+
+        assert!(state.put_trie(correlation_id, &[42u8; 3]).is_err());
+    }
+
+    #[test]
+    fn named_stores_are_created_when_opening_the_environment() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().to_path_buf();
+        {
+            let environment = RocksDbEnvironment::new(&path).unwrap();
+            assert!(RocksDbTrieStore::new(&environment, Some("named")).is_err());
+            assert!(RocksDbProtocolDataStore::new(&environment, Some("named")).is_err());
+        }
+        {
+            let environment = RocksDbEnvironment::with_store_names(&path, &["named"]).unwrap();
+            assert!(RocksDbTrieStore::new(&environment, Some("named")).is_ok());
+            assert!(RocksDbProtocolDataStore::new(&environment, Some("named")).is_ok());
+        }
+        // Once created, the column families are opened along with the database.
+        let environment = RocksDbEnvironment::new(&path).unwrap();
+        assert!(RocksDbTrieStore::new(&environment, Some("named")).is_ok());
+        assert!(RocksDbProtocolDataStore::new(&environment, Some("named")).is_ok());
+    }
+}
@@ -0,0 +1,452 @@
+//! A global state which buffers its writes in memory on top of an [`LmdbGlobalState`].
+//!
+//! Block proposers execute and commit deploys whose effects are thrown away unless the block is
+//! finalized.  Committing them to a scratch state keeps that churn out of LMDB: the resulting
+//! tries are either written to LMDB at once with [`ScratchGlobalState::flush`], or dropped with
+//! [`ScratchGlobalState::discard`].
+
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::Value;
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use engine_shared::transform::Transform;
+
+use crate::error;
+use crate::global_state::lmdb::LmdbGlobalState;
+use crate::global_state::{
+    commit, commit_batch, commit_with_protocol_data, diff, get_trie_chunk, missing_trie_keys,
+    prune, put_trie, verify_integrity, CommitBatchResult, CommitResult, DiffResult,
+    IntegrityReport, PruneResult, StateProvider, TrieChunkResult,
+};
+use crate::global_state::{StateProofReader, StateReader};
+use crate::protocol_data::ProtocolData;
+use crate::protocol_data_store::lmdb::LmdbProtocolDataStore;
+use crate::protocol_data_store::ProtocolVersion;
+use crate::store::Store;
+use crate::transaction_source::scratch::ScratchEnvironment;
+use crate::transaction_source::{Transaction, TransactionSource};
+use crate::trie::{Trie, TrieMerkleProof};
+use crate::trie_store::lmdb::LmdbTrieStore;
+use crate::trie_store::operations::{read, read_with_proof, ReadResult};
+
+pub struct ScratchGlobalState {
+    pub environment: Arc<ScratchEnvironment>,
+    pub trie_store: Arc<LmdbTrieStore>,
+    pub protocol_data_store: Arc<LmdbProtocolDataStore>,
+    pub empty_root_hash: Blake2bHash,
+}
+
+/// Represents a "view" of global state at a particular root hash.
+pub struct ScratchGlobalStateView {
+    pub environment: Arc<ScratchEnvironment>,
+    pub store: Arc<LmdbTrieStore>,
+    pub root_hash: Blake2bHash,
+}
+
+impl ScratchGlobalState {
+    /// Creates a scratch state over the stores of `state`, with nothing buffered yet.
+    pub fn new(state: &LmdbGlobalState) -> Self {
+        ScratchGlobalState {
+            environment: Arc::new(ScratchEnvironment::new(Arc::clone(&state.environment))),
+            trie_store: Arc::clone(&state.trie_store),
+            protocol_data_store: Arc::clone(&state.protocol_data_store),
+            empty_root_hash: state.empty_root_hash,
+        }
+    }
+
+    /// Returns true if no writes are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.environment.is_empty()
+    }
+
+    /// Writes everything committed to this state so far to LMDB, atomically.
+    pub fn flush(&self) -> Result<(), error::Error> {
+        self.environment.flush().map_err(Into::into)
+    }
+
+    /// Drops everything committed to this state so far.  Roots committed since the last flush
+    /// can no longer be checked out.
+    pub fn discard(&self) {
+        self.environment.discard()
+    }
+}
+
+impl StateReader<Key, Value> for ScratchGlobalStateView {
+    type Error = error::Error;
+
+    fn read(&self, correlation_id: CorrelationId, key: &Key) -> Result<Option<Value>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read::<Key, Value, _, LmdbTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(value) => Some(value),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("ScratchGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
+impl StateProofReader<Key, Value> for ScratchGlobalStateView {
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, Value>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<Key, Value, _, _, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("ScratchGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
+impl StateProvider for ScratchGlobalState {
+    type Error = error::Error;
+
+    type Reader = ScratchGlobalStateView;
+
+    fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let maybe_root: Option<Trie<Key, Value>> = self.trie_store.get(&txn, &state_hash)?;
+        let maybe_state = maybe_root.map(|_| ScratchGlobalStateView {
+            environment: Arc::clone(&self.environment),
+            store: Arc::clone(&self.trie_store),
+            root_hash: state_hash,
+        });
+        txn.commit()?;
+        Ok(maybe_state)
+    }
+
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: HashMap<Key, Transform>,
+    ) -> Result<CommitResult, Self::Error> {
+        commit::<ScratchEnvironment, LmdbTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            effects,
+        )
+    }
+
+    fn commit_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: Vec<(Blake2bHash, HashMap<Key, Transform>)>,
+    ) -> Result<CommitBatchResult, Self::Error> {
+        commit_batch::<ScratchEnvironment, LmdbTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            batch,
+        )
+    }
+
+    fn commit_with_protocol_data(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: HashMap<Key, Transform>,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<CommitResult, Self::Error> {
+        commit_with_protocol_data::<ScratchEnvironment, LmdbTrieStore, _, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            &*self.protocol_data_store,
+            correlation_id,
+            prestate_hash,
+            effects,
+            protocol_version,
+            protocol_data,
+        )
+    }
+
+    fn put_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        self.protocol_data_store
+            .put(&mut txn, &protocol_version, protocol_data)?;
+        txn.commit().map_err(Into::into)
+    }
+
+    fn get_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ProtocolData>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.protocol_data_store.get(&txn, &protocol_version)?;
+        txn.commit()?;
+        Ok(result)
+    }
+
+    fn empty_root(&self) -> Blake2bHash {
+        self.empty_root_hash
+    }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retain_roots: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error> {
+        let retain_roots = [retain_roots, &[self.empty_root_hash]].concat();
+        let prune_result = prune::<ScratchEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            &retain_roots,
+        )?;
+        Ok(prune_result)
+    }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        poststate_hash: Blake2bHash,
+    ) -> Result<DiffResult, Self::Error> {
+        let diff_result = diff::<ScratchEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            poststate_hash,
+        )?;
+        Ok(diff_result)
+    }
+
+    fn get_trie_chunk(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        cursor: &[Blake2bHash],
+    ) -> Result<TrieChunkResult, Self::Error> {
+        let trie_chunk_result = get_trie_chunk::<ScratchEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            root_hash,
+            cursor,
+        )?;
+        Ok(trie_chunk_result)
+    }
+
+    fn put_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_bytes: &[u8],
+    ) -> Result<Blake2bHash, Self::Error> {
+        let trie_hash = put_trie::<ScratchEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            trie_bytes,
+        )?;
+        Ok(trie_hash)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let missing_trie_keys = missing_trie_keys::<ScratchEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            root_hash,
+        )?;
+        Ok(missing_trie_keys)
+    }
+
+    fn verify_integrity(
+        &self,
+        correlation_id: CorrelationId,
+        root_hashes: &[Blake2bHash],
+    ) -> Result<IntegrityReport, Self::Error> {
+        verify_integrity::<ScratchEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            root_hashes,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lmdb::DatabaseFlags;
+    use tempfile::{tempdir, TempDir};
+
+    use crate::transaction_source::lmdb::LmdbEnvironment;
+    use crate::TEST_MAP_SIZE;
+
+    use super::*;
+
+    const TEST_PAIRS: [(Key, Value); 2] = [
+        (Key::Account([1u8; 32]), Value::Int32(1)),
+        (Key::Account([2u8; 32]), Value::Int32(2)),
+    ];
+
+    fn create_lmdb_state() -> (TempDir, LmdbGlobalState) {
+        let temp_dir = tempdir().unwrap();
+        let environment =
+            Arc::new(LmdbEnvironment::new(&temp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap());
+        let trie_store =
+            Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        let protocol_data_store = Arc::new(
+            LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        (temp_dir, state)
+    }
+
+    fn test_effects() -> HashMap<Key, Transform> {
+        TEST_PAIRS
+            .iter()
+            .map(|(key, value)| (*key, Transform::Write(value.to_owned())))
+            .collect()
+    }
+
+    fn commit_test_effects<S>(state: &S) -> Blake2bHash
+    where
+        S: StateProvider,
+        S::Error: std::fmt::Debug,
+    {
+        let correlation_id = CorrelationId::new();
+        match state
+            .commit(correlation_id, state.empty_root(), test_effects())
+            .unwrap()
+        {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        }
+    }
+
+    fn assert_test_pairs_readable<S>(state: &S, root_hash: Blake2bHash)
+    where
+        S: StateProvider,
+        S::Error: std::fmt::Debug,
+    {
+        let correlation_id = CorrelationId::new();
+        let checkout = state
+            .checkout(root_hash)
+            .unwrap()
+            .expect("should check out");
+        for (key, value) in TEST_PAIRS.iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn commit_is_readable_from_scratch_but_not_from_lmdb() {
+        let (_temp_dir, lmdb_state) = create_lmdb_state();
+        let scratch_state = ScratchGlobalState::new(&lmdb_state);
+
+        let root_hash = commit_test_effects(&scratch_state);
+
+        assert!(!scratch_state.is_empty());
+        assert_test_pairs_readable(&scratch_state, root_hash);
+        assert!(lmdb_state.checkout(root_hash).unwrap().is_none());
+    }
+
+    #[test]
+    fn flush_writes_commits_to_lmdb() {
+        let (_temp_dir, lmdb_state) = create_lmdb_state();
+        let scratch_state = ScratchGlobalState::new(&lmdb_state);
+        let root_hash = commit_test_effects(&scratch_state);
+
+        scratch_state.flush().unwrap();
+
+        assert!(scratch_state.is_empty());
+        assert_test_pairs_readable(&lmdb_state, root_hash);
+        assert_test_pairs_readable(&scratch_state, root_hash);
+    }
+
+    #[test]
+    fn discard_drops_commits() {
+        let (_temp_dir, lmdb_state) = create_lmdb_state();
+        let scratch_state = ScratchGlobalState::new(&lmdb_state);
+        let root_hash = commit_test_effects(&scratch_state);
+
+        scratch_state.discard();
+
+        assert!(scratch_state.is_empty());
+        assert!(scratch_state.checkout(root_hash).unwrap().is_none());
+        assert!(lmdb_state.checkout(root_hash).unwrap().is_none());
+        assert!(scratch_state
+            .checkout(scratch_state.empty_root())
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn commits_build_on_lmdb_state() {
+        let (_temp_dir, lmdb_state) = create_lmdb_state();
+        let root_hash = commit_test_effects(&lmdb_state);
+        let scratch_state = ScratchGlobalState::new(&lmdb_state);
+
+        let correlation_id = CorrelationId::new();
+        let key = Key::Account([3u8; 32]);
+        let mut effects = HashMap::new();
+        effects.insert(key, Transform::Write(Value::Int32(3)));
+        let updated_hash = match scratch_state
+            .commit(correlation_id, root_hash, effects)
+            .unwrap()
+        {
+            CommitResult::Success(hash) => hash,
+            _ => panic!("commit failed"),
+        };
+
+        assert_test_pairs_readable(&scratch_state, updated_hash);
+        let checkout = scratch_state.checkout(updated_hash).unwrap().unwrap();
+        assert_eq!(
+            Some(Value::Int32(3)),
+            checkout.read(correlation_id, &key).unwrap()
+        );
+    }
+
+    #[test]
+    fn prune_of_scratch_state_leaves_lmdb_untouched_until_flushed() {
+        let (_temp_dir, lmdb_state) = create_lmdb_state();
+        let root_hash = commit_test_effects(&lmdb_state);
+        let scratch_state = ScratchGlobalState::new(&lmdb_state);
+        let correlation_id = CorrelationId::new();
+
+        match scratch_state.prune(correlation_id, &[]).unwrap() {
+            PruneResult::Success(pruned) => assert!(pruned > 0),
+            _ => panic!("prune failed"),
+        }
+
+        assert!(scratch_state.checkout(root_hash).unwrap().is_none());
+        assert_test_pairs_readable(&lmdb_state, root_hash);
+
+        scratch_state.flush().unwrap();
+
+        assert!(lmdb_state.checkout(root_hash).unwrap().is_none());
+    }
+}
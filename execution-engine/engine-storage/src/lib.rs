@@ -3,8 +3,10 @@
 // modules
 pub mod error;
 pub mod global_state;
+pub mod metrics;
 pub mod protocol_data;
 pub mod protocol_data_store;
+pub mod storage_config;
 pub mod store;
 pub mod transaction_source;
 pub mod trie;
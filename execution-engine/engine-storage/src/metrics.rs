@@ -0,0 +1,278 @@
+//! Process-wide counters and latency histograms describing how global state is used.
+//!
+//! Unlike the per-event metrics sent through `engine_shared::logging`, these accumulate for the
+//! life of the process, so they can be scraped periodically and rendered in the Prometheus text
+//! exposition format by [`scrape_metrics`].
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+
+const LATENCY_BUCKET_COUNT: usize = 10;
+
+/// Upper bounds of the latency histogram buckets, in microseconds.  Every histogram also has an
+/// implicit `+Inf` bucket.
+pub const LATENCY_BUCKET_BOUNDS_MICROS: [u64; LATENCY_BUCKET_COUNT] = [
+    100, 500, 1_000, 5_000, 10_000, 50_000, 100_000, 500_000, 1_000_000, 5_000_000,
+];
+
+const MICROS_PER_SECOND: f64 = 1_000_000.0;
+
+lazy_static! {
+    static ref STORAGE_METRICS: StorageMetrics = StorageMetrics::new();
+}
+
+/// A histogram of durations over [`LATENCY_BUCKET_BOUNDS_MICROS`].
+#[derive(Debug, Default)]
+pub struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKET_COUNT],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    pub fn observe(&self, duration: Duration) {
+        let micros = duration.as_micros() as u64;
+        if let Some(index) = LATENCY_BUCKET_BOUNDS_MICROS
+            .iter()
+            .position(|bound| micros <= *bound)
+        {
+            self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+    }
+
+    /// Returns the number of observations.
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the cumulative bucket counts, i.e. for each bound the number of observations
+    /// lasting at most that long.  The `+Inf` bucket is given by [`Histogram::count`].
+    pub fn cumulative_counts(&self) -> Vec<u64> {
+        self.buckets
+            .iter()
+            .scan(0, |total, bucket| {
+                *total += bucket.load(Ordering::Relaxed);
+                Some(*total)
+            })
+            .collect()
+    }
+
+    fn render(&self, name: &str, help: &str, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        for (bound, count) in LATENCY_BUCKET_BOUNDS_MICROS
+            .iter()
+            .zip(self.cumulative_counts())
+        {
+            let _ = writeln!(
+                out,
+                "{}_bucket{{le=\"{}\"}} {}",
+                name,
+                *bound as f64 / MICROS_PER_SECOND,
+                count
+            );
+        }
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, self.count());
+        let _ = writeln!(
+            out,
+            "{}_sum {}",
+            name,
+            self.sum_micros.load(Ordering::Relaxed) as f64 / MICROS_PER_SECOND
+        );
+        let _ = writeln!(out, "{}_count {}", name, self.count());
+    }
+}
+
+/// The storage metrics of a process.
+#[derive(Debug, Default)]
+pub struct StorageMetrics {
+    reads: Histogram,
+    writes: Histogram,
+    commits: Histogram,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    trie_cache_hits: AtomicU64,
+    trie_cache_misses: AtomicU64,
+}
+
+impl StorageMetrics {
+    pub fn new() -> StorageMetrics {
+        Default::default()
+    }
+
+    /// Records a read of a single key from a trie store.
+    pub fn record_read(&self, duration: Duration) {
+        self.reads.observe(duration)
+    }
+
+    /// Records a write of a single key to a trie store.
+    pub fn record_write(&self, duration: Duration) {
+        self.writes.observe(duration)
+    }
+
+    /// Records the commit of a set of effects to global state.
+    pub fn record_commit(&self, duration: Duration) {
+        self.commits.observe(duration)
+    }
+
+    /// Records a read served from a cache in front of global state.
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a read which missed a cache in front of global state and went to the trie store.
+    pub fn record_cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a trie value served from the trie cache.
+    pub fn record_trie_cache_hit(&self) {
+        self.trie_cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a trie value which missed the trie cache and was read from the trie store.
+    pub fn record_trie_cache_miss(&self) {
+        self.trie_cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn reads(&self) -> &Histogram {
+        &self.reads
+    }
+
+    pub fn writes(&self) -> &Histogram {
+        &self.writes
+    }
+
+    pub fn commits(&self) -> &Histogram {
+        &self.commits
+    }
+
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    pub fn cache_misses(&self) -> u64 {
+        self.cache_misses.load(Ordering::Relaxed)
+    }
+
+    pub fn trie_cache_hits(&self) -> u64 {
+        self.trie_cache_hits.load(Ordering::Relaxed)
+    }
+
+    pub fn trie_cache_misses(&self) -> u64 {
+        self.trie_cache_misses.load(Ordering::Relaxed)
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        render_counter(
+            "global_state_cache_hits_total",
+            "Number of global state reads served from the cache.",
+            self.cache_hits(),
+            &mut out,
+        );
+        render_counter(
+            "global_state_cache_misses_total",
+            "Number of global state reads which missed the cache.",
+            self.cache_misses(),
+            &mut out,
+        );
+        render_counter(
+            "global_state_trie_cache_hits_total",
+            "Number of trie values served from the trie cache.",
+            self.trie_cache_hits(),
+            &mut out,
+        );
+        render_counter(
+            "global_state_trie_cache_misses_total",
+            "Number of trie values which missed the trie cache.",
+            self.trie_cache_misses(),
+            &mut out,
+        );
+        self.reads.render(
+            "global_state_read_duration_seconds",
+            "Duration of reads of a single key from the trie store.",
+            &mut out,
+        );
+        self.writes.render(
+            "global_state_write_duration_seconds",
+            "Duration of writes of a single key to the trie store.",
+            &mut out,
+        );
+        self.commits.render(
+            "global_state_commit_duration_seconds",
+            "Duration of commits of effects to global state.",
+            &mut out,
+        );
+        out
+    }
+}
+
+fn render_counter(name: &str, help: &str, value: u64, out: &mut String) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} counter", name);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// Returns the storage metrics of this process.
+pub fn storage_metrics() -> &'static StorageMetrics {
+    &STORAGE_METRICS
+}
+
+/// Renders the storage metrics of this process in the Prometheus text exposition format.
+pub fn scrape_metrics() -> String {
+    STORAGE_METRICS.render()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn histogram_counts_observations_in_cumulative_buckets() {
+        let histogram = Histogram::default();
+        histogram.observe(Duration::from_micros(50));
+        histogram.observe(Duration::from_micros(100));
+        histogram.observe(Duration::from_millis(2));
+        histogram.observe(Duration::from_secs(10));
+
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(
+            histogram.cumulative_counts(),
+            vec![2, 2, 2, 3, 3, 3, 3, 3, 3, 3]
+        );
+    }
+
+    #[test]
+    fn render_should_produce_prometheus_text() {
+        let metrics = StorageMetrics::new();
+        metrics.record_cache_hit();
+        metrics.record_cache_hit();
+        metrics.record_cache_miss();
+        metrics.record_trie_cache_miss();
+        metrics.record_commit(Duration::from_millis(20));
+
+        let text = metrics.render();
+
+        assert!(text.contains("# TYPE global_state_cache_hits_total counter\n"));
+        assert!(text.contains("global_state_cache_hits_total 2\n"));
+        assert!(text.contains("global_state_cache_misses_total 1\n"));
+        assert!(text.contains("global_state_trie_cache_hits_total 0\n"));
+        assert!(text.contains("global_state_trie_cache_misses_total 1\n"));
+        assert!(text.contains("# TYPE global_state_commit_duration_seconds histogram\n"));
+        assert!(text.contains("global_state_commit_duration_seconds_bucket{le=\"0.01\"} 0\n"));
+        assert!(text.contains("global_state_commit_duration_seconds_bucket{le=\"0.05\"} 1\n"));
+        assert!(text.contains("global_state_commit_duration_seconds_bucket{le=\"+Inf\"} 1\n"));
+        assert!(text.contains("global_state_commit_duration_seconds_sum 0.02\n"));
+        assert!(text.contains("global_state_commit_duration_seconds_count 1\n"));
+        assert!(text.contains("global_state_read_duration_seconds_count 0\n"));
+    }
+}
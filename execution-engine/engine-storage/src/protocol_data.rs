@@ -1,29 +1,75 @@
 use contract_ffi::bytesrepr;
-use contract_ffi::bytesrepr::{FromBytes, ToBytes};
+use contract_ffi::bytesrepr::{FromBytes, ToBytes, U64_SIZE};
+use contract_ffi::system_contracts::pos::RefundRatio;
+use engine_shared::key_limits::{KeyLimits, KEY_LIMITS_SIZE_SERIALIZED};
 use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SIZE_SERIALIZED};
 
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
     wasm_costs: WasmCosts,
+    refund_ratio: RefundRatio,
+    min_gas_price: u64,
+    native_transfer_cost: u64,
+    key_limits: KeyLimits,
 }
 
 impl ProtocolData {
-    /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value.
-    pub fn new(wasm_costs: WasmCosts) -> Self {
-        ProtocolData { wasm_costs }
+    /// Creates a new [`ProtocolData`] value from given [`WasmCosts`] and [`RefundRatio`] values,
+    /// a minimum gas price, the flat cost of a native transfer and the [`KeyLimits`] accounts are
+    /// subject to.
+    pub fn new(
+        wasm_costs: WasmCosts,
+        refund_ratio: RefundRatio,
+        min_gas_price: u64,
+        native_transfer_cost: u64,
+        key_limits: KeyLimits,
+    ) -> Self {
+        ProtocolData {
+            wasm_costs,
+            refund_ratio,
+            min_gas_price,
+            native_transfer_cost,
+            key_limits,
+        }
     }
 
     /// Gets the [`WasmCosts`] value from a given [`ProtocolData`] value.
     pub fn wasm_costs(&self) -> &WasmCosts {
         &self.wasm_costs
     }
+
+    /// Gets the [`RefundRatio`] value from a given [`ProtocolData`] value.
+    pub fn refund_ratio(&self) -> RefundRatio {
+        self.refund_ratio
+    }
+
+    /// Gets the minimum gas price, in motes per unit of gas, from a given [`ProtocolData`] value.
+    pub fn min_gas_price(&self) -> u64 {
+        self.min_gas_price
+    }
+
+    /// Gets the gas charged for a native transfer from a given [`ProtocolData`] value.
+    pub fn native_transfer_cost(&self) -> u64 {
+        self.native_transfer_cost
+    }
+
+    /// Gets the [`KeyLimits`] value from a given [`ProtocolData`] value.
+    pub fn key_limits(&self) -> KeyLimits {
+        self.key_limits
+    }
 }
 
 impl ToBytes for ProtocolData {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let mut ret: Vec<u8> = Vec::with_capacity(WASM_COSTS_SIZE_SERIALIZED);
+        let mut ret: Vec<u8> = Vec::with_capacity(
+            WASM_COSTS_SIZE_SERIALIZED + 4 * U64_SIZE + KEY_LIMITS_SIZE_SERIALIZED,
+        );
         ret.append(&mut self.wasm_costs.to_bytes()?);
+        ret.append(&mut self.refund_ratio.to_bytes()?);
+        ret.append(&mut self.min_gas_price.to_bytes()?);
+        ret.append(&mut self.native_transfer_cost.to_bytes()?);
+        ret.append(&mut self.key_limits.to_bytes()?);
         Ok(ret)
     }
 }
@@ -31,7 +77,26 @@ impl ToBytes for ProtocolData {
 impl FromBytes for ProtocolData {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (wasm_costs, rem): (WasmCosts, &[u8]) = FromBytes::from_bytes(bytes)?;
-        Ok((ProtocolData { wasm_costs }, rem))
+        let (refund_ratio, rem): (RefundRatio, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (min_gas_price, rem): (u64, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (native_transfer_cost, rem): (u64, &[u8]) = FromBytes::from_bytes(rem)?;
+        // Protocol data stored before key limits were introduced ends here; those protocol
+        // versions keep the limits accounts were subject to back then.
+        let (key_limits, rem): (KeyLimits, &[u8]) = if rem.is_empty() {
+            (KeyLimits::default(), rem)
+        } else {
+            FromBytes::from_bytes(rem)?
+        };
+        Ok((
+            ProtocolData {
+                wasm_costs,
+                refund_ratio,
+                min_gas_price,
+                native_transfer_cost,
+                key_limits,
+            },
+            rem,
+        ))
     }
 }
 
@@ -39,13 +104,36 @@ impl FromBytes for ProtocolData {
 pub(crate) mod gens {
     use proptest::prop_compose;
 
+    use contract_ffi::system_contracts::pos::RefundRatio;
+    use engine_shared::key_limits::gens::key_limits_arb;
     use engine_wasm_prep::wasm_costs::gens;
 
     use super::ProtocolData;
 
     prop_compose! {
-        pub fn protocol_data_arb()(wasm_costs in gens::wasm_costs_arb()) -> ProtocolData {
-            ProtocolData { wasm_costs }
+        pub fn refund_ratio_arb()(denominator in 1u64..)(
+            numerator in 0..=denominator,
+            denominator in proptest::strategy::Just(denominator),
+        ) -> RefundRatio {
+            RefundRatio::new(numerator, denominator).unwrap()
+        }
+    }
+
+    prop_compose! {
+        pub fn protocol_data_arb()(
+            wasm_costs in gens::wasm_costs_arb(),
+            refund_ratio in refund_ratio_arb(),
+            min_gas_price in 1u64..,
+            native_transfer_cost in 1u64..,
+            key_limits in key_limits_arb(),
+        ) -> ProtocolData {
+            ProtocolData {
+                wasm_costs,
+                refund_ratio,
+                min_gas_price,
+                native_transfer_cost,
+                key_limits,
+            }
         }
     }
 }
@@ -54,6 +142,9 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
+    use contract_ffi::bytesrepr::{self, ToBytes};
+    use contract_ffi::system_contracts::pos::RefundRatio;
+    use engine_shared::key_limits::KeyLimits;
     use engine_shared::test_utils;
     use engine_wasm_prep::wasm_costs::WasmCosts;
 
@@ -62,17 +153,48 @@ mod tests {
     #[test]
     fn should_serialize_and_deserialize() {
         let v1 = {
-            let costs = WasmCosts::from_version(1).unwrap();
-            ProtocolData::new(costs)
+            let costs = WasmCosts::default();
+            ProtocolData::new(
+                costs,
+                RefundRatio::default(),
+                1,
+                10_000,
+                KeyLimits::default(),
+            )
         };
         let free = {
             let costs = WasmCosts::free();
-            ProtocolData::new(costs)
+            ProtocolData::new(
+                costs,
+                RefundRatio::new(1, 3).unwrap(),
+                10,
+                1,
+                KeyLimits::new(1, 1),
+            )
         };
         assert!(test_utils::test_serialization_roundtrip(&v1));
         assert!(test_utils::test_serialization_roundtrip(&free));
     }
 
+    #[test]
+    fn should_deserialize_protocol_data_without_key_limits() {
+        let protocol_data = ProtocolData::new(
+            WasmCosts::default(),
+            RefundRatio::default(),
+            1,
+            10_000,
+            KeyLimits::new(20, 100),
+        );
+        let mut bytes = protocol_data.to_bytes().unwrap();
+        let key_limits_len = KeyLimits::default().to_bytes().unwrap().len();
+        bytes.truncate(bytes.len() - key_limits_len);
+
+        let deserialized: ProtocolData = bytesrepr::deserialize(&bytes).unwrap();
+
+        assert_eq!(deserialized.key_limits(), KeyLimits::default());
+        assert_eq!(deserialized.native_transfer_cost(), 10_000);
+    }
+
     proptest! {
         #[test]
         fn should_serialize_and_deserialize_with_arbitrary_values(
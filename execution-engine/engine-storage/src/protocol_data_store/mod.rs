@@ -2,13 +2,14 @@
 
 pub mod in_memory;
 pub mod lmdb;
+pub mod rocksdb;
 #[cfg(test)]
 mod tests;
 
 use crate::protocol_data::ProtocolData;
 use crate::store::Store;
 
-const NAME: &str = "PROTOCOL_DATA_STORE";
+pub(crate) const NAME: &str = "PROTOCOL_DATA_STORE";
 
 /// TODO: update to reflect semantic versioning
 pub type ProtocolVersion = u64;
@@ -0,0 +1,37 @@
+use crate::protocol_data::ProtocolData;
+use crate::protocol_data_store::{ProtocolDataStore, ProtocolVersion};
+use crate::store::Store;
+use crate::transaction_source::rocksdb::{column_family_name, RocksDbEnvironment};
+use crate::{error, protocol_data_store};
+
+/// A RocksDB-backed protocol data store.
+///
+/// Protocol data is kept in its own column family of a [`RocksDbEnvironment`].
+#[derive(Debug, Clone)]
+pub struct RocksDbProtocolDataStore {
+    name: String,
+}
+
+impl RocksDbProtocolDataStore {
+    /// Returns a store backed by the column family of the given name.
+    ///
+    /// The environment must have been opened with `maybe_name`, see
+    /// [`RocksDbEnvironment::with_store_names`].
+    pub fn new(env: &RocksDbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let name = column_family_name(protocol_data_store::NAME, maybe_name);
+        env.check_column_family(&name)?;
+        Ok(RocksDbProtocolDataStore { name })
+    }
+}
+
+impl Store<ProtocolVersion, ProtocolData> for RocksDbProtocolDataStore {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn handle(&self) -> Self::Handle {
+        self.name.to_owned()
+    }
+}
+
+impl ProtocolDataStore for RocksDbProtocolDataStore {}
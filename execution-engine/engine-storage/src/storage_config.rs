@@ -0,0 +1,105 @@
+//! The configuration of the LMDB environment backing global state.
+
+/// Default size of the memory map, in bytes.  Matches LMDB's own default.
+pub const DEFAULT_MAP_SIZE: usize = 10 * 1024 * 1024;
+
+/// Default maximum number of concurrent read transactions.  Matches LMDB's own default.
+pub const DEFAULT_MAX_READERS: u32 = 126;
+
+/// Default number of trie values kept in the trie cache.
+pub const DEFAULT_TRIE_CACHE_SIZE: usize = 1024;
+
+/// The settings an `LmdbEnvironment` is opened with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageConfig {
+    map_size: usize,
+    max_readers: u32,
+    no_sync: bool,
+    write_map: bool,
+    auto_resize: bool,
+    trie_cache_size: usize,
+}
+
+impl StorageConfig {
+    /// Creates a new storage configuration with default parameters.
+    pub fn new() -> StorageConfig {
+        Default::default()
+    }
+
+    /// Sets the `map_size` field to the given arg.  It should be a multiple of the OS page size.
+    pub fn set_map_size(mut self, arg: usize) -> StorageConfig {
+        self.map_size = arg;
+        self
+    }
+
+    pub fn map_size(&self) -> usize {
+        self.map_size
+    }
+
+    /// Sets the `max_readers` field to the given arg.
+    pub fn set_max_readers(mut self, arg: u32) -> StorageConfig {
+        self.max_readers = arg;
+        self
+    }
+
+    pub fn max_readers(&self) -> u32 {
+        self.max_readers
+    }
+
+    /// Sets the `no_sync` field to the given arg.  When true, commits aren't flushed to disk, so
+    /// the last transactions may be lost if the system crashes.
+    pub fn set_no_sync(mut self, arg: bool) -> StorageConfig {
+        self.no_sync = arg;
+        self
+    }
+
+    pub fn no_sync(&self) -> bool {
+        self.no_sync
+    }
+
+    /// Sets the `write_map` field to the given arg.  When true, writes go straight to a writeable
+    /// memory map, which is faster but lets stray pointer writes corrupt the database.
+    pub fn set_write_map(mut self, arg: bool) -> StorageConfig {
+        self.write_map = arg;
+        self
+    }
+
+    pub fn write_map(&self) -> bool {
+        self.write_map
+    }
+
+    /// Sets the `auto_resize` field to the given arg.  When true, the memory map is doubled and
+    /// the write retried whenever a write fails because the map is full.
+    pub fn set_auto_resize(mut self, arg: bool) -> StorageConfig {
+        self.auto_resize = arg;
+        self
+    }
+
+    pub fn auto_resize(&self) -> bool {
+        self.auto_resize
+    }
+
+    /// Sets the `trie_cache_size` field to the given arg.  It is the number of trie values cached
+    /// for reads shared across exec requests; zero disables the cache.
+    pub fn set_trie_cache_size(mut self, arg: usize) -> StorageConfig {
+        self.trie_cache_size = arg;
+        self
+    }
+
+    pub fn trie_cache_size(&self) -> usize {
+        self.trie_cache_size
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        StorageConfig {
+            map_size: DEFAULT_MAP_SIZE,
+            max_readers: DEFAULT_MAX_READERS,
+            no_sync: false,
+            write_map: false,
+            auto_resize: false,
+            trie_cache_size: DEFAULT_TRIE_CACHE_SIZE,
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::sync::{self, Arc, Mutex, MutexGuard};
 
 use crate::error::in_memory::Error;
-use crate::transaction_source::{Readable, Transaction, TransactionSource, Writable};
+use crate::transaction_source::{Deletable, Readable, Transaction, TransactionSource, Writable};
 
 /// A marker for use in a mutex which represents the capability to perform a
 /// write transaction.
@@ -103,6 +103,23 @@ impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
     }
 }
 
+impl<'a> Deletable for InMemoryReadWriteTransaction<'a> {
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let ret = match self.view.get(&handle) {
+            Some(sub_view) => sub_view.keys().cloned().collect(),
+            None => Vec::new(),
+        };
+        Ok(ret)
+    }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        if let Some(sub_view) = self.view.get_mut(&handle) {
+            sub_view.remove(key);
+        }
+        Ok(())
+    }
+}
+
 /// An environment for the in-memory trie store.
 pub struct InMemoryEnvironment {
     data: Arc<Mutex<HashMap<Option<String>, BytesMap>>>,
@@ -1,10 +1,19 @@
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard};
 
-use lmdb::{self, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use lmdb::{
+    self, Database, Environment, EnvironmentFlags, RoTransaction, RwTransaction, WriteFlags,
+};
 
-use crate::transaction_source::{Readable, Transaction, TransactionSource, Writable};
+use crate::storage_config::StorageConfig;
+use crate::transaction_source::{Deletable, Readable, Transaction, TransactionSource, Writable};
 use crate::{error, MAX_DBS};
 
+/// Held by every open transaction, as the memory map may only be resized while no transaction
+/// is open.
+type ResizeGuard<'a> = RwLockReadGuard<'a, ()>;
+
 impl<'a> Transaction for RoTransaction<'a> {
     type Error = lmdb::Error;
 
@@ -52,6 +61,98 @@ impl<'a> Writable for RwTransaction<'a> {
     }
 }
 
+impl<'a> Deletable for RwTransaction<'a> {
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let mut cursor = lmdb::Transaction::open_ro_cursor(self, handle)?;
+        let ret = lmdb::Cursor::iter(&mut cursor)
+            .map(|(key, _)| key.to_vec())
+            .collect();
+        Ok(ret)
+    }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        match self.del(handle, &key, None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// A read transaction for the LMDB-backed trie store.
+///
+/// Wraps [`lmdb::RoTransaction`], keeping the memory map from being resized while it is open.
+pub struct LmdbReadTransaction<'a> {
+    txn: RoTransaction<'a>,
+    _resize_guard: ResizeGuard<'a>,
+}
+
+impl<'a> Transaction for LmdbReadTransaction<'a> {
+    type Error = lmdb::Error;
+
+    type Handle = Database;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        Transaction::commit(self.txn)
+    }
+}
+
+impl<'a> Readable for LmdbReadTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        self.txn.read(handle, key)
+    }
+}
+
+impl<'a> LmdbReadTransaction<'a> {
+    /// Returns all keys in a given database.
+    pub(crate) fn keys(&self, handle: Database) -> Result<Vec<Vec<u8>>, lmdb::Error> {
+        let mut cursor = lmdb::Transaction::open_ro_cursor(&self.txn, handle)?;
+        let ret = lmdb::Cursor::iter(&mut cursor)
+            .map(|(key, _)| key.to_vec())
+            .collect();
+        Ok(ret)
+    }
+}
+
+/// A read-write transaction for the LMDB-backed trie store.
+///
+/// Wraps [`lmdb::RwTransaction`], keeping the memory map from being resized while it is open.
+pub struct LmdbReadWriteTransaction<'a> {
+    txn: RwTransaction<'a>,
+    _resize_guard: ResizeGuard<'a>,
+}
+
+impl<'a> Transaction for LmdbReadWriteTransaction<'a> {
+    type Error = lmdb::Error;
+
+    type Handle = Database;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        Transaction::commit(self.txn)
+    }
+}
+
+impl<'a> Readable for LmdbReadWriteTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        self.txn.read(handle, key)
+    }
+}
+
+impl<'a> Writable for LmdbReadWriteTransaction<'a> {
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        self.txn.write(handle, key, value)
+    }
+}
+
+impl<'a> Deletable for LmdbReadWriteTransaction<'a> {
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        self.txn.keys(handle)
+    }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        self.txn.delete(handle, key)
+    }
+}
+
 /// The environment for an LMDB-backed trie store.
 ///
 /// Wraps [`lmdb::Environment`].
@@ -59,16 +160,39 @@ impl<'a> Writable for RwTransaction<'a> {
 pub struct LmdbEnvironment {
     path: PathBuf,
     env: Environment,
+    config: StorageConfig,
+    map_size: AtomicUsize,
+    resize_lock: RwLock<()>,
 }
 
 impl LmdbEnvironment {
     pub fn new(path: &PathBuf, map_size: usize) -> Result<Self, error::Error> {
+        Self::with_config(path, StorageConfig::new().set_map_size(map_size))
+    }
+
+    /// Opens the environment at `path` with the given settings.
+    pub fn with_config(path: &PathBuf, config: StorageConfig) -> Result<Self, error::Error> {
+        let mut flags = EnvironmentFlags::empty();
+        if config.no_sync() {
+            flags.insert(EnvironmentFlags::NO_SYNC);
+        }
+        if config.write_map() {
+            flags.insert(EnvironmentFlags::WRITE_MAP);
+        }
         let env = Environment::new()
+            .set_flags(flags)
             .set_max_dbs(MAX_DBS)
-            .set_map_size(map_size)
+            .set_max_readers(config.max_readers())
+            .set_map_size(config.map_size())
             .open(path)?;
         let path = path.to_owned();
-        Ok(LmdbEnvironment { path, env })
+        Ok(LmdbEnvironment {
+            path,
+            env,
+            config,
+            map_size: AtomicUsize::new(config.map_size()),
+            resize_lock: RwLock::new(()),
+        })
     }
 
     pub fn path(&self) -> &PathBuf {
@@ -78,6 +202,40 @@ impl LmdbEnvironment {
     pub fn env(&self) -> &Environment {
         &self.env
     }
+
+    pub fn config(&self) -> &StorageConfig {
+        &self.config
+    }
+
+    /// Returns the current size of the memory map, in bytes.
+    pub fn map_size(&self) -> usize {
+        self.map_size.load(Ordering::SeqCst)
+    }
+
+    /// Resizes the memory map to `map_size` bytes, waiting for every open transaction to end
+    /// first.
+    ///
+    /// Must not be called while the calling thread holds a transaction, which would never end.
+    pub fn set_map_size(&self, map_size: usize) -> Result<(), error::Error> {
+        let _resize_lock = self
+            .resize_lock
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Safe as no transaction is open while the resize lock is held for writing.
+        let result = unsafe { lmdb_sys::mdb_env_set_mapsize(self.env.env(), map_size) };
+        if result != lmdb_sys::MDB_SUCCESS {
+            return Err(lmdb::Error::from_err_code(result).into());
+        }
+        self.map_size.store(map_size, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn resize_guard(&self) -> ResizeGuard {
+        // The lock guards no data, so it is still usable if a thread panicked while holding it.
+        self.resize_lock
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
 }
 
 impl<'a> TransactionSource<'a> for LmdbEnvironment {
@@ -85,15 +243,19 @@ impl<'a> TransactionSource<'a> for LmdbEnvironment {
 
     type Handle = Database;
 
-    type ReadTransaction = RoTransaction<'a>;
+    type ReadTransaction = LmdbReadTransaction<'a>;
 
-    type ReadWriteTransaction = RwTransaction<'a>;
+    type ReadWriteTransaction = LmdbReadWriteTransaction<'a>;
 
-    fn create_read_txn(&'a self) -> Result<RoTransaction<'a>, Self::Error> {
-        self.env.begin_ro_txn()
+    fn create_read_txn(&'a self) -> Result<LmdbReadTransaction<'a>, Self::Error> {
+        let _resize_guard = self.resize_guard();
+        let txn = self.env.begin_ro_txn()?;
+        Ok(LmdbReadTransaction { txn, _resize_guard })
     }
 
-    fn create_read_write_txn(&'a self) -> Result<RwTransaction<'a>, Self::Error> {
-        self.env.begin_rw_txn()
+    fn create_read_write_txn(&'a self) -> Result<LmdbReadWriteTransaction<'a>, Self::Error> {
+        let _resize_guard = self.resize_guard();
+        let txn = self.env.begin_rw_txn()?;
+        Ok(LmdbReadWriteTransaction { txn, _resize_guard })
     }
 }
@@ -1,5 +1,7 @@
 pub mod in_memory;
 pub mod lmdb;
+pub mod rocksdb;
+pub mod scratch;
 
 /// A transaction which can be committed or aborted.
 pub trait Transaction: Sized {
@@ -33,6 +35,18 @@ pub trait Writable: Transaction {
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error>;
 }
 
+/// A transaction with the capability to list and delete the entries of a given
+/// [`Handle`](Transaction::Handle).
+pub trait Deletable: Readable + Writable {
+    /// Returns all keys in a given [`Transaction::Handle`].
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error>;
+
+    /// Removes the key-value pair with the given key from a given [`Transaction::Handle`].
+    ///
+    /// Deleting a key which does not exist is not an error.
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error>;
+}
+
 /// A source of transactions e.g. values that implement [`Readable`]
 /// and/or [`Writable`].
 pub trait TransactionSource<'a> {
@@ -48,7 +62,8 @@ pub trait TransactionSource<'a> {
 
     /// Represents the type of read-write transactions.
     type ReadWriteTransaction: Readable<Error = Self::Error, Handle = Self::Handle>
-        + Writable<Error = Self::Error, Handle = Self::Handle>;
+        + Writable<Error = Self::Error, Handle = Self::Handle>
+        + Deletable<Error = Self::Error, Handle = Self::Handle>;
 
     /// Creates a read transaction.
     fn create_read_txn(&'a self) -> Result<Self::ReadTransaction, Self::Error>;
@@ -0,0 +1,246 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+use rocksdb::{
+    ColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options, Snapshot, WriteBatch, DB,
+};
+
+use crate::error::rocksdb::Error;
+use crate::transaction_source::{Deletable, Readable, Transaction, TransactionSource, Writable};
+use crate::{error, protocol_data_store, trie_store};
+
+/// A marker for use in a mutex which represents the capability to perform a
+/// write transaction.
+struct WriteCapability;
+
+type WriteLock<'a> = MutexGuard<'a, WriteCapability>;
+
+/// Returns the name of the column family of the store with the given base name, optionally
+/// qualified by `maybe_name`, e.g. `TRIE_STORE-foo`.
+pub(crate) fn column_family_name(base_name: &str, maybe_name: Option<&str>) -> String {
+    maybe_name
+        .map(|name| format!("{}-{}", base_name, name))
+        .unwrap_or_else(|| String::from(base_name))
+}
+
+fn column_family<'a>(db: &'a DB, name: &str) -> Result<ColumnFamily<'a>, Error> {
+    db.cf_handle(name)
+        .ok_or_else(|| Error::ColumnFamilyNotFound(name.to_string()))
+}
+
+/// A read transaction for the RocksDB-backed trie store.
+///
+/// Reads are served from a [`rocksdb::Snapshot`] taken when the transaction
+/// was created.
+pub struct RocksDbReadTransaction<'a> {
+    db: &'a DB,
+    snapshot: Snapshot<'a>,
+}
+
+impl<'a> RocksDbReadTransaction<'a> {
+    pub fn new(env: &'a RocksDbEnvironment) -> Result<RocksDbReadTransaction<'a>, Error> {
+        let db = env.db();
+        let snapshot = db.snapshot();
+        Ok(RocksDbReadTransaction { db, snapshot })
+    }
+}
+
+impl<'a> Transaction for RocksDbReadTransaction<'a> {
+    type Error = Error;
+
+    type Handle = String;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a> Readable for RocksDbReadTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        let cf = column_family(self.db, &handle)?;
+        let ret = self.snapshot.get_cf(cf, key)?;
+        Ok(ret.map(|bytes| bytes.to_vec()))
+    }
+}
+
+/// A read-write transaction for the RocksDB-backed trie store.
+///
+/// Writes and deletions are buffered in memory and applied atomically as a
+/// single [`rocksdb::WriteBatch`] when the transaction is committed.  Dropping
+/// the transaction without committing it discards the pending changes.
+pub struct RocksDbReadWriteTransaction<'a> {
+    db: &'a DB,
+    /// Pending changes, where `None` marks a deletion.
+    pending: BTreeMap<(String, Vec<u8>), Option<Vec<u8>>>,
+    _write_lock: WriteLock<'a>,
+}
+
+impl<'a> RocksDbReadWriteTransaction<'a> {
+    pub fn new(env: &'a RocksDbEnvironment) -> Result<RocksDbReadWriteTransaction<'a>, Error> {
+        let _write_lock = env.write_mutex.lock()?;
+        Ok(RocksDbReadWriteTransaction {
+            db: env.db(),
+            pending: BTreeMap::new(),
+            _write_lock,
+        })
+    }
+}
+
+impl<'a> Transaction for RocksDbReadWriteTransaction<'a> {
+    type Error = Error;
+
+    type Handle = String;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        let mut batch = WriteBatch::default();
+        for ((handle, key), maybe_value) in self.pending {
+            let cf = column_family(self.db, &handle)?;
+            match maybe_value {
+                Some(value) => batch.put_cf(cf, key, value)?,
+                None => batch.delete_cf(cf, key)?,
+            }
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+}
+
+impl<'a> Readable for RocksDbReadWriteTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        let cf = column_family(self.db, &handle)?;
+        if let Some(maybe_value) = self.pending.get(&(handle, key.to_vec())) {
+            return Ok(maybe_value.to_owned());
+        }
+        let ret = self.db.get_cf(cf, key)?;
+        Ok(ret.map(|bytes| bytes.to_vec()))
+    }
+}
+
+impl<'a> Writable for RocksDbReadWriteTransaction<'a> {
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        column_family(self.db, &handle)?;
+        self.pending
+            .insert((handle, key.to_vec()), Some(value.to_vec()));
+        Ok(())
+    }
+}
+
+impl<'a> Deletable for RocksDbReadWriteTransaction<'a> {
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let cf = column_family(self.db, &handle)?;
+        let mut ret: BTreeSet<Vec<u8>> = self
+            .db
+            .iterator_cf(cf, IteratorMode::Start)?
+            .map(|(key, _)| key.to_vec())
+            .collect();
+        for ((pending_handle, key), maybe_value) in self.pending.iter() {
+            if *pending_handle != handle {
+                continue;
+            }
+            match maybe_value {
+                Some(_) => ret.insert(key.to_owned()),
+                None => ret.remove(key),
+            };
+        }
+        Ok(ret.into_iter().collect())
+    }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        column_family(self.db, &handle)?;
+        self.pending.insert((handle, key.to_vec()), None);
+        Ok(())
+    }
+}
+
+/// The environment for a RocksDB-backed trie store.
+///
+/// Wraps [`rocksdb::DB`].  Each store is kept in its own column family, so
+/// tries and protocol data can be compacted independently.
+pub struct RocksDbEnvironment {
+    path: PathBuf,
+    db: DB,
+    write_mutex: Mutex<WriteCapability>,
+}
+
+impl RocksDbEnvironment {
+    /// Opens (or creates) a database at the given path.
+    ///
+    /// The database is opened with the column families used by the default
+    /// trie and protocol data stores, along with any column families which
+    /// already exist in the database.
+    pub fn new(path: &PathBuf) -> Result<Self, error::Error> {
+        RocksDbEnvironment::with_store_names(path, &[])
+    }
+
+    /// Opens (or creates) a database at the given path, along with the column
+    /// families of the trie and protocol data stores named in `store_names`.
+    ///
+    /// Column families can only be created while opening the database, so every
+    /// named store to be used with the environment must be listed here.
+    pub fn with_store_names(path: &PathBuf, store_names: &[&str]) -> Result<Self, error::Error> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let column_families: HashSet<String> = {
+            // Listing fails if the database does not exist yet, in which case
+            // only the default column families are created.
+            let mut ret: HashSet<String> = DB::list_cf(&options, path)
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            let maybe_names = std::iter::once(None).chain(store_names.iter().cloned().map(Some));
+            for maybe_name in maybe_names {
+                ret.insert(column_family_name(trie_store::NAME, maybe_name));
+                ret.insert(column_family_name(protocol_data_store::NAME, maybe_name));
+            }
+            ret
+        };
+
+        let descriptors = column_families
+            .into_iter()
+            .map(|name| ColumnFamilyDescriptor::new(name, Options::default()))
+            .collect::<Vec<ColumnFamilyDescriptor>>();
+
+        let db = DB::open_cf_descriptors(&options, path, descriptors).map_err(Error::from)?;
+        let path = path.to_owned();
+        let write_mutex = Mutex::new(WriteCapability);
+        Ok(RocksDbEnvironment {
+            path,
+            db,
+            write_mutex,
+        })
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn db(&self) -> &DB {
+        &self.db
+    }
+
+    /// Returns `Ok(())` if the environment has a column family with the given name.
+    pub(crate) fn check_column_family(&self, name: &str) -> Result<(), Error> {
+        column_family(&self.db, name).map(|_| ())
+    }
+}
+
+impl<'a> TransactionSource<'a> for RocksDbEnvironment {
+    type Error = Error;
+
+    type Handle = String;
+
+    type ReadTransaction = RocksDbReadTransaction<'a>;
+
+    type ReadWriteTransaction = RocksDbReadWriteTransaction<'a>;
+
+    fn create_read_txn(&'a self) -> Result<RocksDbReadTransaction<'a>, Self::Error> {
+        RocksDbReadTransaction::new(self)
+    }
+
+    fn create_read_write_txn(&'a self) -> Result<RocksDbReadWriteTransaction<'a>, Self::Error> {
+        RocksDbReadWriteTransaction::new(self)
+    }
+}
@@ -0,0 +1,237 @@
+//! A transaction source which buffers writes in memory on top of an LMDB environment.
+//!
+//! Reads see the buffered writes first and fall through to LMDB for everything else.  The buffer
+//! is either written to LMDB in a single transaction with [`ScratchEnvironment::flush`], or
+//! dropped with [`ScratchEnvironment::discard`].
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use lmdb::{self, Database};
+use lmdb_sys::MDB_dbi;
+use parking_lot::{Mutex, MutexGuard, RwLock, RwLockReadGuard};
+
+use crate::transaction_source::lmdb::{LmdbEnvironment, LmdbReadTransaction};
+use crate::transaction_source::{Deletable, Readable, Transaction, TransactionSource, Writable};
+
+/// Buffered entries of a database.  An entry of `None` marks a deletion, hiding the entry stored
+/// in LMDB.
+type BytesMap = HashMap<Vec<u8>, Option<Vec<u8>>>;
+
+/// Buffered entries of every database written to, keyed by database identifier.
+#[derive(Default)]
+struct Buffer {
+    databases: HashMap<MDB_dbi, (Database, BytesMap)>,
+}
+
+impl Buffer {
+    /// Returns `None` if `key` isn't buffered, or the buffered entry otherwise.
+    fn get(&self, handle: Database, key: &[u8]) -> Option<&Option<Vec<u8>>> {
+        self.databases
+            .get(&handle.dbi())
+            .and_then(|(_, entries)| entries.get(key))
+    }
+
+    fn entries_mut(&mut self, handle: Database) -> &mut BytesMap {
+        &mut self
+            .databases
+            .entry(handle.dbi())
+            .or_insert_with(|| (handle, BytesMap::new()))
+            .1
+    }
+
+    /// Applies the buffered entries of `handle` to `keys`.
+    fn apply_to_keys(&self, handle: Database, keys: &mut HashSet<Vec<u8>>) {
+        if let Some((_, entries)) = self.databases.get(&handle.dbi()) {
+            for (key, maybe_value) in entries {
+                if maybe_value.is_some() {
+                    keys.insert(key.to_owned());
+                } else {
+                    keys.remove(key);
+                }
+            }
+        }
+    }
+
+    fn extend(&mut self, other: Buffer) {
+        for (_, (handle, entries)) in other.databases {
+            self.entries_mut(handle).extend(entries);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.databases
+            .values()
+            .map(|(_, entries)| entries.len())
+            .sum()
+    }
+}
+
+/// A read transaction for the scratch trie store.
+///
+/// Keeps the buffer from being flushed while it is open, so that it sees each entry either in the
+/// buffer or in LMDB.
+pub struct ScratchReadTransaction<'a> {
+    buffer: RwLockReadGuard<'a, Buffer>,
+    txn: LmdbReadTransaction<'a>,
+}
+
+impl<'a> Transaction for ScratchReadTransaction<'a> {
+    type Error = lmdb::Error;
+
+    type Handle = Database;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        self.txn.commit()
+    }
+}
+
+impl<'a> Readable for ScratchReadTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        match self.buffer.get(handle, key) {
+            Some(maybe_value) => Ok(maybe_value.to_owned()),
+            None => self.txn.read(handle, key),
+        }
+    }
+}
+
+/// A read-write transaction for the scratch trie store.
+///
+/// Its writes are kept apart until it is committed, when they are added to the buffer of the
+/// environment.  Nothing is written to LMDB.
+pub struct ScratchReadWriteTransaction<'a> {
+    buffer: &'a RwLock<Buffer>,
+    pending: Buffer,
+    txn: LmdbReadTransaction<'a>,
+    _write_lock: MutexGuard<'a, ()>,
+}
+
+impl<'a> Transaction for ScratchReadWriteTransaction<'a> {
+    type Error = lmdb::Error;
+
+    type Handle = Database;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        self.buffer.write().extend(self.pending);
+        self.txn.commit()
+    }
+}
+
+impl<'a> Readable for ScratchReadWriteTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        if let Some(maybe_value) = self.pending.get(handle, key) {
+            return Ok(maybe_value.to_owned());
+        }
+        if let Some(maybe_value) = self.buffer.read().get(handle, key) {
+            return Ok(maybe_value.to_owned());
+        }
+        self.txn.read(handle, key)
+    }
+}
+
+impl<'a> Writable for ScratchReadWriteTransaction<'a> {
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        self.pending
+            .entries_mut(handle)
+            .insert(key.to_vec(), Some(value.to_vec()));
+        Ok(())
+    }
+}
+
+impl<'a> Deletable for ScratchReadWriteTransaction<'a> {
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let mut keys: HashSet<Vec<u8>> = self.txn.keys(handle)?.into_iter().collect();
+        self.buffer.read().apply_to_keys(handle, &mut keys);
+        self.pending.apply_to_keys(handle, &mut keys);
+        Ok(keys.into_iter().collect())
+    }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        self.pending.entries_mut(handle).insert(key.to_vec(), None);
+        Ok(())
+    }
+}
+
+/// An environment which buffers the writes made to an [`LmdbEnvironment`] in memory.
+pub struct ScratchEnvironment {
+    environment: Arc<LmdbEnvironment>,
+    buffer: RwLock<Buffer>,
+    write_mutex: Mutex<()>,
+}
+
+impl ScratchEnvironment {
+    pub fn new(environment: Arc<LmdbEnvironment>) -> Self {
+        ScratchEnvironment {
+            environment,
+            buffer: RwLock::new(Buffer::default()),
+            write_mutex: Mutex::new(()),
+        }
+    }
+
+    pub fn environment(&self) -> &Arc<LmdbEnvironment> {
+        &self.environment
+    }
+
+    /// Returns the number of buffered writes and deletions.
+    pub fn len(&self) -> usize {
+        self.buffer.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Writes the buffered entries to LMDB in a single transaction, then empties the buffer.
+    ///
+    /// If writing fails, nothing is written and the buffer is left as it was.
+    pub fn flush(&self) -> Result<(), lmdb::Error> {
+        let _write_lock = self.write_mutex.lock();
+        let mut buffer = self.buffer.write();
+        let mut txn = self.environment.create_read_write_txn()?;
+        for (handle, entries) in buffer.databases.values() {
+            for (key, maybe_value) in entries {
+                match maybe_value {
+                    Some(value) => txn.write(*handle, key, value)?,
+                    None => txn.delete(*handle, key)?,
+                }
+            }
+        }
+        txn.commit()?;
+        buffer.databases.clear();
+        Ok(())
+    }
+
+    /// Drops the buffered entries, leaving LMDB untouched.
+    pub fn discard(&self) {
+        let _write_lock = self.write_mutex.lock();
+        self.buffer.write().databases.clear();
+    }
+}
+
+impl<'a> TransactionSource<'a> for ScratchEnvironment {
+    type Error = lmdb::Error;
+
+    type Handle = Database;
+
+    type ReadTransaction = ScratchReadTransaction<'a>;
+
+    type ReadWriteTransaction = ScratchReadWriteTransaction<'a>;
+
+    fn create_read_txn(&'a self) -> Result<ScratchReadTransaction<'a>, Self::Error> {
+        // A recursive read lock, as a thread may open a read transaction within another one.
+        let buffer = self.buffer.read_recursive();
+        let txn = self.environment.create_read_txn()?;
+        Ok(ScratchReadTransaction { buffer, txn })
+    }
+
+    fn create_read_write_txn(&'a self) -> Result<ScratchReadWriteTransaction<'a>, Self::Error> {
+        let _write_lock = self.write_mutex.lock();
+        let txn = self.environment.create_read_txn()?;
+        Ok(ScratchReadWriteTransaction {
+            buffer: &self.buffer,
+            pending: Buffer::default(),
+            txn,
+            _write_lock,
+        })
+    }
+}
@@ -0,0 +1,104 @@
+use contract_ffi::bytesrepr::{self, ToBytes};
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::trie::Trie;
+
+/// A Merkle proof of the inclusion of a key-value pair in a trie.
+///
+/// Consists of the nodes and extensions on the path from a root down to the
+/// leaf holding the key-value pair, ordered from the root (shallow to deep).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMerkleProof<K, V> {
+    key: K,
+    value: V,
+    proof_steps: Vec<Trie<K, V>>,
+}
+
+impl<K, V> TrieMerkleProof<K, V> {
+    pub fn new(key: K, value: V, proof_steps: Vec<Trie<K, V>>) -> Self {
+        TrieMerkleProof {
+            key,
+            value,
+            proof_steps,
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    pub fn proof_steps(&self) -> &[Trie<K, V>] {
+        &self.proof_steps
+    }
+
+    pub fn into_value(self) -> V {
+        self.value
+    }
+}
+
+impl<K, V> TrieMerkleProof<K, V>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    /// Returns the serialized tries on the path from the root down to and
+    /// including the leaf holding the key-value pair.
+    pub fn path_bytes(&self) -> Result<Vec<Vec<u8>>, bytesrepr::Error> {
+        let leaf: Trie<K, V> = Trie::leaf(self.key.clone(), self.value.clone());
+        let mut ret = Vec::with_capacity(self.proof_steps.len() + 1);
+        for step in self.proof_steps.iter() {
+            ret.push(step.to_bytes()?);
+        }
+        ret.push(leaf.to_bytes()?);
+        Ok(ret)
+    }
+
+    /// Returns `true` if the first step of the proof hashes to `root` and each step points to the
+    /// next one along the path of the key: a node through the pointer at the index of the key's
+    /// byte at its depth, and an extension only if its affix matches the key's bytes at its depth.
+    pub fn verify(&self, root: &Blake2bHash) -> Result<bool, bytesrepr::Error> {
+        let path = self.key.to_bytes()?;
+
+        // The hash each step should point to is that of the step after it, or of the leaf for the
+        // deepest step.
+        let mut hashes = Vec::with_capacity(self.proof_steps.len() + 1);
+        for step in self.proof_steps.iter() {
+            hashes.push(step.hash()?);
+        }
+        let leaf: Trie<K, V> = Trie::leaf(self.key.clone(), self.value.clone());
+        hashes.push(leaf.hash()?);
+
+        if hashes[0] != *root {
+            return Ok(false);
+        }
+
+        let mut depth: usize = 0;
+        for (step, next_hash) in self.proof_steps.iter().zip(hashes.iter().skip(1)) {
+            let points_to_next = match step {
+                Trie::Leaf { .. } => false,
+                Trie::Node { pointer_block } => match path.get(depth) {
+                    Some(index) => {
+                        depth += 1;
+                        pointer_block[usize::from(*index)]
+                            .map(|pointer| pointer.hash() == next_hash)
+                            .unwrap_or(false)
+                    }
+                    None => false,
+                },
+                Trie::Extension { affix, pointer } => {
+                    let sub_path = path.get(depth..depth + affix.len());
+                    depth += affix.len();
+                    sub_path == Some(affix.as_slice()) && pointer.hash() == next_hash
+                }
+            };
+            if !points_to_next {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
@@ -4,14 +4,18 @@ use std::mem::size_of;
 use std::ops::Deref;
 
 use contract_ffi::bytesrepr::{self, FromBytes, ToBytes};
-use engine_shared::newtypes::Blake2bHash;
+use engine_shared::newtypes::{Blake2bHash, Blake2bHasher};
 
 #[cfg(test)]
 pub mod gens;
 
+mod merkle_proof;
+
 #[cfg(test)]
 mod tests;
 
+pub use self::merkle_proof::TrieMerkleProof;
+
 pub const RADIX: usize = 256;
 
 const U32_SIZE: usize = size_of::<u32>();
@@ -193,6 +197,46 @@ impl<K, V> Trie<K, V> {
     }
 }
 
+impl<K, V> Trie<K, V>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    /// Returns the hash of the serialized trie, feeding it to the hasher piece by piece rather
+    /// than serializing the whole trie first.
+    pub fn hash(&self) -> Result<Blake2bHash, bytesrepr::Error> {
+        let mut hasher = Blake2bHasher::new();
+        hasher.update(&self.tag().to_bytes()?);
+        match self {
+            Trie::Leaf { key, value } => {
+                let key_bytes = ToBytes::to_bytes(key)?;
+                let value_bytes = ToBytes::to_bytes(value)?;
+                if key_bytes.len() + value_bytes.len() > u32::max_value() as usize - U32_SIZE {
+                    return Err(bytesrepr::Error::OutOfMemoryError);
+                }
+                hasher.update(&key_bytes);
+                hasher.update(&value_bytes);
+            }
+            Trie::Node { pointer_block } => {
+                hasher.update(&(RADIX as u32).to_bytes()?);
+                for index in 0..RADIX {
+                    hasher.update(&pointer_block[index].to_bytes()?);
+                }
+            }
+            Trie::Extension { affix, pointer } => {
+                let affix_bytes = ToBytes::to_bytes(affix)?;
+                let pointer_bytes = ToBytes::to_bytes(pointer)?;
+                if affix_bytes.len() + pointer_bytes.len() > u32::max_value() as usize - U32_SIZE {
+                    return Err(bytesrepr::Error::OutOfMemoryError);
+                }
+                hasher.update(&affix_bytes);
+                hasher.update(&pointer_bytes);
+            }
+        }
+        Ok(hasher.finalize())
+    }
+}
+
 impl<K, V> ToBytes for Trie<K, V>
 where
     K: ToBytes,
@@ -277,7 +321,6 @@ pub(crate) mod operations {
         let root: Trie<K, V> = Trie::Node {
             pointer_block: Default::default(),
         };
-        let root_bytes: Vec<u8> = root.to_bytes()?;
-        Ok((Blake2bHash::new(&root_bytes), root))
+        Ok((root.hash()?, root))
     }
 }
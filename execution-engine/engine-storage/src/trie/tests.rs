@@ -48,9 +48,80 @@ mod pointer_block {
     }
 }
 
+mod merkle_proof {
+    use contract_ffi::bytesrepr::ToBytes;
+    use contract_ffi::key::Key;
+    use contract_ffi::value::Value;
+    use engine_shared::newtypes::Blake2bHash;
+
+    use crate::trie::*;
+
+    const KEY: Key = Key::Account([1u8; 32]);
+    const VALUE: Value = Value::Int32(1);
+
+    fn leaf_pointer() -> Pointer {
+        let leaf: Trie<Key, Value> = Trie::leaf(KEY, VALUE);
+        Pointer::LeafPointer(leaf.hash().unwrap())
+    }
+
+    /// Returns a proof through a node holding the leaf at `index`, along with its root.
+    fn proof_through_node(index: u8) -> (Blake2bHash, TrieMerkleProof<Key, Value>) {
+        let node: Trie<Key, Value> = Trie::node(&[(usize::from(index), leaf_pointer())]);
+        let root = node.hash().unwrap();
+        (root, TrieMerkleProof::new(KEY, VALUE, vec![node]))
+    }
+
+    /// Returns a proof through an extension with the given affix, followed by a node holding the
+    /// leaf at the index of the key's byte after the affix, along with its root.
+    fn proof_through_extension(affix: Vec<u8>) -> (Blake2bHash, TrieMerkleProof<Key, Value>) {
+        let path = KEY.to_bytes().unwrap();
+        let node: Trie<Key, Value> =
+            Trie::node(&[(usize::from(path[affix.len()]), leaf_pointer())]);
+        let extension: Trie<Key, Value> =
+            Trie::extension(affix, Pointer::NodePointer(node.hash().unwrap()));
+        let root = extension.hash().unwrap();
+        (
+            root,
+            TrieMerkleProof::new(KEY, VALUE, vec![extension, node]),
+        )
+    }
+
+    #[test]
+    fn proof_through_node_at_key_byte_verifies() {
+        let path = KEY.to_bytes().unwrap();
+        let (root, proof) = proof_through_node(path[0]);
+        assert!(proof.verify(&root).unwrap());
+    }
+
+    #[test]
+    fn proof_through_node_at_wrong_key_byte_does_not_verify() {
+        let path = KEY.to_bytes().unwrap();
+        let (root, proof) = proof_through_node(path[0].wrapping_add(1));
+        assert!(!proof.verify(&root).unwrap());
+    }
+
+    #[test]
+    fn proof_through_extension_matching_key_verifies() {
+        let path = KEY.to_bytes().unwrap();
+        let (root, proof) = proof_through_extension(path[..2].to_vec());
+        assert!(proof.verify(&root).unwrap());
+    }
+
+    #[test]
+    fn proof_through_mismatched_extension_does_not_verify() {
+        let path = KEY.to_bytes().unwrap();
+        let mut affix = path[..2].to_vec();
+        affix[1] = affix[1].wrapping_add(1);
+        let (root, proof) = proof_through_extension(affix);
+        assert!(!proof.verify(&root).unwrap());
+    }
+}
+
 mod proptests {
     use proptest::prelude::proptest;
 
+    use contract_ffi::bytesrepr::ToBytes;
+    use engine_shared::newtypes::Blake2bHash;
     use engine_shared::test_utils::test_serialization_roundtrip;
 
     use crate::trie::gens::*;
@@ -75,5 +146,11 @@ mod proptests {
         fn roundtrip_trie(trie in trie_arb()) {
             assert!(test_serialization_roundtrip(&trie));
         }
+
+        #[test]
+        fn trie_hash_matches_hash_of_serialized_trie(trie in trie_arb()) {
+            let expected = Blake2bHash::new(&trie.to_bytes().unwrap());
+            assert_eq!(trie.hash().unwrap(), expected);
+        }
     }
 }
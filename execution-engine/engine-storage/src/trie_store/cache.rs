@@ -0,0 +1,214 @@
+//! A cache of trie values shared by the readers of a global state.
+//!
+//! Trie values are stored at their hashes, so a cached value is valid whatever root it is
+//! reached from.  Reads of hot values, such as the nodes leading to the system contracts, are then
+//! served from memory instead of being deserialized from the store on every exec.
+
+use std::fmt;
+
+use linked_hash_map::LinkedHashMap;
+use parking_lot::Mutex;
+
+use contract_ffi::bytesrepr::{FromBytes, ToBytes};
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::metrics;
+use crate::store::Store;
+use crate::transaction_source::{Readable, Writable};
+use crate::trie::Trie;
+use crate::trie_store::TrieStore;
+
+/// A least-recently-used cache of trie values, keyed by their hashes.
+pub struct TrieCache<K, V> {
+    capacity: usize,
+    tries: Mutex<LinkedHashMap<Blake2bHash, Trie<K, V>>>,
+}
+
+impl<K: Clone, V: Clone> TrieCache<K, V> {
+    /// Creates a cache holding at most `capacity` trie values.  A capacity of zero disables
+    /// caching.
+    pub fn new(capacity: usize) -> Self {
+        TrieCache {
+            capacity,
+            tries: Mutex::new(LinkedHashMap::new()),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.tries.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cached trie value stored at `hash`, marking it as the most recently used.
+    pub fn get(&self, hash: &Blake2bHash) -> Option<Trie<K, V>> {
+        self.tries.lock().get_refresh(hash).cloned()
+    }
+
+    /// Caches `trie` at `hash`, evicting the least recently used values beyond the capacity.
+    pub fn insert(&self, hash: Blake2bHash, trie: Trie<K, V>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut tries = self.tries.lock();
+        tries.insert(hash, trie);
+        while tries.len() > self.capacity {
+            tries.pop_front();
+        }
+    }
+
+    /// Empties the cache, e.g. once trie values have been deleted from the store.
+    pub fn clear(&self) {
+        self.tries.lock().clear()
+    }
+}
+
+impl<K, V> fmt::Debug for TrieCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TrieCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.tries.lock().len())
+            .finish()
+    }
+}
+
+/// A trie store which serves reads from a [`TrieCache`] before falling back to the wrapped store.
+///
+/// Writes go straight to the wrapped store and aren't cached, as the transaction they are made in
+/// may yet be aborted.  It is meant for reading committed state, not for committing to it.
+pub struct CachingTrieStore<'a, K, V, S> {
+    store: &'a S,
+    cache: &'a TrieCache<K, V>,
+}
+
+impl<'a, K, V, S> CachingTrieStore<'a, K, V, S> {
+    pub fn new(store: &'a S, cache: &'a TrieCache<K, V>) -> Self {
+        CachingTrieStore { store, cache }
+    }
+}
+
+impl<'a, K, V, S> Store<Blake2bHash, Trie<K, V>> for CachingTrieStore<'a, K, V, S>
+where
+    K: Clone,
+    V: Clone,
+    S: TrieStore<K, V>,
+{
+    type Error = S::Error;
+
+    type Handle = S::Handle;
+
+    fn handle(&self) -> Self::Handle {
+        self.store.handle()
+    }
+
+    fn get<T>(&self, txn: &T, key: &Blake2bHash) -> Result<Option<Trie<K, V>>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Trie<K, V>: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        if self.cache.capacity() == 0 {
+            return self.store.get(txn, key);
+        }
+        if let Some(trie) = self.cache.get(key) {
+            metrics::storage_metrics().record_trie_cache_hit();
+            return Ok(Some(trie));
+        }
+        metrics::storage_metrics().record_trie_cache_miss();
+        let maybe_trie = self.store.get(txn, key)?;
+        if let Some(ref trie) = maybe_trie {
+            self.cache.insert(*key, trie.clone());
+        }
+        Ok(maybe_trie)
+    }
+
+    fn put<T>(&self, txn: &mut T, key: &Blake2bHash, value: &Trie<K, V>) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Trie<K, V>: ToBytes,
+        Self::Error: From<T::Error>,
+    {
+        self.store.put(txn, key, value)
+    }
+}
+
+impl<'a, K, V, S> TrieStore<K, V> for CachingTrieStore<'a, K, V, S>
+where
+    K: Clone,
+    V: Clone,
+    S: TrieStore<K, V>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::newtypes::Blake2bHash;
+
+    use super::TrieCache;
+    use crate::trie::Trie;
+
+    fn leaf(value: u8) -> (Blake2bHash, Trie<u8, u8>) {
+        (Blake2bHash::new(&[value]), Trie::Leaf { key: value, value })
+    }
+
+    #[test]
+    fn should_get_inserted_trie() {
+        let cache = TrieCache::new(2);
+        let (hash, trie) = leaf(1);
+
+        assert_eq!(cache.get(&hash), None);
+        cache.insert(hash, trie.clone());
+        assert_eq!(cache.get(&hash), Some(trie));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_trie() {
+        let cache = TrieCache::new(2);
+        let (hash_1, trie_1) = leaf(1);
+        let (hash_2, trie_2) = leaf(2);
+        let (hash_3, trie_3) = leaf(3);
+
+        cache.insert(hash_1, trie_1.clone());
+        cache.insert(hash_2, trie_2);
+        // Refreshes the first trie, leaving the second as the least recently used
+        assert!(cache.get(&hash_1).is_some());
+        cache.insert(hash_3, trie_3.clone());
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&hash_1), Some(trie_1));
+        assert_eq!(cache.get(&hash_2), None);
+        assert_eq!(cache.get(&hash_3), Some(trie_3));
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_capacity() {
+        let cache = TrieCache::new(0);
+        let (hash, trie) = leaf(1);
+
+        cache.insert(hash, trie);
+
+        assert_eq!(cache.get(&hash), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn should_clear_cache() {
+        let cache = TrieCache::new(2);
+        let (hash, trie) = leaf(1);
+
+        cache.insert(hash, trie);
+        cache.clear();
+
+        assert_eq!(cache.get(&hash), None);
+        assert!(cache.is_empty());
+    }
+}
@@ -1,14 +1,16 @@
 #[cfg(test)]
 mod tests;
 
+use std::collections::HashSet;
 use std::time::Instant;
 
 use contract_ffi::bytesrepr::{self, FromBytes, ToBytes};
 use engine_shared::logging::{log_duration, log_metric, GAUGE};
 use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 
+use crate::metrics;
 use crate::transaction_source::{Readable, Writable};
-use crate::trie::{self, Parents, Pointer, Trie};
+use crate::trie::{self, Parents, Pointer, PointerBlock, Trie, TrieMerkleProof};
 use crate::trie_store::TrieStore;
 
 const TRIE_STORE_READ_DURATION: &str = "trie_store_read_duration";
@@ -17,11 +19,40 @@ const TRIE_STORE_SCAN_DURATION: &str = "trie_store_scan_duration";
 const TRIE_STORE_SCAN_GETS: &str = "trie_store_scan_gets";
 const TRIE_STORE_WRITE_DURATION: &str = "trie_store_write_duration";
 const TRIE_STORE_WRITE_PUTS: &str = "trie_store_write_puts";
+const TRIE_STORE_DELETE_DURATION: &str = "trie_store_delete_duration";
+const TRIE_STORE_DELETE_PUTS: &str = "trie_store_delete_puts";
+const TRIE_STORE_REACHABLE_DURATION: &str = "trie_store_reachable_duration";
+const TRIE_STORE_REACHABLE_GETS: &str = "trie_store_reachable_gets";
+const TRIE_STORE_MISSING_TRIE_KEYS_DURATION: &str = "trie_store_missing_trie_keys_duration";
+const TRIE_STORE_MISSING_TRIE_KEYS_GETS: &str = "trie_store_missing_trie_keys_gets";
+const TRIE_STORE_VERIFY_INTEGRITY_DURATION: &str = "trie_store_verify_integrity_duration";
+const TRIE_STORE_VERIFY_INTEGRITY_GETS: &str = "trie_store_verify_integrity_gets";
 const READ: &str = "read";
 const GET: &str = "get";
 const SCAN: &str = "scan";
 const WRITE: &str = "write";
 const PUT: &str = "put";
+const DELETE: &str = "delete";
+const REACHABLE: &str = "reachable";
+const MISSING_TRIE_KEYS: &str = "missing_trie_keys";
+const VERIFY_INTEGRITY: &str = "verify_integrity";
+
+/// The outcome of walking the tries under some roots with [`verify_integrity`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// The number of trie values which were read.
+    pub checked: usize,
+    /// Stored trie values whose bytes don't hash to their key or can't be deserialized.
+    pub corrupt: Vec<Blake2bHash>,
+    /// Trie values which are referenced, or given as a root, but absent from the store.
+    pub dangling: Vec<Blake2bHash>,
+}
+
+impl IntegrityReport {
+    pub fn is_ok(&self) -> bool {
+        self.corrupt.is_empty() && self.dangling.is_empty()
+    }
+}
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ReadResult<V> {
@@ -83,6 +114,7 @@ where
                     READ,
                     start.elapsed(),
                 );
+                metrics::storage_metrics().record_read(start.elapsed());
                 return Ok(result);
             }
             Trie::Node { pointer_block } => {
@@ -116,6 +148,7 @@ where
                                 READ,
                                 start.elapsed(),
                             );
+                            metrics::storage_metrics().record_read(start.elapsed());
                             panic!(
                                 "No trie value at key: {:?} (reading from key: {:?})",
                                 pointer.hash(),
@@ -137,6 +170,7 @@ where
                             READ,
                             start.elapsed(),
                         );
+                        metrics::storage_metrics().record_read(start.elapsed());
                         return Ok(ReadResult::NotFound);
                     }
                 }
@@ -166,6 +200,7 @@ where
                                 READ,
                                 start.elapsed(),
                             );
+                            metrics::storage_metrics().record_read(start.elapsed());
                             panic!(
                                 "No trie value at key: {:?} (reading from key: {:?})",
                                 pointer.hash(),
@@ -187,6 +222,7 @@ where
                         READ,
                         start.elapsed(),
                     );
+                    metrics::storage_metrics().record_read(start.elapsed());
                     return Ok(ReadResult::NotFound);
                 }
             }
@@ -194,6 +230,51 @@ where
     }
 }
 
+/// Returns a value from the corresponding key at a given root in a given
+/// store, along with a Merkle proof of its inclusion under that root.
+pub fn read_with_proof<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<ReadResult<TrieMerkleProof<K, V>>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq,
+    V: ToBytes + FromBytes + Clone,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let path: Vec<u8> = key.to_bytes()?;
+
+    let current_root: Trie<K, V> = match store.get(txn, root)? {
+        Some(root) => root,
+        None => return Ok(ReadResult::RootNotFound),
+    };
+
+    let TrieScan { tip, parents } =
+        scan::<K, V, T, S, E>(correlation_id, txn, store, &path, &current_root)?;
+
+    match tip {
+        Trie::Leaf {
+            key: leaf_key,
+            value: leaf_value,
+        } => {
+            if *key != leaf_key {
+                // Keys may not match in the case of a compressed path from
+                // a Node directly to a Leaf
+                return Ok(ReadResult::NotFound);
+            }
+            let proof_steps = parents.into_iter().map(|(_, parent)| parent).collect();
+            let proof = TrieMerkleProof::new(leaf_key, leaf_value, proof_steps);
+            Ok(ReadResult::Found(proof))
+        }
+        _ => Ok(ReadResult::NotFound),
+    }
+}
+
 struct TrieScan<K, V> {
     tip: Trie<K, V>,
     parents: Parents<K, V>,
@@ -376,10 +457,7 @@ where
     V: ToBytes + Clone,
 {
     let mut ret: Vec<(Blake2bHash, Trie<K, V>)> = Vec::new();
-    let mut tip_hash = {
-        let trie_bytes = tip.to_bytes()?;
-        Blake2bHash::new(&trie_bytes)
-    };
+    let mut tip_hash = tip.hash()?;
     ret.push((tip_hash, tip.to_owned()));
 
     for (index, parent) in parents.into_iter().rev() {
@@ -397,10 +475,7 @@ where
                     pointer_block[index.into()] = Some(pointer);
                     Trie::Node { pointer_block }
                 };
-                tip_hash = {
-                    let node_bytes = tip.to_bytes()?;
-                    Blake2bHash::new(&node_bytes)
-                };
+                tip_hash = tip.hash()?;
                 ret.push((tip_hash, tip.to_owned()))
             }
             Trie::Extension { affix, pointer } => {
@@ -408,10 +483,7 @@ where
                     let pointer = pointer.update(tip_hash);
                     Trie::Extension { affix, pointer }
                 };
-                tip_hash = {
-                    let extension_bytes = tip.to_bytes()?;
-                    Blake2bHash::new(&extension_bytes)
-                };
+                tip_hash = tip.hash()?;
                 ret.push((tip_hash, tip.to_owned()))
             }
         }
@@ -523,8 +595,7 @@ where
     // If the affix is non-empty, create an extension node and add it
     // to parents.
     if !affix.is_empty() {
-        let new_node_bytes = new_node.to_bytes()?;
-        let new_node_hash = Blake2bHash::new(&new_node_bytes);
+        let new_node_hash = new_node.hash()?;
         let new_extension = Trie::extension(affix.to_vec(), Pointer::NodePointer(new_node_hash));
         parents.push((child_index, new_extension));
     }
@@ -577,8 +648,7 @@ where
             None
         } else {
             let child_extension = Trie::extension(child_extension_affix.to_vec(), pointer);
-            let child_extension_bytes = child_extension.to_bytes()?;
-            let child_extension_hash = Blake2bHash::new(&child_extension_bytes);
+            let child_extension_hash = child_extension.hash()?;
             Some((child_extension_hash, child_extension))
         };
     // Assemble a new node.
@@ -591,8 +661,7 @@ where
     };
     // Create a parent extension if necessary
     if !parent_extension_affix.is_empty() {
-        let new_node_bytes = new_node.to_bytes()?;
-        let new_node_hash = Blake2bHash::new(&new_node_bytes);
+        let new_node_hash = new_node.hash()?;
         let parent_extension = Trie::extension(
             parent_extension_affix.to_vec(),
             Pointer::NodePointer(new_node_hash),
@@ -700,6 +769,7 @@ where
                     WRITE,
                     start.elapsed(),
                 );
+                metrics::storage_metrics().record_write(start.elapsed());
                 return Ok(WriteResult::AlreadyExists);
             }
             let mut root_hash = root.to_owned();
@@ -721,7 +791,410 @@ where
                 WRITE,
                 start.elapsed(),
             );
+            metrics::storage_metrics().record_write(start.elapsed());
             Ok(WriteResult::Written(root_hash))
         }
     }
 }
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeleteResult {
+    Deleted(Blake2bHash),
+    DoesNotExist,
+    RootNotFound,
+}
+
+/// Returns the trie to put in place of a node which lost a child, given the parents of that node.
+///
+/// A node which is left with a single child is collapsed into it, so that the result has the same
+/// shape, and therefore the same hash, as a trie which never held the removed keys.  The root is
+/// always kept as a node.
+fn collapse_node<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    pointer_block: Box<PointerBlock>,
+    parents: &mut Parents<K, V>,
+) -> Result<Trie<K, V>, E>
+where
+    K: ToBytes + FromBytes + Clone,
+    V: ToBytes + FromBytes + Clone,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let children: Vec<(usize, Pointer)> = (0..trie::RADIX)
+        .filter_map(|index| pointer_block[index].map(|pointer| (index, pointer)))
+        .collect();
+    if parents.is_empty() || children.len() != 1 {
+        return Ok(Trie::Node { pointer_block });
+    }
+    let (child_index, child_pointer) = children[0];
+    let child: Trie<K, V> = match store.get(txn, child_pointer.hash())? {
+        Some(child) => child,
+        None => panic!("No trie value at key: {:?}", child_pointer.hash()),
+    };
+    // An extension can't point to a leaf, so a leaf left on its own replaces a parent extension
+    // as well, while a node's sole remaining subtrie is merged into it.
+    let has_parent_extension = match parents.last() {
+        Some((_, Trie::Extension { .. })) => true,
+        _ => false,
+    };
+    let parent_affix = if has_parent_extension {
+        match parents.pop() {
+            Some((_, Trie::Extension { affix, .. })) => affix,
+            _ => unreachable!(),
+        }
+    } else {
+        Vec::new()
+    };
+    match child {
+        leaf @ Trie::Leaf { .. } => Ok(leaf),
+        Trie::Node { .. } => {
+            let mut affix = parent_affix;
+            affix.push(child_index as u8);
+            Ok(Trie::extension(affix, child_pointer))
+        }
+        Trie::Extension {
+            affix: child_affix,
+            pointer,
+        } => {
+            let mut affix = parent_affix;
+            affix.push(child_index as u8);
+            affix.extend(child_affix);
+            Ok(Trie::extension(affix, pointer))
+        }
+    }
+}
+
+/// Deletes every leaf whose key, serialized, starts with `prefix` from the trie under `root`.
+///
+/// The subtrie holding those leaves is cut off from its parent node and the tries above it are
+/// rehashed, so the new root is the one a trie holding only the remaining leaves would have.
+/// Giving the serialized key of a single leaf deletes just that leaf.
+pub fn delete_prefix<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    prefix: &[u8],
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut put_counter: i32 = 0;
+
+    let mut current: Trie<K, V> = match store.get(txn, root)? {
+        Some(current_root) => current_root,
+        None => return Ok(DeleteResult::RootNotFound),
+    };
+    let mut depth: usize = 0;
+    let mut parents: Parents<K, V> = Vec::new();
+
+    // Walk down along the prefix to the shallowest trie all of whose leaves have keys starting
+    // with it.
+    while depth < prefix.len() {
+        let (index, next_hash, next_depth) = match &current {
+            Trie::Leaf { key, .. } => {
+                if key.to_bytes()?.starts_with(prefix) {
+                    break;
+                }
+                return Ok(DeleteResult::DoesNotExist);
+            }
+            Trie::Node { pointer_block } => {
+                let index = prefix[depth];
+                match pointer_block[index.into()] {
+                    Some(pointer) => (index, *pointer.hash(), depth + 1),
+                    None => return Ok(DeleteResult::DoesNotExist),
+                }
+            }
+            Trie::Extension { affix, pointer } => {
+                let remaining = &prefix[depth..];
+                if remaining.len() <= affix.len() {
+                    if affix.starts_with(remaining) {
+                        break;
+                    }
+                    return Ok(DeleteResult::DoesNotExist);
+                }
+                if !remaining.starts_with(affix) {
+                    return Ok(DeleteResult::DoesNotExist);
+                }
+                (prefix[depth], *pointer.hash(), depth + affix.len())
+            }
+        };
+        let next: Trie<K, V> = match store.get(txn, &next_hash)? {
+            Some(next) => next,
+            None => panic!(
+                "No trie value at key: {:?} (reading from prefix: {:?})",
+                next_hash, prefix
+            ),
+        };
+        parents.push((index, current));
+        current = next;
+        depth = next_depth;
+    }
+
+    let tip = match parents.pop() {
+        // The whole trie is deleted.
+        None => Trie::node(&[]),
+        Some((index, Trie::Node { mut pointer_block })) => {
+            pointer_block[index.into()] = None;
+            collapse_node::<K, V, T, S, E>(&*txn, store, pointer_block, &mut parents)?
+        }
+        Some(_) => panic!("A deleted subtrie should have a node for its parent"),
+    };
+
+    let mut root_hash = root.to_owned();
+    for (hash, element) in rehash(tip, parents)?.iter() {
+        put_counter += 1;
+        store.put(txn, hash, element)?;
+        root_hash = *hash;
+    }
+    log_metric(
+        correlation_id,
+        TRIE_STORE_DELETE_PUTS,
+        PUT,
+        GAUGE,
+        f64::from(put_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DELETE_DURATION,
+        DELETE,
+        start.elapsed(),
+    );
+    Ok(DeleteResult::Deleted(root_hash))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReachableResult {
+    Found(HashSet<Blake2bHash>),
+    /// A trie value reachable from the roots, possibly one of the roots themselves, is absent
+    /// from the store.
+    MissingNode(Blake2bHash),
+}
+
+/// Returns the hashes of all trie values reachable from the given roots,
+/// including the roots themselves.
+///
+/// Leaves are not read from the store, as they contain no pointers.
+pub fn reachable<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    roots: &[Blake2bHash],
+) -> Result<ReachableResult, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut get_counter: i32 = 0;
+
+    let mut ret: HashSet<Blake2bHash> = HashSet::new();
+    let mut pending: Vec<Blake2bHash> = roots.to_vec();
+
+    while let Some(hash) = pending.pop() {
+        if !ret.insert(hash) {
+            continue;
+        }
+        get_counter += 1;
+        let trie: Trie<K, V> = match store.get(txn, &hash)? {
+            Some(trie) => trie,
+            None => return Ok(ReachableResult::MissingNode(hash)),
+        };
+        let mut visit = |pointer: Pointer| match pointer {
+            Pointer::LeafPointer(leaf_hash) => {
+                ret.insert(leaf_hash);
+            }
+            Pointer::NodePointer(node_hash) => pending.push(node_hash),
+        };
+        match trie {
+            Trie::Leaf { .. } => (),
+            Trie::Node { pointer_block } => {
+                for index in 0..trie::RADIX {
+                    if let Some(pointer) = pointer_block[index] {
+                        visit(pointer);
+                    }
+                }
+            }
+            Trie::Extension { pointer, .. } => visit(pointer),
+        }
+    }
+
+    log_metric(
+        correlation_id,
+        TRIE_STORE_REACHABLE_GETS,
+        REACHABLE,
+        GAUGE,
+        f64::from(get_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_REACHABLE_DURATION,
+        REACHABLE,
+        start.elapsed(),
+    );
+
+    Ok(ReachableResult::Found(ret))
+}
+
+/// Returns the hashes of the trie values which are referenced from `root` but absent from the
+/// store, including `root` itself if it is absent.
+///
+/// The descendants of an absent trie value are unknown, so once the returned trie values have
+/// been added the store has to be checked again.
+pub fn missing_trie_keys<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: Blake2bHash,
+) -> Result<Vec<Blake2bHash>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut get_counter: i32 = 0;
+
+    let mut ret: Vec<Blake2bHash> = Vec::new();
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut pending: Vec<Blake2bHash> = vec![root];
+
+    while let Some(hash) = pending.pop() {
+        if !visited.insert(hash) {
+            continue;
+        }
+        get_counter += 1;
+        let trie: Trie<K, V> = match store.get(txn, &hash)? {
+            Some(trie) => trie,
+            None => {
+                ret.push(hash);
+                continue;
+            }
+        };
+        match trie {
+            Trie::Leaf { .. } => (),
+            Trie::Node { pointer_block } => {
+                for index in 0..trie::RADIX {
+                    if let Some(pointer) = pointer_block[index] {
+                        pending.push(*pointer.hash());
+                    }
+                }
+            }
+            Trie::Extension { pointer, .. } => pending.push(*pointer.hash()),
+        }
+    }
+
+    log_metric(
+        correlation_id,
+        TRIE_STORE_MISSING_TRIE_KEYS_GETS,
+        MISSING_TRIE_KEYS,
+        GAUGE,
+        f64::from(get_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_MISSING_TRIE_KEYS_DURATION,
+        MISSING_TRIE_KEYS,
+        start.elapsed(),
+    );
+
+    Ok(ret)
+}
+
+/// Walks the tries under `roots`, checking that each stored trie value hashes to its key and
+/// deserializes, and that each referenced trie value is present.
+///
+/// The children of a corrupt trie value are not visited, as its pointers can't be trusted.
+pub fn verify_integrity<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    roots: &[Blake2bHash],
+) -> Result<IntegrityReport, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut get_counter: i32 = 0;
+
+    let mut ret = IntegrityReport::default();
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut pending: Vec<Blake2bHash> = roots.to_vec();
+
+    while let Some(hash) = pending.pop() {
+        if !visited.insert(hash) {
+            continue;
+        }
+        get_counter += 1;
+        let trie_bytes = match txn
+            .read(store.handle(), &hash.to_bytes()?)
+            .map_err(S::Error::from)?
+        {
+            Some(trie_bytes) => trie_bytes,
+            None => {
+                ret.dangling.push(hash);
+                continue;
+            }
+        };
+        ret.checked += 1;
+        if Blake2bHash::new(&trie_bytes) != hash {
+            ret.corrupt.push(hash);
+            continue;
+        }
+        let trie: Trie<K, V> = match bytesrepr::deserialize(&trie_bytes) {
+            Ok(trie) => trie,
+            Err(_) => {
+                ret.corrupt.push(hash);
+                continue;
+            }
+        };
+        match trie {
+            Trie::Leaf { .. } => (),
+            Trie::Node { pointer_block } => {
+                for index in 0..trie::RADIX {
+                    if let Some(pointer) = pointer_block[index] {
+                        pending.push(*pointer.hash());
+                    }
+                }
+            }
+            Trie::Extension { pointer, .. } => pending.push(*pointer.hash()),
+        }
+    }
+
+    log_metric(
+        correlation_id,
+        TRIE_STORE_VERIFY_INTEGRITY_GETS,
+        VERIFY_INTEGRITY,
+        GAUGE,
+        f64::from(get_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_VERIFY_INTEGRITY_DURATION,
+        VERIFY_INTEGRITY,
+        start.elapsed(),
+    );
+
+    Ok(ret)
+}
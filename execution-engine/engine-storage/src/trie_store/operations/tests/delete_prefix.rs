@@ -0,0 +1,279 @@
+use super::*;
+use crate::trie_store::operations::{delete_prefix, DeleteResult};
+
+/// Writes the given leaves one after the other, returning the root hash after each write,
+/// preceded by `root_hash`.
+fn write_leaves<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    leaves: &[TestTrie],
+) -> Result<Vec<Blake2bHash>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let mut ret = vec![*root_hash];
+    let mut txn = environment.create_read_write_txn()?;
+    for leaf in leaves {
+        if let Trie::Leaf { key, value } = leaf {
+            let current_root = *ret.last().unwrap();
+            match write::<_, _, _, _, E>(
+                correlation_id,
+                &mut txn,
+                store,
+                &current_root,
+                key,
+                value,
+            )? {
+                WriteResult::Written(hash) => ret.push(hash),
+                _ => panic!("write_leaves resulted in non-write"),
+            }
+        } else {
+            panic!("leaves should contain only leaves");
+        }
+    }
+    txn.commit()?;
+    Ok(ret)
+}
+
+fn delete<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    prefix: &[u8],
+) -> Result<DeleteResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let result = delete_prefix::<TestKey, TestValue, _, _, E>(
+        correlation_id,
+        &mut txn,
+        store,
+        root_hash,
+        prefix,
+    )?;
+    txn.commit()?;
+    Ok(result)
+}
+
+fn key_bytes(leaf: &TestTrie) -> Vec<u8> {
+    match leaf {
+        Trie::Leaf { key, .. } => key.to_bytes().unwrap(),
+        _ => panic!("leaf should be a leaf"),
+    }
+}
+
+fn deleting_the_last_written_leaf_restores_the_previous_root<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    empty_root_hash: &Blake2bHash,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let states = write_leaves::<_, _, E>(
+        correlation_id,
+        environment,
+        store,
+        empty_root_hash,
+        &TEST_LEAVES,
+    )?;
+
+    for num_leaves in 1..=TEST_LEAVES_LENGTH {
+        let prefix = key_bytes(&TEST_LEAVES[num_leaves - 1]);
+        let result = delete::<_, _, E>(
+            correlation_id,
+            environment,
+            store,
+            &states[num_leaves],
+            &prefix,
+        )?;
+        assert_eq!(result, DeleteResult::Deleted(states[num_leaves - 1]));
+    }
+
+    Ok(())
+}
+
+fn deleting_a_prefix_deletes_every_leaf_under_it<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    empty_root_hash: &Blake2bHash,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    // The keys of the first four leaves start with [0, 0, 0], unlike the last two.
+    let test_leaves = TEST_LEAVES;
+    let (deleted, retained) = test_leaves.split_at(4);
+
+    let root_hash = *write_leaves::<_, _, E>(
+        correlation_id,
+        environment,
+        store,
+        empty_root_hash,
+        &TEST_LEAVES,
+    )?
+    .last()
+    .unwrap();
+    let expected_root_hash = *write_leaves::<_, _, E>(
+        correlation_id,
+        environment,
+        store,
+        empty_root_hash,
+        retained,
+    )?
+    .last()
+    .unwrap();
+
+    let result = delete::<_, _, E>(correlation_id, environment, store, &root_hash, &[0, 0, 0])?;
+    assert_eq!(result, DeleteResult::Deleted(expected_root_hash));
+    check_leaves::<_, _, E>(
+        correlation_id,
+        environment,
+        store,
+        &expected_root_hash,
+        retained,
+        deleted,
+    )?;
+
+    Ok(())
+}
+
+fn deleting_an_absent_prefix_does_nothing<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    empty_root_hash: &Blake2bHash,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let root_hash = *write_leaves::<_, _, E>(
+        correlation_id,
+        environment,
+        store,
+        empty_root_hash,
+        &TEST_LEAVES,
+    )?
+    .last()
+    .unwrap();
+
+    let mut absent_prefixes = vec![vec![0, 3], vec![1]];
+    absent_prefixes.extend(TEST_LEAVES_ADJACENTS.iter().map(key_bytes));
+    for prefix in absent_prefixes {
+        let result = delete::<_, _, E>(correlation_id, environment, store, &root_hash, &prefix)?;
+        assert_eq!(result, DeleteResult::DoesNotExist);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn lmdb_deleting_the_last_written_leaf_restores_the_previous_root() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    deleting_the_last_written_leaf_restores_the_previous_root::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_deleting_the_last_written_leaf_restores_the_previous_root() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    deleting_the_last_written_leaf_restores_the_previous_root::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_deleting_a_prefix_deletes_every_leaf_under_it() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    deleting_a_prefix_deletes_every_leaf_under_it::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_deleting_a_prefix_deletes_every_leaf_under_it() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    deleting_a_prefix_deletes_every_leaf_under_it::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_deleting_an_absent_prefix_does_nothing() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    deleting_an_absent_prefix_does_nothing::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_deleting_an_absent_prefix_does_nothing() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    deleting_an_absent_prefix_does_nothing::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+}
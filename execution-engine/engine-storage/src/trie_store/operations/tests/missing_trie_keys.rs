@@ -0,0 +1,113 @@
+use super::*;
+use crate::error::{self, in_memory};
+use crate::trie_store::operations::missing_trie_keys;
+
+fn check_missing_trie_keys<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    expected: &[Blake2bHash],
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error> + std::fmt::Debug,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let txn: R::ReadTransaction = environment.create_read_txn()?;
+    let missing = missing_trie_keys::<TestKey, TestValue, R::ReadTransaction, S, E>(
+        correlation_id,
+        &txn,
+        store,
+        *root_hash,
+    )?;
+    assert_eq!(missing, expected);
+    txn.commit()?;
+    Ok(())
+}
+
+/// Returns the given tries except the one at `index`.
+fn without(tries: &[HashedTestTrie], index: usize) -> Vec<HashedTestTrie> {
+    let mut ret = tries.to_vec();
+    ret.remove(index);
+    ret
+}
+
+#[test]
+fn lmdb_finds_no_missing_trie_keys_in_n_leaf_full_trie() {
+    for generator in &TEST_TRIE_GENERATORS {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        check_missing_trie_keys::<_, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &[],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_finds_no_missing_trie_keys_in_n_leaf_full_trie() {
+    for generator in &TEST_TRIE_GENERATORS {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+
+        check_missing_trie_keys::<_, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &[],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn lmdb_finds_each_missing_trie_key_in_n_leaf_trie() {
+    for generator in &TEST_TRIE_GENERATORS {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+
+        for index in 0..tries.len() {
+            let context = LmdbTestContext::new(&without(&tries, index)).unwrap();
+
+            check_missing_trie_keys::<_, _, error::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                &root_hash,
+                &[tries[index].hash],
+            )
+            .unwrap();
+        }
+    }
+}
+
+#[test]
+fn in_memory_finds_each_missing_trie_key_in_n_leaf_trie() {
+    for generator in &TEST_TRIE_GENERATORS {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+
+        for index in 0..tries.len() {
+            let context = InMemoryTestContext::new(&without(&tries, index)).unwrap();
+
+            check_missing_trie_keys::<_, _, in_memory::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                &root_hash,
+                &[tries[index].hash],
+            )
+            .unwrap();
+        }
+    }
+}
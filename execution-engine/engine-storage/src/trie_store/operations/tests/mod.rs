@@ -1,6 +1,10 @@
+mod delete_prefix;
+mod missing_trie_keys;
 mod proptests;
 mod read;
+mod read_with_proof;
 mod scan;
+mod verify_integrity;
 mod write;
 
 use std::collections::HashMap;
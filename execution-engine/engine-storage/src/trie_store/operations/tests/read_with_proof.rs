@@ -0,0 +1,93 @@
+//! This module contains tests for [`read_with_proof`].
+//!
+//! The tests read each of the [`TEST_LEAVES`](super::TEST_LEAVES) from a
+//! series of "partial" tries and check that the returned proofs verify
+//! against the root they were read from, and only against that root.
+
+use super::*;
+use crate::error::{self, in_memory};
+use crate::trie::TrieMerkleProof;
+use crate::trie_store::operations::read_with_proof;
+
+fn check_proofs<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    present: &[TestTrie],
+    absent: &[TestTrie],
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let txn: R::ReadTransaction = environment.create_read_txn()?;
+    let fake_root: Blake2bHash = [1u8; 32].into();
+
+    for leaf in present {
+        if let Trie::Leaf { key, value } = leaf {
+            match read_with_proof::<_, _, _, _, E>(correlation_id, &txn, store, root, key)? {
+                ReadResult::Found(proof) => {
+                    assert_eq!(proof.key(), key);
+                    assert_eq!(proof.value(), value);
+                    assert!(proof.verify(root)?);
+                    assert!(!proof.verify(&fake_root)?);
+                }
+                _ => panic!("leaf should have been found"),
+            }
+        }
+    }
+
+    for leaf in absent {
+        if let Trie::Leaf { key, .. } = leaf {
+            let result: ReadResult<TrieMerkleProof<TestKey, TestValue>> =
+                read_with_proof::<_, _, _, _, E>(correlation_id, &txn, store, root, key)?;
+            assert_eq!(result, ReadResult::NotFound);
+        }
+    }
+
+    txn.commit()?;
+    Ok(())
+}
+
+#[test]
+fn lmdb_proofs_from_n_leaf_partial_trie_verify_against_root() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+        let (used, unused) = TEST_LEAVES.split_at(num_leaves);
+
+        check_proofs::<_, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            used,
+            unused,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_proofs_from_n_leaf_partial_trie_verify_against_root() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let (used, unused) = TEST_LEAVES.split_at(num_leaves);
+
+        check_proofs::<_, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            used,
+            unused,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+use super::*;
+use crate::error::{self, in_memory};
+use crate::trie_store::operations::{verify_integrity, IntegrityReport};
+
+fn check_integrity<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    expected: IntegrityReport,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error> + std::fmt::Debug,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let txn: R::ReadTransaction = environment.create_read_txn()?;
+    let report = verify_integrity::<TestKey, TestValue, R::ReadTransaction, S, E>(
+        correlation_id,
+        &txn,
+        store,
+        &[*root_hash],
+    )?;
+    assert_eq!(report, expected);
+    txn.commit()?;
+    Ok(())
+}
+
+/// Returns the given tries with the trie at `index` stored under the hash of its successor.
+fn with_corrupt(tries: &[HashedTestTrie], index: usize) -> Vec<HashedTestTrie> {
+    let mut ret = tries.to_vec();
+    ret[index].trie = tries[(index + 1) % tries.len()].trie.clone();
+    ret
+}
+
+#[test]
+fn lmdb_finds_no_corruption_in_n_leaf_full_trie() {
+    for generator in &TEST_TRIE_GENERATORS {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        check_integrity::<_, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            IntegrityReport {
+                checked: tries.len(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_finds_no_corruption_in_n_leaf_full_trie() {
+    for generator in &TEST_TRIE_GENERATORS {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+
+        check_integrity::<_, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            IntegrityReport {
+                checked: tries.len(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn lmdb_finds_dangling_root() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, _) = create_6_leaf_trie().unwrap();
+    let context = LmdbTestContext::new(&[]).unwrap();
+
+    check_integrity::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+        IntegrityReport {
+            dangling: vec![root_hash],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_finds_corrupt_root() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let root_index = tries
+        .iter()
+        .position(|trie| trie.hash == root_hash)
+        .unwrap();
+    let context = LmdbTestContext::new(&with_corrupt(&tries, root_index)).unwrap();
+
+    check_integrity::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+        IntegrityReport {
+            checked: 1,
+            corrupt: vec![root_hash],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_finds_corrupt_root() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let root_index = tries
+        .iter()
+        .position(|trie| trie.hash == root_hash)
+        .unwrap();
+    let context = InMemoryTestContext::new(&with_corrupt(&tries, root_index)).unwrap();
+
+    check_integrity::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+        IntegrityReport {
+            checked: 1,
+            corrupt: vec![root_hash],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
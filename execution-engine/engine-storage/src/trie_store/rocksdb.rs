@@ -0,0 +1,52 @@
+//! A RocksDB-backed trie store.
+//!
+//! Tries are kept in their own column family of a [`RocksDbEnvironment`],
+//! which is opened with the column families of the default trie store and of
+//! any named trie stores.
+use contract_ffi::bytesrepr::{FromBytes, ToBytes};
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::error;
+use crate::store::Store;
+use crate::transaction_source::rocksdb::{column_family_name, RocksDbEnvironment};
+use crate::trie::Trie;
+use crate::trie_store::{self, TrieStore};
+
+/// A RocksDB-backed trie store.
+#[derive(Debug, Clone)]
+pub struct RocksDbTrieStore {
+    name: String,
+}
+
+impl RocksDbTrieStore {
+    /// Returns a store backed by the column family of the given name.
+    ///
+    /// The environment must have been opened with `maybe_name`, see
+    /// [`RocksDbEnvironment::with_store_names`].
+    pub fn new(env: &RocksDbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let name = column_family_name(trie_store::NAME, maybe_name);
+        env.check_column_family(&name)?;
+        Ok(RocksDbTrieStore { name })
+    }
+}
+
+impl<K, V> Store<Blake2bHash, Trie<K, V>> for RocksDbTrieStore
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+{
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn handle(&self) -> Self::Handle {
+        self.name.to_owned()
+    }
+}
+
+impl<K, V> TrieStore<K, V> for RocksDbTrieStore
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+{
+}
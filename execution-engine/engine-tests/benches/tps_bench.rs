@@ -0,0 +1,276 @@
+#[macro_use]
+extern crate criterion;
+extern crate contract_ffi;
+extern crate engine_core;
+extern crate engine_grpc_server;
+extern crate engine_storage;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::env;
+
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, Criterion, Throughput};
+use tempfile::TempDir;
+
+use casperlabs_engine_tests::support::test_support::{
+    DeployBuilder, DeployHashGenerator, ExecRequestBuilder, InMemoryWasmTestBuilder,
+    LmdbWasmTestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, EngineState, MAX_PAYMENT};
+use engine_core::execution;
+use engine_grpc_server::engine_server::ipc::Deploy;
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_storage::global_state::StateProvider;
+use engine_storage::storage_config::StorageConfig;
+
+/// Environment variable setting the number of deploys measured per iteration.
+const BATCH_SIZE_VAR: &str = "TPS_BENCH_BATCH_SIZE";
+/// Environment variable setting the number of named keys of the account in the
+/// `large_named_keys_account` scenario.
+const NAMED_KEYS_VAR: &str = "TPS_BENCH_NAMED_KEYS";
+const DEFAULT_BATCH_SIZE: u64 = 3;
+const DEFAULT_NAMED_KEYS: u64 = 100;
+
+const GENESIS_ADDR: [u8; 32] = [1; 32];
+const GENESIS_VALIDATOR_ADDR: [u8; 32] = [42; 32];
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const TARGET_ADDR: [u8; 32] = [127; 32];
+const TRANSFER_AMOUNT: u64 = 1;
+const BOND_AMOUNT: u64 = 100_000;
+
+struct BenchConfig {
+    batch_size: u64,
+    named_keys: u64,
+}
+
+impl BenchConfig {
+    fn from_env() -> Self {
+        BenchConfig {
+            batch_size: env_or(BATCH_SIZE_VAR, DEFAULT_BATCH_SIZE),
+            named_keys: env_or(NAMED_KEYS_VAR, DEFAULT_NAMED_KEYS),
+        }
+    }
+}
+
+fn env_or(name: &str, default: u64) -> u64 {
+    match env::var(name) {
+        Ok(value) => value
+            .parse()
+            .unwrap_or_else(|_| panic!("{} should be a number, got {:?}", name, value)),
+        Err(_) => default,
+    }
+}
+
+/// A workload measured as one committed exec request per deploy.
+struct Scenario {
+    name: &'static str,
+    /// Deploys executed once before measuring.
+    setup: fn(&BenchConfig, &mut DeployHashGenerator) -> Vec<Deploy>,
+    /// Returns the deploy at `index` of the measured ones.
+    deploy: fn(u64, &mut DeployHashGenerator) -> Deploy,
+}
+
+const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        name: "new_account_transfers",
+        setup: no_setup,
+        deploy: new_account_transfer,
+    },
+    Scenario {
+        name: "stored_contract_calls",
+        setup: install_stored_contract,
+        deploy: stored_contract_call,
+    },
+    Scenario {
+        name: "pos_bond_unbond_churn",
+        setup: no_setup,
+        deploy: bond_or_unbond,
+    },
+    Scenario {
+        name: "large_named_keys_account",
+        setup: add_named_keys,
+        deploy: existing_account_transfer,
+    },
+];
+
+fn genesis_deploy(
+    deploy_hashes: &mut DeployHashGenerator,
+    session_file: &str,
+    session_args: impl contract_ffi::contract_api::argsparser::ArgsParser,
+) -> Deploy {
+    DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(MAX_PAYMENT),))
+        .with_session_code(session_file, session_args)
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .with_deploy_hash(deploy_hashes.next_deploy_hash())
+        .build()
+}
+
+fn no_setup(_config: &BenchConfig, _deploy_hashes: &mut DeployHashGenerator) -> Vec<Deploy> {
+    Vec::new()
+}
+
+fn new_account_transfer(_index: u64, deploy_hashes: &mut DeployHashGenerator) -> Deploy {
+    // Generated hashes never repeat, so they double as addresses of accounts which don't exist yet
+    let target = PublicKey::new(deploy_hashes.next_deploy_hash());
+    genesis_deploy(
+        deploy_hashes,
+        "transfer_purse_to_account.wasm",
+        (target, U512::from(TRANSFER_AMOUNT)),
+    )
+}
+
+fn install_stored_contract(
+    _config: &BenchConfig,
+    deploy_hashes: &mut DeployHashGenerator,
+) -> Vec<Deploy> {
+    vec![genesis_deploy(
+        deploy_hashes,
+        "versioned_contract_install.wasm",
+        (),
+    )]
+}
+
+fn stored_contract_call(_index: u64, deploy_hashes: &mut DeployHashGenerator) -> Deploy {
+    // Calls version 1 of the installed package, expecting it to return 1
+    genesis_deploy(deploy_hashes, "versioned_contract_call.wasm", (1u32, 1u32))
+}
+
+/// Bonds and unbonds in turn, each request applied to the stakes by the PoS step of the following
+/// block.
+fn bond_or_unbond(index: u64, deploy_hashes: &mut DeployHashGenerator) -> Deploy {
+    if index % 2 == 0 {
+        genesis_deploy(
+            deploy_hashes,
+            "pos_bonding.wasm",
+            (String::from("bond"), U512::from(BOND_AMOUNT)),
+        )
+    } else {
+        genesis_deploy(
+            deploy_hashes,
+            "pos_bonding.wasm",
+            (String::from("unbond"), Some(U512::from(BOND_AMOUNT))),
+        )
+    }
+}
+
+fn add_named_keys(config: &BenchConfig, deploy_hashes: &mut DeployHashGenerator) -> Vec<Deploy> {
+    let mut deploys: Vec<Deploy> = (0..config.named_keys)
+        .map(|index| {
+            genesis_deploy(
+                deploy_hashes,
+                "create_purse_01.wasm",
+                (format!("purse-{}", index),),
+            )
+        })
+        .collect();
+    // Creates the target account, so that the measured transfers don't create accounts
+    deploys.push(existing_account_transfer(0, deploy_hashes));
+    deploys
+}
+
+fn existing_account_transfer(_index: u64, deploy_hashes: &mut DeployHashGenerator) -> Deploy {
+    genesis_deploy(
+        deploy_hashes,
+        "transfer_purse_to_account.wasm",
+        (PublicKey::new(TARGET_ADDR), U512::from(TRANSFER_AMOUNT)),
+    )
+}
+
+/// Executes `deploy` in a block of its own, after the PoS step of the block.
+fn exec_and_commit<S>(builder: &mut WasmTestBuilder<S>, deploy: Deploy)
+where
+    S: StateProvider,
+    S::Error: Into<execution::Error>,
+    EngineState<S>: ExecutionEngineService,
+{
+    let exec_request = ExecRequestBuilder::new().push_deploy(deploy).build();
+    builder
+        .step(DEFAULT_BLOCK_TIME)
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+}
+
+fn bench_scenario<S>(
+    group: &mut BenchmarkGroup<WallTime>,
+    backend: &str,
+    mut builder: WasmTestBuilder<S>,
+    scenario: &Scenario,
+    config: &BenchConfig,
+) where
+    S: StateProvider,
+    S::Error: Into<execution::Error>,
+    EngineState<S>: ExecutionEngineService,
+{
+    let genesis_validators = {
+        let mut result = HashMap::new();
+        result.insert(
+            PublicKey::new(GENESIS_VALIDATOR_ADDR),
+            U512::from(GENESIS_VALIDATOR_STAKE),
+        );
+        result
+    };
+    builder.run_genesis(GENESIS_ADDR, genesis_validators);
+
+    let mut deploy_hashes = DeployHashGenerator::default();
+    for deploy in (scenario.setup)(config, &mut deploy_hashes) {
+        exec_and_commit(&mut builder, deploy);
+    }
+
+    let mut index = 0;
+    group.bench_function(backend, |b| {
+        b.iter(|| {
+            for _ in 0..config.batch_size {
+                let deploy = (scenario.deploy)(index, &mut deploy_hashes);
+                exec_and_commit(&mut builder, deploy);
+                index += 1;
+            }
+        })
+    });
+}
+
+pub fn tps_bench(c: &mut Criterion) {
+    let config = BenchConfig::from_env();
+    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    // Kept alive until all scenarios ran, as the LMDB builders use them
+    let data_dirs: RefCell<Vec<TempDir>> = RefCell::new(Vec::new());
+    let new_lmdb_builder = || {
+        let data_dir = TempDir::new().expect("should create temp dir");
+        let builder = LmdbWasmTestBuilder::new_with_storage_config(
+            data_dir.path(),
+            engine_config.clone(),
+            StorageConfig::new(),
+        );
+        data_dirs.borrow_mut().push(data_dir);
+        builder
+    };
+
+    for scenario in SCENARIOS {
+        let mut group = c.benchmark_group(format!("tps/{}", scenario.name));
+
+        // Minimize no of samples to keep the whole suite reasonably fast
+        group.sample_size(10);
+
+        // Measure by elements where one element/s is one transaction per second
+        group.throughput(Throughput::Elements(config.batch_size));
+
+        bench_scenario(
+            &mut group,
+            "in_memory",
+            InMemoryWasmTestBuilder::new(engine_config.clone()),
+            scenario,
+            &config,
+        );
+        bench_scenario(&mut group, "lmdb", new_lmdb_builder(), scenario, &config);
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, tps_bench);
+criterion_main!(benches);
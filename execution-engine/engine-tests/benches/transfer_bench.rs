@@ -10,14 +10,16 @@ use criterion::{Criterion, Throughput};
 use tempfile::TempDir;
 
 use casperlabs_engine_tests::support::test_support::{
-    DeployBuilder, ExecRequestBuilder, LmdbWasmTestBuilder, WasmTestResult, DEFAULT_BLOCK_TIME,
-    STANDARD_PAYMENT_CONTRACT,
+    DeployBuilder, DeployHashGenerator, ExecRequestBuilder, LmdbWasmTestBuilder, WasmTestResult,
+    DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
 };
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
+use engine_core::engine_state::engine_config::DEFAULT_MODULE_CACHE_SIZE;
 use engine_core::engine_state::EngineConfig;
 use engine_core::engine_state::MAX_PAYMENT;
 use engine_storage::global_state::lmdb::LmdbGlobalState;
+use engine_storage::storage_config::{StorageConfig, DEFAULT_TRIE_CACHE_SIZE};
 
 /// Size of batch used in multiple execs benchmark, and multiple deploys per exec cases.
 const TRANSFER_BATCH_SIZE: u64 = 3;
@@ -29,15 +31,31 @@ fn engine_with_payments() -> EngineConfig {
     EngineConfig::new().set_use_payment_code(true)
 }
 
-fn bootstrap(accounts: &[PublicKey]) -> (WasmTestResult<LmdbGlobalState>, TempDir) {
+fn bootstrap(
+    accounts: &[PublicKey],
+    engine_config: EngineConfig,
+) -> (WasmTestResult<LmdbGlobalState>, TempDir) {
+    bootstrap_with_storage_config(accounts, engine_config, StorageConfig::new())
+}
+
+fn bootstrap_with_storage_config(
+    accounts: &[PublicKey],
+    engine_config: EngineConfig,
+    storage_config: StorageConfig,
+) -> (WasmTestResult<LmdbGlobalState>, TempDir) {
     let accounts_bytes: Vec<Vec<u8>> = accounts
         .iter()
-        .map(|public_key| public_key.value().to_vec())
+        .map(|public_key| public_key.as_bytes().to_vec())
         .collect();
     let amount = U512::one();
 
     let data_dir = TempDir::new().expect("should create temp dir");
-    let result = LmdbWasmTestBuilder::new_with_config(&data_dir.path(), engine_with_payments())
+    let mut builder = LmdbWasmTestBuilder::new_with_storage_config(
+        &data_dir.path(),
+        engine_config,
+        storage_config,
+    );
+    let result = builder
         .run_genesis(GENESIS_ADDR, HashMap::new())
         .exec_with_args(
             GENESIS_ADDR,
@@ -57,10 +75,14 @@ fn bootstrap(accounts: &[PublicKey]) -> (WasmTestResult<LmdbGlobalState>, TempDi
 
 /// Uses multiple exec requests with a single deploy to transfer tokens. Executes all transfers in
 /// batch determined by value of TRANSFER_BATCH_SIZE.
-fn transfer_to_account_multiple_execs(builder: &mut LmdbWasmTestBuilder, account: PublicKey) {
+fn transfer_to_account_multiple_execs(
+    builder: &mut LmdbWasmTestBuilder,
+    account: PublicKey,
+    deploy_hashes: &mut DeployHashGenerator,
+) {
     let amount = U512::one();
     // To see raw numbers take current time
-    for i in 0..TRANSFER_BATCH_SIZE {
+    for _ in 0..TRANSFER_BATCH_SIZE {
         builder
             .exec_with_args(
                 GENESIS_ADDR,
@@ -69,7 +91,7 @@ fn transfer_to_account_multiple_execs(builder: &mut LmdbWasmTestBuilder, account
                 "transfer_to_existing_account.wasm",
                 (account, amount),  //args
                 DEFAULT_BLOCK_TIME, // blocktime
-                [2 + i as u8; 32],  // deploy_hash
+                deploy_hashes.next_deploy_hash(),
             )
             .expect_success()
             .commit();
@@ -77,16 +99,46 @@ fn transfer_to_account_multiple_execs(builder: &mut LmdbWasmTestBuilder, account
 }
 
 /// Executes multiple deploys per single exec with based on TRANSFER_BATCH_SIZE.
-fn transfer_to_account_multiple_deploys(builder: &mut LmdbWasmTestBuilder, account: PublicKey) {
+fn transfer_to_account_multiple_deploys(
+    builder: &mut LmdbWasmTestBuilder,
+    account: PublicKey,
+    deploy_hashes: &mut DeployHashGenerator,
+) {
     let mut exec_builder = ExecRequestBuilder::new();
 
-    for i in 0..TRANSFER_BATCH_SIZE {
+    for _ in 0..TRANSFER_BATCH_SIZE {
         let deploy = DeployBuilder::default()
             .with_address(GENESIS_ADDR)
             .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(MAX_PAYMENT),))
             .with_session_code("transfer_to_existing_account.wasm", (account, U512::one()))
             .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
-            .with_deploy_hash([2 + i as u8; 32]) // deploy_hash
+            .with_deploy_hash(deploy_hashes.next_deploy_hash())
+            .build();
+        exec_builder = exec_builder.push_deploy(deploy);
+    }
+
+    builder
+        .exec_with_exec_request(exec_builder.build())
+        .expect_success()
+        .commit();
+}
+
+/// Executes multiple native transfers per single exec based on TRANSFER_BATCH_SIZE. Native
+/// transfers run no wasm, so this measures the throughput gained over
+/// `transfer_to_account_multiple_deploys`.
+fn native_transfer_multiple_deploys(
+    builder: &mut LmdbWasmTestBuilder,
+    account: PublicKey,
+    deploy_hashes: &mut DeployHashGenerator,
+) {
+    let mut exec_builder = ExecRequestBuilder::new();
+
+    for _ in 0..TRANSFER_BATCH_SIZE {
+        let deploy = DeployBuilder::default()
+            .with_address(GENESIS_ADDR)
+            .with_native_transfer(None, account, U512::one(), None)
+            .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+            .with_deploy_hash(deploy_hashes.next_deploy_hash())
             .build();
         exec_builder = exec_builder.push_deploy(deploy);
     }
@@ -111,8 +163,9 @@ pub fn transfer_bench(c: &mut Criterion) {
     group.throughput(Throughput::Elements(TRANSFER_BATCH_SIZE));
 
     // Bootstrap database once
-    let (result_1, _source_dir_1) = bootstrap(&bootstrap_accounts);
+    let (result_1, _source_dir_1) = bootstrap(&bootstrap_accounts, engine_with_payments());
     let mut builder_1 = LmdbWasmTestBuilder::from_result(result_1);
+    let mut deploy_hashes_1 = DeployHashGenerator::default();
 
     group.bench_function(
         format!(
@@ -122,13 +175,18 @@ pub fn transfer_bench(c: &mut Criterion) {
         |b| {
             b.iter(|| {
                 // Execute multiple deploys with one exec request each
-                transfer_to_account_multiple_execs(&mut builder_1, target_account)
+                transfer_to_account_multiple_execs(
+                    &mut builder_1,
+                    target_account,
+                    &mut deploy_hashes_1,
+                )
             })
         },
     );
 
-    let (result_2, _source_dir_2) = bootstrap(&bootstrap_accounts);
+    let (result_2, _source_dir_2) = bootstrap(&bootstrap_accounts, engine_with_payments());
     let mut builder_2 = LmdbWasmTestBuilder::from_result(result_2);
+    let mut deploy_hashes_2 = DeployHashGenerator::default();
 
     group.bench_function(
         format!(
@@ -139,12 +197,118 @@ pub fn transfer_bench(c: &mut Criterion) {
             // Create new directory with copied contents of existing bootstrapped LMDB database
             b.iter(|| {
                 // Execute multiple deploys with a single exec request
-                transfer_to_account_multiple_deploys(&mut builder_2, target_account)
+                transfer_to_account_multiple_deploys(
+                    &mut builder_2,
+                    target_account,
+                    &mut deploy_hashes_2,
+                )
             })
         },
     );
+
+    let (result_3, _source_dir_3) = bootstrap(&bootstrap_accounts, engine_with_payments());
+    let mut builder_3 = LmdbWasmTestBuilder::from_result(result_3);
+    let mut deploy_hashes_3 = DeployHashGenerator::default();
+
+    group.bench_function(
+        format!(
+            "native_transfer_multiple_deploys_per_exec/{}",
+            TRANSFER_BATCH_SIZE
+        ),
+        |b| {
+            b.iter(|| {
+                // Execute multiple native transfers with a single exec request
+                native_transfer_multiple_deploys(
+                    &mut builder_3,
+                    target_account,
+                    &mut deploy_hashes_3,
+                )
+            })
+        },
+    );
+    group.finish();
+}
+
+/// Compares transfers with and without the module cache, which spares re-preprocessing the
+/// standard payment and transfer contracts on every deploy.
+pub fn module_cache_bench(c: &mut Criterion) {
+    let target_account = PublicKey::new(TARGET_ADDR);
+    let bootstrap_accounts = vec![target_account];
+
+    let mut group = c.benchmark_group("module_cache");
+
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(TRANSFER_BATCH_SIZE));
+
+    for &(name, module_cache_size) in &[("cached", DEFAULT_MODULE_CACHE_SIZE), ("uncached", 0)] {
+        let engine_config = engine_with_payments().set_module_cache_size(module_cache_size);
+        let (result, _source_dir) = bootstrap(&bootstrap_accounts, engine_config);
+        let mut builder = LmdbWasmTestBuilder::from_result(result);
+        let mut deploy_hashes = DeployHashGenerator::default();
+
+        group.bench_function(
+            format!(
+                "transfer_to_existing_account_multiple_deploys_per_exec/{}/{}",
+                name, TRANSFER_BATCH_SIZE
+            ),
+            |b| {
+                b.iter(|| {
+                    transfer_to_account_multiple_deploys(
+                        &mut builder,
+                        target_account,
+                        &mut deploy_hashes,
+                    )
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Compares transfers with and without the trie cache, which spares reading the trie nodes leading
+/// to the system contracts and accounts from LMDB on every exec.
+pub fn trie_cache_bench(c: &mut Criterion) {
+    let target_account = PublicKey::new(TARGET_ADDR);
+    let bootstrap_accounts = vec![target_account];
+
+    let mut group = c.benchmark_group("trie_cache");
+
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(TRANSFER_BATCH_SIZE));
+
+    for &(name, trie_cache_size) in &[("cached", DEFAULT_TRIE_CACHE_SIZE), ("uncached", 0)] {
+        let storage_config = StorageConfig::new().set_trie_cache_size(trie_cache_size);
+        let (result, _source_dir) = bootstrap_with_storage_config(
+            &bootstrap_accounts,
+            engine_with_payments(),
+            storage_config,
+        );
+        let mut builder = LmdbWasmTestBuilder::from_result(result);
+        let mut deploy_hashes = DeployHashGenerator::default();
+
+        group.bench_function(
+            format!(
+                "transfer_to_existing_account_multiple_execs/{}/{}",
+                name, TRANSFER_BATCH_SIZE
+            ),
+            |b| {
+                b.iter(|| {
+                    transfer_to_account_multiple_execs(
+                        &mut builder,
+                        target_account,
+                        &mut deploy_hashes,
+                    )
+                })
+            },
+        );
+    }
     group.finish();
 }
 
-criterion_group!(benches, transfer_bench);
+criterion_group!(
+    benches,
+    transfer_bench,
+    module_cache_bench,
+    trie_cache_bench
+);
 criterion_main!(benches);
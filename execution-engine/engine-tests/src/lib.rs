@@ -1,6 +1,4 @@
 extern crate grpc;
-
-#[cfg(test)]
 #[macro_use]
 extern crate lazy_static;
 extern crate lmdb;
@@ -14,7 +14,6 @@ use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
 use engine_shared::gas::Gas;
 use engine_shared::newtypes::CorrelationId;
 use engine_storage::global_state::StateProvider;
-use engine_wasm_prep::wasm_costs::WasmCosts;
 use engine_wasm_prep::WasmiPreprocessor;
 use std::cell::RefCell;
 use std::collections::BTreeSet;
@@ -23,6 +22,7 @@ use std::rc::Rc;
 
 const INIT_FN_STORE_ID: u32 = 0;
 const INIT_PROTOCOL_VERSION: u64 = 1;
+const INIT_BLOCK_HEIGHT: u64 = 0;
 
 /// This function allows executing the contract stored in the given `wasm_file`, while capturing the
 /// output. It is essentially the same functionality as `Executor::exec`, but the return value of
@@ -91,6 +91,7 @@ where
         &account,
         base_key,
         BlockTime(block_time),
+        INIT_BLOCK_HEIGHT,
         deploy_hash,
         gas_limit,
         gas_counter,
@@ -106,7 +107,10 @@ where
         module_bytes: wasm_bytes,
         args: Vec::new(),
     };
-    let wasm_costs = WasmCosts::from_version(protocol_version).unwrap();
+    let wasm_costs = builder
+        .get_engine_state()
+        .get_wasm_costs(protocol_version)
+        .expect("should get wasm costs");
     let preprocessor = WasmiPreprocessor::new(wasm_costs);
     let parity_module = builder
         .get_engine_state()
@@ -115,6 +119,7 @@ where
             &deploy_item,
             &account,
             correlation_id,
+            wasm_costs,
             &preprocessor,
         )
         .expect("should get wasm module");
@@ -123,7 +128,13 @@ where
         execution::instance_and_memory(parity_module.clone(), protocol_version)
             .expect("should be able to make wasm instance from module");
 
-    let mut runtime = execution::Runtime::new(memory, parity_module, context);
+    let mut runtime = execution::Runtime::new(
+        memory,
+        parity_module,
+        wasm_costs,
+        context,
+        builder.get_engine_state().config().use_system_contracts(),
+    );
 
     match instance.invoke_export("call", &[], &mut runtime) {
         Ok(_) => None,
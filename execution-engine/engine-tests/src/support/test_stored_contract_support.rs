@@ -7,13 +7,13 @@ use grpc::RequestOptions;
 
 use contract_ffi::uref::URef;
 use engine_core::engine_state::utils::WasmiBytes;
-use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_core::engine_state::{hash_deploy, EngineConfig, EngineState, CONV_RATE};
 use engine_core::execution::POS_NAME;
 use engine_grpc_server::engine_server::ipc::{
     CommitRequest, DeployCode, DeployItem, DeployPayload, DeployResult,
     DeployResult_ExecutionResult, DeployResult_PreconditionFailure, ExecuteRequest,
-    ExecuteResponse, GenesisRequest, GenesisResponse, QueryRequest, StoredContractHash,
-    StoredContractName, StoredContractURef,
+    ExecuteResponse, GenesisRequest, GenesisResponse, NativeTransfer, QueryRequest,
+    StoredContractHash, StoredContractName, StoredContractURef,
 };
 use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
 use engine_grpc_server::engine_server::mappings::{to_domain_validators, CommitTransforms};
@@ -190,18 +190,64 @@ impl DeployBuilder {
         self
     }
 
+    /// Replaces the serialized args of the session already set on the builder, e.g. to pass args
+    /// which fail to deserialize.
+    pub fn with_session_args_bytes(mut self, args_bytes: &[u8]) -> Self {
+        let args = args_bytes.to_vec();
+        let session = self.deploy.mut_session();
+        if session.has_deploy_code() {
+            session.mut_deploy_code().set_args(args);
+        } else if session.has_stored_contract_hash() {
+            session.mut_stored_contract_hash().set_args(args);
+        } else if session.has_stored_contract_name() {
+            session.mut_stored_contract_name().set_args(args);
+        } else if session.has_stored_contract_uref() {
+            session.mut_stored_contract_uref().set_args(args);
+        } else {
+            panic!("session should be set before its args");
+        }
+        self
+    }
+
+    pub fn with_native_transfer(
+        mut self,
+        source: Option<URef>,
+        target: contract_ffi::value::account::PublicKey,
+        amount: contract_ffi::value::U512,
+        id: Option<u64>,
+    ) -> Self {
+        let mut native_transfer = NativeTransfer::new();
+        if let Some(source) = source {
+            native_transfer.set_source(source.into());
+        }
+        native_transfer.set_target(target.as_bytes().to_vec());
+        native_transfer.set_amount(amount.into());
+        native_transfer.set_id(id.unwrap_or_default());
+        self.deploy.set_native_transfer(native_transfer);
+        self
+    }
+
     pub fn with_deploy_hash(mut self, deploy_hash: [u8; 32]) -> Self {
         self.deploy.set_deploy_hash(deploy_hash.to_vec());
         self
     }
 
+    /// Sets the header and body of the deploy, and its hash to the hash of them.
+    pub fn with_header_and_body(mut self, header: &[u8], body: &[u8]) -> Self {
+        self.deploy.set_header(header.to_vec());
+        self.deploy.set_body(body.to_vec());
+        self.deploy
+            .set_deploy_hash(hash_deploy(header, body).to_vec());
+        self
+    }
+
     pub fn with_authorization_keys(
         mut self,
         authorization_keys: &[contract_ffi::value::account::PublicKey],
     ) -> Self {
         let authorization_keys = authorization_keys
             .iter()
-            .map(|public_key| public_key.value().to_vec())
+            .map(|public_key| public_key.as_bytes().to_vec())
             .collect();
         self.deploy.set_authorization_keys(authorization_keys);
         self
@@ -216,7 +262,7 @@ impl Default for DeployBuilder {
     fn default() -> Self {
         let mut deploy = DeployItem::new();
         deploy.set_motes_transferred_in_payment(1_000_000_000);
-        deploy.set_gas_price(1);
+        deploy.set_gas_price(CONV_RATE);
         DeployBuilder { deploy }
     }
 }
@@ -290,7 +336,7 @@ pub fn get_mock_deploy() -> DeployItem {
     let mut deploy = DeployItem::new();
     deploy.set_address(MOCKED_ACCOUNT_ADDRESS.to_vec());
     deploy.set_motes_transferred_in_payment(1000);
-    deploy.set_gas_price(1);
+    deploy.set_gas_price(CONV_RATE);
     deploy.set_deploy_hash(vec![1; 32]);
     let mut deploy_code = DeployCode::new();
     deploy_code.set_code(test_utils::create_empty_wasm_module_bytes());
@@ -364,7 +410,7 @@ pub fn create_genesis_request(
         .iter()
         .map(|(pk, bond)| {
             let mut grpc_bond = ipc::Bond::new();
-            grpc_bond.set_validator_public_key(pk.value().to_vec());
+            grpc_bond.set_validator_public_key(pk.as_bytes().to_vec());
             grpc_bond.set_stake((*bond).into());
             grpc_bond
         })
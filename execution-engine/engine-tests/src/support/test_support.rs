@@ -7,12 +7,19 @@ use std::sync::Arc;
 
 use grpc::RequestOptions;
 use lmdb::DatabaseFlags;
+use protobuf::Message;
 
 use contract_ffi::key::Key;
+use contract_ffi::system_contracts::error::PosError;
+use contract_ffi::system_contracts::pos::RefundRatio;
+use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
-use engine_core::engine_state::genesis::GenesisConfig;
+use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
 use engine_core::engine_state::utils::WasmiBytes;
-use engine_core::engine_state::{EngineConfig, EngineState, MAX_PAYMENT, SYSTEM_ACCOUNT_ADDR};
+use engine_core::engine_state::{
+    hash_deploy, EngineConfig, EngineState, CONV_RATE, DEFAULT_MIN_GAS_PRICE,
+    DEFAULT_NATIVE_TRANSFER_COST, MAX_PAYMENT, SYSTEM_ACCOUNT_ADDR,
+};
 use engine_core::execution::{self, MINT_NAME, POS_NAME};
 use engine_grpc_server::engine_server::ipc::{
     CommitRequest, Deploy, DeployCode, DeployResult, DeployResult_ExecutionResult,
@@ -24,6 +31,8 @@ use engine_grpc_server::engine_server::mappings::{to_domain_validators, CommitTr
 use engine_grpc_server::engine_server::state::{BigInt, ProtocolVersion};
 use engine_grpc_server::engine_server::{ipc, transforms};
 use engine_shared::gas::Gas;
+use engine_shared::key_limits::KeyLimits;
+use engine_shared::motes::Motes;
 use engine_shared::newtypes::Blake2bHash;
 use engine_shared::os::get_page_size;
 use engine_shared::test_utils;
@@ -32,14 +41,32 @@ use engine_storage::global_state::in_memory::InMemoryGlobalState;
 use engine_storage::global_state::lmdb::LmdbGlobalState;
 use engine_storage::global_state::StateProvider;
 use engine_storage::protocol_data_store::lmdb::LmdbProtocolDataStore;
+use engine_storage::storage_config::StorageConfig;
 use engine_storage::transaction_source::lmdb::LmdbEnvironment;
 use engine_storage::trie_store::lmdb::LmdbTrieStore;
+use engine_wasm_prep::wasm_costs::WasmCosts;
 use transforms::TransformEntry;
 pub const DEFAULT_BLOCK_TIME: u64 = 0;
 pub const MOCKED_ACCOUNT_ADDRESS: [u8; 32] = [48u8; 32];
 pub const COMPILED_WASM_PATH: &str = "../target/wasm32-unknown-unknown/release";
 pub const GENESIS_INITIAL_BALANCE: u64 = 100_000_000_000;
 
+pub const MINT_INSTALL_CONTRACT: &str = "mint_install.wasm";
+pub const POS_INSTALL_CONTRACT: &str = "pos_install.wasm";
+pub const DEFAULT_CHAIN_NAME: &str = "casperlabs";
+pub const DEFAULT_GENESIS_TIMESTAMP: u64 = 0;
+pub const DEFAULT_PROTOCOL_VERSION: u64 = 1;
+pub const DEFAULT_ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+pub const DEFAULT_ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
+pub const DEFAULT_ACCOUNT_INITIAL_BALANCE: u64 = 100_000_000_000;
+pub const DEFAULT_ACCOUNT_1_BONDED_AMOUNT: u64 = 1_000_000;
+
+lazy_static! {
+    /// Genesis config with two funded accounts, the first of which is bonded as the sole
+    /// validator, and the default costs and limits.
+    pub static ref DEFAULT_GENESIS_CONFIG: GenesisConfig = GenesisConfigBuilder::new().build();
+}
+
 /// LMDB initial map size is calculated based on DEFAULT_LMDB_PAGES and systems page size.
 ///
 /// This default value should give 1MiB initial map size by default.
@@ -50,6 +77,8 @@ pub type LmdbWasmTestBuilder = WasmTestBuilder<LmdbGlobalState>;
 
 pub struct DeployBuilder {
     deploy: Deploy,
+    bad_deploy_hash: bool,
+    derived_deploy_hash: bool,
 }
 
 impl DeployBuilder {
@@ -96,24 +125,132 @@ impl DeployBuilder {
         self
     }
 
+    /// Replaces the serialized args of the session code, e.g. to pass args which fail to
+    /// deserialize.
+    ///
+    /// Sessions of a [`Deploy`] are always wasm; stored contract sessions are built with
+    /// `test_stored_contract_support::DeployBuilder` instead.
+    pub fn with_session_args_bytes(mut self, args_bytes: &[u8]) -> Self {
+        self.deploy.mut_session().set_args(args_bytes.to_vec());
+        self
+    }
+
     pub fn with_authorization_keys(
         mut self,
         authorization_keys: &[contract_ffi::value::account::PublicKey],
     ) -> Self {
         let authorization_keys = authorization_keys
             .iter()
-            .map(|public_key| public_key.value().to_vec())
+            .map(|public_key| public_key.as_bytes().to_vec())
             .collect();
         self.deploy.set_authorization_keys(authorization_keys);
         self
     }
 
+    pub fn with_approvals(
+        mut self,
+        approvals: &[engine_core::engine_state::approval::Approval],
+    ) -> Self {
+        let approvals = approvals
+            .iter()
+            .map(|approval| {
+                let mut ipc_approval = ipc::Approval::new();
+                ipc_approval.set_public_key(approval.public_key().as_bytes().to_vec());
+                ipc_approval.set_signature(approval.signature().to_vec());
+                ipc_approval
+            })
+            .collect();
+        self.deploy.set_approvals(approvals);
+        self
+    }
+
     pub fn with_deploy_hash(mut self, hash: [u8; 32]) -> Self {
         self.deploy.set_deploy_hash(hash.to_vec());
         self
     }
 
-    pub fn build(self) -> Deploy {
+    /// Sets the header and body of the deploy, and its hash to the hash of them.
+    pub fn with_header_and_body(mut self, header: &[u8], body: &[u8]) -> Self {
+        self.deploy.set_header(header.to_vec());
+        self.deploy.set_body(body.to_vec());
+        self.deploy
+            .set_deploy_hash(hash_deploy(header, body).to_vec());
+        self
+    }
+
+    /// Makes the hash of the built deploy differ from the hash of its header and body.
+    pub fn with_bad_deploy_hash(mut self) -> Self {
+        self.bad_deploy_hash = true;
+        self
+    }
+
+    /// Sets the hash of the built deploy to the hash of its serialized contents, so distinct
+    /// deploys get distinct hashes without picking them by hand.
+    ///
+    /// Unlike [`with_header_and_body`](DeployBuilder::with_header_and_body), the derived hash
+    /// doesn't pass deploy hash verification.
+    pub fn with_derived_deploy_hash(mut self) -> Self {
+        self.derived_deploy_hash = true;
+        self
+    }
+
+    pub fn with_dependencies(mut self, dependencies: &[[u8; 32]]) -> Self {
+        let dependencies = dependencies
+            .iter()
+            .map(|dependency| dependency.to_vec())
+            .collect();
+        self.deploy.set_dependencies(dependencies);
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.deploy.set_timestamp(timestamp);
+        self
+    }
+
+    pub fn with_ttl_millis(mut self, ttl_millis: u32) -> Self {
+        self.deploy.set_ttl_millis(ttl_millis);
+        self
+    }
+
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.deploy.set_gas_price(gas_price);
+        self
+    }
+
+    pub fn with_native_transfer(
+        mut self,
+        source: Option<contract_ffi::uref::URef>,
+        target: contract_ffi::value::account::PublicKey,
+        amount: U512,
+        id: Option<u64>,
+    ) -> Self {
+        let mut native_transfer = ipc::NativeTransfer::new();
+        if let Some(source) = source {
+            native_transfer.set_source(source.into());
+        }
+        native_transfer.set_target(target.as_bytes().to_vec());
+        native_transfer.set_amount(amount.into());
+        native_transfer.set_id(id.unwrap_or_default());
+        self.deploy.set_native_transfer(native_transfer);
+        self
+    }
+
+    pub fn build(mut self) -> Deploy {
+        if self.derived_deploy_hash {
+            self.deploy.clear_deploy_hash();
+            let deploy_bytes = self
+                .deploy
+                .write_to_bytes()
+                .expect("should serialize deploy");
+            let deploy_hash: [u8; 32] = Blake2bHash::new(&deploy_bytes).into();
+            self.deploy.set_deploy_hash(deploy_hash.to_vec());
+        }
+        if self.bad_deploy_hash {
+            let mut deploy_hash = hash_deploy(self.deploy.get_header(), self.deploy.get_body());
+            deploy_hash[0] = !deploy_hash[0];
+            self.deploy.set_deploy_hash(deploy_hash.to_vec());
+        }
         self.deploy
     }
 }
@@ -121,8 +258,39 @@ impl DeployBuilder {
 impl Default for DeployBuilder {
     fn default() -> Self {
         let mut deploy = Deploy::new();
-        deploy.set_gas_price(1);
-        DeployBuilder { deploy }
+        deploy.set_gas_price(CONV_RATE);
+        DeployBuilder {
+            deploy,
+            bad_deploy_hash: false,
+            derived_deploy_hash: false,
+        }
+    }
+}
+
+/// Generates distinct deploy hashes for deploys which may otherwise be identical, as executed
+/// deploys can't be replayed.
+///
+/// Generators with different namespaces never generate the same hash.
+#[derive(Debug, Default)]
+pub struct DeployHashGenerator {
+    namespace: u64,
+    counter: u64,
+}
+
+impl DeployHashGenerator {
+    pub fn new(namespace: u64) -> Self {
+        DeployHashGenerator {
+            namespace,
+            counter: 0,
+        }
+    }
+
+    pub fn next_deploy_hash(&mut self) -> [u8; 32] {
+        self.counter += 1;
+        let mut deploy_hash = [0u8; 32];
+        deploy_hash[..8].copy_from_slice(&self.namespace.to_le_bytes());
+        deploy_hash[8..16].copy_from_slice(&self.counter.to_le_bytes());
+        deploy_hash
     }
 }
 
@@ -152,6 +320,16 @@ impl ExecRequestBuilder {
         self
     }
 
+    pub fn with_block_height(mut self, block_height: u64) -> Self {
+        self.exec_request.set_block_height(block_height);
+        self
+    }
+
+    pub fn with_proposer(mut self, proposer: contract_ffi::value::account::PublicKey) -> Self {
+        self.exec_request.set_proposer(proposer.as_bytes().to_vec());
+        self
+    }
+
     pub fn with_protocol_version(mut self, version: u64) -> Self {
         let mut protocol_version = ProtocolVersion::new();
         protocol_version.set_value(version);
@@ -159,6 +337,11 @@ impl ExecRequestBuilder {
         self
     }
 
+    pub fn with_trace(mut self, trace: bool) -> Self {
+        self.exec_request.set_trace(trace);
+        self
+    }
+
     pub fn build(mut self) -> ExecRequest {
         let mut deploys: protobuf::RepeatedField<Deploy> = <protobuf::RepeatedField<Deploy>>::new();
         for deploy in self.deploys {
@@ -184,6 +367,130 @@ impl Default for ExecRequestBuilder {
     }
 }
 
+pub struct GenesisConfigBuilder {
+    name: String,
+    timestamp: u64,
+    protocol_version: u64,
+    accounts: Vec<GenesisAccount>,
+    wasm_costs: WasmCosts,
+    refund_ratio: RefundRatio,
+    min_gas_price: u64,
+    native_transfer_cost: u64,
+    key_limits: KeyLimits,
+}
+
+impl GenesisConfigBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_protocol_version(mut self, protocol_version: u64) -> Self {
+        self.protocol_version = protocol_version;
+        self
+    }
+
+    /// Replaces the default accounts with `accounts`.
+    pub fn with_accounts(mut self, accounts: Vec<GenesisAccount>) -> Self {
+        self.accounts = accounts;
+        self
+    }
+
+    pub fn push_account(mut self, account: GenesisAccount) -> Self {
+        self.accounts.push(account);
+        self
+    }
+
+    /// Adds a validator funded with [`DEFAULT_ACCOUNT_INITIAL_BALANCE`] and bonded with
+    /// `bonded_amount`.
+    pub fn push_validator(mut self, public_key: PublicKey, bonded_amount: U512) -> Self {
+        self.accounts.push(GenesisAccount::new(
+            public_key,
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            Motes::new(bonded_amount),
+        ));
+        self
+    }
+
+    pub fn with_wasm_costs(mut self, wasm_costs: WasmCosts) -> Self {
+        self.wasm_costs = wasm_costs;
+        self
+    }
+
+    pub fn with_refund_ratio(mut self, refund_ratio: RefundRatio) -> Self {
+        self.refund_ratio = refund_ratio;
+        self
+    }
+
+    pub fn with_min_gas_price(mut self, min_gas_price: u64) -> Self {
+        self.min_gas_price = min_gas_price;
+        self
+    }
+
+    pub fn with_native_transfer_cost(mut self, native_transfer_cost: u64) -> Self {
+        self.native_transfer_cost = native_transfer_cost;
+        self
+    }
+
+    pub fn with_key_limits(mut self, key_limits: KeyLimits) -> Self {
+        self.key_limits = key_limits;
+        self
+    }
+
+    pub fn build(self) -> GenesisConfig {
+        GenesisConfig::new(
+            self.name,
+            self.timestamp,
+            self.protocol_version,
+            read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+            read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+            self.accounts,
+            self.wasm_costs,
+            self.refund_ratio,
+            self.min_gas_price,
+            self.native_transfer_cost,
+            self.key_limits,
+        )
+    }
+}
+
+impl Default for GenesisConfigBuilder {
+    fn default() -> Self {
+        let accounts = vec![
+            GenesisAccount::new(
+                PublicKey::new(DEFAULT_ACCOUNT_1_ADDR),
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+                Motes::new(DEFAULT_ACCOUNT_1_BONDED_AMOUNT.into()),
+            ),
+            GenesisAccount::new(
+                PublicKey::new(DEFAULT_ACCOUNT_2_ADDR),
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+                Motes::zero(),
+            ),
+        ];
+        GenesisConfigBuilder {
+            name: DEFAULT_CHAIN_NAME.to_string(),
+            timestamp: DEFAULT_GENESIS_TIMESTAMP,
+            protocol_version: DEFAULT_PROTOCOL_VERSION,
+            accounts,
+            wasm_costs: WasmCosts::default(),
+            refund_ratio: RefundRatio::default(),
+            min_gas_price: DEFAULT_MIN_GAS_PRICE,
+            native_transfer_cost: DEFAULT_NATIVE_TRANSFER_COST,
+            key_limits: KeyLimits::default(),
+        }
+    }
+}
+
 pub fn get_protocol_version() -> ProtocolVersion {
     let mut protocol_version: ProtocolVersion = ProtocolVersion::new();
     protocol_version.set_value(1);
@@ -193,7 +500,7 @@ pub fn get_protocol_version() -> ProtocolVersion {
 pub fn get_mock_deploy() -> Deploy {
     let mut deploy = Deploy::new();
     deploy.set_address(MOCKED_ACCOUNT_ADDRESS.to_vec());
-    deploy.set_gas_price(1);
+    deploy.set_gas_price(CONV_RATE);
     let mut deploy_code = DeployCode::new();
     deploy_code.set_code(test_utils::create_empty_wasm_module_bytes());
     deploy.set_session(deploy_code);
@@ -244,8 +551,7 @@ pub fn create_genesis_request(
         let contract_file = "mint_token.wasm";
         let wasm_bytes = read_wasm_file_bytes(contract_file);
         let wasmi_bytes =
-            WasmiBytes::new(&wasm_bytes, engine_wasm_prep::wasm_costs::WasmCosts::free())
-                .expect("should have wasmi bytes");
+            WasmiBytes::new(&wasm_bytes, WasmCosts::free()).expect("should have wasmi bytes");
         contracts.insert(SystemContractType::Mint, wasmi_bytes);
         ret.set_code(wasm_bytes);
         ret
@@ -256,8 +562,7 @@ pub fn create_genesis_request(
         let contract_file = "pos.wasm";
         let wasm_bytes = read_wasm_file_bytes(contract_file);
         let wasmi_bytes =
-            WasmiBytes::new(&wasm_bytes, engine_wasm_prep::wasm_costs::WasmCosts::free())
-                .expect("should have wasmi bytes");
+            WasmiBytes::new(&wasm_bytes, WasmCosts::free()).expect("should have wasmi bytes");
         contracts.insert(SystemContractType::ProofOfStake, wasmi_bytes);
         ret.set_code(wasm_bytes);
         ret
@@ -267,7 +572,7 @@ pub fn create_genesis_request(
         .iter()
         .map(|(pk, bond)| {
             let mut grpc_bond = ipc::Bond::new();
-            grpc_bond.set_validator_public_key(pk.value().to_vec());
+            grpc_bond.set_validator_public_key(pk.as_bytes().to_vec());
             grpc_bond.set_stake((*bond).into());
             grpc_bond
         })
@@ -487,7 +792,13 @@ pub fn get_error_message(execution_result: DeployResult_ExecutionResult) -> Stri
     }
 }
 
+/// Returns the error message of a deploy the Proof of Stake contract reverted with `error`.
+pub fn pos_error_message(error: PosError) -> String {
+    format!("Proof of Stake error: {}", u32::from(error))
+}
+
 pub const STANDARD_PAYMENT_CONTRACT: &str = "standard_payment.wasm";
+pub const STANDARD_PAYMENT_FROM_PURSE_CONTRACT: &str = "standard_payment_from_purse.wasm";
 
 /// Builder for simple WASM test
 pub struct WasmTestBuilder<S> {
@@ -580,8 +891,18 @@ impl LmdbWasmTestBuilder {
         engine_config: EngineConfig,
     ) -> Self {
         let page_size = get_page_size().expect("should get page size");
+        let storage_config = StorageConfig::new().set_map_size(page_size * DEFAULT_LMDB_PAGES);
+        Self::new_with_storage_config(data_dir, engine_config, storage_config)
+    }
+
+    /// Creates new instance of builder whose LMDB environment is opened with `storage_config`.
+    pub fn new_with_storage_config<T: AsRef<OsStr> + ?Sized>(
+        data_dir: &T,
+        engine_config: EngineConfig,
+        storage_config: StorageConfig,
+    ) -> Self {
         let environment = Arc::new(
-            LmdbEnvironment::new(&data_dir.into(), page_size * DEFAULT_LMDB_PAGES)
+            LmdbEnvironment::with_config(&data_dir.into(), storage_config)
                 .expect("should create LmdbEnvironment"),
         );
         let trie_store = Arc::new(
@@ -630,6 +951,31 @@ impl LmdbWasmTestBuilder {
         builder.pos_contract_uref = result.0.pos_contract_uref;
         builder
     }
+
+    /// Opens the existing LMDB database in `data_dir` and continues from `post_state_hash`,
+    /// allowing a costly setup persisted by an earlier builder to be reused.
+    ///
+    /// The mint and Proof of Stake contract urefs are restored from the system account's named
+    /// keys when it exists under `post_state_hash`.  Any other builder using `data_dir` must have
+    /// been dropped first.
+    pub fn open<T: AsRef<OsStr> + ?Sized>(
+        data_dir: &T,
+        engine_config: EngineConfig,
+        post_state_hash: &[u8],
+    ) -> Self {
+        let mut builder = Self::new_with_config(data_dir, engine_config);
+        builder.post_state_hash = Some(post_state_hash.to_vec());
+
+        if let Some(contract_ffi::value::Value::Account(system_account)) =
+            builder.query(None, Key::Account(SYSTEM_ACCOUNT_ADDR), &[])
+        {
+            let known_keys = system_account.urefs_lookup();
+            builder.mint_contract_uref = known_keys.get(MINT_NAME).and_then(Key::as_uref).cloned();
+            builder.pos_contract_uref = known_keys.get(POS_NAME).and_then(Key::as_uref).cloned();
+        }
+
+        builder
+    }
 }
 
 impl<S> WasmTestBuilder<S>
@@ -654,6 +1000,26 @@ where
         }
     }
 
+    /// Creates a builder sharing this builder's engine state which continues from
+    /// `post_state_hash`, so scenarios can branch from a common setup without re-running it.
+    ///
+    /// Exec responses and transforms of earlier runs are not carried over, so the first run on
+    /// the returned builder is at index 0.
+    pub fn clone_at(&self, post_state_hash: &[u8]) -> Self {
+        WasmTestBuilder {
+            engine_state: Rc::clone(&self.engine_state),
+            exec_responses: Vec::new(),
+            genesis_hash: self.genesis_hash.clone(),
+            post_state_hash: Some(post_state_hash.to_vec()),
+            transforms: Vec::new(),
+            bonded_validators: self.bonded_validators.clone(),
+            genesis_account: self.genesis_account.clone(),
+            mint_contract_uref: self.mint_contract_uref,
+            pos_contract_uref: self.pos_contract_uref,
+            genesis_transforms: self.genesis_transforms.clone(),
+        }
+    }
+
     pub fn run_genesis(
         &mut self,
         genesis_addr: [u8; 32],
@@ -765,6 +1131,40 @@ where
         Ok(self)
     }
 
+    /// Runs genesis with [`DEFAULT_GENESIS_CONFIG`]
+    pub fn run_genesis_default(&mut self) -> &mut Self {
+        self.run_genesis_with_genesis_config(DEFAULT_GENESIS_CONFIG.clone())
+            .expect("should run default genesis")
+    }
+
+    /// Runs an upgrade request, expects a successful response, and overwrites the cached post
+    /// state hash with the upgraded one.
+    pub fn upgrade_with_upgrade_request(
+        &mut self,
+        mut upgrade_request: ipc::UpgradeRequest,
+    ) -> &mut Self {
+        let pre_state_hash = self
+            .post_state_hash
+            .clone()
+            .expect("Should have genesis hash");
+        upgrade_request.set_parent_state_hash(pre_state_hash);
+
+        let upgrade_response = self
+            .engine_state
+            .upgrade(RequestOptions::new(), upgrade_request)
+            .wait_drop_metadata()
+            .expect("Should have upgrade response");
+        if !upgrade_response.has_success() {
+            panic!(
+                "Expected upgrade success but received a failure instead: {:?}",
+                upgrade_response
+            );
+        }
+        let upgrade_success = upgrade_response.get_success();
+        self.post_state_hash = Some(upgrade_success.get_post_state_hash().to_vec());
+        self
+    }
+
     pub fn query(
         &self,
         maybe_post_state: Option<Vec<u8>>,
@@ -901,6 +1301,30 @@ where
         )
     }
 
+    /// Executes `session_file` as `address`, paying `amount` from `purse` instead of the
+    /// account's main purse.
+    #[allow(clippy::too_many_arguments)]
+    pub fn exec_with_payment_purse(
+        &mut self,
+        address: [u8; 32],
+        purse: contract_ffi::value::account::PurseId,
+        amount: U512,
+        session_file: &str,
+        session_args: impl contract_ffi::contract_api::argsparser::ArgsParser,
+        block_time: u64,
+        deploy_hash: [u8; 32],
+    ) -> &mut Self {
+        self.exec_with_args(
+            address,
+            STANDARD_PAYMENT_FROM_PURSE_CONTRACT,
+            (purse, amount),
+            session_file,
+            session_args,
+            block_time,
+            deploy_hash,
+        )
+    }
+
     /// Commit effects of previous exec call on the latest post-state hash.
     pub fn commit(&mut self) -> &mut Self {
         let prestate_hash = self
@@ -917,6 +1341,40 @@ where
         self.commit_effects(prestate_hash, effects)
     }
 
+    /// Runs the PoS step of a block at `block_time` on the latest post-state hash and commits its
+    /// effects, as the node does before executing the deploys of a block.
+    pub fn step(&mut self, block_time: u64) -> &mut Self {
+        let prestate_hash = self
+            .post_state_hash
+            .clone()
+            .expect("Should have genesis hash");
+
+        let mut step_request = ipc::StepRequest::new();
+        step_request.set_parent_state_hash(prestate_hash.clone());
+        step_request.set_block_time(block_time);
+        step_request.set_protocol_version(get_protocol_version());
+
+        let step_response = self
+            .engine_state
+            .step(RequestOptions::new(), step_request)
+            .wait_drop_metadata()
+            .expect("Should have step response");
+        if !step_response.has_success() {
+            panic!(
+                "Expected step success but received a failure instead: {:?}",
+                step_response
+            );
+        }
+        let commit_transforms: CommitTransforms = step_response
+            .get_success()
+            .get_effect()
+            .get_transform_map()
+            .try_into()
+            .expect("should convert");
+
+        self.commit_effects(prestate_hash, commit_transforms.value())
+    }
+
     /// Runs a commit request, expects a successful response, and
     /// overwrites existing cached post state hash with a new one.
     pub fn commit_effects(
@@ -988,6 +1446,72 @@ where
         deploy_result.get_execution_result().has_error()
     }
 
+    /// Gets the error of the last run, or `None` if it succeeded
+    pub fn get_error(&self) -> Option<ipc::DeployError> {
+        let exec_response = self
+            .exec_responses
+            .last()
+            .expect("Expected to be called after run()");
+        let deploy_result = exec_response
+            .get_success()
+            .get_deploy_results()
+            .get(0)
+            .expect("Unable to get first deploy result");
+        if !deploy_result.has_execution_result() {
+            panic!("Expected ExecutionResult, got {:?} instead", deploy_result);
+        }
+
+        let execution_result = deploy_result.get_execution_result();
+        if execution_result.has_error() {
+            Some(execution_result.get_error().to_owned())
+        } else {
+            None
+        }
+    }
+
+    /// Expects the last run to have failed
+    pub fn expect_failure(&mut self) -> &mut Self {
+        if self.get_error().is_none() {
+            panic!("Expected failed execution result, but instead the run succeeded");
+        }
+        self
+    }
+
+    /// Expects the last run to have reverted with `code`, which also matches mint and Proof of
+    /// Stake errors by their full revert value
+    pub fn expect_revert(&mut self, code: u32) -> &mut Self {
+        let error = self
+            .get_error()
+            .expect("Expected a revert, but instead the run succeeded");
+        let exec_error = error.get_exec_error();
+        if !exec_error.has_revert_value() || exec_error.get_revert_value().get_value() != code {
+            panic!(
+                "Expected revert with code {}, but instead got: {:?}",
+                code, error
+            );
+        }
+        self
+    }
+
+    /// Expects the last run to have failed with an error message containing `expected`
+    pub fn expect_error_contains(&mut self, expected: &str) -> &mut Self {
+        let error = self
+            .get_error()
+            .expect("Expected an error, but instead the run succeeded");
+        let message = if error.has_gas_error() {
+            "Gas limit"
+        } else {
+            error.get_exec_error().get_message()
+        };
+        if !message.contains(expected) {
+            panic!(
+                "Expected error containing {:?}, but instead got: {:?}",
+                expected, error
+            );
+        }
+        self
+    }
+
     /// Gets the transform map that's cached between runs
     pub fn get_transforms(&self) -> Vec<HashMap<contract_ffi::key::Key, Transform>> {
         self.transforms.clone()
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use grpc::RequestOptions;
+
+use contract_ffi::key::Key;
+use engine_core::execution::MINT_NAME;
+use engine_grpc_server::engine_server::ipc::{
+    BatchQueryRequest, BatchQueryRequest_Query, BatchQueryResponse,
+};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+
+use crate::support::test_support::InMemoryWasmTestBuilder;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const UNKNOWN_ADDR: [u8; 32] = [42u8; 32];
+
+fn create_query(base_key: &Key, path: &[&str]) -> BatchQueryRequest_Query {
+    let mut query = BatchQueryRequest_Query::new();
+    query.set_base_key(base_key.into());
+    query.set_path(path.iter().map(ToString::to_string).collect());
+    query
+}
+
+fn batch_query(
+    builder: &InMemoryWasmTestBuilder,
+    batch_query_request: BatchQueryRequest,
+) -> BatchQueryResponse {
+    builder
+        .get_engine_state()
+        .batch_query(RequestOptions::new(), batch_query_request)
+        .wait_drop_metadata()
+        .expect("should batch query")
+}
+
+#[ignore]
+#[test]
+fn should_resolve_batch_of_queries_in_request_order() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let queries = vec![
+        create_query(&Key::Account(GENESIS_ADDR), &[]),
+        create_query(&Key::Account(UNKNOWN_ADDR), &[]),
+        create_query(&Key::Account(GENESIS_ADDR), &[MINT_NAME]),
+    ];
+
+    let mut batch_query_request = BatchQueryRequest::new();
+    batch_query_request.set_state_hash(builder.get_post_state_hash());
+    batch_query_request.set_queries(queries.into());
+
+    let batch_query_response = batch_query(&builder, batch_query_request);
+
+    assert!(
+        batch_query_response.has_success(),
+        "expected success, got {:?}",
+        batch_query_response
+    );
+
+    let results = batch_query_response.get_success().get_results();
+    assert_eq!(results.len(), 3);
+    assert!(results[0].get_success().has_account());
+    assert!(results[1].has_failure());
+    assert!(results[2].has_success());
+}
+
+#[ignore]
+#[test]
+fn should_not_batch_query_missing_state() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let mut batch_query_request = BatchQueryRequest::new();
+    batch_query_request.set_state_hash(vec![0u8; 32]);
+    batch_query_request.set_queries(vec![create_query(&Key::Account(GENESIS_ADDR), &[])].into());
+
+    let batch_query_response = batch_query(&builder, batch_query_request);
+
+    assert!(
+        batch_query_response.has_missing_state(),
+        "expected missing state, got {:?}",
+        batch_query_response
+    );
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, EngineState, MAX_PAYMENT};
+use engine_core::execution;
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_storage::global_state::StateProvider;
+
+use crate::support::test_support::{
+    InMemoryWasmTestBuilder, LmdbWasmTestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME,
+    STANDARD_PAYMENT_CONTRACT,
+};
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+
+fn transfer_to_account<S>(
+    builder: &mut WasmTestBuilder<S>,
+    account_addr: [u8; 32],
+    deploy_hash: [u8; 32],
+) where
+    S: StateProvider,
+    S::Error: Into<execution::Error>,
+    EngineState<S>: ExecutionEngineService,
+{
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_purse_to_account.wasm",
+            (account_addr, U512::from(TRANSFER_AMOUNT)),
+            DEFAULT_BLOCK_TIME,
+            deploy_hash,
+        )
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_branch_from_common_post_state_hash() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    transfer_to_account(&mut builder, ACCOUNT_1_ADDR, [1; 32]);
+    let common_hash = builder.get_post_state_hash();
+
+    let mut branch_1 = builder.clone_at(&common_hash);
+    transfer_to_account(&mut branch_1, ACCOUNT_2_ADDR, [2; 32]);
+    assert!(branch_1
+        .query(None, Key::Account(ACCOUNT_2_ADDR), &[])
+        .is_some());
+
+    // The second branch starts from the common state, so it doesn't see the first branch's
+    // transfer and may even reuse its deploy hash
+    let mut branch_2 = builder.clone_at(&common_hash);
+    assert_eq!(branch_2.get_post_state_hash(), common_hash);
+    assert!(branch_2
+        .query(None, Key::Account(ACCOUNT_2_ADDR), &[])
+        .is_none());
+    transfer_to_account(&mut branch_2, ACCOUNT_2_ADDR, [2; 32]);
+    assert!(branch_2.get_exec_response(0).is_some());
+    assert!(branch_2.get_exec_response(1).is_none());
+
+    assert_eq!(builder.get_post_state_hash(), common_hash);
+}
+
+#[ignore]
+#[test]
+fn should_open_lmdb_builder_at_post_state_hash() {
+    let data_dir = tempfile::tempdir().expect("should create temp dir");
+
+    let post_state_hash = {
+        let mut builder = LmdbWasmTestBuilder::new(data_dir.path());
+        builder.run_genesis(GENESIS_ADDR, HashMap::new());
+        transfer_to_account(&mut builder, ACCOUNT_1_ADDR, [1; 32]);
+        builder.get_post_state_hash()
+    };
+
+    let mut builder =
+        LmdbWasmTestBuilder::open(data_dir.path(), EngineConfig::default(), &post_state_hash);
+    assert!(builder
+        .query(None, Key::Account(ACCOUNT_1_ADDR), &[])
+        .is_some());
+
+    transfer_to_account(&mut builder, ACCOUNT_2_ADDR, [2; 32]);
+    assert!(builder
+        .query(None, Key::Account(ACCOUNT_2_ADDR), &[])
+        .is_some());
+}
@@ -5,8 +5,7 @@ use crate::support::test_support::{
 };
 use contract_ffi::value::account::{PublicKey, Weight};
 use contract_ffi::value::U512;
-use engine_core::engine_state::{self, MAX_PAYMENT};
-use engine_core::execution;
+use engine_core::engine_state::MAX_PAYMENT;
 
 const GENESIS_ADDR: [u8; 32] = [7u8; 32];
 
@@ -34,7 +33,7 @@ fn should_deploy_with_authorized_identity_key() {
 #[test]
 fn should_raise_auth_failure_with_invalid_key() {
     // tests that authorized keys that does not belong to account raises
-    // AuthorizationError
+    // an authorization failure reporting them as unknown
     let key_1 = [254; 32];
     assert_ne!(GENESIS_ADDR, key_1);
     // Basic deploy with single key
@@ -63,19 +62,24 @@ fn should_raise_auth_failure_with_invalid_key() {
         .expect("should have at least one deploy result");
 
     assert!(deploy_result.has_precondition_failure());
-    let message = deploy_result.get_precondition_failure().get_message();
+    let authorization_failure = deploy_result
+        .get_precondition_failure()
+        .get_authorization_failure();
 
     assert_eq!(
-        message,
-        format!("{}", engine_state::error::Error::AuthorizationError)
-    )
+        authorization_failure.get_unknown_keys(),
+        &[key_1.to_vec()][..]
+    );
+    assert!(authorization_failure.get_below_threshold_keys().is_empty());
+    assert_eq!(authorization_failure.get_required_weight(), 1);
+    assert_eq!(authorization_failure.get_provided_weight(), 0);
 }
 
 #[ignore]
 #[test]
 fn should_raise_auth_failure_with_invalid_keys() {
     // tests that authorized keys that does not belong to account raises
-    // AuthorizationError
+    // an authorization failure reporting them as unknown
     let key_1 = [254; 32];
     let key_2 = [253; 32];
     let key_3 = [252; 32];
@@ -112,12 +116,18 @@ fn should_raise_auth_failure_with_invalid_keys() {
         .expect("should have at least one deploy result");
 
     assert!(deploy_result.has_precondition_failure());
-    let message = deploy_result.get_precondition_failure().get_message();
+    let authorization_failure = deploy_result
+        .get_precondition_failure()
+        .get_authorization_failure();
 
-    assert_eq!(
-        message,
-        format!("{}", engine_state::error::Error::AuthorizationError)
-    )
+    let unknown_keys = authorization_failure.get_unknown_keys();
+    assert_eq!(unknown_keys.len(), 3);
+    for key in &[key_1, key_2, key_3] {
+        assert!(unknown_keys.contains(&key.to_vec()));
+    }
+    assert!(authorization_failure.get_below_threshold_keys().is_empty());
+    assert_eq!(authorization_failure.get_required_weight(), 1);
+    assert_eq!(authorization_failure.get_provided_weight(), 0);
 }
 
 #[ignore]
@@ -216,11 +226,16 @@ fn should_raise_deploy_authorization_failure() {
             .expect("should have at least one deploy result");
 
         assert!(deploy_result.has_precondition_failure());
-        let message = deploy_result.get_precondition_failure().get_message();
-        assert!(message.contains(&format!(
-            "{}",
-            execution::Error::DeploymentAuthorizationFailure
-        )))
+        let authorization_failure = deploy_result
+            .get_precondition_failure()
+            .get_authorization_failure();
+        assert!(authorization_failure.get_unknown_keys().is_empty());
+        assert_eq!(
+            authorization_failure.get_below_threshold_keys(),
+            &[key_1.to_vec()][..]
+        );
+        assert_eq!(authorization_failure.get_required_weight(), 3);
+        assert_eq!(authorization_failure.get_provided_weight(), 2);
     }
 
     // identity key (w: 1) and key_1 (w: 2) passes threshold of 3
@@ -245,7 +260,7 @@ fn should_raise_deploy_authorization_failure() {
         .commit()
         .finish();
 
-    // deployment threshold is now 4
+    // deployment threshold is now 5
     // failure: key_2 weight + key_1 weight < deployment threshold
     let result4 = InMemoryWasmTestBuilder::from_result(result3)
         .exec_with_args_and_keys(
@@ -272,11 +287,16 @@ fn should_raise_deploy_authorization_failure() {
             .expect("should have at least one deploy result");
 
         assert!(deploy_result.has_precondition_failure());
-        let message = deploy_result.get_precondition_failure().get_message();
-        assert!(message.contains(&format!(
-            "{}",
-            execution::Error::DeploymentAuthorizationFailure
-        )))
+        let authorization_failure = deploy_result
+            .get_precondition_failure()
+            .get_authorization_failure();
+        assert!(authorization_failure.get_unknown_keys().is_empty());
+        let below_threshold_keys = authorization_failure.get_below_threshold_keys();
+        assert_eq!(below_threshold_keys.len(), 2);
+        assert!(below_threshold_keys.contains(&key_1.to_vec()));
+        assert!(below_threshold_keys.contains(&key_2.to_vec()));
+        assert_eq!(authorization_failure.get_required_weight(), 5);
+        assert_eq!(authorization_failure.get_provided_weight(), 4);
     }
 
     // success: identity key weight + key_1 weight + key_2 weight >= deployment
@@ -434,9 +454,14 @@ fn should_not_authorize_deploy_with_duplicated_keys() {
         "{:?}",
         deploy_result
     );
-    let message = deploy_result.get_precondition_failure().get_message();
-    assert!(message.contains(&format!(
-        "{}",
-        execution::Error::DeploymentAuthorizationFailure
-    )))
+    let authorization_failure = deploy_result
+        .get_precondition_failure()
+        .get_authorization_failure();
+    assert!(authorization_failure.get_unknown_keys().is_empty());
+    assert_eq!(
+        authorization_failure.get_below_threshold_keys(),
+        &[key_1.to_vec()][..]
+    );
+    assert_eq!(authorization_failure.get_required_weight(), 3);
+    assert_eq!(authorization_failure.get_provided_weight(), 2);
 }
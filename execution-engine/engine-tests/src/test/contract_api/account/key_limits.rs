@@ -0,0 +1,110 @@
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::DeployError_ErrorCode;
+use engine_shared::key_limits::KeyLimits;
+
+use crate::support::test_support::{
+    GenesisConfigBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_1_ADDR, DEFAULT_ACCOUNT_2_ADDR,
+    DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+
+// `add_update_associated_key.wasm` reverts with this value when it can't add the key
+const ADD_FAILURE: u32 = 1;
+
+// Genesis accounts hold the `mint` and `pos` named keys
+const MAX_NAMED_KEYS: u32 = 3;
+
+fn setup(key_limits: KeyLimits) -> InMemoryWasmTestBuilder {
+    let genesis_config = GenesisConfigBuilder::new()
+        .with_key_limits(key_limits)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new(EngineConfig::default());
+
+    builder
+        .run_genesis_with_genesis_config(genesis_config)
+        .expect("should run genesis");
+
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_not_add_associated_key_beyond_limit() {
+    let mut builder = setup(KeyLimits::new(1, MAX_NAMED_KEYS));
+
+    builder
+        .exec_with_args(
+            DEFAULT_ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "add_update_associated_key.wasm",
+            (PublicKey::new(DEFAULT_ACCOUNT_2_ADDR),),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .expect_revert(ADD_FAILURE)
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_add_associated_keys_beyond_previous_fixed_limit() {
+    let mut builder = setup(KeyLimits::new(20, MAX_NAMED_KEYS));
+
+    // Accounts used to be limited to ten associated keys
+    for i in 2u8..=15 {
+        builder
+            .exec_with_args(
+                DEFAULT_ACCOUNT_1_ADDR,
+                STANDARD_PAYMENT_CONTRACT,
+                (U512::from(MAX_PAYMENT),),
+                "add_update_associated_key.wasm",
+                (PublicKey::new([i; 32]),),
+                DEFAULT_BLOCK_TIME,
+                [i; 32],
+            )
+            .expect_success()
+            .commit();
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_add_named_key_beyond_limit() {
+    let mut builder = setup(KeyLimits::new(1, MAX_NAMED_KEYS));
+
+    builder
+        .exec_with_args(
+            DEFAULT_ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "create_purse_01.wasm",
+            ("purse-1",),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .expect_success()
+        .commit()
+        .exec_with_args(
+            DEFAULT_ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "create_purse_01.wasm",
+            ("purse-2",),
+            DEFAULT_BLOCK_TIME,
+            [2u8; 32],
+        )
+        .commit();
+
+    let exec_error = builder
+        .get_error()
+        .expect("should fail to add named key")
+        .get_exec_error()
+        .to_owned();
+    assert_eq!(
+        exec_error.get_code(),
+        DeployError_ErrorCode::MAX_NAMED_KEYS_LIMIT
+    );
+}
@@ -5,4 +5,10 @@ mod authorized_keys;
 #[cfg(test)]
 mod key_management_thresholds;
 #[cfg(test)]
+mod key_limits;
+#[cfg(test)]
 mod known_urefs;
+#[cfg(test)]
+mod recover_account;
+#[cfg(test)]
+mod transfer_thresholds;
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    self, InMemoryWasmTestBuilder, WasmTestResult, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::contract_api::safe::ApiError;
+use contract_ffi::key::Key;
+use contract_ffi::value::account::{Account, PublicKey, RemoveKeyFailure, Weight};
+use contract_ffi::value::U512;
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+
+const GENESIS_ADDR: [u8; 32] = [7; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1; 32];
+const BACKUP_KEY: [u8; 32] = [2; 32];
+const NEW_KEY: [u8; 32] = [3; 32];
+const UNKNOWN_KEY: [u8; 32] = [4; 32];
+const ACCOUNT_1_INITIAL_BALANCE: u64 = MAX_PAYMENT * 10;
+
+/// Funds account 1 and associates the backup key with it.
+fn setup() -> WasmTestResult<InMemoryGlobalState> {
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_purse_to_account.wasm",
+            (ACCOUNT_1_ADDR, U512::from(ACCOUNT_1_INITIAL_BALANCE)),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .expect_success()
+        .commit()
+        .exec_with_args(
+            ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "add_associated_key.wasm",
+            (PublicKey::new(BACKUP_KEY), Weight::new(1)),
+            DEFAULT_BLOCK_TIME,
+            [2; 32],
+        )
+        .expect_success()
+        .commit()
+        .finish()
+}
+
+fn account_1(builder: &InMemoryWasmTestBuilder) -> Account {
+    builder
+        .get_account(Key::Account(ACCOUNT_1_ADDR))
+        .expect("should have account 1")
+}
+
+#[ignore]
+#[test]
+fn should_add_update_and_remove_associated_key() {
+    let mut builder = InMemoryWasmTestBuilder::from_result(setup());
+    assert_eq!(
+        account_1(&builder).get_associated_key_weight(PublicKey::new(BACKUP_KEY)),
+        Some(&Weight::new(1))
+    );
+
+    builder
+        .exec_with_args(
+            ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "update_associated_key_weight.wasm",
+            (PublicKey::new(BACKUP_KEY), Weight::new(2)),
+            DEFAULT_BLOCK_TIME,
+            [3; 32],
+        )
+        .expect_success()
+        .commit();
+    assert_eq!(
+        account_1(&builder).get_associated_key_weight(PublicKey::new(BACKUP_KEY)),
+        Some(&Weight::new(2))
+    );
+
+    builder
+        .exec_with_args(
+            ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "remove_associated_key.wasm",
+            (PublicKey::new(BACKUP_KEY),),
+            DEFAULT_BLOCK_TIME,
+            [4; 32],
+        )
+        .expect_success()
+        .commit();
+    assert_eq!(
+        account_1(&builder).get_associated_key_weight(PublicKey::new(BACKUP_KEY)),
+        None
+    );
+}
+
+#[ignore]
+#[test]
+fn should_recover_account_with_backup_key() {
+    let mut builder = InMemoryWasmTestBuilder::from_result(setup());
+
+    // The primary key is lost, so the recovery is signed with the backup key only
+    builder
+        .exec_with_args_and_keys(
+            ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "recover_account.wasm",
+            (
+                PublicKey::new(ACCOUNT_1_ADDR),
+                PublicKey::new(NEW_KEY),
+                Weight::new(1),
+            ),
+            DEFAULT_BLOCK_TIME,
+            [3; 32],
+            vec![PublicKey::new(BACKUP_KEY)],
+        )
+        .expect_success()
+        .commit();
+
+    let account = account_1(&builder);
+    assert_eq!(
+        account.get_associated_key_weight(PublicKey::new(ACCOUNT_1_ADDR)),
+        None
+    );
+    assert_eq!(
+        account.get_associated_key_weight(PublicKey::new(NEW_KEY)),
+        Some(&Weight::new(1))
+    );
+    assert_eq!(
+        account.get_associated_key_weight(PublicKey::new(BACKUP_KEY)),
+        Some(&Weight::new(1))
+    );
+
+    // The new key can deploy, the lost key can't
+    let result = builder
+        .exec_with_args_and_keys(
+            ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "do_nothing.wasm",
+            (),
+            DEFAULT_BLOCK_TIME,
+            [4; 32],
+            vec![PublicKey::new(NEW_KEY)],
+        )
+        .expect_success()
+        .commit()
+        .exec_with_args_and_keys(
+            ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "do_nothing.wasm",
+            (),
+            DEFAULT_BLOCK_TIME,
+            [5; 32],
+            vec![PublicKey::new(ACCOUNT_1_ADDR)],
+        )
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(2)
+        .expect("should have a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure
+            .get_authorization_failure()
+            .get_unknown_keys(),
+        &[ACCOUNT_1_ADDR.to_vec()][..]
+    );
+}
+
+#[ignore]
+#[test]
+fn should_update_weight_of_new_key_already_associated() {
+    let mut builder = InMemoryWasmTestBuilder::from_result(setup());
+
+    builder
+        .exec_with_args_and_keys(
+            ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "recover_account.wasm",
+            (
+                PublicKey::new(ACCOUNT_1_ADDR),
+                PublicKey::new(BACKUP_KEY),
+                Weight::new(3),
+            ),
+            DEFAULT_BLOCK_TIME,
+            [3; 32],
+            vec![PublicKey::new(BACKUP_KEY)],
+        )
+        .expect_success()
+        .commit();
+
+    let account = account_1(&builder);
+    assert_eq!(
+        account.get_associated_key_weight(PublicKey::new(ACCOUNT_1_ADDR)),
+        None
+    );
+    assert_eq!(
+        account.get_associated_key_weight(PublicKey::new(BACKUP_KEY)),
+        Some(&Weight::new(3))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_recover_account_from_unknown_key() {
+    let result = InMemoryWasmTestBuilder::from_result(setup())
+        .exec_with_args_and_keys(
+            ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "recover_account.wasm",
+            (
+                PublicKey::new(UNKNOWN_KEY),
+                PublicKey::new(NEW_KEY),
+                Weight::new(1),
+            ),
+            DEFAULT_BLOCK_TIME,
+            [3; 32],
+            vec![PublicKey::new(BACKUP_KEY)],
+        )
+        .commit()
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("should have a response");
+    let error_message = test_support::get_error_message(test_support::get_success_result(response));
+    let revert_code = u32::from(ApiError::RemoveKey(RemoveKeyFailure::MissingKey));
+    assert_eq!(error_message, format!("Exit code: {}", revert_code));
+
+    // Adding the new key was reverted along with the rest of the deploy
+    let builder = InMemoryWasmTestBuilder::from_result(result);
+    assert_eq!(
+        account_1(&builder).get_associated_key_weight(PublicKey::new(NEW_KEY)),
+        None
+    );
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    self, InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::key::Key;
+use contract_ffi::value::account::{PublicKey, Weight};
+use contract_ffi::value::U512;
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_core::execution;
+
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ASSOCIATED_KEY: [u8; 32] = [123u8; 32];
+
+#[ignore]
+#[test]
+fn should_raise_transfer_threshold() {
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_thresholds.wasm",
+            (Weight::new(2),),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .expect_success()
+        .commit()
+        .finish();
+
+    let genesis_account = result
+        .builder()
+        .get_account(Key::Account(GENESIS_ADDR))
+        .expect("should get genesis account");
+    let action_thresholds = genesis_account.action_thresholds();
+    assert_eq!(*action_thresholds.transfer(), Weight::new(2));
+    assert_eq!(*action_thresholds.deployment(), Weight::new(1));
+    assert_eq!(*action_thresholds.key_management(), Weight::new(1));
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_with_keys_below_transfer_threshold() {
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_thresholds.wasm",
+            (Weight::new(2),),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .expect_success()
+        .commit()
+        // The identity key alone meets the deployment threshold, so the deploy
+        // runs, but the transfer made by the session code fails.
+        .exec_with_args_and_keys(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_to_account_01.wasm",
+            (ACCOUNT_1_ADDR,),
+            DEFAULT_BLOCK_TIME,
+            [2u8; 32],
+            vec![PublicKey::new(GENESIS_ADDR)],
+        )
+        .commit()
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(1)
+        .expect("there should be a response")
+        .clone();
+    let execution_result = test_support::get_success_result(&response);
+    let error_message = test_support::get_error_message(execution_result);
+    assert!(
+        error_message.contains(&format!(
+            "{:?}",
+            execution::Error::TransferAuthorizationFailure
+        )),
+        "{}",
+        error_message
+    );
+
+    let account_1 = result.builder().get_account(Key::Account(ACCOUNT_1_ADDR));
+    assert!(account_1.is_none(), "account 1 should not be created");
+}
+
+#[ignore]
+#[test]
+fn should_transfer_with_keys_meeting_transfer_threshold() {
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_thresholds.wasm",
+            (Weight::new(2),),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .expect_success()
+        .commit()
+        .exec_with_args_and_keys(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_to_account_01.wasm",
+            (ACCOUNT_1_ADDR,),
+            DEFAULT_BLOCK_TIME,
+            [2u8; 32],
+            vec![PublicKey::new(GENESIS_ADDR), PublicKey::new(ASSOCIATED_KEY)],
+        )
+        .expect_success()
+        .commit()
+        .finish();
+
+    result
+        .builder()
+        .get_account(Key::Account(ACCOUNT_1_ADDR))
+        .expect("should create account 1");
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    self, InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::contract_api::safe::ApiError;
+use contract_ffi::key::Key;
+use contract_ffi::value::{CLValue, Value, U512};
+use engine_core::engine_state::MAX_PAYMENT;
+
+const GENESIS_ADDR: [u8; 32] = [7; 32];
+
+#[ignore]
+#[test]
+fn should_store_and_read_cl_values() {
+    let pair = (String::from("amount"), U512::from(100));
+    let arg = CLValue::from_t(&pair).expect("should create CLValue");
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "cl_value.wasm",
+            (arg,),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .expect_success()
+        .commit();
+
+    let stored = builder
+        .query(None, Key::Account(GENESIS_ADDR), &["cl_value"])
+        .expect("should have stored value");
+    let stored_pair = match stored {
+        Value::CLValue(cl_value) => cl_value.into_t::<(String, U512)>(),
+        other => panic!("unexpected value {:?}", other),
+    };
+    assert_eq!(stored_pair, Ok(pair));
+
+    let flag = builder
+        .query(None, Key::Account(GENESIS_ADDR), &["flag"])
+        .expect("should have stored flag");
+    assert_eq!(flag, Value::from(true));
+}
+
+#[ignore]
+#[test]
+fn should_revert_on_mismatched_cl_type() {
+    let arg = CLValue::from_t(&U512::from(100)).expect("should create CLValue");
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "cl_value.wasm",
+            (arg,),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .commit()
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("should have a response");
+    let error_message = test_support::get_error_message(test_support::get_success_result(response));
+    assert_eq!(
+        error_message,
+        format!("Exit code: {}", u32::from(ApiError::InvalidArgument))
+    );
+}
@@ -71,7 +71,7 @@ fn should_insert_mint_add_keys_transform() {
                 "create_purse_01.wasm",
                 (TEST_PURSE_NAME,),
                 DEFAULT_BLOCK_TIME,
-                [1; 32],
+                [2; 32],
             )
             .expect_success()
             .commit()
@@ -107,7 +107,7 @@ fn should_insert_into_account_known_urefs() {
             "create_purse_01.wasm",
             (TEST_PURSE_NAME,),
             DEFAULT_BLOCK_TIME,
-            [1; 32],
+            [2; 32],
         )
         .expect_success()
         .commit()
@@ -145,7 +145,7 @@ fn should_create_usable_purse_id() {
             "create_purse_01.wasm",
             (TEST_PURSE_NAME,),
             DEFAULT_BLOCK_TIME,
-            [1; 32],
+            [2; 32],
         )
         .expect_success()
         .commit()
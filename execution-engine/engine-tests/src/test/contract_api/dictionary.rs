@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    self, InMemoryWasmTestBuilder, WasmTestResult, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::key::Key;
+use contract_ffi::uref::AccessRights;
+use contract_ffi::value::{Value, U512};
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_core::execution::Error;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+
+const GENESIS_ADDR: [u8; 32] = [7; 32];
+const DICTIONARY_WASM: &str = "dictionary.wasm";
+
+/// Creates a dictionary holding a greeting under the genesis account.
+fn setup() -> WasmTestResult<InMemoryGlobalState> {
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            DICTIONARY_WASM,
+            (String::from("create"),),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .expect_success()
+        .commit()
+        .finish()
+}
+
+#[ignore]
+#[test]
+fn should_query_dictionary_item_by_name() {
+    let builder = InMemoryWasmTestBuilder::from_result(setup());
+
+    let item = builder
+        .query(
+            None,
+            Key::Account(GENESIS_ADDR),
+            &["dictionary", "greeting"],
+        )
+        .expect("should have dictionary item");
+    assert_eq!(item, Value::String(String::from("hello")));
+
+    assert!(builder
+        .query(None, Key::Account(GENESIS_ADDR), &["dictionary", "missing"])
+        .is_none());
+}
+
+#[ignore]
+#[test]
+fn should_query_dictionary_item_by_seed() {
+    let builder = InMemoryWasmTestBuilder::from_result(setup());
+
+    let account = builder
+        .get_account(Key::Account(GENESIS_ADDR))
+        .expect("should have account");
+    let seed = *account
+        .urefs_lookup()
+        .get("dictionary")
+        .expect("should have dictionary");
+
+    let item = builder
+        .query(None, seed, &["greeting"])
+        .expect("should have dictionary item");
+    assert_eq!(item, Value::String(String::from("hello")));
+}
+
+#[ignore]
+#[test]
+fn should_not_put_through_read_only_seed() {
+    let result = InMemoryWasmTestBuilder::from_result(setup())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            DICTIONARY_WASM,
+            (String::from("put_read_only"),),
+            DEFAULT_BLOCK_TIME,
+            [2; 32],
+        )
+        .commit()
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("should have a response");
+    let error_message = test_support::get_error_message(test_support::get_success_result(response));
+    let expected_error = Error::InvalidAccess {
+        required: AccessRights::WRITE,
+    };
+    assert_eq!(error_message, format!("{:?}", expected_error));
+
+    let builder = InMemoryWasmTestBuilder::from_result(result);
+    let item = builder
+        .query(
+            None,
+            Key::Account(GENESIS_ADDR),
+            &["dictionary", "greeting"],
+        )
+        .expect("should have dictionary item");
+    assert_eq!(item, Value::String(String::from("hello")));
+}
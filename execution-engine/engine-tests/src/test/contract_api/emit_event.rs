@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+use crate::support::test_support::{
+    self, InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::key::Key;
+use contract_ffi::value::U512;
+use engine_core::engine_state::MAX_PAYMENT;
+
+const CONTRACT_EMIT_EVENT: &str = "emit_event.wasm";
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+const TOPIC: &str = "greeting";
+const DONE_TOPIC: &str = "done";
+
+#[ignore]
+#[test]
+fn should_report_emitted_events_in_order() {
+    let payload: Vec<u8> = b"hello".to_vec();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            CONTRACT_EMIT_EVENT,
+            (TOPIC.to_string(), payload.clone(), 0u32),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    let mut execution_result = test_support::get_success_result(response);
+    let events = execution_result.take_effects().take_events().into_vec();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].get_topic(), TOPIC);
+    assert_eq!(events[0].get_payload(), payload.as_slice());
+    assert_eq!(events[1].get_topic(), DONE_TOPIC);
+    assert!(events[1].get_payload().is_empty());
+
+    for event in events.iter() {
+        let emitter: Key = event
+            .get_emitter()
+            .try_into()
+            .expect("should parse emitter");
+        assert_eq!(emitter, Key::Account(GENESIS_ADDR));
+    }
+}
+
+#[ignore]
+#[test]
+fn should_discard_events_emitted_by_failed_session() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            CONTRACT_EMIT_EVENT,
+            (TOPIC.to_string(), b"hello".to_vec(), 100u32),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .commit();
+
+    assert!(builder.is_error());
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    let execution_result = test_support::get_success_result(response);
+    assert!(execution_result.get_effects().get_events().is_empty());
+}
@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
+
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+
+#[ignore]
+#[test]
+fn should_run_get_block_height_contract() {
+    let block_height: u64 = 42;
+
+    let engine_config = EngineConfig::new().set_use_payment_code(true);
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_session_code("get_block_height.wasm", (block_height,))
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(MAX_PAYMENT),))
+            .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+            .build();
+
+        ExecRequestBuilder::new()
+            .push_deploy(deploy)
+            .with_block_height(block_height)
+            .build()
+    };
+
+    InMemoryWasmTestBuilder::new(engine_config)
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .commit()
+        .expect_success();
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::key::Key;
+use contract_ffi::value::{Value, U512};
+use engine_core::engine_state::MAX_PAYMENT;
+
+const GENESIS_ADDR: [u8; 32] = [7; 32];
+
+/// Runs the entropy contract in a deploy with the given hash and block time, and returns the
+/// seed it stored.
+fn get_entropy(deploy_hash: [u8; 32], blocktime: u64) -> Value {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "get_entropy.wasm",
+            (),
+            blocktime,
+            deploy_hash,
+        )
+        .expect_success()
+        .commit();
+
+    builder
+        .query(None, Key::Account(GENESIS_ADDR), &["entropy"])
+        .expect("should have stored entropy")
+}
+
+#[ignore]
+#[test]
+fn should_get_same_entropy_for_same_deploy() {
+    assert_eq!(
+        get_entropy([1; 32], DEFAULT_BLOCK_TIME),
+        get_entropy([1; 32], DEFAULT_BLOCK_TIME)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_get_different_entropy_for_different_deploy_or_block_time() {
+    let entropy = get_entropy([1; 32], DEFAULT_BLOCK_TIME);
+
+    assert_ne!(entropy, get_entropy([2; 32], DEFAULT_BLOCK_TIME));
+    assert_ne!(entropy, get_entropy([1; 32], DEFAULT_BLOCK_TIME + 1));
+}
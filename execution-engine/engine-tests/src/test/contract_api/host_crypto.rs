@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::base16;
+use contract_ffi::key::Key;
+use contract_ffi::value::{Value, U512};
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_shared::newtypes::Blake2bHash;
+
+const GENESIS_ADDR: [u8; 32] = [7; 32];
+const HOST_CRYPTO_WASM: &str = "host_crypto.wasm";
+
+// Test 1 of RFC 8032, section 7.1: the signature of an empty message.
+const ED25519_PUBLIC_KEY: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+const ED25519_SIGNATURE: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+
+/// Runs the host crypto contract with the given data and signature and returns the values it
+/// stored under the genesis account.
+fn run_host_crypto(data: Vec<u8>, signature: Vec<u8>) -> HashMap<&'static str, Value> {
+    let public_key = base16::decode_lower(ED25519_PUBLIC_KEY).unwrap();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            HOST_CRYPTO_WASM,
+            (data, public_key, signature),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .expect_success()
+        .commit();
+
+    ["blake2b256", "sha256", "ed25519_verify"]
+        .iter()
+        .map(|name| {
+            let value = builder
+                .query(None, Key::Account(GENESIS_ADDR), &[name])
+                .expect("should have stored value");
+            (*name, value)
+        })
+        .collect()
+}
+
+#[ignore]
+#[test]
+fn should_compute_digests_and_verify_signature() {
+    let signature = base16::decode_lower(ED25519_SIGNATURE).unwrap();
+    let values = run_host_crypto(Vec::new(), signature);
+
+    assert_eq!(
+        values["blake2b256"],
+        Value::ByteArray(Blake2bHash::new(&[]).to_vec())
+    );
+    assert_eq!(
+        values["sha256"],
+        Value::ByteArray(
+            base16::decode_lower(
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+            )
+            .unwrap()
+        )
+    );
+    assert_eq!(values["ed25519_verify"], Value::Int32(1));
+}
+
+#[ignore]
+#[test]
+fn should_reject_signature_of_other_data() {
+    let signature = base16::decode_lower(ED25519_SIGNATURE).unwrap();
+    let values = run_host_crypto(b"abc".to_vec(), signature);
+
+    assert_eq!(
+        values["sha256"],
+        Value::ByteArray(
+            base16::decode_lower(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            )
+            .unwrap()
+        )
+    );
+    assert_eq!(values["ed25519_verify"], Value::Int32(0));
+}
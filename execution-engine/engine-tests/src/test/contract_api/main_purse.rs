@@ -77,7 +77,7 @@ fn should_run_main_purse_contract_account_1() {
             "main_purse.wasm",
             (account_1.purse_id(),),
             DEFAULT_BLOCK_TIME,
-            [1u8; 32],
+            [2u8; 32],
         )
         .expect_success()
         .commit();
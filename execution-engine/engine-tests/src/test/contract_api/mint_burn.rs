@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    WasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::value::U512;
+use engine_core::engine_state::MAX_PAYMENT;
+
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+
+#[ignore]
+#[test]
+fn should_burn_motes_from_purse() {
+    let purse_amount = U512::from(1000);
+    let burn_amount = U512::from(300);
+
+    WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "mint_burn.wasm",
+            (purse_amount, burn_amount),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .commit()
+        .expect_success();
+}
@@ -1,18 +1,20 @@
 use std::collections::HashMap;
 
 use crate::support::test_support::{
-    WasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+    self, InMemoryWasmTestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
 };
 use contract_ffi::value::U512;
-use engine_core::engine_state::MAX_PAYMENT;
+use engine_core::engine_state::error::Error;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
 
 const GENESIS_ADDR: [u8; 32] = [7u8; 32];
 const SYSTEM_ADDR: [u8; 32] = [0u8; 32];
 
-#[ignore]
-#[test]
-fn should_run_mint_purse_contract() {
-    WasmTestBuilder::default()
+/// Funds the system account, so that it can pay for its deploys.
+fn initialize(engine_config: EngineConfig) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config);
+
+    builder
         .run_genesis(GENESIS_ADDR, HashMap::new())
         .exec_with_args(
             GENESIS_ADDR,
@@ -24,7 +26,16 @@ fn should_run_mint_purse_contract() {
             [1u8; 32],
         )
         .commit()
-        .expect_success()
+        .expect_success();
+
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_run_mint_purse_contract_when_system_account_unrestricted() {
+    // Minting is reserved to the system account, which deploys can only execute as when allowed
+    initialize(EngineConfig::new().set_restrict_system_account(false))
         .exec(
             SYSTEM_ADDR,
             "mint_purse.wasm",
@@ -35,6 +46,29 @@ fn should_run_mint_purse_contract() {
         .expect_success();
 }
 
+#[ignore]
+#[test]
+fn should_not_run_mint_purse_contract_when_system_account_restricted() {
+    let mut builder = initialize(EngineConfig::new());
+    builder.exec(
+        SYSTEM_ADDR,
+        "mint_purse.wasm",
+        DEFAULT_BLOCK_TIME,
+        [2u8; 32],
+    );
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response")
+        .clone();
+    let precondition_failure = test_support::get_precondition_failure(&response);
+
+    assert_eq!(
+        precondition_failure.message,
+        Error::SystemAccountDeploy.to_string()
+    );
+}
+
 #[ignore]
 #[test]
 fn should_not_allow_non_system_accounts_to_mint() {
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    InMemoryWasmTestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME, GENESIS_INITIAL_BALANCE,
+    STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
+
+const GENESIS_ADDR: [u8; 32] = [7u8; 32];
+const SYSTEM_ADDR: [u8; 32] = [0u8; 32];
+const MINTED_AMOUNT: u64 = 12345;
+
+#[ignore]
+#[test]
+fn should_read_total_supply_after_genesis() {
+    WasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "mint_total_supply.wasm",
+            (U512::from(GENESIS_INITIAL_BALANCE),),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_increase_total_supply_when_unrestricted_system_account_mints() {
+    // `mint_purse.wasm` mints a purse holding `MINTED_AMOUNT` motes
+    let expected_total_supply = U512::from(GENESIS_INITIAL_BALANCE) + U512::from(MINTED_AMOUNT);
+
+    // Minting is reserved to the system account, which deploys can only execute as when allowed
+    InMemoryWasmTestBuilder::new(EngineConfig::new().set_restrict_system_account(false))
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_to_account_01.wasm",
+            (SYSTEM_ADDR,),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .commit()
+        .expect_success()
+        .exec(
+            SYSTEM_ADDR,
+            "mint_purse.wasm",
+            DEFAULT_BLOCK_TIME,
+            [2u8; 32],
+        )
+        .commit()
+        .expect_success()
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "mint_total_supply.wasm",
+            (expected_total_supply,),
+            DEFAULT_BLOCK_TIME,
+            [3u8; 32],
+        )
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_not_increase_total_supply_when_restricted_system_account_mints() {
+    InMemoryWasmTestBuilder::new(EngineConfig::new())
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_to_account_01.wasm",
+            (SYSTEM_ADDR,),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .commit()
+        .expect_success()
+        // Rejected before executing, so nothing is minted
+        .exec(
+            SYSTEM_ADDR,
+            "mint_purse.wasm",
+            DEFAULT_BLOCK_TIME,
+            [2u8; 32],
+        )
+        .commit()
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "mint_total_supply.wasm",
+            (U512::from(GENESIS_INITIAL_BALANCE),),
+            DEFAULT_BLOCK_TIME,
+            [3u8; 32],
+        )
+        .commit()
+        .expect_success();
+}
@@ -1,18 +1,36 @@
 #[cfg(test)]
+mod cl_value;
+#[cfg(test)]
 mod create_purse;
 #[cfg(test)]
+mod dictionary;
+#[cfg(test)]
+mod emit_event;
+#[cfg(test)]
+mod get_block_height;
+#[cfg(test)]
 mod get_blocktime;
 #[cfg(test)]
 mod get_caller;
 #[cfg(test)]
+mod get_entropy;
+#[cfg(test)]
 mod get_phase;
 #[cfg(test)]
+mod host_crypto;
+#[cfg(test)]
 mod local_state;
 #[cfg(test)]
 mod main_purse;
 #[cfg(test)]
+mod mint_burn;
+#[cfg(test)]
 mod mint_purse;
 #[cfg(test)]
+mod mint_total_supply;
+#[cfg(test)]
+mod named_args;
+#[cfg(test)]
 mod revert;
 #[cfg(test)]
 mod transfer;
@@ -20,6 +38,10 @@ mod transfer;
 mod transfer_purse_to_account;
 #[cfg(test)]
 mod transfer_purse_to_purse;
+#[cfg(test)]
+mod transfer_purse_to_purse_access;
+#[cfg(test)]
+mod versioned_contract;
 
 #[cfg(test)]
 pub mod account;
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    self, InMemoryWasmTestBuilder, WasmTestResult, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::contract_api::safe::ApiError;
+use contract_ffi::value::U512;
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+
+const GENESIS_ADDR: [u8; 32] = [7; 32];
+
+/// Calls a contract adding its named args `a` and `b`, with `a` set to 2.
+fn exec_named_args(b: Option<u64>) -> WasmTestResult<InMemoryGlobalState> {
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "named_args.wasm",
+            (2u64, b),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .commit()
+        .finish()
+}
+
+#[ignore]
+#[test]
+fn should_call_contract_with_named_args() {
+    let result = exec_named_args(Some(3));
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("should have a response");
+    let execution_result = test_support::get_success_result(response);
+    assert!(!execution_result.has_error(), "{:?}", execution_result);
+}
+
+#[ignore]
+#[test]
+fn should_revert_on_missing_named_arg() {
+    let result = exec_named_args(None);
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("should have a response");
+    let error_message = test_support::get_error_message(test_support::get_success_result(response));
+    assert_eq!(
+        error_message,
+        format!("Exit code: {}", u32::from(ApiError::MissingArgument))
+    );
+}
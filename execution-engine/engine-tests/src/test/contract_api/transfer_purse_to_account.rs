@@ -1,13 +1,14 @@
 use std::collections::HashMap;
 
 use contract_ffi::key::Key;
-use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::account::{PublicKey, Weight, SECP256K1_PUBLIC_KEY_LENGTH};
 use contract_ffi::value::{Value, U512};
 use engine_core::engine_state::MAX_PAYMENT;
 use engine_shared::transform::Transform;
 
 use crate::support::test_support::{
-    InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME, GENESIS_INITIAL_BALANCE, STANDARD_PAYMENT_CONTRACT,
+    DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME,
+    GENESIS_INITIAL_BALANCE, STANDARD_PAYMENT_CONTRACT,
 };
 
 const GENESIS_ADDR: [u8; 32] = [12; 32];
@@ -34,7 +35,7 @@ fn should_run_purse_to_account_transfer() {
         .expect_success()
         .commit()
         .exec_with_args(
-            account_1_public_key.value(),
+            account_1_public_key.to_account_address(),
             STANDARD_PAYMENT_CONTRACT,
             (U512::from(MAX_PAYMENT),),
             "transfer_purse_to_account.wasm",
@@ -255,7 +256,52 @@ fn should_fail_when_sending_too_much_from_purse_to_account() {
 
     // Main assertion for the result of `transfer_from_purse_to_purse`
     assert_eq!(
-        transfer_result_string, "TransferError",
+        transfer_result_string, "TransferError(InsufficientFunds)",
         "TransferError incorrect"
     );
 }
+
+#[ignore]
+#[test]
+fn should_transfer_to_secp256k1_account_and_deploy_from_it() {
+    let secp256k1_public_key = PublicKey::secp256k1([42u8; SECP256K1_PUBLIC_KEY_LENGTH]);
+    let secp256k1_account_addr = secp256k1_public_key.to_account_address();
+
+    let deploy = DeployBuilder::new()
+        .with_address(secp256k1_account_addr)
+        .with_deploy_hash([2u8; 32])
+        .with_session_code("do_nothing.wasm", ())
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[secp256k1_public_key])
+        .build();
+    let exec_request = ExecRequestBuilder::new().push_deploy(deploy).build();
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_purse_to_account.wasm",
+            (secp256k1_public_key, U512::from(ACCOUNT_1_INITIAL_FUND)),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .expect_success()
+        .commit()
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit()
+        .finish();
+
+    // The account is stored under the address derived from the key, not the
+    // key itself, and is controlled by the key.
+    let account = result
+        .builder()
+        .get_account(Key::Account(secp256k1_account_addr))
+        .expect("should get secp256k1 account");
+    assert_eq!(
+        account.get_associated_key_weight(secp256k1_public_key),
+        Some(&Weight::new(1))
+    );
+}
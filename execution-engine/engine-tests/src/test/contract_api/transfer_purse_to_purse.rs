@@ -153,7 +153,7 @@ fn should_run_purse_to_purse_transfer_with_error() {
         panic!("Purse transfer result is expected to contain Write with String value");
     };
     // Main assertion for the result of `transfer_from_purse_to_purse`
-    assert_eq!(purse_transfer_result, "TransferError");
+    assert_eq!(purse_transfer_result, "TransferError(InsufficientFunds)");
 
     // Obtain main purse's balance
     let main_purse_balance =
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    self, InMemoryWasmTestBuilder, WasmTestResult, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::contract_api::safe::ApiError;
+use contract_ffi::key::Key;
+use contract_ffi::uref::AccessRights;
+use contract_ffi::value::{Value, U512};
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_core::execution::Error;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+
+const GENESIS_ADDR: [u8; 32] = [7; 32];
+const PACKAGE_NAME: &str = "versioned_contract";
+const READ_ONLY_PACKAGE_NAME: &str = "versioned_contract_read_only";
+
+/// Stores a contract package with a single version.
+fn install() -> WasmTestResult<InMemoryGlobalState> {
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "versioned_contract_install.wasm",
+            (),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .expect_success()
+        .commit()
+        .finish()
+}
+
+fn upgrade(builder: &mut InMemoryWasmTestBuilder, package_name: &str, deploy_hash: [u8; 32]) {
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "versioned_contract_upgrade.wasm",
+            (package_name.to_string(),),
+            DEFAULT_BLOCK_TIME,
+            deploy_hash,
+        )
+        .commit();
+}
+
+fn set_version_enabled(
+    builder: &mut InMemoryWasmTestBuilder,
+    action: &str,
+    package_name: &str,
+    version: u32,
+    deploy_hash: [u8; 32],
+) {
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "versioned_contract_disable.wasm",
+            (action.to_string(), package_name.to_string(), version),
+            DEFAULT_BLOCK_TIME,
+            deploy_hash,
+        )
+        .commit();
+}
+
+fn call_version(
+    builder: &mut InMemoryWasmTestBuilder,
+    version: u32,
+    expected: u32,
+    deploy_hash: [u8; 32],
+) {
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "versioned_contract_call.wasm",
+            (version, expected),
+            DEFAULT_BLOCK_TIME,
+            deploy_hash,
+        )
+        .commit();
+}
+
+/// Returns the error message of the deploy executed at `index` after `install`.
+fn get_error_message(builder: &InMemoryWasmTestBuilder, index: usize) -> String {
+    let response = builder
+        .get_exec_response(index)
+        .expect("should have a response");
+    test_support::get_error_message(test_support::get_success_result(response))
+}
+
+#[ignore]
+#[test]
+fn should_call_each_version_after_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::from_result(install());
+    call_version(&mut builder, 1, 1, [2; 32]);
+    builder.expect_success();
+
+    upgrade(&mut builder, PACKAGE_NAME, [3; 32]);
+    builder.expect_success();
+
+    call_version(&mut builder, 1, 1, [4; 32]);
+    builder.expect_success();
+    call_version(&mut builder, 2, 2, [5; 32]);
+    builder.expect_success();
+
+    let package = match builder.query(None, Key::Account(GENESIS_ADDR), &[PACKAGE_NAME]) {
+        Some(Value::ContractPackage(package)) => package,
+        other => panic!("expected a contract package, got {:?}", other),
+    };
+    assert_eq!(package.latest_version(), Some(2));
+    for version in 1..=2 {
+        match package.get_version(version) {
+            Some(Key::Hash(_)) => (),
+            other => panic!("expected a contract hash, got {:?}", other),
+        }
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_call_missing_version() {
+    let mut builder = InMemoryWasmTestBuilder::from_result(install());
+    call_version(&mut builder, 2, 2, [2; 32]);
+
+    assert!(builder.is_error());
+    assert_eq!(
+        get_error_message(&builder, 0),
+        format!(
+            "Exit code: {}",
+            u32::from(ApiError::ContractVersionNotFound)
+        )
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_upgrade_with_read_only_reference() {
+    let mut builder = InMemoryWasmTestBuilder::from_result(install());
+    upgrade(&mut builder, READ_ONLY_PACKAGE_NAME, [2; 32]);
+
+    assert!(builder.is_error());
+    let expected_error = Error::InvalidAccess {
+        required: AccessRights::WRITE,
+    };
+    assert_eq!(
+        get_error_message(&builder, 0),
+        format!("{:?}", expected_error)
+    );
+
+    // The upgrade was reverted, so the package still has a single version
+    call_version(&mut builder, 2, 2, [3; 32]);
+    assert!(builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_fail_calls_to_disabled_version_until_enabled() {
+    let mut builder = InMemoryWasmTestBuilder::from_result(install());
+    set_version_enabled(&mut builder, "disable", PACKAGE_NAME, 1, [2; 32]);
+    builder.expect_success();
+
+    call_version(&mut builder, 1, 1, [3; 32]);
+    assert!(builder.is_error());
+    assert_eq!(
+        get_error_message(&builder, 1),
+        format!(
+            "Exit code: {}",
+            u32::from(ApiError::ContractVersionDisabled)
+        )
+    );
+
+    set_version_enabled(&mut builder, "enable", PACKAGE_NAME, 1, [4; 32]);
+    builder.expect_success();
+    call_version(&mut builder, 1, 1, [5; 32]);
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_not_disable_version_with_read_only_reference() {
+    let mut builder = InMemoryWasmTestBuilder::from_result(install());
+    set_version_enabled(&mut builder, "disable", READ_ONLY_PACKAGE_NAME, 1, [2; 32]);
+
+    assert!(builder.is_error());
+    let expected_error = Error::InvalidAccess {
+        required: AccessRights::WRITE,
+    };
+    assert_eq!(
+        get_error_message(&builder, 0),
+        format!("{:?}", expected_error)
+    );
+
+    call_version(&mut builder, 1, 1, [3; 32]);
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_not_disable_missing_version() {
+    let mut builder = InMemoryWasmTestBuilder::from_result(install());
+    set_version_enabled(&mut builder, "disable", PACKAGE_NAME, 2, [2; 32]);
+
+    assert!(builder.is_error());
+    assert_eq!(
+        get_error_message(&builder, 0),
+        format!(
+            "Exit code: {}",
+            u32::from(ApiError::ContractVersionNotFound)
+        )
+    );
+}
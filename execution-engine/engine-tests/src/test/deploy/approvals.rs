@@ -0,0 +1,101 @@
+use std::collections::{BTreeSet, HashMap};
+
+use ed25519_dalek::{Keypair, PublicKey as Ed25519PublicKey, SecretKey};
+
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder,
+};
+use contract_ffi::key::Key;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::approval::Approval;
+use engine_core::engine_state::error::{AuthorizationFailure, Error};
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::Deploy;
+
+const STANDARD_PAYMENT_WASM: &str = "standard_payment.wasm";
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const DEPLOY_HASH: [u8; 32] = [1; 32];
+
+fn genesis_keypair() -> Keypair {
+    let secret = SecretKey::from_bytes(&[1; 32]).expect("should create secret key");
+    let public = Ed25519PublicKey::from(&secret);
+    Keypair { secret, public }
+}
+
+fn genesis_addr() -> [u8; 32] {
+    genesis_keypair().public.to_bytes()
+}
+
+fn approval(message: &[u8]) -> Approval {
+    let signature = genesis_keypair().sign(message).to_bytes().to_vec();
+    Approval::new(PublicKey::new(genesis_addr()), signature)
+}
+
+fn approved_deploy(approvals: &[Approval]) -> Deploy {
+    DeployBuilder::new()
+        .with_address(genesis_addr())
+        .with_deploy_hash(DEPLOY_HASH)
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(genesis_addr())])
+        .with_approvals(approvals)
+        .build()
+}
+
+fn exec_deploy(builder: &mut InMemoryWasmTestBuilder, deploy: Deploy) {
+    let exec_request = ExecRequestBuilder::new().push_deploy(deploy).build();
+    builder
+        .run_genesis(genesis_addr(), HashMap::new())
+        .exec_with_exec_request(exec_request);
+}
+
+fn verifying_builder() -> InMemoryWasmTestBuilder {
+    InMemoryWasmTestBuilder::new(EngineConfig::new().set_verify_approvals(true))
+}
+
+fn assert_unapproved(builder: &InMemoryWasmTestBuilder) {
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+
+    let account = builder
+        .get_account(Key::Account(genesis_addr()))
+        .expect("should get genesis account");
+    let expected_error =
+        Error::AuthorizationFailure(AuthorizationFailure::new(&account, &BTreeSet::new()));
+    assert_eq!(precondition_failure.message, expected_error.to_string());
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_approved_by_authorization_key() {
+    let mut builder = verifying_builder();
+    exec_deploy(&mut builder, approved_deploy(&[approval(&DEPLOY_HASH)]));
+    builder.expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_deploy_without_approvals() {
+    let mut builder = verifying_builder();
+    exec_deploy(&mut builder, approved_deploy(&[]));
+    assert_unapproved(&builder);
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_approval_of_other_deploy_hash() {
+    let mut builder = verifying_builder();
+    exec_deploy(&mut builder, approved_deploy(&[approval(&[2; 32])]));
+    assert_unapproved(&builder);
+}
+
+#[ignore]
+#[test]
+fn should_not_verify_approvals_by_default() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    exec_deploy(&mut builder, approved_deploy(&[]));
+    builder.expect_success().commit();
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder,
+};
+use contract_ffi::value::account::{BlockTime, PublicKey};
+use contract_ffi::value::U512;
+use engine_core::engine_state::error::Error;
+use engine_core::engine_state::{executed_deploy_key, EXECUTED_DEPLOY_WINDOW_MILLIS, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::Deploy;
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const STANDARD_PAYMENT_WASM: &str = "standard_payment.wasm";
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const DEPLOY_1_HASH: [u8; 32] = [1; 32];
+const DEPLOY_2_HASH: [u8; 32] = [2; 32];
+
+fn do_nothing_deploy(deploy_hash: [u8; 32], dependencies: &[[u8; 32]]) -> Deploy {
+    timed_do_nothing_deploy(deploy_hash, dependencies, 0)
+}
+
+fn timed_do_nothing_deploy(
+    deploy_hash: [u8; 32],
+    dependencies: &[[u8; 32]],
+    timestamp: u64,
+) -> Deploy {
+    DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash(deploy_hash)
+        .with_dependencies(dependencies)
+        .with_timestamp(timestamp)
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_whose_dependency_was_executed_in_earlier_block() {
+    let exec_request_1 = ExecRequestBuilder::new()
+        .push_deploy(do_nothing_deploy(DEPLOY_1_HASH, &[]))
+        .build();
+    let exec_request_2 = ExecRequestBuilder::new()
+        .push_deploy(do_nothing_deploy(DEPLOY_2_HASH, &[DEPLOY_1_HASH]))
+        .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec_with_exec_request(exec_request_2)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_whose_dependency_was_executed_two_windows_earlier() {
+    // By then the record rejecting replays of the dependency has been purged, but not the record
+    // of its execution.
+    let later_block_time = 2 * EXECUTED_DEPLOY_WINDOW_MILLIS;
+    let exec_request_1 = ExecRequestBuilder::new()
+        .push_deploy(do_nothing_deploy(DEPLOY_1_HASH, &[]))
+        .build();
+    let exec_request_2 = ExecRequestBuilder::new()
+        .push_deploy(timed_do_nothing_deploy(
+            DEPLOY_2_HASH,
+            &[DEPLOY_1_HASH],
+            later_block_time,
+        ))
+        .with_block_time(later_block_time)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec_with_exec_request(exec_request_2)
+        .expect_success()
+        .commit();
+
+    assert!(builder
+        .query(None, executed_deploy_key(BlockTime(0), DEPLOY_1_HASH), &[])
+        .is_none());
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_dependency_not_executed() {
+    let exec_request = ExecRequestBuilder::new()
+        .push_deploy(do_nothing_deploy(DEPLOY_2_HASH, &[DEPLOY_1_HASH]))
+        .build();
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    let precondition_failure = test_support::get_precondition_failure(response);
+
+    assert_eq!(
+        precondition_failure.message,
+        Error::MissingDeployDependency(DEPLOY_1_HASH).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_whose_dependency_precedes_it_in_same_request() {
+    let exec_request = ExecRequestBuilder::new()
+        .push_deploy(do_nothing_deploy(DEPLOY_1_HASH, &[]))
+        .push_deploy(do_nothing_deploy(DEPLOY_2_HASH, &[DEPLOY_1_HASH]))
+        .build();
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .finish();
+
+    let deploy_results = result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .get_success()
+        .get_deploy_results();
+
+    assert_eq!(deploy_results.len(), 2);
+    for deploy_result in deploy_results {
+        assert!(deploy_result.has_execution_result());
+        assert!(!deploy_result.get_execution_result().has_error());
+    }
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_dependency_following_it_in_same_request() {
+    let exec_request = ExecRequestBuilder::new()
+        .push_deploy(do_nothing_deploy(DEPLOY_2_HASH, &[DEPLOY_1_HASH]))
+        .push_deploy(do_nothing_deploy(DEPLOY_1_HASH, &[]))
+        .build();
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .finish();
+
+    let deploy_results = result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .get_success()
+        .get_deploy_results();
+
+    assert_eq!(deploy_results.len(), 2);
+    assert_eq!(
+        deploy_results[0].get_precondition_failure().message,
+        Error::MissingDeployDependency(DEPLOY_1_HASH).to_string()
+    );
+    assert!(deploy_results[1].has_execution_result());
+}
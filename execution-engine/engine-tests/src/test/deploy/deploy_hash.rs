@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use crate::support::test_stored_contract_support::{self, WasmTestBuilder};
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder,
+};
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::error::Error;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::{Deploy, DeployItem};
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const STANDARD_PAYMENT_WASM: &str = "standard_payment.wasm";
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const DEPLOY_HEADER: &[u8] = b"header";
+const DEPLOY_BODY: &[u8] = b"body";
+const BAD_DEPLOY_HASH: [u8; 32] = [1u8; 32];
+
+fn deploy_builder() -> DeployBuilder {
+    DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_header_and_body(DEPLOY_HEADER, DEPLOY_BODY)
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+}
+
+fn verifying_builder() -> InMemoryWasmTestBuilder {
+    InMemoryWasmTestBuilder::new(EngineConfig::new().set_verify_deploy_hashes(true))
+}
+
+fn exec_deploy(builder: &mut InMemoryWasmTestBuilder, deploy: Deploy) {
+    let exec_request = ExecRequestBuilder::new().push_deploy(deploy).build();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request);
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_with_hash_of_header_and_body() {
+    let mut builder = verifying_builder();
+    exec_deploy(&mut builder, deploy_builder().build());
+    builder.expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_bad_deploy_hash() {
+    let mut builder = verifying_builder();
+    let deploy = deploy_builder().with_bad_deploy_hash().build();
+    let deploy_hash = {
+        let mut tmp = [0u8; 32];
+        tmp.copy_from_slice(deploy.get_deploy_hash());
+        tmp
+    };
+    exec_deploy(&mut builder, deploy);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    let precondition_failure = test_support::get_precondition_failure(response);
+
+    assert_eq!(
+        precondition_failure.message,
+        Error::InvalidDeployHash(deploy_hash).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_verify_deploy_hash_by_default() {
+    let deploy = deploy_builder().with_bad_deploy_hash().build();
+    let exec_request = ExecRequestBuilder::new().push_deploy(deploy).build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+}
+
+fn deploy_item_builder() -> test_stored_contract_support::DeployBuilder {
+    test_stored_contract_support::DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_header_and_body(DEPLOY_HEADER, DEPLOY_BODY)
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+}
+
+fn execute_deploy_item(builder: &mut WasmTestBuilder, deploy: DeployItem) {
+    let execute_request = test_stored_contract_support::ExecRequestBuilder::new()
+        .push_deploy(deploy)
+        .build();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(execute_request);
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_item_with_hash_of_header_and_body() {
+    let mut builder = WasmTestBuilder::new(EngineConfig::new().set_verify_deploy_hashes(true));
+    execute_deploy_item(&mut builder, deploy_item_builder().build());
+    builder.expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_bad_deploy_item_hash() {
+    let mut builder = WasmTestBuilder::new(EngineConfig::new().set_verify_deploy_hashes(true));
+    let deploy = deploy_item_builder()
+        .with_deploy_hash(BAD_DEPLOY_HASH)
+        .build();
+    execute_deploy_item(&mut builder, deploy);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    let precondition_failure = test_stored_contract_support::get_precondition_failure(response);
+
+    assert_eq!(
+        precondition_failure.message,
+        Error::InvalidDeployHash(BAD_DEPLOY_HASH).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploys_with_derived_hashes() {
+    let derived_deploy = |timestamp: u64| {
+        DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_session_code(DO_NOTHING_WASM, ())
+            .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+            .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+            .with_timestamp(timestamp)
+            .with_derived_deploy_hash()
+            .build()
+    };
+
+    let deploy_1 = derived_deploy(1);
+    let deploy_2 = derived_deploy(2);
+    assert_eq!(
+        deploy_1.get_deploy_hash(),
+        derived_deploy(1).get_deploy_hash()
+    );
+    assert_ne!(deploy_1.get_deploy_hash(), deploy_2.get_deploy_hash());
+
+    let exec_request = ExecRequestBuilder::new()
+        .push_deploy(deploy_1)
+        .push_deploy(deploy_2)
+        .with_block_time(2)
+        .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use contract_ffi::contract_api::argsparser::ArgsParser;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::DeployResult_ExecutionResult;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, STANDARD_PAYMENT_CONTRACT,
+};
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+
+fn exec_session(session_wasm: &str, args: impl ArgsParser) -> DeployResult_ExecutionResult {
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_session_code(session_wasm, args)
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(MAX_PAYMENT),))
+            .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+            .build();
+
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::new(EngineConfig::new().set_use_payment_code(true));
+
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    test_support::get_success_result(response)
+}
+
+#[ignore]
+#[test]
+fn should_report_cost_breakdown_summing_to_cost() {
+    let execution_result = exec_session("do_nothing.wasm", ());
+
+    assert!(execution_result.has_cost_breakdown());
+    let cost_breakdown = execution_result.get_cost_breakdown();
+
+    assert!(cost_breakdown.get_opcodes() > 0);
+    assert_eq!(
+        cost_breakdown.get_opcodes()
+            + cost_breakdown.get_storage_writes()
+            + cost_breakdown.get_host_calls()
+            + cost_breakdown.get_transfers(),
+        execution_result.get_cost()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_storage_writes_per_byte_written() {
+    const SMALL_VALUE_SIZE: u64 = 100;
+    const LARGE_VALUE_SIZE: u64 = 1_100;
+
+    let storage_byte_cost = WasmCosts::default().storage_byte_cost;
+
+    let small_write = exec_session("write_local_bytes.wasm", (SMALL_VALUE_SIZE,));
+    let large_write = exec_session("write_local_bytes.wasm", (LARGE_VALUE_SIZE,));
+
+    let small_storage_cost = small_write.get_cost_breakdown().get_storage_writes();
+    let large_storage_cost = large_write.get_cost_breakdown().get_storage_writes();
+
+    assert!(small_storage_cost > 0);
+    assert_eq!(
+        large_storage_cost - small_storage_cost,
+        (LARGE_VALUE_SIZE - SMALL_VALUE_SIZE) * u64::from(storage_byte_cost)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_crypto_host_calls_per_call() {
+    let wasm_costs = WasmCosts::default();
+
+    let no_calls = exec_session("do_nothing.wasm", ());
+    let crypto_calls = exec_session(
+        "host_crypto.wasm",
+        (b"abc".to_vec(), vec![0u8; 32], vec![0u8; 64]),
+    );
+
+    assert_eq!(
+        crypto_calls.get_cost_breakdown().get_host_calls()
+            - no_calls.get_cost_breakdown().get_host_calls(),
+        u64::from(wasm_costs.blake2b256_cost)
+            + u64::from(wasm_costs.sha256_cost)
+            + u64::from(wasm_costs.ed25519_verify_cost)
+    );
+}
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::{PublicKey, PurseId};
+use contract_ffi::value::U512;
+use engine_core::engine_state::error::Error;
+use engine_core::engine_state::genesis::POS_REWARDS_PURSE;
+use engine_core::engine_state::{EngineConfig, CONV_RATE, DEFAULT_MIN_GAS_PRICE, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::{
+    ChainSpec_ActivationPoint, ChainSpec_UpgradePoint, ExecRequest, UpgradeRequest,
+};
+use engine_grpc_server::engine_server::state;
+
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, GENESIS_INITIAL_BALANCE,
+    STANDARD_PAYMENT_CONTRACT,
+};
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const PROTOCOL_VERSION: u64 = 1;
+const NEW_PROTOCOL_VERSION: u64 = 2;
+const ACTIVATION_POINT_RANK: u64 = 100;
+
+fn create_exec_request(gas_price: u64, protocol_version: u64) -> ExecRequest {
+    let deploy = DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_gas_price(gas_price)
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build();
+
+    ExecRequestBuilder::new()
+        .with_protocol_version(protocol_version)
+        .push_deploy(deploy)
+        .build()
+}
+
+fn create_upgrade_request(new_min_gas_price: u64) -> UpgradeRequest {
+    let mut upgrade_point = ChainSpec_UpgradePoint::new();
+    {
+        let mut activation_point = ChainSpec_ActivationPoint::new();
+        activation_point.set_rank(ACTIVATION_POINT_RANK);
+        upgrade_point.set_activation_point(activation_point);
+    }
+    {
+        let mut protocol_version = state::ProtocolVersion::new();
+        protocol_version.set_value(NEW_PROTOCOL_VERSION);
+        upgrade_point.set_protocol_version(protocol_version);
+    }
+    upgrade_point.set_new_min_gas_price(new_min_gas_price);
+
+    let mut upgrade_request = UpgradeRequest::new();
+    upgrade_request.set_upgrade_point(upgrade_point);
+    {
+        let mut protocol_version = state::ProtocolVersion::new();
+        protocol_version.set_value(PROTOCOL_VERSION);
+        upgrade_request.set_protocol_version(protocol_version);
+    }
+    upgrade_request
+}
+
+fn get_pos_rewards_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse_id = builder
+        .get_pos_contract()
+        .urefs_lookup()
+        .get(POS_REWARDS_PURSE)
+        .and_then(Key::as_uref)
+        .map(|uref| PurseId::new(*uref))
+        .expect("should find PoS rewards purse");
+    builder.get_purse_balance(purse_id)
+}
+
+#[ignore]
+#[test]
+fn should_charge_deploy_at_its_gas_price() {
+    let gas_price = 3 * CONV_RATE;
+
+    let mut builder = InMemoryWasmTestBuilder::new(EngineConfig::new().set_use_payment_code(true));
+
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(create_exec_request(gas_price, PROTOCOL_VERSION))
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .clone();
+    let execution_result = test_support::get_success_result(&response);
+    assert_eq!(execution_result.gas_price, gas_price);
+
+    let fees = U512::from(execution_result.cost * gas_price);
+    assert!(!fees.is_zero());
+
+    let genesis_account = builder
+        .get_account(Key::Account(GENESIS_ADDR))
+        .expect("should have genesis account");
+    assert_eq!(
+        builder.get_purse_balance(genesis_account.purse_id()),
+        U512::from(GENESIS_INITIAL_BALANCE) - fees
+    );
+    assert_eq!(get_pos_rewards_purse_balance(&builder), fees);
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_zero_gas_price() {
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(create_exec_request(0, PROTOCOL_VERSION))
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    let precondition_failure = test_support::get_precondition_failure(response);
+
+    assert_eq!(
+        precondition_failure.message,
+        Error::GasPriceTooLow {
+            gas_price: 0,
+            min_gas_price: DEFAULT_MIN_GAS_PRICE
+        }
+        .to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_gas_price_below_upgraded_minimum() {
+    let min_gas_price = 2 * CONV_RATE;
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(create_upgrade_request(min_gas_price))
+        .exec_with_exec_request(create_exec_request(CONV_RATE, NEW_PROTOCOL_VERSION))
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    let precondition_failure = test_support::get_precondition_failure(response);
+
+    assert_eq!(
+        precondition_failure.message,
+        Error::GasPriceTooLow {
+            gas_price: CONV_RATE,
+            min_gas_price
+        }
+        .to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_at_upgraded_minimum_gas_price() {
+    let min_gas_price = 2 * CONV_RATE;
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(create_upgrade_request(min_gas_price))
+        .exec_with_exec_request(create_exec_request(min_gas_price, NEW_PROTOCOL_VERSION))
+        .expect_success()
+        .commit();
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::DeployResult_HostCall;
+
+use crate::support::test_support::{
+    DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, STANDARD_PAYMENT_CONTRACT,
+};
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const BLOCK_TIME: u64 = 42;
+
+fn exec_get_blocktime(trace: bool) -> Vec<DeployResult_HostCall> {
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_session_code("get_blocktime.wasm", (BLOCK_TIME,))
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(MAX_PAYMENT),))
+            .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+            .build();
+
+        ExecRequestBuilder::new()
+            .push_deploy(deploy)
+            .with_block_time(BLOCK_TIME)
+            .with_trace(trace)
+            .build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::new(EngineConfig::new().set_use_payment_code(true));
+
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    response.get_success().get_deploy_results()[0]
+        .get_host_calls()
+        .to_vec()
+}
+
+#[ignore]
+#[test]
+fn should_trace_host_calls_when_asked() {
+    let host_calls = exec_get_blocktime(true);
+
+    let get_blocktime = host_calls
+        .iter()
+        .find(|host_call| host_call.get_name() == "get_blocktime")
+        .expect("should have traced get_blocktime");
+    // The only argument is the pointer the block time is written to
+    assert!(get_blocktime.get_args().starts_with("I32("));
+    assert!(host_calls
+        .iter()
+        .all(|host_call| host_call.get_name() != "gas"));
+}
+
+#[ignore]
+#[test]
+fn should_not_trace_host_calls_by_default() {
+    assert!(exec_get_blocktime(false).is_empty());
+}
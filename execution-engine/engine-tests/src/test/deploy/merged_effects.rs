@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+use crate::support::test_support::{
+    DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, WasmTestResult, DEFAULT_BLOCK_TIME,
+};
+use contract_ffi::value::account::{BlockTime, PublicKey};
+use contract_ffi::value::U512;
+use engine_core::engine_state::{executed_deploy_key, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::{Deploy, ExecResult};
+use engine_grpc_server::engine_server::mappings::CommitTransforms;
+use engine_shared::transform::Transform;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+
+const GENESIS_ADDR: [u8; 32] = [6; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1; 32];
+const ACCOUNT_2_ADDR: [u8; 32] = [2; 32];
+const ACCOUNT_3_ADDR: [u8; 32] = [3; 32];
+const STANDARD_PAYMENT_WASM: &str = "standard_payment.wasm";
+const TRANSFER_WASM: &str = "transfer_purse_to_account.wasm";
+const INITIAL_AMOUNT: u64 = 100_000_000;
+const TRANSFER_AMOUNT: u64 = 1_000;
+
+fn transfer_deploy(from: [u8; 32], to: [u8; 32], amount: u64, deploy_hash: [u8; 32]) -> Deploy {
+    DeployBuilder::new()
+        .with_address(from)
+        .with_deploy_hash(deploy_hash)
+        .with_session_code(TRANSFER_WASM, (PublicKey::new(to), U512::from(amount)))
+        .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(from)])
+        .build()
+}
+
+/// Funds accounts 1 and 2, and creates account 3.
+fn setup() -> WasmTestResult<InMemoryGlobalState> {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    for (i, (to, amount)) in [
+        (ACCOUNT_1_ADDR, INITIAL_AMOUNT),
+        (ACCOUNT_2_ADDR, INITIAL_AMOUNT),
+        (ACCOUNT_3_ADDR, TRANSFER_AMOUNT),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let deploy = transfer_deploy(GENESIS_ADDR, *to, *amount, [i as u8 + 1; 32]);
+        let exec_request = ExecRequestBuilder::new().push_deploy(deploy).build();
+        builder
+            .exec_with_exec_request(exec_request)
+            .expect_success()
+            .commit();
+    }
+    builder.finish()
+}
+
+fn exec_result(deploys: Vec<Deploy>) -> ExecResult {
+    let mut exec_request_builder = ExecRequestBuilder::new();
+    for deploy in deploys {
+        exec_request_builder = exec_request_builder.push_deploy(deploy);
+    }
+
+    let result = InMemoryWasmTestBuilder::from_result(setup())
+        .exec_with_exec_request(exec_request_builder.build())
+        .expect_success()
+        .finish();
+
+    result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .get_success()
+        .to_owned()
+}
+
+#[ignore]
+#[test]
+fn should_merge_transfers_to_same_account() {
+    let exec_result = exec_result(vec![
+        transfer_deploy(ACCOUNT_1_ADDR, ACCOUNT_3_ADDR, TRANSFER_AMOUNT, [11; 32]),
+        transfer_deploy(ACCOUNT_2_ADDR, ACCOUNT_3_ADDR, TRANSFER_AMOUNT, [12; 32]),
+    ]);
+
+    assert_eq!(exec_result.get_merged_deploys(), &[0, 1]);
+
+    let merged_transforms: CommitTransforms = exec_result
+        .get_merged_effect()
+        .get_transform_map()
+        .try_into()
+        .expect("should parse merged transforms");
+    let merged_addition = Transform::AddUInt512(U512::from(2 * TRANSFER_AMOUNT));
+    assert!(merged_transforms
+        .value()
+        .values()
+        .any(|transform| *transform == merged_addition));
+}
+
+#[ignore]
+#[test]
+fn should_not_merge_transfers_from_same_account() {
+    let exec_result = exec_result(vec![
+        transfer_deploy(ACCOUNT_1_ADDR, ACCOUNT_3_ADDR, TRANSFER_AMOUNT, [11; 32]),
+        transfer_deploy(ACCOUNT_1_ADDR, ACCOUNT_3_ADDR, TRANSFER_AMOUNT, [12; 32]),
+    ]);
+
+    assert_eq!(exec_result.get_merged_deploys(), &[0]);
+}
+
+#[ignore]
+#[test]
+fn should_report_effects_of_each_deploy() {
+    let deploy_hashes = [[11; 32], [12; 32]];
+    let exec_result = exec_result(vec![
+        transfer_deploy(
+            ACCOUNT_1_ADDR,
+            ACCOUNT_3_ADDR,
+            TRANSFER_AMOUNT,
+            deploy_hashes[0],
+        ),
+        transfer_deploy(
+            ACCOUNT_2_ADDR,
+            ACCOUNT_3_ADDR,
+            TRANSFER_AMOUNT,
+            deploy_hashes[1],
+        ),
+    ]);
+
+    for (index, deploy_result) in exec_result.get_deploy_results().iter().enumerate() {
+        let transforms: CommitTransforms = deploy_result
+            .get_execution_result()
+            .get_effects()
+            .get_transform_map()
+            .try_into()
+            .expect("should parse deploy transforms");
+        for (other_index, deploy_hash) in deploy_hashes.iter().enumerate() {
+            assert_eq!(
+                transforms
+                    .get(&executed_deploy_key(
+                        BlockTime(DEFAULT_BLOCK_TIME),
+                        *deploy_hash
+                    ))
+                    .is_some(),
+                index == other_index,
+                "each deploy should report only its own effects"
+            );
+        }
+    }
+}
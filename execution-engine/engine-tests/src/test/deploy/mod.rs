@@ -1,6 +1,34 @@
 #[cfg(test)]
+mod approvals;
+#[cfg(test)]
+mod dependencies;
+#[cfg(test)]
+mod deploy_hash;
+#[cfg(test)]
+mod gas_breakdown;
+#[cfg(test)]
+mod gas_price;
+#[cfg(test)]
+mod host_call_trace;
+#[cfg(test)]
+mod merged_effects;
+#[cfg(test)]
+mod native_transfer;
+#[cfg(test)]
+mod parallel_execution;
+#[cfg(test)]
 mod payment_code;
 #[cfg(test)]
 mod preconditions;
 #[cfg(test)]
+mod refund_ratio;
+#[cfg(test)]
+mod replay_protection;
+#[cfg(test)]
+mod stack_height;
+#[cfg(test)]
 mod stored_contracts;
+#[cfg(test)]
+mod system_account;
+#[cfg(test)]
+mod ttl;
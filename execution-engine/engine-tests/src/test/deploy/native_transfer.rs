@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder,
+};
+use contract_ffi::key::Key;
+use contract_ffi::uref::{AccessRights, URef};
+use contract_ffi::value::account::{PublicKey, PurseId};
+use contract_ffi::value::U512;
+use engine_core::engine_state::{
+    EngineConfig, CONV_RATE, DEFAULT_NATIVE_TRANSFER_COST, MAX_PAYMENT,
+};
+use engine_grpc_server::engine_server::ipc::{DeployError_ErrorCode, ExecRequest};
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [42u8; 32];
+const NONEXISTENT_ADDR: [u8; 32] = [99u8; 32];
+const STANDARD_PAYMENT_WASM: &str = "standard_payment.wasm";
+const TEST_PURSE_NAME: &str = "test_purse";
+const ACCOUNT_1_FUNDING_AMOUNT: u64 = 1_000_000_000;
+const TEST_PURSE_FUNDING_AMOUNT: u64 = 1_000_000;
+const TRANSFER_AMOUNT: u64 = 1_000;
+
+fn native_transfer_fee() -> U512 {
+    U512::from(DEFAULT_NATIVE_TRANSFER_COST * CONV_RATE)
+}
+
+/// Funds account 1 and gives it a funded purse named [`TEST_PURSE_NAME`].
+fn setup() -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config);
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_WASM,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_purse_to_account.wasm",
+            (
+                PublicKey::new(ACCOUNT_1_ADDR),
+                U512::from(ACCOUNT_1_FUNDING_AMOUNT),
+            ),
+            0,
+            [1; 32],
+        )
+        .expect_success()
+        .commit()
+        .exec_with_args(
+            ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_WASM,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_main_purse_to_new_purse.wasm",
+            (TEST_PURSE_NAME, U512::from(TEST_PURSE_FUNDING_AMOUNT)),
+            0,
+            [2; 32],
+        )
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn native_transfer_request(source: Option<URef>, target: [u8; 32], amount: U512) -> ExecRequest {
+    let deploy = DeployBuilder::new()
+        .with_address(ACCOUNT_1_ADDR)
+        .with_deploy_hash([3; 32])
+        .with_native_transfer(source, PublicKey::new(target), amount, Some(1))
+        .with_authorization_keys(&[PublicKey::new(ACCOUNT_1_ADDR)])
+        .build();
+
+    ExecRequestBuilder::new().push_deploy(deploy).build()
+}
+
+fn main_purse_id(builder: &InMemoryWasmTestBuilder, addr: [u8; 32]) -> PurseId {
+    builder
+        .get_account(Key::Account(addr))
+        .expect("should have account")
+        .purse_id()
+}
+
+fn test_purse_id(builder: &InMemoryWasmTestBuilder) -> PurseId {
+    let account_1 = builder
+        .get_account(Key::Account(ACCOUNT_1_ADDR))
+        .expect("should have account");
+    let purse_key = account_1.urefs_lookup()[TEST_PURSE_NAME];
+    PurseId::new(*purse_key.as_uref().expect("should have uref"))
+}
+
+#[ignore]
+#[test]
+fn should_transfer_from_main_purse_without_wasm() {
+    let mut builder = setup();
+    let account_1_purse = main_purse_id(&builder, ACCOUNT_1_ADDR);
+    let genesis_purse = main_purse_id(&builder, GENESIS_ADDR);
+    let account_1_starting_balance = builder.get_purse_balance(account_1_purse);
+    let genesis_starting_balance = builder.get_purse_balance(genesis_purse);
+
+    builder
+        .exec_with_exec_request(native_transfer_request(
+            None,
+            GENESIS_ADDR,
+            U512::from(TRANSFER_AMOUNT),
+        ))
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(2)
+        .expect("there should be a response")
+        .clone();
+    assert_eq!(
+        test_support::get_success_result(&response).cost,
+        DEFAULT_NATIVE_TRANSFER_COST,
+        "native transfer should cost the flat native transfer cost"
+    );
+
+    assert_eq!(
+        builder.get_purse_balance(account_1_purse),
+        account_1_starting_balance - TRANSFER_AMOUNT - native_transfer_fee(),
+        "main purse should pay the amount and the fee"
+    );
+    assert_eq!(
+        builder.get_purse_balance(genesis_purse),
+        genesis_starting_balance + TRANSFER_AMOUNT,
+        "target should receive the amount"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_transfer_from_named_purse() {
+    let mut builder = setup();
+    let account_1_purse = main_purse_id(&builder, ACCOUNT_1_ADDR);
+    let test_purse = test_purse_id(&builder);
+    let account_1_starting_balance = builder.get_purse_balance(account_1_purse);
+
+    builder
+        .exec_with_exec_request(native_transfer_request(
+            Some(test_purse.value()),
+            GENESIS_ADDR,
+            U512::from(TRANSFER_AMOUNT),
+        ))
+        .expect_success()
+        .commit();
+
+    assert_eq!(
+        builder.get_purse_balance(test_purse),
+        U512::from(TEST_PURSE_FUNDING_AMOUNT - TRANSFER_AMOUNT),
+        "named purse should pay the amount"
+    );
+    assert_eq!(
+        builder.get_purse_balance(account_1_purse),
+        account_1_starting_balance - native_transfer_fee(),
+        "main purse should pay the fee"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_forged_source_purse() {
+    let mut builder = setup();
+    let account_1_purse = main_purse_id(&builder, ACCOUNT_1_ADDR);
+    let account_1_starting_balance = builder.get_purse_balance(account_1_purse);
+    let forged_purse = URef::new([1; 32], AccessRights::READ_ADD_WRITE);
+
+    builder
+        .exec_with_exec_request(native_transfer_request(
+            Some(forged_purse),
+            GENESIS_ADDR,
+            U512::from(TRANSFER_AMOUNT),
+        ))
+        .commit();
+
+    let response = builder
+        .get_exec_response(2)
+        .expect("there should be a response")
+        .clone();
+    let execution_result = test_support::get_success_result(&response);
+    assert_eq!(
+        execution_result.get_error().get_exec_error().get_code(),
+        DeployError_ErrorCode::FORGED_REFERENCE
+    );
+    assert_eq!(
+        builder.get_purse_balance(account_1_purse),
+        account_1_starting_balance,
+        "no fee should be charged for a forged source purse"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_fee_when_target_account_does_not_exist() {
+    let mut builder = setup();
+    let account_1_purse = main_purse_id(&builder, ACCOUNT_1_ADDR);
+    let account_1_starting_balance = builder.get_purse_balance(account_1_purse);
+
+    builder
+        .exec_with_exec_request(native_transfer_request(
+            None,
+            NONEXISTENT_ADDR,
+            U512::from(TRANSFER_AMOUNT),
+        ))
+        .commit();
+
+    let response = builder
+        .get_exec_response(2)
+        .expect("there should be a response")
+        .clone();
+    let error_message =
+        test_support::get_error_message(test_support::get_success_result(&response));
+    assert_eq!(error_message, "Mint error: Destination not found");
+    assert_eq!(
+        builder.get_purse_balance(account_1_purse),
+        account_1_starting_balance - native_transfer_fee(),
+        "only the fee should be charged"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_fee_when_source_purse_lacks_funds() {
+    let mut builder = setup();
+    let account_1_purse = main_purse_id(&builder, ACCOUNT_1_ADDR);
+    let account_1_starting_balance = builder.get_purse_balance(account_1_purse);
+
+    // Once the fee is paid, the main purse can no longer cover its whole starting balance.
+    builder
+        .exec_with_exec_request(native_transfer_request(
+            None,
+            GENESIS_ADDR,
+            account_1_starting_balance,
+        ))
+        .commit();
+
+    let response = builder
+        .get_exec_response(2)
+        .expect("there should be a response")
+        .clone();
+    let error_message =
+        test_support::get_error_message(test_support::get_success_result(&response));
+    assert_eq!(error_message, "Mint error: Insufficient funds");
+    assert_eq!(
+        builder.get_purse_balance(account_1_purse),
+        account_1_starting_balance - native_transfer_fee(),
+        "only the fee should be charged"
+    );
+}
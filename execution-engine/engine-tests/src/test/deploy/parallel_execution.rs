@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder};
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::error::Error;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::{Deploy, DeployResult, ExecRequest};
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1; 32];
+const STANDARD_PAYMENT_WASM: &str = "standard_payment.wasm";
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const TRANSFER_WASM: &str = "transfer_purse_to_account.wasm";
+const DEPLOY_1_HASH: [u8; 32] = [1; 32];
+const DEPLOY_2_HASH: [u8; 32] = [2; 32];
+const DEPLOY_3_HASH: [u8; 32] = [3; 32];
+const DEPLOY_4_HASH: [u8; 32] = [4; 32];
+const DEPLOY_THREADS: usize = 4;
+
+fn do_nothing_deploy(deploy_hash: [u8; 32], dependencies: &[[u8; 32]]) -> Deploy {
+    DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash(deploy_hash)
+        .with_dependencies(dependencies)
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build()
+}
+
+fn transfer_deploy(deploy_hash: [u8; 32]) -> Deploy {
+    DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash(deploy_hash)
+        .with_session_code(
+            TRANSFER_WASM,
+            (PublicKey::new(ACCOUNT_1_ADDR), U512::from(MAX_PAYMENT)),
+        )
+        .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build()
+}
+
+fn deploy_results(deploy_threads: usize, exec_request: ExecRequest) -> Vec<DeployResult> {
+    let engine_config = EngineConfig::new().set_deploy_threads(deploy_threads);
+    let result = InMemoryWasmTestBuilder::new(engine_config)
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .finish();
+
+    result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .get_success()
+        .get_deploy_results()
+        .to_vec()
+}
+
+#[ignore]
+#[test]
+fn should_execute_independent_deploys_in_parallel_like_serially() {
+    let exec_request = ExecRequestBuilder::new()
+        .push_deploy(transfer_deploy(DEPLOY_1_HASH))
+        .push_deploy(do_nothing_deploy(DEPLOY_2_HASH, &[]))
+        .push_deploy(transfer_deploy(DEPLOY_3_HASH))
+        .build();
+
+    let serial = deploy_results(1, exec_request.clone());
+    let parallel = deploy_results(DEPLOY_THREADS, exec_request);
+
+    assert_eq!(parallel.len(), 3);
+    for deploy_result in &parallel {
+        assert!(deploy_result.has_execution_result());
+        assert!(!deploy_result.get_execution_result().has_error());
+    }
+    assert_eq!(parallel, serial);
+}
+
+#[ignore]
+#[test]
+fn should_execute_conflicting_deploys_in_parallel_like_serially() {
+    let exec_request = ExecRequestBuilder::new()
+        .push_deploy(do_nothing_deploy(DEPLOY_1_HASH, &[]))
+        .push_deploy(do_nothing_deploy(DEPLOY_2_HASH, &[DEPLOY_1_HASH]))
+        .push_deploy(do_nothing_deploy(DEPLOY_1_HASH, &[]))
+        .push_deploy(do_nothing_deploy(DEPLOY_3_HASH, &[DEPLOY_4_HASH]))
+        .push_deploy(do_nothing_deploy(DEPLOY_4_HASH, &[]))
+        .build();
+
+    let serial = deploy_results(1, exec_request.clone());
+    let parallel = deploy_results(DEPLOY_THREADS, exec_request);
+
+    assert_eq!(parallel.len(), 5);
+    assert!(parallel[0].has_execution_result());
+    assert!(parallel[1].has_execution_result());
+    assert_eq!(
+        parallel[2].get_precondition_failure().message,
+        Error::DuplicateDeploy(DEPLOY_1_HASH).to_string()
+    );
+    assert_eq!(
+        parallel[3].get_precondition_failure().message,
+        Error::MissingDeployDependency(DEPLOY_4_HASH).to_string()
+    );
+    assert!(parallel[4].has_execution_result());
+    assert_eq!(parallel, serial);
+}
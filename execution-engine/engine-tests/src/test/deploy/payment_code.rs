@@ -5,10 +5,13 @@ use crate::support::test_support::{
     DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, GENESIS_INITIAL_BALANCE,
 };
 use contract_ffi::key::Key;
+use contract_ffi::system_contracts::mint::TOTAL_SUPPLY_KEY;
+use contract_ffi::system_contracts::pos::FeeHandling;
 use contract_ffi::value::account::{PublicKey, PurseId};
 use contract_ffi::value::{Value, U512};
 use engine_core::engine_state::genesis::POS_REWARDS_PURSE;
 use engine_core::engine_state::{EngineConfig, CONV_RATE, MAX_PAYMENT};
+use engine_shared::motes::Motes;
 use engine_shared::transform::Transform;
 
 use crate::contract_ffi::bytesrepr::ToBytes;
@@ -16,6 +19,7 @@ use crate::support::test_support;
 
 const GENESIS_ADDR: [u8; 32] = [12; 32];
 const ACCOUNT_1_ADDR: [u8; 32] = [42u8; 32];
+const PROPOSER_ADDR: [u8; 32] = [43u8; 32];
 const STANDARD_PAYMENT_WASM: &str = "standard_payment.wasm";
 const DO_NOTHING_WASM: &str = "do_nothing.wasm";
 
@@ -56,7 +60,7 @@ fn should_raise_insufficient_payment_when_caller_lacks_minimum_balance() {
     let account_1_request = {
         let deploy = DeployBuilder::new()
             .with_address(ACCOUNT_1_ADDR)
-            .with_deploy_hash([1; 32])
+            .with_deploy_hash([2; 32])
             .with_session_code("revert.wasm", ())
             .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT - 1),))
             .with_authorization_keys(&[account_1_public_key])
@@ -79,8 +83,13 @@ fn should_raise_insufficient_payment_when_caller_lacks_minimum_balance() {
     };
 
     assert_eq!(
-        error_message, "Insufficient payment",
-        "expected insufficient payment"
+        error_message,
+        format!(
+            "Insufficient balance: {} required, {} available",
+            Motes::from(MAX_PAYMENT),
+            Motes::from(MAX_PAYMENT - 1)
+        ),
+        "expected insufficient balance"
     );
 
     let expected_transfers_count = 0;
@@ -578,6 +587,113 @@ fn should_finalize_to_rewards_purse() {
     assert!(!rewards_purse_balance.is_zero());
 }
 
+fn get_total_supply(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let mint_key = Key::URef(builder.get_mint_contract_uref());
+    match builder.query(None, mint_key, &[TOTAL_SUPPLY_KEY]) {
+        Some(Value::UInt512(total_supply)) => total_supply,
+        other => panic!("total supply should be a U512, got {:?}", other),
+    }
+}
+
+/// Runs a transfer deploy with the given fee handling policy and proposer,
+/// returning the builder and the fees charged for the deploy in motes.
+fn exec_with_fee_handling(
+    fee_handling: FeeHandling,
+    proposer: Option<PublicKey>,
+) -> (InMemoryWasmTestBuilder, U512) {
+    let genesis_public_key = PublicKey::new(GENESIS_ADDR);
+    let account_1_public_key = PublicKey::new(ACCOUNT_1_ADDR);
+    let payment_purse_amount = 10_000_000;
+    let transferred_amount = 1;
+
+    let engine_config = EngineConfig::new()
+        .set_use_payment_code(true)
+        .set_fee_handling(fee_handling);
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_session_code(
+                "transfer_purse_to_account.wasm",
+                (account_1_public_key, U512::from(transferred_amount)),
+            )
+            .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(payment_purse_amount),))
+            .with_authorization_keys(&[genesis_public_key])
+            .with_deploy_hash([1; 32])
+            .build();
+
+        let exec_request_builder = ExecRequestBuilder::new().push_deploy(deploy);
+        match proposer {
+            Some(proposer) => exec_request_builder.with_proposer(proposer).build(),
+            None => exec_request_builder.build(),
+        }
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config);
+
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .clone();
+    let fees = U512::from(test_support::get_success_result(&response).cost * CONV_RATE);
+
+    (builder, fees)
+}
+
+#[ignore]
+#[test]
+fn should_burn_fees_when_configured_to_burn() {
+    let (builder, fees) = exec_with_fee_handling(FeeHandling::Burn, None);
+
+    let rewards_purse_balance = get_pos_rewards_purse_balance(&builder);
+    assert!(rewards_purse_balance.is_zero());
+
+    assert_eq!(
+        get_total_supply(&builder),
+        U512::from(GENESIS_INITIAL_BALANCE) - fees,
+        "fees should be removed from the total supply"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_pay_fees_to_proposer_when_configured_to_pay_proposer() {
+    let proposer = PublicKey::new(PROPOSER_ADDR);
+    let (builder, fees) = exec_with_fee_handling(FeeHandling::PayToProposer, Some(proposer));
+
+    let rewards_purse_balance = get_pos_rewards_purse_balance(&builder);
+    assert!(rewards_purse_balance.is_zero());
+
+    let proposer_account = builder
+        .get_account(Key::Account(PROPOSER_ADDR))
+        .expect("proposer account should be created");
+    assert_eq!(
+        builder.get_purse_balance(proposer_account.purse_id()),
+        fees,
+        "proposer should receive the fees"
+    );
+    assert_eq!(
+        get_total_supply(&builder),
+        U512::from(GENESIS_INITIAL_BALANCE),
+        "paying the proposer should not change the total supply"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_finalize_to_rewards_purse_when_proposer_is_unknown() {
+    let (builder, fees) = exec_with_fee_handling(FeeHandling::PayToProposer, None);
+
+    let rewards_purse_balance = get_pos_rewards_purse_balance(&builder);
+    assert_eq!(rewards_purse_balance, fees);
+}
+
 #[ignore]
 #[test]
 fn independent_standard_payments_should_not_write_the_same_keys() {
@@ -629,7 +745,7 @@ fn independent_standard_payments_should_not_write_the_same_keys() {
             .with_session_code(DO_NOTHING_WASM, ())
             .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(payment_purse_amount),))
             .with_authorization_keys(&[account_1_public_key])
-            .with_deploy_hash([1; 32])
+            .with_deploy_hash([3; 32])
             .build();
 
         ExecRequestBuilder::new().push_deploy(deploy).build()
@@ -815,3 +931,84 @@ fn should_charge_non_main_purse() {
         "purse resting balance should equal funding amount minus exec costs"
     );
 }
+
+#[ignore]
+#[test]
+fn should_pay_from_purse_passed_to_standard_payment_from_purse() {
+    const TEST_PURSE_NAME: &str = "treasury";
+
+    let payment_purse_amount = U512::from(10_000_000);
+    let account_1_funding_amount = U512::from(100_000_000);
+    let account_1_purse_funding_amount = U512::from(50_000_000);
+
+    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config);
+
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_WASM,
+            (payment_purse_amount,),
+            "transfer_purse_to_account.wasm",
+            (PublicKey::new(ACCOUNT_1_ADDR), account_1_funding_amount),
+            0,
+            [1; 32],
+        )
+        .expect_success()
+        .commit()
+        .exec_with_args(
+            ACCOUNT_1_ADDR,
+            STANDARD_PAYMENT_WASM,
+            (payment_purse_amount,),
+            "transfer_main_purse_to_new_purse.wasm",
+            (TEST_PURSE_NAME, account_1_purse_funding_amount),
+            0,
+            [2; 32],
+        )
+        .expect_success()
+        .commit();
+
+    let account_1 = builder
+        .get_account(Key::Account(ACCOUNT_1_ADDR))
+        .expect("should have account");
+    let main_purse_starting_balance = builder.get_purse_balance(account_1.purse_id());
+    let purse_id_key = account_1.urefs_lookup()[TEST_PURSE_NAME];
+    let purse_id = PurseId::new(*purse_id_key.as_uref().expect("should have uref"));
+
+    assert_eq!(
+        builder.get_purse_balance(purse_id),
+        account_1_purse_funding_amount,
+        "purse should be funded with expected amount"
+    );
+
+    builder
+        .exec_with_payment_purse(
+            ACCOUNT_1_ADDR,
+            purse_id,
+            payment_purse_amount,
+            DO_NOTHING_WASM,
+            (),
+            0,
+            [3; 32],
+        )
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(2)
+        .expect("there should be a response")
+        .clone();
+    let motes = test_support::get_success_result(&response).cost * CONV_RATE;
+
+    assert_eq!(
+        builder.get_purse_balance(purse_id),
+        account_1_purse_funding_amount - motes,
+        "purse should pay for the deploy and receive the refund"
+    );
+    assert_eq!(
+        builder.get_purse_balance(account_1.purse_id()),
+        main_purse_starting_balance,
+        "main purse should not pay for the deploy"
+    );
+}
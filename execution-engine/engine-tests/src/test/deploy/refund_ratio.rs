@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::RefundRatio;
+use contract_ffi::value::account::{PublicKey, PurseId};
+use contract_ffi::value::U512;
+use engine_core::engine_state::genesis::POS_REWARDS_PURSE;
+use engine_core::engine_state::{EngineConfig, CONV_RATE};
+use engine_grpc_server::engine_server::ipc::{
+    ChainSpec_ActivationPoint, ChainSpec_RefundRatio, ChainSpec_UpgradePoint, UpgradeRequest,
+};
+use engine_grpc_server::engine_server::state;
+
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, GENESIS_INITIAL_BALANCE,
+    STANDARD_PAYMENT_CONTRACT,
+};
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const PROTOCOL_VERSION: u64 = 1;
+const NEW_PROTOCOL_VERSION: u64 = 2;
+const ACTIVATION_POINT_RANK: u64 = 100;
+const PAYMENT_AMOUNT: u64 = 10_000_000;
+
+fn create_upgrade_request(refund_ratio: RefundRatio) -> UpgradeRequest {
+    let mut upgrade_point = ChainSpec_UpgradePoint::new();
+    {
+        let mut activation_point = ChainSpec_ActivationPoint::new();
+        activation_point.set_rank(ACTIVATION_POINT_RANK);
+        upgrade_point.set_activation_point(activation_point);
+    }
+    {
+        let mut protocol_version = state::ProtocolVersion::new();
+        protocol_version.set_value(NEW_PROTOCOL_VERSION);
+        upgrade_point.set_protocol_version(protocol_version);
+    }
+    upgrade_point.set_new_refund_ratio(ChainSpec_RefundRatio::from(refund_ratio));
+
+    let mut upgrade_request = UpgradeRequest::new();
+    upgrade_request.set_upgrade_point(upgrade_point);
+    {
+        let mut protocol_version = state::ProtocolVersion::new();
+        protocol_version.set_value(PROTOCOL_VERSION);
+        upgrade_request.set_protocol_version(protocol_version);
+    }
+    upgrade_request
+}
+
+fn get_pos_rewards_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse_id = builder
+        .get_pos_contract()
+        .urefs_lookup()
+        .get(POS_REWARDS_PURSE)
+        .and_then(Key::as_uref)
+        .map(|uref| PurseId::new(*uref))
+        .expect("should find PoS rewards purse");
+    builder.get_purse_balance(purse_id)
+}
+
+/// Runs a deploy paying `PAYMENT_AMOUNT` under a protocol version which refunds
+/// `refund_ratio` of the unspent payment, returning the builder and the fees
+/// charged for the deploy in motes.
+fn exec_with_refund_ratio(refund_ratio: RefundRatio) -> (InMemoryWasmTestBuilder, U512) {
+    let genesis_public_key = PublicKey::new(GENESIS_ADDR);
+
+    let engine_config = EngineConfig::new().set_use_payment_code(true);
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(GENESIS_ADDR)
+            .with_session_code("do_nothing.wasm", ())
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(PAYMENT_AMOUNT),))
+            .with_authorization_keys(&[genesis_public_key])
+            .with_deploy_hash([1; 32])
+            .build();
+
+        ExecRequestBuilder::new()
+            .with_protocol_version(NEW_PROTOCOL_VERSION)
+            .push_deploy(deploy)
+            .build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config);
+
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::default())
+        .upgrade_with_upgrade_request(create_upgrade_request(refund_ratio))
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .clone();
+    let fees = U512::from(test_support::get_success_result(&response).cost * CONV_RATE);
+
+    (builder, fees)
+}
+
+fn assert_refunded(builder: &InMemoryWasmTestBuilder, expected_refund: U512) {
+    let payment_amount = U512::from(PAYMENT_AMOUNT);
+
+    let genesis_account = builder
+        .get_account(Key::Account(GENESIS_ADDR))
+        .expect("should have genesis account");
+    assert_eq!(
+        builder.get_purse_balance(genesis_account.purse_id()),
+        U512::from(GENESIS_INITIAL_BALANCE) - payment_amount + expected_refund,
+        "deployer should receive the refund"
+    );
+
+    assert_eq!(
+        get_pos_rewards_purse_balance(builder),
+        payment_amount - expected_refund,
+        "the rest of the payment should go to the rewards purse"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_refund_share_of_unspent_payment_rounded_down() {
+    let (builder, fees) = exec_with_refund_ratio(RefundRatio::new(1, 3).unwrap());
+
+    let unspent = U512::from(PAYMENT_AMOUNT) - fees;
+    let expected_refund = unspent / 3;
+
+    assert_refunded(&builder, expected_refund);
+}
+
+#[ignore]
+#[test]
+fn should_round_down_refund_with_numerator_greater_than_one() {
+    let (builder, fees) = exec_with_refund_ratio(RefundRatio::new(2, 3).unwrap());
+
+    let unspent = U512::from(PAYMENT_AMOUNT) - fees;
+    let expected_refund = unspent * 2 / 3;
+
+    assert_refunded(&builder, expected_refund);
+}
+
+#[ignore]
+#[test]
+fn should_not_refund_with_zero_refund_ratio() {
+    let (builder, _fees) = exec_with_refund_ratio(RefundRatio::new(0, 1).unwrap());
+
+    assert_refunded(&builder, U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_refund_all_unspent_payment_with_default_refund_ratio() {
+    let (builder, fees) = exec_with_refund_ratio(RefundRatio::default());
+
+    let expected_refund = U512::from(PAYMENT_AMOUNT) - fees;
+
+    assert_refunded(&builder, expected_refund);
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder,
+};
+use contract_ffi::value::account::{BlockTime, PublicKey};
+use contract_ffi::value::U512;
+use engine_core::engine_state::error::Error;
+use engine_core::engine_state::{executed_deploy_key, EXECUTED_DEPLOY_WINDOW_MILLIS, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::{Deploy, ExecRequest};
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const STANDARD_PAYMENT_WASM: &str = "standard_payment.wasm";
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const DEPLOY_HASH: [u8; 32] = [1; 32];
+const OTHER_DEPLOY_HASH: [u8; 32] = [2; 32];
+const TTL_MILLIS: u32 = std::u32::MAX;
+
+fn do_nothing_deploy() -> Deploy {
+    timed_do_nothing_deploy(DEPLOY_HASH, 0)
+}
+
+fn timed_do_nothing_deploy(deploy_hash: [u8; 32], timestamp: u64) -> Deploy {
+    do_nothing_deploy_with_ttl(deploy_hash, timestamp, TTL_MILLIS)
+}
+
+fn do_nothing_deploy_with_ttl(deploy_hash: [u8; 32], timestamp: u64, ttl_millis: u32) -> Deploy {
+    DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash(deploy_hash)
+        .with_timestamp(timestamp)
+        .with_ttl_millis(ttl_millis)
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build()
+}
+
+fn exec_request_at(deploy: Deploy, block_time: u64) -> ExecRequest {
+    ExecRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_block_time(block_time)
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_deploy_executed_in_earlier_block() {
+    let exec_request_1 = ExecRequestBuilder::new()
+        .push_deploy(do_nothing_deploy())
+        .build();
+    let exec_request_2 = ExecRequestBuilder::new()
+        .push_deploy(do_nothing_deploy())
+        .build();
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec_with_exec_request(exec_request_2)
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(1)
+        .expect("there should be a response");
+
+    let precondition_failure = test_support::get_precondition_failure(response);
+
+    assert_eq!(
+        precondition_failure.message,
+        Error::DuplicateDeploy(DEPLOY_HASH).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_deploy_repeated_in_same_request() {
+    let exec_request = ExecRequestBuilder::new()
+        .push_deploy(do_nothing_deploy())
+        .push_deploy(do_nothing_deploy())
+        .build();
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .finish();
+
+    let deploy_results = result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .get_success()
+        .get_deploy_results();
+
+    assert_eq!(deploy_results.len(), 2);
+    assert!(deploy_results[0].has_execution_result());
+    assert_eq!(
+        deploy_results[1].get_precondition_failure().message,
+        Error::DuplicateDeploy(DEPLOY_HASH).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_deploy_executed_in_previous_window() {
+    // The deploy executes at the end of a window, and is replayed within its time to live at the
+    // start of the next one.
+    let timestamp = EXECUTED_DEPLOY_WINDOW_MILLIS - 2;
+    let exec_request_1 = exec_request_at(
+        timed_do_nothing_deploy(DEPLOY_HASH, timestamp),
+        EXECUTED_DEPLOY_WINDOW_MILLIS - 1,
+    );
+    let exec_request_2 = exec_request_at(
+        timed_do_nothing_deploy(DEPLOY_HASH, timestamp),
+        EXECUTED_DEPLOY_WINDOW_MILLIS + 1,
+    );
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec_with_exec_request(exec_request_2)
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(1)
+        .expect("there should be a response");
+
+    let precondition_failure = test_support::get_precondition_failure(response);
+
+    assert_eq!(
+        precondition_failure.message,
+        Error::DuplicateDeploy(DEPLOY_HASH).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_purge_records_of_deploys_executed_two_windows_earlier() {
+    let later_block_time = 2 * EXECUTED_DEPLOY_WINDOW_MILLIS;
+    let record_key = executed_deploy_key(BlockTime(0), DEPLOY_HASH);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request_at(do_nothing_deploy(), 0))
+        .expect_success()
+        .commit();
+    assert!(builder.query(None, record_key, &[]).is_some());
+
+    builder
+        .exec_with_exec_request(exec_request_at(
+            timed_do_nothing_deploy(OTHER_DEPLOY_HASH, later_block_time),
+            later_block_time,
+        ))
+        .expect_success()
+        .commit();
+    assert!(builder.query(None, record_key, &[]).is_none());
+    assert!(builder
+        .query(
+            None,
+            executed_deploy_key(BlockTime(later_block_time), OTHER_DEPLOY_HASH),
+            &[]
+        )
+        .is_some());
+
+    // The purged deploy can't be replayed, as its time to live has elapsed.
+    let result = builder
+        .exec_with_exec_request(exec_request_at(do_nothing_deploy(), later_block_time))
+        .finish();
+    let response = result
+        .builder()
+        .get_exec_response(2)
+        .expect("there should be a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure.message,
+        Error::DeployExpired {
+            expired_at: u64::from(TTL_MILLIS),
+            blocktime: later_block_time,
+        }
+        .to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_deploy_without_ttl_executed_two_windows_earlier() {
+    // Deploys without a time to live never expire, so their replays are rejected even once the
+    // records of the window they were executed in have been purged.
+    let later_block_time = 2 * EXECUTED_DEPLOY_WINDOW_MILLIS;
+    let untimed_deploy = || do_nothing_deploy_with_ttl(DEPLOY_HASH, 0, 0);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request_at(untimed_deploy(), 0))
+        .expect_success()
+        .commit()
+        .exec_with_exec_request(exec_request_at(
+            timed_do_nothing_deploy(OTHER_DEPLOY_HASH, later_block_time),
+            later_block_time,
+        ))
+        .expect_success()
+        .commit();
+    assert!(builder
+        .query(None, executed_deploy_key(BlockTime(0), DEPLOY_HASH), &[])
+        .is_none());
+
+    let result = builder
+        .exec_with_exec_request(exec_request_at(untimed_deploy(), later_block_time))
+        .finish();
+    let response = result
+        .builder()
+        .get_exec_response(2)
+        .expect("there should be a response");
+    let precondition_failure = test_support::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure.message,
+        Error::DuplicateDeploy(DEPLOY_HASH).to_string()
+    );
+}
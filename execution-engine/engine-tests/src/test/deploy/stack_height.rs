@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_grpc_server::engine_server::ipc::{
+    ChainSpec_ActivationPoint, ChainSpec_CostTable, ChainSpec_UpgradePoint, ExecRequest,
+    UpgradeRequest,
+};
+use engine_grpc_server::engine_server::state;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+
+use crate::support::test_support::{
+    DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, STANDARD_PAYMENT_CONTRACT,
+};
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const RECURSION_WASM: &str = "recursion.wasm";
+const PROTOCOL_VERSION: u64 = 1;
+const NEW_PROTOCOL_VERSION: u64 = 2;
+const ACTIVATION_POINT_RANK: u64 = 100;
+const STACK_LIMITER_TRAP: &str = "Trap(Trap { kind: Unreachable })";
+
+fn create_exec_request(depth: u32, protocol_version: u64) -> ExecRequest {
+    let deploy = DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_session_code(RECURSION_WASM, (depth,))
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build();
+
+    ExecRequestBuilder::new()
+        .with_protocol_version(protocol_version)
+        .push_deploy(deploy)
+        .build()
+}
+
+fn create_upgrade_request(max_stack_height: u32) -> UpgradeRequest {
+    let mut upgrade_point = ChainSpec_UpgradePoint::new();
+    {
+        let mut activation_point = ChainSpec_ActivationPoint::new();
+        activation_point.set_rank(ACTIVATION_POINT_RANK);
+        upgrade_point.set_activation_point(activation_point);
+    }
+    {
+        let mut protocol_version = state::ProtocolVersion::new();
+        protocol_version.set_value(NEW_PROTOCOL_VERSION);
+        upgrade_point.set_protocol_version(protocol_version);
+    }
+    {
+        let wasm_costs = WasmCosts {
+            max_stack_height,
+            ..WasmCosts::default()
+        };
+        let mut cost_table = ChainSpec_CostTable::new();
+        cost_table.set_wasm(wasm_costs.into());
+        upgrade_point.set_new_costs(cost_table);
+    }
+
+    let mut upgrade_request = UpgradeRequest::new();
+    upgrade_request.set_upgrade_point(upgrade_point);
+    {
+        let mut protocol_version = state::ProtocolVersion::new();
+        protocol_version.set_value(PROTOCOL_VERSION);
+        upgrade_request.set_protocol_version(protocol_version);
+    }
+    upgrade_request
+}
+
+#[ignore]
+#[test]
+fn should_allow_recursion_within_stack_height_limit() {
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(create_exec_request(1_000, PROTOCOL_VERSION))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_trap_on_recursion_exceeding_stack_height_limit() {
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(create_exec_request(1_000_000, PROTOCOL_VERSION))
+        .expect_error_contains(STACK_LIMITER_TRAP)
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_take_stack_height_limit_from_wasm_costs() {
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .upgrade_with_upgrade_request(create_upgrade_request(4 * 1024))
+        .exec_with_exec_request(create_exec_request(1_000, NEW_PROTOCOL_VERSION))
+        .expect_error_contains(STACK_LIMITER_TRAP)
+        .commit();
+}
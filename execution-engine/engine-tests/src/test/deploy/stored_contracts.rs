@@ -4,14 +4,14 @@ use std::collections::{BTreeMap, HashMap};
 use contract_ffi::key::Key;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::{Value, U512};
-use engine_core::engine_state::{EngineConfig, CONV_RATE};
+use engine_core::engine_state::{EngineConfig, CONV_RATE, DEFAULT_NATIVE_TRANSFER_COST};
 use engine_shared::transform::Transform;
 
 use crate::support::test_stored_contract_support::{
     DeployBuilder, Diff, ExecRequestBuilder, WasmTestBuilder, WasmTestResult,
     GENESIS_INITIAL_BALANCE,
 };
-use engine_grpc_server::engine_server::ipc::ExecuteRequest;
+use engine_grpc_server::engine_server::ipc::{DeployError_ErrorCode, ExecuteRequest};
 
 use crate::support::test_stored_contract_support;
 use crate::support::test_support;
@@ -932,3 +932,118 @@ fn should_have_equivalent_transforms_with_stored_contract_pointers() {
         }
     }
 }
+
+#[ignore]
+#[test]
+fn should_exec_native_transfer_without_payloads() {
+    let genesis_addr = GENESIS_ADDR;
+    let genesis_public_key = PublicKey::new(genesis_addr);
+    let transferred_amount = 1;
+
+    let engine_config = EngineConfig::new().set_use_payment_code(true);
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(genesis_addr)
+            .with_native_transfer(
+                None,
+                genesis_public_key,
+                U512::from(transferred_amount),
+                None,
+            )
+            .with_authorization_keys(&[genesis_public_key])
+            .with_deploy_hash([1; 32])
+            .build();
+
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = WasmTestBuilder::new(engine_config);
+    builder.run_genesis(genesis_addr, HashMap::default());
+
+    let _test_result = get_test_result(&mut builder, exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response")
+        .clone();
+    let cost = test_stored_contract_support::get_success_result(&response).cost;
+    assert_eq!(cost, DEFAULT_NATIVE_TRANSFER_COST);
+
+    let genesis_account = builder
+        .get_account(Key::Account(genesis_addr))
+        .expect("should get genesis account");
+    let modified_balance: U512 = builder.get_purse_balance(genesis_account.purse_id());
+    let expected_balance =
+        U512::from(GENESIS_INITIAL_BALANCE) - U512::from(cost) * U512::from(CONV_RATE);
+
+    assert_eq!(
+        modified_balance, expected_balance,
+        "only the native transfer fee should be charged"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_stored_session_with_malformed_args_bytes() {
+    let genesis_addr = GENESIS_ADDR;
+    let genesis_public_key = PublicKey::new(genesis_addr);
+    let payment_purse_amount = 100_000_000;
+
+    let engine_config = EngineConfig::new().set_use_payment_code(true);
+
+    // first, store transfer contract
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(genesis_addr)
+            .with_session_code(
+                &format!("{}_stored.wasm", TRANSFER_PURSE_TO_ACCOUNT_CONTRACT_NAME),
+                (),
+            )
+            .with_payment_code(
+                &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
+                (U512::from(payment_purse_amount),),
+            )
+            .with_authorization_keys(&[genesis_public_key])
+            .with_deploy_hash([1; 32])
+            .build();
+
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = WasmTestBuilder::new(engine_config);
+    builder.run_genesis(genesis_addr, HashMap::default());
+
+    let _test_result = get_test_result(&mut builder, exec_request);
+
+    // the args claim to hold one argument, but end before its length
+    let exec_request_stored_session = {
+        let deploy = DeployBuilder::new()
+            .with_address(genesis_addr)
+            .with_stored_session_named_key(TRANSFER_PURSE_TO_ACCOUNT_CONTRACT_NAME, ())
+            .with_session_args_bytes(&[1, 0, 0, 0])
+            .with_payment_code(
+                &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
+                (U512::from(payment_purse_amount),),
+            )
+            .with_authorization_keys(&[genesis_public_key])
+            .with_deploy_hash([2; 32])
+            .build();
+
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder
+        .exec_with_exec_request(exec_request_stored_session)
+        .commit();
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response")
+        .clone();
+    let exec_error = test_stored_contract_support::get_success_result(&response)
+        .get_error()
+        .get_exec_error()
+        .to_owned();
+    assert_eq!(exec_error.get_code(), DeployError_ErrorCode::SERIALIZATION);
+}
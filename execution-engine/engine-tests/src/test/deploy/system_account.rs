@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use contract_ffi::value::account::{PublicKey, PurseId};
+use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT, SYSTEM_ACCOUNT_ADDR};
+use engine_grpc_server::engine_server::ipc::ExecRequest;
+
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME,
+    STANDARD_PAYMENT_CONTRACT,
+};
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [42u8; 32];
+const SYSTEM_ACCOUNT_DEPLOY_MESSAGE: &str = "Deploys cannot be executed as the system account";
+
+fn system_account_exec_request(session_code: &str, deploy_hash: [u8; 32]) -> ExecRequest {
+    let deploy = DeployBuilder::new()
+        .with_address(SYSTEM_ACCOUNT_ADDR)
+        .with_deploy_hash(deploy_hash)
+        .with_session_code(session_code, ())
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(SYSTEM_ACCOUNT_ADDR)])
+        .build();
+
+    ExecRequestBuilder::new().push_deploy(deploy).build()
+}
+
+/// Funds the system account so that its deploys would otherwise be able to pay for themselves.
+fn initialize(engine_config: EngineConfig) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config);
+
+    builder
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "transfer_purse_to_account.wasm",
+            (SYSTEM_ACCOUNT_ADDR, U512::from(MAX_PAYMENT)),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .expect_success()
+        .commit();
+
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_system_account_deploy() {
+    let exec_request = system_account_exec_request("do_nothing.wasm", [2; 32]);
+
+    let mut builder = initialize(EngineConfig::new());
+    builder.exec_with_exec_request(exec_request);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response")
+        .clone();
+
+    let precondition_failure = test_support::get_precondition_failure(&response);
+
+    assert_eq!(precondition_failure.message, SYSTEM_ACCOUNT_DEPLOY_MESSAGE);
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_finalize_payment_to_be_spoofed_as_system_account() {
+    let refund_purse: Option<PurseId> = None;
+    let args = (
+        U512::from(MAX_PAYMENT),
+        refund_purse,
+        Some(U512::from(MAX_PAYMENT)),
+        Some(ACCOUNT_1_ADDR),
+    );
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(SYSTEM_ACCOUNT_ADDR)
+            .with_deploy_hash([2; 32])
+            .with_session_code("pos_finalize_payment.wasm", args)
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(MAX_PAYMENT),))
+            .with_authorization_keys(&[PublicKey::new(SYSTEM_ACCOUNT_ADDR)])
+            .build();
+
+        ExecRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = initialize(EngineConfig::new());
+    let pre_state_hash = builder.get_post_state_hash();
+    builder.exec_with_exec_request(exec_request);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response")
+        .clone();
+
+    let precondition_failure = test_support::get_precondition_failure(&response);
+
+    assert_eq!(precondition_failure.message, SYSTEM_ACCOUNT_DEPLOY_MESSAGE);
+    assert_eq!(builder.get_post_state_hash(), pre_state_hash);
+}
+
+#[ignore]
+#[test]
+fn should_allow_system_account_deploy_when_unrestricted() {
+    let exec_request = system_account_exec_request("do_nothing.wasm", [2; 32]);
+
+    initialize(EngineConfig::new().set_restrict_system_account(false))
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder,
+};
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::error::Error;
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_grpc_server::engine_server::ipc::ExecRequest;
+
+const GENESIS_ADDR: [u8; 32] = [12; 32];
+const STANDARD_PAYMENT_WASM: &str = "standard_payment.wasm";
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const DEPLOY_TIMESTAMP: u64 = 1_000;
+const TTL_MILLIS: u32 = 500;
+/// A block time in milliseconds since the Unix epoch, long after any time to live could last.
+const CURRENT_BLOCK_TIME: u64 = 1_570_000_000_000;
+
+fn create_exec_request(ttl_millis: u32, block_time: u64) -> ExecRequest {
+    let deploy = DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_timestamp(DEPLOY_TIMESTAMP)
+        .with_ttl_millis(ttl_millis)
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build();
+
+    ExecRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_block_time(block_time)
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_within_ttl() {
+    let block_time = DEPLOY_TIMESTAMP + u64::from(TTL_MILLIS);
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(create_exec_request(TTL_MILLIS, block_time))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_without_ttl() {
+    let block_time = DEPLOY_TIMESTAMP + u64::from(TTL_MILLIS) + 1;
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(create_exec_request(0, block_time))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_without_timestamp_or_ttl_at_current_block_time() {
+    // Deploys which leave both unset, like the node's, never expire whatever the block time.
+    let deploy = DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_payment_code(STANDARD_PAYMENT_WASM, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build();
+    let exec_request = ExecRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_block_time(CURRENT_BLOCK_TIME)
+        .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(exec_request)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_failure_for_expired_deploy() {
+    let expired_at = DEPLOY_TIMESTAMP + u64::from(TTL_MILLIS);
+    let block_time = expired_at + 1;
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(GENESIS_ADDR, HashMap::new())
+        .exec_with_exec_request(create_exec_request(TTL_MILLIS, block_time))
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("there should be a response");
+
+    let precondition_failure = test_support::get_precondition_failure(response);
+
+    assert_eq!(
+        precondition_failure.message,
+        Error::DeployExpired {
+            expired_at,
+            blocktime: block_time
+        }
+        .to_string()
+    );
+}
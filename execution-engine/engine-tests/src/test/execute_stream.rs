@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use grpc::RequestOptions;
+
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, EngineState, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::{
+    DeployItem, DeployResult, ExecuteRequest, ExecuteStreamResponse,
+    ExecuteStreamResponse_oneof_result,
+};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_grpc_server::engine_server::limited_service::LimitedService;
+use engine_grpc_server::engine_server::server_config::ServerConfig;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+
+use crate::support::test_stored_contract_support::{self, DeployBuilder, ExecRequestBuilder};
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+
+fn transfer_deploy(deploy_hash: [u8; 32]) -> DeployItem {
+    DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash(deploy_hash)
+        .with_session_code(
+            "transfer_purse_to_account.wasm",
+            (PublicKey::new(ACCOUNT_1_ADDR), U512::from(TRANSFER_AMOUNT)),
+        )
+        .with_payment_code("standard_payment.wasm", (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build()
+}
+
+fn execute_request(parent_state_hash: &[u8]) -> ExecuteRequest {
+    ExecRequestBuilder::new()
+        .push_deploy(transfer_deploy([1; 32]))
+        .push_deploy(transfer_deploy([2; 32]))
+        .with_pre_state_hash(parent_state_hash)
+        .build()
+}
+
+type Service = LimitedService<EngineState<InMemoryGlobalState>>;
+
+/// Returns the service streamed exec requests are served through, along with the post state hash
+/// of its genesis.
+fn service_after_genesis() -> (Service, Vec<u8>) {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let engine_state = EngineState::new(global_state, EngineConfig::new());
+    let service = LimitedService::new(engine_state, &ServerConfig::new());
+
+    let (genesis_request, _) =
+        test_stored_contract_support::create_genesis_request(GENESIS_ADDR, HashMap::new());
+    let genesis_response = service
+        .run_genesis(RequestOptions::new(), genesis_request)
+        .wait_drop_metadata()
+        .expect("should run genesis");
+    let post_state_hash = genesis_response.get_success().get_poststate_hash().to_vec();
+    (service, post_state_hash)
+}
+
+fn streamed_deploy_result(response: &ExecuteStreamResponse) -> &DeployResult {
+    match &response.result {
+        Some(ExecuteStreamResponse_oneof_result::deploy_result(deploy_result)) => deploy_result,
+        other => panic!("should be a deploy result, got: {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_stream_each_deploy_result_before_the_response() {
+    let (service, post_state_hash) = service_after_genesis();
+
+    let responses: Vec<ExecuteStreamResponse> = service
+        .execute_stream(RequestOptions::new(), execute_request(&post_state_hash))
+        .wait_drop_metadata()
+        .collect::<Result<_, _>>()
+        .expect("should execute");
+
+    assert_eq!(responses.len(), 3);
+    let done = responses[2].get_done();
+    assert!(done.has_success(), "{:?}", done);
+    let deploy_results = done.get_success().get_deploy_results();
+    assert_eq!(deploy_results.len(), 2);
+    for (response, deploy_result) in responses.iter().zip(deploy_results) {
+        assert_eq!(streamed_deploy_result(response), deploy_result);
+    }
+}
+
+#[ignore]
+#[test]
+fn should_stream_first_deploy_result_before_the_request_finishes() {
+    let (service, post_state_hash) = service_after_genesis();
+
+    let mut responses = service
+        .execute_stream(RequestOptions::new(), execute_request(&post_state_hash))
+        .wait_drop_metadata();
+
+    let first = responses
+        .next()
+        .expect("should stream a response")
+        .expect("should execute");
+    streamed_deploy_result(&first);
+    // The request keeps its exec permit until the response following the second deploy result
+    // is sent, which waits for that deploy result to be received
+    assert_eq!(service.exec_permits().in_flight(), 1);
+
+    let rest: Vec<ExecuteStreamResponse> =
+        responses.collect::<Result<_, _>>().expect("should execute");
+    assert_eq!(rest.len(), 2);
+    streamed_deploy_result(&rest[0]);
+    assert!(rest[1].get_done().has_success(), "{:?}", rest[1]);
+}
+
+#[ignore]
+#[test]
+fn should_stream_missing_parent() {
+    let (service, _) = service_after_genesis();
+
+    let responses: Vec<ExecuteStreamResponse> = service
+        .execute_stream(RequestOptions::new(), execute_request(&[0u8; 32]))
+        .wait_drop_metadata()
+        .collect::<Result<_, _>>()
+        .expect("should execute");
+
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].get_done().has_missing_parent());
+}
+
+#[ignore]
+#[test]
+fn should_not_stream_without_limited_service() {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let engine_state = EngineState::new(global_state, EngineConfig::new());
+
+    let result: Result<Vec<ExecuteStreamResponse>, _> = engine_state
+        .execute_stream(RequestOptions::new(), execute_request(&[0u8; 32]))
+        .wait_drop_metadata()
+        .collect();
+
+    assert!(result.is_err());
+}
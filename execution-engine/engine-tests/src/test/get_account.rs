@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use grpc::RequestOptions;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::Account;
+use engine_grpc_server::engine_server::ipc::{GetAccountRequest, GetAccountResponse};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+
+use crate::support::test_support::InMemoryWasmTestBuilder;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const UNKNOWN_ADDR: [u8; 32] = [42u8; 32];
+
+fn get_account(
+    builder: &InMemoryWasmTestBuilder,
+    state_hash: Vec<u8>,
+    account_address: Vec<u8>,
+) -> GetAccountResponse {
+    let mut get_account_request = GetAccountRequest::new();
+    get_account_request.set_state_hash(state_hash);
+    get_account_request.set_account_address(account_address);
+    builder
+        .get_engine_state()
+        .get_account(RequestOptions::new(), get_account_request)
+        .wait_drop_metadata()
+        .expect("should get account")
+}
+
+#[ignore]
+#[test]
+fn should_get_decoded_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let response = get_account(
+        &builder,
+        builder.get_post_state_hash(),
+        GENESIS_ADDR.to_vec(),
+    );
+
+    assert!(
+        response.has_account(),
+        "expected account, got {:?}",
+        response
+    );
+    let account = Account::try_from(response.get_account()).expect("should parse account");
+    let expected_account = builder
+        .get_account(Key::Account(GENESIS_ADDR))
+        .expect("should have genesis account");
+    assert_eq!(account, expected_account);
+}
+
+#[ignore]
+#[test]
+fn should_not_get_missing_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let response = get_account(
+        &builder,
+        builder.get_post_state_hash(),
+        UNKNOWN_ADDR.to_vec(),
+    );
+
+    assert!(
+        response.has_missing_account(),
+        "expected missing account, got {:?}",
+        response
+    );
+    assert_eq!(
+        response.get_missing_account().get_account_address(),
+        UNKNOWN_ADDR
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_get_account_on_missing_state() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let missing_hash = vec![0u8; 32];
+
+    let response = get_account(&builder, missing_hash.clone(), GENESIS_ADDR.to_vec());
+
+    assert!(
+        response.has_missing_state(),
+        "expected missing state, got {:?}",
+        response
+    );
+    assert_eq!(
+        response.get_missing_state().get_hash(),
+        missing_hash.as_slice()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_get_account_with_invalid_address() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let response = get_account(&builder, builder.get_post_state_hash(), vec![1u8; 3]);
+
+    assert!(
+        response.has_failure(),
+        "expected failure, got {:?}",
+        response
+    );
+}
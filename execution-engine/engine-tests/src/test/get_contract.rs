@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+
+use grpc::RequestOptions;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::{Contract, Value};
+use engine_grpc_server::engine_server::ipc::{GetContractRequest, GetContractResponse};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::support::test_support::InMemoryWasmTestBuilder;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+
+fn get_contract(
+    builder: &InMemoryWasmTestBuilder,
+    state_hash: Vec<u8>,
+    contract_key: Key,
+    include_body: bool,
+) -> GetContractResponse {
+    let mut get_contract_request = GetContractRequest::new();
+    get_contract_request.set_state_hash(state_hash);
+    get_contract_request.set_contract_key((&contract_key).into());
+    get_contract_request.set_include_body(include_body);
+    builder
+        .get_engine_state()
+        .get_contract(RequestOptions::new(), get_contract_request)
+        .wait_drop_metadata()
+        .expect("should get contract")
+}
+
+fn mint_contract(builder: &InMemoryWasmTestBuilder) -> (Key, Contract) {
+    let mint_key = Key::URef(builder.get_mint_contract_uref());
+    match builder.query(None, mint_key, &[]) {
+        Some(Value::Contract(contract)) => (mint_key, contract),
+        other => panic!("expected mint contract, got {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_get_contract_without_body() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let (mint_key, mint_contract) = mint_contract(&builder);
+
+    let response = get_contract(&builder, builder.get_post_state_hash(), mint_key, false);
+
+    assert!(
+        response.has_contract(),
+        "expected contract, got {:?}",
+        response
+    );
+    let contract_info = response.get_contract();
+    let named_keys: BTreeMap<String, Key> = contract_info
+        .get_named_keys()
+        .iter()
+        .map(|named_key| {
+            let key = Key::try_from(named_key.get_key()).expect("should parse key");
+            (named_key.get_name().to_string(), key)
+        })
+        .collect();
+    assert_eq!(&named_keys, mint_contract.urefs_lookup());
+    assert_eq!(
+        contract_info.get_protocol_version().get_value(),
+        mint_contract.protocol_version()
+    );
+    assert_eq!(
+        contract_info.get_body_hash(),
+        Blake2bHash::new(mint_contract.bytes()).to_vec().as_slice()
+    );
+    assert!(contract_info.get_body().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_get_contract_with_body() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let (mint_key, mint_contract) = mint_contract(&builder);
+
+    let response = get_contract(&builder, builder.get_post_state_hash(), mint_key, true);
+
+    assert!(
+        response.has_contract(),
+        "expected contract, got {:?}",
+        response
+    );
+    assert_eq!(response.get_contract().get_body(), mint_contract.bytes());
+}
+
+#[ignore]
+#[test]
+fn should_not_get_missing_contract() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let missing_key = Key::Hash([42u8; 32]);
+
+    let response = get_contract(&builder, builder.get_post_state_hash(), missing_key, false);
+
+    assert!(
+        response.has_missing_contract(),
+        "expected missing contract, got {:?}",
+        response
+    );
+    assert_eq!(
+        Key::try_from(response.get_missing_contract().get_contract_key())
+            .expect("should parse key"),
+        missing_key
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_get_account_as_contract() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let response = get_contract(
+        &builder,
+        builder.get_post_state_hash(),
+        Key::Account(GENESIS_ADDR),
+        false,
+    );
+
+    assert!(
+        response.has_failure(),
+        "expected failure, got {:?}",
+        response
+    );
+}
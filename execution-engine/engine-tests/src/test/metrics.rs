@@ -10,7 +10,7 @@ use engine_shared::test_utils;
 use engine_storage::global_state::in_memory::InMemoryGlobalState;
 
 use engine_grpc_server::engine_server::ipc::{
-    CommitRequest, Deploy, ExecRequest, QueryRequest, ValidateRequest,
+    CommitRequest, Deploy, ExecRequest, QueryRequest, ScrapeMetricsRequest, ValidateRequest,
 };
 use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
 use engine_grpc_server::engine_server::state::{Key, Key_Address};
@@ -235,3 +235,52 @@ fn should_validate_with_metrics() {
         assert_eq!(log_item.log_level, "Metric", "expected Metric");
     }
 }
+
+#[test]
+fn should_scrape_storage_metrics() {
+    let correlation_id = CorrelationId::new();
+    let mocked_account = test_utils::mocked_account(test_support::MOCKED_ACCOUNT_ADDRESS);
+    let (global_state, root_hash) =
+        InMemoryGlobalState::from_pairs(correlation_id, &mocked_account).unwrap();
+    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let engine_state = EngineState::new(global_state, engine_config);
+
+    let mut commit_request = CommitRequest::new();
+    commit_request.set_effects(vec![].into());
+    commit_request.set_prestate_hash(root_hash.to_vec());
+
+    let _commit_response_result = engine_state
+        .commit(RequestOptions::new(), commit_request)
+        .wait_drop_metadata();
+
+    let scrape_metrics_response = engine_state
+        .scrape_metrics(RequestOptions::new(), ScrapeMetricsRequest::new())
+        .wait_drop_metadata()
+        .expect("should scrape metrics");
+    let text = scrape_metrics_response.get_text();
+
+    for name in &[
+        "global_state_cache_hits_total",
+        "global_state_cache_misses_total",
+        "global_state_read_duration_seconds_count",
+        "global_state_write_duration_seconds_count",
+    ] {
+        assert!(
+            text.lines()
+                .any(|line| line.starts_with(&format!("{} ", name))),
+            "should have {} in {}",
+            name,
+            text
+        );
+    }
+
+    // Other tests run in the same process, so only a lower bound on the count is known.
+    let commit_count: u64 = text
+        .lines()
+        .find(|line| line.starts_with("global_state_commit_duration_seconds_count "))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .expect("should have commit count")
+        .parse()
+        .expect("should parse commit count");
+    assert!(commit_count >= 1, "should count the commit");
+}
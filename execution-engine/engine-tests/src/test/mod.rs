@@ -1,5 +1,29 @@
 #[cfg(test)]
+mod batch_query;
+#[cfg(test)]
+mod builder_snapshot;
+#[cfg(test)]
+mod execute_stream;
+#[cfg(test)]
+mod get_account;
+#[cfg(test)]
+mod get_balance;
+#[cfg(test)]
+mod get_contract;
+#[cfg(test)]
 mod metrics;
+#[cfg(test)]
+mod query_access_rights;
+#[cfg(test)]
+mod query_proofs;
+#[cfg(test)]
+mod replay;
+#[cfg(test)]
+mod speculative_exec;
+#[cfg(test)]
+mod state_diff;
+#[cfg(test)]
+mod trie_sync;
 
 #[cfg(test)]
 pub mod contract_api;
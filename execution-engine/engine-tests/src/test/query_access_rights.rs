@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use grpc::RequestOptions;
+
+use contract_ffi::key::Key;
+use engine_core::execution::{MINT_NAME, POS_NAME};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_grpc_server::engine_server::state::Key_URef_AccessRights;
+
+use crate::support::test_support::{self, InMemoryWasmTestBuilder};
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+
+fn query_access_rights(
+    builder: &InMemoryWasmTestBuilder,
+    base_key: Key,
+    path: &[&str],
+) -> Key_URef_AccessRights {
+    let query_request = test_support::create_query_request(
+        builder.get_post_state_hash(),
+        &base_key,
+        path.iter().map(|name| name.to_string()).collect(),
+    );
+
+    let query_response = builder
+        .get_engine_state()
+        .query(RequestOptions::new(), query_request)
+        .wait_drop_metadata()
+        .expect("should query");
+
+    assert!(query_response.has_success(), "{:?}", query_response);
+    query_response.get_access_rights()
+}
+
+#[ignore]
+#[test]
+fn should_return_access_rights_held_at_end_of_path() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    // Genesis accounts only hold attenuated references to the system contracts
+    for name in &[MINT_NAME, POS_NAME] {
+        assert_eq!(
+            query_access_rights(&builder, Key::Account(GENESIS_ADDR), &[name]),
+            Key_URef_AccessRights::READ
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_return_access_rights_for_value_not_under_uref() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    assert_eq!(
+        query_access_rights(&builder, Key::Account(GENESIS_ADDR), &[]),
+        Key_URef_AccessRights::UNKNOWN
+    );
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use grpc::RequestOptions;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::Value;
+use engine_core::execution::MINT_NAME;
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+
+use crate::support::test_support::{self, InMemoryWasmTestBuilder};
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+
+#[ignore]
+#[test]
+fn should_get_verifiable_proofs_for_query_path() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let post_state_hash = Blake2bHash::try_from(builder.get_post_state_hash().as_slice())
+        .expect("should have valid post state hash");
+
+    let proofs = builder
+        .get_engine_state()
+        .get_query_proofs(
+            CorrelationId::new(),
+            post_state_hash,
+            Key::Account(GENESIS_ADDR),
+            &[MINT_NAME.to_string()],
+        )
+        .expect("should get proofs")
+        .expect("should find post state hash");
+
+    assert_eq!(proofs.len(), 2);
+
+    match proofs[0].value() {
+        Value::Account(account) => assert_eq!(account.pub_key(), GENESIS_ADDR),
+        other => panic!("expected account, got {:?}", other),
+    }
+
+    for proof in proofs.iter() {
+        assert!(proof
+            .verify(&post_state_hash)
+            .expect("should serialize proof"));
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_get_proofs_of_missing_state() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let missing_state_hash: Blake2bHash = [0u8; 32].into();
+
+    let maybe_proofs = builder
+        .get_engine_state()
+        .get_query_proofs(
+            CorrelationId::new(),
+            missing_state_hash,
+            Key::Account(GENESIS_ADDR),
+            &[],
+        )
+        .expect("should not fail");
+
+    assert!(maybe_proofs.is_none());
+}
+
+#[ignore]
+#[test]
+fn should_include_proofs_in_query_response_when_requested() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let post_state_hash = builder.get_post_state_hash();
+
+    let query_request = {
+        let mut tmp = test_support::create_query_request(
+            post_state_hash.clone(),
+            &Key::Account(GENESIS_ADDR),
+            vec![MINT_NAME.to_string()],
+        );
+        tmp.set_include_proofs(true);
+        tmp
+    };
+
+    let query_response = builder
+        .get_engine_state()
+        .query(RequestOptions::new(), query_request)
+        .wait_drop_metadata()
+        .expect("should query");
+
+    assert!(query_response.has_success(), "{:?}", query_response);
+
+    let proofs = query_response.get_proofs();
+    assert_eq!(proofs.len(), 2);
+
+    // The first serialized trie value of each proof is the root of the state
+    for proof in proofs {
+        let root_bytes = proof.get_path().first().expect("should have a path");
+        assert_eq!(Blake2bHash::new(root_bytes).to_vec(), post_state_hash);
+    }
+}
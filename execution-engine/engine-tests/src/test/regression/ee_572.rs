@@ -11,7 +11,7 @@ use crate::support::test_support::{
 
 const CREATE: &str = "create";
 
-const GENESIS_ADDR: [u8; 32] = [0u8; 32];
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
 const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
 const ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
 const INITIAL_AMOUNT: u32 = 100_500_000;
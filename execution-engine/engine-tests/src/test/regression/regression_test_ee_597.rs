@@ -7,6 +7,7 @@ extern crate grpc;
 
 use std::collections::HashMap;
 
+use contract_ffi::system_contracts::error::PosError;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
 
@@ -49,6 +50,8 @@ fn should_fail_when_bonding_amount_is_zero_ee_597_regression() {
         let execution_result = test_support::get_success_result(&response);
         test_support::get_error_message(execution_result)
     };
-    // Error::BondTooSmall => 9,
-    assert_eq!(error_message, "Exit code: 9");
+    assert_eq!(
+        error_message,
+        test_support::pos_error_message(PosError::BondTooSmall)
+    );
 }
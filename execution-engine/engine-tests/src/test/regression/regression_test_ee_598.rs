@@ -7,6 +7,7 @@ extern crate grpc;
 
 use std::collections::HashMap;
 
+use contract_ffi::system_contracts::error::PosError;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
 
@@ -65,6 +66,8 @@ fn should_fail_unboding_more_than_it_was_staked_ee_598_regression() {
         let execution_result = test_support::get_success_result(&response);
         test_support::get_error_message(execution_result)
     };
-    // Error::UnbondTooLarge => 6,
-    assert_eq!(error_message, "Exit code: 6");
+    assert_eq!(
+        error_message,
+        test_support::pos_error_message(PosError::UnbondTooLarge)
+    );
 }
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use grpc::RequestOptions;
+use protobuf::{Message, RepeatedField};
+
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_grpc_server::engine_server::ipc::{
+    ExecuteRequest, ExecutionEffect, ReplayRequest, ReplayResponse,
+};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+
+use crate::support::test_stored_contract_support::{self, DeployBuilder};
+use crate::support::test_support::InMemoryWasmTestBuilder;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+const DEPLOY_HASH: [u8; 32] = [1u8; 32];
+
+fn execute_request() -> ExecuteRequest {
+    let deploy = DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash(DEPLOY_HASH)
+        .with_session_code(
+            "transfer_purse_to_account.wasm",
+            (PublicKey::new(ACCOUNT_1_ADDR), U512::from(TRANSFER_AMOUNT)),
+        )
+        .with_payment_code("standard_payment.wasm", (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build();
+
+    let mut execute_request = ExecuteRequest::new();
+    execute_request.set_deploys(RepeatedField::from_vec(vec![deploy]));
+    execute_request.set_protocol_version(test_stored_contract_support::get_protocol_version());
+    execute_request
+}
+
+/// Executes the transfer on top of `prestate_hash`, returning the effects to replay it against.
+fn recorded_effects(
+    builder: &InMemoryWasmTestBuilder,
+    prestate_hash: Vec<u8>,
+) -> Vec<ExecutionEffect> {
+    let mut execute_request = execute_request();
+    execute_request.set_parent_state_hash(prestate_hash);
+    let execute_response = builder
+        .get_engine_state()
+        .execute(RequestOptions::new(), execute_request)
+        .wait_drop_metadata()
+        .expect("should execute");
+    execute_response
+        .get_success()
+        .get_deploy_results()
+        .iter()
+        .map(|deploy_result| deploy_result.get_execution_result().get_effects().clone())
+        .collect()
+}
+
+fn replay(
+    builder: &InMemoryWasmTestBuilder,
+    prestate_hash: Vec<u8>,
+    recorded_effects: Vec<ExecutionEffect>,
+) -> ReplayResponse {
+    let mut replay_request = ReplayRequest::new();
+    replay_request.set_prestate_hash(prestate_hash);
+    replay_request.set_execute_request(
+        execute_request()
+            .write_to_bytes()
+            .expect("should serialize execute request"),
+    );
+    replay_request.set_recorded_effects(RepeatedField::from_vec(recorded_effects));
+
+    builder
+        .get_engine_state()
+        .replay(RequestOptions::new(), replay_request)
+        .wait_drop_metadata()
+        .expect("should replay")
+}
+
+#[ignore]
+#[test]
+fn should_reproduce_recorded_effects() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let prestate_hash = builder.get_post_state_hash();
+    let recorded_effects = recorded_effects(&builder, prestate_hash.clone());
+
+    let response = replay(&builder, prestate_hash.clone(), recorded_effects);
+
+    assert!(response.has_success(), "{:?}", response);
+    let result = response.get_success();
+    assert_eq!(result.get_recorded_count(), 1);
+    assert_eq!(result.get_replayed_count(), 1);
+    assert!(result.get_divergences().is_empty(), "{:?}", result);
+    assert_eq!(builder.get_post_state_hash(), prestate_hash);
+}
+
+#[ignore]
+#[test]
+fn should_report_diverging_transforms() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let prestate_hash = builder.get_post_state_hash();
+    let mut recorded_effects = recorded_effects(&builder, prestate_hash.clone());
+    let dropped_entry = recorded_effects[0].mut_transform_map().pop().unwrap();
+
+    let response = replay(&builder, prestate_hash, recorded_effects);
+
+    assert!(response.has_success(), "{:?}", response);
+    let divergences = response.get_success().get_divergences();
+    assert_eq!(divergences.len(), 1);
+    assert_eq!(divergences[0].get_deploy_index(), 0);
+    assert_eq!(divergences[0].get_deploy_hash(), DEPLOY_HASH);
+    let transforms = divergences[0].get_transforms();
+    assert_eq!(transforms.len(), 1);
+    assert_eq!(transforms[0].get_key(), dropped_entry.get_key());
+    assert!(!transforms[0].has_recorded());
+    assert_eq!(transforms[0].get_replayed(), dropped_entry.get_transform());
+}
+
+#[ignore]
+#[test]
+fn should_not_replay_on_missing_prestate() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let missing_hash = vec![0u8; 32];
+
+    let response = replay(&builder, missing_hash.clone(), Vec::new());
+
+    assert!(response.has_missing_parent(), "{:?}", response);
+    assert_eq!(
+        response.get_missing_parent().get_hash(),
+        missing_hash.as_slice()
+    );
+}
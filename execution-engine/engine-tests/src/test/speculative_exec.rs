@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use grpc::RequestOptions;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_grpc_server::engine_server::ipc::{
+    DeployItem, SpeculativeExecRequest, SpeculativeExecResponse,
+};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_grpc_server::engine_server::state::ProtocolVersion;
+
+use crate::support::test_stored_contract_support::{self, DeployBuilder};
+use crate::support::test_support::InMemoryWasmTestBuilder;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+
+fn transfer_deploy() -> DeployItem {
+    DeployBuilder::new()
+        .with_address(GENESIS_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_session_code(
+            "transfer_purse_to_account.wasm",
+            (PublicKey::new(ACCOUNT_1_ADDR), U512::from(TRANSFER_AMOUNT)),
+        )
+        .with_payment_code("standard_payment.wasm", (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(GENESIS_ADDR)])
+        .build()
+}
+
+fn speculative_exec(
+    builder: &InMemoryWasmTestBuilder,
+    parent_state_hash: Vec<u8>,
+) -> SpeculativeExecResponse {
+    speculative_exec_with_protocol_version(
+        builder,
+        parent_state_hash,
+        test_stored_contract_support::get_protocol_version(),
+    )
+}
+
+fn speculative_exec_with_protocol_version(
+    builder: &InMemoryWasmTestBuilder,
+    parent_state_hash: Vec<u8>,
+    protocol_version: ProtocolVersion,
+) -> SpeculativeExecResponse {
+    let mut speculative_exec_request = SpeculativeExecRequest::new();
+    speculative_exec_request.set_parent_state_hash(parent_state_hash);
+    speculative_exec_request.set_deploy(transfer_deploy());
+    speculative_exec_request.set_protocol_version(protocol_version);
+
+    builder
+        .get_engine_state()
+        .speculative_exec(RequestOptions::new(), speculative_exec_request)
+        .wait_drop_metadata()
+        .expect("should execute speculatively")
+}
+
+#[ignore]
+#[test]
+fn should_return_cost_and_effects_without_committing() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let post_state_hash = builder.get_post_state_hash();
+
+    let response = speculative_exec(&builder, post_state_hash.clone());
+
+    assert!(response.has_success(), "{:?}", response);
+    let execution_result = response.get_success().get_execution_result();
+    assert!(!execution_result.has_error());
+    assert!(execution_result.get_cost() > 0);
+    assert!(!execution_result
+        .get_effects()
+        .get_transform_map()
+        .is_empty());
+
+    assert_eq!(builder.get_post_state_hash(), post_state_hash);
+    assert!(builder
+        .query(None, Key::Account(ACCOUNT_1_ADDR), &[])
+        .is_none());
+}
+
+#[ignore]
+#[test]
+fn should_return_same_result_when_run_twice() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let post_state_hash = builder.get_post_state_hash();
+
+    let first = speculative_exec(&builder, post_state_hash.clone());
+    let second = speculative_exec(&builder, post_state_hash);
+
+    assert_eq!(first, second);
+}
+
+#[ignore]
+#[test]
+fn should_return_missing_parent_for_unknown_state_hash() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let response = speculative_exec(&builder, vec![255; 32]);
+
+    assert!(response.has_missing_parent(), "{:?}", response);
+    assert_eq!(response.get_missing_parent().get_hash(), &[255; 32][..]);
+}
+
+#[ignore]
+#[test]
+fn should_return_precondition_failure_for_unknown_protocol_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let post_state_hash = builder.get_post_state_hash();
+    let mut protocol_version = ProtocolVersion::new();
+    protocol_version.set_value(u64::max_value());
+
+    let response =
+        speculative_exec_with_protocol_version(&builder, post_state_hash, protocol_version);
+
+    assert!(response.has_success(), "{:?}", response);
+    assert!(response.get_success().has_precondition_failure());
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use grpc::RequestOptions;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::MAX_PAYMENT;
+use engine_grpc_server::engine_server::ipc::{StateDiffRequest, StateDiffResponse};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+
+use crate::support::test_support::InMemoryWasmTestBuilder;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+
+fn state_diff(
+    builder: &InMemoryWasmTestBuilder,
+    prestate_hash: Vec<u8>,
+    poststate_hash: Vec<u8>,
+) -> StateDiffResponse {
+    let mut state_diff_request = StateDiffRequest::new();
+    state_diff_request.set_prestate_hash(prestate_hash);
+    state_diff_request.set_poststate_hash(poststate_hash);
+    builder
+        .get_engine_state()
+        .state_diff(RequestOptions::new(), state_diff_request)
+        .wait_drop_metadata()
+        .expect("should diff states")
+}
+
+#[ignore]
+#[test]
+fn should_diff_states_before_and_after_commit() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let prestate_hash = builder.get_post_state_hash();
+
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            "standard_payment.wasm",
+            (U512::from(MAX_PAYMENT),),
+            "transfer_purse_to_account.wasm",
+            (PublicKey::new(ACCOUNT_1_ADDR), U512::from(TRANSFER_AMOUNT)),
+            0,
+            [1u8; 32],
+        )
+        .expect_success()
+        .commit();
+    let poststate_hash = builder.get_post_state_hash();
+    let transforms = &builder.get_transforms()[0];
+
+    let state_diff_response = state_diff(&builder, prestate_hash, poststate_hash.clone());
+    assert!(
+        state_diff_response.has_success(),
+        "expected success, got {:?}",
+        state_diff_response
+    );
+    let keys = state_diff_response
+        .get_success()
+        .get_keys()
+        .iter()
+        .map(|key| Key::try_from(key).expect("should parse key"))
+        .collect::<Vec<Key>>();
+
+    assert!(keys.contains(&Key::Account(ACCOUNT_1_ADDR)));
+    for key in &keys {
+        assert!(
+            transforms.contains_key(key),
+            "{:?} should have been transformed by the commit",
+            key
+        );
+    }
+
+    let state_diff_response = state_diff(&builder, poststate_hash.clone(), poststate_hash);
+    assert!(state_diff_response.get_success().get_keys().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_not_diff_missing_state() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let missing_hash = vec![0u8; 32];
+
+    let state_diff_response = state_diff(
+        &builder,
+        builder.get_post_state_hash(),
+        missing_hash.clone(),
+    );
+
+    assert!(
+        state_diff_response.has_missing_state(),
+        "expected missing state, got {:?}",
+        state_diff_response
+    );
+    assert_eq!(
+        state_diff_response.get_missing_state().get_hash(),
+        missing_hash.as_slice()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_diff_invalid_state_hash() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+
+    let state_diff_response = state_diff(&builder, builder.get_post_state_hash(), vec![1u8; 3]);
+
+    assert!(
+        state_diff_response.has_failure(),
+        "expected failure, got {:?}",
+        state_diff_response
+    );
+}
@@ -1,19 +1,27 @@
 use std::collections::HashMap;
+use std::env;
+use std::fs;
 
 use grpc::RequestOptions;
 
 use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::RefundRatio;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::{Value, U512};
+use engine_core::engine_state::chainspec;
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
-use engine_core::engine_state::{EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR};
+use engine_core::engine_state::{
+    EngineConfig, EngineState, DEFAULT_MIN_GAS_PRICE, DEFAULT_NATIVE_TRANSFER_COST,
+    SYSTEM_ACCOUNT_ADDR,
+};
 use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_shared::key_limits::KeyLimits;
 use engine_shared::motes::Motes;
 use engine_storage::global_state::in_memory::InMemoryGlobalState;
 use engine_wasm_prep::wasm_costs::WasmCosts;
 
 use crate::support::test_support;
-use crate::support::test_support::InMemoryWasmTestBuilder;
+use crate::support::test_support::{GenesisConfigBuilder, InMemoryWasmTestBuilder};
 
 const MINT_INSTALL: &str = "mint_install.wasm";
 const POS_INSTALL: &str = "pos_install.wasm";
@@ -108,7 +116,7 @@ fn should_run_genesis_with_chainspec() {
     let mint_installer_bytes = test_support::read_wasm_file_bytes(MINT_INSTALL);
     let pos_installer_bytes = test_support::read_wasm_file_bytes(POS_INSTALL);
     let accounts = vec![account_1, account_2];
-    let wasm_costs = WasmCosts::from_version(PROTOCOL_VERSION).unwrap();
+    let wasm_costs = WasmCosts::default();
 
     let genesis_config = GenesisConfig::new(
         name,
@@ -118,6 +126,10 @@ fn should_run_genesis_with_chainspec() {
         pos_installer_bytes,
         accounts,
         wasm_costs,
+        RefundRatio::default(),
+        DEFAULT_MIN_GAS_PRICE,
+        DEFAULT_NATIVE_TRANSFER_COST,
+        KeyLimits::default(),
     );
 
     let mut builder = {
@@ -165,6 +177,103 @@ fn should_run_genesis_with_chainspec() {
     }
 }
 
+#[ignore]
+#[test]
+fn should_produce_same_genesis_regardless_of_account_order() {
+    let account_1 = GenesisAccount::new(
+        PublicKey::new(ACCOUNT_1_ADDR),
+        Motes::new(ACCOUNT_1_BALANCE.into()),
+        Motes::new(ACCOUNT_1_BONDED_AMOUNT.into()),
+    );
+    let account_2 = GenesisAccount::new(
+        PublicKey::new(ACCOUNT_2_ADDR),
+        Motes::new(ACCOUNT_2_BALANCE.into()),
+        Motes::new(ACCOUNT_2_BONDED_AMOUNT.into()),
+    );
+
+    let run_genesis = |accounts: Vec<GenesisAccount>| {
+        let genesis_config = GenesisConfig::new(
+            CHAIN_NAME.to_string(),
+            TIMESTAMP,
+            PROTOCOL_VERSION,
+            test_support::read_wasm_file_bytes(MINT_INSTALL),
+            test_support::read_wasm_file_bytes(POS_INSTALL),
+            accounts,
+            WasmCosts::default(),
+            RefundRatio::default(),
+            DEFAULT_MIN_GAS_PRICE,
+            DEFAULT_NATIVE_TRANSFER_COST,
+            KeyLimits::default(),
+        );
+        let mut builder = InMemoryWasmTestBuilder::new(EngineConfig::default());
+        builder
+            .run_genesis_with_genesis_config(genesis_config)
+            .expect("should run genesis");
+        builder.get_post_state_hash()
+    };
+
+    let post_state_hash = run_genesis(vec![account_1, account_2]);
+    let reversed_post_state_hash = run_genesis(vec![account_2, account_1]);
+    assert_eq!(post_state_hash, reversed_post_state_hash);
+
+    // Running genesis again from the same config yields the same post-state as well
+    assert_eq!(post_state_hash, run_genesis(vec![account_1, account_2]));
+}
+
+#[ignore]
+#[test]
+fn should_run_genesis_with_parsed_chainspec() {
+    let dir = tempfile::tempdir().expect("should create temp dir");
+    let wasm_dir = env::current_dir()
+        .expect("should get working directory")
+        .join(test_support::COMPILED_WASM_PATH);
+    let manifest = format!(
+        r#"
+[genesis]
+name = "{}"
+timestamp = {}
+protocol-version = {}
+mint-code-path = "{}"
+pos-code-path = "{}"
+initial-accounts-path = "accounts.csv"
+"#,
+        CHAIN_NAME,
+        TIMESTAMP,
+        PROTOCOL_VERSION,
+        wasm_dir.join(MINT_INSTALL).display(),
+        wasm_dir.join(POS_INSTALL).display(),
+    );
+    let accounts = format!(
+        "{},{},{},10\n{},{},0\n",
+        PublicKey::new(ACCOUNT_1_ADDR).to_hex(),
+        ACCOUNT_1_BALANCE,
+        ACCOUNT_1_BONDED_AMOUNT,
+        PublicKey::new(ACCOUNT_2_ADDR).to_hex(),
+        ACCOUNT_2_BALANCE,
+    );
+    let manifest_path = dir.path().join("chainspec.toml");
+    fs::write(&manifest_path, manifest).expect("should write manifest");
+    fs::write(dir.path().join("accounts.csv"), accounts).expect("should write accounts");
+
+    let genesis_config = chainspec::parse_chainspec(&manifest_path).expect("should parse");
+
+    let mut builder = {
+        let engine_config = EngineConfig::default().set_use_payment_code(true);
+        InMemoryWasmTestBuilder::new(engine_config)
+    };
+    builder
+        .run_genesis_with_genesis_config(genesis_config)
+        .expect("should run genesis");
+
+    let account_1 = builder
+        .get_account(Key::Account(ACCOUNT_1_ADDR))
+        .expect("account 1 should exist");
+    assert_eq!(
+        builder.get_purse_balance(account_1.purse_id()),
+        U512::from(ACCOUNT_1_BALANCE)
+    );
+}
+
 #[ignore]
 #[test]
 fn should_fail_if_bad_mint_install_contract_is_provided() {
@@ -193,7 +302,7 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
         let mint_installer_bytes = test_support::read_wasm_file_bytes(BAD_INSTALL);
         let pos_installer_bytes = test_support::read_wasm_file_bytes(POS_INSTALL);
         let accounts = vec![account_1, account_2];
-        let wasm_costs = WasmCosts::from_version(PROTOCOL_VERSION).unwrap();
+        let wasm_costs = WasmCosts::default();
         GenesisConfig::new(
             name,
             TIMESTAMP,
@@ -202,6 +311,10 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
             pos_installer_bytes,
             accounts,
             wasm_costs,
+            RefundRatio::default(),
+            DEFAULT_MIN_GAS_PRICE,
+            DEFAULT_NATIVE_TRANSFER_COST,
+            KeyLimits::default(),
         )
     };
 
@@ -246,7 +359,7 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
         let mint_installer_bytes = test_support::read_wasm_file_bytes(MINT_INSTALL);
         let pos_installer_bytes = test_support::read_wasm_file_bytes(BAD_INSTALL);
         let accounts = vec![account_1, account_2];
-        let wasm_costs = WasmCosts::from_version(PROTOCOL_VERSION).unwrap();
+        let wasm_costs = WasmCosts::default();
         GenesisConfig::new(
             name,
             TIMESTAMP,
@@ -255,6 +368,10 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
             pos_installer_bytes,
             accounts,
             wasm_costs,
+            RefundRatio::default(),
+            DEFAULT_MIN_GAS_PRICE,
+            DEFAULT_NATIVE_TRANSFER_COST,
+            KeyLimits::default(),
         )
     };
 
@@ -270,3 +387,35 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
         panic!("genesis should fail with a bad install contract")
     }
 }
+
+#[ignore]
+#[test]
+fn should_run_default_genesis_with_extra_validator() {
+    let validator_addr = [3u8; 32];
+    let genesis_config = GenesisConfigBuilder::new()
+        .push_validator(
+            PublicKey::new(validator_addr),
+            U512::from(ACCOUNT_2_BONDED_AMOUNT),
+        )
+        .build();
+    assert_ne!(genesis_config, *test_support::DEFAULT_GENESIS_CONFIG);
+
+    let mut builder = InMemoryWasmTestBuilder::new(EngineConfig::default());
+    builder
+        .run_genesis_with_genesis_config(genesis_config)
+        .expect("should run genesis");
+
+    for account_addr in &[
+        test_support::DEFAULT_ACCOUNT_1_ADDR,
+        test_support::DEFAULT_ACCOUNT_2_ADDR,
+        validator_addr,
+    ] {
+        let account = builder
+            .get_account(Key::Account(*account_addr))
+            .expect("account should exist");
+        assert_eq!(
+            builder.get_purse_balance(account.purse_id()),
+            U512::from(test_support::DEFAULT_ACCOUNT_INITIAL_BALANCE)
+        );
+    }
+}
@@ -3,9 +3,13 @@ mod genesis;
 #[cfg(test)]
 mod mint_install;
 #[cfg(test)]
+mod native_system_contracts;
+#[cfg(test)]
 mod pos_install;
 #[cfg(test)]
 mod system_contract_urefs_access_rights;
+#[cfg(test)]
+mod upgrade;
 
 #[cfg(test)]
 pub mod proof_of_stake;
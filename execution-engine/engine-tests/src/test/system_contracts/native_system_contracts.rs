@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use crate::support::test_support::{
+    DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, STANDARD_PAYMENT_CONTRACT,
+};
+use contract_ffi::contract_api::argsparser::ArgsParser;
+use contract_ffi::system_contracts::pos::FeeHandling;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::ExecRequest;
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
+const TRANSFER_AMOUNT: u64 = 100_000_000;
+
+fn session_request(
+    address: [u8; 32],
+    deploy_hash: [u8; 32],
+    session_file: &str,
+    session_args: impl ArgsParser,
+    payment_file: &str,
+    proposer: Option<PublicKey>,
+) -> ExecRequest {
+    let deploy = DeployBuilder::new()
+        .with_address(address)
+        .with_deploy_hash(deploy_hash)
+        .with_session_code(session_file, session_args)
+        .with_payment_code(payment_file, (U512::from(MAX_PAYMENT),))
+        .with_authorization_keys(&[PublicKey::new(address)])
+        .build();
+
+    let exec_request_builder = ExecRequestBuilder::new().push_deploy(deploy);
+    match proposer {
+        Some(proposer) => exec_request_builder.with_proposer(proposer).build(),
+        None => exec_request_builder.build(),
+    }
+}
+
+fn transfer_request(deploy_hash: [u8; 32], target: [u8; 32]) -> ExecRequest {
+    session_request(
+        GENESIS_ADDR,
+        deploy_hash,
+        "transfer_purse_to_account.wasm",
+        (PublicKey::new(target), U512::from(TRANSFER_AMOUNT)),
+        STANDARD_PAYMENT_CONTRACT,
+        None,
+    )
+}
+
+fn run(engine_config: EngineConfig, exec_requests: &[ExecRequest]) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config);
+    builder.run_genesis(GENESIS_ADDR, HashMap::default());
+    for exec_request in exec_requests {
+        builder
+            .exec_with_exec_request(exec_request.clone())
+            .expect_success()
+            .commit();
+    }
+    builder
+}
+
+/// Runs `exec_requests` once with the wasm system contracts and once with their host-side
+/// implementations, asserting that both produce the same effects.
+fn assert_native_matches_wasm(engine_config: EngineConfig, exec_requests: &[ExecRequest]) {
+    let engine_config = engine_config.set_use_payment_code(true);
+    let wasm_builder = run(
+        engine_config.clone().set_use_system_contracts(true),
+        exec_requests,
+    );
+    let native_builder = run(engine_config.set_use_system_contracts(false), exec_requests);
+
+    assert_eq!(
+        wasm_builder.get_genesis_hash(),
+        native_builder.get_genesis_hash()
+    );
+    assert_eq!(
+        wasm_builder.get_transforms(),
+        native_builder.get_transforms()
+    );
+    assert_eq!(
+        wasm_builder.get_post_state_hash(),
+        native_builder.get_post_state_hash()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_match_wasm_effects_of_transfer_to_new_account() {
+    assert_native_matches_wasm(
+        EngineConfig::new(),
+        &[transfer_request([1; 32], ACCOUNT_1_ADDR)],
+    );
+}
+
+#[ignore]
+#[test]
+fn should_match_wasm_effects_of_transfer_to_existing_account() {
+    assert_native_matches_wasm(
+        EngineConfig::new(),
+        &[
+            transfer_request([1; 32], ACCOUNT_1_ADDR),
+            transfer_request([2; 32], ACCOUNT_1_ADDR),
+            transfer_request([3; 32], ACCOUNT_2_ADDR),
+        ],
+    );
+}
+
+#[ignore]
+#[test]
+fn should_match_wasm_effects_of_purse_to_purse_transfer() {
+    let exec_request = session_request(
+        GENESIS_ADDR,
+        [1; 32],
+        "transfer_purse_to_purse.wasm",
+        (
+            "purse:main".to_string(),
+            "purse:secondary".to_string(),
+            U512::from(TRANSFER_AMOUNT),
+        ),
+        STANDARD_PAYMENT_CONTRACT,
+        None,
+    );
+    assert_native_matches_wasm(EngineConfig::new(), &[exec_request]);
+}
+
+#[ignore]
+#[test]
+fn should_match_wasm_effects_of_refund_purse() {
+    let exec_request = session_request(
+        GENESIS_ADDR,
+        [1; 32],
+        "do_nothing.wasm",
+        (),
+        "pos_refund_purse.wasm",
+        None,
+    );
+    assert_native_matches_wasm(EngineConfig::new(), &[exec_request]);
+}
+
+#[ignore]
+#[test]
+fn should_match_wasm_effects_of_burned_fees() {
+    assert_native_matches_wasm(
+        EngineConfig::new().set_fee_handling(FeeHandling::Burn),
+        &[transfer_request([1; 32], ACCOUNT_1_ADDR)],
+    );
+}
+
+#[ignore]
+#[test]
+fn should_match_wasm_effects_of_fees_paid_to_proposer() {
+    let exec_request = session_request(
+        GENESIS_ADDR,
+        [1; 32],
+        "transfer_purse_to_account.wasm",
+        (PublicKey::new(ACCOUNT_1_ADDR), U512::from(TRANSFER_AMOUNT)),
+        STANDARD_PAYMENT_CONTRACT,
+        Some(PublicKey::new(ACCOUNT_2_ADDR)),
+    );
+    assert_native_matches_wasm(
+        EngineConfig::new().set_fee_handling(FeeHandling::PayToProposer),
+        &[exec_request],
+    );
+}
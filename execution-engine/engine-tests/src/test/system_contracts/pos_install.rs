@@ -2,7 +2,12 @@ use crate::support::exec_with_return;
 use crate::support::test_support::{
     WasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
 };
+use contract_ffi::bytesrepr::ToBytes;
 use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::{
+    BondLimits, DelegationRate, DelegationRateLimits, DELEGATION_RATE_LIMITS_PREFIX,
+    VALIDATOR_BONDING_PURSE_PREFIX,
+};
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{PublicKey, PurseId};
 use contract_ffi::value::Value;
@@ -10,6 +15,7 @@ use contract_ffi::value::U512;
 use engine_core::engine_state::MAX_PAYMENT;
 use engine_shared::transform::Transform;
 use std::collections::{BTreeMap, HashMap};
+use std::iter;
 
 const GENESIS_ADDR: [u8; 32] = [7u8; 32];
 const SYSTEM_ADDR: [u8; 32] = [0u8; 32];
@@ -17,8 +23,20 @@ const DEPLOY_HASH_1: [u8; 32] = [1u8; 32];
 const DEPLOY_HASH_2: [u8; 32] = [2u8; 32];
 const N_VALIDATORS: u8 = 5;
 
-// one known_uref for each validator, one for the mint and three for the purses
-const EXPECTED_UREFS_LEN: usize = (N_VALIDATORS as usize) + 1 + 3;
+const DELEGATOR_ADDR: [u8; 32] = [42u8; 32];
+const DELEGATED_STAKE: u64 = 100;
+
+// for each validator one known_uref for its stake, one for its delegation rate and one for its
+// bonding purse, plus one for the delegator, one for the delegation rate limits, one for the mint
+// and three for the purses
+const EXPECTED_UREFS_LEN: usize = 3 * (N_VALIDATORS as usize) + 1 + 1 + 1 + 3;
+// the local state keys under which PoS stores its unbonding delay, its era duration and its bond
+// limits
+const UNBOND_DELAY_KEY: u8 = 3;
+const ERA_DURATION_KEY: u8 = 5;
+const BOND_LIMITS_KEY: u8 = 9;
+const ERA_DURATION: u64 = 1000;
+const UNBOND_DELAY: u64 = 100;
 
 const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
@@ -48,15 +66,29 @@ fn should_run_pos_install_contract() {
         .map(|i| (PublicKey::new([i; 32]), U512::from(i)))
         .collect();
 
-    let total_bond = genesis_validators.values().fold(U512::zero(), |x, y| x + y);
+    let delegation_rates: BTreeMap<PublicKey, DelegationRate> = (1u8..=N_VALIDATORS)
+        .map(|i| (PublicKey::new([i; 32]), i * 10))
+        .collect();
+    let genesis_delegators: BTreeMap<PublicKey, U512> =
+        iter::once((PublicKey::new(DELEGATOR_ADDR), U512::from(DELEGATED_STAKE))).collect();
 
+    let bond_limits = BondLimits::new(U512::one(), 10, 20).expect("should create bond limits");
     let (ret_value, ret_urefs, effect): (URef, _, _) = exec_with_return::exec(
         &mut builder,
         SYSTEM_ADDR,
         "pos_install.wasm",
         DEFAULT_BLOCK_TIME,
         DEPLOY_HASH_2,
-        (mint_uref, genesis_validators),
+        (
+            mint_uref,
+            genesis_validators.clone(),
+            delegation_rates.clone(),
+            genesis_delegators,
+            DelegationRateLimits::new(3, 2).expect("should create delegation rate limits"),
+            bond_limits,
+            ERA_DURATION,
+            UNBOND_DELAY,
+        ),
         vec![mint_uref],
     )
     .expect("should run successfully");
@@ -84,7 +116,49 @@ fn should_run_pos_install_contract() {
         .expect("should find bonding purse in known_urefs");
 
     let bonding_purse_balance = builder.get_purse_balance(bonding_purse);
-    assert_eq!(bonding_purse_balance, total_bond);
+    assert_eq!(bonding_purse_balance, U512::from(DELEGATED_STAKE));
+
+    // each validator's bonding purse holds its stake
+    for (pub_key, stake) in genesis_validators.iter() {
+        let name = format!("{}{}", VALIDATOR_BONDING_PURSE_PREFIX, pub_key.to_hex());
+        let validator_bonding_purse = get_purse(known_urefs, &name)
+            .expect("should find validator bonding purse in known_urefs");
+        assert_eq!(builder.get_purse_balance(validator_bonding_purse), *stake);
+    }
+    for (pub_key, rate) in delegation_rates.iter() {
+        let delegation_rate_name = format!("r_{}_{}", pub_key.to_hex(), rate);
+        assert!(known_urefs.contains_key(&delegation_rate_name));
+    }
+    let delegator_name = format!(
+        "d_{}_{}",
+        PublicKey::new(DELEGATOR_ADDR).to_hex(),
+        DELEGATED_STAKE
+    );
+    assert!(known_urefs.contains_key(&delegator_name));
+    let limits_name = format!("{}3_2", DELEGATION_RATE_LIMITS_PREFIX);
+    assert!(known_urefs.contains_key(&limits_name));
+
+    // bond limits, era duration and unbonding delay are stored in the local state of the contract
+    let bond_limits_key = Key::local(ret_value.addr(), &BOND_LIMITS_KEY.to_bytes().unwrap());
+    let bond_limits_bytes = bond_limits.to_bytes().unwrap();
+    match effect.transforms.get(&bond_limits_key) {
+        Some(Transform::Write(Value::ByteArray(bytes))) => assert_eq!(*bytes, bond_limits_bytes),
+        _ => panic!("Expected bond limits to be written to the local state"),
+    }
+    let era_duration_key = Key::local(ret_value.addr(), &ERA_DURATION_KEY.to_bytes().unwrap());
+    match effect.transforms.get(&era_duration_key) {
+        Some(Transform::Write(Value::UInt64(era_duration))) => {
+            assert_eq!(*era_duration, ERA_DURATION)
+        }
+        _ => panic!("Expected era duration to be written to the local state"),
+    }
+    let unbond_delay_key = Key::local(ret_value.addr(), &UNBOND_DELAY_KEY.to_bytes().unwrap());
+    match effect.transforms.get(&unbond_delay_key) {
+        Some(Transform::Write(Value::UInt64(unbond_delay))) => {
+            assert_eq!(*unbond_delay, UNBOND_DELAY)
+        }
+        _ => panic!("Expected unbonding delay to be written to the local state"),
+    }
 
     // payment purse has correct balance
     let payment_purse = get_purse(known_urefs, POS_PAYMENT_PURSE)
@@ -1,19 +1,24 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use contract_ffi::base16;
+use contract_ffi::bytesrepr;
 use contract_ffi::key::Key;
+use contract_ffi::system_contracts::error::PosError;
+use contract_ffi::system_contracts::pos::{DelegationRate, DelegationRateLimits, EraId};
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::account::PurseId;
 use contract_ffi::value::{Value, U512};
 
-use engine_core::engine_state::genesis::POS_BONDING_PURSE;
+use engine_core::engine_state::genesis::{GenesisAccount, POS_BONDING_PURSE};
+use engine_core::engine_state::EngineConfig;
 use engine_core::engine_state::CONV_RATE;
 use engine_core::engine_state::MAX_PAYMENT;
 use engine_shared::motes::Motes;
-use engine_shared::transform::Transform;
 
 use crate::support::test_support::{
-    self, InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+    self, GenesisConfigBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_1_ADDR,
+    DEFAULT_ACCOUNT_1_BONDED_AMOUNT, DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_BLOCK_TIME,
+    STANDARD_PAYMENT_CONTRACT,
 };
 
 const GENESIS_ADDR: [u8; 32] = [6u8; 32];
@@ -52,6 +57,63 @@ const TEST_BOND: &str = "bond";
 const TEST_BOND_FROM_MAIN_PURSE: &str = "bond-from-main-purse";
 const TEST_SEED_NEW_ACCOUNT: &str = "seed_new_account";
 const TEST_UNBOND: &str = "unbond";
+const TEST_STEP: &str = "step";
+const TEST_GET_ERA_SNAPSHOT: &str = "get_era_snapshot";
+const TEST_SET_DELEGATION_RATE: &str = "set_delegation_rate";
+const ERA_SNAPSHOT_KEY: &str = "era_snapshot";
+const ERA_DURATION: u64 = 1000;
+const UNBOND_DELAY: u64 = 1000;
+
+fn engine_config() -> EngineConfig {
+    EngineConfig::new().set_use_payment_code(true)
+}
+
+/// Queries the snapshot of `era` through the `pos_bonding.wasm` contract, which stores it under
+/// the genesis account.
+fn get_era_snapshot(
+    builder: &mut InMemoryWasmTestBuilder,
+    era: EraId,
+    deploy_hash: [u8; 32],
+) -> Option<BTreeMap<PublicKey, U512>> {
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "pos_bonding.wasm",
+            (String::from(TEST_GET_ERA_SNAPSHOT), era),
+            DEFAULT_BLOCK_TIME,
+            deploy_hash,
+        )
+        .expect_success()
+        .commit();
+
+    match builder.query(None, Key::Account(GENESIS_ADDR), &[ERA_SNAPSHOT_KEY]) {
+        Some(Value::ByteArray(bytes)) => {
+            bytesrepr::deserialize(&bytes).expect("should deserialize era snapshot")
+        }
+        other => panic!("unexpected era snapshot value: {:?}", other),
+    }
+}
+
+/// Sets the delegation rate of the default genesis validator through the `pos_bonding.wasm`
+/// contract, in a block at `block_time`.
+fn set_delegation_rate(
+    builder: &mut InMemoryWasmTestBuilder,
+    rate: DelegationRate,
+    block_time: u64,
+    deploy_hash: [u8; 32],
+) -> &mut InMemoryWasmTestBuilder {
+    builder.exec_with_args(
+        DEFAULT_ACCOUNT_1_ADDR,
+        STANDARD_PAYMENT_CONTRACT,
+        (U512::from(MAX_PAYMENT),),
+        "pos_bonding.wasm",
+        (String::from(TEST_SET_DELEGATION_RATE), rate),
+        block_time,
+        deploy_hash,
+    )
+}
 
 #[ignore]
 #[test]
@@ -66,18 +128,8 @@ fn should_run_successful_bond_and_unbond() {
         result
     };
 
-    let result = InMemoryWasmTestBuilder::default()
+    let result = InMemoryWasmTestBuilder::new(engine_config())
         .run_genesis(GENESIS_ADDR, genesis_validators)
-        .finish();
-
-    let genesis_account = result
-        .builder()
-        .get_account(genesis_account_key)
-        .expect("should get account 1");
-
-    let pos = result.builder().get_pos_contract_uref();
-
-    let result = InMemoryWasmTestBuilder::from_result(result)
         .exec_with_args(
             GENESIS_ADDR,
             STANDARD_PAYMENT_CONTRACT,
@@ -91,32 +143,33 @@ fn should_run_successful_bond_and_unbond() {
         .commit()
         .finish();
 
-    let exec_response = result
+    let genesis_account = result
+        .builder()
+        .get_account(genesis_account_key)
+        .expect("should get account 1");
+
+    let bond_exec_response = result
         .builder()
         .get_exec_response(0)
         .expect("should have exec response");
-    let mut genesis_gas_cost = test_support::get_exec_costs(&exec_response)[0];
-
-    let transforms = &result.builder().get_transforms()[0];
-
-    let pos_transform = &transforms[&pos.into()];
-
-    // Verify that genesis account is in validator queue
-    let contract = if let Transform::Write(Value::Contract(contract)) = pos_transform {
-        contract
-    } else {
-        panic!(
-            "pos transform is expected to be of AddKeys variant but received {:?}",
-            pos_transform
-        );
-    };
+    let mut genesis_gas_cost = test_support::get_exec_costs(&bond_exec_response)[0];
 
     let lookup_key = format!(
         "v_{}_{}",
         base16::encode_lower(&GENESIS_ADDR),
         GENESIS_ACCOUNT_STAKE
     );
-    assert!(contract.urefs_lookup().contains_key(&lookup_key));
+    // Genesis account is in the bonding queue, but not bonded before the next step
+    let pos_contract = result.builder().get_pos_contract();
+    assert!(!pos_contract.urefs_lookup().contains_key(&lookup_key));
+
+    // The step of the next block applies the bond
+    let result = InMemoryWasmTestBuilder::from_result(result)
+        .step(DEFAULT_BLOCK_TIME)
+        .finish();
+
+    let pos_contract = result.builder().get_pos_contract();
+    assert!(pos_contract.urefs_lookup().contains_key(&lookup_key));
 
     // Gensis validator [42; 32] bonded 50k, and genesis account bonded 100k inside
     // the test contract
@@ -138,7 +191,7 @@ fn should_run_successful_bond_and_unbond() {
                 U512::from(ACCOUNT_1_SEED_AMOUNT),
             ),
             DEFAULT_BLOCK_TIME,
-            [2u8; 32],
+            [4u8; 32],
         )
         .expect_success()
         .commit()
@@ -152,10 +205,11 @@ fn should_run_successful_bond_and_unbond() {
                 U512::from(ACCOUNT_1_STAKE),
             ),
             DEFAULT_BLOCK_TIME,
-            [1; 32],
+            [5; 32],
         )
         .expect_success()
         .commit()
+        .step(DEFAULT_BLOCK_TIME)
         .finish();
 
     let account_1_key = Key::Account(ACCOUNT_1_ADDR);
@@ -171,28 +225,14 @@ fn should_run_successful_bond_and_unbond() {
         .get_account(account_1_key)
         .expect("should get account 1");
 
-    let pos = result.builder().get_pos_contract_uref();
-
-    let transforms = &result.builder().get_transforms()[1];
-
-    let pos_transform = &transforms[&pos.into()];
-
-    // Verify that genesis account is in validator queue
-    let contract = if let Transform::Write(Value::Contract(contract)) = pos_transform {
-        contract
-    } else {
-        panic!(
-            "pos transform is expected to be of AddKeys variant but received {:?}",
-            pos_transform
-        );
-    };
+    let pos_contract = result.builder().get_pos_contract();
 
     let lookup_key = format!(
         "v_{}_{}",
         base16::encode_lower(&ACCOUNT_1_ADDR),
         ACCOUNT_1_STAKE
     );
-    assert!(contract.urefs_lookup().contains_key(&lookup_key));
+    assert!(pos_contract.urefs_lookup().contains_key(&lookup_key));
 
     // Gensis validator [42; 32] bonded 50k, and genesis account bonded 100k inside
     // the test contract
@@ -219,10 +259,11 @@ fn should_run_successful_bond_and_unbond() {
                 Some(U512::from(ACCOUNT_1_UNBOND_1)),
             ),
             DEFAULT_BLOCK_TIME,
-            [2; 32],
+            [7; 32],
         )
         .expect_success()
         .commit()
+        .step(DEFAULT_BLOCK_TIME)
         .finish();
 
     let account_1_bal_after = result.builder().get_purse_balance(account_1.purse_id());
@@ -233,6 +274,7 @@ fn should_run_successful_bond_and_unbond() {
     let gas_cost_b = Motes::from_gas(test_support::get_exec_costs(&exec_response)[0], CONV_RATE)
         .expect("should convert");
 
+    // The unbonded motes are paid out by the step
     assert_eq!(
         account_1_bal_after,
         account_1_bal_before - gas_cost_b.value() + ACCOUNT_1_UNBOND_1,
@@ -279,10 +321,11 @@ fn should_run_successful_bond_and_unbond() {
                 Some(U512::from(GENESIS_ACCOUNT_UNBOND_1)),
             ),
             DEFAULT_BLOCK_TIME,
-            [3; 32],
+            [9; 32],
         )
         .expect_success()
         .commit()
+        .step(DEFAULT_BLOCK_TIME)
         .finish();
 
     let exec_response = result
@@ -328,10 +371,11 @@ fn should_run_successful_bond_and_unbond() {
                 Some(U512::from(ACCOUNT_1_UNBOND_2)),
             ), // <-- rest of accont1's funds
             DEFAULT_BLOCK_TIME,
-            [3; 32],
+            [11; 32],
         )
         .expect_success()
         .commit()
+        .step(DEFAULT_BLOCK_TIME)
         .finish();
 
     let account_1_bal_after = result.builder().get_purse_balance(account_1.purse_id());
@@ -377,10 +421,11 @@ fn should_run_successful_bond_and_unbond() {
             "pos_bonding.wasm",
             (String::from(TEST_UNBOND), None as Option<U512>), // <-- va banque
             DEFAULT_BLOCK_TIME,
-            [4; 32],
+            [13; 32],
         )
         .expect_success()
         .commit()
+        .step(DEFAULT_BLOCK_TIME)
         .finish();
 
     let exec_response = result
@@ -389,7 +434,7 @@ fn should_run_successful_bond_and_unbond() {
         .expect("should have exec response");
     genesis_gas_cost = genesis_gas_cost + test_support::get_exec_costs(&exec_response)[0];
 
-    // Back to original after funding account1's pursee
+    // Back to original after funding account1's purse
     assert_eq!(
         result
             .builder()
@@ -511,8 +556,10 @@ fn should_fail_bonding_with_insufficient_funds() {
         let execution_result = crate::support::test_support::get_success_result(&response);
         test_support::get_error_message(execution_result)
     };
-    // Error::BondTransferFailed => 7
-    assert_eq!(error_message, "Exit code: 7");
+    assert_eq!(
+        error_message,
+        test_support::pos_error_message(PosError::BondTransferFailed)
+    );
 }
 
 #[ignore]
@@ -551,6 +598,224 @@ fn should_fail_unbonding_validator_without_bonding_first() {
         let execution_result = crate::support::test_support::get_success_result(&response);
         test_support::get_error_message(execution_result)
     };
-    // Error::NotBonded => 0
-    assert_eq!(error_message, "Exit code: 0");
+    assert_eq!(
+        error_message,
+        test_support::pos_error_message(PosError::NotBonded)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_step_by_non_system_account() {
+    let genesis_validators = {
+        let mut result = HashMap::new();
+        result.insert(
+            PublicKey::new([42; 32]),
+            U512::from(GENESIS_VALIDATOR_STAKE),
+        );
+        result
+    };
+
+    let result = InMemoryWasmTestBuilder::new(engine_config())
+        .run_genesis(GENESIS_ADDR, genesis_validators)
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "pos_bonding.wasm",
+            (String::from(TEST_STEP),),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .commit()
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("should have a response")
+        .to_owned();
+
+    let error_message = {
+        let execution_result = crate::support::test_support::get_success_result(&response);
+        test_support::get_error_message(execution_result)
+    };
+    assert_eq!(
+        error_message,
+        test_support::pos_error_message(PosError::SystemFunctionCalledByUserAccount)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_snapshot_stakes_when_era_advances() {
+    let genesis_config = GenesisConfigBuilder::new()
+        .push_account(GenesisAccount::new(
+            PublicKey::new(GENESIS_ADDR),
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            Motes::zero(),
+        ))
+        .build()
+        .with_era_duration(ERA_DURATION);
+
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config());
+    builder
+        .run_genesis_with_genesis_config(genesis_config)
+        .expect("should run genesis");
+    builder
+        // The first era starts with the step the engine runs in the first block.
+        .step(DEFAULT_BLOCK_TIME)
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "pos_bonding.wasm",
+            (String::from(TEST_BOND), U512::from(GENESIS_ACCOUNT_STAKE)),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .expect_success()
+        .commit()
+        // The step of the first block past the era duration starts the next era.
+        .step(DEFAULT_BLOCK_TIME + ERA_DURATION);
+
+    let genesis_stakes: BTreeMap<PublicKey, U512> = vec![(
+        PublicKey::new(DEFAULT_ACCOUNT_1_ADDR),
+        U512::from(DEFAULT_ACCOUNT_1_BONDED_AMOUNT),
+    )]
+    .into_iter()
+    .collect();
+    assert_eq!(
+        get_era_snapshot(&mut builder, 0, [2; 32]),
+        Some(genesis_stakes.clone())
+    );
+
+    let mut bonded_stakes = genesis_stakes;
+    bonded_stakes.insert(
+        PublicKey::new(GENESIS_ADDR),
+        U512::from(GENESIS_ACCOUNT_STAKE),
+    );
+    assert_eq!(
+        get_era_snapshot(&mut builder, 1, [3; 32]),
+        Some(bonded_stakes)
+    );
+
+    assert_eq!(get_era_snapshot(&mut builder, 2, [4; 32]), None);
+}
+
+#[ignore]
+#[test]
+fn should_cap_delegation_rate_changes_per_era() {
+    let genesis_config = GenesisConfigBuilder::new()
+        .build()
+        .with_era_duration(ERA_DURATION)
+        .with_delegation_rate_limits(
+            DelegationRateLimits::new(5, 2).expect("should create delegation rate limits"),
+        );
+    let too_often = test_support::pos_error_message(PosError::DelegationRateChangedTooOften);
+    let too_large = test_support::pos_error_message(PosError::DelegationRateIncreaseTooLarge);
+
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config());
+    builder
+        .run_genesis_with_genesis_config(genesis_config)
+        .expect("should run genesis");
+    builder.step(DEFAULT_BLOCK_TIME);
+
+    // The validator chose no rate at genesis, so it may pick any rate, but only change it twice in
+    // the first era.
+    set_delegation_rate(&mut builder, 10, DEFAULT_BLOCK_TIME, [1; 32])
+        .expect_success()
+        .commit();
+    set_delegation_rate(&mut builder, 15, DEFAULT_BLOCK_TIME, [2; 32])
+        .expect_success()
+        .commit();
+    set_delegation_rate(&mut builder, 12, DEFAULT_BLOCK_TIME, [3; 32])
+        .expect_error_contains(&too_often)
+        .commit();
+
+    // The next era starts with the step of the first block past the era duration, and caps the
+    // increases relative to the rate it started with.
+    let next_era_block_time = DEFAULT_BLOCK_TIME + ERA_DURATION;
+    builder.step(next_era_block_time);
+    set_delegation_rate(&mut builder, 21, next_era_block_time, [4; 32])
+        .expect_error_contains(&too_large)
+        .commit();
+    set_delegation_rate(&mut builder, 20, next_era_block_time, [5; 32])
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_pay_out_unbonded_stake_after_unbond_delay() {
+    let genesis_config = GenesisConfigBuilder::new()
+        .push_account(GenesisAccount::new(
+            PublicKey::new(GENESIS_ADDR),
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            Motes::zero(),
+        ))
+        .build()
+        .with_unbond_delay(UNBOND_DELAY);
+
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config());
+    builder
+        .run_genesis_with_genesis_config(genesis_config)
+        .expect("should run genesis");
+    builder
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "pos_bonding.wasm",
+            (String::from(TEST_BOND), U512::from(GENESIS_ACCOUNT_STAKE)),
+            DEFAULT_BLOCK_TIME,
+            [1; 32],
+        )
+        .expect_success()
+        .commit()
+        .step(DEFAULT_BLOCK_TIME)
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "pos_bonding.wasm",
+            (
+                String::from(TEST_UNBOND),
+                Some(U512::from(GENESIS_ACCOUNT_STAKE)),
+            ),
+            DEFAULT_BLOCK_TIME,
+            [2; 32],
+        )
+        .expect_success()
+        .commit()
+        // Applies the unbonding request, but the stake is only paid out once the delay passed.
+        .step(DEFAULT_BLOCK_TIME);
+
+    let genesis_purse_id = builder
+        .get_account(Key::Account(GENESIS_ADDR))
+        .expect("should get genesis account")
+        .purse_id();
+    let balance_after_unbond = builder.get_purse_balance(genesis_purse_id);
+    let bonding_purse_balance_after_unbond = get_pos_bonding_purse_balance(&builder);
+    assert_eq!(
+        bonding_purse_balance_after_unbond,
+        U512::from(GENESIS_ACCOUNT_STAKE)
+    );
+
+    builder.step(DEFAULT_BLOCK_TIME + UNBOND_DELAY - 1);
+    assert_eq!(
+        builder.get_purse_balance(genesis_purse_id),
+        balance_after_unbond
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        bonding_purse_balance_after_unbond
+    );
+
+    builder.step(DEFAULT_BLOCK_TIME + UNBOND_DELAY);
+    assert_eq!(
+        builder.get_purse_balance(genesis_purse_id),
+        balance_after_unbond + U512::from(GENESIS_ACCOUNT_STAKE)
+    );
+    assert_eq!(get_pos_bonding_purse_balance(&builder), U512::zero());
 }
@@ -1,11 +1,21 @@
 use std::collections::HashMap;
+use std::convert::TryInto;
+
+use grpc::RequestOptions;
 
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
+use engine_core::engine_state::{EngineConfig, MAX_PAYMENT};
+use engine_grpc_server::engine_server::ipc::{QueryValidatorsRequest, StepRequest};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_grpc_server::engine_server::mappings::{to_domain_validators, CommitTransforms};
 
-use crate::support::test_support::{InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME};
+use crate::support::test_support::{
+    self, InMemoryWasmTestBuilder, DEFAULT_BLOCK_TIME, STANDARD_PAYMENT_CONTRACT,
+};
 
 const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+const GENESIS_ACCOUNT_STAKE: u64 = 100_000;
 
 #[ignore]
 #[test]
@@ -33,3 +43,140 @@ fn should_return_bonded_validators() {
     assert_eq!(bonded_validators[0], genesis_validators);
     assert_eq!(bonded_validators[1], genesis_validators);
 }
+
+#[ignore]
+#[test]
+fn should_return_bonded_validator_changes() {
+    let genesis_validators: HashMap<PublicKey, U512> = vec![
+        (PublicKey::new([1u8; 32]), U512::from(1000)),
+        (PublicKey::new([2u8; 32]), U512::from(200)),
+    ]
+    .into_iter()
+    .collect();
+
+    // The bond only changes the stakes once the step of the next block runs.
+    let engine_config = EngineConfig::new().set_use_payment_code(true);
+    let mut builder = InMemoryWasmTestBuilder::new(engine_config);
+    builder
+        .run_genesis(GENESIS_ADDR, genesis_validators)
+        .exec_with_args(
+            GENESIS_ADDR,
+            STANDARD_PAYMENT_CONTRACT,
+            (U512::from(MAX_PAYMENT),),
+            "pos_bonding.wasm",
+            (String::from("bond"), U512::from(GENESIS_ACCOUNT_STAKE)),
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .expect_success()
+        .commit();
+
+    let mut step_request = StepRequest::new();
+    step_request.set_parent_state_hash(builder.get_post_state_hash());
+    step_request.set_block_time(DEFAULT_BLOCK_TIME);
+    step_request.set_protocol_version(test_support::get_protocol_version());
+
+    let step_response = builder
+        .get_engine_state()
+        .step(RequestOptions::new(), step_request)
+        .wait_drop_metadata()
+        .expect("should step");
+
+    assert!(step_response.has_success());
+
+    let effects: CommitTransforms = step_response
+        .get_success()
+        .get_effect()
+        .get_transform_map()
+        .try_into()
+        .expect("should convert");
+    let commit_request =
+        test_support::create_commit_request(&builder.get_post_state_hash(), &effects.value());
+
+    let commit_response = builder
+        .get_engine_state()
+        .commit(RequestOptions::new(), commit_request)
+        .wait_drop_metadata()
+        .expect("should commit");
+
+    assert!(commit_response.has_success());
+
+    let bonded_validator_changes: HashMap<PublicKey, U512> = commit_response
+        .get_success()
+        .get_bonded_validator_changes()
+        .iter()
+        .map(|bond| to_domain_validators(bond).expect("should convert bond"))
+        .collect();
+
+    let expected: HashMap<PublicKey, U512> = vec![(
+        PublicKey::new(GENESIS_ADDR),
+        U512::from(GENESIS_ACCOUNT_STAKE),
+    )]
+    .into_iter()
+    .collect();
+    assert_eq!(bonded_validator_changes, expected);
+}
+
+#[ignore]
+#[test]
+fn should_query_bonded_validators() {
+    let genesis_validators: HashMap<PublicKey, U512> = vec![
+        (PublicKey::new([1u8; 32]), U512::from(1000)),
+        (PublicKey::new([2u8; 32]), U512::from(200)),
+    ]
+    .into_iter()
+    .collect();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(GENESIS_ADDR, genesis_validators.clone())
+        .exec(
+            GENESIS_ADDR,
+            "local_state.wasm",
+            DEFAULT_BLOCK_TIME,
+            [1u8; 32],
+        )
+        .commit();
+
+    let mut query_validators_request = QueryValidatorsRequest::new();
+    query_validators_request.set_state_hash(builder.get_post_state_hash());
+
+    let query_validators_response = builder
+        .get_engine_state()
+        .query_validators(RequestOptions::new(), query_validators_request)
+        .wait_drop_metadata()
+        .expect("should query validators");
+
+    assert!(query_validators_response.has_success());
+
+    let bonded_validators: HashMap<PublicKey, U512> = query_validators_response
+        .get_success()
+        .get_bonded_validators()
+        .iter()
+        .map(|bond| to_domain_validators(bond).expect("should convert bond"))
+        .collect();
+
+    assert_eq!(bonded_validators, genesis_validators);
+}
+
+#[ignore]
+#[test]
+fn should_not_query_validators_of_missing_state() {
+    let builder = InMemoryWasmTestBuilder::default();
+
+    let mut query_validators_request = QueryValidatorsRequest::new();
+    query_validators_request.set_state_hash(vec![255u8; 32]);
+
+    let query_validators_response = builder
+        .get_engine_state()
+        .query_validators(RequestOptions::new(), query_validators_request)
+        .wait_drop_metadata()
+        .expect("should query validators");
+
+    assert!(query_validators_response.has_missing_state());
+    assert_eq!(
+        query_validators_response.get_missing_state().get_hash(),
+        &[255u8; 32][..]
+    );
+}
@@ -0,0 +1,450 @@
+use grpc::RequestOptions;
+
+use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::RefundRatio;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::{Contract, Value, U512};
+use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
+use engine_core::engine_state::{
+    protocol_version_key, EngineConfig, DEFAULT_MIN_GAS_PRICE, DEFAULT_NATIVE_TRANSFER_COST,
+};
+use engine_grpc_server::engine_server::ipc::{
+    ChainSpec_ActivationPoint, ChainSpec_CostTable, ChainSpec_RefundRatio, ChainSpec_UpgradePoint,
+    GetProtocolDataRequest, GetProtocolDataResponse, UpgradeRequest, UpgradeResponse,
+};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_grpc_server::engine_server::state;
+use engine_shared::key_limits::KeyLimits;
+use engine_shared::motes::Motes;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+
+use crate::support::test_support::{
+    self, DeployBuilder, ExecRequestBuilder, InMemoryWasmTestBuilder, STANDARD_PAYMENT_CONTRACT,
+};
+
+const MINT_INSTALL: &str = "mint_install.wasm";
+const POS_INSTALL: &str = "pos_install.wasm";
+const NEW_MINT_CODE: &str = "mint_token.wasm";
+
+const CHAIN_NAME: &str = "Jeremiah";
+const TIMESTAMP: u64 = 0;
+const PROTOCOL_VERSION: u64 = 1;
+const NEW_PROTOCOL_VERSION: u64 = 2;
+const ACTIVATION_POINT_RANK: u64 = 100;
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_1_BALANCE: u64 = 1_000_000_000;
+const ACCOUNT_1_BONDED_AMOUNT: u64 = 1_000_000;
+
+fn run_genesis() -> InMemoryWasmTestBuilder {
+    let genesis_config = {
+        let account_1 = GenesisAccount::new(
+            PublicKey::new(ACCOUNT_1_ADDR),
+            Motes::new(ACCOUNT_1_BALANCE.into()),
+            Motes::new(ACCOUNT_1_BONDED_AMOUNT.into()),
+        );
+        GenesisConfig::new(
+            CHAIN_NAME.to_string(),
+            TIMESTAMP,
+            PROTOCOL_VERSION,
+            test_support::read_wasm_file_bytes(MINT_INSTALL),
+            test_support::read_wasm_file_bytes(POS_INSTALL),
+            vec![account_1],
+            WasmCosts::default(),
+            RefundRatio::default(),
+            DEFAULT_MIN_GAS_PRICE,
+            DEFAULT_NATIVE_TRANSFER_COST,
+            KeyLimits::default(),
+        )
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::new(EngineConfig::default());
+
+    builder
+        .run_genesis_with_genesis_config(genesis_config)
+        .expect("should run genesis");
+
+    builder
+}
+
+fn create_upgrade_request(
+    parent_state_hash: Vec<u8>,
+    new_protocol_version: u64,
+    new_mint_code: Vec<u8>,
+    new_wasm_costs: Option<WasmCosts>,
+) -> UpgradeRequest {
+    let mut upgrade_point = ChainSpec_UpgradePoint::new();
+    {
+        let mut activation_point = ChainSpec_ActivationPoint::new();
+        activation_point.set_rank(ACTIVATION_POINT_RANK);
+        upgrade_point.set_activation_point(activation_point);
+    }
+    {
+        let mut protocol_version = state::ProtocolVersion::new();
+        protocol_version.set_value(new_protocol_version);
+        upgrade_point.set_protocol_version(protocol_version);
+    }
+    if let Some(wasm_costs) = new_wasm_costs {
+        let mut cost_table = ChainSpec_CostTable::new();
+        cost_table.set_wasm(wasm_costs.into());
+        upgrade_point.set_new_costs(cost_table);
+    }
+    upgrade_point.set_new_mint_code(new_mint_code);
+
+    let mut upgrade_request = UpgradeRequest::new();
+    upgrade_request.set_parent_state_hash(parent_state_hash);
+    upgrade_request.set_upgrade_point(upgrade_point);
+    {
+        let mut protocol_version = state::ProtocolVersion::new();
+        protocol_version.set_value(PROTOCOL_VERSION);
+        upgrade_request.set_protocol_version(protocol_version);
+    }
+    upgrade_request
+}
+
+fn upgrade(builder: &InMemoryWasmTestBuilder, upgrade_request: UpgradeRequest) -> UpgradeResponse {
+    builder
+        .get_engine_state()
+        .upgrade(RequestOptions::new(), upgrade_request)
+        .wait_drop_metadata()
+        .expect("should upgrade")
+}
+
+fn get_protocol_data(
+    builder: &InMemoryWasmTestBuilder,
+    state_hash: Vec<u8>,
+) -> GetProtocolDataResponse {
+    let mut get_protocol_data_request = GetProtocolDataRequest::new();
+    get_protocol_data_request.set_state_hash(state_hash);
+
+    // Called through the trait, as `EngineState::get_protocol_data` shadows the gRPC method
+    ExecutionEngineService::get_protocol_data(
+        builder.get_engine_state(),
+        RequestOptions::new(),
+        get_protocol_data_request,
+    )
+    .wait_drop_metadata()
+    .expect("should get protocol data")
+}
+
+fn get_contract(
+    builder: &InMemoryWasmTestBuilder,
+    post_state_hash: Vec<u8>,
+    contract_key: Key,
+) -> Contract {
+    match builder.query(Some(post_state_hash), contract_key.normalize(), &[]) {
+        Some(Value::Contract(contract)) => contract,
+        _ => panic!("should have contract under {:?}", contract_key),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_upgrade_system_contracts() {
+    let builder = run_genesis();
+
+    let mint_contract_key = Key::URef(builder.get_mint_contract_uref());
+    let pos_contract_key = Key::URef(builder.get_pos_contract_uref());
+    let genesis_pos_contract =
+        get_contract(&builder, builder.get_post_state_hash(), pos_contract_key);
+
+    let new_mint_code = test_support::read_wasm_file_bytes(NEW_MINT_CODE);
+
+    let upgrade_request = create_upgrade_request(
+        builder.get_post_state_hash(),
+        NEW_PROTOCOL_VERSION,
+        new_mint_code.clone(),
+        None,
+    );
+
+    let upgrade_response = upgrade(&builder, upgrade_request);
+
+    assert!(
+        upgrade_response.has_success(),
+        "expected success, got {:?}",
+        upgrade_response
+    );
+
+    let post_state_hash = upgrade_response
+        .get_success()
+        .get_post_state_hash()
+        .to_vec();
+
+    let mint_contract = get_contract(&builder, post_state_hash.clone(), mint_contract_key);
+    assert_eq!(mint_contract.bytes(), new_mint_code.as_slice());
+    assert_eq!(mint_contract.protocol_version(), NEW_PROTOCOL_VERSION);
+
+    let pos_contract = get_contract(&builder, post_state_hash, pos_contract_key);
+    assert_eq!(pos_contract.bytes(), genesis_pos_contract.bytes());
+    assert_eq!(
+        pos_contract.urefs_lookup(),
+        genesis_pos_contract.urefs_lookup()
+    );
+    assert_eq!(pos_contract.protocol_version(), NEW_PROTOCOL_VERSION);
+}
+
+#[ignore]
+#[test]
+fn should_upgrade_with_new_wasm_costs() {
+    let builder = run_genesis();
+
+    let upgrade_request = create_upgrade_request(
+        builder.get_post_state_hash(),
+        NEW_PROTOCOL_VERSION,
+        Vec::new(),
+        Some(WasmCosts::free()),
+    );
+
+    let upgrade_response = upgrade(&builder, upgrade_request);
+
+    assert!(
+        upgrade_response.has_success(),
+        "expected success, got {:?}",
+        upgrade_response
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_upgrade_to_same_protocol_version() {
+    let builder = run_genesis();
+
+    let upgrade_request = create_upgrade_request(
+        builder.get_post_state_hash(),
+        PROTOCOL_VERSION,
+        Vec::new(),
+        None,
+    );
+
+    let upgrade_response = upgrade(&builder, upgrade_request);
+
+    assert!(upgrade_response.has_failed_deploy());
+}
+
+#[ignore]
+#[test]
+fn should_not_upgrade_with_invalid_mint_code() {
+    let builder = run_genesis();
+
+    let upgrade_request = create_upgrade_request(
+        builder.get_post_state_hash(),
+        NEW_PROTOCOL_VERSION,
+        vec![1, 2, 3],
+        None,
+    );
+
+    let upgrade_response = upgrade(&builder, upgrade_request);
+
+    assert!(upgrade_response.has_failed_deploy());
+}
+
+#[ignore]
+#[test]
+fn should_not_upgrade_missing_state() {
+    let builder = run_genesis();
+
+    let upgrade_request =
+        create_upgrade_request(vec![255u8; 32], NEW_PROTOCOL_VERSION, Vec::new(), None);
+
+    let upgrade_response = upgrade(&builder, upgrade_request);
+
+    assert!(upgrade_response.has_failed_deploy());
+}
+
+#[ignore]
+#[test]
+fn should_get_protocol_data_of_genesis_state() {
+    let builder = run_genesis();
+
+    let get_protocol_data_response = get_protocol_data(&builder, builder.get_post_state_hash());
+
+    assert!(get_protocol_data_response.has_success());
+
+    let protocol_data = get_protocol_data_response.get_success();
+    assert_eq!(
+        protocol_data.get_protocol_version().get_value(),
+        PROTOCOL_VERSION
+    );
+    assert_eq!(
+        WasmCosts::from(protocol_data.get_costs().get_wasm().to_owned()),
+        WasmCosts::default()
+    );
+    assert_eq!(
+        protocol_data.get_refund_ratio(),
+        &ChainSpec_RefundRatio::from(RefundRatio::default())
+    );
+    assert_eq!(protocol_data.get_min_gas_price(), DEFAULT_MIN_GAS_PRICE);
+    assert_eq!(
+        protocol_data.get_native_transfer_cost(),
+        DEFAULT_NATIVE_TRANSFER_COST
+    );
+}
+
+#[ignore]
+#[test]
+fn should_upgrade_with_new_refund_ratio() {
+    let builder = run_genesis();
+
+    let refund_ratio = RefundRatio::new(1, 3).unwrap();
+
+    let mut upgrade_request = create_upgrade_request(
+        builder.get_post_state_hash(),
+        NEW_PROTOCOL_VERSION,
+        Vec::new(),
+        None,
+    );
+    upgrade_request
+        .mut_upgrade_point()
+        .set_new_refund_ratio(refund_ratio.into());
+
+    let upgrade_response = upgrade(&builder, upgrade_request);
+
+    let post_state_hash = upgrade_response
+        .get_success()
+        .get_post_state_hash()
+        .to_vec();
+
+    let get_protocol_data_response = get_protocol_data(&builder, post_state_hash);
+
+    assert!(get_protocol_data_response.has_success());
+
+    let protocol_data = get_protocol_data_response.get_success();
+    assert_eq!(
+        protocol_data.get_refund_ratio(),
+        &ChainSpec_RefundRatio::from(refund_ratio)
+    );
+    // Costs carry over when the upgrade does not change them
+    assert_eq!(
+        WasmCosts::from(protocol_data.get_costs().get_wasm().to_owned()),
+        WasmCosts::default()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_upgrade_with_invalid_refund_ratio() {
+    let builder = run_genesis();
+
+    let mut upgrade_request = create_upgrade_request(
+        builder.get_post_state_hash(),
+        NEW_PROTOCOL_VERSION,
+        Vec::new(),
+        None,
+    );
+    {
+        let mut refund_ratio = ChainSpec_RefundRatio::new();
+        refund_ratio.set_numerator(1);
+        refund_ratio.set_denominator(0);
+        upgrade_request
+            .mut_upgrade_point()
+            .set_new_refund_ratio(refund_ratio);
+    }
+
+    let upgrade_response = upgrade(&builder, upgrade_request);
+
+    assert!(upgrade_response.has_failed_deploy());
+}
+
+#[ignore]
+#[test]
+fn should_record_protocol_version_under_derived_key() {
+    let builder = run_genesis();
+
+    let maybe_protocol_version = builder.query(None, protocol_version_key(), &[]);
+    assert_eq!(
+        maybe_protocol_version,
+        Some(Value::UInt64(PROTOCOL_VERSION))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_get_protocol_data_of_upgraded_state() {
+    let builder = run_genesis();
+
+    let upgrade_request = create_upgrade_request(
+        builder.get_post_state_hash(),
+        NEW_PROTOCOL_VERSION,
+        Vec::new(),
+        Some(WasmCosts::free()),
+    );
+
+    let upgrade_response = upgrade(&builder, upgrade_request);
+
+    let post_state_hash = upgrade_response
+        .get_success()
+        .get_post_state_hash()
+        .to_vec();
+
+    let get_protocol_data_response = get_protocol_data(&builder, post_state_hash);
+
+    assert!(get_protocol_data_response.has_success());
+
+    let protocol_data = get_protocol_data_response.get_success();
+    assert_eq!(
+        protocol_data.get_protocol_version().get_value(),
+        NEW_PROTOCOL_VERSION
+    );
+    assert_eq!(
+        WasmCosts::from(protocol_data.get_costs().get_wasm().to_owned()),
+        WasmCosts::free()
+    );
+
+    // The pre-state still reports the protocol version it was produced with
+    let get_protocol_data_response = get_protocol_data(&builder, builder.get_post_state_hash());
+    assert_eq!(
+        get_protocol_data_response
+            .get_success()
+            .get_protocol_version()
+            .get_value(),
+        PROTOCOL_VERSION
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_with_mismatched_protocol_version() {
+    let builder = run_genesis();
+
+    let upgrade_request = create_upgrade_request(
+        builder.get_post_state_hash(),
+        NEW_PROTOCOL_VERSION,
+        Vec::new(),
+        Some(WasmCosts::free()),
+    );
+
+    let upgrade_response = upgrade(&builder, upgrade_request);
+
+    let post_state_hash = upgrade_response
+        .get_success()
+        .get_post_state_hash()
+        .to_vec();
+
+    let exec_request = {
+        let deploy = DeployBuilder::new()
+            .with_address(ACCOUNT_1_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_session_code("do_nothing.wasm", ())
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (U512::from(10_000_000),))
+            .with_authorization_keys(&[PublicKey::new(ACCOUNT_1_ADDR)])
+            .build();
+
+        ExecRequestBuilder::new()
+            .with_pre_state_hash(&post_state_hash)
+            .with_protocol_version(PROTOCOL_VERSION)
+            .push_deploy(deploy)
+            .build()
+    };
+
+    let exec_response = builder
+        .get_engine_state()
+        .exec(RequestOptions::new(), exec_request)
+        .wait_drop_metadata()
+        .expect("should exec");
+
+    let precondition_failure = test_support::get_precondition_failure(&exec_response);
+
+    assert_eq!(
+        precondition_failure.message,
+        format!("Invalid protocol version: {}", PROTOCOL_VERSION)
+    );
+}
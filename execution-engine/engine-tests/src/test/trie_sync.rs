@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+use grpc::RequestOptions;
+
+use contract_ffi::key::Key;
+use engine_grpc_server::engine_server::ipc::{
+    GetProtocolDataRequest, GetProtocolDataResponse, GetTrieChunkRequest,
+    ImportProtocolDataRequest, MissingTrieKeysRequest, PutTrieRequest,
+};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::support::test_support::InMemoryWasmTestBuilder;
+
+// The gRPC methods are called through `ExecutionEngineService`, as the `EngineState` methods of
+// the same names shadow them.
+
+const GENESIS_ADDR: [u8; 32] = [6u8; 32];
+
+fn missing_trie_keys(builder: &InMemoryWasmTestBuilder, state_hash: Vec<u8>) -> Vec<Vec<u8>> {
+    let mut missing_trie_keys_request = MissingTrieKeysRequest::new();
+    missing_trie_keys_request.set_state_hash(state_hash);
+    let missing_trie_keys_response = ExecutionEngineService::missing_trie_keys(
+        builder.get_engine_state(),
+        RequestOptions::new(),
+        missing_trie_keys_request,
+    )
+    .wait_drop_metadata()
+    .expect("should get missing trie keys");
+    assert!(
+        missing_trie_keys_response.has_success(),
+        "expected success, got {:?}",
+        missing_trie_keys_response
+    );
+    missing_trie_keys_response
+        .get_success()
+        .get_trie_hashes()
+        .to_vec()
+}
+
+fn get_protocol_data(
+    builder: &InMemoryWasmTestBuilder,
+    state_hash: Vec<u8>,
+) -> GetProtocolDataResponse {
+    let mut get_protocol_data_request = GetProtocolDataRequest::new();
+    get_protocol_data_request.set_state_hash(state_hash);
+    ExecutionEngineService::get_protocol_data(
+        builder.get_engine_state(),
+        RequestOptions::new(),
+        get_protocol_data_request,
+    )
+    .wait_drop_metadata()
+    .expect("should get protocol data")
+}
+
+/// Copies the state at `state_hash` from `source` to `target` chunk by chunk, checking every
+/// trie value against the hash it is stored under.
+fn sync_state(
+    source: &InMemoryWasmTestBuilder,
+    target: &InMemoryWasmTestBuilder,
+    state_hash: Vec<u8>,
+) {
+    let mut cursor: Vec<Vec<u8>> = Vec::new();
+    loop {
+        let mut get_trie_chunk_request = GetTrieChunkRequest::new();
+        get_trie_chunk_request.set_state_hash(state_hash.clone());
+        get_trie_chunk_request.set_cursor(cursor.into());
+        let get_trie_chunk_response = ExecutionEngineService::get_trie_chunk(
+            source.get_engine_state(),
+            RequestOptions::new(),
+            get_trie_chunk_request,
+        )
+        .wait_drop_metadata()
+        .expect("should get trie chunk");
+        assert!(
+            get_trie_chunk_response.has_success(),
+            "expected success, got {:?}",
+            get_trie_chunk_response
+        );
+        let trie_chunk = get_trie_chunk_response.get_success();
+
+        for trie in trie_chunk.get_tries() {
+            let mut put_trie_request = PutTrieRequest::new();
+            put_trie_request.set_trie(trie.clone());
+            let put_trie_response = ExecutionEngineService::put_trie(
+                target.get_engine_state(),
+                RequestOptions::new(),
+                put_trie_request,
+            )
+            .wait_drop_metadata()
+            .expect("should put trie");
+            assert_eq!(
+                put_trie_response.get_trie_hash(),
+                Blake2bHash::new(trie).to_vec().as_slice()
+            );
+        }
+
+        if trie_chunk.get_cursor().is_empty() {
+            break;
+        }
+        cursor = trie_chunk.get_cursor().to_vec();
+    }
+}
+
+#[ignore]
+#[test]
+fn should_sync_state_to_new_node() {
+    let mut source = InMemoryWasmTestBuilder::default();
+    source.run_genesis(GENESIS_ADDR, HashMap::new());
+    let state_hash = source.get_post_state_hash();
+
+    let target = InMemoryWasmTestBuilder::default();
+    assert!(target
+        .query(Some(state_hash.clone()), Key::Account(GENESIS_ADDR), &[])
+        .is_none());
+
+    sync_state(&source, &target, state_hash.clone());
+
+    let genesis_account = source.query(Some(state_hash.clone()), Key::Account(GENESIS_ADDR), &[]);
+    assert!(genesis_account.is_some(), "should have genesis account");
+    assert_eq!(
+        target.query(Some(state_hash), Key::Account(GENESIS_ADDR), &[]),
+        genesis_account
+    );
+}
+
+#[ignore]
+#[test]
+fn should_import_protocol_data_of_synced_state() {
+    let mut source = InMemoryWasmTestBuilder::default();
+    source.run_genesis(GENESIS_ADDR, HashMap::new());
+    let state_hash = source.get_post_state_hash();
+
+    let target = InMemoryWasmTestBuilder::default();
+    sync_state(&source, &target, state_hash.clone());
+
+    // The protocol data is part of the synced state, but is only used once imported.
+    let get_protocol_data_response = get_protocol_data(&target, state_hash.clone());
+    assert!(
+        get_protocol_data_response.has_failure(),
+        "expected failure, got {:?}",
+        get_protocol_data_response
+    );
+
+    let mut import_protocol_data_request = ImportProtocolDataRequest::new();
+    import_protocol_data_request.set_state_hash(state_hash.clone());
+    let import_protocol_data_response = ExecutionEngineService::import_protocol_data(
+        target.get_engine_state(),
+        RequestOptions::new(),
+        import_protocol_data_request,
+    )
+    .wait_drop_metadata()
+    .expect("should import protocol data");
+    assert!(
+        import_protocol_data_response.has_success(),
+        "expected success, got {:?}",
+        import_protocol_data_response
+    );
+
+    let source_protocol_data = get_protocol_data(&source, state_hash.clone());
+    assert!(source_protocol_data.has_success());
+    assert_eq!(
+        import_protocol_data_response.get_success(),
+        source_protocol_data.get_success()
+    );
+    assert_eq!(get_protocol_data(&target, state_hash), source_protocol_data);
+}
+
+#[ignore]
+#[test]
+fn should_report_missing_trie_keys_until_synced() {
+    let mut source = InMemoryWasmTestBuilder::default();
+    source.run_genesis(GENESIS_ADDR, HashMap::new());
+    let state_hash = source.get_post_state_hash();
+
+    let target = InMemoryWasmTestBuilder::default();
+    assert_eq!(
+        missing_trie_keys(&target, state_hash.clone()),
+        vec![state_hash.clone()]
+    );
+
+    // Only the root is imported, so its children are missing.
+    let mut get_trie_chunk_request = GetTrieChunkRequest::new();
+    get_trie_chunk_request.set_state_hash(state_hash.clone());
+    let get_trie_chunk_response = ExecutionEngineService::get_trie_chunk(
+        source.get_engine_state(),
+        RequestOptions::new(),
+        get_trie_chunk_request,
+    )
+    .wait_drop_metadata()
+    .expect("should get trie chunk");
+    let mut put_trie_request = PutTrieRequest::new();
+    put_trie_request.set_trie(get_trie_chunk_response.get_success().get_tries()[0].clone());
+    ExecutionEngineService::put_trie(
+        target.get_engine_state(),
+        RequestOptions::new(),
+        put_trie_request,
+    )
+    .wait_drop_metadata()
+    .expect("should put trie");
+    let missing = missing_trie_keys(&target, state_hash.clone());
+    assert!(!missing.is_empty());
+    assert!(!missing.contains(&state_hash));
+
+    sync_state(&source, &target, state_hash.clone());
+    assert!(missing_trie_keys(&target, state_hash).is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_not_get_trie_chunk_of_missing_state() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(GENESIS_ADDR, HashMap::new());
+    let missing_hash = vec![0u8; 32];
+
+    let mut get_trie_chunk_request = GetTrieChunkRequest::new();
+    get_trie_chunk_request.set_state_hash(missing_hash.clone());
+    let get_trie_chunk_response = ExecutionEngineService::get_trie_chunk(
+        builder.get_engine_state(),
+        RequestOptions::new(),
+        get_trie_chunk_request,
+    )
+    .wait_drop_metadata()
+    .expect("should get trie chunk");
+
+    assert!(
+        get_trie_chunk_response.has_missing_state(),
+        "expected missing state, got {:?}",
+        get_trie_chunk_response
+    );
+    assert_eq!(
+        get_trie_chunk_response.get_missing_state().get_hash(),
+        missing_hash.as_slice()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_put_invalid_trie() {
+    let builder = InMemoryWasmTestBuilder::default();
+
+    let mut put_trie_request = PutTrieRequest::new();
+    put_trie_request.set_trie(vec![42u8; 3]);
+    let put_trie_response = ExecutionEngineService::put_trie(
+        builder.get_engine_state(),
+        RequestOptions::new(),
+        put_trie_request,
+    )
+    .wait_drop_metadata()
+    .expect("should put trie");
+
+    assert!(
+        put_trie_response.has_failure(),
+        "expected failure, got {:?}",
+        put_trie_response
+    );
+}
@@ -0,0 +1,23 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate engine_wasm_prep;
+extern crate parity_wasm;
+
+use parity_wasm::elements::Module;
+
+use engine_wasm_prep::fuzzing::inject_gas_counters;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::FloatHandling;
+
+fuzz_target!(|module_bytes: &[u8]| {
+    // Skips the module limits and other passes of `preprocess`, so that the gas injection sees
+    // every module parity-wasm accepts
+    if let Ok(module) = parity_wasm::deserialize_buffer::<Module>(module_bytes) {
+        let _ = inject_gas_counters(
+            module,
+            &WasmCosts::default(),
+            FloatHandling::CanonicalizeNaNs,
+        );
+    }
+});
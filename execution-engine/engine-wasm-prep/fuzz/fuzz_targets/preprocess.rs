@@ -0,0 +1,16 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate engine_wasm_prep;
+
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{FloatHandling, Preprocessor, WasmiPreprocessor};
+
+fuzz_target!(|module_bytes: &[u8]| {
+    // Errors are expected for most inputs, only panics are failures
+    for &float_handling in &[FloatHandling::Reject, FloatHandling::CanonicalizeNaNs] {
+        let preprocessor =
+            WasmiPreprocessor::new(WasmCosts::default()).set_float_handling(float_handling);
+        let _ = preprocessor.preprocess(module_bytes);
+    }
+});
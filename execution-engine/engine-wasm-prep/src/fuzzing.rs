@@ -0,0 +1,15 @@
+//! Internal passes of the preprocessor, exposed so that they can be fuzzed individually.
+
+use parity_wasm::elements::Module;
+
+use crate::wasm_costs::WasmCosts;
+use crate::{FloatHandling, PreprocessingError};
+
+/// Injects gas counters into `module` the way [`crate::WasmiPreprocessor`] does.
+pub fn inject_gas_counters(
+    module: Module,
+    wasm_costs: &WasmCosts,
+    float_handling: FloatHandling,
+) -> Result<Module, PreprocessingError> {
+    crate::inject_gas_counters(module, wasm_costs, float_handling)
+}
@@ -5,8 +5,13 @@ extern crate contract_ffi;
 #[cfg(test)]
 extern crate engine_shared;
 
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+pub mod module_limits;
+mod nan_canonicalization;
 pub mod wasm_costs;
 
+use module_limits::ModuleLimits;
 use parity_wasm::elements::{Error as ParityWasmError, Module};
 use pwasm_utils::{externalize_mem, inject_gas_counter, rules};
 use std::error::Error;
@@ -15,7 +20,7 @@ use wasm_costs::WasmCosts;
 //NOTE: size of Wasm memory page is 64 KiB
 pub const MEM_PAGES: u32 = 64;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum PreprocessingError {
     InvalidImportsError(String),
     NoExportSection,
@@ -23,10 +28,32 @@ pub enum PreprocessingError {
     DeserializeError(String),
     OperationForbiddenByGasRules,
     StackLimiterError,
+    InvalidFunctionType,
+    ModuleTooLarge { max: usize, actual: usize },
+    TooManyFunctions { max: usize, actual: usize },
+    FunctionBodyTooLarge { max: usize, actual: usize },
+    TooManyGlobals { max: usize, actual: usize },
+    TableTooLarge { max: u32, actual: u32 },
 }
 
 use PreprocessingError::*;
 
+/// Selects how the preprocessor treats modules using floating point instructions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FloatHandling {
+    /// Modules using floating point instructions are rejected.
+    Reject,
+    /// Floating point instructions are allowed, with every NaN they produce
+    /// replaced by the canonical NaN so that results do not depend on the platform.
+    CanonicalizeNaNs,
+}
+
+impl Default for FloatHandling {
+    fn default() -> Self {
+        FloatHandling::Reject
+    }
+}
+
 pub trait Preprocessor<A> {
     fn preprocess(&self, module_bytes: &[u8]) -> Result<A, PreprocessingError>;
     fn deserialize(&self, module_bytes: &[u8]) -> Result<A, PreprocessingError>;
@@ -36,6 +63,8 @@ pub struct WasmiPreprocessor {
     wasm_costs: WasmCosts,
     // Number of memory pages.
     mem_pages: u32,
+    float_handling: FloatHandling,
+    module_limits: ModuleLimits,
 }
 
 impl WasmiPreprocessor {
@@ -43,15 +72,38 @@ impl WasmiPreprocessor {
         WasmiPreprocessor {
             wasm_costs,
             mem_pages: MEM_PAGES,
+            float_handling: FloatHandling::default(),
+            module_limits: ModuleLimits::default(),
         }
     }
+
+    /// Sets the `float_handling` field to the given arg.
+    pub fn set_float_handling(mut self, arg: FloatHandling) -> WasmiPreprocessor {
+        self.float_handling = arg;
+        self
+    }
+
+    /// Sets the `module_limits` field to the given arg.
+    pub fn set_module_limits(mut self, arg: ModuleLimits) -> WasmiPreprocessor {
+        self.module_limits = arg;
+        self
+    }
 }
 
 impl Preprocessor<Module> for WasmiPreprocessor {
     fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
+        self.module_limits.check_module_bytes(module_bytes)?;
         let deserialized_module = self.deserialize(module_bytes)?;
-        let ext_mod = externalize_mem(deserialized_module, None, self.mem_pages);
-        let gas_mod = inject_gas_counters(ext_mod, &self.wasm_costs)?;
+        self.module_limits.check_module(&deserialized_module)?;
+        // Canonicalization happens first, so that the instructions it adds are metered too
+        let float_mod = match self.float_handling {
+            FloatHandling::Reject => deserialized_module,
+            FloatHandling::CanonicalizeNaNs => {
+                nan_canonicalization::canonicalize_nans(deserialized_module)?
+            }
+        };
+        let ext_mod = externalize_mem(float_mod, None, self.mem_pages);
+        let gas_mod = inject_gas_counters(ext_mod, &self.wasm_costs, self.float_handling)?;
         let module =
             pwasm_utils::stack_height::inject_limiter(gas_mod, self.wasm_costs.max_stack_height)
                 .map_err(|_| StackLimiterError)?;
@@ -67,8 +119,8 @@ impl Preprocessor<Module> for WasmiPreprocessor {
     }
 }
 
-fn gas_rules(wasm_costs: &WasmCosts) -> rules::Set {
-    rules::Set::new(wasm_costs.regular, {
+fn gas_rules(wasm_costs: &WasmCosts, float_handling: FloatHandling) -> rules::Set {
+    let rules = rules::Set::new(wasm_costs.regular, {
         let mut vals = ::std::collections::BTreeMap::new();
         vals.insert(
             rules::InstructionType::Load,
@@ -88,13 +140,18 @@ fn gas_rules(wasm_costs: &WasmCosts) -> rules::Set {
         );
         vals
     })
-    .with_grow_cost(wasm_costs.grow_mem)
-    .with_forbidden_floats()
+    .with_grow_cost(wasm_costs.grow_mem);
+    match float_handling {
+        FloatHandling::Reject => rules.with_forbidden_floats(),
+        FloatHandling::CanonicalizeNaNs => rules,
+    }
 }
 
 fn inject_gas_counters(
     module: Module,
     wasm_costs: &WasmCosts,
+    float_handling: FloatHandling,
 ) -> Result<Module, PreprocessingError> {
-    inject_gas_counter(module, &gas_rules(wasm_costs)).map_err(|_| OperationForbiddenByGasRules)
+    inject_gas_counter(module, &gas_rules(wasm_costs, float_handling))
+        .map_err(|_| OperationForbiddenByGasRules)
 }
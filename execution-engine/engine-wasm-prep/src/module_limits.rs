@@ -0,0 +1,185 @@
+use parity_wasm::elements::Module;
+
+use crate::PreprocessingError;
+
+/// Upper bounds on the size and complexity of modules accepted by the
+/// preprocessor, so that a deploy cannot exhaust the memory of the engine.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ModuleLimits {
+    /// Max size of the serialized module in bytes
+    pub max_module_bytes: usize,
+    /// Max number of functions defined by the module
+    pub max_functions: usize,
+    /// Max number of instructions in a single function body
+    pub max_function_instructions: usize,
+    /// Max number of globals defined by the module
+    pub max_globals: usize,
+    /// Max number of entries in a table
+    pub max_table_size: u32,
+}
+
+impl Default for ModuleLimits {
+    fn default() -> Self {
+        ModuleLimits {
+            max_module_bytes: 4 * 1024 * 1024,
+            max_functions: 10_000,
+            max_function_instructions: 100_000,
+            max_globals: 1_000,
+            max_table_size: 10_000,
+        }
+    }
+}
+
+impl ModuleLimits {
+    /// Checks the size of a module before it is deserialized.
+    pub(crate) fn check_module_bytes(&self, module_bytes: &[u8]) -> Result<(), PreprocessingError> {
+        if module_bytes.len() > self.max_module_bytes {
+            return Err(PreprocessingError::ModuleTooLarge {
+                max: self.max_module_bytes,
+                actual: module_bytes.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks the contents of a deserialized module.
+    pub(crate) fn check_module(&self, module: &Module) -> Result<(), PreprocessingError> {
+        let functions = module
+            .function_section()
+            .map_or(0, |section| section.entries().len());
+        if functions > self.max_functions {
+            return Err(PreprocessingError::TooManyFunctions {
+                max: self.max_functions,
+                actual: functions,
+            });
+        }
+
+        if let Some(code_section) = module.code_section() {
+            for body in code_section.bodies() {
+                let instructions = body.code().elements().len();
+                if instructions > self.max_function_instructions {
+                    return Err(PreprocessingError::FunctionBodyTooLarge {
+                        max: self.max_function_instructions,
+                        actual: instructions,
+                    });
+                }
+            }
+        }
+
+        let globals = module
+            .global_section()
+            .map_or(0, |section| section.entries().len());
+        if globals > self.max_globals {
+            return Err(PreprocessingError::TooManyGlobals {
+                max: self.max_globals,
+                actual: globals,
+            });
+        }
+
+        if let Some(table_section) = module.table_section() {
+            for table in table_section.entries() {
+                let table_size = table.limits().initial();
+                if table_size > self.max_table_size {
+                    return Err(PreprocessingError::TableTooLarge {
+                        max: self.max_table_size,
+                        actual: table_size,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::Module;
+
+    use super::ModuleLimits;
+    use crate::PreprocessingError;
+
+    // (module (type (func)) (func) (table 10 anyfunc) (global i32 (i32.const 0)))
+    const MODULE: [u8; 38] = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+        0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section
+        0x03, 0x02, 0x01, 0x00, // function section
+        0x04, 0x04, 0x01, 0x70, 0x00, 0x0a, // table section
+        0x06, 0x06, 0x01, 0x7f, 0x00, 0x41, 0x00, 0x0b, // global section
+        0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b, // code section
+    ];
+
+    fn module() -> Module {
+        parity_wasm::deserialize_buffer(&MODULE).expect("should deserialize test module")
+    }
+
+    #[test]
+    fn should_accept_module_within_limits() {
+        let limits = ModuleLimits::default();
+        assert!(limits.check_module_bytes(&MODULE).is_ok());
+        assert!(limits.check_module(&module()).is_ok());
+    }
+
+    #[test]
+    fn should_reject_too_large_module() {
+        let limits = ModuleLimits {
+            max_module_bytes: 10,
+            ..ModuleLimits::default()
+        };
+        assert_eq!(
+            limits.check_module_bytes(&MODULE),
+            Err(PreprocessingError::ModuleTooLarge {
+                max: 10,
+                actual: MODULE.len()
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_too_many_functions() {
+        let limits = ModuleLimits {
+            max_functions: 0,
+            ..ModuleLimits::default()
+        };
+        assert_eq!(
+            limits.check_module(&module()),
+            Err(PreprocessingError::TooManyFunctions { max: 0, actual: 1 })
+        );
+    }
+
+    #[test]
+    fn should_reject_too_large_function_body() {
+        let limits = ModuleLimits {
+            max_function_instructions: 0,
+            ..ModuleLimits::default()
+        };
+        assert_eq!(
+            limits.check_module(&module()),
+            Err(PreprocessingError::FunctionBodyTooLarge { max: 0, actual: 1 })
+        );
+    }
+
+    #[test]
+    fn should_reject_too_many_globals() {
+        let limits = ModuleLimits {
+            max_globals: 0,
+            ..ModuleLimits::default()
+        };
+        assert_eq!(
+            limits.check_module(&module()),
+            Err(PreprocessingError::TooManyGlobals { max: 0, actual: 1 })
+        );
+    }
+
+    #[test]
+    fn should_reject_too_large_table() {
+        let limits = ModuleLimits {
+            max_table_size: 9,
+            ..ModuleLimits::default()
+        };
+        assert_eq!(
+            limits.check_module(&module()),
+            Err(PreprocessingError::TableTooLarge { max: 9, actual: 10 })
+        );
+    }
+}
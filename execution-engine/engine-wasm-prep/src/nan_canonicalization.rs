@@ -0,0 +1,200 @@
+//! Makes floating point execution deterministic by replacing every NaN produced
+//! by a float instruction with the canonical NaN, as the bit pattern of a NaN
+//! result may otherwise differ between platforms.
+
+use parity_wasm::elements::{FuncBody, Instruction, Local, Module, Type, ValueType};
+
+use crate::PreprocessingError;
+
+const CANONICAL_NAN_F32: u32 = 0x7fc0_0000;
+const CANONICAL_NAN_F64: u64 = 0x7ff8_0000_0000_0000;
+
+/// Returns the type of the value produced by `instruction` if that value may be
+/// a NaN with a platform-dependent bit pattern.
+fn nan_producing_type(instruction: &Instruction) -> Option<ValueType> {
+    match instruction {
+        Instruction::F32Add
+        | Instruction::F32Sub
+        | Instruction::F32Mul
+        | Instruction::F32Div
+        | Instruction::F32Min
+        | Instruction::F32Max
+        | Instruction::F32Sqrt
+        | Instruction::F32Ceil
+        | Instruction::F32Floor
+        | Instruction::F32Trunc
+        | Instruction::F32Nearest
+        | Instruction::F32DemoteF64 => Some(ValueType::F32),
+        Instruction::F64Add
+        | Instruction::F64Sub
+        | Instruction::F64Mul
+        | Instruction::F64Div
+        | Instruction::F64Min
+        | Instruction::F64Max
+        | Instruction::F64Sqrt
+        | Instruction::F64Ceil
+        | Instruction::F64Floor
+        | Instruction::F64Trunc
+        | Instruction::F64Nearest
+        | Instruction::F64PromoteF32 => Some(ValueType::F64),
+        _ => None,
+    }
+}
+
+/// Appends instructions replacing the value on top of the stack with the
+/// canonical NaN if it is a NaN, using `scratch_local` to hold it.
+fn push_canonicalization(
+    instructions: &mut Vec<Instruction>,
+    value_type: ValueType,
+    scratch_local: u32,
+) {
+    let (canonical_nan, eq) = match value_type {
+        ValueType::F32 => (Instruction::F32Const(CANONICAL_NAN_F32), Instruction::F32Eq),
+        _ => (Instruction::F64Const(CANONICAL_NAN_F64), Instruction::F64Eq),
+    };
+    // select(value, canonical_nan, value == value) yields the value unless it is a NaN
+    instructions.extend_from_slice(&[
+        Instruction::TeeLocal(scratch_local),
+        canonical_nan,
+        Instruction::GetLocal(scratch_local),
+        Instruction::GetLocal(scratch_local),
+        eq,
+        Instruction::Select,
+    ]);
+}
+
+fn canonicalize_body(body: &mut FuncBody, param_count: u32) {
+    let uses_type = |value_type: ValueType| {
+        body.code()
+            .elements()
+            .iter()
+            .any(|instruction| nan_producing_type(instruction) == Some(value_type))
+    };
+    let uses_f32 = uses_type(ValueType::F32);
+    let uses_f64 = uses_type(ValueType::F64);
+    if !uses_f32 && !uses_f64 {
+        return;
+    }
+
+    // Scratch locals are appended after the params and the existing locals
+    let mut next_local = param_count + body.locals().iter().map(Local::count).sum::<u32>();
+    let mut add_scratch_local = |used: bool, value_type: ValueType| {
+        if !used {
+            return None;
+        }
+        body.locals_mut().push(Local::new(1, value_type));
+        next_local += 1;
+        Some(next_local - 1)
+    };
+    let scratch_f32 = add_scratch_local(uses_f32, ValueType::F32);
+    let scratch_f64 = add_scratch_local(uses_f64, ValueType::F64);
+
+    let instructions = body.code_mut().elements_mut();
+    let original = std::mem::replace(instructions, Vec::new());
+    for instruction in original {
+        let value_type = nan_producing_type(&instruction);
+        instructions.push(instruction);
+        let scratch_local = match value_type {
+            Some(ValueType::F32) => scratch_f32,
+            Some(ValueType::F64) => scratch_f64,
+            _ => None,
+        };
+        if let (Some(value_type), Some(scratch_local)) = (value_type, scratch_local) {
+            push_canonicalization(instructions, value_type, scratch_local);
+        }
+    }
+}
+
+/// Rewrites every function in `module` so that the float instructions in it
+/// only ever produce the canonical NaN.
+pub fn canonicalize_nans(mut module: Module) -> Result<Module, PreprocessingError> {
+    let param_counts = {
+        let types = module
+            .type_section()
+            .map_or(&[][..], |section| section.types());
+        let functions = module
+            .function_section()
+            .map_or(&[][..], |section| section.entries());
+        functions
+            .iter()
+            .map(|function| match types.get(function.type_ref() as usize) {
+                Some(Type::Function(function_type)) => Ok(function_type.params().len() as u32),
+                None => Err(PreprocessingError::InvalidFunctionType),
+            })
+            .collect::<Result<Vec<u32>, PreprocessingError>>()?
+    };
+
+    if let Some(code_section) = module.code_section_mut() {
+        let bodies = code_section.bodies_mut();
+        if bodies.len() != param_counts.len() {
+            return Err(PreprocessingError::InvalidFunctionType);
+        }
+        for (body, param_count) in bodies.iter_mut().zip(param_counts) {
+            canonicalize_body(body, param_count);
+        }
+    }
+
+    Ok(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{Instruction, Module, ValueType};
+
+    use super::{canonicalize_nans, CANONICAL_NAN_F32};
+
+    // (module (func (param f32 f32) (result f32) get_local 0 get_local 1 f32.add))
+    const F32_ADD_MODULE: [u8; 32] = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+        0x01, 0x07, 0x01, 0x60, 0x02, 0x7d, 0x7d, 0x01, 0x7d, // type section
+        0x03, 0x02, 0x01, 0x00, // function section
+        0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x92, 0x0b, // code section
+    ];
+
+    fn f32_add_module() -> Module {
+        parity_wasm::deserialize_buffer(&F32_ADD_MODULE).expect("should deserialize test module")
+    }
+
+    #[test]
+    fn should_canonicalize_nan_produced_by_float_arithmetic() {
+        let module = canonicalize_nans(f32_add_module()).expect("should canonicalize");
+        let body = &module.code_section().expect("should have code").bodies()[0];
+
+        assert_eq!(body.locals().len(), 1);
+        assert_eq!(body.locals()[0].count(), 1);
+        assert_eq!(body.locals()[0].value_type(), ValueType::F32);
+
+        let scratch_local = 2;
+        assert_eq!(
+            body.code().elements(),
+            &[
+                Instruction::GetLocal(0),
+                Instruction::GetLocal(1),
+                Instruction::F32Add,
+                Instruction::TeeLocal(scratch_local),
+                Instruction::F32Const(CANONICAL_NAN_F32),
+                Instruction::GetLocal(scratch_local),
+                Instruction::GetLocal(scratch_local),
+                Instruction::F32Eq,
+                Instruction::Select,
+                Instruction::End,
+            ][..]
+        );
+    }
+
+    #[test]
+    fn should_leave_functions_without_float_arithmetic_unchanged() {
+        let mut without_floats = f32_add_module();
+        without_floats
+            .code_section_mut()
+            .expect("should have code")
+            .bodies_mut()[0]
+            .code_mut()
+            .elements_mut()
+            .retain(|instruction| *instruction != Instruction::F32Add);
+
+        let canonicalized = canonicalize_nans(without_floats.clone()).expect("should canonicalize");
+
+        assert_eq!(canonicalized, without_floats);
+    }
+}
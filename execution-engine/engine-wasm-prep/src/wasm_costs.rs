@@ -1,11 +1,11 @@
 use contract_ffi::bytesrepr;
 use contract_ffi::bytesrepr::{FromBytes, ToBytes, U32_SIZE};
 
-const NUM_FIELDS: usize = 10;
+const NUM_FIELDS: usize = 15;
 pub const WASM_COSTS_SIZE_SERIALIZED: usize = NUM_FIELDS * U32_SIZE;
 
 // Taken (partially) from parity-ethereum
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct WasmCosts {
     /// Default opcode cost
     pub regular: u32,
@@ -30,27 +30,43 @@ pub struct WasmCosts {
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` /
     /// `opcodes_div`
     pub opcodes_div: u32,
+    /// Cost per byte of a value written to global state (by `write`, `add`, `new_uref`
+    /// or `store_function`)
+    pub storage_byte_cost: u32,
+    /// Cost of a call to the `blake2b256` host function
+    pub blake2b256_cost: u32,
+    /// Cost of a call to the `sha256` host function
+    pub sha256_cost: u32,
+    /// Cost of a call to the `ed25519_verify` host function
+    pub ed25519_verify_cost: u32,
+    /// Cost of a call to the `secp256k1_recover` host function
+    pub secp256k1_recover_cost: u32,
 }
 
-impl WasmCosts {
-    pub fn from_version(protocol_version: u64) -> Option<WasmCosts> {
-        match protocol_version {
-            1 => Some(WasmCosts {
-                regular: 1,
-                div: 16,
-                mul: 4,
-                mem: 2,
-                initial_mem: 4096,
-                grow_mem: 8192,
-                memcpy: 1,
-                max_stack_height: 64 * 1024,
-                opcodes_mul: 3,
-                opcodes_div: 8,
-            }),
-            _ => None,
+impl Default for WasmCosts {
+    /// The cost table used when a chainspec does not provide one.
+    fn default() -> Self {
+        WasmCosts {
+            regular: 1,
+            div: 16,
+            mul: 4,
+            mem: 2,
+            initial_mem: 4096,
+            grow_mem: 8192,
+            memcpy: 1,
+            max_stack_height: 64 * 1024,
+            opcodes_mul: 3,
+            opcodes_div: 8,
+            storage_byte_cost: 1,
+            blake2b256_cost: 200,
+            sha256_cost: 200,
+            ed25519_verify_cost: 2_000,
+            secp256k1_recover_cost: 2_000,
         }
     }
+}
 
+impl WasmCosts {
     pub fn free() -> WasmCosts {
         WasmCosts {
             regular: 0,
@@ -63,6 +79,11 @@ impl WasmCosts {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            storage_byte_cost: 0,
+            blake2b256_cost: 0,
+            sha256_cost: 0,
+            ed25519_verify_cost: 0,
+            secp256k1_recover_cost: 0,
         }
     }
 }
@@ -80,6 +101,11 @@ impl ToBytes for WasmCosts {
         ret.append(&mut self.max_stack_height.to_bytes()?);
         ret.append(&mut self.opcodes_mul.to_bytes()?);
         ret.append(&mut self.opcodes_div.to_bytes()?);
+        ret.append(&mut self.storage_byte_cost.to_bytes()?);
+        ret.append(&mut self.blake2b256_cost.to_bytes()?);
+        ret.append(&mut self.sha256_cost.to_bytes()?);
+        ret.append(&mut self.ed25519_verify_cost.to_bytes()?);
+        ret.append(&mut self.secp256k1_recover_cost.to_bytes()?);
         Ok(ret)
     }
 }
@@ -96,6 +122,11 @@ impl FromBytes for WasmCosts {
         let (max_stack_height, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (storage_byte_cost, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (blake2b256_cost, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (sha256_cost, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (ed25519_verify_cost, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (secp256k1_recover_cost, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let wasm_costs = WasmCosts {
             regular,
             div,
@@ -107,6 +138,11 @@ impl FromBytes for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            storage_byte_cost,
+            blake2b256_cost,
+            sha256_cost,
+            ed25519_verify_cost,
+            secp256k1_recover_cost,
         };
         Ok((wasm_costs, rem))
     }
@@ -130,6 +166,11 @@ pub mod gens {
             max_stack_height in num::u32::ANY,
             opcodes_mul in num::u32::ANY,
             opcodes_div in num::u32::ANY,
+            storage_byte_cost in num::u32::ANY,
+            blake2b256_cost in num::u32::ANY,
+            sha256_cost in num::u32::ANY,
+            ed25519_verify_cost in num::u32::ANY,
+            secp256k1_recover_cost in num::u32::ANY,
         ) -> WasmCosts {
             WasmCosts {
                 regular,
@@ -142,6 +183,11 @@ pub mod gens {
                 max_stack_height,
                 opcodes_mul,
                 opcodes_div,
+                storage_byte_cost,
+                blake2b256_cost,
+                sha256_cost,
+                ed25519_verify_cost,
+                secp256k1_recover_cost,
             }
         }
     }
@@ -157,9 +203,9 @@ mod tests {
 
     #[test]
     fn should_serialize_and_deserialize() {
-        let v1 = WasmCosts::from_version(1).unwrap();
+        let default = WasmCosts::default();
         let free = WasmCosts::free();
-        assert!(test_utils::test_serialization_roundtrip(&v1));
+        assert!(test_utils::test_serialization_roundtrip(&default));
         assert!(test_utils::test_serialization_roundtrip(&free));
     }
 
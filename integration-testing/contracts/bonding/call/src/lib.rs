@@ -45,6 +45,6 @@ pub extern "C" fn call() {
             );
         }
 
-        PurseTransferResult::TransferError => revert(1324),
+        PurseTransferResult::TransferError(_) => revert(1324),
     }
 }
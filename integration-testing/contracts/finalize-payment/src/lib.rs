@@ -9,6 +9,7 @@ use alloc::vec::Vec;
 use contract_ffi::contract_api::pointers::{ContractPointer, UPointer};
 use contract_ffi::contract_api::{self, PurseTransferResult};
 use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::{FeeHandling, RefundRatio};
 use contract_ffi::value::account::{PublicKey, PurseId};
 use contract_ffi::value::U512;
 
@@ -31,7 +32,7 @@ fn get_payment_purse(pos: &ContractPointer) -> PurseId {
 fn submit_payment(pos: &ContractPointer, amount: U512) {
     let payment_purse = get_payment_purse(pos);
     let main_purse = contract_api::main_purse();
-    if let PurseTransferResult::TransferError =
+    if let PurseTransferResult::TransferError(_) =
         contract_api::transfer_from_purse_to_purse(main_purse, payment_purse, amount)
     {
         contract_api::revert(99);
@@ -41,7 +42,14 @@ fn submit_payment(pos: &ContractPointer, amount: U512) {
 fn finalize_payment(pos: &ContractPointer, amount_spent: U512, account: PublicKey) {
     contract_api::call_contract::<_, ()>(
         pos.clone(),
-        &("finalize_payment", amount_spent, account),
+        &(
+            "finalize_payment",
+            amount_spent,
+            account,
+            FeeHandling::Accumulate,
+            None::<PublicKey>,
+            RefundRatio::default(),
+        ),
         &Vec::new(),
     )
 }
@@ -22,7 +22,7 @@ pub extern "C" fn call() {
         contract_api::call_contract(pos_pointer, &("get_payment_purse",), &Vec::new());
 
     // can deposit
-    if let PurseTransferResult::TransferError =
+    if let PurseTransferResult::TransferError(_) =
         contract_api::transfer_from_purse_to_purse(source_purse, payment_purse, payment_amount)
     {
         contract_api::revert(1);
@@ -10,8 +10,7 @@ use alloc::vec::Vec;
 use contract_ffi::contract_api::pointers::{ContractPointer, UPointer};
 use contract_ffi::contract_api::{
     call_contract, create_purse, get_arg, get_uref, main_purse, read, revert,
-    transfer_from_purse_to_account, transfer_from_purse_to_purse, PurseTransferResult,
-    TransferResult,
+    transfer_from_purse_to_account, transfer_from_purse_to_purse,
 };
 use contract_ffi::key::Key;
 use contract_ffi::uref::AccessRights;
@@ -72,9 +71,7 @@ pub extern "C" fn call() {
         let amount: U512 = get_arg(1);
         let p1 = create_purse();
 
-        if transfer_from_purse_to_purse(main_purse(), p1, amount)
-            == PurseTransferResult::TransferError
-        {
+        if !transfer_from_purse_to_purse(main_purse(), p1, amount).is_success() {
             revert(Error::PurseToPurseTransfer as u32);
         }
 
@@ -86,9 +83,7 @@ pub extern "C" fn call() {
     } else if command == TEST_SEED_NEW_ACCOUNT {
         let account: PublicKey = get_arg(1);
         let amount: U512 = get_arg(2);
-        if transfer_from_purse_to_account(main_purse(), account, amount)
-            == TransferResult::TransferError
-        {
+        if !transfer_from_purse_to_account(main_purse(), account, amount).is_success() {
             revert(Error::UnableToSeedAccount as u32);
         }
     } else if command == TEST_UNBOND {
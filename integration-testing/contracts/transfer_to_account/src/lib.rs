@@ -7,7 +7,7 @@ extern crate contract_ffi;
 
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::U512;
-use contract_ffi::contract_api::{get_arg, revert, TransferResult};
+use contract_ffi::contract_api::{get_arg, revert};
 
 #[no_mangle]
 pub extern "C" fn call() {
@@ -19,7 +19,7 @@ pub extern "C" fn call() {
 
     let result = contract_ffi::contract_api::transfer_to_account(public_key, amount);
 
-    if result == TransferResult::TransferError {
+    if !result.is_success() {
         revert(1);
     }
 }
@@ -141,6 +141,21 @@ package object types {
     )
   )
 
+  lazy val ContractPackageVersion = ObjectType(
+    "ContractPackageVersion",
+    fields[Unit, state.ContractPackage.Version](
+      Field("version", IntType, resolve = _.value.version),
+      Field("contract", KeyType, resolve = _.value.contract.get)
+    )
+  )
+
+  lazy val ContractPackage = ObjectType(
+    "ContractPackage",
+    fields[Unit, state.ContractPackage](
+      Field("versions", ListType(ContractPackageVersion), resolve = _.value.versions)
+    )
+  )
+
   lazy val AccountAssociatedKey = ObjectType(
     "AccountAssociatedKey",
     fields[Unit, state.Account.AssociatedKey](
@@ -253,6 +268,18 @@ package object types {
     )
   )
 
+  lazy val ClValue = ObjectType(
+    "CLValue",
+    fields[Unit, state.CLValue](
+      Field("clType", StringType, resolve = _.value.clType.fold("")(_.toProtoString)),
+      Field(
+        "serializedValue",
+        StringType,
+        resolve = c => Base16.encode(c.value.serializedValue.toByteArray)
+      )
+    )
+  )
+
   lazy val ValueUnion = UnionType(
     "ValueUnion",
     types = List(
@@ -263,11 +290,13 @@ package object types {
       ValueString,
       Account,
       Contract,
+      ContractPackage,
       StringList,
       NamedKey,
       RustBigInt,
       KeyType,
-      UnitType
+      UnitType,
+      ClValue
     )
   )
 
@@ -278,19 +307,21 @@ package object types {
         "value",
         ValueUnion,
         resolve = _.value.value match {
-          case state.Value.Value.Contract(value)    => value
-          case state.Value.Value.BytesValue(value)  => value
-          case state.Value.Value.BigInt(value)      => value
-          case state.Value.Value.LongValue(value)   => value
-          case value: state.Value.Value.StringValue => value
-          case state.Value.Value.Key(value)         => value
-          case state.Value.Value.Unit(value)        => value
-          case value: state.Value.Value.IntValue    => value
-          case state.Value.Value.NamedKey(value)    => value
-          case state.Value.Value.Account(value)     => value
-          case state.Value.Value.StringList(value)  => value
-          case state.Value.Value.IntList(value)     => value
-          case state.Value.Value.Empty              => ???
+          case state.Value.Value.Contract(value)        => value
+          case state.Value.Value.ContractPackage(value) => value
+          case state.Value.Value.BytesValue(value)      => value
+          case state.Value.Value.BigInt(value)          => value
+          case state.Value.Value.LongValue(value)       => value
+          case value: state.Value.Value.StringValue     => value
+          case state.Value.Value.Key(value)             => value
+          case state.Value.Value.Unit(value)            => value
+          case value: state.Value.Value.IntValue        => value
+          case state.Value.Value.NamedKey(value)        => value
+          case state.Value.Value.Account(value)         => value
+          case state.Value.Value.StringList(value)      => value
+          case state.Value.Value.IntList(value)         => value
+          case state.Value.Value.ClValue(value)         => value
+          case state.Value.Value.Empty                  => ???
         }
       )
     )
@@ -32,6 +32,11 @@ import io.casperlabs.catscontrib.MonadThrowable
       deploys: Seq[DeployItem],
       protocolVersion: ProtocolVersion
   ): F[Either[Throwable, Seq[DeployResult]]]
+  def step(
+      prestate: ByteString,
+      blocktime: Long,
+      protocolVersion: ProtocolVersion
+  ): F[Either[Throwable, Seq[TransformEntry]]]
   def commit(
       prestate: ByteString,
       effects: Seq[TransformEntry]
@@ -84,7 +89,7 @@ class GrpcExecutionEngineService[F[_]: Defer: Sync: Log: TaskLift: Metrics] priv
       result <- batches.traverse { request =>
                  sendMessage(request, _.execute) {
                    _.result match {
-                     case ExecuteResponse.Result.Success(ExecResult(deployResults)) =>
+                     case ExecuteResponse.Result.Success(ExecResult(deployResults, _, _)) =>
                        Right(deployResults) //TODO: Capture errors better than just as a string
                      case ExecuteResponse.Result.Empty =>
                        Left(new SmartContractEngineError("empty response"))
@@ -108,6 +113,24 @@ class GrpcExecutionEngineService[F[_]: Defer: Sync: Log: TaskLift: Metrics] priv
     } yield result
   }
 
+  override def step(
+      prestate: ByteString,
+      blocktime: Long,
+      protocolVersion: ProtocolVersion
+  ): F[Either[Throwable, Seq[TransformEntry]]] =
+    sendMessage(StepRequest(prestate, blocktime, Some(protocolVersion)), _.step) {
+      _.result match {
+        case StepResponse.Result.Success(result) =>
+          Right(result.getEffect.transformMap)
+        case StepResponse.Result.Empty =>
+          Left(SmartContractEngineError("empty response"))
+        case StepResponse.Result.MissingParent(RootNotFound(missing)) =>
+          Left(SmartContractEngineError(s"Missing states: ${Base16.encode(missing.toByteArray)}"))
+        case StepResponse.Result.Failure(message) =>
+          Left(SmartContractEngineError(s"Error running the PoS step: $message"))
+      }
+    }
+
   override def runGenesis(
       deploys: Seq[DeployItem],
       protocolVersion: ProtocolVersion